@@ -5,4 +5,4 @@ mod state;
 pub mod v1;
 
 pub use routes::create_router;
-pub use state::AppState;
+pub use state::{AppState, Readiness, ReadinessPhase};
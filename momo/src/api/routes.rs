@@ -1,10 +1,11 @@
+use axum::extract::DefaultBodyLimit;
 use axum::http::StatusCode;
 use axum::routing::{any, get};
 use axum::Router;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 
-use crate::mcp;
+use crate::{mcp, telemetry};
 
 use super::frontend;
 use super::v1;
@@ -21,6 +22,9 @@ pub fn create_router(state: AppState) -> Router {
         .allow_headers(Any);
 
     // legacy v3/v4/admin routers removed — only v1 remains mounted
+    // Bodies read by extractors are capped; routes that stream their body,
+    // like media uploads, opt out with their own `DefaultBodyLimit`.
+    let body_limit = DefaultBodyLimit::max(state.config.server.max_request_body_bytes);
     let v1 = v1::router::v1_router(state.clone());
     let mcp = mcp::mcp_router(state.clone());
 
@@ -31,7 +35,8 @@ pub fn create_router(state: AppState) -> Router {
         .route("/api/{*path}", any(api_not_found))
         .route("/", get(frontend::serve_root))
         .route("/{*path}", get(frontend::serve_path))
+        .layer(body_limit)
         .layer(cors)
-        .layer(TraceLayer::new_for_http())
+        .layer(TraceLayer::new_for_http().make_span_with(telemetry::http_request_span))
         .with_state(state)
 }
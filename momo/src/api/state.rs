@@ -1,13 +1,14 @@
+use std::sync::atomic::{AtomicU8, Ordering};
 use std::sync::Arc;
 
 use crate::config::Config;
-use crate::db::DatabaseBackend;
+use crate::db::{DatabaseBackend, ReplicaHealth};
 use crate::embeddings::{EmbeddingProvider, RerankerProvider};
 use crate::intelligence::MemoryExtractor;
 use crate::llm::LlmProvider;
 use crate::ocr::OcrProvider;
-use crate::processing::ProcessingPipeline;
-use crate::services::{MemoryService, SearchService};
+use crate::processing::{ProcessingPipeline, StatusEvents};
+use crate::services::{AlternateEmbeddings, MemoryService, SearchService};
 use crate::transcription::TranscriptionProvider;
 
 #[derive(Clone)]
@@ -24,6 +25,12 @@ pub struct AppState {
     pub memory: MemoryService,
     pub pipeline: ProcessingPipeline,
     pub extractor: MemoryExtractor,
+    /// Document status changes published by the pipeline.
+    pub status_events: StatusEvents,
+    /// Sync results for the read replica, when one is configured.
+    pub replica: Option<ReplicaHealth>,
+    /// Whether the process should receive traffic, for readiness probes.
+    pub readiness: Readiness,
 }
 
 impl AppState {
@@ -48,13 +55,16 @@ impl AppState {
             &config,
         );
         let memory = MemoryService::new(db.clone(), embeddings.clone());
-        let extractor = MemoryExtractor::new(llm.clone(), embeddings.clone());
+        let extractor = MemoryExtractor::new(llm.clone(), embeddings.clone())
+            .with_examples(config.extraction.examples.clone());
+        let status_events = StatusEvents::new();
         let pipeline = ProcessingPipeline::new(
             db.clone(),
             embeddings.clone(),
             ocr,
             transcription,
             llm.clone(),
+            status_events.clone(),
             &config,
         );
 
@@ -69,6 +79,72 @@ impl AppState {
             memory,
             pipeline,
             extractor,
+            status_events,
+            replica: None,
+            readiness: Readiness::default(),
         }
     }
+
+    /// Let memory search target the given alternate embedding models.
+    pub fn with_alternate_embeddings(mut self, alternate_embeddings: AlternateEmbeddings) -> Self {
+        self.search = self.search.with_alternate_embeddings(alternate_embeddings);
+        self
+    }
+
+    /// Expose read-replica sync results recorded by the sync loop, and let
+    /// search fall back to the primary while the replica is unhealthy.
+    pub fn with_replica_health(mut self, replica: ReplicaHealth) -> Self {
+        self.search = self.search.with_replica_health(replica.clone());
+        self.replica = Some(replica);
+        self
+    }
+}
+
+/// Lifecycle phase reported by the readiness probe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadinessPhase {
+    /// Still initializing; not ready for traffic.
+    Starting,
+    Ready,
+    /// Draining after a shutdown signal; not accepting new traffic.
+    ShuttingDown,
+}
+
+impl ReadinessPhase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Starting => "starting",
+            Self::Ready => "ready",
+            Self::ShuttingDown => "shutting_down",
+        }
+    }
+}
+
+/// Startup and shutdown state shared with the readiness probe. Starts out
+/// `Starting`; startup marks it ready once the database, embedding model and
+/// any in-process workers are up, and the shutdown signal marks it draining.
+#[derive(Debug, Clone, Default)]
+pub struct Readiness {
+    phase: Arc<AtomicU8>,
+}
+
+impl Readiness {
+    pub fn phase(&self) -> ReadinessPhase {
+        match self.phase.load(Ordering::Acquire) {
+            0 => ReadinessPhase::Starting,
+            1 => ReadinessPhase::Ready,
+            _ => ReadinessPhase::ShuttingDown,
+        }
+    }
+
+    /// Mark startup complete. Has no effect once shutdown has begun.
+    pub fn mark_ready(&self) {
+        let _ = self
+            .phase
+            .compare_exchange(0, 1, Ordering::AcqRel, Ordering::Acquire);
+    }
+
+    pub fn mark_shutting_down(&self) {
+        self.phase.store(2, Ordering::Release);
+    }
 }
@@ -1,6 +1,18 @@
 //! Admin DTOs for the v1 API.
 
-use serde::Serialize;
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::documents::DocumentResponse;
+use super::memories::MemoryResponse;
+use crate::api::v1::response::ApiError;
+use crate::config::MemoryConfig;
+use crate::intelligence::inference::InferenceStats;
+use crate::intelligence::relationship::RelationDetectionStats;
+use crate::models;
+use crate::services;
 
 /// Response for `POST /v1/admin/run-forgetting`.
 #[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
@@ -11,3 +23,672 @@ pub struct ForgettingRunResponse {
     /// Number of memories evaluated for forgetting.
     pub memories_evaluated: u32,
 }
+
+/// What the next forgetting run would do to a memory.
+///
+/// Wire format: `"forget"` or `"scheduleForget"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ForgettingAction {
+    /// The memory would be forgotten.
+    Forget,
+    /// The episode has decayed and would get a `forgetAfter` expiry.
+    ScheduleForget,
+}
+
+/// One memory in a `GET /v1/admin/forgetting/preview` response.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ForgettingPreviewItem {
+    pub memory_id: String,
+    /// Memory content.
+    pub memory: String,
+    pub action: ForgettingAction,
+    /// Reason that would be recorded when forgetting.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Expiry that would be set on a decayed episode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub forget_after: Option<DateTime<Utc>>,
+    /// Decayed relevance of the episode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relevance: Option<f64>,
+}
+
+impl From<services::PlannedForget> for ForgettingPreviewItem {
+    fn from(plan: services::PlannedForget) -> Self {
+        Self {
+            memory_id: plan.memory.id,
+            memory: plan.memory.memory,
+            action: ForgettingAction::Forget,
+            reason: Some(plan.reason),
+            forget_after: None,
+            relevance: None,
+        }
+    }
+}
+
+impl From<services::PlannedDecay> for ForgettingPreviewItem {
+    fn from(plan: services::PlannedDecay) -> Self {
+        Self {
+            memory_id: plan.memory.id,
+            memory: plan.memory.memory,
+            action: ForgettingAction::ScheduleForget,
+            reason: None,
+            forget_after: Some(plan.forget_after),
+            relevance: Some(plan.relevance),
+        }
+    }
+}
+
+/// Response for `GET /v1/admin/forgetting/preview`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ForgettingPreviewResponse {
+    /// Memories the next forgetting run would forget, followed by episodes
+    /// the next decay run would schedule for forgetting.
+    pub memories: Vec<ForgettingPreviewItem>,
+}
+
+/// Response for `POST /v1/admin/inference:run`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InferenceRunResponse {
+    /// Whether the inference engine ran. `false` when inference is disabled or
+    /// the LLM is unavailable.
+    pub ran: bool,
+    /// Why the run was skipped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Number of seed memories processed.
+    pub seeds_processed: u32,
+    /// Number of inferred memories created.
+    pub inferences_created: u32,
+    /// Inferences skipped because one already exists for the same sources.
+    pub duplicates_skipped: u32,
+    /// Inferences skipped for falling below the confidence threshold.
+    pub low_confidence_skipped: u32,
+    /// Number of errors while generating inferences.
+    pub errors: u32,
+    /// LLM requests made during the run.
+    pub llm_calls: u64,
+    /// LLM tokens spent during the run, as reported by the provider.
+    pub llm_tokens: u64,
+    /// True when the run stopped early because its LLM budget
+    /// (`LLM_MAX_CALLS_PER_RUN`, `LLM_MAX_TOKENS_PER_RUN`) ran out.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub budget_exhausted: bool,
+}
+
+impl InferenceRunResponse {
+    /// A run that was not started, with the reason.
+    pub fn skipped(message: impl Into<String>) -> Self {
+        Self::from_stats(false, &InferenceStats::default(), Some(message.into()))
+    }
+
+    fn from_stats(ran: bool, stats: &InferenceStats, message: Option<String>) -> Self {
+        Self {
+            ran,
+            message,
+            seeds_processed: stats.seeds_processed as u32,
+            inferences_created: stats.inferences_created as u32,
+            duplicates_skipped: stats.duplicates_skipped as u32,
+            low_confidence_skipped: stats.low_confidence_skipped as u32,
+            errors: stats.errors as u32,
+            llm_calls: stats.llm_calls,
+            llm_tokens: stats.llm_tokens,
+            budget_exhausted: stats.budget_exhausted,
+        }
+    }
+}
+
+impl From<InferenceStats> for InferenceRunResponse {
+    fn from(stats: InferenceStats) -> Self {
+        Self::from_stats(true, &stats, None)
+    }
+}
+
+/// Query parameters for `POST /v1/admin/containers/{tag}/detect-relations`.
+#[derive(Debug, Clone, Default, Deserialize, utoipa::ToSchema, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectRelationsQuery {
+    /// Maximum number of memory pairs to classify with the LLM (default 100).
+    #[serde(alias = "max_pairs")]
+    pub max_pairs: Option<u32>,
+}
+
+/// Response for `POST /v1/admin/containers/{tag}/detect-relations`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectRelationsResponse {
+    /// Whether detection ran. `false` when the LLM is unavailable.
+    pub ran: bool,
+    /// Why the run was skipped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Number of memories whose similar memories were looked up.
+    pub memories_scanned: u32,
+    /// Number of memory pairs classified by the LLM.
+    pub pairs_evaluated: u32,
+    /// Similar pairs skipped because they were already related.
+    pub pairs_skipped: u32,
+    /// Number of relations added.
+    pub relations_added: u32,
+    /// True when pairs were left unclassified because `maxPairs` was reached.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub max_pairs_reached: bool,
+}
+
+impl DetectRelationsResponse {
+    /// A run that was not started, with the reason.
+    pub fn skipped(message: impl Into<String>) -> Self {
+        Self::from_stats(
+            false,
+            &RelationDetectionStats::default(),
+            Some(message.into()),
+        )
+    }
+
+    fn from_stats(ran: bool, stats: &RelationDetectionStats, message: Option<String>) -> Self {
+        Self {
+            ran,
+            message,
+            memories_scanned: stats.memories_scanned as u32,
+            pairs_evaluated: stats.pairs_evaluated as u32,
+            pairs_skipped: stats.pairs_skipped as u32,
+            relations_added: stats.relations_added as u32,
+            max_pairs_reached: stats.max_pairs_reached,
+        }
+    }
+}
+
+impl From<RelationDetectionStats> for DetectRelationsResponse {
+    fn from(stats: RelationDetectionStats) -> Self {
+        Self::from_stats(true, &stats, None)
+    }
+}
+
+/// Query parameters for `GET /v1/admin/contradictions`.
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ListContradictionsQuery {
+    /// Filter by container tag.
+    #[serde(alias = "container_tag")]
+    pub container_tag: Option<String>,
+    /// Include already resolved contradictions (default false).
+    #[serde(alias = "include_resolved")]
+    pub include_resolved: Option<bool>,
+    /// Maximum results per page (default 20, max 100).
+    pub limit: Option<u32>,
+    /// Opaque cursor for pagination.
+    pub cursor: Option<String>,
+}
+
+/// Decision applied to a flagged contradiction.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ContradictionDecision {
+    /// Keep memory A; memory B is superseded.
+    KeepA,
+    /// Keep memory B; memory A is superseded.
+    KeepB,
+    /// Keep both memories and close the flag.
+    KeepBoth,
+}
+
+impl From<ContradictionDecision> for models::ContradictionResolution {
+    fn from(decision: ContradictionDecision) -> Self {
+        match decision {
+            ContradictionDecision::KeepA => Self::KeepA,
+            ContradictionDecision::KeepB => Self::KeepB,
+            ContradictionDecision::KeepBoth => Self::KeepBoth,
+        }
+    }
+}
+
+impl From<models::ContradictionResolution> for ContradictionDecision {
+    fn from(resolution: models::ContradictionResolution) -> Self {
+        match resolution {
+            models::ContradictionResolution::KeepA => Self::KeepA,
+            models::ContradictionResolution::KeepB => Self::KeepB,
+            models::ContradictionResolution::KeepBoth => Self::KeepBoth,
+        }
+    }
+}
+
+/// Request body for `POST /v1/admin/contradictions/resolve`.
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolveContradictionRequest {
+    /// ID of the flagged contradiction.
+    pub contradiction_id: String,
+    /// Which memory (or both) to keep.
+    pub decision: ContradictionDecision,
+}
+
+/// A pair of memories flagged as contradicting each other.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ContradictionResponse {
+    /// Contradiction ID.
+    pub contradiction_id: String,
+    /// Container tag the memories belong to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container_tag: Option<String>,
+    /// Memory that triggered the contradiction check.
+    pub memory_a: MemoryResponse,
+    /// Existing memory it conflicts with.
+    pub memory_b: MemoryResponse,
+    /// Why the pair was flagged.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<String>,
+    /// Whether the contradiction has been resolved.
+    pub resolved: bool,
+    /// Decision applied, if resolved.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decision: Option<ContradictionDecision>,
+    /// When the contradiction was flagged.
+    #[schema(value_type = String)]
+    pub created_at: DateTime<Utc>,
+    /// When the contradiction was resolved.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl From<models::ContradictionDetail> for ContradictionResponse {
+    fn from(detail: models::ContradictionDetail) -> Self {
+        let contradiction = detail.contradiction;
+        Self {
+            contradiction_id: contradiction.id,
+            container_tag: contradiction.container_tag,
+            memory_a: detail.memory.into(),
+            memory_b: detail.conflicting_memory.into(),
+            reasoning: contradiction.reasoning,
+            resolved: contradiction.status == models::ContradictionStatus::Resolved,
+            decision: contradiction.resolution.map(Into::into),
+            created_at: contradiction.created_at,
+            resolved_at: contradiction.resolved_at,
+        }
+    }
+}
+
+/// Response for `GET /v1/admin/contradictions`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListContradictionsResponse {
+    pub contradictions: Vec<ContradictionResponse>,
+}
+
+/// Response for `POST /v1/admin/contradictions/resolve`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolveContradictionResponse {
+    /// Contradiction ID.
+    pub contradiction_id: String,
+    /// Decision that was applied.
+    pub decision: ContradictionDecision,
+    /// Memories that remain active.
+    pub kept_memory_ids: Vec<String>,
+    /// Memory that was superseded or forgotten, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub superseded_memory_id: Option<String>,
+}
+
+impl From<models::ResolveContradictionResponse> for ResolveContradictionResponse {
+    fn from(resp: models::ResolveContradictionResponse) -> Self {
+        Self {
+            contradiction_id: resp.id,
+            decision: resp.resolution.into(),
+            kept_memory_ids: resp.kept_memory_ids,
+            superseded_memory_id: resp.superseded_memory_id,
+        }
+    }
+}
+
+/// Request body for `PUT /v1/admin/containers/{tag}/settings`. Replaces the
+/// container's overrides; omitted or `null` fields use the global setting.
+#[derive(Debug, Clone, Default, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateContainerSettingsRequest {
+    /// Days until an episode's relevance halves (overrides `EPISODE_DECAY_DAYS`).
+    pub episode_decay_days: Option<f64>,
+    /// Decay curve factor, between 0 and 1 exclusive (overrides `EPISODE_DECAY_FACTOR`).
+    pub episode_decay_factor: Option<f64>,
+}
+
+/// Response for `GET` and `PUT /v1/admin/containers/{tag}/settings`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerSettingsResponse {
+    /// Container tag.
+    pub container_tag: String,
+    /// Stored override of `EPISODE_DECAY_DAYS`, if any.
+    pub episode_decay_days: Option<f64>,
+    /// Stored override of `EPISODE_DECAY_FACTOR`, if any.
+    pub episode_decay_factor: Option<f64>,
+    /// Episode decay days searches in this container use.
+    pub effective_episode_decay_days: f64,
+    /// Episode decay factor searches in this container use.
+    pub effective_episode_decay_factor: f64,
+}
+
+impl ContainerSettingsResponse {
+    /// Response for `settings`, filling effective values from the global
+    /// memory config.
+    pub fn new(settings: models::ContainerSettings, defaults: &MemoryConfig) -> Self {
+        Self {
+            effective_episode_decay_days: settings
+                .episode_decay_days
+                .unwrap_or(defaults.episode_decay_days),
+            effective_episode_decay_factor: settings
+                .episode_decay_factor
+                .unwrap_or(defaults.episode_decay_factor),
+            container_tag: settings.container_tag,
+            episode_decay_days: settings.episode_decay_days,
+            episode_decay_factor: settings.episode_decay_factor,
+        }
+    }
+}
+
+/// Query parameters for `GET /v1/admin/containers/{tag}/export`.
+#[derive(Debug, Clone, Default, Deserialize, utoipa::ToSchema, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportContainerQuery {
+    /// Also export the documents tagged with the container (default false).
+    #[serde(alias = "include_documents")]
+    pub include_documents: Option<bool>,
+    /// Include each memory's embedding vector (default false).
+    #[serde(alias = "include_embeddings")]
+    pub include_embeddings: Option<bool>,
+}
+
+/// One line of a `GET /v1/admin/containers/{tag}/export` response.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ContainerExportRecord {
+    Memory(Box<ExportedMemory>),
+    Document(Box<DocumentResponse>),
+    /// Reading the container failed; earlier lines are valid.
+    Error {
+        error: ApiError,
+    },
+}
+
+/// A memory with the version chain, relations and lifecycle fields
+/// `MemoryResponse` leaves out.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedMemory {
+    #[serde(flatten)]
+    pub memory: MemoryResponse,
+    /// Previous version of this memory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_memory_id: Option<String>,
+    /// First version of this memory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root_memory_id: Option<String>,
+    /// Related memory IDs mapped to the relation type (`updates`, `extends`
+    /// or `derives`).
+    pub relations: BTreeMap<String, String>,
+    /// Number of sources this memory was extracted from.
+    pub source_count: i32,
+    /// Why the memory was forgotten.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forget_reason: Option<String>,
+    /// When an episode was last returned by a search.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub last_accessed: Option<DateTime<Utc>>,
+    /// Embedding vector, when requested with `includeEmbeddings`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
+}
+
+impl From<models::Memory> for ExportedMemory {
+    fn from(mem: models::Memory) -> Self {
+        Self {
+            parent_memory_id: mem.parent_memory_id.clone(),
+            root_memory_id: mem.root_memory_id.clone(),
+            relations: mem
+                .memory_relations
+                .iter()
+                .map(|(id, relation)| (id.clone(), relation.to_string()))
+                .collect(),
+            source_count: mem.source_count,
+            forget_reason: mem.forget_reason.clone(),
+            last_accessed: mem.last_accessed,
+            embedding: None,
+            memory: MemoryResponse::from(mem),
+        }
+    }
+}
+
+/// Request body for `POST /v1/admin/webhooks`.
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWebhookRequest {
+    /// Container whose documents trigger the webhook.
+    pub container_tag: String,
+    /// `http` or `https` URL that receives `POST` notifications.
+    pub url: String,
+    /// Secret used to sign payloads. Generated when omitted.
+    pub secret: Option<String>,
+}
+
+/// Query parameters for `GET /v1/admin/webhooks`.
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWebhooksQuery {
+    /// Filter by container tag.
+    #[serde(alias = "container_tag")]
+    pub container_tag: Option<String>,
+}
+
+/// A registered webhook.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookResponse {
+    pub id: String,
+    pub container_tag: String,
+    pub url: String,
+    /// Signing secret. Only returned when the webhook is created.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    #[schema(value_type = String)]
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookResponse {
+    /// Response for `webhook` with its secret withheld.
+    pub fn redacted(webhook: models::Webhook) -> Self {
+        Self {
+            secret: None,
+            ..Self::with_secret(webhook)
+        }
+    }
+
+    /// Response for a newly created `webhook`, including its secret.
+    pub fn with_secret(webhook: models::Webhook) -> Self {
+        Self {
+            id: webhook.id,
+            container_tag: webhook.container_tag,
+            url: webhook.url,
+            secret: Some(webhook.secret),
+            created_at: webhook.created_at,
+        }
+    }
+}
+
+/// Response for `GET /v1/admin/webhooks`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWebhooksResponse {
+    pub webhooks: Vec<WebhookResponse>,
+}
+
+/// A document that is no longer retried after failing repeatedly.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeadLetterDocumentResponse {
+    pub document_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Number of failed processing runs.
+    pub retry_count: u32,
+    /// Error from the last failed run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+    #[schema(value_type = String)]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<models::DeadLetterDocument> for DeadLetterDocumentResponse {
+    fn from(doc: models::DeadLetterDocument) -> Self {
+        Self {
+            document_id: doc.id,
+            title: doc.title,
+            retry_count: doc.retry_count,
+            error_message: doc.error_message,
+            updated_at: doc.updated_at,
+        }
+    }
+}
+
+/// Response for `GET /v1/admin/documents/dead-letter`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListDeadLetterDocumentsResponse {
+    pub documents: Vec<DeadLetterDocumentResponse>,
+}
+
+/// Response for `GET /v1/admin/processing/stats`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessingStatsResponse {
+    /// Number of documents in each processing status.
+    pub counts: BTreeMap<String, u64>,
+    /// Seconds since the oldest document not yet `done`, `failed` or
+    /// `dead_letter` was created. Absent when nothing is pending.
+    pub oldest_pending_age_secs: Option<u64>,
+    /// Durations of recent processing runs.
+    pub durations: ProcessingDurationsResponse,
+}
+
+/// Summary of recent document processing durations.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessingDurationsResponse {
+    /// Number of recent runs summarized.
+    pub sample_size: usize,
+    pub average_ms: Option<u64>,
+    pub p95_ms: Option<u64>,
+}
+
+impl From<models::ProcessingStats> for ProcessingStatsResponse {
+    fn from(stats: models::ProcessingStats) -> Self {
+        use models::ProcessingStatus;
+
+        let mut counts: BTreeMap<String, u64> = [
+            ProcessingStatus::Queued,
+            ProcessingStatus::Extracting,
+            ProcessingStatus::Chunking,
+            ProcessingStatus::Embedding,
+            ProcessingStatus::Indexing,
+            ProcessingStatus::Done,
+            ProcessingStatus::Failed,
+            ProcessingStatus::DeadLetter,
+        ]
+        .iter()
+        .map(|status| (status.to_string(), 0))
+        .collect();
+        for (status, count) in &stats.counts {
+            *counts.entry(status.to_string()).or_default() += count;
+        }
+
+        Self {
+            counts,
+            oldest_pending_age_secs: stats
+                .oldest_pending_at
+                .map(|at| (Utc::now() - at).num_seconds().max(0) as u64),
+            durations: ProcessingDurationsResponse {
+                sample_size: stats.recent_durations_ms.len(),
+                average_ms: stats.average_duration_ms(),
+                p95_ms: stats.p95_duration_ms(),
+            },
+        }
+    }
+}
+
+/// Response for `GET /v1/admin/replica/status`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplicaStatusResponse {
+    /// Whether a dedicated read replica is configured. When `false`, reads go
+    /// to the primary and the remaining fields are empty.
+    pub configured: bool,
+    pub message: String,
+    pub sync_interval_secs: Option<u64>,
+    /// When the replica last synced successfully.
+    pub last_sync_at: Option<DateTime<Utc>>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    /// Error from the latest attempt, cleared by the next successful sync.
+    pub last_error: Option<String>,
+    pub consecutive_failures: u32,
+    /// Latest document or memory write seen on the primary.
+    pub primary_latest_write_at: Option<DateTime<Utc>>,
+    /// Latest document or memory write seen on the replica.
+    pub replica_latest_write_at: Option<DateTime<Utc>>,
+    /// Estimated replication lag: how far the replica's latest write trails the
+    /// primary's. Absent when it cannot be estimated.
+    pub lag_secs: Option<u64>,
+}
+
+impl ReplicaStatusResponse {
+    pub fn not_configured() -> Self {
+        Self {
+            configured: false,
+            message: "No read replica configured; reads use the primary database".to_string(),
+            sync_interval_secs: None,
+            last_sync_at: None,
+            last_attempt_at: None,
+            last_error: None,
+            consecutive_failures: 0,
+            primary_latest_write_at: None,
+            replica_latest_write_at: None,
+            lag_secs: None,
+        }
+    }
+
+    pub fn new(
+        sync_interval_secs: u64,
+        sync: crate::db::ReplicaSyncSnapshot,
+        primary_latest_write_at: Option<DateTime<Utc>>,
+        replica_latest_write_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        let lag_secs = match (primary_latest_write_at, replica_latest_write_at) {
+            (None, _) => Some(0),
+            (Some(primary), Some(replica)) => Some((primary - replica).num_seconds().max(0) as u64),
+            (Some(_), None) => None,
+        };
+        let message = match (&sync.last_success_at, &sync.last_error) {
+            (_, Some(_)) => "Read replica sync is failing",
+            (None, None) => "Read replica has not synced yet",
+            (Some(_), None) => "Read replica is syncing",
+        };
+
+        Self {
+            configured: true,
+            message: message.to_string(),
+            sync_interval_secs: Some(sync_interval_secs),
+            last_sync_at: sync.last_success_at,
+            last_attempt_at: sync.last_attempt_at,
+            last_error: sync.last_error,
+            consecutive_failures: sync.consecutive_failures,
+            primary_latest_write_at,
+            replica_latest_write_at,
+            lag_secs,
+        }
+    }
+}
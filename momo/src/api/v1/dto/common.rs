@@ -6,7 +6,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use crate::models::{DocumentType, MemoryType, ProcessingStatus};
+use crate::models::{ChunkerType, DocumentType, MemoryType, ProcessingStatus};
 
 /// Metadata type alias for v1 API — arbitrary key-value pairs.
 ///
@@ -28,7 +28,8 @@ pub enum IngestionStatus {
     Processing,
     /// The document has been fully processed and indexed.
     Completed,
-    /// Processing failed. Check `errorMessage` for details.
+    /// Processing failed (or was given up on after repeated failures). Check
+    /// `errorMessage` for details.
     Failed,
 }
 
@@ -41,7 +42,7 @@ impl From<ProcessingStatus> for IngestionStatus {
             | ProcessingStatus::Embedding
             | ProcessingStatus::Indexing => IngestionStatus::Processing,
             ProcessingStatus::Done => IngestionStatus::Completed,
-            ProcessingStatus::Failed => IngestionStatus::Failed,
+            ProcessingStatus::Failed | ProcessingStatus::DeadLetter => IngestionStatus::Failed,
         }
     }
 }
@@ -64,6 +65,8 @@ pub enum V1DocumentType {
     Docx,
     Pptx,
     Xlsx,
+    Json,
+    Epub,
     Unknown,
 }
 
@@ -87,6 +90,8 @@ impl From<DocumentType> for V1DocumentType {
             DocumentType::Docx => V1DocumentType::Docx,
             DocumentType::Pptx => V1DocumentType::Pptx,
             DocumentType::Xlsx => V1DocumentType::Xlsx,
+            DocumentType::Json => V1DocumentType::Json,
+            DocumentType::Epub => V1DocumentType::Epub,
             DocumentType::Unknown => V1DocumentType::Unknown,
         }
     }
@@ -126,6 +131,54 @@ impl From<V1MemoryType> for MemoryType {
     }
 }
 
+/// Chunker that produced a chunk, chosen from the document type at ingestion.
+///
+/// Wire format: `"text"`, `"code"`, `"markdown"`, `"webpage"`, `"structuredData"`,
+/// `"semantic"`, or `"transcript"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum V1ChunkerType {
+    Text,
+    /// Syntax-aware splitting of source files in a supported language.
+    Code,
+    Markdown,
+    Webpage,
+    /// Row-based splitting of CSV and spreadsheet data.
+    StructuredData,
+    /// Prose split at topic shifts between sentences.
+    Semantic,
+    /// Audio or video transcripts split between timed segments.
+    Transcript,
+}
+
+impl From<ChunkerType> for V1ChunkerType {
+    fn from(ct: ChunkerType) -> Self {
+        match ct {
+            ChunkerType::Text => V1ChunkerType::Text,
+            ChunkerType::Code => V1ChunkerType::Code,
+            ChunkerType::Markdown => V1ChunkerType::Markdown,
+            ChunkerType::Webpage => V1ChunkerType::Webpage,
+            ChunkerType::StructuredData => V1ChunkerType::StructuredData,
+            ChunkerType::Semantic => V1ChunkerType::Semantic,
+            ChunkerType::Transcript => V1ChunkerType::Transcript,
+        }
+    }
+}
+
+impl From<V1ChunkerType> for ChunkerType {
+    fn from(ct: V1ChunkerType) -> Self {
+        match ct {
+            V1ChunkerType::Text => ChunkerType::Text,
+            V1ChunkerType::Code => ChunkerType::Code,
+            V1ChunkerType::Markdown => ChunkerType::Markdown,
+            V1ChunkerType::Webpage => ChunkerType::Webpage,
+            V1ChunkerType::StructuredData => ChunkerType::StructuredData,
+            V1ChunkerType::Semantic => ChunkerType::Semantic,
+            V1ChunkerType::Transcript => ChunkerType::Transcript,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -172,6 +225,10 @@ mod tests {
             IngestionStatus::from(ProcessingStatus::Failed),
             IngestionStatus::Failed
         );
+        assert_eq!(
+            IngestionStatus::from(ProcessingStatus::DeadLetter),
+            IngestionStatus::Failed
+        );
     }
 
     #[test]
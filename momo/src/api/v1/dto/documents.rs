@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 
 use super::common::{IngestionStatus, Metadata, V1DocumentType};
 use crate::models;
+use crate::processing::DocumentStatusEvent;
 
 // ---------------------------------------------------------------------------
 // Request DTOs
@@ -79,18 +80,55 @@ pub struct UpdateDocumentRequest {
     pub container_tags: Option<Vec<String>>,
 }
 
+/// Request body for `PATCH /v1/documents/{documentId}/chunks/{chunkId}`.
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateChunkRequest {
+    /// Replacement chunk text. The chunk is re-embedded in the background.
+    pub content: String,
+}
+
 /// Query parameters for `GET /v1/documents` (list endpoint).
 #[derive(Debug, Clone, Deserialize, utoipa::ToSchema, utoipa::IntoParams)]
 #[serde(rename_all = "camelCase")]
 pub struct ListDocumentsQuery {
     /// Filter by container tags.
     pub container_tags: Option<Vec<String>>,
+    /// Filter by `metadata.category`, e.g. `recipe` or `meeting_notes`.
+    pub category: Option<String>,
     /// Maximum results per page (default 20, max 100).
     pub limit: Option<u32>,
     /// Opaque cursor for pagination.
     pub cursor: Option<String>,
 }
 
+/// Optional request body for `POST /v1/documents/{documentId}/reprocess`.
+#[derive(Debug, Clone, Default, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReprocessDocumentRequest {
+    /// Override whether memories are extracted once processing finishes.
+    /// Omit to keep the document's current setting.
+    pub extract_memories: Option<bool>,
+}
+
+/// Query parameters for `DELETE /v1/documents` (bulk delete).
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteDocumentsQuery {
+    /// Delete documents carrying this container tag.
+    #[serde(alias = "container_tag")]
+    pub container_tag: Option<String>,
+}
+
+/// Optional request body for `DELETE /v1/documents`.
+#[derive(Debug, Clone, Default, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteDocumentsRequest {
+    /// Only delete documents whose metadata has all of these scalar values.
+    #[schema(value_type = Object)]
+    pub metadata: Option<Metadata>,
+}
+
 // ---------------------------------------------------------------------------
 // Response DTOs
 // ---------------------------------------------------------------------------
@@ -108,6 +146,23 @@ pub struct CreateDocumentResponse {
     pub document_id: String,
     /// The ingestion tracking ID (UUID v4).
     pub ingestion_id: String,
+    /// Existing document this one is a near-duplicate of, when the
+    /// near-duplicate check is enabled and found a match.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub near_duplicate_of: Option<String>,
+}
+
+/// Response body for `POST /v1/documents/{documentId}/reprocess` and the
+/// dead-letter requeue.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReprocessDocumentResponse {
+    /// The document being processed again.
+    pub document_id: String,
+    /// The ingestion tracking ID (same as `documentId`).
+    pub ingestion_id: String,
+    /// Job tracking the new processing run; poll `GET /v1/jobs/{jobId}`.
+    pub job_id: String,
 }
 
 /// Response body for `POST /v1/documents/batch`.
@@ -116,6 +171,8 @@ pub struct CreateDocumentResponse {
 pub struct BatchCreateDocumentResponse {
     /// Results for each document in the batch, in order.
     pub documents: Vec<CreateDocumentResponse>,
+    /// Job tracking processing of the whole batch; poll `GET /v1/jobs/{jobId}`.
+    pub job_id: String,
 }
 
 /// Full document response for `GET /v1/documents/{documentId}`.
@@ -153,6 +210,9 @@ pub struct DocumentResponse {
     /// Error message if ingestion failed.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error_message: Option<String>,
+    /// OCR or transcription confidence, for image, audio and video documents.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extraction_confidence: Option<ExtractionConfidenceResponse>,
     /// When the document was created.
     #[schema(value_type = String)]
     pub created_at: DateTime<Utc>,
@@ -161,8 +221,31 @@ pub struct DocumentResponse {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Confidence of text extracted by OCR or transcription.
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractionConfidenceResponse {
+    /// Average confidence across recognized words or segments (0.0–1.0).
+    pub average: f32,
+    /// Lowest word or segment confidence (0.0–1.0).
+    pub minimum: f32,
+    /// Whether the average fell below the server's low-confidence threshold.
+    pub low_confidence: bool,
+}
+
+impl ExtractionConfidenceResponse {
+    fn from_metadata(metadata: &Metadata) -> Option<Self> {
+        models::ExtractionConfidence::from_metadata(metadata).map(|confidence| Self {
+            average: confidence.average,
+            minimum: confidence.minimum,
+            low_confidence: models::is_low_confidence(metadata),
+        })
+    }
+}
+
 impl From<models::Document> for DocumentResponse {
     fn from(doc: models::Document) -> Self {
+        let extraction_confidence = ExtractionConfidenceResponse::from_metadata(&doc.metadata);
         Self {
             document_id: doc.id,
             custom_id: doc.custom_id,
@@ -176,6 +259,7 @@ impl From<models::Document> for DocumentResponse {
             container_tags: doc.container_tags,
             chunk_count: doc.chunk_count,
             error_message: doc.error_message,
+            extraction_confidence,
             created_at: doc.created_at,
             updated_at: doc.updated_at,
         }
@@ -243,6 +327,14 @@ impl From<models::Document> for DocumentSummaryResponse {
     }
 }
 
+/// Response body for `DELETE /v1/documents`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteDocumentsResponse {
+    /// Number of documents deleted.
+    pub deleted: u64,
+}
+
 /// Response wrapper for document list endpoints.
 ///
 /// Pagination is handled by the envelope's `meta.nextCursor` / `meta.total`.
@@ -253,6 +345,28 @@ pub struct ListDocumentsResponse {
     pub documents: Vec<DocumentSummaryResponse>,
 }
 
+/// A single chunk of a document, returned after editing it.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChunkResponse {
+    pub chunk_id: String,
+    pub document_id: String,
+    pub content: String,
+    /// Zero-based position of the chunk within its document.
+    pub position: i32,
+}
+
+impl From<models::Chunk> for ChunkResponse {
+    fn from(chunk: models::Chunk) -> Self {
+        Self {
+            chunk_id: chunk.id,
+            document_id: chunk.document_id,
+            content: chunk.content,
+            position: chunk.position,
+        }
+    }
+}
+
 /// Ingestion status for a single document (used in status polling).
 #[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -280,6 +394,29 @@ impl From<models::ProcessingDocument> for IngestionStatusResponse {
     }
 }
 
+/// Data of a `status` event on `GET /v1/documents/{documentId}/events`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentStatusEventResponse {
+    pub document_id: String,
+    /// Pipeline stage: `queued`, `extracting`, `chunking`, `embedding`,
+    /// `indexing`, `done`, `failed` or `dead_letter`.
+    #[schema(value_type = String)]
+    pub status: models::ProcessingStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+}
+
+impl From<DocumentStatusEvent> for DocumentStatusEventResponse {
+    fn from(event: DocumentStatusEvent) -> Self {
+        Self {
+            document_id: event.document_id,
+            status: event.status,
+            error_message: event.error_message,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -314,6 +451,26 @@ mod tests {
         assert_eq!(resp.doc_type, V1DocumentType::Pdf);
         assert_eq!(resp.ingestion_status, IngestionStatus::Processing);
         assert_eq!(resp.chunk_count, 5);
+        assert!(resp.extraction_confidence.is_none());
+    }
+
+    #[test]
+    fn document_response_exposes_extraction_confidence() {
+        let mut doc = models::Document::new("img_doc".to_string());
+        doc.metadata.insert(
+            models::EXTRACTION_CONFIDENCE_METADATA_KEY.to_string(),
+            serde_json::json!({"average": 0.5, "minimum": 0.25}),
+        );
+        doc.metadata.insert(
+            models::LOW_CONFIDENCE_METADATA_KEY.to_string(),
+            serde_json::json!(true),
+        );
+
+        let json = serde_json::to_value(DocumentResponse::from(doc)).expect("serialize");
+        assert_eq!(
+            json["extractionConfidence"],
+            serde_json::json!({"average": 0.5, "minimum": 0.25, "lowConfidence": true})
+        );
     }
 
     #[test]
@@ -331,6 +488,7 @@ mod tests {
             container_tags: vec![],
             chunk_count: 0,
             error_message: None,
+            extraction_confidence: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
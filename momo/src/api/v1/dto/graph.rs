@@ -49,6 +49,65 @@ impl From<models::GraphEdgeType> for GraphEdgeType {
     }
 }
 
+/// Which relations a graph traversal follows from each memory.
+///
+/// Wire format: `"outbound"`, `"inbound"`, or `"both"`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum GraphDirection {
+    /// Relations the memory has to other memories.
+    Outbound,
+    /// Relations other memories have to the memory.
+    Inbound,
+    #[default]
+    Both,
+}
+
+impl From<GraphDirection> for models::GraphDirection {
+    fn from(direction: GraphDirection) -> Self {
+        match direction {
+            GraphDirection::Outbound => models::GraphDirection::Outbound,
+            GraphDirection::Inbound => models::GraphDirection::Inbound,
+            GraphDirection::Both => models::GraphDirection::Both,
+        }
+    }
+}
+
+/// Serialization of a graph response.
+///
+/// Wire format: `"json"`, `"dot"` (GraphViz), or `"gexf"`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum GraphFormat {
+    #[default]
+    Json,
+    /// GraphViz DOT (`text/vnd.graphviz`).
+    Dot,
+    /// GEXF 1.3 (`application/gexf+xml`).
+    Gexf,
+}
+
+/// How to score node centrality in a graph response.
+///
+/// Wire format: `"degree"` or `"pageRank"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum GraphCentrality {
+    /// Share of the other nodes linked to the node, in either direction (0–1).
+    Degree,
+    /// PageRank over the directed links; scores sum to 1.
+    PageRank,
+}
+
+impl From<GraphCentrality> for models::GraphCentrality {
+    fn from(centrality: GraphCentrality) -> Self {
+        match centrality {
+            GraphCentrality::Degree => models::GraphCentrality::Degree,
+            GraphCentrality::PageRank => models::GraphCentrality::PageRank,
+        }
+    }
+}
+
 /// A node in the knowledge graph response.
 #[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -61,6 +120,9 @@ pub struct GraphNodeResponse {
     /// Additional visualization metadata.
     #[schema(value_type = Object)]
     pub metadata: Metadata,
+    /// Centrality score, when `centrality` was requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub centrality: Option<f64>,
 }
 
 impl From<models::GraphNode> for GraphNodeResponse {
@@ -69,6 +131,7 @@ impl From<models::GraphNode> for GraphNodeResponse {
             id: node.id,
             node_type: node.node_type.into(),
             metadata: node.metadata,
+            centrality: node.centrality,
         }
     }
 }
@@ -134,6 +197,7 @@ mod tests {
                 id: "n1".to_string(),
                 node_type: GraphNodeType::Memory,
                 metadata: std::collections::HashMap::new(),
+                centrality: None,
             }],
             links: vec![GraphEdgeResponse {
                 source: "n1".to_string(),
@@ -0,0 +1,115 @@
+//! Job DTOs for the v1 API.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::models::{self, JobKind, JobStatus};
+
+/// Operation a job tracks.
+///
+/// Wire format: `"batchImport"`, `"chunkReembed"`, `"reprocess"` or
+/// `"containerExport"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum JobType {
+    /// Processing of documents created by `POST /v1/documents:batch`.
+    BatchImport,
+    /// Re-embedding started by `POST /v1/admin/chunks:reembed`.
+    ChunkReembed,
+    /// Processing started by `POST /v1/documents/{documentId}/reprocess` or
+    /// a dead-letter requeue.
+    Reprocess,
+    /// Export streamed by `GET /v1/admin/containers/{tag}/export`.
+    ContainerExport,
+}
+
+impl From<JobKind> for JobType {
+    fn from(kind: JobKind) -> Self {
+        match kind {
+            JobKind::BatchImport => JobType::BatchImport,
+            JobKind::ChunkReembed => JobType::ChunkReembed,
+            JobKind::Reprocess => JobType::Reprocess,
+            JobKind::ContainerExport => JobType::ContainerExport,
+        }
+    }
+}
+
+/// Lifecycle state of a job.
+///
+/// Wire format: `"queued"`, `"running"`, `"done"`, or `"failed"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum JobState {
+    Queued,
+    Running,
+    Done,
+    /// The job stopped early. Check `error` for details.
+    Failed,
+}
+
+impl From<JobStatus> for JobState {
+    fn from(status: JobStatus) -> Self {
+        match status {
+            JobStatus::Queued => JobState::Queued,
+            JobStatus::Running => JobState::Running,
+            JobStatus::Done => JobState::Done,
+            JobStatus::Failed => JobState::Failed,
+        }
+    }
+}
+
+/// Response for `GET /v1/jobs/{jobId}`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct JobResponse {
+    /// Job ID.
+    pub job_id: String,
+    #[serde(rename = "type")]
+    pub job_type: JobType,
+    pub status: JobState,
+    /// Units of work completed so far (documents for batch imports and
+    /// reprocessing, chunks for re-embedding, records for exports).
+    pub processed: u64,
+    /// Total units of work, when known up front.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+    /// Fraction of the work completed (0 to 1), when the total is known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<f64>,
+    /// Operation-specific summary, present once the job is done.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub result: Option<serde_json::Value>,
+    /// Why the job failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[schema(value_type = String)]
+    pub created_at: DateTime<Utc>,
+    #[schema(value_type = String)]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<models::Job> for JobResponse {
+    fn from(job: models::Job) -> Self {
+        Self {
+            progress: job.progress(),
+            job_id: job.id,
+            job_type: job.kind.into(),
+            status: job.status.into(),
+            processed: job.processed,
+            total: job.total,
+            result: job.result,
+            error: job.error,
+            created_at: job.created_at,
+            updated_at: job.updated_at,
+        }
+    }
+}
+
+/// Response for endpoints that start a job, e.g. `POST /v1/admin/chunks:reembed`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct JobAcceptedResponse {
+    /// Poll `GET /v1/jobs/{jobId}` for progress.
+    pub job_id: String,
+}
@@ -16,10 +16,20 @@ use crate::models;
 pub struct CreateMemoryRequest {
     /// The memory content text.
     pub content: String,
-    /// Container tag for multi-tenant isolation.
-    pub container_tag: String,
+    /// Container tag for multi-tenant isolation. Defaults to the API key's
+    /// configured container tag when omitted.
+    pub container_tag: Option<String>,
     /// Memory type classification.
     pub memory_type: Option<V1MemoryType>,
+    /// Pin this memory so it's never forgotten (default false).
+    pub is_static: Option<bool>,
+    /// Confidence score (0.0–1.0).
+    pub confidence: Option<f64>,
+    /// When the memory expires and is forgotten; must be in the future.
+    #[schema(value_type = Option<String>)]
+    pub forget_after: Option<DateTime<Utc>>,
+    /// Days until the memory expires, as an alternative to `forgetAfter`.
+    pub ttl_days: Option<f64>,
     /// Arbitrary key-value metadata.
     #[schema(value_type = Object)]
     pub metadata: Option<Metadata>,
@@ -36,6 +46,9 @@ pub struct UpdateMemoryRequest {
     pub metadata: Option<Metadata>,
     /// Pin this memory so it's never forgotten.
     pub is_static: Option<bool>,
+    /// Expected current version of the memory. When set, the update is rejected
+    /// with `409 conflict` if the memory has been superseded in the meantime.
+    pub version: Option<i32>,
 }
 
 /// Request body for `DELETE /v1/memories/{memoryId}`.
@@ -52,8 +65,9 @@ pub struct ForgetMemoryRequest {
 pub struct ContentForgetRequest {
     /// The memory content to search for and forget.
     pub content: String,
-    /// Container tag to scope the search.
-    pub container_tag: String,
+    /// Container tag to scope the search. Defaults to the API key's
+    /// configured container tag when omitted.
+    pub container_tag: Option<String>,
     /// Reason for forgetting (stored for audit trail).
     pub reason: Option<String>,
 }
@@ -100,6 +114,10 @@ pub struct MemoryResponse {
     /// Confidence score (0.0–1.0).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub confidence: Option<f64>,
+    /// When the memory expires and is forgotten.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub forget_after: Option<DateTime<Utc>>,
     /// Attached metadata.
     #[schema(value_type = Object)]
     pub metadata: Metadata,
@@ -124,6 +142,7 @@ impl From<models::Memory> for MemoryResponse {
             is_forgotten: mem.is_forgotten,
             is_static: mem.is_static,
             confidence: mem.confidence,
+            forget_after: mem.forget_after,
             metadata: mem.metadata,
             created_at: mem.created_at,
             updated_at: mem.updated_at,
@@ -222,6 +241,7 @@ mod tests {
             is_forgotten: false,
             is_static: false,
             confidence: Some(0.85),
+            forget_after: None,
             metadata: std::collections::HashMap::new(),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
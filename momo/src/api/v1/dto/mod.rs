@@ -9,6 +9,7 @@ pub mod common;
 pub mod conversation;
 pub mod documents;
 pub mod graph;
+pub mod jobs;
 pub mod memories;
 pub mod profile;
 pub mod search;
@@ -17,5 +18,6 @@ pub mod search;
 pub use admin::*;
 pub use documents::*;
 pub use graph::*;
+pub use jobs::*;
 pub use memories::*;
 pub use search::*;
@@ -21,6 +21,48 @@ pub struct ComputeProfileRequest {
     pub limit: Option<u32>,
     /// Generate a narrative summary.
     pub generate_narrative: Option<bool>,
+    /// Leave out facts with a lower confidence (0.0–1.0).
+    pub min_confidence: Option<f64>,
+    /// Order of the facts (default recency).
+    pub sort: Option<ProfileFactSort>,
+}
+
+/// Order of the facts in a profile.
+///
+/// Wire format: `"recency"` or `"confidence"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ProfileFactSort {
+    /// Newest first.
+    Recency,
+    /// Most confident first. Facts without a confidence come last.
+    Confidence,
+}
+
+impl From<ProfileFactSort> for models::ProfileFactSort {
+    fn from(sort: ProfileFactSort) -> Self {
+        match sort {
+            ProfileFactSort::Recency => models::ProfileFactSort::Recency,
+            ProfileFactSort::Confidence => models::ProfileFactSort::Confidence,
+        }
+    }
+}
+
+/// Query parameters for `GET /v1/profile/{containerTag}`.
+#[derive(Debug, Clone, Default, Deserialize, utoipa::ToSchema, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct GetProfileQuery {
+    /// Regenerate the narrative from current memories instead of returning
+    /// the cached one (default false).
+    pub refresh: Option<bool>,
+    /// Include dynamic (episode-based) facts (default true).
+    pub include_dynamic: Option<bool>,
+    /// Maximum number of facts to include (default 50).
+    pub limit: Option<u32>,
+    /// Leave out facts with a lower confidence (0.0–1.0).
+    pub min_confidence: Option<f64>,
+    /// Order of the facts (default recency).
+    pub sort: Option<ProfileFactSort>,
 }
 
 /// Profile response for `POST /v1/profile`.
@@ -1,14 +1,17 @@
 //! Search request/response DTOs for the v1 API.
 
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use super::common::{Metadata, V1DocumentType};
+use super::common::{Metadata, V1ChunkerType, V1DocumentType};
+use crate::api::v1::response::ApiError;
 use crate::models;
 
 /// Search scope determines which indices to query.
 ///
-/// Wire format: `"documents"`, `"memories"`, or `"hybrid"`.
+/// Wire format: `"documents"`, `"memories"`, `"hybrid"`, or `"keyword"`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema, Default)]
 #[serde(rename_all = "camelCase")]
 pub enum SearchScope {
@@ -16,9 +19,13 @@ pub enum SearchScope {
     Documents,
     /// Search memories only.
     Memories,
-    /// Search both documents and memories (default).
+    /// Search both documents and memories, fusing vector and keyword
+    /// matches (default).
     #[default]
     Hybrid,
+    /// BM25 keyword search over documents and memories. Finds exact terms
+    /// such as error codes or SKUs; the query is not embedded.
+    Keyword,
 }
 
 impl From<SearchScope> for models::SearchMode {
@@ -27,6 +34,7 @@ impl From<SearchScope> for models::SearchMode {
             SearchScope::Documents => models::SearchMode::Documents,
             SearchScope::Memories => models::SearchMode::Memories,
             SearchScope::Hybrid => models::SearchMode::Hybrid,
+            SearchScope::Keyword => models::SearchMode::Keyword,
         }
     }
 }
@@ -37,6 +45,108 @@ impl From<models::SearchMode> for SearchScope {
             models::SearchMode::Documents => SearchScope::Documents,
             models::SearchMode::Memories => SearchScope::Memories,
             models::SearchMode::Hybrid => SearchScope::Hybrid,
+            models::SearchMode::Keyword => SearchScope::Keyword,
+        }
+    }
+}
+
+/// Optional grouping applied to search results before they are returned.
+///
+/// Wire format: `"document"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum SearchGroupBy {
+    /// Nest chunk hits under their parent document and memory hits under a
+    /// single `memories` group.
+    Document,
+}
+
+/// Transform spreading returned scores across 0–1 for client-side thresholds.
+/// Ranking is unchanged.
+///
+/// Wire format: `"minMax"` or `"sigmoid"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ScoreCalibration {
+    /// Rescale so the best result scores 1.0 and the worst 0.0. Relative to
+    /// the result set, so scores are not comparable across queries.
+    MinMax,
+    /// Logistic curve around the server's midpoint
+    /// (`SEARCH_SCORE_SIGMOID_MIDPOINT`). Comparable across queries.
+    Sigmoid,
+}
+
+impl From<ScoreCalibration> for models::ScoreCalibration {
+    fn from(calibration: ScoreCalibration) -> Self {
+        match calibration {
+            ScoreCalibration::MinMax => models::ScoreCalibration::MinMax,
+            ScoreCalibration::Sigmoid => models::ScoreCalibration::Sigmoid,
+        }
+    }
+}
+
+/// Optional search stage that was dropped to stay within the response time budget.
+///
+/// Wire format: `"query_rewrite"`, `"rerank"`, `"threshold_relaxation"`, or
+/// `"highlight"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SkippedStage {
+    /// LLM query rewriting did not run or did not finish in time.
+    QueryRewrite,
+    /// Cross-encoder reranking did not run or did not finish in time.
+    Rerank,
+    /// Threshold relaxation for `minResults` stopped early.
+    ThresholdRelaxation,
+    /// Snippets for `highlight` were not computed in time.
+    Highlight,
+}
+
+impl From<models::SearchStage> for SkippedStage {
+    fn from(stage: models::SearchStage) -> Self {
+        match stage {
+            models::SearchStage::QueryRewrite => SkippedStage::QueryRewrite,
+            models::SearchStage::Rerank => SkippedStage::Rerank,
+            models::SearchStage::ThresholdRelaxation => SkippedStage::ThresholdRelaxation,
+            models::SearchStage::Highlight => SkippedStage::Highlight,
+        }
+    }
+}
+
+/// Retrieval a search switched to because its vector search found nothing.
+///
+/// Wire format: `"lexical"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchFallback {
+    /// Keyword (full-text) search over the same query.
+    Lexical,
+}
+
+impl From<models::SearchFallback> for SearchFallback {
+    fn from(fallback: models::SearchFallback) -> Self {
+        match fallback {
+            models::SearchFallback::Lexical => SearchFallback::Lexical,
+        }
+    }
+}
+
+/// Score multipliers applied to memories and document chunks before they are
+/// ranked together in `hybrid` and `keyword` scopes.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HybridWeights {
+    /// Multiplier for memory scores.
+    pub memory: f32,
+    /// Multiplier for document chunk scores.
+    pub document: f32,
+}
+
+impl From<models::HybridWeights> for HybridWeights {
+    fn from(weights: models::HybridWeights) -> Self {
+        Self {
+            memory: weights.memory,
+            document: weights.document,
         }
     }
 }
@@ -51,6 +161,10 @@ pub struct SearchIncludeFlags {
     /// Include individual chunks in document results (default: false).
     #[serde(default)]
     pub chunks: bool,
+    /// Include each memory's score before and after episode decay as
+    /// `decayDebug` (default: false). `memories` scope only.
+    #[serde(default)]
+    pub decay_debug: bool,
 }
 
 fn default_true() -> bool {
@@ -62,6 +176,7 @@ impl Default for SearchIncludeFlags {
         Self {
             documents: true,
             chunks: false,
+            decay_debug: false,
         }
     }
 }
@@ -77,6 +192,9 @@ pub struct SearchRequest {
     pub scope: SearchScope,
     /// Filter by container tags.
     pub container_tags: Option<Vec<String>>,
+    /// Score multipliers keyed by container tag, applied before final ranking.
+    /// Tags without an entry keep a weight of 1.0.
+    pub container_tag_weights: Option<HashMap<String, f32>>,
     /// Minimum similarity threshold (0.0–1.0).
     pub threshold: Option<f32>,
     /// Maximum number of results to return.
@@ -86,6 +204,179 @@ pub struct SearchRequest {
     pub include: SearchIncludeFlags,
     /// Enable cross-encoder reranking.
     pub rerank: Option<bool>,
+    /// Group results in the response. Grouping does not change ranking.
+    pub group_by: Option<SearchGroupBy>,
+    /// Only return memories tagged with this language (ISO 639-3 code, e.g.
+    /// `"fra"`). Document results are not filtered.
+    pub language: Option<String>,
+    /// Attach this many neighboring chunks (0–5) before and after each returned
+    /// chunk. Applies to `documents` scope; does not change ranking.
+    pub context_chunks: Option<u32>,
+    /// Search memories using this alternate embedding model's stored embeddings
+    /// instead of the primary model's. Applies to `memories` scope.
+    pub embedding_model: Option<String>,
+    /// Exclude documents whose OCR or transcription was flagged low-confidence.
+    /// Applies to `documents` and `hybrid` scopes.
+    pub exclude_low_confidence: Option<bool>,
+    /// Only return documents whose `metadata.category` is one of these (see
+    /// `DOCUMENT_CATEGORIZATION_ENABLED`). Applies to `documents` and `hybrid`
+    /// scopes; memories are not filtered.
+    pub categories: Option<Vec<String>>,
+    /// Rank episode memories by raw similarity instead of decaying older
+    /// episodes. Applies to `memories` and `hybrid` scopes.
+    pub disable_decay: Option<bool>,
+    /// Expand the query with synonyms from the server's synonym dictionary
+    /// (`SEARCH_SYNONYMS_PATH`) before embedding it. No LLM is involved.
+    pub expand_synonyms: Option<bool>,
+    /// Only match chunks produced by one of these chunkers, e.g. `["code"]`.
+    /// Applies to `documents` and `hybrid` scopes; chunks ingested before the
+    /// chunker was recorded never match.
+    pub chunker_types: Option<Vec<V1ChunkerType>>,
+    /// Only match code chunks in one of these languages, e.g. `["rust", "python"]`.
+    /// Applies to `documents` and `hybrid` scopes; prose chunks never match.
+    pub code_languages: Option<Vec<String>>,
+    /// When fewer results than this are found, retry with progressively lower
+    /// thresholds down to the server's floor (`SEARCH_MIN_RESULTS_THRESHOLD_FLOOR`).
+    /// Capped at `limit`; the lowered threshold is reported as `relaxedThreshold`.
+    pub min_results: Option<u32>,
+    /// Calibrate each result's top-level `score`/`similarity` after ranking,
+    /// returning the original as `rawScore`/`rawSimilarity`. Chunk scores are
+    /// left uncalibrated, and `threshold` still applies to raw scores.
+    pub score_calibration: Option<ScoreCalibration>,
+    /// `nextCursor` from the previous response, to fetch the following page.
+    /// Cannot be combined with `offset`.
+    pub cursor: Option<String>,
+    /// Number of ranked results to skip. Prefer `cursor`, which stays stable
+    /// when results tie on score.
+    pub offset: Option<u32>,
+    /// Multiplier for memory scores (default 1.0). Applies to `hybrid` and
+    /// `keyword` scopes; must be finite and non-negative.
+    pub memory_weight: Option<f32>,
+    /// Multiplier for document chunk scores (default 1.0). Applies to
+    /// `hybrid` and `keyword` scopes; must be finite and non-negative.
+    pub document_weight: Option<f32>,
+    /// Rerank document chunks with maximal marginal relevance so that
+    /// near-duplicate chunks don't crowd out other results. `documents` scope
+    /// only.
+    pub diversify: Option<bool>,
+    /// Trade-off between relevance (1.0) and diversity (0.0) when
+    /// `diversify` is set. Defaults to 0.5.
+    pub diversity_lambda: Option<f32>,
+    /// Return a `snippet` per chunk around the passage that best matches the
+    /// query, with query terms wrapped in `<mark>` (`documents` scope), and a
+    /// `highlight` FTS5 excerpt per keyword-matched chunk (`hybrid` and
+    /// `keyword` scopes, and `documents` results from the lexical fallback).
+    pub highlight: Option<bool>,
+    /// Blend recency of each memory's `updatedAt` into its score with this
+    /// weight (0–1): `score * (1 - w) + recency * w`. Recency halves every
+    /// `SEARCH_FRESHNESS_HALF_LIFE_DAYS`. `memories` scope only.
+    pub freshness_weight: Option<f32>,
+    /// Return each memory version chain once, as its newest version. Applies
+    /// to `memories`, `hybrid` and `keyword` scopes; defaults to the server's
+    /// `SEARCH_DEDUPE_VERSION_CHAINS`.
+    pub dedupe_version_chains: Option<bool>,
+}
+
+/// One memory search of a `POST /v1/search/batch` request.
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchSearchQuery {
+    /// The search query string.
+    pub q: String,
+    /// Only return memories in this container.
+    pub container_tag: Option<String>,
+    /// Minimum similarity threshold (0.0–1.0).
+    pub threshold: Option<f32>,
+    /// Maximum number of results to return.
+    pub limit: Option<u32>,
+    /// Enable cross-encoder reranking.
+    pub rerank: Option<bool>,
+    /// Only return memories tagged with this language (ISO 639-3 code).
+    pub language: Option<String>,
+    /// Search this alternate embedding model's stored embeddings instead of
+    /// the primary model's.
+    pub embedding_model: Option<String>,
+    /// Rank episode memories by raw similarity instead of decaying older
+    /// episodes.
+    pub disable_decay: Option<bool>,
+    /// Expand the query with synonyms before embedding it.
+    pub expand_synonyms: Option<bool>,
+}
+
+/// Request body for `POST /v1/search/batch`.
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchSearchRequest {
+    /// Memory searches to run, at most 20.
+    pub queries: Vec<BatchSearchQuery>,
+}
+
+/// Response for `POST /v1/search/batch`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchSearchResponse {
+    /// One response per query, in request order. Each `timingMs` covers that
+    /// query's search only.
+    pub results: Vec<SearchResponse>,
+}
+
+/// Request body for `POST /v1/search/stream`.
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamSearchRequest {
+    /// The search query string.
+    pub q: String,
+    /// Only return memories in this container.
+    pub container_tag: Option<String>,
+    /// Minimum similarity threshold (0.0–1.0).
+    pub threshold: Option<f32>,
+    /// Maximum number of results to return (default 10, max 1000).
+    pub limit: Option<u32>,
+    /// Only return memories tagged with this language (ISO 639-3 code).
+    pub language: Option<String>,
+    /// Search this alternate embedding model's stored embeddings instead of
+    /// the primary model's.
+    pub embedding_model: Option<String>,
+    /// Rank episode memories by raw similarity instead of decaying older
+    /// episodes.
+    pub disable_decay: Option<bool>,
+    /// Expand the query with synonyms before embedding it.
+    pub expand_synonyms: Option<bool>,
+    /// Score multipliers keyed by container tag.
+    pub container_tag_weights: Option<HashMap<String, f32>>,
+    /// Blend recency of each memory's `updatedAt` into its score (0–1).
+    pub freshness_weight: Option<f32>,
+}
+
+/// Last line of a `POST /v1/search/stream` response.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum SearchStreamEnd {
+    /// All results were written.
+    Summary {
+        /// Number of results written.
+        total: u32,
+        /// Search time in milliseconds.
+        timing_ms: u64,
+    },
+    /// The search failed after results were written; earlier lines are valid.
+    Error { error: ApiError },
+}
+
+/// Query parameters for `GET /v1/search/similar/{memoryId}`.
+#[derive(Debug, Clone, Default, Deserialize, utoipa::ToSchema, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarMemoriesQuery {
+    /// Maximum results to return (default 10, max 100).
+    pub limit: Option<u32>,
+    /// Minimum similarity score (default 0.6).
+    pub threshold: Option<f32>,
+    /// Only return memories in this container.
+    pub container_tag: Option<String>,
 }
 
 /// Unified search response for `POST /v1/search`.
@@ -93,11 +384,37 @@ pub struct SearchRequest {
 #[serde(rename_all = "camelCase")]
 pub struct SearchResponse {
     /// Combined search results (documents and/or memories based on scope).
+    /// Empty when `groupBy` is set; hits are returned in `groups` instead.
     pub results: Vec<SearchResultItem>,
+    /// Grouped results, present only when `groupBy` was requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<SearchResultGroup>>,
     /// Total number of matching results.
     pub total: u32,
     /// Query execution time in milliseconds.
     pub timing_ms: u64,
+    /// True when the server's response time budget forced optional stages to be skipped.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub degraded: bool,
+    /// Optional stages skipped because of the response time budget.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped_stages: Vec<SkippedStage>,
+    /// Threshold the results were found with, when it was lowered to reach
+    /// `minResults`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relaxed_threshold: Option<f32>,
+    /// Opaque token to pass as `cursor` for the next page. Absent on the last
+    /// page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Weights memories and document chunks were ranked with. Present for
+    /// `hybrid` and `keyword` scopes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hybrid_weights: Option<HybridWeights>,
+    /// Set to `"lexical"` when the vector search found nothing and the
+    /// results come from keyword search instead (`SEARCH_LEXICAL_FALLBACK`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<SearchFallback>,
 }
 
 /// A single item in the search results — can be a document or memory hit.
@@ -113,6 +430,41 @@ pub enum SearchResultItem {
     Memory(MemorySearchResult),
 }
 
+/// Kind of a [`SearchResultGroup`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum SearchGroupType {
+    /// Hits belonging to one document.
+    Document,
+    /// Synthetic group holding all memory hits.
+    Memories,
+}
+
+/// A group of search hits, returned when `groupBy` is set.
+///
+/// Groups are ordered by the rank of their best hit.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResultGroup {
+    #[serde(rename = "type")]
+    pub group_type: SearchGroupType,
+    /// Parent document ID (document groups only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub document_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc_type: Option<V1DocumentType>,
+    /// Highest score among the group's hits (rerank score when available).
+    pub max_score: f32,
+    /// Matched chunks of the document, in rank order.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub chunks: Vec<ChunkResult>,
+    /// Matched memories, in rank order.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub memories: Vec<MemorySearchResult>,
+}
+
 /// Document match within search results.
 #[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -123,8 +475,11 @@ pub struct DocumentSearchResult {
     pub title: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub doc_type: Option<V1DocumentType>,
-    /// Vector similarity score.
+    /// Vector similarity score, calibrated when `scoreCalibration` is set.
     pub score: f32,
+    /// Uncalibrated score, present when `scoreCalibration` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_score: Option<f32>,
     /// Reranking score (if reranking was applied).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rerank_score: Option<f32>,
@@ -151,6 +506,25 @@ pub struct ChunkResult {
     pub score: f32,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rerank_score: Option<f32>,
+    /// Chunker that produced the chunk. Absent for chunks ingested before it
+    /// was recorded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunker: Option<V1ChunkerType>,
+    /// Preceding chunks of the same document, when `contextChunks` is set.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub context_before: Vec<String>,
+    /// Following chunks of the same document, when `contextChunks` is set.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub context_after: Vec<String>,
+    /// Best-matching excerpt with query terms in `<mark>`, when `highlight`
+    /// is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+    /// FTS5 excerpt of a keyword-matched chunk with the matched terms wrapped
+    /// in the server's highlight markers (`<mark>` by default), when
+    /// `highlight` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<String>,
 }
 
 impl From<models::ChunkSearchResult> for ChunkResult {
@@ -159,6 +533,11 @@ impl From<models::ChunkSearchResult> for ChunkResult {
             content: chunk.content,
             score: chunk.score,
             rerank_score: chunk.rerank_score,
+            chunker: chunk.chunker.map(Into::into),
+            context_before: chunk.context_before,
+            context_after: chunk.context_after,
+            snippet: chunk.snippet,
+            highlight: chunk.highlight,
         }
     }
 }
@@ -170,6 +549,7 @@ impl From<models::DocumentSearchResult> for DocumentSearchResult {
             title: doc.title,
             doc_type: doc.doc_type.map(Into::into),
             score: doc.score,
+            raw_score: doc.raw_score,
             rerank_score: doc.rerank_score,
             chunks: doc.chunks.into_iter().map(Into::into).collect(),
             summary: doc.summary,
@@ -189,8 +569,11 @@ pub struct MemorySearchResult {
     pub memory_id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<String>,
-    /// Vector similarity score.
+    /// Vector similarity score, calibrated when `scoreCalibration` is set.
     pub similarity: f32,
+    /// Uncalibrated similarity, present when `scoreCalibration` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_similarity: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rerank_score: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -199,6 +582,33 @@ pub struct MemorySearchResult {
     pub metadata: Metadata,
     #[schema(value_type = String)]
     pub updated_at: DateTime<Utc>,
+    /// Score before and after episode decay, when `include.decayDebug` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decay_debug: Option<DecayDebug>,
+}
+
+/// How episode decay changed a memory's score.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DecayDebug {
+    /// Similarity returned by the database.
+    pub db_similarity: f32,
+    /// Multiplier applied for episode decay; 1.0 for facts, preferences, and
+    /// when `disableDecay` is set.
+    pub decay_factor: f32,
+    /// `dbSimilarity * decayFactor`. `similarity` is this score after
+    /// importance, freshness, container tag weights, and calibration.
+    pub decayed_similarity: f32,
+}
+
+impl From<models::MemoryDecayDebug> for DecayDebug {
+    fn from(debug: models::MemoryDecayDebug) -> Self {
+        Self {
+            db_similarity: debug.db_similarity,
+            decay_factor: debug.decay_factor,
+            decayed_similarity: debug.decayed_similarity,
+        }
+    }
 }
 
 impl From<models::MemorySearchResult> for MemorySearchResult {
@@ -207,10 +617,12 @@ impl From<models::MemorySearchResult> for MemorySearchResult {
             memory_id: mem.id,
             content: mem.memory,
             similarity: mem.similarity,
+            raw_similarity: mem.raw_similarity,
             rerank_score: mem.rerank_score,
             version: mem.version,
             metadata: mem.metadata,
             updated_at: mem.updated_at,
+            decay_debug: mem.decay_debug.map(Into::into),
         }
     }
 }
@@ -226,8 +638,14 @@ pub struct HybridSearchResultResponse {
     pub chunk: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub document_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunker: Option<V1ChunkerType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<String>,
     pub similarity: f32,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_similarity: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub rerank_score: Option<f32>,
     #[schema(value_type = Object)]
     pub metadata: Metadata,
@@ -242,7 +660,10 @@ impl From<models::HybridSearchResult> for HybridSearchResultResponse {
             memory: result.memory,
             chunk: result.chunk,
             document_id: result.document_id,
+            chunker: result.chunker.map(Into::into),
+            highlight: result.highlight,
             similarity: result.similarity,
+            raw_similarity: result.raw_similarity,
             rerank_score: result.rerank_score,
             metadata: result.metadata,
             updated_at: result.updated_at,
@@ -286,10 +707,52 @@ mod tests {
             version: Some(1),
             metadata: std::collections::HashMap::new(),
             updated_at: chrono::Utc::now(),
+            raw_similarity: None,
+            decay_debug: None,
         });
 
         let json = serde_json::to_value(&item).expect("serialize");
         assert_eq!(json.get("type").expect("type field"), "memory");
         assert!(json.get("memoryId").is_some());
     }
+
+    #[test]
+    fn search_response_omits_degraded_fields_when_complete() {
+        let response = SearchResponse {
+            results: vec![],
+            groups: None,
+            total: 0,
+            timing_ms: 3,
+            degraded: false,
+            skipped_stages: vec![],
+            relaxed_threshold: None,
+            next_cursor: None,
+            hybrid_weights: None,
+            fallback: None,
+        };
+
+        let json = serde_json::to_value(&response).expect("serialize");
+        assert!(json.get("degraded").is_none());
+        assert!(json.get("skippedStages").is_none());
+    }
+
+    #[test]
+    fn search_response_reports_skipped_stages() {
+        let response = SearchResponse {
+            results: vec![],
+            groups: None,
+            total: 0,
+            timing_ms: 250,
+            degraded: true,
+            skipped_stages: vec![models::SearchStage::Rerank.into()],
+            relaxed_threshold: None,
+            next_cursor: None,
+            hybrid_weights: None,
+            fallback: None,
+        };
+
+        let json = serde_json::to_value(&response).expect("serialize");
+        assert_eq!(json["degraded"], true);
+        assert_eq!(json["skippedStages"], serde_json::json!(["rerank"]));
+    }
 }
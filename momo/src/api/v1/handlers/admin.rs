@@ -1,12 +1,32 @@
 //! v1 Admin handlers.
 
-use axum::extract::State;
-use chrono::Utc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
 
-use crate::api::v1::dto::ForgettingRunResponse;
-use crate::api::v1::response::{ApiResponse, ErrorCode};
+use axum::extract::{Path, State};
+use axum::http::HeaderValue;
+use axum::response::{IntoResponse, Response};
+use axum_extra::extract::Query;
+use chrono::{DateTime, Utc};
+
+use super::documents::process_in_background;
+use super::search::{ndjson_line, ndjson_response};
+use crate::api::v1::dto::{
+    ContainerExportRecord, ContainerSettingsResponse, ContradictionResponse, CreateWebhookRequest,
+    DeadLetterDocumentResponse, DetectRelationsQuery, DetectRelationsResponse, DocumentResponse,
+    ExportContainerQuery, ExportedMemory, ForgettingPreviewResponse, ForgettingRunResponse,
+    InferenceRunResponse, JobAcceptedResponse, ListContradictionsQuery, ListContradictionsResponse,
+    ListDeadLetterDocumentsResponse, ListWebhooksQuery, ListWebhooksResponse,
+    ProcessingStatsResponse, ReplicaStatusResponse, ReprocessDocumentResponse,
+    ResolveContradictionRequest, ResolveContradictionResponse, UpdateContainerSettingsRequest,
+    WebhookResponse,
+};
+use crate::api::v1::response::{ApiError, ApiResponse, ErrorCode, ResponseMeta};
 use crate::api::AppState;
-use crate::services::ForgettingManager;
+use crate::error::{MomoError, Result};
+use crate::intelligence::{InferenceEngine, RelationshipDetector};
+use crate::models::{ContainerSettings, JobKind, ProcessingStatus, Webhook};
+use crate::services::{ChunkReembedder, EpisodeDecayManager, ForgettingManager, JobTracker};
 
 /// `POST /api/v1/admin/forgetting:run`
 #[utoipa::path(
@@ -29,7 +49,10 @@ pub async fn run_forgetting(State(state): State<AppState>) -> ApiResponse<Forget
     let manager = ForgettingManager::new(
         state.db.clone(),
         state.config.memory.forgetting_check_interval_secs,
-    );
+    )
+    .with_importance(state.config.memory.importance.clone())
+    .with_idle_forgetting(state.config.memory.idle_forgetting.clone())
+    .with_dry_run(state.config.memory.forgetting_dry_run);
 
     match manager.run_once().await {
         Ok(forgotten_count) => ApiResponse::success(ForgettingRunResponse {
@@ -43,9 +66,895 @@ pub async fn run_forgetting(State(state): State<AppState>) -> ApiResponse<Forget
     }
 }
 
+/// `GET /api/v1/admin/forgetting/preview`
+///
+/// Lists what the next forgetting and episode decay runs would do, without
+/// changing anything.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/forgetting/preview",
+    tag = "admin",
+    operation_id = "admin.previewForgetting",
+    responses(
+        (status = 200, description = "Memories the next run would forget", body = ForgettingPreviewResponse),
+        (status = 500, description = "Internal server error", body = ApiError),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn preview_forgetting(
+    State(state): State<AppState>,
+) -> ApiResponse<ForgettingPreviewResponse> {
+    let config = &state.config.memory;
+    let forgetting =
+        ForgettingManager::new(state.db.clone(), config.forgetting_check_interval_secs)
+            .with_importance(config.importance.clone())
+            .with_idle_forgetting(config.idle_forgetting.clone());
+    let decay = EpisodeDecayManager::new(
+        state.db.clone(),
+        config.episode_decay_threshold,
+        config.episode_forget_grace_days,
+        config.episode_decay_days,
+        config.episode_decay_factor,
+    );
+
+    let forgets = match forgetting.preview().await {
+        Ok(planned) => planned,
+        Err(e) => return e.into(),
+    };
+    let decays = match decay.preview().await {
+        Ok(planned) => planned,
+        Err(e) => return e.into(),
+    };
+
+    let memories = forgets
+        .into_iter()
+        .map(Into::into)
+        .chain(decays.into_iter().map(Into::into))
+        .collect();
+    ApiResponse::success(ForgettingPreviewResponse { memories })
+}
+
+/// `POST /api/v1/admin/inference:run`
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/inference:run",
+    tag = "admin",
+    operation_id = "admin.runInference",
+    responses(
+        (status = 200, description = "Inference run completed or skipped", body = InferenceRunResponse),
+        (status = 409, description = "An inference run is already in progress", body = ApiError),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn run_inference(State(state): State<AppState>) -> ApiResponse<InferenceRunResponse> {
+    if !state.config.memory.inference.enabled {
+        return ApiResponse::success(InferenceRunResponse::skipped(
+            "Inference is disabled; set ENABLE_INFERENCES=true to enable it",
+        ));
+    }
+    if !state.llm.is_available() {
+        return ApiResponse::success(InferenceRunResponse::skipped(
+            "LLM provider is unavailable; inference requires an LLM",
+        ));
+    }
+
+    let engine = InferenceEngine::new(
+        state.db.clone(),
+        state.llm.clone(),
+        state.embeddings.clone(),
+        state.config.memory.inference.clone(),
+    );
+
+    // Spawned so the run finishes and releases its lease even if the client
+    // disconnects and this request future is dropped.
+    let outcome = match tokio::spawn(async move { engine.try_run_once().await }).await {
+        Ok(outcome) => outcome,
+        Err(e) => Err(MomoError::Internal(e.to_string())),
+    };
+
+    match outcome {
+        Ok(Some(stats)) => ApiResponse::success(InferenceRunResponse::from(stats)),
+        Ok(None) => ApiResponse::error(
+            ErrorCode::Conflict,
+            "An inference run is already in progress",
+        ),
+        Err(e) => ApiResponse::error(
+            ErrorCode::InternalError,
+            format!("Inference run failed: {e}"),
+        ),
+    }
+}
+
+/// `POST /api/v1/admin/chunks:reembed`
+///
+/// Starts re-embedding every chunk with a stale or missing embedding in the
+/// background, instead of waiting for the periodic re-embedding worker.
+/// Returns 202 Accepted with a `jobId` to poll at `GET /api/v1/jobs/{jobId}`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/chunks:reembed",
+    tag = "admin",
+    operation_id = "admin.reembedChunks",
+    responses(
+        (status = 202, description = "Re-embedding job started", body = JobAcceptedResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn reembed_chunks(State(state): State<AppState>) -> ApiResponse<JobAcceptedResponse> {
+    let job = match JobTracker::create(state.db.clone(), JobKind::ChunkReembed, None).await {
+        Ok(job) => job,
+        Err(e) => return e.into(),
+    };
+    let job_id = job.id().to_string();
+
+    let reembedder = ChunkReembedder::new(
+        state.db.clone(),
+        state.embeddings.clone(),
+        &state.config.processing,
+    );
+    tokio::spawn(async move {
+        if let Err(e) = reembedder.run_job(&job).await {
+            tracing::error!(job_id = %job.id(), error = %e, "Chunk re-embedding job failed");
+        }
+    });
+
+    ApiResponse::accepted(JobAcceptedResponse { job_id })
+}
+
+/// `GET /api/v1/admin/contradictions`
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/contradictions",
+    tag = "admin",
+    operation_id = "admin.listContradictions",
+    params(ListContradictionsQuery),
+    responses(
+        (status = 200, description = "Flagged contradictions", body = ListContradictionsResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_contradictions(
+    State(state): State<AppState>,
+    Query(query): Query<ListContradictionsQuery>,
+) -> ApiResponse<ListContradictionsResponse> {
+    let container_tag = query.container_tag.as_deref().filter(|tag| !tag.is_empty());
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    // Cursor encodes a page number (1-based)
+    let page = query
+        .cursor
+        .as_deref()
+        .and_then(|c| c.parse::<u32>().ok())
+        .unwrap_or(1)
+        .max(1);
+    let offset = (page - 1).saturating_mul(limit);
+
+    match state
+        .memory
+        .list_contradictions(
+            container_tag,
+            query.include_resolved.unwrap_or(false),
+            limit,
+            offset,
+        )
+        .await
+    {
+        Ok((details, total)) => {
+            let next_cursor =
+                (total > u64::from(offset) + u64::from(limit)).then(|| (page + 1).to_string());
+            ApiResponse::success_with_meta(
+                ListContradictionsResponse {
+                    contradictions: details
+                        .into_iter()
+                        .map(ContradictionResponse::from)
+                        .collect(),
+                },
+                ResponseMeta {
+                    next_cursor,
+                    total: Some(total),
+                },
+            )
+        }
+        Err(e) => e.into(),
+    }
+}
+
+/// `POST /api/v1/admin/contradictions/resolve`
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/contradictions/resolve",
+    tag = "admin",
+    operation_id = "admin.resolveContradiction",
+    request_body = ResolveContradictionRequest,
+    responses(
+        (status = 200, description = "Contradiction resolved", body = ResolveContradictionResponse),
+        (status = 400, description = "Contradiction already resolved", body = ApiError),
+        (status = 404, description = "Contradiction not found", body = ApiError),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn resolve_contradiction(
+    State(state): State<AppState>,
+    axum::Json(req): axum::Json<ResolveContradictionRequest>,
+) -> ApiResponse<ResolveContradictionResponse> {
+    if req.contradiction_id.trim().is_empty() {
+        return ApiResponse::error(ErrorCode::InvalidRequest, "contradictionId cannot be empty");
+    }
+
+    match state
+        .memory
+        .resolve_contradiction(&req.contradiction_id, req.decision.into())
+        .await
+    {
+        Ok(resp) => ApiResponse::success(ResolveContradictionResponse::from(resp)),
+        Err(e) => e.into(),
+    }
+}
+
+/// `GET /api/v1/admin/containers/{tag}/settings`
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/containers/{tag}/settings",
+    tag = "admin",
+    operation_id = "admin.getContainerSettings",
+    params(("tag" = String, Path, description = "Container tag")),
+    responses(
+        (status = 200, description = "Container settings", body = ContainerSettingsResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_container_settings(
+    State(state): State<AppState>,
+    Path(tag): Path<String>,
+) -> ApiResponse<ContainerSettingsResponse> {
+    match state.db.get_container_settings(&tag).await {
+        Ok(settings) => {
+            let settings = settings.unwrap_or(ContainerSettings {
+                container_tag: tag,
+                ..Default::default()
+            });
+            ApiResponse::success(ContainerSettingsResponse::new(
+                settings,
+                &state.config.memory,
+            ))
+        }
+        Err(e) => e.into(),
+    }
+}
+
+/// `PUT /api/v1/admin/containers/{tag}/settings`
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/containers/{tag}/settings",
+    tag = "admin",
+    operation_id = "admin.updateContainerSettings",
+    params(("tag" = String, Path, description = "Container tag")),
+    request_body = UpdateContainerSettingsRequest,
+    responses(
+        (status = 200, description = "Container settings updated", body = ContainerSettingsResponse),
+        (status = 400, description = "Invalid settings", body = ApiError),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_container_settings(
+    State(state): State<AppState>,
+    Path(tag): Path<String>,
+    axum::Json(req): axum::Json<UpdateContainerSettingsRequest>,
+) -> ApiResponse<ContainerSettingsResponse> {
+    if tag.trim().is_empty() {
+        return ApiResponse::error(ErrorCode::InvalidRequest, "Container tag cannot be empty");
+    }
+    if req
+        .episode_decay_days
+        .is_some_and(|days| !(days.is_finite() && days > 0.0))
+    {
+        return ApiResponse::error(
+            ErrorCode::InvalidRequest,
+            "episodeDecayDays must be greater than 0",
+        );
+    }
+    if req
+        .episode_decay_factor
+        .is_some_and(|factor| !(factor > 0.0 && factor < 1.0))
+    {
+        return ApiResponse::error(
+            ErrorCode::InvalidRequest,
+            "episodeDecayFactor must be between 0 and 1",
+        );
+    }
+
+    let settings = ContainerSettings {
+        container_tag: tag,
+        episode_decay_days: req.episode_decay_days,
+        episode_decay_factor: req.episode_decay_factor,
+    };
+    match state.db.set_container_settings(&settings).await {
+        Ok(()) => ApiResponse::success(ContainerSettingsResponse::new(
+            settings,
+            &state.config.memory,
+        )),
+        Err(e) => e.into(),
+    }
+}
+
+/// Pairs classified by `detect-relations` when `maxPairs` is not given.
+const DEFAULT_DETECT_MAX_PAIRS: u32 = 100;
+
+/// `POST /api/v1/admin/containers/{tag}/detect-relations`
+///
+/// Runs relationship detection over a container's existing latest memories,
+/// adding `updates`, `extends` and `derives` relations between similar
+/// memories that are not yet related.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/containers/{tag}/detect-relations",
+    tag = "admin",
+    operation_id = "admin.detectRelations",
+    params(("tag" = String, Path, description = "Container tag"), DetectRelationsQuery),
+    responses(
+        (status = 200, description = "Detection completed or skipped", body = DetectRelationsResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn detect_relations(
+    State(state): State<AppState>,
+    Path(tag): Path<String>,
+    Query(query): Query<DetectRelationsQuery>,
+) -> ApiResponse<DetectRelationsResponse> {
+    if !state.llm.is_available() {
+        return ApiResponse::success(DetectRelationsResponse::skipped(
+            "LLM provider is unavailable; relationship detection requires an LLM",
+        ));
+    }
+
+    let detector = RelationshipDetector::new(state.llm.clone(), state.embeddings.clone());
+    let max_pairs = query.max_pairs.unwrap_or(DEFAULT_DETECT_MAX_PAIRS) as usize;
+    match detector
+        .detect_in_container(&tag, state.db.as_ref(), max_pairs)
+        .await
+    {
+        Ok(stats) => ApiResponse::success(stats.into()),
+        Err(e) => ApiResponse::error(
+            ErrorCode::InternalError,
+            format!("Relationship detection failed: {e}"),
+        ),
+    }
+}
+
+/// Memories or documents read per query while exporting a container.
+const EXPORT_PAGE_SIZE: usize = 200;
+
+/// Response header carrying the job that tracks a container export.
+const JOB_ID_HEADER: &str = "X-Momo-Job-Id";
+
+/// `GET /api/v1/admin/containers/{tag}/export`
+///
+/// Snapshot of a container as NDJSON, read from the database a page at a
+/// time: every memory, including forgotten and superseded versions, oldest
+/// first, then optionally the container's documents. A failure after output
+/// has started ends the stream with an `error` line. The export is tracked
+/// by a job whose id is returned in the `X-Momo-Job-Id` header.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/containers/{tag}/export",
+    tag = "admin",
+    operation_id = "admin.exportContainer",
+    params(("tag" = String, Path, description = "Container tag"), ExportContainerQuery),
+    responses(
+        (status = 200, description = "One `ContainerExportRecord` per line", content_type = "application/x-ndjson", body = ContainerExportRecord,
+            headers(("X-Momo-Job-Id" = String, description = "Job tracking the export"))),
+        (status = 400, description = "Invalid request", body = ApiError),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn export_container(
+    State(state): State<AppState>,
+    Path(tag): Path<String>,
+    Query(query): Query<ExportContainerQuery>,
+) -> Response {
+    if tag.trim().is_empty() {
+        return ApiResponse::<()>::error(
+            ErrorCode::InvalidRequest,
+            "Container tag cannot be empty",
+        )
+        .into_response();
+    }
+
+    let job = match JobTracker::create(state.db.clone(), JobKind::ContainerExport, None).await {
+        Ok(job) => job,
+        Err(e) => return ApiResponse::<()>::from(e).into_response(),
+    };
+    job.start().await;
+    let job_id = job.id().to_string();
+
+    let export = Arc::new(ContainerExport {
+        state,
+        tag,
+        include_documents: query.include_documents.unwrap_or(false),
+        include_embeddings: query.include_embeddings.unwrap_or(false),
+        job,
+        memories_written: AtomicU64::new(0),
+        documents_written: AtomicU64::new(0),
+        finished: AtomicBool::new(false),
+    });
+    let lines = futures::stream::unfold(Some(ExportStage::Memories(None)), move |stage| {
+        let export = export.clone();
+        async move {
+            match export.next_page(stage?).await {
+                Ok((lines, next)) => {
+                    export.track_page(next.is_none()).await;
+                    Some((lines, next))
+                }
+                Err(e) => {
+                    export.finished.store(true, Ordering::Relaxed);
+                    export.job.fail(&e.to_string()).await;
+                    let error = ApiResponse::<()>::from(e)
+                        .error
+                        .expect("error responses carry an error");
+                    Some((ndjson_line(&ContainerExportRecord::Error { error }), None))
+                }
+            }
+        }
+    });
+    let mut response = ndjson_response(lines);
+    if let Ok(value) = HeaderValue::from_str(&job_id) {
+        response.headers_mut().insert(JOB_ID_HEADER, value);
+    }
+    response
+}
+
+/// Where a container export resumes: the `(created_at, id)` of the last
+/// record written, if any.
+enum ExportStage {
+    Memories(Option<(DateTime<Utc>, String)>),
+    Documents(Option<(DateTime<Utc>, String)>),
+}
+
+struct ContainerExport {
+    state: AppState,
+    tag: String,
+    include_documents: bool,
+    include_embeddings: bool,
+    job: JobTracker,
+    memories_written: AtomicU64,
+    documents_written: AtomicU64,
+    /// Set once the job has been completed or failed.
+    finished: AtomicBool,
+}
+
+impl Drop for ContainerExport {
+    /// A client that disconnects mid-export drops the stream; fail the job
+    /// instead of leaving it running.
+    fn drop(&mut self) {
+        if self.finished.load(Ordering::Relaxed) {
+            return;
+        }
+        let job = self.job.clone();
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            handle.spawn(async move {
+                job.fail("Export stream closed before it completed").await;
+            });
+        }
+    }
+}
+
+impl ContainerExport {
+    /// Report the records written so far on the job, completing it after
+    /// the last page.
+    async fn track_page(&self, last: bool) {
+        let memories = self.memories_written.load(Ordering::Relaxed);
+        let documents = self.documents_written.load(Ordering::Relaxed);
+        if last {
+            self.finished.store(true, Ordering::Relaxed);
+            self.job
+                .complete(serde_json::json!({
+                    "memories": memories,
+                    "documents": documents,
+                }))
+                .await;
+        } else {
+            self.job.progress(memories + documents).await;
+        }
+    }
+
+    /// NDJSON lines for the page starting at `stage`, and the stage after it;
+    /// `None` once the export is complete.
+    async fn next_page(&self, stage: ExportStage) -> Result<(String, Option<ExportStage>)> {
+        match stage {
+            ExportStage::Memories(after) => {
+                let memories = self
+                    .state
+                    .db
+                    .list_container_memories_after(
+                        &self.tag,
+                        after.as_ref().map(|(at, id)| (*at, id.as_str())),
+                        EXPORT_PAGE_SIZE,
+                    )
+                    .await?;
+                let next = match memories.last() {
+                    Some(last) if memories.len() == EXPORT_PAGE_SIZE => Some(
+                        ExportStage::Memories(Some((last.created_at, last.id.clone()))),
+                    ),
+                    _ => self
+                        .include_documents
+                        .then_some(ExportStage::Documents(None)),
+                };
+
+                self.memories_written
+                    .fetch_add(memories.len() as u64, Ordering::Relaxed);
+                let mut embeddings = if self.include_embeddings {
+                    let ids: Vec<String> = memories.iter().map(|m| m.id.clone()).collect();
+                    self.state.db.get_memory_embeddings(&ids).await?
+                } else {
+                    Default::default()
+                };
+                let lines = memories
+                    .into_iter()
+                    .map(|memory| {
+                        let embedding = embeddings.remove(&memory.id);
+                        let mut exported = ExportedMemory::from(memory);
+                        exported.embedding = embedding;
+                        ndjson_line(&ContainerExportRecord::Memory(Box::new(exported)))
+                    })
+                    .collect();
+                Ok((lines, next))
+            }
+            ExportStage::Documents(after) => {
+                let documents = self
+                    .state
+                    .db
+                    .list_container_documents_after(
+                        &self.tag,
+                        after.as_ref().map(|(at, id)| (*at, id.as_str())),
+                        EXPORT_PAGE_SIZE,
+                    )
+                    .await?;
+                let next = match documents.last() {
+                    Some(last) if documents.len() == EXPORT_PAGE_SIZE => Some(
+                        ExportStage::Documents(Some((last.created_at, last.id.clone()))),
+                    ),
+                    _ => None,
+                };
+                self.documents_written
+                    .fetch_add(documents.len() as u64, Ordering::Relaxed);
+                let lines = documents
+                    .into_iter()
+                    .map(|doc| {
+                        ndjson_line(&ContainerExportRecord::Document(Box::new(
+                            DocumentResponse::from(doc),
+                        )))
+                    })
+                    .collect();
+                Ok((lines, next))
+            }
+        }
+    }
+}
+
+/// `POST /api/v1/admin/webhooks`
+///
+/// Registers a webhook notified when documents in the container finish
+/// processing. The signing secret is only returned in this response.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/webhooks",
+    tag = "admin",
+    operation_id = "admin.createWebhook",
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 201, description = "Webhook registered", body = WebhookResponse),
+        (status = 400, description = "Invalid webhook", body = ApiError),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    axum::Json(req): axum::Json<CreateWebhookRequest>,
+) -> ApiResponse<WebhookResponse> {
+    if req.container_tag.trim().is_empty() {
+        return ApiResponse::error(ErrorCode::InvalidRequest, "containerTag cannot be empty");
+    }
+    let valid_url = reqwest::Url::parse(&req.url)
+        .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.has_host());
+    if !valid_url {
+        return ApiResponse::error(ErrorCode::InvalidRequest, "url must be an http(s) URL");
+    }
+    let secret = match req.secret {
+        Some(secret) if secret.trim().is_empty() => {
+            return ApiResponse::error(ErrorCode::InvalidRequest, "secret cannot be empty");
+        }
+        Some(secret) => secret,
+        None => nanoid::nanoid!(32),
+    };
+
+    let webhook = Webhook {
+        id: nanoid::nanoid!(),
+        container_tag: req.container_tag,
+        url: req.url,
+        secret,
+        created_at: Utc::now(),
+    };
+    match state.db.create_webhook(&webhook).await {
+        Ok(()) => ApiResponse::created(WebhookResponse::with_secret(webhook)),
+        Err(e) => e.into(),
+    }
+}
+
+/// `GET /api/v1/admin/webhooks`
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/webhooks",
+    tag = "admin",
+    operation_id = "admin.listWebhooks",
+    params(ListWebhooksQuery),
+    responses(
+        (status = 200, description = "Registered webhooks", body = ListWebhooksResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_webhooks(
+    State(state): State<AppState>,
+    Query(query): Query<ListWebhooksQuery>,
+) -> ApiResponse<ListWebhooksResponse> {
+    let container_tags: Option<Vec<String>> = query
+        .container_tag
+        .filter(|tag| !tag.is_empty())
+        .map(|tag| vec![tag]);
+
+    match state.db.list_webhooks(container_tags.as_deref()).await {
+        Ok(webhooks) => ApiResponse::success(ListWebhooksResponse {
+            webhooks: webhooks
+                .into_iter()
+                .map(WebhookResponse::redacted)
+                .collect(),
+        }),
+        Err(e) => e.into(),
+    }
+}
+
+/// `DELETE /api/v1/admin/webhooks/{webhookId}`
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/webhooks/{webhookId}",
+    tag = "admin",
+    operation_id = "admin.deleteWebhook",
+    params(("webhookId" = String, Path, description = "Webhook ID")),
+    responses(
+        (status = 200, description = "Webhook deleted", body = Object),
+        (status = 404, description = "Webhook not found", body = ApiError),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResponse<serde_json::Value> {
+    match state.db.delete_webhook(&id).await {
+        Ok(true) => ApiResponse::success(serde_json::json!({ "deleted": true })),
+        Ok(false) => ApiResponse::error(ErrorCode::NotFound, format!("Webhook {id} not found")),
+        Err(e) => e.into(),
+    }
+}
+
+/// `GET /api/v1/admin/processing/stats`
+///
+/// Documents per processing status, the age of the oldest pending document
+/// and the average and p95 duration of recent processing runs.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/processing/stats",
+    tag = "admin",
+    operation_id = "admin.processingStats",
+    responses(
+        (status = 200, description = "Processing statistics", body = ProcessingStatsResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn processing_stats(
+    State(state): State<AppState>,
+) -> ApiResponse<ProcessingStatsResponse> {
+    match state.db.get_processing_stats().await {
+        Ok(stats) => ApiResponse::success(stats.into()),
+        Err(e) => e.into(),
+    }
+}
+
+/// `GET /api/v1/admin/documents/dead-letter`
+///
+/// Documents that failed `PROCESSING_DEAD_LETTER_AFTER` times and are no
+/// longer retried, most recently failed first.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/documents/dead-letter",
+    tag = "admin",
+    operation_id = "admin.listDeadLetterDocuments",
+    responses(
+        (status = 200, description = "Dead-lettered documents", body = ListDeadLetterDocumentsResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_dead_letter_documents(
+    State(state): State<AppState>,
+) -> ApiResponse<ListDeadLetterDocumentsResponse> {
+    match state.db.list_dead_letter_documents().await {
+        Ok(docs) => ApiResponse::success(ListDeadLetterDocumentsResponse {
+            documents: docs
+                .into_iter()
+                .map(DeadLetterDocumentResponse::from)
+                .collect(),
+        }),
+        Err(e) => e.into(),
+    }
+}
+
+/// `POST /api/v1/admin/documents/{documentId}/requeue`
+///
+/// Resets a dead-lettered document's retry count and processes it again,
+/// tracked by the returned `jobId`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/documents/{documentId}/requeue",
+    tag = "admin",
+    operation_id = "admin.requeueDocument",
+    params(("documentId" = String, Path, description = "Document ID")),
+    responses(
+        (status = 202, description = "Document queued for processing", body = ReprocessDocumentResponse),
+        (status = 404, description = "Document not found", body = ApiError),
+        (status = 409, description = "Document is not dead-lettered", body = ApiError),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn requeue_document(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResponse<ReprocessDocumentResponse> {
+    let doc = match state.db.get_document_by_id(&id).await {
+        Ok(Some(doc)) => doc,
+        Ok(None) => {
+            return ApiResponse::error(ErrorCode::NotFound, format!("Document {id} not found"))
+        }
+        Err(e) => return e.into(),
+    };
+
+    if doc.status != ProcessingStatus::DeadLetter {
+        return ApiResponse::error(
+            ErrorCode::Conflict,
+            format!("Document {id} is not dead-lettered"),
+        );
+    }
+
+    match state.db.requeue_dead_letter_document(&id).await {
+        Ok(true) => {}
+        // Requeued concurrently.
+        Ok(false) => {
+            return ApiResponse::error(
+                ErrorCode::Conflict,
+                format!("Document {id} is not dead-lettered"),
+            )
+        }
+        Err(e) => return e.into(),
+    }
+
+    let job = match JobTracker::create(state.db.clone(), JobKind::Reprocess, Some(1)).await {
+        Ok(job) => job,
+        Err(e) => return e.into(),
+    };
+    ApiResponse::accepted(process_in_background(state.pipeline.clone(), job, id))
+}
+
+/// `GET /api/v1/admin/replica/status`
+///
+/// Read-replica sync results recorded by the sync loop, plus a lag estimate
+/// from the latest document or memory write on each side.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/replica/status",
+    tag = "admin",
+    operation_id = "admin.replicaStatus",
+    responses(
+        (status = 200, description = "Read-replica status", body = ReplicaStatusResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn replica_status(State(state): State<AppState>) -> ApiResponse<ReplicaStatusResponse> {
+    let Some(replica) = &state.replica else {
+        return ApiResponse::success(ReplicaStatusResponse::not_configured());
+    };
+
+    let primary_latest = match state.db.get_latest_write_at().await {
+        Ok(latest) => latest,
+        Err(e) => return e.into(),
+    };
+    // An unreachable replica is reported through the sync fields instead.
+    let replica_latest = match state.read_db.get_latest_write_at().await {
+        Ok(latest) => latest,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to read latest write from read replica");
+            None
+        }
+    };
+
+    ApiResponse::success(ReplicaStatusResponse::new(
+        replica.sync_interval_secs(),
+        replica.snapshot(),
+        primary_latest,
+        replica_latest,
+    ))
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::api::v1::dto::ForgettingRunResponse;
+    use crate::api::v1::dto::{
+        ContainerSettingsResponse, ContradictionDecision, DeadLetterDocumentResponse,
+        ForgettingRunResponse, InferenceRunResponse, ListContradictionsQuery,
+        ProcessingStatsResponse, ReplicaStatusResponse, ResolveContradictionRequest,
+    };
+    use crate::config::Config;
+    use crate::db::ReplicaSyncSnapshot;
+    use crate::intelligence::inference::InferenceStats;
+    use crate::models::{ContainerSettings, DeadLetterDocument, ProcessingStats, ProcessingStatus};
+    use chrono::Utc;
+
+    #[test]
+    fn processing_stats_response_fills_missing_statuses() {
+        let resp = ProcessingStatsResponse::from(ProcessingStats {
+            counts: vec![(ProcessingStatus::Queued, 3), (ProcessingStatus::Done, 7)],
+            oldest_pending_at: Some(Utc::now() - chrono::Duration::minutes(5)),
+            recent_durations_ms: vec![1200, 800],
+        });
+        let json = serde_json::to_value(&resp).expect("serialize");
+        assert_eq!(json["counts"]["queued"], 3);
+        assert_eq!(json["counts"]["embedding"], 0);
+        assert_eq!(json["counts"]["done"], 7);
+        assert!(json["oldestPendingAgeSecs"].as_u64().unwrap() >= 300);
+        assert_eq!(json["durations"]["sampleSize"], 2);
+        assert_eq!(json["durations"]["averageMs"], 1000);
+        assert_eq!(json["durations"]["p95Ms"], 1200);
+    }
+
+    #[test]
+    fn replica_status_response_estimates_lag() {
+        let now = Utc::now();
+        let sync = ReplicaSyncSnapshot {
+            last_success_at: Some(now),
+            last_attempt_at: Some(now),
+            ..Default::default()
+        };
+        let resp = ReplicaStatusResponse::new(
+            2,
+            sync,
+            Some(now),
+            Some(now - chrono::Duration::seconds(30)),
+        );
+        let json = serde_json::to_value(&resp).expect("serialize");
+        assert_eq!(json["configured"], true);
+        assert_eq!(json["syncIntervalSecs"], 2);
+        assert_eq!(json["lagSecs"], 30);
+        assert_eq!(json["consecutiveFailures"], 0);
+
+        let resp = ReplicaStatusResponse::new(2, ReplicaSyncSnapshot::default(), Some(now), None);
+        assert!(resp.lag_secs.is_none());
+
+        let json =
+            serde_json::to_value(ReplicaStatusResponse::not_configured()).expect("serialize");
+        assert_eq!(json["configured"], false);
+        assert!(json["lastSyncAt"].is_null());
+    }
+
+    #[test]
+    fn dead_letter_document_response_keeps_last_error() {
+        let resp = DeadLetterDocumentResponse::from(DeadLetterDocument {
+            id: "doc1".to_string(),
+            title: None,
+            retry_count: 5,
+            error_message: Some("Unsupported file format".to_string()),
+            updated_at: Utc::now(),
+        });
+        let json = serde_json::to_value(&resp).expect("serialize");
+        assert_eq!(json["documentId"], "doc1");
+        assert_eq!(json["retryCount"], 5);
+        assert_eq!(json["errorMessage"], "Unsupported file format");
+        assert!(json.get("title").is_none());
+    }
 
     #[test]
     fn forgetting_run_response_serializes_camel_case() {
@@ -57,4 +966,81 @@ mod tests {
         assert_eq!(json["memoriesForgotten"], 5);
         assert_eq!(json["memoriesEvaluated"], 42);
     }
+
+    #[test]
+    fn inference_run_response_reports_stats_or_skip_reason() {
+        let ran = InferenceRunResponse::from(InferenceStats {
+            seeds_processed: 4,
+            inferences_created: 2,
+            duplicates_skipped: 1,
+            low_confidence_skipped: 1,
+            errors: 0,
+            llm_calls: 4,
+            llm_tokens: 1200,
+            budget_exhausted: true,
+        });
+        let json = serde_json::to_value(&ran).expect("serialize");
+        assert_eq!(json["ran"], true);
+        assert_eq!(json["seedsProcessed"], 4);
+        assert_eq!(json["inferencesCreated"], 2);
+        assert_eq!(json["llmTokens"], 1200);
+        assert_eq!(json["budgetExhausted"], true);
+        assert!(json.get("message").is_none());
+
+        let skipped = InferenceRunResponse::skipped("Inference is disabled");
+        let json = serde_json::to_value(&skipped).expect("serialize");
+        assert_eq!(json["ran"], false);
+        assert_eq!(json["message"], "Inference is disabled");
+        assert_eq!(json["inferencesCreated"], 0);
+        assert!(json.get("budgetExhausted").is_none());
+    }
+
+    #[test]
+    fn resolve_contradiction_request_parses_decisions() {
+        let req: ResolveContradictionRequest = serde_json::from_value(serde_json::json!({
+            "contradictionId": "c1",
+            "decision": "keepB"
+        }))
+        .expect("deserialize");
+        assert_eq!(req.contradiction_id, "c1");
+        assert_eq!(req.decision, ContradictionDecision::KeepB);
+
+        let both: ContradictionDecision =
+            serde_json::from_value(serde_json::json!("keepBoth")).expect("deserialize");
+        assert_eq!(both, ContradictionDecision::KeepBoth);
+        assert!(
+            serde_json::from_value::<ContradictionDecision>(serde_json::json!("keep")).is_err()
+        );
+    }
+
+    #[test]
+    fn list_contradictions_query_accepts_snake_case_container_tag() {
+        let query: ListContradictionsQuery =
+            serde_json::from_value(serde_json::json!({ "container_tag": "user_1" }))
+                .expect("deserialize");
+        assert_eq!(query.container_tag.as_deref(), Some("user_1"));
+        assert!(query.include_resolved.is_none());
+    }
+
+    #[test]
+    fn container_settings_response_falls_back_to_global_decay() {
+        let mut config = Config::default();
+        config.memory.episode_decay_days = 30.0;
+        config.memory.episode_decay_factor = 0.9;
+
+        let resp = ContainerSettingsResponse::new(
+            ContainerSettings {
+                container_tag: "journal".to_string(),
+                episode_decay_days: Some(7.0),
+                episode_decay_factor: None,
+            },
+            &config.memory,
+        );
+        let json = serde_json::to_value(&resp).expect("serialize");
+        assert_eq!(json["containerTag"], "journal");
+        assert_eq!(json["episodeDecayDays"], 7.0);
+        assert!(json["episodeDecayFactor"].is_null());
+        assert_eq!(json["effectiveEpisodeDecayDays"], 7.0);
+        assert_eq!(json["effectiveEpisodeDecayFactor"], 0.9);
+    }
 }
@@ -1,9 +1,11 @@
 //! v1 Conversation handlers.
 
 use axum::extract::State;
+use axum::Extension;
 use nanoid::nanoid;
 
 use crate::api::v1::dto::conversation::{ConversationIngestRequest, ConversationIngestResponse};
+use crate::api::v1::middleware::{check_container_tag, DefaultContainerTag};
 use crate::api::v1::response::{ApiError, ApiResponse, ErrorCode};
 use crate::api::AppState;
 use crate::models::{ConversationMessage, ConversationResponse, MemoryType};
@@ -25,6 +27,7 @@ use crate::models::{ConversationMessage, ConversationResponse, MemoryType};
 )]
 pub async fn ingest_conversation(
     State(state): State<AppState>,
+    default_tag: Option<Extension<DefaultContainerTag>>,
     axum::Json(req): axum::Json<ConversationIngestRequest>,
 ) -> ApiResponse<ConversationIngestResponse> {
     if req.messages.is_empty() {
@@ -34,6 +37,9 @@ pub async fn ingest_conversation(
     if req.container_tag.trim().is_empty() {
         return ApiResponse::error(ErrorCode::InvalidRequest, "Container tag cannot be empty");
     }
+    if let Err(forbidden) = check_container_tag(&req.container_tag, default_tag.as_ref()) {
+        return forbidden.into();
+    }
 
     let session_id = req.session_id.clone().unwrap_or_else(|| nanoid!());
 
@@ -83,7 +89,14 @@ pub async fn ingest_conversation(
 
         match state
             .memory
-            .create_memory_with_type(&memory.content, &req.container_tag, false, memory_type)
+            .create_memory_with_type(
+                &memory.content,
+                &req.container_tag,
+                false,
+                memory_type,
+                None,
+                None,
+            )
             .await
         {
             Ok(created) => memory_ids.push(created.id),
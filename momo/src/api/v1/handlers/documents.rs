@@ -4,21 +4,41 @@
 //! file upload, and ingestion status polling. All responses are wrapped
 //! in [`ApiResponse`] envelopes.
 
+use std::convert::Infallible;
+use std::time::Duration;
+
 use axum::extract::{Multipart, Path, State};
+use axum::http::HeaderMap;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::Extension;
 use axum_extra::extract::Query;
 use base64::Engine;
 use chrono::Utc;
 use nanoid::nanoid;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::broadcast::{self, error::RecvError};
 
 use crate::api::v1::dto::{
-    BatchCreateDocumentRequest, BatchCreateDocumentResponse, CreateDocumentRequest,
-    CreateDocumentResponse, DocumentResponse, DocumentSummaryResponse, IngestionStatusResponse,
-    ListDocumentsQuery, ListDocumentsResponse, UpdateDocumentRequest,
+    BatchCreateDocumentRequest, BatchCreateDocumentResponse, BatchDocumentItem, ChunkResponse,
+    CreateDocumentRequest, CreateDocumentResponse, DeleteDocumentsQuery, DeleteDocumentsRequest,
+    DeleteDocumentsResponse, DocumentResponse, DocumentStatusEventResponse,
+    DocumentSummaryResponse, IngestionStatusResponse, ListDocumentsQuery, ListDocumentsResponse,
+    ReprocessDocumentRequest, ReprocessDocumentResponse, UpdateChunkRequest, UpdateDocumentRequest,
+};
+use crate::api::v1::middleware::{
+    check_container_tag, resolve_container_tag, resolve_container_tags, ApiKeyId,
+    DefaultContainerTag,
 };
 use crate::api::v1::response::{ApiError, ApiResponse, ErrorCode, ResponseMeta};
 use crate::api::AppState;
-use crate::models::{Document, DocumentType, ProcessingStatus};
-use crate::processing::ContentExtractor;
+use crate::db::{DatabaseBackend, IdempotentDocument};
+use crate::models::{
+    Document, DocumentDeleteFilter, DocumentType, JobKind, Metadata, NearDuplicateAction,
+    ProcessingStatus,
+};
+use crate::processing::{media_upload, ContentExtractor, DocumentStatusEvent, ProcessingPipeline};
+use crate::services::JobTracker;
 
 fn parse_form_bool(value: &str) -> Option<bool> {
     match value.trim().to_lowercase().as_str() {
@@ -28,23 +48,61 @@ fn parse_form_bool(value: &str) -> Option<bool> {
     }
 }
 
+/// Batch-level metadata with the item's own metadata layered on top.
+fn batch_item_metadata(batch: Option<&Metadata>, item: &BatchDocumentItem) -> Metadata {
+    let mut metadata = batch.cloned().unwrap_or_default();
+    if let Some(ref item_metadata) = item.metadata {
+        metadata.extend(item_metadata.clone());
+    }
+    metadata
+}
+
+const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Reads the `Idempotency-Key` header. `Ok(None)` when absent.
+fn idempotency_key(headers: &HeaderMap) -> Result<Option<String>, &'static str> {
+    let Some(value) = headers.get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(None);
+    };
+    let key = value
+        .to_str()
+        .map_err(|_| "Idempotency-Key must be visible ASCII")?
+        .trim();
+    if key.is_empty() || key.len() > 255 {
+        return Err("Idempotency-Key must be 1-255 characters");
+    }
+    Ok(Some(key.to_string()))
+}
+
 /// `POST /api/v1/documents`
 ///
 /// Creates a new document and queues it for async ingestion.
 /// Returns 202 Accepted with `documentId` and `ingestionId`.
+///
+/// With an `Idempotency-Key` header, a repeat request from the same API key
+/// returns the originally created document with 200 instead of creating a
+/// duplicate.
 #[utoipa::path(
     post,
     path = "/api/v1/documents",
     tag = "documents",
     operation_id = "documents.create",
     request_body = CreateDocumentRequest,
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Client-chosen key that makes retries return the original document"),
+    ),
     responses(
+        (status = 200, description = "Document already accepted under this idempotency key", body = CreateDocumentResponse),
         (status = 202, description = "Document accepted for processing", body = CreateDocumentResponse),
         (status = 400, description = "Invalid request", body = ApiError),
+        (status = 409, description = "Near-duplicate rejected", body = ApiError),
     )
 )]
 pub async fn create_document(
     State(state): State<AppState>,
+    default_tag: Option<Extension<DefaultContainerTag>>,
+    key_id: Option<Extension<ApiKeyId>>,
+    headers: HeaderMap,
     axum::Json(req): axum::Json<CreateDocumentRequest>,
 ) -> ApiResponse<CreateDocumentResponse> {
     // Validate content
@@ -52,8 +110,26 @@ pub async fn create_document(
         return ApiResponse::error(ErrorCode::InvalidRequest, "Content cannot be empty");
     }
 
+    let idempotency = match idempotency_key(&headers) {
+        Ok(key) => key.zip(key_id.map(|Extension(ApiKeyId(id))| id)),
+        Err(msg) => return ApiResponse::error(ErrorCode::InvalidRequest, msg),
+    };
+    if let Some((ref key, ref api_key_id)) = idempotency {
+        match state.db.get_idempotent_document(api_key_id, key).await {
+            Ok(Some(original)) => return replay_created_document(original),
+            Ok(None) => {}
+            Err(e) => return e.into(),
+        }
+    }
+
+    let container_tag = match resolve_container_tag(req.container_tag.clone(), default_tag.as_ref())
+    {
+        Ok(tag) => tag,
+        Err(forbidden) => return forbidden.into(),
+    };
+
     // Validate container_tag length
-    if let Some(ref tag) = req.container_tag {
+    if let Some(ref tag) = container_tag {
         if tag.len() > 255 {
             return ApiResponse::error(
                 ErrorCode::InvalidRequest,
@@ -66,7 +142,7 @@ pub async fn create_document(
     let now = Utc::now();
 
     let mut container_tags = Vec::new();
-    if let Some(ref tag) = req.container_tag {
+    if let Some(ref tag) = container_tag {
         container_tags.push(tag.clone());
     }
 
@@ -78,7 +154,9 @@ pub async fn create_document(
             "xlsx" => DocumentType::Xlsx,
             "pptx" => DocumentType::Pptx,
             "csv" => DocumentType::Csv,
-            "pdf" => DocumentType::Pdf,
+            "pdf" | "application/pdf" => DocumentType::Pdf,
+            "epub" | "application/epub+zip" => DocumentType::Epub,
+            "json" | "jsonl" | "application/json" | "application/x-ndjson" => DocumentType::Json,
             ct if ct.starts_with("image/")
                 || ct == "image"
                 || ct == "png"
@@ -117,6 +195,9 @@ pub async fn create_document(
     };
 
     let mut metadata = req.metadata.unwrap_or_default();
+    if let Err(e) = state.pipeline.check_chunk_overrides(&metadata) {
+        return e.into();
+    }
     // v1 default: don't extract memories unless explicitly requested
     let extract_memories = req.extract_memories.unwrap_or(false);
     metadata.insert(
@@ -124,6 +205,47 @@ pub async fn create_document(
         serde_json::json!(extract_memories),
     );
 
+    let mut near_duplicate_of = None;
+    if doc_type == DocumentType::Text {
+        match state
+            .pipeline
+            .find_near_duplicate(&req.content, &container_tags)
+            .await
+        {
+            Ok(Some(duplicate)) => match state.pipeline.near_duplicate_action() {
+                NearDuplicateAction::Reject => {
+                    return ApiResponse::error(
+                        ErrorCode::Conflict,
+                        format!(
+                            "Document is a near-duplicate of {} (similarity {:.3})",
+                            duplicate.document_id, duplicate.similarity
+                        ),
+                    );
+                }
+                NearDuplicateAction::Link => {
+                    metadata.insert(
+                        "revision_of".to_string(),
+                        serde_json::json!(duplicate.document_id),
+                    );
+                    near_duplicate_of = Some(duplicate.document_id);
+                }
+                NearDuplicateAction::Warn => {
+                    tracing::warn!(
+                        doc_id = %id,
+                        duplicate_of = %duplicate.document_id,
+                        similarity = duplicate.similarity,
+                        "Ingesting near-duplicate document"
+                    );
+                    near_duplicate_of = Some(duplicate.document_id);
+                }
+            },
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!(doc_id = %id, error = %e, "Near-duplicate check failed (non-blocking)");
+            }
+        }
+    }
+
     let doc = Document {
         id: id.clone(),
         custom_id: req.custom_id,
@@ -145,7 +267,35 @@ pub async fn create_document(
         updated_at: now,
     };
 
+    // Reserve the key before creating the document, so concurrent retries
+    // cannot both get past the lookup above and create two documents.
+    if let Some((ref key, ref api_key_id)) = idempotency {
+        let ttl = Duration::from_secs(state.config.server.idempotency_key_ttl_secs);
+        let claim = IdempotentDocument {
+            document_id: id.clone(),
+            near_duplicate_of: near_duplicate_of.clone(),
+        };
+        match state
+            .db
+            .claim_idempotency_key(api_key_id, key, &claim, ttl)
+            .await
+        {
+            Ok(holder) if holder.document_id != id => return replay_created_document(holder),
+            Ok(_) => {}
+            Err(e) => return e.into(),
+        }
+    }
+
     if let Err(e) = state.db.create_document(&doc).await {
+        if let Some((key, api_key_id)) = idempotency {
+            if let Err(release_err) = state
+                .db
+                .release_idempotency_key(&api_key_id, &key, &id)
+                .await
+            {
+                tracing::warn!(doc_id = %id, error = %release_err, "Failed to release idempotency key");
+            }
+        }
         let resp: ApiResponse<CreateDocumentResponse> = e.into();
         return resp;
     }
@@ -163,6 +313,17 @@ pub async fn create_document(
     ApiResponse::accepted(CreateDocumentResponse {
         document_id: id.clone(),
         ingestion_id: id,
+        near_duplicate_of,
+    })
+}
+
+/// Response of a retried `create_document`: the original request's body,
+/// with 200 since nothing new was accepted.
+fn replay_created_document(original: IdempotentDocument) -> ApiResponse<CreateDocumentResponse> {
+    ApiResponse::success(CreateDocumentResponse {
+        document_id: original.document_id.clone(),
+        ingestion_id: original.document_id,
+        near_duplicate_of: original.near_duplicate_of,
     })
 }
 
@@ -173,7 +334,8 @@ const MAX_FILE_SIZE: usize = 25 * 1024 * 1024; // 25 MB
 ///
 /// Creates multiple documents in a single request and queues them for
 /// async ingestion. Returns 202 Accepted with an array of
-/// `{ documentId, ingestionId }` pairs.
+/// `{ documentId, ingestionId }` pairs and a `jobId` tracking the whole
+/// batch at `GET /api/v1/jobs/{jobId}`.
 #[utoipa::path(
     post,
     path = "/api/v1/documents:batch",
@@ -187,6 +349,7 @@ const MAX_FILE_SIZE: usize = 25 * 1024 * 1024; // 25 MB
 )]
 pub async fn batch_create_documents(
     State(state): State<AppState>,
+    default_tag: Option<Extension<DefaultContainerTag>>,
     axum::Json(req): axum::Json<BatchCreateDocumentRequest>,
 ) -> ApiResponse<BatchCreateDocumentResponse> {
     if req.documents.is_empty() {
@@ -200,7 +363,13 @@ pub async fn batch_create_documents(
         );
     }
 
-    if let Some(ref tag) = req.container_tag {
+    let container_tag = match resolve_container_tag(req.container_tag.clone(), default_tag.as_ref())
+    {
+        Ok(tag) => tag,
+        Err(forbidden) => return forbidden.into(),
+    };
+
+    if let Some(ref tag) = container_tag {
         if tag.len() > 255 {
             return ApiResponse::error(
                 ErrorCode::InvalidRequest,
@@ -209,26 +378,45 @@ pub async fn batch_create_documents(
         }
     }
 
+    if req
+        .documents
+        .iter()
+        .any(|item| item.content.trim().is_empty())
+    {
+        return ApiResponse::error(ErrorCode::InvalidRequest, "Content cannot be empty");
+    }
+
+    for item in &req.documents {
+        let metadata = batch_item_metadata(req.metadata.as_ref(), item);
+        if let Err(e) = state.pipeline.check_chunk_overrides(&metadata) {
+            return e.into();
+        }
+    }
+
+    let job = match JobTracker::create(
+        state.db.clone(),
+        JobKind::BatchImport,
+        Some(req.documents.len() as u64),
+    )
+    .await
+    {
+        Ok(job) => job,
+        Err(e) => return e.into(),
+    };
+
     let now = Utc::now();
     let mut results = Vec::with_capacity(req.documents.len());
     let mut doc_ids = Vec::with_capacity(req.documents.len());
 
     for item in &req.documents {
-        if item.content.trim().is_empty() {
-            return ApiResponse::error(ErrorCode::InvalidRequest, "Content cannot be empty");
-        }
-
         let id = nanoid!();
 
         let mut container_tags = Vec::new();
-        if let Some(ref tag) = req.container_tag {
+        if let Some(ref tag) = container_tag {
             container_tags.push(tag.clone());
         }
 
-        let mut metadata = req.metadata.clone().unwrap_or_default();
-        if let Some(ref item_metadata) = item.metadata {
-            metadata.extend(item_metadata.clone());
-        }
+        let mut metadata = batch_item_metadata(req.metadata.as_ref(), item);
         let extract_memories = item.extract_memories.unwrap_or(false);
         metadata.insert(
             "extract_memories".to_string(),
@@ -257,6 +445,7 @@ pub async fn batch_create_documents(
         };
 
         if let Err(e) = state.db.create_document(&doc).await {
+            job.fail(&e.to_string()).await;
             let resp: ApiResponse<BatchCreateDocumentResponse> = e.into();
             return resp;
         }
@@ -265,20 +454,34 @@ pub async fn batch_create_documents(
         results.push(CreateDocumentResponse {
             document_id: id.clone(),
             ingestion_id: id,
+            near_duplicate_of: None,
         });
     }
 
-    // Fire-and-forget background processing for all documents
+    // Background processing for all documents, tracked by the batch job
     let pipeline = state.pipeline.clone();
+    let job_id = job.id().to_string();
     tokio::spawn(async move {
-        for doc_id in doc_ids {
-            if let Err(e) = pipeline.process_document(&doc_id).await {
+        job.start().await;
+        let mut failed_document_ids = Vec::new();
+        for (processed, doc_id) in doc_ids.iter().enumerate() {
+            if let Err(e) = pipeline.process_document(doc_id).await {
                 tracing::error!(doc_id = %doc_id, error = %e, "Failed to process document");
+                failed_document_ids.push(doc_id.clone());
             }
+            job.progress(processed as u64 + 1).await;
         }
+        job.complete(serde_json::json!({
+            "documentIds": doc_ids,
+            "failedDocumentIds": failed_document_ids,
+        }))
+        .await;
     });
 
-    ApiResponse::accepted(BatchCreateDocumentResponse { documents: results })
+    ApiResponse::accepted(BatchCreateDocumentResponse {
+        documents: results,
+        job_id,
+    })
 }
 
 /// `POST /api/v1/documents:upload`
@@ -295,10 +498,12 @@ pub async fn batch_create_documents(
     responses(
         (status = 202, description = "Upload accepted for processing", body = CreateDocumentResponse),
         (status = 400, description = "Invalid request", body = ApiError),
+        (status = 413, description = "File too large", body = ApiError),
     )
 )]
 pub async fn upload_document(
     State(state): State<AppState>,
+    default_tag: Option<Extension<DefaultContainerTag>>,
     mut multipart: Multipart,
 ) -> ApiResponse<CreateDocumentResponse> {
     let mut file_bytes: Option<Vec<u8>> = None;
@@ -332,7 +537,7 @@ pub async fn upload_document(
 
                 if bytes.len() > MAX_FILE_SIZE {
                     return ApiResponse::error(
-                        ErrorCode::InvalidRequest,
+                        ErrorCode::PayloadTooLarge,
                         format!(
                             "File too large: {} bytes (max {} bytes)",
                             bytes.len(),
@@ -409,6 +614,11 @@ pub async fn upload_document(
     let id = nanoid!();
     let now = Utc::now();
 
+    let container_tag = match resolve_container_tag(container_tag, default_tag.as_ref()) {
+        Ok(tag) => tag,
+        Err(forbidden) => return forbidden.into(),
+    };
+
     let mut container_tags = Vec::new();
     if let Some(ref tag) = container_tag {
         if tag.len() > 255 {
@@ -420,9 +630,24 @@ pub async fn upload_document(
         container_tags.push(tag.clone());
     }
 
-    let content_b64 = base64::engine::general_purpose::STANDARD.encode(&bytes);
+    // JSON and HTML go through the text extraction path, so store them as text.
+    let content = match doc_type {
+        DocumentType::Json | DocumentType::Webpage => match String::from_utf8(bytes.to_vec()) {
+            Ok(text) => text,
+            Err(_) => {
+                return ApiResponse::error(
+                    ErrorCode::InvalidRequest,
+                    format!("{doc_type} file is not valid UTF-8"),
+                )
+            }
+        },
+        _ => base64::engine::general_purpose::STANDARD.encode(&bytes),
+    };
 
     let mut doc_metadata = metadata.unwrap_or_default();
+    if let Err(e) = state.pipeline.check_chunk_overrides(&doc_metadata) {
+        return e.into();
+    }
     doc_metadata.insert(
         "extract_memories".to_string(),
         serde_json::json!(extract_memories.unwrap_or(false)),
@@ -433,7 +658,7 @@ pub async fn upload_document(
         custom_id: None,
         connection_id: None,
         title: None,
-        content: Some(content_b64),
+        content: Some(content),
         summary: None,
         url: None,
         source: None,
@@ -466,6 +691,273 @@ pub async fn upload_document(
     ApiResponse::accepted(CreateDocumentResponse {
         document_id: id.clone(),
         ingestion_id: id,
+        near_duplicate_of: None,
+    })
+}
+
+/// Bytes kept from the start of a media upload to sniff its type.
+const MEDIA_SNIFF_LEN: usize = 512;
+
+/// A media file being streamed to disk, deleted on drop unless it's been
+/// handed to a document with [`keep`](Self::keep).
+struct PendingUpload {
+    path: Option<std::path::PathBuf>,
+}
+
+impl PendingUpload {
+    fn keep(mut self) {
+        self.path = None;
+    }
+}
+
+impl Drop for PendingUpload {
+    fn drop(&mut self) {
+        if let Some(path) = self.path.take() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// `POST /api/v1/documents:uploadMedia`
+///
+/// Like `documents:upload`, for audio and video too large to hold in memory.
+/// The `file` field is streamed to the transcription upload directory and
+/// the document's content references that file, which is deleted once it's
+/// been transcribed. Accepts files up to `TRANSCRIPTION_MAX_FILE_SIZE`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/documents:uploadMedia",
+    tag = "documents",
+    operation_id = "documents.uploadMedia",
+    request_body(content_type = "multipart/form-data", content = String, description = "Audio or video file upload with optional containerTag and metadata fields"),
+    responses(
+        (status = 202, description = "Upload accepted for processing", body = CreateDocumentResponse),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 413, description = "File too large", body = ApiError),
+    )
+)]
+pub async fn upload_media(
+    State(state): State<AppState>,
+    default_tag: Option<Extension<DefaultContainerTag>>,
+    mut multipart: Multipart,
+) -> ApiResponse<CreateDocumentResponse> {
+    let id = nanoid!();
+    let upload_dir = media_upload::upload_dir(&state.config.transcription);
+    let max_size = state.config.transcription.max_file_size;
+
+    let mut upload: Option<PendingUpload> = None;
+    let mut head: Vec<u8> = Vec::new();
+    let mut file_name: Option<String> = None;
+    let mut file_content_type: Option<String> = None;
+    let mut container_tag: Option<String> = None;
+    let mut metadata: Option<std::collections::HashMap<String, serde_json::Value>> = None;
+    let mut extract_memories: Option<bool> = None;
+
+    while let Ok(Some(mut field)) = multipart.next_field().await {
+        let name = field.name().unwrap_or("").to_string();
+
+        match name.as_str() {
+            "file" => {
+                if upload.is_some() {
+                    return ApiResponse::error(
+                        ErrorCode::InvalidRequest,
+                        "Only one 'file' field is allowed",
+                    );
+                }
+                file_name = field.file_name().map(str::to_string);
+                file_content_type = field.content_type().map(str::to_string);
+
+                if let Err(e) = tokio::fs::create_dir_all(&upload_dir).await {
+                    tracing::error!(dir = %upload_dir.display(), error = %e, "Failed to create media upload directory");
+                    return ApiResponse::error(
+                        ErrorCode::InternalError,
+                        "Failed to store uploaded file",
+                    );
+                }
+                let path = upload_dir.join(&id);
+                let mut file = match tokio::fs::File::create(&path).await {
+                    Ok(file) => file,
+                    Err(e) => {
+                        tracing::error!(path = %path.display(), error = %e, "Failed to create media upload file");
+                        return ApiResponse::error(
+                            ErrorCode::InternalError,
+                            "Failed to store uploaded file",
+                        );
+                    }
+                };
+                let pending = upload.insert(PendingUpload { path: Some(path) });
+
+                let mut written: u64 = 0;
+                loop {
+                    let chunk = match field.chunk().await {
+                        Ok(Some(chunk)) => chunk,
+                        Ok(None) => break,
+                        Err(e) => {
+                            return ApiResponse::error(
+                                ErrorCode::InvalidRequest,
+                                format!("Failed to read file: {e}"),
+                            );
+                        }
+                    };
+                    written += chunk.len() as u64;
+                    if written > max_size {
+                        return ApiResponse::error(
+                            ErrorCode::PayloadTooLarge,
+                            format!("File too large (max {max_size} bytes)"),
+                        );
+                    }
+                    if head.len() < MEDIA_SNIFF_LEN {
+                        let take = (MEDIA_SNIFF_LEN - head.len()).min(chunk.len());
+                        head.extend_from_slice(&chunk[..take]);
+                    }
+                    if let Err(e) = file.write_all(&chunk).await {
+                        tracing::error!(path = ?pending.path, error = %e, "Failed to write media upload file");
+                        return ApiResponse::error(
+                            ErrorCode::InternalError,
+                            "Failed to store uploaded file",
+                        );
+                    }
+                }
+                if let Err(e) = file.flush().await {
+                    tracing::error!(path = ?pending.path, error = %e, "Failed to write media upload file");
+                    return ApiResponse::error(
+                        ErrorCode::InternalError,
+                        "Failed to store uploaded file",
+                    );
+                }
+            }
+            "containerTag" | "container_tag" => {
+                container_tag = match field.text().await {
+                    Ok(t) => Some(t),
+                    Err(e) => {
+                        return ApiResponse::error(
+                            ErrorCode::InvalidRequest,
+                            format!("Invalid container tag: {e}"),
+                        );
+                    }
+                };
+            }
+            "metadata" => {
+                let json_str = match field.text().await {
+                    Ok(t) => t,
+                    Err(e) => {
+                        return ApiResponse::error(
+                            ErrorCode::InvalidRequest,
+                            format!("Invalid metadata: {e}"),
+                        );
+                    }
+                };
+                metadata = serde_json::from_str(&json_str).ok();
+            }
+            "extractMemories" | "extract_memories" => {
+                let raw = match field.text().await {
+                    Ok(t) => t,
+                    Err(e) => {
+                        return ApiResponse::error(
+                            ErrorCode::InvalidRequest,
+                            format!("Invalid extractMemories value: {e}"),
+                        );
+                    }
+                };
+                match parse_form_bool(&raw) {
+                    Some(value) => extract_memories = Some(value),
+                    None => {
+                        return ApiResponse::error(
+                            ErrorCode::InvalidRequest,
+                            "extractMemories must be one of true/false/1/0/yes/no",
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let Some(upload) = upload else {
+        return ApiResponse::error(ErrorCode::InvalidRequest, "Missing required 'file' field");
+    };
+    if head.is_empty() {
+        return ApiResponse::error(ErrorCode::InvalidRequest, "Uploaded file is empty");
+    }
+
+    let doc_type = ContentExtractor::detect_type_from_upload(
+        &head,
+        file_name.as_deref(),
+        file_content_type.as_deref(),
+    );
+    if !matches!(doc_type, DocumentType::Audio | DocumentType::Video) {
+        return ApiResponse::error(
+            ErrorCode::InvalidRequest,
+            "documents:uploadMedia only accepts audio and video files",
+        );
+    }
+
+    let now = Utc::now();
+    let container_tag = match resolve_container_tag(container_tag, default_tag.as_ref()) {
+        Ok(tag) => tag,
+        Err(forbidden) => return forbidden.into(),
+    };
+
+    let mut container_tags = Vec::new();
+    if let Some(ref tag) = container_tag {
+        if tag.len() > 255 {
+            return ApiResponse::error(
+                ErrorCode::InvalidRequest,
+                "Container tag too long (max 255 characters)",
+            );
+        }
+        container_tags.push(tag.clone());
+    }
+
+    let mut doc_metadata = metadata.unwrap_or_default();
+    if let Err(e) = state.pipeline.check_chunk_overrides(&doc_metadata) {
+        return e.into();
+    }
+    doc_metadata.insert(
+        "extract_memories".to_string(),
+        serde_json::json!(extract_memories.unwrap_or(false)),
+    );
+
+    let doc = Document {
+        id: id.clone(),
+        custom_id: None,
+        connection_id: None,
+        title: None,
+        content: Some(media_upload::reference(&id)),
+        summary: None,
+        url: None,
+        source: None,
+        doc_type,
+        status: ProcessingStatus::Queued,
+        metadata: doc_metadata,
+        container_tags,
+        chunk_count: 0,
+        token_count: None,
+        word_count: None,
+        error_message: None,
+        created_at: now,
+        updated_at: now,
+    };
+
+    if let Err(e) = state.db.create_document(&doc).await {
+        let resp: ApiResponse<CreateDocumentResponse> = e.into();
+        return resp;
+    }
+    // From here the pipeline owns the file and deletes it after transcription.
+    upload.keep();
+
+    let pipeline = state.pipeline.clone();
+    let doc_id = id.clone();
+    tokio::spawn(async move {
+        if let Err(e) = pipeline.process_document(&doc_id).await {
+            tracing::error!(doc_id = %doc_id, error = %e, "Failed to process document");
+        }
+    });
+
+    ApiResponse::accepted(CreateDocumentResponse {
+        document_id: id.clone(),
+        ingestion_id: id,
+        near_duplicate_of: None,
     })
 }
 
@@ -518,9 +1010,16 @@ pub async fn get_document(
 )]
 pub async fn update_document(
     State(state): State<AppState>,
+    default_tag: Option<Extension<DefaultContainerTag>>,
     Path(id): Path<String>,
     axum::Json(req): axum::Json<UpdateDocumentRequest>,
 ) -> ApiResponse<DocumentResponse> {
+    for tag in req.container_tags.iter().flatten() {
+        if let Err(forbidden) = check_container_tag(tag, default_tag.as_ref()) {
+            return forbidden.into();
+        }
+    }
+
     let mut doc = match state.db.get_document_by_id(&id).await {
         Ok(Some(doc)) => doc,
         Ok(None) => {
@@ -547,6 +1046,226 @@ pub async fn update_document(
     ApiResponse::success(doc.into())
 }
 
+/// `POST /api/v1/documents/{documentId}/reprocess`
+///
+/// Re-runs ingestion for an existing document so it is re-chunked and
+/// re-embedded with the current processing settings; the pipeline replaces
+/// the old chunks. Returns 202 Accepted with `documentId`, `ingestionId` and
+/// a `jobId` tracking the run.
+#[utoipa::path(
+    post,
+    path = "/api/v1/documents/{documentId}/reprocess",
+    tag = "documents",
+    operation_id = "documents.reprocess",
+    params(("documentId" = String, Path, description = "Document ID")),
+    request_body(content = ReprocessDocumentRequest, description = "Optional overrides"),
+    responses(
+        (status = 202, description = "Document queued for reprocessing", body = ReprocessDocumentResponse),
+        (status = 404, description = "Document not found", body = ApiError),
+        (status = 409, description = "Document is queued, being processed or dead-lettered", body = ApiError),
+    )
+)]
+pub async fn reprocess_document(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    body: Option<axum::Json<ReprocessDocumentRequest>>,
+) -> ApiResponse<ReprocessDocumentResponse> {
+    let req = body.map(|axum::Json(req)| req).unwrap_or_default();
+    // Only one caller can move the document out of `done`/`failed`, so a
+    // queued or in-flight document never gets a second pipeline run.
+    match state
+        .db
+        .queue_document_for_reprocess(&id, req.extract_memories)
+        .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            return match state.db.get_document_by_id(&id).await {
+                Ok(Some(_)) => ApiResponse::error(
+                    ErrorCode::Conflict,
+                    format!("Document {id} is queued or being processed"),
+                ),
+                Ok(None) => {
+                    ApiResponse::error(ErrorCode::NotFound, format!("Document {id} not found"))
+                }
+                Err(e) => e.into(),
+            }
+        }
+        Err(e) => return e.into(),
+    }
+
+    // Without a job the queued document is still picked up by the background
+    // processing loop.
+    let job = match JobTracker::create(state.db.clone(), JobKind::Reprocess, Some(1)).await {
+        Ok(job) => job,
+        Err(e) => return e.into(),
+    };
+    ApiResponse::accepted(process_in_background(state.pipeline.clone(), job, id))
+}
+
+/// Process an already queued document in the background, reporting the run
+/// on `job`.
+pub(super) fn process_in_background(
+    pipeline: ProcessingPipeline,
+    job: JobTracker,
+    doc_id: String,
+) -> ReprocessDocumentResponse {
+    let response = ReprocessDocumentResponse {
+        document_id: doc_id.clone(),
+        ingestion_id: doc_id.clone(),
+        job_id: job.id().to_string(),
+    };
+    tokio::spawn(async move {
+        job.start().await;
+        match pipeline.process_document(&doc_id).await {
+            Ok(_) => {
+                job.progress(1).await;
+                job.complete(serde_json::json!({ "documentId": doc_id }))
+                    .await;
+            }
+            Err(e) => {
+                tracing::error!(doc_id = %doc_id, error = %e, "Failed to reprocess document");
+                job.fail(&e.to_string()).await;
+            }
+        }
+    });
+    response
+}
+
+/// `GET /api/v1/documents/{documentId}/events`
+///
+/// Server-Sent Events stream of the document's processing status. The first
+/// `status` event carries the current status; one follows for every stage the
+/// pipeline enters. The stream closes after a final status, or once no
+/// status change arrives for `MOMO_DOCUMENT_EVENTS_IDLE_TIMEOUT_SECS`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/documents/{documentId}/events",
+    tag = "documents",
+    operation_id = "documents.events",
+    params(("documentId" = String, Path, description = "Document ID")),
+    responses(
+        (status = 200, description = "`status` events, one per processing stage", content_type = "text/event-stream", body = DocumentStatusEventResponse),
+        (status = 404, description = "Document not found", body = ApiError),
+    )
+)]
+pub async fn document_events(State(state): State<AppState>, Path(id): Path<String>) -> Response {
+    // Subscribe before reading the current status so no transition is missed
+    // in between.
+    let rx = state.status_events.subscribe();
+    let doc = match state.db.get_document_by_id(&id).await {
+        Ok(Some(doc)) => doc,
+        Ok(None) => {
+            return ApiResponse::<()>::error(
+                ErrorCode::NotFound,
+                format!("Document {id} not found"),
+            )
+            .into_response()
+        }
+        Err(e) => return ApiResponse::<()>::from(e).into_response(),
+    };
+
+    let idle = Duration::from_secs(state.config.server.document_events_idle_timeout_secs);
+    let events = futures::stream::unfold(
+        Some((Some(status_event(doc)), rx, state.db.clone(), id)),
+        move |stream_state| async move {
+            let (pending, mut rx, db, id) = stream_state?;
+            let event = match pending {
+                Some(event) => event,
+                None => next_status_event(&mut rx, db.as_ref(), &id, idle).await?,
+            };
+            let sse = Event::default().event("status").data(
+                serde_json::to_string(&DocumentStatusEventResponse::from(event.clone()))
+                    .unwrap_or_else(|_| "{}".to_string()),
+            );
+            let next = (!event.is_final()).then_some((None, rx, db, id));
+            Some((Ok::<_, Infallible>(sse), next))
+        },
+    );
+
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+fn status_event(doc: Document) -> DocumentStatusEvent {
+    DocumentStatusEvent {
+        document_id: doc.id,
+        status: doc.status,
+        error_message: doc.error_message,
+    }
+}
+
+/// Wait for the next status change of `doc_id`, or `None` once `idle` passes
+/// without one.
+async fn next_status_event(
+    rx: &mut broadcast::Receiver<DocumentStatusEvent>,
+    db: &dyn DatabaseBackend,
+    doc_id: &str,
+    idle: Duration,
+) -> Option<DocumentStatusEvent> {
+    let deadline = tokio::time::Instant::now() + idle;
+    loop {
+        match tokio::time::timeout_at(deadline, rx.recv()).await {
+            Ok(Ok(event)) if event.document_id == doc_id => return Some(event),
+            Ok(Ok(_)) => {}
+            // Events were dropped, possibly ours: report the stored status.
+            Ok(Err(RecvError::Lagged(_))) => match db.get_document_by_id(doc_id).await {
+                Ok(Some(doc)) => return Some(status_event(doc)),
+                Ok(None) => return None,
+                Err(e) => {
+                    tracing::warn!(doc_id, error = %e, "Failed to reload document status");
+                }
+            },
+            Ok(Err(RecvError::Closed)) | Err(_) => return None,
+        }
+    }
+}
+
+/// `PATCH /api/v1/documents/{documentId}/chunks/{chunkId}`
+///
+/// Replaces a chunk's text. Its embedding is cleared and regenerated by the
+/// background chunk re-embedding job; until then the chunk is left out of
+/// vector search.
+#[utoipa::path(
+    patch,
+    path = "/api/v1/documents/{documentId}/chunks/{chunkId}",
+    tag = "documents",
+    operation_id = "documents.chunks.update",
+    params(
+        ("documentId" = String, Path, description = "Document ID"),
+        ("chunkId" = String, Path, description = "Chunk ID"),
+    ),
+    request_body = UpdateChunkRequest,
+    responses(
+        (status = 200, description = "Chunk updated", body = ChunkResponse),
+        (status = 400, description = "Invalid request", body = ApiError),
+        (status = 404, description = "Chunk not found", body = ApiError),
+    )
+)]
+pub async fn update_chunk(
+    State(state): State<AppState>,
+    Path((document_id, chunk_id)): Path<(String, String)>,
+    axum::Json(req): axum::Json<UpdateChunkRequest>,
+) -> ApiResponse<ChunkResponse> {
+    if req.content.trim().is_empty() {
+        return ApiResponse::error(ErrorCode::InvalidRequest, "Content cannot be empty");
+    }
+
+    match state
+        .db
+        .update_chunk_content(&document_id, &chunk_id, &req.content)
+        .await
+    {
+        Ok(Some(chunk)) => ApiResponse::success(chunk.into()),
+        Ok(None) => ApiResponse::error(
+            ErrorCode::NotFound,
+            format!("Chunk {chunk_id} not found in document {document_id}"),
+        ),
+        Err(e) => e.into(),
+    }
+}
+
 /// `DELETE /api/v1/documents/{documentId}`
 ///
 /// Deletes a document by ID. Also tries custom_id as fallback.
@@ -567,6 +1286,7 @@ pub async fn delete_document(
 ) -> ApiResponse<serde_json::Value> {
     match state.db.delete_document(&id).await {
         Ok(true) => {
+            media_upload::remove_for_document(&state.config.transcription, &id).await;
             return ApiResponse::success(serde_json::json!({ "deleted": true }));
         }
         Ok(false) => {}
@@ -574,13 +1294,76 @@ pub async fn delete_document(
     }
 
     // Fallback: try custom_id
+    let doc_id = match state.db.get_document_by_custom_id(&id).await {
+        Ok(Some(doc)) => doc.id,
+        Ok(None) => {
+            return ApiResponse::error(ErrorCode::NotFound, format!("Document {id} not found"))
+        }
+        Err(e) => return e.into(),
+    };
     match state.db.delete_document_by_custom_id(&id).await {
-        Ok(true) => ApiResponse::success(serde_json::json!({ "deleted": true })),
+        Ok(true) => {
+            media_upload::remove_for_document(&state.config.transcription, &doc_id).await;
+            ApiResponse::success(serde_json::json!({ "deleted": true }))
+        }
         Ok(false) => ApiResponse::error(ErrorCode::NotFound, format!("Document {id} not found")),
         Err(e) => e.into(),
     }
 }
 
+/// `DELETE /api/v1/documents`
+///
+/// Deletes every document matching the `containerTag` query parameter (or the
+/// API key's default container tag) and/or the metadata conditions in the
+/// optional body, along with their chunks. At least one filter is required so
+/// a bare request cannot wipe everything.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/documents",
+    tag = "documents",
+    operation_id = "documents.deleteMany",
+    params(DeleteDocumentsQuery),
+    request_body(content = DeleteDocumentsRequest, description = "Optional metadata filter"),
+    responses(
+        (status = 200, description = "Matching documents deleted", body = DeleteDocumentsResponse),
+        (status = 400, description = "No filter provided or invalid filter", body = ApiError),
+    )
+)]
+pub async fn delete_documents(
+    State(state): State<AppState>,
+    default_tag: Option<Extension<DefaultContainerTag>>,
+    Query(query): Query<DeleteDocumentsQuery>,
+    body: Option<axum::Json<DeleteDocumentsRequest>>,
+) -> ApiResponse<DeleteDocumentsResponse> {
+    let body = body.map(|axum::Json(body)| body).unwrap_or_default();
+    let container_tag = match resolve_container_tag(query.container_tag, default_tag.as_ref()) {
+        Ok(tag) => tag,
+        Err(forbidden) => return forbidden.into(),
+    };
+    let filter = DocumentDeleteFilter {
+        container_tag,
+        metadata: body.metadata.unwrap_or_default(),
+    };
+    if filter.is_empty() {
+        return ApiResponse::error(
+            ErrorCode::InvalidRequest,
+            "Provide containerTag or a metadata filter; refusing to delete all documents",
+        );
+    }
+
+    match state.db.delete_documents_matching(&filter).await {
+        Ok(deleted_ids) => {
+            for id in &deleted_ids {
+                media_upload::remove_for_document(&state.config.transcription, id).await;
+            }
+            ApiResponse::success(DeleteDocumentsResponse {
+                deleted: deleted_ids.len() as u64,
+            })
+        }
+        Err(e) => e.into(),
+    }
+}
+
 /// `GET /api/v1/documents`
 ///
 /// Lists documents with cursor-based pagination. Supports filtering by
@@ -597,6 +1380,7 @@ pub async fn delete_document(
 )]
 pub async fn list_documents(
     State(state): State<AppState>,
+    default_tag: Option<Extension<DefaultContainerTag>>,
     Query(query): Query<ListDocumentsQuery>,
 ) -> ApiResponse<ListDocumentsResponse> {
     let limit = query.limit.unwrap_or(20).clamp(1, 100);
@@ -609,8 +1393,14 @@ pub async fn list_documents(
         .and_then(|c| c.parse::<u32>().ok())
         .unwrap_or(1);
 
+    let container_tags = match resolve_container_tags(query.container_tags, default_tag.as_ref()) {
+        Ok(tags) => tags,
+        Err(forbidden) => return forbidden.into(),
+    };
+
     let internal_req = crate::models::ListDocumentsRequest {
-        container_tags: query.container_tags,
+        container_tags,
+        category: query.category.filter(|category| !category.is_empty()),
         filters: None,
         limit: Some(limit),
         page: Some(page),
@@ -717,6 +1507,7 @@ mod tests {
         let resp = CreateDocumentResponse {
             document_id: "doc_123".to_string(),
             ingestion_id: "doc_123".to_string(),
+            near_duplicate_of: None,
         };
         let json = serde_json::to_value(&resp).expect("serialize");
         assert_eq!(json["documentId"], "doc_123");
@@ -4,15 +4,21 @@
 //! or across an entire container.
 
 use axum::extract::{Path, Query, State};
+use axum::http::{header, HeaderMap};
+use axum::response::{IntoResponse, Response};
+use axum::Extension;
 use serde::Deserialize;
 use serde_json::json;
 
-use crate::api::v1::dto::{ContainerTagsResponse, GraphResponse};
+use crate::api::v1::dto::{
+    ContainerTagsResponse, GraphCentrality, GraphDirection, GraphFormat, GraphResponse,
+};
+use crate::api::v1::middleware::{check_container_tag, DefaultContainerTag};
 use crate::api::v1::response::{ApiError, ApiResponse, ErrorCode};
 use crate::api::AppState;
 use crate::models::{
     GraphData, GraphEdgeType, GraphNode, GraphNodeType, GraphResponse as DomainGraphResponse,
-    Metadata,
+    MemoryType, Metadata,
 };
 
 /// Query parameters for `GET /api/v1/memories/{memoryId}/graph`.
@@ -25,6 +31,13 @@ pub struct MemoryGraphParams {
     pub max_nodes: Option<u32>,
     /// Comma-separated edge types to include (e.g. "updates,relatesto").
     pub relation_types: Option<String>,
+    /// Comma-separated memory types to traverse to and return (e.g.
+    /// "fact,preference"). The requested memory is always returned.
+    pub memory_types: Option<String>,
+    /// Which relations to follow from each memory (default: both).
+    pub direction: Option<GraphDirection>,
+    /// Response serialization; overrides the `Accept` header.
+    pub format: Option<GraphFormat>,
 }
 
 /// Query parameters for `GET /api/v1/containers/{tag}/graph`.
@@ -33,6 +46,65 @@ pub struct MemoryGraphParams {
 pub struct ContainerGraphParams {
     /// Maximum number of memory nodes to return (default: 100).
     pub max_nodes: Option<u32>,
+    /// Score each node's centrality, e.g. to size nodes in a UI. JSON only.
+    pub centrality: Option<GraphCentrality>,
+    /// Response serialization; overrides the `Accept` header.
+    pub format: Option<GraphFormat>,
+}
+
+/// Query parameters for `GET /api/v1/graph/path`.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphPathParams {
+    /// Memory the path starts from.
+    pub from: String,
+    /// Memory the path ends at.
+    pub to: String,
+    /// Maximum number of hops to search (default: 4, at most 10).
+    pub max_depth: Option<u32>,
+}
+
+const DEFAULT_PATH_DEPTH: u32 = 4;
+const MAX_PATH_DEPTH: u32 = 10;
+
+const DOT_MEDIA_TYPE: &str = "text/vnd.graphviz";
+const GEXF_MEDIA_TYPE: &str = "application/gexf+xml";
+
+/// Serialization requested with `format`, or else the first DOT or GEXF media
+/// type in the `Accept` header. Defaults to JSON.
+fn requested_format(format: Option<GraphFormat>, headers: &HeaderMap) -> GraphFormat {
+    if let Some(format) = format {
+        return format;
+    }
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .find_map(
+            |media_range| match media_range.split(';').next().map(str::trim) {
+                Some(DOT_MEDIA_TYPE) => Some(GraphFormat::Dot),
+                Some(GEXF_MEDIA_TYPE) => Some(GraphFormat::Gexf),
+                _ => None,
+            },
+        )
+        .unwrap_or_default()
+}
+
+/// `data` as a DOT or GEXF document, or `None` for JSON.
+fn export_graph(data: &GraphData, format: GraphFormat) -> Option<Response> {
+    let (media_type, body) = match format {
+        GraphFormat::Json => return None,
+        GraphFormat::Dot => (DOT_MEDIA_TYPE, data.to_dot()),
+        GraphFormat::Gexf => (GEXF_MEDIA_TYPE, data.to_gexf()),
+    };
+    Some(
+        (
+            [(header::CONTENT_TYPE, format!("{media_type}; charset=utf-8"))],
+            body,
+        )
+            .into_response(),
+    )
 }
 
 /// Convert repository [`GraphData`] into the domain [`DomainGraphResponse`].
@@ -97,6 +169,11 @@ fn parse_relation_types(input: &str) -> Vec<GraphEdgeType> {
         .collect()
 }
 
+/// Parse a comma-separated string of memory type names.
+fn parse_memory_types(input: &str) -> Result<Vec<MemoryType>, String> {
+    input.split(',').map(|s| s.trim().parse()).collect()
+}
+
 /// `GET /api/v1/memories/{memoryId}/graph`
 ///
 /// Returns the knowledge graph neighborhood around a specific memory.
@@ -110,7 +187,12 @@ fn parse_relation_types(input: &str) -> Vec<GraphEdgeType> {
         MemoryGraphParams,
     ),
     responses(
-        (status = 200, description = "Graph neighborhood", body = GraphResponse),
+        (status = 200, description = "Graph neighborhood", content(
+            (GraphResponse = "application/json"),
+            (String = "text/vnd.graphviz"),
+            (String = "application/gexf+xml"),
+        )),
+        (status = 400, description = "Unknown memory type", body = ApiError),
         (status = 404, description = "Memory not found", body = ApiError),
     )
 )]
@@ -118,14 +200,16 @@ pub async fn get_memory_graph(
     State(state): State<AppState>,
     Path(id): Path<String>,
     Query(params): Query<MemoryGraphParams>,
-) -> ApiResponse<GraphResponse> {
+    headers: HeaderMap,
+) -> Response {
     // Verify memory exists
     let _memory = match state.db.get_memory_by_id(&id).await {
         Ok(Some(mem)) => mem,
         Ok(None) => {
-            return ApiResponse::error(ErrorCode::NotFound, format!("Memory {id} not found"))
+            return ApiResponse::<()>::error(ErrorCode::NotFound, format!("Memory {id} not found"))
+                .into_response()
         }
-        Err(e) => return e.into(),
+        Err(e) => return ApiResponse::<()>::from(e).into_response(),
     };
 
     let depth = params.depth.unwrap_or(2);
@@ -133,18 +217,36 @@ pub async fn get_memory_graph(
 
     let types = params.relation_types.as_deref().map(parse_relation_types);
     let types_slice = types.as_deref();
+    let memory_types = match params.memory_types.as_deref().map(parse_memory_types) {
+        Some(Err(e)) => {
+            return ApiResponse::<()>::error(ErrorCode::InvalidRequest, e).into_response()
+        }
+        Some(Ok(memory_types)) => Some(memory_types),
+        None => None,
+    };
+    let direction = params.direction.unwrap_or_default().into();
 
     let graph_data = match state
         .db
-        .get_graph_neighborhood(&id, depth, max_nodes, types_slice)
+        .get_graph_neighborhood(
+            &id,
+            depth,
+            max_nodes,
+            types_slice,
+            memory_types.as_deref(),
+            direction,
+        )
         .await
     {
         Ok(data) => data,
-        Err(e) => return e.into(),
+        Err(e) => return ApiResponse::<()>::from(e).into_response(),
     };
 
+    if let Some(export) = export_graph(&graph_data, requested_format(params.format, &headers)) {
+        return export;
+    }
     let domain_response = graph_data_to_response(graph_data);
-    ApiResponse::success(domain_response.into())
+    ApiResponse::<GraphResponse>::success(domain_response.into()).into_response()
 }
 
 /// `GET /api/v1/containers/{tag}/graph`
@@ -160,18 +262,93 @@ pub async fn get_memory_graph(
         ContainerGraphParams,
     ),
     responses(
-        (status = 200, description = "Container graph", body = GraphResponse),
+        (status = 200, description = "Container graph", content(
+            (GraphResponse = "application/json"),
+            (String = "text/vnd.graphviz"),
+            (String = "application/gexf+xml"),
+        )),
     )
 )]
 pub async fn get_container_graph(
     State(state): State<AppState>,
+    default_tag: Option<Extension<DefaultContainerTag>>,
     Path(tag): Path<String>,
     Query(params): Query<ContainerGraphParams>,
-) -> ApiResponse<GraphResponse> {
+    headers: HeaderMap,
+) -> Response {
+    if let Err(forbidden) = check_container_tag(&tag, default_tag.as_ref()) {
+        return ApiResponse::<()>::from(forbidden).into_response();
+    }
     let max_nodes = params.max_nodes.unwrap_or(100);
 
     let graph_data = match state.db.get_container_graph(&tag, max_nodes).await {
         Ok(data) => data,
+        Err(e) => return ApiResponse::<()>::from(e).into_response(),
+    };
+
+    if let Some(export) = export_graph(&graph_data, requested_format(params.format, &headers)) {
+        return export;
+    }
+    let mut domain_response = graph_data_to_response(graph_data);
+    if let Some(centrality) = params.centrality {
+        domain_response.score_centrality(centrality.into());
+    }
+    ApiResponse::<GraphResponse>::success(domain_response.into()).into_response()
+}
+
+/// `GET /api/v1/graph/path`
+///
+/// Returns the shortest chain of relations between two memories, with the
+/// memories in path order.
+#[utoipa::path(
+    get,
+    path = "/api/v1/graph/path",
+    tag = "graph",
+    operation_id = "graph.findPath",
+    params(GraphPathParams),
+    responses(
+        (status = 200, description = "Shortest path", body = GraphResponse),
+        (status = 400, description = "maxDepth out of range", body = ApiError),
+        (status = 404, description = "Memory not found, or no path within maxDepth", body = ApiError),
+    )
+)]
+pub async fn find_memory_path(
+    State(state): State<AppState>,
+    Query(params): Query<GraphPathParams>,
+) -> ApiResponse<GraphResponse> {
+    let max_depth = params.max_depth.unwrap_or(DEFAULT_PATH_DEPTH);
+    if max_depth > MAX_PATH_DEPTH {
+        return ApiResponse::error(
+            ErrorCode::InvalidRequest,
+            format!("maxDepth must be at most {MAX_PATH_DEPTH}"),
+        );
+    }
+
+    for id in [&params.from, &params.to] {
+        match state.db.get_memory_by_id(id).await {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                return ApiResponse::error(ErrorCode::NotFound, format!("Memory {id} not found"))
+            }
+            Err(e) => return e.into(),
+        }
+    }
+
+    let graph_data = match state
+        .db
+        .find_memory_path(&params.from, &params.to, max_depth)
+        .await
+    {
+        Ok(Some(data)) => data,
+        Ok(None) => {
+            return ApiResponse::error(
+                ErrorCode::NotFound,
+                format!(
+                    "No path from {} to {} within depth {max_depth}",
+                    params.from, params.to
+                ),
+            )
+        }
         Err(e) => return e.into(),
     };
 
@@ -269,6 +446,29 @@ mod tests {
         assert_eq!(params.relation_types.as_deref(), Some("updates,sources"));
     }
 
+    #[test]
+    fn memory_graph_params_deserializes_memory_types_and_direction() {
+        let json = r#"{"memoryTypes": "fact, Episode", "direction": "inbound"}"#;
+        let params: MemoryGraphParams = serde_json::from_str(json).expect("deserialize");
+        assert_eq!(params.direction, Some(GraphDirection::Inbound));
+        assert_eq!(
+            parse_memory_types(params.memory_types.as_deref().unwrap()),
+            Ok(vec![MemoryType::Fact, MemoryType::Episode])
+        );
+        assert!(parse_memory_types("fact,bogus").is_err());
+    }
+
+    #[test]
+    fn graph_path_params_deserializes_max_depth() {
+        let json = r#"{"from": "mem_a", "to": "mem_b", "maxDepth": 6}"#;
+        let params: GraphPathParams = serde_json::from_str(json).expect("deserialize");
+        assert_eq!(params.from, "mem_a");
+        assert_eq!(params.to, "mem_b");
+        assert_eq!(params.max_depth, Some(6));
+
+        assert!(serde_json::from_str::<GraphPathParams>(r#"{"from": "mem_a"}"#).is_err());
+    }
+
     #[test]
     fn container_graph_params_deserializes_defaults() {
         let json = r#"{}"#;
@@ -283,6 +483,32 @@ mod tests {
         assert_eq!(params.max_nodes, Some(200));
     }
 
+    #[test]
+    fn container_graph_params_deserializes_centrality() {
+        let json = r#"{"centrality": "pageRank"}"#;
+        let params: ContainerGraphParams = serde_json::from_str(json).expect("deserialize");
+        assert_eq!(params.centrality, Some(GraphCentrality::PageRank));
+    }
+
+    #[test]
+    fn requested_format_prefers_query_over_accept() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(requested_format(None, &headers), GraphFormat::Json);
+
+        headers.insert(
+            header::ACCEPT,
+            "text/html, application/gexf+xml;q=0.9".parse().unwrap(),
+        );
+        assert_eq!(requested_format(None, &headers), GraphFormat::Gexf);
+        assert_eq!(
+            requested_format(Some(GraphFormat::Dot), &headers),
+            GraphFormat::Dot
+        );
+
+        headers.insert(header::ACCEPT, "text/vnd.graphviz".parse().unwrap());
+        assert_eq!(requested_format(None, &headers), GraphFormat::Dot);
+    }
+
     #[test]
     fn graph_data_to_response_converts_memories() {
         let mut memory = crate::models::Memory::new(
@@ -1,24 +1,46 @@
 use axum::extract::State;
-use serde::Serialize;
+use axum_extra::extract::Query;
+use serde::{Deserialize, Serialize};
 
-use crate::api::state::AppState;
+use crate::api::state::{AppState, ReadinessPhase};
 use crate::api::v1::response::ApiResponse;
 use crate::llm::LlmBackend;
 
+/// Text embedded by the deep health check.
+const EMBEDDING_PROBE_TEXT: &str = "health check";
+
+#[derive(Debug, Clone, Default, Deserialize, utoipa::IntoParams)]
+#[serde(default)]
+pub struct HealthQuery {
+    /// Probe the database, embedding model and keyword index instead of only
+    /// reporting configuration.
+    pub deep: bool,
+}
+
 /// Health data returned inside the v1 envelope.
 #[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct HealthData {
+    /// `ok`, `degraded` when a non-critical subsystem failed its probe, or
+    /// `unavailable` when the database or embedding model did.
     pub status: String,
     pub version: String,
     pub database: DatabaseStatus,
     pub embeddings: EmbeddingsStatus,
     pub llm: LlmStatus,
     pub reranker: RerankerStatus,
+    /// Full-text index used by keyword search. Only reported by deep checks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keyword_index: Option<KeywordIndexStatus>,
 }
 
+/// Subsystem status. Shallow checks report `unchecked` for anything they
+/// would have to probe.
 #[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
 pub struct DatabaseStatus {
     pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
@@ -26,6 +48,16 @@ pub struct EmbeddingsStatus {
     pub status: String,
     pub model: String,
     pub dimensions: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct KeywordIndexStatus {
+    /// `ok`, `missing` when the index tables don't exist, or `error`.
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
@@ -46,30 +78,80 @@ pub struct RerankerStatus {
 }
 
 /// `GET /api/v1/health`
+///
+/// The shallow check only reports configuration and always returns 200, for
+/// liveness probes. With `deep=true` the database, embedding model and keyword
+/// index are probed, and a failed database or embedding probe returns 503.
 #[utoipa::path(
     get,
     path = "/api/v1/health",
     tag = "health",
     operation_id = "health.get",
+    params(HealthQuery),
     responses(
         (status = 200, description = "Service health status", body = HealthData),
+        (status = 503, description = "A critical subsystem failed its deep probe", body = HealthData),
     )
 )]
-pub async fn health_check(State(state): State<AppState>) -> ApiResponse<HealthData> {
-    let db_status = match state.db.sync().await {
-        Ok(_) => DatabaseStatus {
-            status: "ok".to_string(),
-        },
-        Err(_) => DatabaseStatus {
-            status: "error".to_string(),
-        },
+pub async fn health_check(
+    State(state): State<AppState>,
+    Query(query): Query<HealthQuery>,
+) -> ApiResponse<HealthData> {
+    let unchecked = || "unchecked".to_string();
+    let mut db_status = DatabaseStatus {
+        status: unchecked(),
+        error: None,
     };
-
-    let embeddings_status = EmbeddingsStatus {
-        status: "ok".to_string(),
+    let mut embeddings_status = EmbeddingsStatus {
+        status: unchecked(),
         model: state.config.embeddings.model.clone(),
         dimensions: state.embeddings.dimensions(),
+        error: None,
     };
+    let mut keyword_index = None;
+
+    if query.deep {
+        db_status = match state.db.ping().await {
+            Ok(()) => DatabaseStatus {
+                status: "ok".to_string(),
+                error: None,
+            },
+            Err(e) => DatabaseStatus {
+                status: "error".to_string(),
+                error: Some(e.to_string()),
+            },
+        };
+
+        let (status, error) = match state.embeddings.embed_query(EMBEDDING_PROBE_TEXT).await {
+            Ok(embedding) if embedding.len() == embeddings_status.dimensions => ("ok", None),
+            Ok(embedding) => (
+                "error",
+                Some(format!(
+                    "Expected {} dimensions, got {}",
+                    embeddings_status.dimensions,
+                    embedding.len()
+                )),
+            ),
+            Err(e) => ("error", Some(e.to_string())),
+        };
+        embeddings_status.status = status.to_string();
+        embeddings_status.error = error;
+
+        keyword_index = Some(match state.db.keyword_indexes_ready().await {
+            Ok(true) => KeywordIndexStatus {
+                status: "ok".to_string(),
+                error: None,
+            },
+            Ok(false) => KeywordIndexStatus {
+                status: "missing".to_string(),
+                error: None,
+            },
+            Err(e) => KeywordIndexStatus {
+                status: "error".to_string(),
+                error: Some(e.to_string()),
+            },
+        });
+    }
 
     let llm_status = if state.llm.is_available() {
         let provider = match state.llm.backend() {
@@ -124,12 +206,80 @@ pub async fn health_check(State(state): State<AppState>) -> ApiResponse<HealthDa
         },
     };
 
-    ApiResponse::success(HealthData {
-        status: "ok".to_string(),
+    let critical_down = db_status.status == "error" || embeddings_status.status == "error";
+    let degraded = keyword_index
+        .as_ref()
+        .is_some_and(|index| index.status != "ok");
+    let status = if critical_down {
+        "unavailable"
+    } else if degraded {
+        "degraded"
+    } else {
+        "ok"
+    };
+
+    let data = HealthData {
+        status: status.to_string(),
         version: env!("CARGO_PKG_VERSION").to_string(),
         database: db_status,
         embeddings: embeddings_status,
         llm: llm_status,
         reranker: reranker_status,
+        keyword_index,
+    };
+    if critical_down {
+        ApiResponse::unavailable(data)
+    } else {
+        ApiResponse::success(data)
+    }
+}
+
+/// Process lifecycle status for the liveness and readiness probes.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct ProbeStatus {
+    /// `alive` for liveness; `starting`, `ready` or `shutting_down` for
+    /// readiness.
+    pub status: String,
+}
+
+/// `GET /api/v1/health/live`
+///
+/// Always 200 while the process can answer requests.
+#[utoipa::path(
+    get,
+    path = "/api/v1/health/live",
+    tag = "health",
+    operation_id = "health.live",
+    responses(
+        (status = 200, description = "The process is running", body = ProbeStatus),
+    )
+)]
+pub async fn liveness() -> ApiResponse<ProbeStatus> {
+    ApiResponse::success(ProbeStatus {
+        status: "alive".to_string(),
     })
 }
+
+/// `GET /api/v1/health/ready`
+///
+/// 200 once startup has finished, 503 while starting or shutting down.
+#[utoipa::path(
+    get,
+    path = "/api/v1/health/ready",
+    tag = "health",
+    operation_id = "health.ready",
+    responses(
+        (status = 200, description = "Ready to serve traffic", body = ProbeStatus),
+        (status = 503, description = "Starting up or shutting down", body = ProbeStatus),
+    )
+)]
+pub async fn readiness(State(state): State<AppState>) -> ApiResponse<ProbeStatus> {
+    let phase = state.readiness.phase();
+    let data = ProbeStatus {
+        status: phase.as_str().to_string(),
+    };
+    match phase {
+        ReadinessPhase::Ready => ApiResponse::success(data),
+        ReadinessPhase::Starting | ReadinessPhase::ShuttingDown => ApiResponse::unavailable(data),
+    }
+}
@@ -0,0 +1,34 @@
+//! v1 Job handlers.
+
+use axum::extract::{Path, State};
+
+use crate::api::v1::dto::JobResponse;
+use crate::api::v1::response::{ApiError, ApiResponse, ErrorCode};
+use crate::api::AppState;
+
+/// `GET /api/v1/jobs/{jobId}`
+///
+/// Reports the status, progress and result of a long-running operation
+/// started by a job-producing endpoint.
+#[utoipa::path(
+    get,
+    path = "/api/v1/jobs/{jobId}",
+    tag = "jobs",
+    operation_id = "jobs.get",
+    params(("jobId" = String, Path, description = "Job ID")),
+    responses(
+        (status = 200, description = "Job found", body = JobResponse),
+        (status = 404, description = "Job not found", body = ApiError),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_job(
+    State(state): State<AppState>,
+    Path(job_id): Path<String>,
+) -> ApiResponse<JobResponse> {
+    match state.db.get_job(&job_id).await {
+        Ok(Some(job)) => ApiResponse::success(job.into()),
+        Ok(None) => ApiResponse::error(ErrorCode::NotFound, format!("Job {job_id} not found")),
+        Err(e) => e.into(),
+    }
+}
@@ -1,13 +1,16 @@
 //! v1 Memory handlers.
 
 use axum::extract::{Path, State};
+use axum::Extension;
 use axum_extra::extract::Query;
+use chrono::{DateTime, Utc};
 
 use crate::api::v1::dto::{
     ContentForgetRequest, CreateMemoryRequest, ForgetMemoryRequest, ForgetMemoryResponse,
     ListMemoriesQuery, ListMemoriesResponse, MemoryResponse, UpdateMemoryRequest,
     UpdateMemoryResponse,
 };
+use crate::api::v1::middleware::{resolve_container_tag, DefaultContainerTag};
 use crate::api::v1::response::{ApiError, ApiResponse, ErrorCode, ResponseMeta};
 use crate::api::AppState;
 use crate::models::MemoryType;
@@ -26,21 +29,60 @@ use crate::models::MemoryType;
 )]
 pub async fn create_memory(
     State(state): State<AppState>,
+    default_tag: Option<Extension<DefaultContainerTag>>,
     axum::Json(req): axum::Json<CreateMemoryRequest>,
 ) -> ApiResponse<MemoryResponse> {
     if req.content.trim().is_empty() {
         return ApiResponse::error(ErrorCode::InvalidRequest, "Content cannot be empty");
     }
 
-    if req.container_tag.trim().is_empty() {
-        return ApiResponse::error(ErrorCode::InvalidRequest, "Container tag cannot be empty");
+    let container_tag = match resolve_container_tag(req.container_tag, default_tag.as_ref()) {
+        Ok(Some(tag)) => tag,
+        Ok(None) => {
+            return ApiResponse::error(ErrorCode::InvalidRequest, "Container tag cannot be empty")
+        }
+        Err(forbidden) => return forbidden.into(),
+    };
+
+    if req.confidence.is_some_and(|c| !(0.0..=1.0).contains(&c)) {
+        return ApiResponse::error(
+            ErrorCode::InvalidRequest,
+            "Confidence must be between 0.0 and 1.0",
+        );
     }
 
+    let forget_after = match (req.forget_after, req.ttl_days) {
+        (Some(_), Some(_)) => {
+            return ApiResponse::error(
+                ErrorCode::InvalidRequest,
+                "Set either forgetAfter or ttlDays, not both",
+            )
+        }
+        (Some(at), None) => Some(at),
+        (None, Some(days)) => match ttl_expiry(days) {
+            Some(at) => Some(at),
+            None => {
+                return ApiResponse::error(
+                    ErrorCode::InvalidRequest,
+                    "ttlDays must be a positive number of days",
+                )
+            }
+        },
+        (None, None) => None,
+    };
+
     let memory_type: MemoryType = req.memory_type.map(Into::into).unwrap_or(MemoryType::Fact);
 
     let memory = match state
         .memory
-        .create_memory_with_type(&req.content, &req.container_tag, false, memory_type)
+        .create_memory_with_type(
+            &req.content,
+            &container_tag,
+            req.is_static.unwrap_or(false),
+            memory_type,
+            req.confidence,
+            forget_after,
+        )
         .await
     {
         Ok(mut mem) => {
@@ -64,6 +106,18 @@ pub async fn create_memory(
     ApiResponse::created(MemoryResponse::from(memory))
 }
 
+/// Expiry `days` from now, or `None` when `days` isn't a usable TTL.
+fn ttl_expiry(days: f64) -> Option<DateTime<Utc>> {
+    if !(days.is_finite() && days > 0.0) {
+        return None;
+    }
+    let millis = days * 86_400_000.0;
+    if millis >= i64::MAX as f64 {
+        return None;
+    }
+    Utc::now().checked_add_signed(chrono::Duration::try_milliseconds(millis as i64)?)
+}
+
 /// `GET /api/v1/memories/{memoryId}`
 #[utoipa::path(
     get,
@@ -98,6 +152,7 @@ pub async fn get_memory(
     responses(
         (status = 200, description = "Memory updated", body = UpdateMemoryResponse),
         (status = 404, description = "Memory not found", body = ApiError),
+        (status = 409, description = "Memory was superseded since `version`, or has been forgotten", body = ApiError),
     )
 )]
 pub async fn update_memory(
@@ -125,6 +180,7 @@ pub async fn update_memory(
         new_content: req.content,
         metadata: req.metadata,
         is_static: req.is_static,
+        expected_version: req.version,
     };
 
     match state.memory.update_memory(internal_req).await {
@@ -179,6 +235,31 @@ pub async fn delete_memory(
     }
 }
 
+/// `POST /api/v1/memories/{memoryId}/restore`
+///
+/// Undo forgetting: the memory is included in search again and is no longer
+/// scheduled to expire.
+#[utoipa::path(
+    post,
+    path = "/api/v1/memories/{memoryId}/restore",
+    tag = "memories",
+    operation_id = "memories.restore",
+    params(("memoryId" = String, Path, description = "Memory ID")),
+    responses(
+        (status = 200, description = "Memory restored", body = MemoryResponse),
+        (status = 404, description = "Memory not found", body = ApiError),
+    )
+)]
+pub async fn restore_memory(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResponse<MemoryResponse> {
+    match state.memory.restore_memory(&id).await {
+        Ok(mem) => ApiResponse::success(MemoryResponse::from(mem)),
+        Err(e) => e.into(),
+    }
+}
+
 /// `GET /api/v1/memories`
 ///
 /// Lists memories with cursor-based pagination. Requires `containerTag` query
@@ -196,16 +277,18 @@ pub async fn delete_memory(
 )]
 pub async fn list_memories(
     State(state): State<AppState>,
+    default_tag: Option<Extension<DefaultContainerTag>>,
     Query(query): Query<ListMemoriesQuery>,
 ) -> ApiResponse<ListMemoriesResponse> {
-    let container_tag = match query.container_tag {
-        Some(ref tag) if !tag.is_empty() => tag.clone(),
-        _ => {
+    let container_tag = match resolve_container_tag(query.container_tag, default_tag.as_ref()) {
+        Ok(Some(tag)) => tag,
+        Ok(None) => {
             return ApiResponse::error(
                 ErrorCode::InvalidRequest,
                 "containerTag query parameter is required",
             );
         }
+        Err(forbidden) => return forbidden.into(),
     };
 
     let limit = query.limit.unwrap_or(20).clamp(1, 100);
@@ -275,20 +358,25 @@ pub async fn list_memories(
 )]
 pub async fn forget_memory(
     State(state): State<AppState>,
+    default_tag: Option<Extension<DefaultContainerTag>>,
     axum::Json(req): axum::Json<ContentForgetRequest>,
 ) -> ApiResponse<ForgetMemoryResponse> {
     if req.content.trim().is_empty() {
         return ApiResponse::error(ErrorCode::InvalidRequest, "Content cannot be empty");
     }
 
-    if req.container_tag.trim().is_empty() {
-        return ApiResponse::error(ErrorCode::InvalidRequest, "Container tag cannot be empty");
-    }
+    let container_tag = match resolve_container_tag(req.container_tag, default_tag.as_ref()) {
+        Ok(Some(tag)) => tag,
+        Ok(None) => {
+            return ApiResponse::error(ErrorCode::InvalidRequest, "Container tag cannot be empty")
+        }
+        Err(forbidden) => return forbidden.into(),
+    };
 
     let internal_req = crate::models::ForgetMemoryRequest {
         id: None,
         content: Some(req.content),
-        container_tag: req.container_tag,
+        container_tag,
         reason: req.reason,
     };
 
@@ -348,10 +436,32 @@ mod tests {
         let json = r#"{"content":"old preference","containerTag":"user_1","reason":"changed"}"#;
         let req: ContentForgetRequest = serde_json::from_str(json).expect("deserialize");
         assert_eq!(req.content, "old preference");
-        assert_eq!(req.container_tag, "user_1");
+        assert_eq!(req.container_tag.as_deref(), Some("user_1"));
         assert_eq!(req.reason, Some("changed".to_string()));
     }
 
+    #[test]
+    fn create_memory_request_accepts_expiry() {
+        let json =
+            r#"{"content":"Meeting at 3pm","forgetAfter":"2030-01-01T15:00:00Z","ttlDays":1.5}"#;
+        let req: CreateMemoryRequest = serde_json::from_str(json).expect("deserialize");
+        assert_eq!(
+            req.forget_after.map(|at| at.to_rfc3339()),
+            Some("2030-01-01T15:00:00+00:00".to_string())
+        );
+        assert_eq!(req.ttl_days, Some(1.5));
+    }
+
+    #[test]
+    fn ttl_expiry_rejects_unusable_durations() {
+        let expiry = ttl_expiry(1.0).expect("one day");
+        let hours = (expiry - Utc::now()).num_minutes() as f64 / 60.0;
+        assert!((hours - 24.0).abs() < 0.1);
+        for days in [0.0, -1.0, f64::NAN, f64::INFINITY, 1e300] {
+            assert!(ttl_expiry(days).is_none(), "{days}");
+        }
+    }
+
     #[test]
     fn list_memories_query_defaults() {
         let json = r#"{}"#;
@@ -3,8 +3,9 @@ pub mod conversation;
 pub mod documents;
 pub mod graph;
 pub(crate) mod health;
+pub mod jobs;
 pub mod memories;
 pub mod profile;
 pub mod search;
 
-pub use health::health_check;
+pub use health::{health_check, liveness, readiness};
@@ -1,11 +1,31 @@
 //! v1 Profile handlers.
 
-use axum::extract::State;
+use axum::extract::{Path, Query, State};
+use axum::Extension;
 
-use crate::api::v1::dto::profile::{ComputeProfileRequest, ProfileResponse};
+use crate::api::v1::dto::profile::{
+    ComputeProfileRequest, GetProfileQuery, ProfileFactSort, ProfileResponse,
+};
+use crate::api::v1::middleware::{check_container_tag, DefaultContainerTag};
 use crate::api::v1::response::{ApiError, ApiResponse, ErrorCode};
 use crate::api::AppState;
-use crate::models::GetProfileRequest;
+use crate::models::{GetProfileRequest, ProfileFactOptions};
+
+/// Fact filtering and ordering for a profile request.
+fn fact_options(
+    state: &AppState,
+    min_confidence: Option<f64>,
+    sort: Option<ProfileFactSort>,
+) -> Result<ProfileFactOptions, &'static str> {
+    if min_confidence.is_some_and(|min| !(0.0..=1.0).contains(&min)) {
+        return Err("minConfidence must be between 0 and 1");
+    }
+    Ok(ProfileFactOptions {
+        min_confidence,
+        exclude_unscored: state.config.memory.profile_exclude_unscored_facts,
+        sort: sort.map(Into::into).unwrap_or_default(),
+    })
+}
 
 /// `POST /api/v1/profile:compute`
 ///
@@ -26,11 +46,19 @@ use crate::models::GetProfileRequest;
 )]
 pub async fn compute_profile(
     State(state): State<AppState>,
+    default_tag: Option<Extension<DefaultContainerTag>>,
     axum::Json(req): axum::Json<ComputeProfileRequest>,
 ) -> ApiResponse<ProfileResponse> {
     if req.container_tag.trim().is_empty() {
         return ApiResponse::error(ErrorCode::InvalidRequest, "Container tag cannot be empty");
     }
+    if let Err(forbidden) = check_container_tag(&req.container_tag, default_tag.as_ref()) {
+        return forbidden.into();
+    }
+    let options = match fact_options(&state, req.min_confidence, req.sort) {
+        Ok(options) => options,
+        Err(msg) => return ApiResponse::error(ErrorCode::InvalidRequest, msg),
+    };
 
     // Fetch the full UserProfile (with ProfileFact details: confidence, created_at)
     // directly from the DB, matching the pattern used by list_memories.
@@ -39,7 +67,7 @@ pub async fn compute_profile(
 
     let mut profile = match state
         .db
-        .get_user_profile(&req.container_tag, include_dynamic, limit)
+        .get_user_profile(&req.container_tag, include_dynamic, limit, &options)
         .await
     {
         Ok(p) => p,
@@ -74,6 +102,54 @@ pub async fn compute_profile(
     ApiResponse::success(ProfileResponse::from(profile))
 }
 
+/// `GET /api/v1/profile/{containerTag}`
+///
+/// Returns a profile with its cached narrative. With `refresh=true` the
+/// narrative is regenerated from current memories and cached before
+/// returning.
+#[utoipa::path(
+    get,
+    path = "/api/v1/profile/{containerTag}",
+    tag = "profile",
+    operation_id = "profile.get",
+    params(
+        ("containerTag" = String, Path, description = "Container tag"),
+        GetProfileQuery,
+    ),
+    responses(
+        (status = 200, description = "Profile", body = ProfileResponse),
+        (status = 400, description = "Invalid request", body = ApiError),
+    )
+)]
+pub async fn get_profile(
+    State(state): State<AppState>,
+    default_tag: Option<Extension<DefaultContainerTag>>,
+    Path(container_tag): Path<String>,
+    Query(query): Query<GetProfileQuery>,
+) -> ApiResponse<ProfileResponse> {
+    if let Err(forbidden) = check_container_tag(&container_tag, default_tag.as_ref()) {
+        return forbidden.into();
+    }
+    let options = match fact_options(&state, query.min_confidence, query.sort) {
+        Ok(options) => options,
+        Err(msg) => return ApiResponse::error(ErrorCode::InvalidRequest, msg),
+    };
+    match state
+        .memory
+        .get_profile_narrative(
+            &container_tag,
+            query.include_dynamic.unwrap_or(true),
+            query.limit.unwrap_or(50),
+            &options,
+            query.refresh.unwrap_or(false),
+        )
+        .await
+    {
+        Ok(profile) => ApiResponse::success(ProfileResponse::from(profile)),
+        Err(e) => e.into(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -93,6 +169,22 @@ mod tests {
         assert_eq!(req.generate_narrative, Some(true));
     }
 
+    #[test]
+    fn get_profile_query_deserializes() {
+        let query: GetProfileQuery =
+            serde_json::from_str(r#"{"refresh": true, "limit": 10}"#).expect("deserialize");
+        assert_eq!(query.refresh, Some(true));
+        assert_eq!(query.limit, Some(10));
+        assert!(query.include_dynamic.is_none());
+        assert!(query.sort.is_none());
+
+        let query: GetProfileQuery =
+            serde_json::from_str(r#"{"minConfidence": 0.8, "sort": "confidence"}"#)
+                .expect("deserialize");
+        assert_eq!(query.min_confidence, Some(0.8));
+        assert_eq!(query.sort, Some(ProfileFactSort::Confidence));
+    }
+
     #[test]
     fn compute_profile_request_minimal() {
         let json = r#"{"containerTag": "user_2"}"#;
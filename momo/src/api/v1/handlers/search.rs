@@ -3,19 +3,38 @@
 //! Implements `POST /api/v1/search` with unified `scope` parameter to
 //! search documents, memories, or both (hybrid).
 
-use axum::extract::State;
+use axum::body::Body;
+use axum::extract::{Path, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use axum::Extension;
+use axum_extra::extract::Query;
+use futures::StreamExt;
+use std::collections::HashMap;
+use std::convert::Infallible;
 use std::time::Instant;
+use tokio::sync::mpsc;
+use tokio::task::JoinError;
 
+use crate::api::v1::dto::common::V1ChunkerType;
 use crate::api::v1::dto::{
-    DocumentSearchResult as V1DocumentSearchResult, HybridSearchResultResponse,
-    MemorySearchResult as V1MemorySearchResult, SearchRequest, SearchResponse, SearchResultItem,
-    SearchScope,
+    BatchSearchRequest, BatchSearchResponse, DocumentSearchResult as V1DocumentSearchResult,
+    HybridSearchResultResponse, HybridWeights, MemorySearchResult as V1MemorySearchResult,
+    SearchGroupBy, SearchGroupType, SearchRequest, SearchResponse, SearchResultGroup,
+    SearchResultItem, SearchScope, SearchStreamEnd, SimilarMemoriesQuery, StreamSearchRequest,
+};
+use crate::api::v1::middleware::{
+    check_container_tag, resolve_container_tag, resolve_container_tags, DefaultContainerTag,
 };
 use crate::api::v1::response::{ApiError, ApiResponse};
 use crate::api::AppState;
+use crate::error::Result;
 use crate::models::{
-    HybridSearchRequest, SearchDocumentsRequest, SearchMemoriesRequest, SearchMode,
+    ChunkerType, FilterCondition, HybridSearchRequest, SearchDocumentsRequest, SearchFallback,
+    SearchFilters, SearchIncludeOptions, SearchMemoriesRequest, SearchStage, SearchStreamSummary,
+    CATEGORY_METADATA_KEY,
 };
+use crate::services::{MAX_BATCH_QUERIES, MAX_CONTEXT_CHUNKS};
 
 /// `POST /api/v1/search`
 ///
@@ -36,7 +55,8 @@ use crate::models::{
 )]
 pub async fn search(
     State(state): State<AppState>,
-    axum::Json(req): axum::Json<SearchRequest>,
+    default_tag: Option<Extension<DefaultContainerTag>>,
+    axum::Json(mut req): axum::Json<SearchRequest>,
 ) -> ApiResponse<SearchResponse> {
     // Validate query
     if req.q.trim().is_empty() {
@@ -46,13 +66,336 @@ pub async fn search(
         );
     }
 
+    if req.context_chunks.is_some_and(|n| n > MAX_CONTEXT_CHUNKS) {
+        return ApiResponse::error(
+            crate::api::v1::response::ErrorCode::InvalidRequest,
+            format!("contextChunks must be at most {MAX_CONTEXT_CHUNKS}"),
+        );
+    }
+
+    if req.embedding_model.is_some() && req.scope != SearchScope::Memories {
+        return ApiResponse::error(
+            crate::api::v1::response::ErrorCode::InvalidRequest,
+            "embeddingModel is only supported with scope \"memories\"",
+        );
+    }
+
+    req.container_tags = match resolve_container_tags(req.container_tags, default_tag.as_ref()) {
+        Ok(tags) => tags,
+        Err(forbidden) => return forbidden.into(),
+    };
+
     let start = Instant::now();
 
     match req.scope {
         SearchScope::Documents => search_documents(&state, &req, start).await,
         SearchScope::Memories => search_memories(&state, &req, start).await,
-        SearchScope::Hybrid => search_hybrid(&state, &req, start).await,
+        SearchScope::Hybrid | SearchScope::Keyword => search_hybrid(&state, &req, start).await,
+    }
+}
+
+/// `POST /api/v1/search/batch`
+///
+/// Runs up to [`MAX_BATCH_QUERIES`] memory searches in one request, sharing a
+/// single embedding call. Responses are returned in query order, each with
+/// its own timing.
+#[utoipa::path(
+    post,
+    path = "/api/v1/search/batch",
+    tag = "search",
+    operation_id = "search.batch",
+    request_body = BatchSearchRequest,
+    responses(
+        (status = 200, description = "One search response per query", body = BatchSearchResponse),
+        (status = 400, description = "Invalid request", body = ApiError),
+    )
+)]
+pub async fn batch_search(
+    State(state): State<AppState>,
+    default_tag: Option<Extension<DefaultContainerTag>>,
+    axum::Json(req): axum::Json<BatchSearchRequest>,
+) -> ApiResponse<BatchSearchResponse> {
+    if req.queries.len() > MAX_BATCH_QUERIES {
+        return ApiResponse::error(
+            crate::api::v1::response::ErrorCode::InvalidRequest,
+            format!("queries can hold at most {MAX_BATCH_QUERIES} searches"),
+        );
+    }
+    if req.queries.iter().any(|query| query.q.trim().is_empty()) {
+        return ApiResponse::error(
+            crate::api::v1::response::ErrorCode::InvalidRequest,
+            "Query cannot be empty",
+        );
+    }
+
+    let mut internal_reqs = Vec::with_capacity(req.queries.len());
+    for query in req.queries {
+        let container_tag = match resolve_container_tag(query.container_tag, default_tag.as_ref()) {
+            Ok(tag) => tag,
+            Err(forbidden) => return forbidden.into(),
+        };
+        internal_reqs.push(SearchMemoriesRequest {
+            q: query.q,
+            container_tag,
+            threshold: query.threshold,
+            limit: query.limit,
+            rerank: query.rerank,
+            language: query.language,
+            embedding_model: query.embedding_model,
+            disable_decay: query.disable_decay,
+            expand_synonyms: query.expand_synonyms,
+            ..Default::default()
+        });
+    }
+
+    let responses = match state.search.search_memories_batch(internal_reqs).await {
+        Ok(responses) => responses,
+        Err(e) => return e.into(),
+    };
+
+    let results = responses
+        .into_iter()
+        .map(|response| {
+            let results: Vec<SearchResultItem> = response
+                .results
+                .into_iter()
+                .map(|mem| SearchResultItem::Memory(V1MemorySearchResult::from(mem)))
+                .collect();
+            SearchResponse {
+                total: results.len() as u32,
+                results,
+                groups: None,
+                timing_ms: response.timing,
+                degraded: response.degraded,
+                skipped_stages: response
+                    .skipped_stages
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+                relaxed_threshold: response.relaxed_threshold,
+                next_cursor: None,
+                hybrid_weights: None,
+                fallback: response.fallback.map(Into::into),
+            }
+        })
+        .collect();
+
+    ApiResponse::success(BatchSearchResponse { results })
+}
+
+/// Results buffered between a streamed search and the response body.
+const STREAM_BUFFER: usize = 32;
+
+/// `POST /api/v1/search/stream`
+///
+/// Memory search that writes NDJSON while results are found: one memory
+/// result per line, then a `summary` line with the total and timing, or an
+/// `error` line when the search fails after output has started.
+#[utoipa::path(
+    post,
+    path = "/api/v1/search/stream",
+    tag = "search",
+    operation_id = "search.stream",
+    request_body = StreamSearchRequest,
+    responses(
+        (status = 200, description = "One memory `SearchResultItem` per line, then a `SearchStreamEnd` line", content_type = "application/x-ndjson", body = SearchStreamEnd),
+        (status = 400, description = "Invalid request", body = ApiError),
+    )
+)]
+pub async fn stream_search(
+    State(state): State<AppState>,
+    default_tag: Option<Extension<DefaultContainerTag>>,
+    axum::Json(req): axum::Json<StreamSearchRequest>,
+) -> Response {
+    if req.q.trim().is_empty() {
+        return ApiResponse::<()>::error(
+            crate::api::v1::response::ErrorCode::InvalidRequest,
+            "Query cannot be empty",
+        )
+        .into_response();
+    }
+
+    let container_tag = match resolve_container_tag(req.container_tag, default_tag.as_ref()) {
+        Ok(tag) => tag,
+        Err(forbidden) => return ApiResponse::<()>::from(forbidden).into_response(),
+    };
+
+    let internal_req = SearchMemoriesRequest {
+        q: req.q,
+        container_tag,
+        threshold: req.threshold,
+        limit: req.limit,
+        language: req.language,
+        embedding_model: req.embedding_model,
+        disable_decay: req.disable_decay,
+        expand_synonyms: req.expand_synonyms,
+        container_tag_weights: req.container_tag_weights,
+        freshness_weight: req.freshness_weight,
+        ..Default::default()
+    };
+
+    let (tx, mut rx) = mpsc::channel(STREAM_BUFFER);
+    let search = state.search.clone();
+    let task = tokio::spawn(async move { search.stream_memories(internal_req, tx).await });
+
+    // Wait for the first result, so that a search failing before any output
+    // (e.g. an unknown embedding model) still gets a regular error response.
+    let Some(first) = rx.recv().await else {
+        let end = match task.await {
+            Ok(Err(e)) => return ApiResponse::<()>::from(e).into_response(),
+            outcome => stream_end(outcome),
+        };
+        return ndjson_response(futures::stream::once(async move { ndjson_line(&end) }));
+    };
+
+    let lines = futures::stream::unfold(Some((Some(first), rx, task)), |state| async move {
+        let (pending, mut rx, task) = state?;
+        let next = match pending {
+            Some(result) => Some(result),
+            None => rx.recv().await,
+        };
+        match next {
+            Some(result) => Some((
+                ndjson_line(&SearchResultItem::Memory(V1MemorySearchResult::from(
+                    result,
+                ))),
+                Some((None, rx, task)),
+            )),
+            None => Some((ndjson_line(&stream_end(task.await)), None)),
+        }
+    });
+    ndjson_response(lines)
+}
+
+/// Final line for a streamed search that ended with `outcome`.
+fn stream_end(
+    outcome: std::result::Result<Result<SearchStreamSummary>, JoinError>,
+) -> SearchStreamEnd {
+    match outcome {
+        Ok(Ok(summary)) => SearchStreamEnd::Summary {
+            total: summary.total,
+            timing_ms: summary.timing,
+        },
+        Ok(Err(e)) => SearchStreamEnd::Error {
+            error: ApiResponse::<()>::from(e)
+                .error
+                .expect("error responses carry an error"),
+        },
+        Err(e) => {
+            tracing::error!(error = %e, "Streamed search task failed");
+            SearchStreamEnd::Error {
+                error: ApiError {
+                    code: crate::api::v1::response::ErrorCode::InternalError,
+                    message: "An internal error occurred".to_string(),
+                },
+            }
+        }
+    }
+}
+
+/// `value` as one NDJSON line.
+pub(super) fn ndjson_line(value: &impl serde::Serialize) -> String {
+    let mut line = serde_json::to_string(value).unwrap_or_else(|_| "{}".to_string());
+    line.push('\n');
+    line
+}
+
+pub(super) fn ndjson_response(
+    lines: impl futures::Stream<Item = String> + Send + 'static,
+) -> Response {
+    (
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(lines.map(Ok::<_, Infallible>)),
+    )
+        .into_response()
+}
+
+/// `GET /api/v1/search/similar/{memoryId}`
+///
+/// "More like this": memories most similar to the given memory, searched with
+/// its stored embedding. The memory itself is not returned.
+#[utoipa::path(
+    get,
+    path = "/api/v1/search/similar/{memoryId}",
+    tag = "search",
+    operation_id = "search.similarMemories",
+    params(
+        ("memoryId" = String, Path, description = "Memory ID"),
+        SimilarMemoriesQuery,
+    ),
+    responses(
+        (status = 200, description = "Similar memories", body = SearchResponse),
+        (status = 404, description = "Memory not found", body = ApiError),
+        (status = 409, description = "Memory has no embedding yet", body = ApiError),
+    )
+)]
+pub async fn similar_memories(
+    State(state): State<AppState>,
+    default_tag: Option<Extension<DefaultContainerTag>>,
+    Path(id): Path<String>,
+    Query(query): Query<SimilarMemoriesQuery>,
+) -> ApiResponse<SearchResponse> {
+    if let Some(tag) = query.container_tag.as_deref() {
+        if let Err(forbidden) = check_container_tag(tag, default_tag.as_ref()) {
+            return forbidden.into();
+        }
     }
+    let start = Instant::now();
+
+    match state.db.get_memory_by_id(&id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return ApiResponse::error(
+                crate::api::v1::response::ErrorCode::NotFound,
+                format!("Memory {id} not found"),
+            )
+        }
+        Err(e) => return e.into(),
+    }
+    let embedding = match state.db.get_memory_embedding(&id).await {
+        Ok(Some(embedding)) => embedding,
+        Ok(None) => {
+            return ApiResponse::error(
+                crate::api::v1::response::ErrorCode::EmbeddingPending,
+                format!("Memory {id} has not been embedded yet"),
+            )
+        }
+        Err(e) => return e.into(),
+    };
+
+    let response = match state
+        .search
+        .search_similar_to_memory(
+            &id,
+            &embedding,
+            query.limit,
+            query.threshold,
+            query.container_tag.as_deref(),
+        )
+        .await
+    {
+        Ok(response) => response,
+        Err(e) => return e.into(),
+    };
+
+    let results: Vec<SearchResultItem> = response
+        .results
+        .into_iter()
+        .map(|mem| SearchResultItem::Memory(V1MemorySearchResult::from(mem)))
+        .collect();
+
+    ApiResponse::success(SearchResponse {
+        total: results.len() as u32,
+        results,
+        groups: None,
+        timing_ms: start.elapsed().as_millis() as u64,
+        degraded: false,
+        skipped_stages: Vec::new(),
+        relaxed_threshold: None,
+        next_cursor: None,
+        hybrid_weights: None,
+        fallback: None,
+    })
 }
 
 /// Document-scope search: delegates to `SearchService::search_documents`.
@@ -69,7 +412,7 @@ async fn search_documents(
             chunk_threshold: req.threshold,
             document_threshold: None,
             doc_id: None,
-            filters: None,
+            filters: category_filters(req.categories.as_deref()),
             include_full_docs: Some(req.include.documents),
             include_summary: Some(req.include.documents),
             limit: req.limit,
@@ -78,6 +421,19 @@ async fn search_documents(
             rerank_level: None,
             rerank_top_k: None,
             rewrite_query: None,
+            container_tag_weights: req.container_tag_weights.clone(),
+            context_chunks: req.context_chunks,
+            exclude_low_confidence: req.exclude_low_confidence,
+            expand_synonyms: req.expand_synonyms,
+            min_results: req.min_results,
+            score_calibration: req.score_calibration.map(Into::into),
+            chunker_types: chunker_types(req.chunker_types.as_deref()),
+            code_languages: code_languages(req.code_languages.as_deref()),
+            cursor: req.cursor.clone(),
+            offset: req.offset,
+            diversify: req.diversify,
+            diversity_lambda: req.diversity_lambda,
+            highlight: req.highlight,
         };
 
         match state.search.search_documents(internal_req).await {
@@ -96,14 +452,17 @@ async fn search_documents(
         .map(|doc| SearchResultItem::Document(V1DocumentSearchResult::from(doc)))
         .collect();
 
-    let total = results.len() as u32;
-    let timing_ms = start.elapsed().as_millis() as u64;
-
-    ApiResponse::success(SearchResponse {
+    build_search_response(
+        req,
         results,
-        total,
-        timing_ms,
-    })
+        start,
+        response.degraded,
+        response.skipped_stages,
+        response.relaxed_threshold,
+        response.next_cursor,
+        None,
+        response.fallback,
+    )
 }
 
 /// Memory-scope search: delegates to `SearchService::search_memories`.
@@ -125,10 +484,24 @@ async fn search_memories(
             container_tag: container_tag.clone(),
             threshold: req.threshold,
             filters: None,
-            include: None,
+            include: req.include.decay_debug.then(|| SearchIncludeOptions {
+                decay_debug: Some(true),
+                ..Default::default()
+            }),
             limit: req.limit,
             rerank: req.rerank,
             rewrite_query: None,
+            container_tag_weights: req.container_tag_weights.clone(),
+            dedupe_version_chains: req.dedupe_version_chains,
+            language: req.language.clone(),
+            embedding_model: req.embedding_model.clone(),
+            disable_decay: req.disable_decay,
+            expand_synonyms: req.expand_synonyms,
+            min_results: req.min_results,
+            score_calibration: req.score_calibration.map(Into::into),
+            cursor: req.cursor.clone(),
+            offset: req.offset,
+            freshness_weight: req.freshness_weight,
         };
 
         match state.search.search_memories(internal_req).await {
@@ -147,17 +520,20 @@ async fn search_memories(
         .map(|mem| SearchResultItem::Memory(V1MemorySearchResult::from(mem)))
         .collect();
 
-    let total = results.len() as u32;
-    let timing_ms = start.elapsed().as_millis() as u64;
-
-    ApiResponse::success(SearchResponse {
+    build_search_response(
+        req,
         results,
-        total,
-        timing_ms,
-    })
+        start,
+        response.degraded,
+        response.skipped_stages,
+        response.relaxed_threshold,
+        response.next_cursor,
+        None,
+        response.fallback,
+    )
 }
 
-/// Hybrid-scope search: delegates to `SearchService::search_hybrid`.
+/// Hybrid- and keyword-scope search: delegates to `SearchService::search_hybrid`.
 ///
 /// Hybrid results can be either memories or document chunks. We map them
 /// into the tagged `SearchResultItem` enum based on which fields are populated.
@@ -177,12 +553,28 @@ async fn search_hybrid(
             q: req.q.clone(),
             container_tag: container_tag.clone(),
             threshold: req.threshold,
-            filters: None,
+            filters: category_filters(req.categories.as_deref()),
             include: None,
             limit: req.limit,
             rerank: req.rerank,
             rewrite_query: None,
-            search_mode: SearchMode::Hybrid,
+            search_mode: req.scope.into(),
+            dedupe_version_chains: req.dedupe_version_chains,
+            container_tag_weights: req.container_tag_weights.clone(),
+            language: req.language.clone(),
+            exclude_low_confidence: req.exclude_low_confidence,
+            disable_decay: req.disable_decay,
+            expand_synonyms: req.expand_synonyms,
+            min_results: req.min_results,
+            score_calibration: req.score_calibration.map(Into::into),
+            chunker_types: chunker_types(req.chunker_types.as_deref()),
+            code_languages: code_languages(req.code_languages.as_deref()),
+            cursor: req.cursor.clone(),
+            offset: req.offset,
+            memory_weight: req.memory_weight,
+            skip_access_tracking: false,
+            document_weight: req.document_weight,
+            highlight: req.highlight,
         };
 
         match state.search.search_hybrid(internal_req).await {
@@ -208,10 +600,12 @@ async fn search_hybrid(
                     memory_id: v1_result.id,
                     content: v1_result.memory,
                     similarity: v1_result.similarity,
+                    raw_similarity: v1_result.raw_similarity,
                     rerank_score: v1_result.rerank_score,
                     version: None,
                     metadata: v1_result.metadata,
                     updated_at: v1_result.updated_at,
+                    decay_debug: None,
                 })
             } else {
                 let chunk_content = v1_result.chunk.clone();
@@ -220,12 +614,19 @@ async fn search_hybrid(
                     title: None,
                     doc_type: None,
                     score: v1_result.similarity,
+                    raw_score: v1_result.raw_similarity,
                     rerank_score: v1_result.rerank_score,
                     chunks: if chunk_content.is_some() {
                         vec![crate::api::v1::dto::ChunkResult {
                             content: chunk_content.clone().unwrap_or_default(),
-                            score: v1_result.similarity,
+                            // Chunk scores stay uncalibrated, as in documents scope.
+                            score: v1_result.raw_similarity.unwrap_or(v1_result.similarity),
                             rerank_score: v1_result.rerank_score,
+                            chunker: v1_result.chunker,
+                            context_before: Vec::new(),
+                            context_after: Vec::new(),
+                            snippet: None,
+                            highlight: v1_result.highlight,
                         }]
                     } else {
                         vec![]
@@ -240,13 +641,151 @@ async fn search_hybrid(
         })
         .collect();
 
+    build_search_response(
+        req,
+        results,
+        start,
+        response.degraded,
+        response.skipped_stages,
+        response.relaxed_threshold,
+        response.next_cursor,
+        Some(response.weights.into()),
+        None,
+    )
+}
+
+/// Wrap ranked hits in a [`SearchResponse`], applying the requested grouping.
+#[allow(clippy::too_many_arguments)]
+fn build_search_response(
+    req: &SearchRequest,
+    results: Vec<SearchResultItem>,
+    start: Instant,
+    degraded: bool,
+    skipped_stages: Vec<SearchStage>,
+    relaxed_threshold: Option<f32>,
+    next_cursor: Option<String>,
+    hybrid_weights: Option<HybridWeights>,
+    fallback: Option<SearchFallback>,
+) -> ApiResponse<SearchResponse> {
     let total = results.len() as u32;
+    let (results, groups) = match req.group_by {
+        Some(SearchGroupBy::Document) => (Vec::new(), Some(group_results_by_document(results))),
+        None => (results, None),
+    };
     let timing_ms = start.elapsed().as_millis() as u64;
 
     ApiResponse::success(SearchResponse {
         results,
+        groups,
         total,
         timing_ms,
+        degraded,
+        skipped_stages: skipped_stages.into_iter().map(Into::into).collect(),
+        relaxed_threshold,
+        next_cursor,
+        hybrid_weights,
+        fallback: fallback.map(Into::into),
+    })
+}
+
+/// Nest ranked hits under their parent document, with memory hits collected in
+/// a single synthetic group. Groups keep the order of their first (best-ranked)
+/// hit, so the ranking itself is unchanged.
+fn group_results_by_document(results: Vec<SearchResultItem>) -> Vec<SearchResultGroup> {
+    let mut groups: Vec<SearchResultGroup> = Vec::new();
+    let mut document_groups: HashMap<String, usize> = HashMap::new();
+    let mut memory_group: Option<usize> = None;
+
+    for item in results {
+        match item {
+            SearchResultItem::Document(doc) => {
+                let score = doc.rerank_score.unwrap_or(doc.score);
+                let index = *document_groups
+                    .entry(doc.document_id.clone())
+                    .or_insert_with(|| {
+                        groups.push(empty_group(
+                            SearchGroupType::Document,
+                            Some(doc.document_id.clone()),
+                            score,
+                        ));
+                        groups.len() - 1
+                    });
+
+                let group = &mut groups[index];
+                group.max_score = group.max_score.max(score);
+                if group.title.is_none() {
+                    group.title = doc.title;
+                }
+                if group.doc_type.is_none() {
+                    group.doc_type = doc.doc_type;
+                }
+                group.chunks.extend(doc.chunks);
+            }
+            SearchResultItem::Memory(mem) => {
+                let score = mem.rerank_score.unwrap_or(mem.similarity);
+                let index = *memory_group.get_or_insert_with(|| {
+                    groups.push(empty_group(SearchGroupType::Memories, None, score));
+                    groups.len() - 1
+                });
+
+                let group = &mut groups[index];
+                group.max_score = group.max_score.max(score);
+                group.memories.push(mem);
+            }
+        }
+    }
+
+    groups
+}
+
+fn empty_group(
+    group_type: SearchGroupType,
+    document_id: Option<String>,
+    max_score: f32,
+) -> SearchResultGroup {
+    SearchResultGroup {
+        group_type,
+        document_id,
+        title: None,
+        doc_type: None,
+        max_score,
+        chunks: Vec::new(),
+        memories: Vec::new(),
+    }
+}
+
+/// Metadata filter matching documents in any of `categories`; `None` when no
+/// categories were requested.
+fn category_filters(categories: Option<&[String]>) -> Option<SearchFilters> {
+    let categories = categories.filter(|categories| !categories.is_empty())?;
+    Some(SearchFilters::Flat {
+        and: None,
+        or: Some(
+            categories
+                .iter()
+                .map(|category| FilterCondition {
+                    key: CATEGORY_METADATA_KEY.to_string(),
+                    value: category.clone().into(),
+                    negate: None,
+                    filter_type: None,
+                    operator: None,
+                })
+                .collect(),
+        ),
+    })
+}
+
+fn chunker_types(types: Option<&[V1ChunkerType]>) -> Option<Vec<ChunkerType>> {
+    types.map(|types| types.iter().copied().map(Into::into).collect())
+}
+
+/// Language names are stored lowercase, e.g. `rust`.
+fn code_languages(languages: Option<&[String]>) -> Option<Vec<String>> {
+    languages.map(|languages| {
+        languages
+            .iter()
+            .map(|language| language.trim().to_lowercase())
+            .collect()
     })
 }
 
@@ -262,6 +801,8 @@ fn is_database_locked_error(error: &crate::error::MomoError) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::v1::dto::search::ScoreCalibration;
+    use crate::models::SearchMode;
 
     #[test]
     fn search_request_defaults_to_hybrid_scope() {
@@ -317,6 +858,121 @@ mod tests {
         assert_eq!(req.rerank, Some(true));
     }
 
+    #[test]
+    fn search_request_with_container_tag_weights() {
+        let json = r#"{
+            "q": "test",
+            "containerTags": ["user_123", "shared_kb"],
+            "containerTagWeights": {"user_123": 1.5, "shared_kb": 0.5}
+        }"#;
+        let req: SearchRequest = serde_json::from_str(json).expect("deserialize");
+        let weights = req.container_tag_weights.expect("weights");
+        assert_eq!(weights.get("user_123"), Some(&1.5));
+        assert_eq!(weights.get("shared_kb"), Some(&0.5));
+    }
+
+    #[test]
+    fn search_request_with_score_calibration() {
+        let json = r#"{"q": "test", "scoreCalibration": "minMax"}"#;
+        let req: SearchRequest = serde_json::from_str(json).expect("deserialize");
+        assert_eq!(req.score_calibration, Some(ScoreCalibration::MinMax));
+
+        let json = r#"{"q": "test", "scoreCalibration": "linear"}"#;
+        assert!(serde_json::from_str::<SearchRequest>(json).is_err());
+    }
+
+    #[test]
+    fn paged_response_returns_next_cursor() {
+        let req: SearchRequest =
+            serde_json::from_str(r#"{"q": "test", "limit": 1, "cursor": "abc"}"#)
+                .expect("deserialize");
+        assert_eq!(req.cursor.as_deref(), Some("abc"));
+
+        let response = build_search_response(
+            &req,
+            vec![memory_hit("mem_1", 0.8, None)],
+            Instant::now(),
+            false,
+            vec![],
+            None,
+            Some("next".to_string()),
+            None,
+        );
+        let json = serde_json::to_value(&response).expect("serialize");
+        assert_eq!(json["data"]["nextCursor"], "next");
+        assert!(json["data"].get("hybridWeights").is_none());
+    }
+
+    #[test]
+    fn hybrid_response_reports_effective_weights() {
+        let req: SearchRequest =
+            serde_json::from_str(r#"{"q": "test", "memoryWeight": 2.0, "documentWeight": 0.5}"#)
+                .expect("deserialize");
+        assert_eq!(req.memory_weight, Some(2.0));
+        assert_eq!(req.document_weight, Some(0.5));
+
+        let response = build_search_response(
+            &req,
+            vec![],
+            Instant::now(),
+            false,
+            vec![],
+            None,
+            None,
+            Some(HybridWeights {
+                memory: 2.0,
+                document: 0.5,
+            }),
+        );
+        let json = serde_json::to_value(&response).expect("serialize");
+        assert_eq!(
+            json["data"]["hybridWeights"],
+            serde_json::json!({"memory": 2.0, "document": 0.5})
+        );
+    }
+
+    #[test]
+    fn stream_end_line_reports_summary_or_error() {
+        let summary = stream_end(Ok(Ok(SearchStreamSummary {
+            total: 3,
+            timing: 12,
+        })));
+        let line = ndjson_line(&summary);
+        assert!(line.ends_with('\n'));
+        assert_eq!(line.matches('\n').count(), 1);
+        let json: serde_json::Value = serde_json::from_str(&line).expect("json");
+        assert_eq!(json["type"], "summary");
+        assert_eq!(json["total"], 3);
+        assert_eq!(json["timingMs"], 12);
+
+        let failed = stream_end(Ok(Err(crate::error::MomoError::Validation(
+            "bad model".to_string(),
+        ))));
+        let json = serde_json::to_value(&failed).expect("json");
+        assert_eq!(json["type"], "error");
+        assert_eq!(json["error"]["code"], "invalid_request");
+        assert_eq!(json["error"]["message"], "bad model");
+    }
+
+    #[test]
+    fn categories_become_or_metadata_filters() {
+        let json = r#"{"q": "dinner ideas", "categories": ["recipe", "meeting_notes"]}"#;
+        let req: SearchRequest = serde_json::from_str(json).expect("deserialize");
+
+        let Some(SearchFilters::Flat { and, or }) = category_filters(req.categories.as_deref())
+        else {
+            panic!("expected flat filters");
+        };
+        assert!(and.is_none());
+        let conditions = or.expect("or conditions");
+        assert_eq!(conditions.len(), 2);
+        assert!(conditions.iter().all(|c| c.key == "category"));
+        assert_eq!(conditions[1].value, "meeting_notes");
+
+        assert!(category_filters(None).is_none());
+        assert!(category_filters(Some(&[])).is_none());
+    }
+
     #[test]
     fn search_scope_converts_to_search_mode() {
         let mode: SearchMode = SearchScope::Documents.into();
@@ -327,5 +983,112 @@ mod tests {
 
         let mode: SearchMode = SearchScope::Hybrid.into();
         assert_eq!(mode, SearchMode::Hybrid);
+
+        let mode: SearchMode = SearchScope::Keyword.into();
+        assert_eq!(mode, SearchMode::Keyword);
+    }
+
+    fn chunk_hit(document_id: &str, content: &str, score: f32) -> SearchResultItem {
+        SearchResultItem::Document(V1DocumentSearchResult {
+            document_id: document_id.to_string(),
+            title: None,
+            doc_type: None,
+            score,
+            rerank_score: None,
+            chunks: vec![crate::api::v1::dto::ChunkResult {
+                content: content.to_string(),
+                score,
+                rerank_score: None,
+                context_before: Vec::new(),
+                context_after: Vec::new(),
+                chunker: None,
+                snippet: None,
+                highlight: None,
+            }],
+            summary: None,
+            content: Some(content.to_string()),
+            metadata: HashMap::new(),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            raw_score: None,
+        })
+    }
+
+    fn memory_hit(memory_id: &str, similarity: f32, rerank_score: Option<f32>) -> SearchResultItem {
+        SearchResultItem::Memory(V1MemorySearchResult {
+            memory_id: memory_id.to_string(),
+            content: Some("memory".to_string()),
+            similarity,
+            rerank_score,
+            version: None,
+            metadata: HashMap::new(),
+            updated_at: chrono::Utc::now(),
+            raw_similarity: None,
+            decay_debug: None,
+        })
+    }
+
+    #[test]
+    fn search_request_with_group_by_document() {
+        let json = r#"{"q": "test", "groupBy": "document"}"#;
+        let req: SearchRequest = serde_json::from_str(json).expect("deserialize");
+        assert_eq!(req.group_by, Some(SearchGroupBy::Document));
+    }
+
+    #[test]
+    fn group_results_nests_hits_and_keeps_rank_order() {
+        let groups = group_results_by_document(vec![
+            chunk_hit("doc_a", "first", 0.9),
+            memory_hit("mem_1", 0.85, None),
+            chunk_hit("doc_b", "second", 0.8),
+            chunk_hit("doc_a", "third", 0.7),
+            memory_hit("mem_2", 0.6, Some(0.95)),
+        ]);
+
+        assert_eq!(groups.len(), 3);
+
+        assert_eq!(groups[0].group_type, SearchGroupType::Document);
+        assert_eq!(groups[0].document_id.as_deref(), Some("doc_a"));
+        assert_eq!(groups[0].max_score, 0.9);
+        let contents: Vec<&str> = groups[0]
+            .chunks
+            .iter()
+            .map(|c| c.content.as_str())
+            .collect();
+        assert_eq!(contents, vec!["first", "third"]);
+
+        assert_eq!(groups[1].group_type, SearchGroupType::Memories);
+        assert!(groups[1].document_id.is_none());
+        assert_eq!(groups[1].memories.len(), 2);
+        assert_eq!(groups[1].max_score, 0.95);
+
+        assert_eq!(groups[2].document_id.as_deref(), Some("doc_b"));
+        assert_eq!(groups[2].chunks.len(), 1);
+    }
+
+    #[test]
+    fn grouped_response_moves_hits_into_groups() {
+        let req: SearchRequest =
+            serde_json::from_str(r#"{"q": "test", "groupBy": "document"}"#).expect("deserialize");
+        let response = build_search_response(
+            &req,
+            vec![
+                chunk_hit("doc_a", "first", 0.9),
+                memory_hit("mem_1", 0.8, None),
+            ],
+            Instant::now(),
+            false,
+            vec![],
+            None,
+            None,
+            None,
+        );
+
+        let json = serde_json::to_value(&response).expect("serialize");
+        assert_eq!(json["data"]["total"], 2);
+        assert_eq!(json["data"]["results"], serde_json::json!([]));
+        assert_eq!(json["data"]["groups"][0]["type"], "document");
+        assert_eq!(json["data"]["groups"][0]["documentId"], "doc_a");
+        assert_eq!(json["data"]["groups"][1]["type"], "memories");
     }
 }
@@ -7,18 +7,127 @@
 //! Unlike the admin middleware (`src/api/middleware.rs`) which returns raw
 //! `StatusCode`, this middleware returns the v1 `ApiResponse` JSON envelope
 //! so auth errors conform to the v1 contract.
+//!
+//! When the authenticated key has a default container tag configured via
+//! `MOMO_API_KEY_CONTAINER_TAGS`, the middleware also places a
+//! [`DefaultContainerTag`] in the request extensions for handlers to use.
+//! Such a key is bound to its tag: [`resolve_container_tag`] rejects any
+//! other explicit tag with 403 `forbidden`.
+//!
+//! The authenticated key's scopes are attached as [`ApiKeyScopes`]. Route
+//! groups then enforce them with [`require_scope`] or
+//! [`require_method_scope`], rejecting insufficient keys with 403 `forbidden`.
 
 use axum::{
     body::Body,
     extract::State,
-    http::Request,
+    http::{Method, Request, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
+    Extension,
 };
 
+use serde::Serialize;
+
 use crate::api::state::AppState;
+use crate::config::{ApiKey, ApiScope};
 
 use super::response::{ApiResponse, ErrorCode};
+use super::versioning::Envelope;
+
+/// Container tag bound to the authenticated API key.
+///
+/// Handlers extract this as `Option<Extension<DefaultContainerTag>>` and fall
+/// back to it when the request does not carry its own container tag. It is
+/// also the only tag the key may name explicitly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DefaultContainerTag(pub String);
+
+/// Fingerprint of the authenticated API key (see [`ApiKey::fingerprint`]),
+/// for handlers that keep per-key state without storing the key itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKeyId(pub String);
+
+/// Scopes granted to the authenticated API key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKeyScopes(pub Vec<ApiScope>);
+
+impl ApiKeyScopes {
+    /// Whether the key may perform operations that need `scope`.
+    pub fn allows(&self, scope: ApiScope) -> bool {
+        self.0.iter().any(|granted| *granted >= scope)
+    }
+}
+
+impl From<&ApiKey> for ApiKeyScopes {
+    fn from(key: &ApiKey) -> Self {
+        Self(key.scopes.clone())
+    }
+}
+
+/// A container tag the authenticated API key is not allowed to use.
+///
+/// Converts into a 403 `forbidden` response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForbiddenContainerTag(pub String);
+
+impl<T: Serialize> From<ForbiddenContainerTag> for ApiResponse<T> {
+    fn from(ForbiddenContainerTag(tag): ForbiddenContainerTag) -> Self {
+        ApiResponse::error(
+            ErrorCode::Forbidden,
+            format!("API key is not allowed to use container tag '{tag}'"),
+        )
+    }
+}
+
+/// Check that the API key may use `tag`. A key bound to a default tag may
+/// only use that tag; keys without one may use any tag.
+pub fn check_container_tag(
+    tag: &str,
+    default: Option<&Extension<DefaultContainerTag>>,
+) -> Result<(), ForbiddenContainerTag> {
+    match default {
+        Some(Extension(DefaultContainerTag(allowed))) if allowed != tag => {
+            Err(ForbiddenContainerTag(tag.to_string()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Resolve the container tag for a request: an explicit, non-empty tag wins,
+/// otherwise the API key's default tag (if any) is used.
+///
+/// An explicit tag other than the key's default tag is rejected with
+/// [`ForbiddenContainerTag`].
+pub fn resolve_container_tag(
+    explicit: Option<String>,
+    default: Option<&Extension<DefaultContainerTag>>,
+) -> Result<Option<String>, ForbiddenContainerTag> {
+    match explicit.filter(|tag| !tag.trim().is_empty()) {
+        Some(tag) => {
+            check_container_tag(&tag, default)?;
+            Ok(Some(tag))
+        }
+        None => Ok(default.map(|Extension(tag)| tag.0.clone())),
+    }
+}
+
+/// Resolve a list of container tags like [`resolve_container_tag`]: a
+/// non-empty explicit list wins, otherwise the key's default tag is used.
+pub fn resolve_container_tags(
+    explicit: Option<Vec<String>>,
+    default: Option<&Extension<DefaultContainerTag>>,
+) -> Result<Option<Vec<String>>, ForbiddenContainerTag> {
+    match explicit.filter(|tags| !tags.is_empty()) {
+        Some(tags) => {
+            for tag in &tags {
+                check_container_tag(tag, default)?;
+            }
+            Ok(Some(tags))
+        }
+        None => Ok(default.map(|Extension(tag)| vec![tag.0.clone()])),
+    }
+}
 
 /// Axum middleware that enforces Bearer token authentication for v1 API routes.
 ///
@@ -28,7 +137,9 @@ use super::response::{ApiResponse, ErrorCode};
 ///   The server still starts, but protected routes are locked down.
 /// - If the `Authorization: Bearer <token>` header is missing or malformed → 401.
 /// - If the token is not in the configured key list → 401.
-/// - If the token is valid → passes the request through to the next handler.
+/// - If the token is valid → passes the request through to the next handler,
+///   attaching the key's [`ApiKeyScopes`], [`ApiKeyId`] and its [`DefaultContainerTag`]
+///   when one is configured.
 ///
 /// # Error format
 ///
@@ -38,7 +149,7 @@ use super::response::{ApiResponse, ErrorCode};
 /// ```
 pub async fn v1_auth_middleware(
     State(state): State<AppState>,
-    request: Request<Body>,
+    mut request: Request<Body>,
     next: Next,
 ) -> Response {
     if state.config.server.api_keys.is_empty() {
@@ -72,11 +183,77 @@ pub async fn v1_auth_middleware(
         }
     };
 
-    if state.config.server.api_keys.contains(&token.to_string()) {
-        next.run(request).await
-    } else {
-        ApiResponse::<()>::error(ErrorCode::Unauthorized, "Invalid API key").into_response()
+    let Some(api_key) = state
+        .config
+        .server
+        .api_keys
+        .iter()
+        .find(|key| key.key == token)
+    else {
+        return ApiResponse::<()>::error(ErrorCode::Unauthorized, "Invalid API key")
+            .into_response();
+    };
+    request.extensions_mut().insert(ApiKeyScopes::from(api_key));
+    request
+        .extensions_mut()
+        .insert(ApiKeyId(api_key.fingerprint()));
+
+    if let Some(tag) = state.config.server.api_key_container_tags.get(&api_key.key) {
+        let tag = DefaultContainerTag(tag.clone());
+        request.extensions_mut().insert(tag);
     }
+
+    next.run(request).await
+}
+
+/// Reject requests whose API key lacks `scope` with 403 `forbidden`.
+///
+/// Must run after [`v1_auth_middleware`]; requests without [`ApiKeyScopes`]
+/// are rejected.
+pub async fn require_scope(scope: ApiScope, request: Request<Body>, next: Next) -> Response {
+    let allowed = request
+        .extensions()
+        .get::<ApiKeyScopes>()
+        .is_some_and(|scopes| scopes.allows(scope));
+    if !allowed {
+        return ApiResponse::<()>::error(
+            ErrorCode::Forbidden,
+            format!("API key is missing the '{scope}' scope"),
+        )
+        .into_response();
+    }
+
+    next.run(request).await
+}
+
+/// Require `read` for safe methods (`GET`, `HEAD`, `OPTIONS`) and `write`
+/// for everything else.
+pub async fn require_method_scope(request: Request<Body>, next: Next) -> Response {
+    let scope = match *request.method() {
+        Method::GET | Method::HEAD | Method::OPTIONS => ApiScope::Read,
+        _ => ApiScope::Write,
+    };
+    require_scope(scope, request, next).await
+}
+
+/// Render the bare 413 axum returns when a body exceeds `DefaultBodyLimit`
+/// as a `payload_too_large` envelope.
+pub async fn payload_too_large_envelope(
+    max_bytes: usize,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let response = next.run(request).await;
+    if response.status() == StatusCode::PAYLOAD_TOO_LARGE
+        && response.extensions().get::<Envelope>().is_none()
+    {
+        return ApiResponse::<()>::error(
+            ErrorCode::PayloadTooLarge,
+            format!("Request body exceeds the {max_bytes} byte limit"),
+        )
+        .into_response();
+    }
+    response
 }
 
 #[cfg(test)]
@@ -84,8 +261,12 @@ mod tests {
     use super::*;
     use crate::api::state::AppState;
     use crate::config::{
-        Config, DatabaseConfig, EmbeddingsConfig, InferenceConfig, McpConfig, MemoryConfig,
-        OcrConfig, ProcessingConfig, ServerConfig, TranscriptionConfig,
+        AlternateEmbeddingsConfig, ApiKey, Config, DatabaseConfig, EmbeddingsConfig,
+        ExtractionConfig, IdleForgettingConfig, ImportanceConfig, InferenceConfig, McpConfig,
+        MemoryConfig, OcrConfig, ProcessingConfig, SearchConfig, ServerConfig, TranscriptionConfig,
+    };
+    use crate::models::{
+        ChunkStrategy, NearDuplicateAction, PiiAction, PiiCategory, StructuredDataOverflow,
     };
     use axum::body::Body;
     use axum::http::Request;
@@ -98,22 +279,51 @@ mod tests {
             server: ServerConfig {
                 host: "127.0.0.1".to_string(),
                 port: 3000,
-                api_keys,
+                api_keys: api_keys.into_iter().map(ApiKey::unrestricted).collect(),
+                api_key_container_tags: std::collections::HashMap::new(),
+                idempotency_key_ttl_secs: 86400,
+                document_events_idle_timeout_secs: 300,
+                max_request_body_bytes: 52428800,
             },
             mcp: McpConfig::default(),
             database: DatabaseConfig {
                 url: "file::memory:".to_string(),
                 auth_token: None,
                 local_path: None,
+                max_connections: 4,
             },
             embeddings: EmbeddingsConfig {
                 model: "BAAI/bge-small-en-v1.5".to_string(),
                 dimensions: 384,
                 batch_size: 256,
+                query_cache_size: 0,
+                max_concurrent_batches: 4,
             },
             processing: ProcessingConfig {
                 chunk_size: 512,
                 chunk_overlap: 50,
+                chunk_strategy: ChunkStrategy::Structural,
+                semantic_chunk_threshold: 0.3,
+                semantic_chunk_min_size: 128,
+                use_model_tokenizer: false,
+                auto_summary: false,
+                auto_summary_min_chars: 2000,
+                contextual_chunks: false,
+                near_duplicate_threshold: None,
+                near_duplicate_action: NearDuplicateAction::Warn,
+                record_stage_timings: false,
+                low_confidence_threshold: None,
+                chunk_reembed_interval_secs: 0,
+                chunk_reembed_batch_size: 64,
+                categorize_documents: false,
+                structured_data_max_chunks: 0,
+                structured_data_overflow: StructuredDataOverflow::Sample,
+                pii_detection: false,
+                pii_categories: PiiCategory::ALL.to_vec(),
+                pii_action: PiiAction::Redact,
+                retry_max_attempts: 3,
+                retry_base_delay_ms: 500,
+                dead_letter_after_failures: 5,
             },
             memory: MemoryConfig {
                 episode_decay_days: 30.0,
@@ -121,7 +331,10 @@ mod tests {
                 episode_decay_threshold: 0.3,
                 episode_forget_grace_days: 7,
                 forgetting_check_interval_secs: 3600,
+                forgetting_dry_run: false,
                 profile_refresh_interval_secs: 86400,
+                profile_exclude_unscored_facts: false,
+                language_detection: false,
                 inference: InferenceConfig {
                     enabled: false,
                     interval_secs: 86400,
@@ -131,6 +344,9 @@ mod tests {
                     seed_limit: 50,
                     exclude_episodes: true,
                 },
+                importance: ImportanceConfig::default(),
+                alternate_embeddings: AlternateEmbeddingsConfig::default(),
+                idle_forgetting: IdleForgettingConfig::default(),
             },
             ocr: OcrConfig {
                 model: "local/tesseract".to_string(),
@@ -140,16 +356,24 @@ mod tests {
                 timeout_secs: 60,
                 max_image_dimension: 4096,
                 min_image_dimension: 50,
+                max_concurrent_jobs: 2,
+                include_layout: false,
+                min_confidence: None,
+                min_document_confidence: None,
             },
             transcription: TranscriptionConfig::default(),
             llm: None,
             reranker: None,
+            search: SearchConfig::default(),
+            extraction: ExtractionConfig::default(),
         }
     }
 
     async fn build_test_app(api_keys: Vec<String>) -> Router {
-        let config = make_config(api_keys);
+        build_test_app_with_config(make_config(api_keys)).await
+    }
 
+    async fn build_test_app_with_config(config: Config) -> Router {
         let raw_db = crate::db::Database::new(&config.database).await.unwrap();
         let db_backend = crate::db::LibSqlBackend::new(raw_db);
         let db: std::sync::Arc<dyn crate::db::DatabaseBackend> = std::sync::Arc::new(db_backend);
@@ -179,10 +403,23 @@ mod tests {
             "healthy"
         }
 
+        async fn tag_handler(
+            default_tag: Option<Extension<DefaultContainerTag>>,
+            axum::extract::Query(query): axum::extract::Query<
+                std::collections::HashMap<String, String>,
+            >,
+        ) -> Response {
+            match resolve_container_tag(query.get("containerTag").cloned(), default_tag.as_ref()) {
+                Ok(tag) => tag.unwrap_or_else(|| "none".to_string()).into_response(),
+                Err(forbidden) => ApiResponse::<()>::from(forbidden).into_response(),
+            }
+        }
+
         let public_routes = Router::new().route("/health", get(health_handler));
 
         let protected_routes = Router::new()
             .route("/protected", get(protected_handler))
+            .route("/tag", get(tag_handler))
             .route_layer(middleware::from_fn_with_state(
                 state.clone(),
                 v1_auth_middleware,
@@ -321,6 +558,166 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    async fn tag_for(app: Router, key: &str, uri: &str) -> String {
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(uri)
+                    .header("Authorization", format!("Bearer {key}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_v1_auth_injects_default_container_tag_for_mapped_key() {
+        let mut config = make_config(vec!["acme-key".to_string(), "plain-key".to_string()]);
+        config
+            .server
+            .api_key_container_tags
+            .insert("acme-key".to_string(), "acme".to_string());
+        let app = build_test_app_with_config(config).await;
+
+        assert_eq!(tag_for(app.clone(), "acme-key", "/tag").await, "acme");
+        assert_eq!(tag_for(app, "plain-key", "/tag").await, "none");
+    }
+
+    #[tokio::test]
+    async fn test_v1_auth_explicit_container_tag_is_limited_to_the_keys_tag() {
+        let mut config = make_config(vec!["acme-key".to_string(), "plain-key".to_string()]);
+        config
+            .server
+            .api_key_container_tags
+            .insert("acme-key".to_string(), "acme".to_string());
+        let app = build_test_app_with_config(config).await;
+
+        assert_eq!(
+            tag_for(app.clone(), "acme-key", "/tag?containerTag=acme").await,
+            "acme"
+        );
+        assert_eq!(
+            tag_for(app.clone(), "plain-key", "/tag?containerTag=globex").await,
+            "globex"
+        );
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/tag?containerTag=globex")
+                    .header("Authorization", "Bearer acme-key")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let (status, json) = parse_error_body(response).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(json["error"]["code"], "forbidden");
+    }
+
+    #[test]
+    fn test_resolve_container_tag_falls_back_to_default_and_rejects_other_tags() {
+        let default = Extension(DefaultContainerTag("acme".to_string()));
+
+        assert_eq!(
+            resolve_container_tag(Some("acme".to_string()), Some(&default)),
+            Ok(Some("acme".to_string()))
+        );
+        assert_eq!(
+            resolve_container_tag(Some("other".to_string()), Some(&default)),
+            Err(ForbiddenContainerTag("other".to_string()))
+        );
+        assert_eq!(
+            resolve_container_tag(Some("  ".to_string()), Some(&default)),
+            Ok(Some("acme".to_string()))
+        );
+        assert_eq!(
+            resolve_container_tag(None, Some(&default)),
+            Ok(Some("acme".to_string()))
+        );
+        assert_eq!(
+            resolve_container_tag(Some("other".to_string()), None),
+            Ok(Some("other".to_string()))
+        );
+        assert_eq!(resolve_container_tag(None, None), Ok(None));
+    }
+
+    #[test]
+    fn test_resolve_container_tags_checks_every_tag() {
+        let default = Extension(DefaultContainerTag("acme".to_string()));
+
+        assert_eq!(
+            resolve_container_tags(Some(vec![]), Some(&default)),
+            Ok(Some(vec!["acme".to_string()]))
+        );
+        assert_eq!(
+            resolve_container_tags(
+                Some(vec!["acme".to_string(), "globex".to_string()]),
+                Some(&default)
+            ),
+            Err(ForbiddenContainerTag("globex".to_string()))
+        );
+        assert_eq!(resolve_container_tags(None, None), Ok(None));
+    }
+
+    #[test]
+    fn test_api_key_scopes_are_hierarchical() {
+        let admin = ApiKeyScopes(vec![ApiScope::Admin]);
+        let reader = ApiKeyScopes(vec![ApiScope::Read]);
+
+        assert!(admin.allows(ApiScope::Read));
+        assert!(admin.allows(ApiScope::Write));
+        assert!(reader.allows(ApiScope::Read));
+        assert!(!reader.allows(ApiScope::Write));
+        assert!(!reader.allows(ApiScope::Admin));
+    }
+
+    #[tokio::test]
+    async fn test_require_method_scope_rejects_read_only_key_on_write() {
+        async fn handler() -> &'static str {
+            "ok"
+        }
+
+        let app = Router::new()
+            .route("/items", get(handler).post(handler))
+            .route_layer(middleware::from_fn(require_method_scope))
+            .layer(Extension(ApiKeyScopes(vec![ApiScope::Read])));
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/items")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/items")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let (status, json) = parse_error_body(response).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(json["error"]["code"], "forbidden");
+    }
+
     #[tokio::test]
     async fn test_v1_auth_error_response_is_json_envelope() {
         let app = build_test_app(vec!["key".to_string()]).await;
@@ -4,6 +4,7 @@ pub mod middleware;
 pub mod openapi;
 pub mod response;
 pub mod router;
+pub mod versioning;
 
 #[cfg(test)]
 mod tests {
@@ -14,31 +15,65 @@ mod tests {
     use crate::api::routes::create_router;
     use crate::api::state::AppState;
     use crate::config::{
-        Config, DatabaseConfig, EmbeddingsConfig, InferenceConfig, McpConfig, MemoryConfig,
-        OcrConfig, ProcessingConfig, ServerConfig, TranscriptionConfig,
+        AlternateEmbeddingsConfig, ApiKey, ApiScope, Config, DatabaseConfig, EmbeddingsConfig,
+        ExtractionConfig, IdleForgettingConfig, ImportanceConfig, InferenceConfig, McpConfig,
+        MemoryConfig, OcrConfig, ProcessingConfig, SearchConfig, ServerConfig, TranscriptionConfig,
+    };
+    use crate::models::{
+        ChunkStrategy, NearDuplicateAction, PiiAction, PiiCategory, ProcessingStatus,
+        StructuredDataOverflow,
     };
 
-    async fn test_state(api_keys: Vec<String>) -> AppState {
+    async fn test_state(api_keys: Vec<ApiKey>) -> AppState {
         let config = Config {
             server: ServerConfig {
                 host: "127.0.0.1".to_string(),
                 port: 3000,
                 api_keys,
+                api_key_container_tags: std::collections::HashMap::new(),
+                idempotency_key_ttl_secs: 86400,
+                document_events_idle_timeout_secs: 300,
+                max_request_body_bytes: 52428800,
             },
             mcp: McpConfig::default(),
             database: DatabaseConfig {
                 url: "file::memory:".to_string(),
                 auth_token: None,
                 local_path: None,
+                max_connections: 4,
             },
             embeddings: EmbeddingsConfig {
                 model: "BAAI/bge-small-en-v1.5".to_string(),
                 dimensions: 384,
                 batch_size: 256,
+                query_cache_size: 0,
+                max_concurrent_batches: 4,
             },
             processing: ProcessingConfig {
                 chunk_size: 512,
                 chunk_overlap: 50,
+                chunk_strategy: ChunkStrategy::Structural,
+                semantic_chunk_threshold: 0.3,
+                semantic_chunk_min_size: 128,
+                use_model_tokenizer: false,
+                auto_summary: false,
+                auto_summary_min_chars: 2000,
+                contextual_chunks: false,
+                near_duplicate_threshold: None,
+                near_duplicate_action: NearDuplicateAction::Warn,
+                record_stage_timings: false,
+                low_confidence_threshold: None,
+                chunk_reembed_interval_secs: 0,
+                chunk_reembed_batch_size: 64,
+                categorize_documents: false,
+                structured_data_max_chunks: 0,
+                structured_data_overflow: StructuredDataOverflow::Sample,
+                pii_detection: false,
+                pii_categories: PiiCategory::ALL.to_vec(),
+                pii_action: PiiAction::Redact,
+                retry_max_attempts: 3,
+                retry_base_delay_ms: 500,
+                dead_letter_after_failures: 5,
             },
             memory: MemoryConfig {
                 episode_decay_days: 30.0,
@@ -46,7 +81,10 @@ mod tests {
                 episode_decay_threshold: 0.3,
                 episode_forget_grace_days: 7,
                 forgetting_check_interval_secs: 3600,
+                forgetting_dry_run: false,
                 profile_refresh_interval_secs: 86400,
+                profile_exclude_unscored_facts: false,
+                language_detection: false,
                 inference: InferenceConfig {
                     enabled: false,
                     interval_secs: 86400,
@@ -56,6 +94,9 @@ mod tests {
                     seed_limit: 50,
                     exclude_episodes: true,
                 },
+                importance: ImportanceConfig::default(),
+                alternate_embeddings: AlternateEmbeddingsConfig::default(),
+                idle_forgetting: IdleForgettingConfig::default(),
             },
             ocr: OcrConfig {
                 model: "local/tesseract".to_string(),
@@ -65,10 +106,16 @@ mod tests {
                 timeout_secs: 60,
                 max_image_dimension: 4096,
                 min_image_dimension: 50,
+                max_concurrent_jobs: 2,
+                include_layout: false,
+                min_confidence: None,
+                min_document_confidence: None,
             },
             transcription: TranscriptionConfig::default(),
             llm: None,
             reranker: None,
+            search: SearchConfig::default(),
+            extraction: ExtractionConfig::default(),
         };
 
         let raw_db = crate::db::Database::new(&config.database).await.unwrap();
@@ -102,7 +149,7 @@ mod tests {
 
     #[tokio::test]
     async fn protected_route_requires_auth() {
-        let app = create_router(test_state(vec!["test-key".to_string()]).await);
+        let app = create_router(test_state(vec![ApiKey::unrestricted("test-key")]).await);
 
         let response = app
             .oneshot(
@@ -121,9 +168,551 @@ mod tests {
         assert_eq!(json["error"]["code"], "unauthorized");
     }
 
+    fn scoped_key(key: &str, scopes: &[ApiScope]) -> ApiKey {
+        ApiKey {
+            key: key.to_string(),
+            scopes: scopes.to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn read_only_key_is_forbidden_from_write_endpoint() {
+        let app = create_router(test_state(vec![scoped_key("reader", &[ApiScope::Read])]).await);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/memories")
+                    .header("Authorization", "Bearer reader")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"content":"hello","containerTag":"t"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let json = body_json(response).await;
+        assert_eq!(json["error"]["code"], "forbidden");
+    }
+
+    #[tokio::test]
+    async fn read_only_key_can_read() {
+        let app = create_router(test_state(vec![scoped_key("reader", &[ApiScope::Read])]).await);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/containers/tags")
+                    .header("Authorization", "Bearer reader")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn admin_endpoints_require_admin_scope() {
+        let app = create_router(
+            test_state(vec![
+                scoped_key("writer", &[ApiScope::Read, ApiScope::Write]),
+                scoped_key("admin", &[ApiScope::Admin]),
+            ])
+            .await,
+        );
+
+        let request = |key: &str| {
+            Request::builder()
+                .uri("/api/v1/admin/contradictions")
+                .header("Authorization", format!("Bearer {key}"))
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(request("writer")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let json = body_json(response).await;
+        assert_eq!(json["error"]["code"], "forbidden");
+
+        let response = app.oneshot(request("admin")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn repeated_create_with_idempotency_key_yields_one_document() {
+        let app = create_router(test_state(vec![ApiKey::unrestricted("key")]).await);
+
+        let create = || {
+            Request::builder()
+                .method("POST")
+                .uri("/api/v1/documents")
+                .header("Authorization", "Bearer key")
+                .header("Idempotency-Key", "retry-1")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    r#"{"content":"Quarterly report","containerTag":"idem"}"#,
+                ))
+                .unwrap()
+        };
+
+        let first = app.clone().oneshot(create()).await.unwrap();
+        assert_eq!(first.status(), StatusCode::ACCEPTED);
+        let first = body_json(first).await;
+
+        let second = app.clone().oneshot(create()).await.unwrap();
+        assert_eq!(second.status(), StatusCode::OK);
+        let second = body_json(second).await;
+        assert_eq!(second["data"], first["data"]);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/documents?containerTags=idem")
+                    .header("Authorization", "Bearer key")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let json = body_json(response).await;
+        assert_eq!(json["data"]["documents"].as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn concurrent_creates_with_idempotency_key_yield_one_document() {
+        let app = create_router(test_state(vec![ApiKey::unrestricted("key")]).await);
+
+        let create = || {
+            Request::builder()
+                .method("POST")
+                .uri("/api/v1/documents")
+                .header("Authorization", "Bearer key")
+                .header("Idempotency-Key", "retry-concurrent")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    r#"{"content":"Quarterly report","containerTag":"idem-concurrent"}"#,
+                ))
+                .unwrap()
+        };
+
+        let responses =
+            futures::future::join_all((0..8).map(|_| app.clone().oneshot(create()))).await;
+        let mut document_ids = std::collections::HashSet::new();
+        let mut statuses = Vec::new();
+        for response in responses {
+            let response = response.unwrap();
+            statuses.push(response.status());
+            let json = body_json(response).await;
+            document_ids.insert(json["data"]["documentId"].as_str().unwrap().to_string());
+        }
+        assert_eq!(document_ids.len(), 1);
+        // Only the request that created the document gets 202; replays get 200.
+        let accepted = statuses
+            .iter()
+            .filter(|s| **s == StatusCode::ACCEPTED)
+            .count();
+        let replayed = statuses.iter().filter(|s| **s == StatusCode::OK).count();
+        assert_eq!((accepted, replayed), (1, 7));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/documents?containerTags=idem-concurrent")
+                    .header("Authorization", "Bearer key")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let json = body_json(response).await;
+        assert_eq!(json["data"]["documents"].as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn bulk_delete_defaults_to_key_container_tag() {
+        let mut state = test_state(vec![ApiKey::unrestricted("key")]).await;
+        std::sync::Arc::make_mut(&mut state.config)
+            .server
+            .api_key_container_tags
+            .insert("key".to_string(), "tenant-a".to_string());
+        for (id, tag) in [("a1", "tenant-a"), ("a2", "tenant-a"), ("b1", "tenant-b")] {
+            let mut doc = crate::models::Document::new(id.to_string());
+            doc.container_tags = vec![tag.to_string()];
+            state.db.create_document(&doc).await.unwrap();
+        }
+        let db = state.db.clone();
+        let app = create_router(state);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("DELETE")
+                    .uri("/api/v1/documents")
+                    .header("Authorization", "Bearer key")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_json(response).await["data"]["deleted"], 2);
+        assert!(db.get_document_by_id("a1").await.unwrap().is_none());
+        assert!(db.get_document_by_id("b1").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn bulk_delete_rejects_another_tenants_container_tag() {
+        let mut state = test_state(vec![ApiKey::unrestricted("key")]).await;
+        std::sync::Arc::make_mut(&mut state.config)
+            .server
+            .api_key_container_tags
+            .insert("key".to_string(), "tenant-a".to_string());
+        let mut doc = crate::models::Document::new("b1".to_string());
+        doc.container_tags = vec!["tenant-b".to_string()];
+        state.db.create_document(&doc).await.unwrap();
+        let db = state.db.clone();
+        let app = create_router(state);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("DELETE")
+                    .uri("/api/v1/documents?containerTag=tenant-b")
+                    .header("Authorization", "Bearer key")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(body_json(response).await["error"]["code"], "forbidden");
+        assert!(db.get_document_by_id("b1").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn reprocess_rejects_unknown_and_in_flight_documents() {
+        let state = test_state(vec![ApiKey::unrestricted("key")]).await;
+        let mut doc = crate::models::Document::new("busy".to_string());
+        doc.status = crate::models::ProcessingStatus::Embedding;
+        state.db.create_document(&doc).await.unwrap();
+        let doc = crate::models::Document::new("queued".to_string());
+        state.db.create_document(&doc).await.unwrap();
+        let mut doc = crate::models::Document::new("done".to_string());
+        doc.content = Some("Reprocess me".to_string());
+        doc.status = crate::models::ProcessingStatus::Done;
+        state.db.create_document(&doc).await.unwrap();
+        let db = state.db.clone();
+        let app = create_router(state);
+
+        let reprocess = |id: &str| {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/api/v1/documents/{id}/reprocess"))
+                .header("Authorization", "Bearer key")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(reprocess("missing")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = app.clone().oneshot(reprocess("busy")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let response = app.clone().oneshot(reprocess("queued")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let response = app.oneshot(reprocess("done")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let json = body_json(response).await;
+        let job_id = json["data"]["jobId"].as_str().unwrap();
+        let job = db.get_job(job_id).await.unwrap().unwrap();
+        assert_eq!(job.kind, crate::models::JobKind::Reprocess);
+        assert_eq!(job.total, Some(1));
+    }
+
+    #[tokio::test]
+    async fn deleting_a_queued_upload_removes_its_file() {
+        let upload_dir = tempfile::tempdir().unwrap();
+        let mut state = test_state(vec![ApiKey::unrestricted("key")]).await;
+        std::sync::Arc::make_mut(&mut state.config)
+            .transcription
+            .upload_dir = Some(upload_dir.path().to_string_lossy().into_owned());
+        let mut doc = crate::models::Document::new("clip".to_string());
+        doc.content = Some(crate::processing::media_upload::reference("clip"));
+        state.db.create_document(&doc).await.unwrap();
+        let file = upload_dir.path().join("clip");
+        std::fs::write(&file, b"ID3").unwrap();
+        let app = create_router(state);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("DELETE")
+                    .uri("/api/v1/documents/clip")
+                    .header("Authorization", "Bearer key")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!file.exists());
+    }
+
+    fn json_request(method: &str, uri: &str, body: &str) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Authorization", "Bearer key")
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn create_memory_stores_static_flag_and_confidence() {
+        let state = test_state(vec![ApiKey::unrestricted("key")]).await;
+        let db = state.db.clone();
+        let app = create_router(state);
+
+        let response = app
+            .clone()
+            .oneshot(json_request(
+                "POST",
+                "/api/v1/memories",
+                r#"{"content":"User lives in Berlin","containerTag":"user_1","isStatic":true,"confidence":0.8}"#,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let json = body_json(response).await;
+        assert_eq!(json["data"]["isStatic"], true);
+        assert_eq!(json["data"]["confidence"], 0.8);
+
+        let id = json["data"]["memoryId"].as_str().unwrap();
+        let stored = db.get_memory_by_id(id).await.unwrap().unwrap();
+        assert!(stored.is_static);
+        assert_eq!(stored.confidence, Some(0.8));
+
+        let response = app
+            .oneshot(json_request(
+                "POST",
+                "/api/v1/memories",
+                r#"{"content":"User lives in Berlin","containerTag":"user_1","confidence":1.5}"#,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn create_memory_with_ttl_schedules_forgetting() {
+        let state = test_state(vec![ApiKey::unrestricted("key")]).await;
+        let db = state.db.clone();
+        let app = create_router(state);
+
+        let response = app
+            .clone()
+            .oneshot(json_request(
+                "POST",
+                "/api/v1/memories",
+                r#"{"content":"Meeting at 3pm tomorrow","containerTag":"user_1","ttlDays":1}"#,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let json = body_json(response).await;
+        let id = json["data"]["memoryId"].as_str().unwrap();
+        let forget_after = db
+            .get_memory_by_id(id)
+            .await
+            .unwrap()
+            .unwrap()
+            .forget_after
+            .expect("forget_after set");
+        assert!(forget_after > chrono::Utc::now() + chrono::Duration::hours(23));
+
+        for body in [
+            r#"{"content":"Old","containerTag":"user_1","forgetAfter":"2020-01-01T00:00:00Z"}"#,
+            r#"{"content":"Both","containerTag":"user_1","forgetAfter":"2999-01-01T00:00:00Z","ttlDays":1}"#,
+            r#"{"content":"Zero","containerTag":"user_1","ttlDays":0}"#,
+        ] {
+            let response = app
+                .clone()
+                .oneshot(json_request("POST", "/api/v1/memories", body))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{body}");
+        }
+    }
+
+    #[tokio::test]
+    async fn update_memory_versions_and_rejects_forgotten_memories() {
+        let state = test_state(vec![ApiKey::unrestricted("key")]).await;
+        let mut memory = crate::models::Memory::new(
+            "mem_live".to_string(),
+            "User lives in Paris".to_string(),
+            "default".to_string(),
+        );
+        memory.container_tag = Some("user_1".to_string());
+        state.db.create_memory(&memory).await.unwrap();
+        memory.id = "mem_gone".to_string();
+        state.db.create_memory(&memory).await.unwrap();
+        state.db.forget_memory("mem_gone", None).await.unwrap();
+        let db = state.db.clone();
+        let app = create_router(state);
+
+        let edit = r#"{"content":"User lives in Berlin"}"#;
+
+        let response = app
+            .clone()
+            .oneshot(json_request("PATCH", "/api/v1/memories/mem_gone", edit))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let json = body_json(response).await;
+        assert!(json["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("forgotten"));
+
+        let response = app
+            .oneshot(json_request("PATCH", "/api/v1/memories/mem_live", edit))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let json = body_json(response).await;
+        assert_eq!(json["data"]["version"], 2);
+        assert_eq!(json["data"]["parentMemoryId"], "mem_live");
+
+        let previous = db.get_memory_by_id("mem_live").await.unwrap().unwrap();
+        assert!(!previous.is_latest);
+        assert_eq!(previous.memory, "User lives in Paris");
+    }
+
+    #[tokio::test]
+    async fn container_export_streams_memories_then_documents() {
+        let state = test_state(vec![ApiKey::unrestricted("key")]).await;
+        for (id, tag) in [
+            ("mem_a", "user_1"),
+            ("mem_b", "user_1"),
+            ("mem_c", "user_2"),
+        ] {
+            let mut memory = crate::models::Memory::new(
+                id.to_string(),
+                format!("Memory {id}"),
+                "default".to_string(),
+            );
+            memory.container_tag = Some(tag.to_string());
+            state.db.create_memory(&memory).await.unwrap();
+        }
+        state.db.forget_memory("mem_b", None).await.unwrap();
+        let mut doc = crate::models::Document::new("doc1".to_string());
+        doc.container_tags = vec!["user_1".to_string()];
+        state.db.create_document(&doc).await.unwrap();
+        let db = state.db.clone();
+        let app = create_router(state);
+
+        let export = |query: &str| {
+            Request::builder()
+                .uri(format!("/api/v1/admin/containers/user_1/export{query}"))
+                .header("Authorization", "Bearer key")
+                .body(Body::empty())
+                .unwrap()
+        };
+        let records = |response: axum::response::Response| async move {
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            String::from_utf8(bytes.to_vec())
+                .unwrap()
+                .lines()
+                .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        let response = app.clone().oneshot(export("")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "application/x-ndjson");
+        let job_id = response.headers()["x-momo-job-id"]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let lines = records(response).await;
+        let job = db.get_job(&job_id).await.unwrap().unwrap();
+        assert_eq!(job.kind, crate::models::JobKind::ContainerExport);
+        assert_eq!(job.status, crate::models::JobStatus::Done);
+        assert_eq!(
+            job.result,
+            Some(serde_json::json!({"memories": 2, "documents": 0}))
+        );
+        let ids: Vec<&str> = lines
+            .iter()
+            .map(|line| line["memoryId"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&"mem_a") && ids.contains(&"mem_b"));
+        assert!(lines.iter().all(|line| line["type"] == "memory"));
+        assert!(lines.iter().all(|line| line.get("embedding").is_none()));
+
+        let response = app
+            .oneshot(export("?includeDocuments=true&includeEmbeddings=true"))
+            .await
+            .unwrap();
+        let lines = records(response).await;
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[2]["type"], "document");
+        assert_eq!(lines[2]["documentId"], "doc1");
+    }
+
+    #[tokio::test]
+    async fn document_events_stream_status_until_final() {
+        let state = test_state(vec![ApiKey::unrestricted("key")]).await;
+        let doc = crate::models::Document::new("doc1".to_string());
+        state.db.create_document(&doc).await.unwrap();
+        let events = state.status_events.clone();
+        let app = create_router(state);
+
+        let request = |id: &str| {
+            Request::builder()
+                .uri(format!("/api/v1/documents/{id}/events"))
+                .header("Authorization", "Bearer key")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(request("missing")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = app.oneshot(request("doc1")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "text/event-stream");
+        events.publish("other", ProcessingStatus::Chunking, None);
+        events.publish("doc1", ProcessingStatus::Extracting, None);
+        events.publish("doc1", ProcessingStatus::Done, None);
+        events.publish("doc1", ProcessingStatus::Failed, Some("not sent"));
+
+        // Completes only because the stream closes after `done`.
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let statuses: Vec<serde_json::Value> = String::from_utf8(bytes.to_vec())
+            .unwrap()
+            .lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .map(|data| serde_json::from_str::<serde_json::Value>(data).unwrap()["status"].clone())
+            .collect();
+        assert_eq!(statuses, vec!["queued", "extracting", "done"]);
+    }
+
     #[tokio::test]
     async fn health_is_public() {
-        let app = create_router(test_state(vec!["secret".to_string()]).await);
+        let app = create_router(test_state(vec![ApiKey::unrestricted("secret")]).await);
 
         let response = app
             .oneshot(
@@ -138,9 +727,98 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn shallow_health_skips_probes() {
+        let app = create_router(test_state(vec![]).await);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/health")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let json = body_json(response).await;
+        assert_eq!(json["data"]["status"], "ok");
+        assert_eq!(json["data"]["database"]["status"], "unchecked");
+        assert_eq!(json["data"]["embeddings"]["status"], "unchecked");
+        assert!(json["data"].get("keywordIndex").is_none());
+    }
+
+    #[tokio::test]
+    async fn deep_health_probes_subsystems() {
+        let app = create_router(test_state(vec![]).await);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/health?deep=true")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let json = body_json(response).await;
+        assert_eq!(json["data"]["status"], "ok");
+        assert_eq!(json["data"]["database"]["status"], "ok");
+        assert_eq!(json["data"]["embeddings"]["status"], "ok");
+        assert_eq!(json["data"]["keywordIndex"]["status"], "ok");
+    }
+
+    #[tokio::test]
+    async fn readiness_tracks_startup_and_shutdown() {
+        let state = test_state(vec![ApiKey::unrestricted("secret")]).await;
+        let readiness = state.readiness.clone();
+        let app = create_router(state);
+        let probe = |uri: &'static str| {
+            let app = app.clone();
+            async move {
+                let response = app
+                    .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                    .await
+                    .unwrap();
+                let status = response.status();
+                (status, body_json(response).await["data"]["status"].clone())
+            }
+        };
+
+        assert_eq!(
+            probe("/api/v1/health/ready").await,
+            (StatusCode::SERVICE_UNAVAILABLE, "starting".into())
+        );
+        assert_eq!(
+            probe("/api/v1/health/live").await,
+            (StatusCode::OK, "alive".into())
+        );
+
+        readiness.mark_ready();
+        assert_eq!(
+            probe("/api/v1/health/ready").await,
+            (StatusCode::OK, "ready".into())
+        );
+
+        readiness.mark_shutting_down();
+        assert_eq!(
+            probe("/api/v1/health/ready").await,
+            (StatusCode::SERVICE_UNAVAILABLE, "shutting_down".into())
+        );
+        readiness.mark_ready();
+        assert_eq!(
+            probe("/api/v1/health/ready").await.0,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(probe("/api/v1/health/live").await.0, StatusCode::OK);
+    }
+
     #[tokio::test]
     async fn openapi_json_is_public_and_valid() {
-        let app = create_router(test_state(vec!["secret".to_string()]).await);
+        let app = create_router(test_state(vec![ApiKey::unrestricted("secret")]).await);
 
         let response = app
             .oneshot(
@@ -165,7 +843,7 @@ mod tests {
 
     #[tokio::test]
     async fn success_envelope_has_data_no_error() {
-        let app = create_router(test_state(vec!["k".to_string()]).await);
+        let app = create_router(test_state(vec![ApiKey::unrestricted("k")]).await);
 
         let response = app
             .oneshot(
@@ -186,9 +864,74 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn oversized_body_returns_payload_too_large_envelope() {
+        let mut state = test_state(vec![ApiKey::unrestricted("key")]).await;
+        std::sync::Arc::make_mut(&mut state.config)
+            .server
+            .max_request_body_bytes = 1024;
+        let app = create_router(state);
+
+        let body = serde_json::json!({ "q": "x".repeat(4096) }).to_string();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/search")
+                    .header("Authorization", "Bearer key")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        let json = body_json(response).await;
+        assert_eq!(json["error"]["code"], "payload_too_large");
+        assert!(json["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("1024 byte limit"));
+        assert!(json.get("data").is_none());
+    }
+
+    #[tokio::test]
+    async fn oversized_media_upload_returns_payload_too_large() {
+        let upload_dir = tempfile::tempdir().unwrap();
+        let mut state = test_state(vec![ApiKey::unrestricted("key")]).await;
+        let config = std::sync::Arc::make_mut(&mut state.config);
+        config.transcription.max_file_size = 16;
+        config.transcription.upload_dir = Some(upload_dir.path().to_string_lossy().into_owned());
+        let app = create_router(state);
+
+        let body = format!(
+            "--boundary\r\nContent-Disposition: form-data; name=\"file\"; filename=\"clip.mp3\"\r\n\
+             Content-Type: audio/mpeg\r\n\r\n{}\r\n--boundary--\r\n",
+            "x".repeat(1024)
+        );
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/documents:uploadMedia")
+                    .header("Authorization", "Bearer key")
+                    .header("content-type", "multipart/form-data; boundary=boundary")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        let json = body_json(response).await;
+        assert_eq!(json["error"]["code"], "payload_too_large");
+        assert_eq!(std::fs::read_dir(upload_dir.path()).unwrap().count(), 0);
+    }
+
     #[tokio::test]
     async fn error_envelope_has_error_no_data() {
-        let app = create_router(test_state(vec!["key".to_string()]).await);
+        let app = create_router(test_state(vec![ApiKey::unrestricted("key")]).await);
 
         let response = app
             .oneshot(
@@ -15,27 +15,57 @@ use super::response;
     ),
     paths(
         handlers::health::health_check,
+        handlers::health::liveness,
+        handlers::health::readiness,
         handlers::documents::create_document,
         handlers::documents::batch_create_documents,
         handlers::documents::upload_document,
+        handlers::documents::upload_media,
         handlers::documents::get_document,
         handlers::documents::update_document,
+        handlers::documents::reprocess_document,
+        handlers::documents::document_events,
+        handlers::documents::update_chunk,
         handlers::documents::delete_document,
+        handlers::documents::delete_documents,
         handlers::documents::list_documents,
         handlers::documents::get_ingestion_status,
         handlers::search::search,
+        handlers::search::similar_memories,
+        handlers::search::batch_search,
+        handlers::search::stream_search,
         handlers::memories::create_memory,
         handlers::memories::get_memory,
         handlers::memories::update_memory,
         handlers::memories::delete_memory,
+        handlers::memories::restore_memory,
         handlers::memories::list_memories,
         handlers::memories::forget_memory,
         handlers::graph::get_memory_graph,
         handlers::graph::get_container_graph,
+        handlers::graph::find_memory_path,
         handlers::graph::list_container_tags,
         handlers::admin::run_forgetting,
+        handlers::admin::preview_forgetting,
+        handlers::admin::run_inference,
+        handlers::admin::reembed_chunks,
+        handlers::admin::list_contradictions,
+        handlers::admin::resolve_contradiction,
+        handlers::admin::get_container_settings,
+        handlers::admin::update_container_settings,
+        handlers::admin::detect_relations,
+        handlers::admin::export_container,
+        handlers::admin::create_webhook,
+        handlers::admin::list_webhooks,
+        handlers::admin::delete_webhook,
+        handlers::admin::processing_stats,
+        handlers::admin::list_dead_letter_documents,
+        handlers::admin::requeue_document,
+        handlers::admin::replica_status,
         handlers::profile::compute_profile,
+        handlers::profile::get_profile,
         handlers::conversation::ingest_conversation,
+        handlers::jobs::get_job,
     ),
     components(schemas(
         // Response envelope
@@ -47,18 +77,28 @@ use super::response;
         dto::common::IngestionStatus,
         dto::common::V1DocumentType,
         dto::common::V1MemoryType,
+        dto::common::V1ChunkerType,
         // Documents
         dto::documents::CreateDocumentRequest,
         dto::documents::BatchCreateDocumentRequest,
         dto::documents::BatchDocumentItem,
         dto::documents::UpdateDocumentRequest,
+        dto::documents::UpdateChunkRequest,
+        dto::documents::ReprocessDocumentRequest,
         dto::documents::ListDocumentsQuery,
+        dto::documents::DeleteDocumentsQuery,
+        dto::documents::DeleteDocumentsRequest,
+        dto::documents::DeleteDocumentsResponse,
         dto::documents::CreateDocumentResponse,
         dto::documents::BatchCreateDocumentResponse,
+        dto::documents::ReprocessDocumentResponse,
         dto::documents::DocumentResponse,
+        dto::documents::ExtractionConfidenceResponse,
         dto::documents::DocumentSummaryResponse,
         dto::documents::ListDocumentsResponse,
         dto::documents::IngestionStatusResponse,
+        dto::documents::DocumentStatusEventResponse,
+        dto::documents::ChunkResponse,
         // Memories
         dto::memories::CreateMemoryRequest,
         dto::memories::UpdateMemoryRequest,
@@ -71,8 +111,22 @@ use super::response;
         dto::memories::ListMemoriesResponse,
         // Search
         dto::search::SearchScope,
+        dto::search::SkippedStage,
+        dto::search::SearchFallback,
+        dto::search::ScoreCalibration,
+        dto::search::HybridWeights,
+        dto::search::SearchGroupBy,
+        dto::search::SearchGroupType,
+        dto::search::SearchResultGroup,
         dto::search::SearchIncludeFlags,
+        dto::search::DecayDebug,
         dto::search::SearchRequest,
+        dto::search::SimilarMemoriesQuery,
+        dto::search::BatchSearchQuery,
+        dto::search::BatchSearchRequest,
+        dto::search::BatchSearchResponse,
+        dto::search::StreamSearchRequest,
+        dto::search::SearchStreamEnd,
         dto::search::SearchResponse,
         dto::search::SearchResultItem,
         dto::search::DocumentSearchResult,
@@ -81,6 +135,8 @@ use super::response;
         dto::search::HybridSearchResultResponse,
         // Profile
         dto::profile::ComputeProfileRequest,
+        dto::profile::GetProfileQuery,
+        dto::profile::ProfileFactSort,
         dto::profile::ProfileResponse,
         dto::profile::ProfileFactResponse,
         // Conversation
@@ -90,18 +146,54 @@ use super::response;
         // Graph
         dto::graph::GraphNodeType,
         dto::graph::GraphEdgeType,
+        dto::graph::GraphDirection,
+        dto::graph::GraphCentrality,
+        dto::graph::GraphFormat,
         dto::graph::GraphNodeResponse,
         dto::graph::GraphEdgeResponse,
         dto::graph::GraphResponse,
         dto::graph::ContainerTagsResponse,
         // Admin
         dto::admin::ForgettingRunResponse,
+        dto::admin::ForgettingAction,
+        dto::admin::ForgettingPreviewItem,
+        dto::admin::ForgettingPreviewResponse,
+        dto::admin::InferenceRunResponse,
+        dto::admin::ListContradictionsQuery,
+        dto::admin::ContradictionDecision,
+        dto::admin::ResolveContradictionRequest,
+        dto::admin::ContradictionResponse,
+        dto::admin::ListContradictionsResponse,
+        dto::admin::ResolveContradictionResponse,
+        dto::admin::UpdateContainerSettingsRequest,
+        dto::admin::ContainerSettingsResponse,
+        dto::admin::DetectRelationsQuery,
+        dto::admin::DetectRelationsResponse,
+        dto::admin::ExportContainerQuery,
+        dto::admin::ContainerExportRecord,
+        dto::admin::ExportedMemory,
+        dto::admin::CreateWebhookRequest,
+        dto::admin::ListWebhooksQuery,
+        dto::admin::WebhookResponse,
+        dto::admin::ListWebhooksResponse,
+        dto::admin::ProcessingStatsResponse,
+        dto::admin::ProcessingDurationsResponse,
+        dto::admin::DeadLetterDocumentResponse,
+        dto::admin::ListDeadLetterDocumentsResponse,
+        dto::admin::ReplicaStatusResponse,
+        // Jobs
+        dto::jobs::JobType,
+        dto::jobs::JobState,
+        dto::jobs::JobResponse,
+        dto::jobs::JobAcceptedResponse,
         // Health (handler-local types)
         handlers::health::HealthData,
         handlers::health::DatabaseStatus,
         handlers::health::EmbeddingsStatus,
         handlers::health::LlmStatus,
         handlers::health::RerankerStatus,
+        handlers::health::KeywordIndexStatus,
+        handlers::health::ProbeStatus,
     )),
     tags(
         (name = "health", description = "Health check"),
@@ -111,6 +203,7 @@ use super::response;
         (name = "graph", description = "Knowledge graph exploration"),
         (name = "profile", description = "User profile computation"),
         (name = "conversation", description = "Conversation ingestion and memory extraction"),
+        (name = "jobs", description = "Status of long-running background operations"),
         (name = "admin", description = "Administrative operations (auth required)"),
     ),
     security(
@@ -21,6 +21,11 @@
 //!
 //! Cursors are opaque base64-encoded strings. Clients must not parse or
 //! construct them. An invalid cursor returns `400 invalid_request`.
+//!
+//! ## Versioning
+//!
+//! The envelope shape is versioned independently of handlers; see
+//! [`super::versioning`] for content negotiation.
 
 use axum::{
     http::StatusCode,
@@ -31,6 +36,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::error::MomoError;
 
+use super::versioning::{Envelope, EnvelopeVersion};
+
 /// Machine-readable error code included in every error response.
 ///
 /// Serialized as a snake_case string on the wire (e.g. `"invalid_request"`).
@@ -44,10 +51,18 @@ pub enum ErrorCode {
     /// Authentication is required or the provided credentials are invalid.
     /// HTTP 401.
     Unauthorized,
+    /// The API key is valid but lacks the scope the endpoint requires.
+    /// HTTP 403.
+    Forbidden,
     /// The requested resource does not exist. HTTP 404.
     NotFound,
     /// The request conflicts with the current state of the resource. HTTP 409.
     Conflict,
+    /// The resource has not been embedded yet, so it cannot be used for
+    /// similarity search. HTTP 409.
+    EmbeddingPending,
+    /// The request body is larger than `MOMO_MAX_REQUEST_BODY_BYTES`. HTTP 413.
+    PayloadTooLarge,
     /// An unexpected server-side error occurred. Internal details are never
     /// leaked to the client. HTTP 500.
     InternalError,
@@ -61,8 +76,10 @@ impl ErrorCode {
         match self {
             Self::InvalidRequest => StatusCode::BAD_REQUEST,
             Self::Unauthorized => StatusCode::UNAUTHORIZED,
+            Self::Forbidden => StatusCode::FORBIDDEN,
             Self::NotFound => StatusCode::NOT_FOUND,
-            Self::Conflict => StatusCode::CONFLICT,
+            Self::Conflict | Self::EmbeddingPending => StatusCode::CONFLICT,
+            Self::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
             Self::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
             Self::NotImplemented => StatusCode::NOT_IMPLEMENTED,
         }
@@ -74,8 +91,11 @@ impl std::fmt::Display for ErrorCode {
         match self {
             Self::InvalidRequest => write!(f, "invalid_request"),
             Self::Unauthorized => write!(f, "unauthorized"),
+            Self::Forbidden => write!(f, "forbidden"),
             Self::NotFound => write!(f, "not_found"),
             Self::Conflict => write!(f, "conflict"),
+            Self::EmbeddingPending => write!(f, "embedding_pending"),
+            Self::PayloadTooLarge => write!(f, "payload_too_large"),
             Self::InternalError => write!(f, "internal_error"),
             Self::NotImplemented => write!(f, "not_implemented"),
         }
@@ -221,6 +241,18 @@ impl<T: Serialize> ApiResponse<T> {
         }
     }
 
+    /// Service unavailable response that still carries data (HTTP 503).
+    ///
+    /// Used by the deep health check so callers see which subsystem failed.
+    pub fn unavailable(data: T) -> Self {
+        Self {
+            data: Some(data),
+            meta: None,
+            error: None,
+            status: StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+
     /// Error response. HTTP status is derived from the [`ErrorCode`].
     pub fn error(code: ErrorCode, message: impl Into<String>) -> Self {
         let status = code.status();
@@ -237,24 +269,31 @@ impl<T: Serialize> ApiResponse<T> {
 }
 
 impl<T: Serialize> IntoResponse for ApiResponse<T> {
+    /// Render the envelope in the default version. The raw [`Envelope`] is
+    /// attached as a response extension so the versioning middleware can
+    /// re-render it for the version the client negotiated.
     fn into_response(self) -> Response {
         let status = self.status;
-        match serde_json::to_value(&self) {
-            Ok(body) => (status, Json(body)).into_response(),
+        let data = match self.data.as_ref().map(serde_json::to_value).transpose() {
+            Ok(data) => data,
             Err(_) => {
-                let fallback = ApiResponse::<()>::error(
+                return ApiResponse::<()>::error(
                     ErrorCode::InternalError,
                     "An internal error occurred",
-                );
-                let body = serde_json::json!({
-                    "error": {
-                        "code": "internal_error",
-                        "message": "An internal error occurred"
-                    }
-                });
-                (fallback.status, Json(body)).into_response()
+                )
+                .into_response();
             }
-        }
+        };
+
+        let envelope = Envelope {
+            data,
+            meta: self.meta,
+            error: self.error,
+        };
+        let body = EnvelopeVersion::default().render(&envelope);
+        let mut response = (status, Json(body)).into_response();
+        response.extensions_mut().insert(envelope);
+        response
     }
 }
 
@@ -272,6 +311,8 @@ impl<T: Serialize> From<MomoError> for ApiResponse<T> {
                 ApiResponse::error(ErrorCode::InvalidRequest, msg.clone())
             }
 
+            MomoError::Conflict(ref msg) => ApiResponse::error(ErrorCode::Conflict, msg.clone()),
+
             MomoError::ApiAuth(_) => {
                 ApiResponse::error(ErrorCode::Unauthorized, "Authentication required")
             }
@@ -321,6 +362,7 @@ impl<T: Serialize> From<MomoError> for ApiResponse<T> {
             | MomoError::Ocr(_)
             | MomoError::Transcription(_)
             | MomoError::Llm(_)
+            | MomoError::LlmTransient(_)
             | MomoError::Reranker(_)) => {
                 tracing::error!(error = %internal, "Internal error mapped to v1 response");
                 ApiResponse::error(ErrorCode::InternalError, "An internal error occurred")
@@ -379,8 +421,14 @@ mod tests {
     fn error_code_status_mapping() {
         assert_eq!(ErrorCode::InvalidRequest.status(), StatusCode::BAD_REQUEST);
         assert_eq!(ErrorCode::Unauthorized.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(ErrorCode::Forbidden.status(), StatusCode::FORBIDDEN);
         assert_eq!(ErrorCode::NotFound.status(), StatusCode::NOT_FOUND);
         assert_eq!(ErrorCode::Conflict.status(), StatusCode::CONFLICT);
+        assert_eq!(ErrorCode::EmbeddingPending.status(), StatusCode::CONFLICT);
+        assert_eq!(
+            ErrorCode::PayloadTooLarge.status(),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
         assert_eq!(
             ErrorCode::InternalError.status(),
             StatusCode::INTERNAL_SERVER_ERROR
@@ -401,6 +449,9 @@ mod tests {
 
         let json = serde_json::to_value(&ErrorCode::NotImplemented).expect("serialize");
         assert_eq!(json, "not_implemented");
+
+        let json = serde_json::to_value(&ErrorCode::EmbeddingPending).expect("serialize");
+        assert_eq!(json, "embedding_pending");
     }
 
     #[test]
@@ -457,6 +508,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn momo_error_conflict_maps_to_409() {
+        let resp: ApiResponse<()> = MomoError::Conflict("stale version".into()).into();
+        assert_eq!(resp.status, StatusCode::CONFLICT);
+        assert_eq!(
+            resp.error.as_ref().expect("error").code,
+            ErrorCode::Conflict
+        );
+    }
+
     #[test]
     fn momo_error_internal_does_not_leak() {
         let resp: ApiResponse<()> = MomoError::Internal("secret debug info".into()).into();
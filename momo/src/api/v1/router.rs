@@ -1,25 +1,45 @@
 use axum::{
+    extract::DefaultBodyLimit,
     middleware,
-    routing::{get, post},
+    routing::{delete, get, patch, post},
     Router,
 };
 
 use crate::api::state::AppState;
+use crate::config::ApiScope;
 
 use super::handlers;
-use super::middleware::v1_auth_middleware;
+use super::middleware::{
+    payload_too_large_envelope, require_method_scope, require_scope, v1_auth_middleware,
+};
+use super::versioning::envelope_version_middleware;
 
 pub fn v1_router(state: AppState) -> Router<AppState> {
+    let max_body_bytes = state.config.server.max_request_body_bytes;
     let documents = Router::new()
         .route(
             "/",
-            get(handlers::documents::list_documents).post(handlers::documents::create_document),
+            get(handlers::documents::list_documents)
+                .post(handlers::documents::create_document)
+                .delete(handlers::documents::delete_documents),
         )
         .route(
             "/{documentId}",
             get(handlers::documents::get_document)
                 .patch(handlers::documents::update_document)
                 .delete(handlers::documents::delete_document),
+        )
+        .route(
+            "/{documentId}/events",
+            get(handlers::documents::document_events),
+        )
+        .route(
+            "/{documentId}/reprocess",
+            post(handlers::documents::reprocess_document),
+        )
+        .route(
+            "/{documentId}/chunks/{chunkId}",
+            patch(handlers::documents::update_chunk),
         );
 
     let ingestions = Router::new().route(
@@ -38,17 +58,83 @@ pub fn v1_router(state: AppState) -> Router<AppState> {
                 .patch(handlers::memories::update_memory)
                 .delete(handlers::memories::delete_memory),
         )
+        .route(
+            "/{memoryId}/restore",
+            post(handlers::memories::restore_memory),
+        )
         .route("/{memoryId}/graph", get(handlers::graph::get_memory_graph));
-    let search = Router::new().route("/", post(handlers::search::search));
+    let search = Router::new()
+        .route("/", post(handlers::search::search))
+        .route("/batch", post(handlers::search::batch_search))
+        .route("/stream", post(handlers::search::stream_search))
+        .route(
+            "/similar/{memoryId}",
+            get(handlers::search::similar_memories),
+        );
     let containers = Router::new()
         .route("/tags", get(handlers::graph::list_container_tags))
         .route("/{tag}/graph", get(handlers::graph::get_container_graph));
     let public_routes = Router::new()
         .route("/health", get(handlers::health_check))
+        .route("/health/live", get(handlers::liveness))
+        .route("/health/ready", get(handlers::readiness))
         .route("/openapi.json", get(super::openapi::openapi_json))
         .merge(super::openapi::redoc_router());
 
-    let protected_routes = Router::new()
+    let admin = Router::new()
+        .route("/forgetting:run", post(handlers::admin::run_forgetting))
+        .route(
+            "/forgetting/preview",
+            get(handlers::admin::preview_forgetting),
+        )
+        .route("/inference:run", post(handlers::admin::run_inference))
+        .route("/chunks:reembed", post(handlers::admin::reembed_chunks))
+        .route("/contradictions", get(handlers::admin::list_contradictions))
+        .route(
+            "/contradictions/resolve",
+            post(handlers::admin::resolve_contradiction),
+        )
+        .route(
+            "/containers/{tag}/settings",
+            get(handlers::admin::get_container_settings)
+                .put(handlers::admin::update_container_settings),
+        )
+        .route(
+            "/containers/{tag}/detect-relations",
+            post(handlers::admin::detect_relations),
+        )
+        .route(
+            "/containers/{tag}/export",
+            get(handlers::admin::export_container),
+        )
+        .route(
+            "/webhooks",
+            get(handlers::admin::list_webhooks).post(handlers::admin::create_webhook),
+        )
+        .route(
+            "/webhooks/{webhookId}",
+            delete(handlers::admin::delete_webhook),
+        )
+        .route("/processing/stats", get(handlers::admin::processing_stats))
+        .route(
+            "/documents/dead-letter",
+            get(handlers::admin::list_dead_letter_documents),
+        )
+        .route(
+            "/documents/{documentId}/requeue",
+            post(handlers::admin::requeue_document),
+        )
+        .route("/replica/status", get(handlers::admin::replica_status))
+        .route_layer(middleware::from_fn(|request, next| {
+            require_scope(ApiScope::Admin, request, next)
+        }));
+
+    // Search is POST-based but read-only, so it only needs the `read` scope.
+    let search = search.route_layer(middleware::from_fn(|request, next| {
+        require_scope(ApiScope::Read, request, next)
+    }));
+
+    let resources = Router::new()
         .nest("/documents", documents)
         .route(
             "/documents:batch",
@@ -58,21 +144,38 @@ pub fn v1_router(state: AppState) -> Router<AppState> {
             "/documents:upload",
             post(handlers::documents::upload_document),
         )
+        .route(
+            "/documents:uploadMedia",
+            post(handlers::documents::upload_media).layer(DefaultBodyLimit::disable()),
+        )
         .route("/memories:forget", post(handlers::memories::forget_memory))
         .route("/profile:compute", post(handlers::profile::compute_profile))
         .route(
-            "/conversations:ingest",
-            post(handlers::conversation::ingest_conversation),
+            "/profile/{containerTag}",
+            get(handlers::profile::get_profile),
         )
         .route(
-            "/admin/forgetting:run",
-            post(handlers::admin::run_forgetting),
+            "/conversations:ingest",
+            post(handlers::conversation::ingest_conversation),
         )
         .nest("/ingestions", ingestions)
+        .route("/jobs/{jobId}", get(handlers::jobs::get_job))
         .nest("/memories", memories)
-        .nest("/search", search)
         .nest("/containers", containers)
+        .route("/graph/path", get(handlers::graph::find_memory_path))
+        .route_layer(middleware::from_fn(require_method_scope));
+
+    let protected_routes = Router::new()
+        .merge(resources)
+        .nest("/search", search)
+        .nest("/admin", admin)
         .route_layer(middleware::from_fn_with_state(state, v1_auth_middleware));
 
-    Router::new().merge(public_routes).merge(protected_routes)
+    Router::new()
+        .merge(public_routes)
+        .merge(protected_routes)
+        .layer(middleware::from_fn(move |request, next| {
+            payload_too_large_envelope(max_body_bytes, request, next)
+        }))
+        .layer(middleware::from_fn(envelope_version_middleware))
 }
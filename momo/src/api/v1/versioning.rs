@@ -0,0 +1,291 @@
+//! # Response Envelope Versioning
+//!
+//! Handlers return version-agnostic [`ApiResponse`] values; the envelope shape
+//! on the wire is chosen here, at the response layer. Clients pick a version
+//! with either:
+//!
+//! - `Accept: application/vnd.momo.v1+json`, or
+//! - the `apiVersion=1` query parameter (takes precedence over `Accept`).
+//!
+//! Requests without either get the v1 envelope. Every enveloped response
+//! carries a `Momo-Api-Version` header, and requests for a version the server
+//! does not support are rejected with `400 invalid_request`.
+//!
+//! [`ApiResponse::into_response`](super::response::ApiResponse) renders the
+//! default version and attaches the raw [`Envelope`] to the response, so
+//! [`envelope_version_middleware`] can re-render it for another version without
+//! handlers knowing which one was requested.
+
+use axum::{
+    body::Body,
+    http::{header, HeaderMap, HeaderValue, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::{Map, Value};
+
+use super::response::{ApiError, ApiResponse, ErrorCode, ResponseMeta};
+
+/// Vendor media type prefix used for content negotiation.
+const VENDOR_MEDIA_TYPE_PREFIX: &str = "application/vnd.momo.v";
+/// Query parameter that selects the envelope version.
+const VERSION_QUERY_PARAM: &str = "apiVersion";
+/// Response header reporting the envelope version that was rendered.
+pub const API_VERSION_HEADER: &str = "momo-api-version";
+
+/// Version-independent contents of a response envelope.
+#[derive(Debug, Clone)]
+pub struct Envelope {
+    pub data: Option<Value>,
+    pub meta: Option<ResponseMeta>,
+    pub error: Option<ApiError>,
+}
+
+/// Wire format version of the response envelope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EnvelopeVersion {
+    /// `{ "data", "meta", "error" }`, each omitted when absent.
+    #[default]
+    V1,
+}
+
+impl EnvelopeVersion {
+    fn from_number(version: &str) -> Option<Self> {
+        match version.trim() {
+            "1" => Some(Self::V1),
+            _ => None,
+        }
+    }
+
+    pub fn number(self) -> &'static str {
+        match self {
+            Self::V1 => "1",
+        }
+    }
+
+    pub fn media_type(self) -> &'static str {
+        match self {
+            Self::V1 => "application/vnd.momo.v1+json",
+        }
+    }
+
+    /// Serialize an envelope in this version's wire format.
+    pub fn render(self, envelope: &Envelope) -> Value {
+        match self {
+            Self::V1 => {
+                let mut body = Map::new();
+                if let Some(data) = &envelope.data {
+                    body.insert("data".to_string(), data.clone());
+                }
+                if let Some(meta) = &envelope.meta {
+                    body.insert(
+                        "meta".to_string(),
+                        serde_json::to_value(meta).unwrap_or(Value::Null),
+                    );
+                }
+                if let Some(error) = &envelope.error {
+                    body.insert(
+                        "error".to_string(),
+                        serde_json::to_value(error).unwrap_or(Value::Null),
+                    );
+                }
+                Value::Object(body)
+            }
+        }
+    }
+}
+
+/// Version requested by a client, and whether it asked for the vendor media type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestedVersion {
+    pub version: EnvelopeVersion,
+    pub vendor_media_type: bool,
+}
+
+/// Determine the envelope version from the query string and `Accept` header.
+///
+/// Returns the unsupported version string on failure.
+pub fn negotiate(
+    query: Option<&str>,
+    headers: &HeaderMap,
+) -> std::result::Result<RequestedVersion, String> {
+    let from_accept = headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|media_range| {
+            media_range
+                .split(';')
+                .next()
+                .map(str::trim)
+                .and_then(|media_type| media_type.strip_prefix(VENDOR_MEDIA_TYPE_PREFIX))
+                .and_then(|rest| rest.strip_suffix("+json"))
+        })
+        .next();
+
+    let from_query = query.and_then(|query| {
+        url::form_urlencoded::parse(query.as_bytes())
+            .find(|(key, _)| key == VERSION_QUERY_PARAM)
+            .map(|(_, value)| value.into_owned())
+    });
+
+    let requested = from_query.as_deref().or(from_accept);
+    let version = match requested {
+        Some(number) => EnvelopeVersion::from_number(number).ok_or_else(|| number.to_string())?,
+        None => EnvelopeVersion::default(),
+    };
+
+    Ok(RequestedVersion {
+        version,
+        vendor_media_type: from_query.is_none() && from_accept.is_some(),
+    })
+}
+
+/// Axum middleware that renders v1 API envelopes in the version the client
+/// negotiated.
+pub async fn envelope_version_middleware(request: Request<Body>, next: Next) -> Response {
+    let requested = match negotiate(request.uri().query(), request.headers()) {
+        Ok(requested) => requested,
+        Err(version) => {
+            return ApiResponse::<()>::error(
+                ErrorCode::InvalidRequest,
+                format!("Unsupported API version: {version}"),
+            )
+            .into_response();
+        }
+    };
+
+    let mut response = next.run(request).await;
+    let Some(envelope) = response.extensions().get::<Envelope>().cloned() else {
+        return response;
+    };
+
+    if requested.version != EnvelopeVersion::default() {
+        let status = response.status();
+        let mut rerendered = (status, Json(requested.version.render(&envelope))).into_response();
+        rerendered.extensions_mut().insert(envelope);
+        response = rerendered;
+    }
+
+    let headers = response.headers_mut();
+    headers.insert(
+        API_VERSION_HEADER,
+        HeaderValue::from_static(requested.version.number()),
+    );
+    if requested.vendor_media_type {
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static(requested.version.media_type()),
+        );
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use axum::{middleware, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        async fn ok_handler() -> ApiResponse<&'static str> {
+            ApiResponse::success("hello")
+        }
+
+        async fn missing_handler() -> ApiResponse<()> {
+            ApiResponse::error(ErrorCode::NotFound, "gone")
+        }
+
+        Router::new()
+            .route("/ok", get(ok_handler))
+            .route("/missing", get(missing_handler))
+            .layer(middleware::from_fn(envelope_version_middleware))
+    }
+
+    async fn send(uri: &str, accept: Option<&str>) -> Response {
+        let mut request = Request::builder().uri(uri);
+        if let Some(accept) = accept {
+            request = request.header(header::ACCEPT, accept);
+        }
+        app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn body_json(response: Response) -> Value {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn v1_envelope_is_returned_by_default() {
+        let response = send("/ok", None).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[API_VERSION_HEADER], "1");
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+        assert_eq!(
+            body_json(response).await,
+            serde_json::json!({"data": "hello"})
+        );
+    }
+
+    #[tokio::test]
+    async fn v1_envelope_is_returned_for_explicit_v1_requests() {
+        let response = send("/ok", Some("application/vnd.momo.v1+json")).await;
+        assert_eq!(response.headers()[API_VERSION_HEADER], "1");
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/vnd.momo.v1+json"
+        );
+        assert_eq!(
+            body_json(response).await,
+            serde_json::json!({"data": "hello"})
+        );
+
+        let response = send("/missing?apiVersion=1", None).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.headers()[API_VERSION_HEADER], "1");
+        assert_eq!(
+            body_json(response).await,
+            serde_json::json!({"error": {"code": "not_found", "message": "gone"}})
+        );
+    }
+
+    #[tokio::test]
+    async fn unsupported_version_is_rejected() {
+        let response = send("/ok", Some("application/vnd.momo.v2+json")).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let json = body_json(response).await;
+        assert_eq!(json["error"]["code"], "invalid_request");
+        assert!(json["error"]["message"].as_str().unwrap().contains('2'));
+
+        let response = send("/ok?apiVersion=9", None).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn query_parameter_takes_precedence_over_accept() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::ACCEPT,
+            HeaderValue::from_static("text/html, application/vnd.momo.v7+json;q=0.9"),
+        );
+
+        assert_eq!(negotiate(None, &headers), Err("7".to_string()));
+        assert_eq!(
+            negotiate(Some("limit=5&apiVersion=1"), &headers),
+            Ok(RequestedVersion {
+                version: EnvelopeVersion::V1,
+                vendor_media_type: false,
+            })
+        );
+    }
+}
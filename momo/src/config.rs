@@ -1,7 +1,14 @@
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::env;
 
+use crate::intelligence::examples::ExtractionExample;
+use crate::models::{
+    ChunkStrategy, NearDuplicateAction, PiiAction, PiiCategory, StructuredDataOverflow,
+};
+use crate::services::search_probe::CanaryQuery;
+
 fn parse_env_or<T: std::str::FromStr>(var: &str, default: T) -> T
 where
     T::Err: std::fmt::Display,
@@ -18,6 +25,38 @@ where
     }
 }
 
+/// Parse `MOMO_API_KEYS` env var.
+/// Format: comma-separated keys, each optionally followed by `:scope1+scope2`,
+/// e.g. `ops-key,reader:read,ci:read+write`. See [`ApiKey::parse_list`].
+fn parse_api_keys() -> Vec<ApiKey> {
+    match env::var("MOMO_API_KEYS") {
+        Ok(val) if !val.trim().is_empty() => ApiKey::parse_list(&val),
+        _ => Vec::new(),
+    }
+}
+
+/// Parse `MOMO_API_KEY_CONTAINER_TAGS` env var.
+/// Format: comma-separated `key:tag` pairs, e.g. `key-acme:acme,key-globex:globex`
+fn parse_api_key_container_tags() -> HashMap<String, String> {
+    match env::var("MOMO_API_KEY_CONTAINER_TAGS") {
+        Ok(val) if !val.is_empty() => val
+            .split(',')
+            .filter_map(|pair| {
+                let mut parts = pair.splitn(2, ':');
+                let key = parts.next()?.trim();
+                let tag = parts.next()?.trim();
+                if key.is_empty() || tag.is_empty() {
+                    tracing::warn!("Invalid key/tag pair in MOMO_API_KEY_CONTAINER_TAGS, skipping");
+                    None
+                } else {
+                    Some((key.to_string(), tag.to_string()))
+                }
+            })
+            .collect(),
+        _ => HashMap::new(),
+    }
+}
+
 /// Parse `RERANK_DOMAIN_MODELS` env var.
 /// Format: comma-separated `domain:model` pairs, e.g. `code:jina-reranker-v1-turbo-en,docs:bge-reranker-v2-m3`
 fn parse_domain_models() -> HashMap<String, String> {
@@ -43,6 +82,50 @@ fn parse_domain_models() -> HashMap<String, String> {
     }
 }
 
+/// Parse `MEMORY_CONTAINER_EMBEDDING_MODELS` env var.
+/// Format: comma-separated `tag:model` pairs, e.g. `acme:BAAI/bge-base-en-v1.5,globex:BAAI/bge-small-en-v1.5`
+fn parse_container_embedding_models() -> HashMap<String, String> {
+    match env::var("MEMORY_CONTAINER_EMBEDDING_MODELS") {
+        Ok(val) if !val.is_empty() => val
+            .split(',')
+            .filter_map(|pair| {
+                let mut parts = pair.splitn(2, ':');
+                let tag = parts.next()?.trim();
+                let model = parts.next()?.trim();
+                if tag.is_empty() || model.is_empty() {
+                    tracing::warn!(
+                        "Invalid tag/model pair '{}' in MEMORY_CONTAINER_EMBEDDING_MODELS, skipping",
+                        pair
+                    );
+                    None
+                } else {
+                    Some((tag.to_string(), model.to_string()))
+                }
+            })
+            .collect(),
+        _ => HashMap::new(),
+    }
+}
+
+/// Parse `PII_CATEGORIES` env var.
+/// Format: comma-separated categories, e.g. `email,credit_card`. Defaults to all.
+fn parse_pii_categories() -> Vec<PiiCategory> {
+    match env::var("PII_CATEGORIES") {
+        Ok(val) if !val.trim().is_empty() => val
+            .split(',')
+            .filter(|category| !category.trim().is_empty())
+            .filter_map(|category| match category.parse() {
+                Ok(parsed) => Some(parsed),
+                Err(e) => {
+                    tracing::warn!("{} in PII_CATEGORIES, skipping", e);
+                    None
+                }
+            })
+            .collect(),
+        _ => PiiCategory::ALL.to_vec(),
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
     pub server: ServerConfig,
@@ -55,13 +138,135 @@ pub struct Config {
     pub transcription: TranscriptionConfig,
     pub llm: Option<LlmConfig>,
     pub reranker: Option<RerankerConfig>,
+    pub search: SearchConfig,
+    pub extraction: ExtractionConfig,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct ServerConfig {
     pub host: String,
     pub port: u16,
-    pub api_keys: Vec<String>,
+    pub api_keys: Vec<ApiKey>,
+    /// Default container tag per API key, applied when a request omits one.
+    pub api_key_container_tags: HashMap<String, String>,
+    /// How long an `Idempotency-Key` on document creation is remembered.
+    pub idempotency_key_ttl_secs: u64,
+    /// Seconds without a status change before a document event stream is closed.
+    pub document_events_idle_timeout_secs: u64,
+    /// Largest request body read into memory; larger bodies get 413.
+    /// Streamed media uploads are exempt.
+    pub max_request_body_bytes: usize,
+}
+
+/// Permission granted to an API key.
+///
+/// Scopes are ordered: `admin` implies `write`, which implies `read`.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiScope {
+    Read,
+    Write,
+    Admin,
+}
+
+impl ApiScope {
+    pub const ALL: [ApiScope; 3] = [ApiScope::Read, ApiScope::Write, ApiScope::Admin];
+}
+
+impl std::fmt::Display for ApiScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read => write!(f, "read"),
+            Self::Write => write!(f, "write"),
+            Self::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+impl std::str::FromStr for ApiScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "read" => Ok(Self::Read),
+            "write" => Ok(Self::Write),
+            "admin" => Ok(Self::Admin),
+            _ => Err(format!("Unknown API key scope: {s}")),
+        }
+    }
+}
+
+/// An API key accepted by the server together with the scopes it grants.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct ApiKey {
+    pub key: String,
+    pub scopes: Vec<ApiScope>,
+}
+
+impl ApiKey {
+    /// A key with every scope, used for keys configured without a scope list.
+    pub fn unrestricted(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            scopes: ApiScope::ALL.to_vec(),
+        }
+    }
+
+    /// Stable, non-secret identifier for this key, safe to store and log.
+    pub fn fingerprint(&self) -> String {
+        let digest = Sha256::digest(self.key.as_bytes());
+        format!("api_key_{:x}", digest)[..24].to_string()
+    }
+
+    /// Parse a comma-separated key list where each key may carry a
+    /// `key:scope1+scope2` scope list.
+    ///
+    /// The scope list follows the last `:`, so keys may themselves contain
+    /// `:` as long as they carry a scope list. Keys without a scope list keep
+    /// full access. Unknown scopes are skipped, and a key left with no valid
+    /// scopes is dropped. A bare entry named like a scope (the old
+    /// `key:read,write` syntax) is dropped rather than becoming a full-access
+    /// key.
+    pub fn parse_list(value: &str) -> Vec<ApiKey> {
+        let mut keys: Vec<ApiKey> = Vec::new();
+
+        for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            if let Some((key, scopes)) = entry.rsplit_once(':') {
+                let mut api_key = ApiKey {
+                    key: key.trim().to_string(),
+                    scopes: Vec::new(),
+                };
+                for scope in scopes.split('+') {
+                    push_scope(&mut api_key, scope);
+                }
+                keys.push(api_key);
+            } else if entry.parse::<ApiScope>().is_ok() {
+                tracing::warn!(
+                    "Bare scope '{}' in MOMO_API_KEYS, skipping; join scopes with '+', e.g. key:read+write",
+                    entry
+                );
+            } else {
+                keys.push(ApiKey::unrestricted(entry));
+            }
+        }
+
+        keys.retain(|key| {
+            let valid = !key.key.is_empty() && !key.scopes.is_empty();
+            if !valid {
+                tracing::warn!("API key without a valid scope in MOMO_API_KEYS, skipping");
+            }
+            valid
+        });
+        keys
+    }
+}
+
+fn push_scope(key: &mut ApiKey, scope: &str) {
+    match scope.parse::<ApiScope>() {
+        Ok(scope) if !key.scopes.contains(&scope) => key.scopes.push(scope),
+        Ok(_) => {}
+        Err(e) => tracing::warn!("{} in MOMO_API_KEYS, skipping", e),
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -80,6 +285,9 @@ pub struct DatabaseConfig {
     pub url: String,
     pub auth_token: Option<String>,
     pub local_path: Option<String>,
+    /// Pooled connections per database; writes share the pool but run one
+    /// at a time.
+    pub max_connections: usize,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -87,6 +295,10 @@ pub struct EmbeddingsConfig {
     pub model: String,
     pub dimensions: usize,
     pub batch_size: usize,
+    /// Query embeddings kept in the search LRU cache; 0 disables the cache.
+    pub query_cache_size: usize,
+    /// `batch_size` groups of one document's chunks embedded at once.
+    pub max_concurrent_batches: usize,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -98,6 +310,18 @@ pub struct OcrConfig {
     pub timeout_secs: u64,
     pub max_image_dimension: u32,
     pub min_image_dimension: u32,
+    /// OCR runs allowed in flight at once across all documents being
+    /// processed; 0 means unlimited.
+    pub max_concurrent_jobs: usize,
+    /// Store recognized text blocks with their bounding boxes in image
+    /// document metadata.
+    pub include_layout: bool,
+    /// Drop OCR words whose confidence (0-1) is below this before the text
+    /// is assembled. Only Tesseract reports word confidence.
+    pub min_confidence: Option<f32>,
+    /// Discard an image's text entirely when its average OCR confidence is
+    /// below this floor, flagging the document instead of indexing noise.
+    pub min_document_confidence: Option<f32>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -109,6 +333,25 @@ pub struct TranscriptionConfig {
     pub timeout_secs: u64,
     pub max_file_size: u64,
     pub max_duration_secs: u64,
+    /// Audio/video transcriptions allowed in flight at once across all
+    /// documents being processed; 0 means unlimited.
+    pub max_concurrent_jobs: usize,
+    /// Where `documents:uploadMedia` streams files until they're transcribed;
+    /// defaults to `momo-uploads` under the system temp directory.
+    pub upload_dir: Option<String>,
+    /// Store the timed (and, where diarized, speaker-labelled) segments of a
+    /// transcript in document metadata.
+    pub include_segments: bool,
+    /// Chunk transcripts between segments, keeping speaker turns together,
+    /// instead of by text size.
+    pub chunk_by_segment: bool,
+    /// Spoken language as an ISO 639-1 code, or `auto` to detect it per
+    /// file; unset leaves it to the backend (Whisper assumes English).
+    pub language: Option<String>,
+    /// Language used in `auto` mode when detection is less confident than
+    /// `language_min_confidence`.
+    pub default_language: String,
+    pub language_min_confidence: f32,
 }
 
 /// LLM configuration for chat/completion models
@@ -130,6 +373,12 @@ pub struct LlmConfig {
     pub enable_contradiction_detection: bool,
     /// Custom prompt template for LLM filtering.
     pub filter_prompt: Option<String>,
+    /// Most LLM requests a single background run (inference, profile refresh)
+    /// may make. `None` means unlimited.
+    pub max_calls_per_run: Option<u64>,
+    /// Most tokens, as reported by the provider, a single background run may
+    /// spend. `None` means unlimited.
+    pub max_tokens_per_run: Option<u64>,
 }
 
 /// Reranker configuration for improving search result ordering
@@ -165,14 +414,141 @@ impl Default for TranscriptionConfig {
             timeout_secs: 300,
             max_file_size: 104857600,
             max_duration_secs: 7200,
+            max_concurrent_jobs: 1,
+            upload_dir: None,
+            include_segments: false,
+            chunk_by_segment: false,
+            language: None,
+            default_language: "en".to_string(),
+            language_min_confidence: 0.5,
         }
     }
 }
 
+/// Request-level search behaviour shared by all search endpoints
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchConfig {
+    /// Overall latency budget for a single search request in milliseconds.
+    /// Optional stages (query rewrite, reranking) are skipped once it is spent.
+    pub max_response_time_ms: Option<u64>,
+    /// Collapse memory search results from the same version chain to the newest version.
+    pub dedupe_version_chains: bool,
+    /// Maximum parents, children and related memories attached to each memory
+    /// search result when related memories are requested.
+    pub max_context_memories: usize,
+    /// Lowest threshold a search asking for `min_results` may be relaxed to.
+    pub min_results_threshold_floor: f32,
+    /// Retry document and memory searches whose vector pass found nothing
+    /// with keyword search.
+    pub lexical_fallback: bool,
+    /// Most keyword hits a lexical fallback fetches.
+    pub lexical_fallback_limit: u32,
+    /// Markers wrapped around matched terms in keyword highlights.
+    pub highlight_open_marker: String,
+    pub highlight_close_marker: String,
+    /// Raw score mapped to 0.5 by sigmoid score calibration.
+    pub score_sigmoid_midpoint: f32,
+    /// Slope of the sigmoid score calibration; higher spreads scores near the
+    /// midpoint further apart.
+    pub score_sigmoid_steepness: f32,
+    /// Days for a memory's recency to halve when a search blends in freshness
+    /// with `freshness_weight`.
+    pub freshness_half_life_days: f64,
+    /// JSON file mapping terms to synonyms, for LLM-free query expansion.
+    pub synonyms_path: Option<String>,
+    /// Synonyms loaded from `synonyms_path`; populated and validated at startup.
+    #[serde(default)]
+    pub synonyms: HashMap<String, Vec<String>>,
+    /// Background search quality probe
+    pub probe: SearchProbeConfig,
+}
+
+/// Canary queries run periodically to track retrieval quality
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchProbeConfig {
+    /// JSON file with `[{"query": ..., "expected_ids": [...]}]` canaries.
+    pub canaries_path: Option<String>,
+    pub interval_secs: u64,
+    /// Number of results inspected per canary.
+    pub top_k: u32,
+    /// Hit rate below which a warning is logged.
+    pub min_hit_rate: f64,
+    /// Mean reciprocal rank below which a warning is logged.
+    pub min_mrr: f64,
+    /// Canaries loaded from `canaries_path`; populated and validated at startup.
+    #[serde(default)]
+    pub canaries: Vec<CanaryQuery>,
+}
+
+/// Few-shot examples injected into memory extraction prompts
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExtractionConfig {
+    /// JSON file with `[{"input": ..., "memories": [...]}]` examples.
+    pub examples_path: Option<String>,
+    /// Upper bound on the estimated prompt tokens the examples may add.
+    pub examples_max_tokens: usize,
+    /// Examples loaded from `examples_path`; populated and validated at startup.
+    #[serde(default)]
+    pub examples: Vec<ExtractionExample>,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct ProcessingConfig {
     pub chunk_size: usize,
     pub chunk_overlap: usize,
+    /// Whether prose is split by size or at semantic breakpoints.
+    pub chunk_strategy: ChunkStrategy,
+    /// Cosine distance between consecutive sentence embeddings above which
+    /// the semantic chunker starts a new chunk.
+    pub semantic_chunk_threshold: f32,
+    /// Smallest semantic chunk; breakpoints are ignored until a chunk reaches
+    /// this size. `chunk_size` is the upper bound.
+    pub semantic_chunk_min_size: usize,
+    /// Measure `chunk_size`/`chunk_overlap` with the embedding model's tokenizer
+    /// instead of the character-based heuristic.
+    pub use_model_tokenizer: bool,
+    /// Generate an LLM summary for documents that arrive without one.
+    pub auto_summary: bool,
+    /// Minimum extracted text length (in characters) before a summary is generated.
+    pub auto_summary_min_chars: usize,
+    /// Prefix each chunk's embedded text with an LLM-generated sentence that
+    /// situates it within its document. Costs one LLM call per new chunk.
+    pub contextual_chunks: bool,
+    /// Similarity above which a new text document counts as a near-duplicate of an
+    /// existing one in the same container. `None` disables the check.
+    pub near_duplicate_threshold: Option<f32>,
+    pub near_duplicate_action: NearDuplicateAction,
+    /// Store per-stage processing durations in document metadata.
+    pub record_stage_timings: bool,
+    /// Average OCR/transcription confidence (0.0–1.0) below which a document is
+    /// flagged `low_confidence`. `None` disables flagging.
+    pub low_confidence_threshold: Option<f32>,
+    /// How often the worker re-embeds chunks whose embedding is stale or
+    /// missing (e.g. after their content was edited). `0` disables it.
+    pub chunk_reembed_interval_secs: u64,
+    pub chunk_reembed_batch_size: usize,
+    /// Classify processed documents into a [`DocumentCategory`] with the LLM and
+    /// store it in `metadata.category`.
+    ///
+    /// [`DocumentCategory`]: crate::models::DocumentCategory
+    pub categorize_documents: bool,
+    /// Most chunks a single structured data document (CSV, XLSX) may produce.
+    /// `0` means unlimited.
+    pub structured_data_max_chunks: usize,
+    /// How rows beyond `structured_data_max_chunks` are handled.
+    pub structured_data_overflow: StructuredDataOverflow,
+    /// Scan extracted text for PII before it is chunked.
+    pub pii_detection: bool,
+    pub pii_categories: Vec<PiiCategory>,
+    pub pii_action: PiiAction,
+    /// Attempts (including the first) for embedding and LLM calls that fail
+    /// with a transient error such as a timeout, 5xx or rate limit.
+    pub retry_max_attempts: u32,
+    /// Delay before the first retry; doubled for each further attempt.
+    pub retry_base_delay_ms: u64,
+    /// Failed processing runs after which a document is moved to
+    /// `dead_letter` and no longer retried. `0` disables dead-lettering.
+    pub dead_letter_after_failures: u32,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -182,8 +558,19 @@ pub struct MemoryConfig {
     pub episode_decay_threshold: f64,
     pub episode_forget_grace_days: u32,
     pub forgetting_check_interval_secs: u64,
+    /// Log what the forgetting and episode decay managers would do instead
+    /// of doing it.
+    pub forgetting_dry_run: bool,
     pub profile_refresh_interval_secs: u64,
+    /// Leave facts without a confidence out of profiles requested with a
+    /// minimum confidence, instead of ranking them last.
+    pub profile_exclude_unscored_facts: bool,
+    /// Detect the language of new memories and store it in their metadata.
+    pub language_detection: bool,
     pub inference: InferenceConfig,
+    pub importance: ImportanceConfig,
+    pub alternate_embeddings: AlternateEmbeddingsConfig,
+    pub idle_forgetting: IdleForgettingConfig,
 }
 
 /// Configuration for the background inference engine that derives new memories
@@ -198,31 +585,122 @@ pub struct InferenceConfig {
     pub exclude_episodes: bool,
 }
 
+/// Importance scoring used to rank memories for retention and search
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImportanceConfig {
+    /// Periodically recompute importance scores in the forgetting manager.
+    pub enabled: bool,
+    pub recency_weight: f64,
+    pub source_weight: f64,
+    pub confidence_weight: f64,
+    pub relation_weight: f64,
+    /// Days after which the recency signal halves.
+    pub recency_half_life_days: f64,
+    /// Multiplier applied to memory search scores: `score * (1 + boost * importance)`.
+    pub search_boost: f64,
+    /// Active memory count above which the least important memories are forgotten.
+    pub max_memories: Option<u64>,
+}
+
+/// Forgetting of episodes nobody has looked at in a while, on top of the
+/// `forget_after` expiry
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdleForgettingConfig {
+    /// Forget idle episodes on each forgetting run.
+    pub enabled: bool,
+    /// Days since an episode was last accessed (or created, if never) after
+    /// which it counts as idle.
+    pub idle_days: u32,
+    /// Only episodes backed by at most this many sources are forgotten.
+    pub max_source_count: i32,
+}
+
+/// Additional embedding models whose memory embeddings are stored side by side
+/// with the primary model's, for evaluation without re-embedding in place
+#[derive(Debug, Clone, Deserialize)]
+pub struct AlternateEmbeddingsConfig {
+    /// Local model names (e.g. `BAAI/bge-base-en-v1.5`). Empty disables the feature.
+    pub models: Vec<String>,
+    /// How often memories missing an alternate embedding are backfilled.
+    pub backfill_interval_secs: u64,
+    /// Memories embedded per model on each backfill run.
+    pub backfill_batch_size: usize,
+    /// Container tag -> model whose embeddings that container's memory searches
+    /// use. Pinned models are loaded like alternate models.
+    pub container_models: HashMap<String, String>,
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
             server: ServerConfig {
                 host: env::var("MOMO_HOST").unwrap_or_else(|_| "0.0.0.0".to_string()),
                 port: parse_env_or("MOMO_PORT", 3000),
-                api_keys: env::var("MOMO_API_KEYS")
-                    .map(|keys| keys.split(',').map(|s| s.trim().to_string()).collect())
-                    .unwrap_or_default(),
+                api_keys: parse_api_keys(),
+                api_key_container_tags: parse_api_key_container_tags(),
+                idempotency_key_ttl_secs: parse_env_or("MOMO_IDEMPOTENCY_KEY_TTL_SECS", 86400),
+                document_events_idle_timeout_secs: parse_env_or(
+                    "MOMO_DOCUMENT_EVENTS_IDLE_TIMEOUT_SECS",
+                    300,
+                ),
+                // 50 MiB: room for base64-encoded media in JSON bodies.
+                max_request_body_bytes: parse_env_or("MOMO_MAX_REQUEST_BODY_BYTES", 52428800),
             },
             mcp: McpConfig::default(),
             database: DatabaseConfig {
                 url: env::var("DATABASE_URL").unwrap_or_else(|_| "file:momo.db".to_string()),
                 auth_token: env::var("DATABASE_AUTH_TOKEN").ok(),
                 local_path: env::var("DATABASE_LOCAL_PATH").ok(),
+                max_connections: 4,
+                max_connections: parse_env_or("DATABASE_MAX_CONNECTIONS", 8),
             },
             embeddings: EmbeddingsConfig {
                 model: env::var("EMBEDDING_MODEL")
                     .unwrap_or_else(|_| "BAAI/bge-small-en-v1.5".to_string()),
                 dimensions: parse_env_or("EMBEDDING_DIMENSIONS", 384),
                 batch_size: parse_env_or("EMBEDDING_BATCH_SIZE", 256),
+                query_cache_size: parse_env_or("EMBEDDING_QUERY_CACHE_SIZE", 1000),
+                max_concurrent_batches: parse_env_or("EMBEDDING_MAX_CONCURRENT_BATCHES", 4),
             },
             processing: ProcessingConfig {
                 chunk_size: parse_env_or("CHUNK_SIZE", 512),
                 chunk_overlap: parse_env_or("CHUNK_OVERLAP", 50),
+                chunk_strategy: parse_env_or("CHUNK_STRATEGY", ChunkStrategy::Structural),
+                semantic_chunk_threshold: parse_env_or("SEMANTIC_CHUNK_THRESHOLD", 0.3),
+                semantic_chunk_min_size: parse_env_or("SEMANTIC_CHUNK_MIN_SIZE", 128),
+                use_model_tokenizer: parse_env_or("CHUNK_USE_MODEL_TOKENIZER", false),
+                auto_summary: parse_env_or("AUTO_SUMMARY_ENABLED", false),
+                auto_summary_min_chars: parse_env_or("AUTO_SUMMARY_MIN_CHARS", 2000),
+                contextual_chunks: parse_env_or("CONTEXTUAL_CHUNKS_ENABLED", false),
+                near_duplicate_threshold: Some(parse_env_or(
+                    "DOCUMENT_NEAR_DUPLICATE_THRESHOLD",
+                    0.0f32,
+                ))
+                .filter(|threshold| *threshold > 0.0),
+                near_duplicate_action: parse_env_or(
+                    "DOCUMENT_NEAR_DUPLICATE_ACTION",
+                    NearDuplicateAction::Warn,
+                ),
+                record_stage_timings: parse_env_or("PROCESSING_STAGE_TIMINGS", false),
+                low_confidence_threshold: Some(parse_env_or(
+                    "EXTRACTION_LOW_CONFIDENCE_THRESHOLD",
+                    0.0f32,
+                ))
+                .filter(|threshold| *threshold > 0.0),
+                chunk_reembed_interval_secs: parse_env_or("CHUNK_REEMBED_INTERVAL_SECS", 60),
+                chunk_reembed_batch_size: parse_env_or("CHUNK_REEMBED_BATCH_SIZE", 64),
+                categorize_documents: parse_env_or("DOCUMENT_CATEGORIZATION_ENABLED", false),
+                structured_data_max_chunks: parse_env_or("STRUCTURED_DATA_MAX_CHUNKS", 200),
+                structured_data_overflow: parse_env_or(
+                    "STRUCTURED_DATA_OVERFLOW_STRATEGY",
+                    StructuredDataOverflow::Sample,
+                ),
+                pii_detection: parse_env_or("PII_DETECTION_ENABLED", false),
+                pii_categories: parse_pii_categories(),
+                pii_action: parse_env_or("PII_ACTION", PiiAction::Redact),
+                retry_max_attempts: parse_env_or("PROCESSING_RETRY_MAX_ATTEMPTS", 3),
+                retry_base_delay_ms: parse_env_or("PROCESSING_RETRY_BASE_DELAY_MS", 500),
+                dead_letter_after_failures: parse_env_or("PROCESSING_DEAD_LETTER_AFTER", 5),
             },
             memory: MemoryConfig {
                 episode_decay_days: parse_env_or("EPISODE_DECAY_DAYS", 30.0),
@@ -230,7 +708,13 @@ impl Default for Config {
                 episode_decay_threshold: parse_env_or("EPISODE_DECAY_THRESHOLD", 0.3),
                 episode_forget_grace_days: parse_env_or("EPISODE_FORGET_GRACE_DAYS", 7),
                 forgetting_check_interval_secs: parse_env_or("FORGETTING_CHECK_INTERVAL", 3600),
+                forgetting_dry_run: parse_env_or("FORGETTING_DRY_RUN", false),
                 profile_refresh_interval_secs: parse_env_or("PROFILE_REFRESH_INTERVAL_SECS", 86400),
+                profile_exclude_unscored_facts: parse_env_or(
+                    "PROFILE_EXCLUDE_UNSCORED_FACTS",
+                    false,
+                ),
+                language_detection: parse_env_or("MEMORY_LANGUAGE_DETECTION", false),
                 inference: InferenceConfig {
                     enabled: parse_env_or("ENABLE_INFERENCES", false),
                     interval_secs: parse_env_or("INFERENCE_INTERVAL_SECS", 86400),
@@ -240,6 +724,9 @@ impl Default for Config {
                     seed_limit: parse_env_or("INFERENCE_SEED_LIMIT", 50),
                     exclude_episodes: parse_env_or("INFERENCE_EXCLUDE_EPISODES", true),
                 },
+                importance: ImportanceConfig::default(),
+                alternate_embeddings: AlternateEmbeddingsConfig::default(),
+                idle_forgetting: IdleForgettingConfig::default(),
             },
             ocr: OcrConfig {
                 model: env::var("OCR_MODEL").unwrap_or_else(|_| "local/tesseract".to_string()),
@@ -249,6 +736,12 @@ impl Default for Config {
                 timeout_secs: parse_env_or("OCR_TIMEOUT", 60),
                 max_image_dimension: parse_env_or("OCR_MAX_DIMENSION", 4096),
                 min_image_dimension: parse_env_or("OCR_MIN_DIMENSION", 50),
+                max_concurrent_jobs: parse_env_or("OCR_MAX_CONCURRENT_JOBS", 2),
+                include_layout: parse_env_or("OCR_INCLUDE_LAYOUT", false),
+                min_confidence: Some(parse_env_or("OCR_MIN_CONFIDENCE", 0.0f32))
+                    .filter(|threshold| *threshold > 0.0),
+                min_document_confidence: Some(parse_env_or("OCR_MIN_DOCUMENT_CONFIDENCE", 0.0f32))
+                    .filter(|threshold| *threshold > 0.0),
             },
             transcription: TranscriptionConfig {
                 model: env::var("TRANSCRIPTION_MODEL")
@@ -259,6 +752,16 @@ impl Default for Config {
                 timeout_secs: parse_env_or("TRANSCRIPTION_TIMEOUT", 300),
                 max_file_size: parse_env_or("TRANSCRIPTION_MAX_FILE_SIZE", 104857600),
                 max_duration_secs: parse_env_or("TRANSCRIPTION_MAX_DURATION", 7200),
+                max_concurrent_jobs: parse_env_or("TRANSCRIPTION_MAX_CONCURRENT_JOBS", 1),
+                upload_dir: env::var("TRANSCRIPTION_UPLOAD_DIR").ok(),
+                include_segments: parse_env_or("TRANSCRIPTION_INCLUDE_SEGMENTS", false),
+                chunk_by_segment: parse_env_or("TRANSCRIPTION_CHUNK_BY_SEGMENT", false),
+                language: env::var("TRANSCRIPTION_LANGUAGE")
+                    .ok()
+                    .filter(|language| !language.trim().is_empty()),
+                default_language: env::var("TRANSCRIPTION_DEFAULT_LANGUAGE")
+                    .unwrap_or_else(|_| "en".to_string()),
+                language_min_confidence: parse_env_or("TRANSCRIPTION_LANGUAGE_MIN_CONFIDENCE", 0.5),
             },
             llm: env::var("LLM_MODEL").ok().map(|model| LlmConfig {
                 model,
@@ -275,6 +778,10 @@ impl Default for Config {
                     false,
                 ),
                 filter_prompt: env::var("DEFAULT_FILTER_PROMPT").ok(),
+                max_calls_per_run: Some(parse_env_or("LLM_MAX_CALLS_PER_RUN", 0u64))
+                    .filter(|max| *max > 0),
+                max_tokens_per_run: Some(parse_env_or("LLM_MAX_TOKENS_PER_RUN", 0u64))
+                    .filter(|max| *max > 0),
             }),
             reranker: {
                 let enabled = parse_env_or("RERANK_ENABLED", false);
@@ -293,6 +800,105 @@ impl Default for Config {
                     None
                 }
             },
+            search: SearchConfig::default(),
+            extraction: ExtractionConfig::default(),
+        }
+    }
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            max_response_time_ms: Some(parse_env_or("SEARCH_MAX_RESPONSE_TIME_MS", 0u64))
+                .filter(|ms| *ms > 0),
+            dedupe_version_chains: parse_env_or("SEARCH_DEDUPE_VERSION_CHAINS", false),
+            max_context_memories: parse_env_or("SEARCH_MAX_CONTEXT_MEMORIES", 10),
+            min_results_threshold_floor: parse_env_or("SEARCH_MIN_RESULTS_THRESHOLD_FLOOR", 0.3),
+            lexical_fallback: parse_env_or("SEARCH_LEXICAL_FALLBACK", false),
+            lexical_fallback_limit: parse_env_or("SEARCH_LEXICAL_FALLBACK_LIMIT", 20),
+            highlight_open_marker: env::var("SEARCH_HIGHLIGHT_OPEN_MARKER")
+                .unwrap_or_else(|_| "<mark>".to_string()),
+            highlight_close_marker: env::var("SEARCH_HIGHLIGHT_CLOSE_MARKER")
+                .unwrap_or_else(|_| "</mark>".to_string()),
+            score_sigmoid_midpoint: parse_env_or("SEARCH_SCORE_SIGMOID_MIDPOINT", 0.75),
+            score_sigmoid_steepness: parse_env_or("SEARCH_SCORE_SIGMOID_STEEPNESS", 15.0),
+            freshness_half_life_days: parse_env_or("SEARCH_FRESHNESS_HALF_LIFE_DAYS", 30.0),
+            synonyms_path: env::var("SEARCH_SYNONYMS_PATH")
+                .ok()
+                .filter(|path| !path.trim().is_empty()),
+            synonyms: HashMap::new(),
+            probe: SearchProbeConfig::default(),
+        }
+    }
+}
+
+impl Default for SearchProbeConfig {
+    fn default() -> Self {
+        Self {
+            canaries_path: env::var("SEARCH_PROBE_CANARIES_PATH")
+                .ok()
+                .filter(|path| !path.trim().is_empty()),
+            interval_secs: parse_env_or("SEARCH_PROBE_INTERVAL_SECS", 3600),
+            top_k: parse_env_or("SEARCH_PROBE_TOP_K", 10),
+            min_hit_rate: parse_env_or("SEARCH_PROBE_MIN_HIT_RATE", 0.8),
+            min_mrr: parse_env_or("SEARCH_PROBE_MIN_MRR", 0.5),
+            canaries: Vec::new(),
+        }
+    }
+}
+
+impl Default for ImportanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: parse_env_or("MEMORY_IMPORTANCE_ENABLED", false),
+            recency_weight: parse_env_or("MEMORY_IMPORTANCE_RECENCY_WEIGHT", 0.4),
+            source_weight: parse_env_or("MEMORY_IMPORTANCE_SOURCE_WEIGHT", 0.2),
+            confidence_weight: parse_env_or("MEMORY_IMPORTANCE_CONFIDENCE_WEIGHT", 0.2),
+            relation_weight: parse_env_or("MEMORY_IMPORTANCE_RELATION_WEIGHT", 0.2),
+            recency_half_life_days: parse_env_or("MEMORY_IMPORTANCE_HALF_LIFE_DAYS", 30.0),
+            search_boost: parse_env_or("MEMORY_IMPORTANCE_SEARCH_BOOST", 0.0),
+            max_memories: Some(parse_env_or("MEMORY_MAX_COUNT", 0u64)).filter(|max| *max > 0),
+        }
+    }
+}
+
+impl Default for IdleForgettingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: parse_env_or("MEMORY_IDLE_FORGETTING_ENABLED", false),
+            idle_days: parse_env_or("MEMORY_IDLE_FORGETTING_DAYS", 90),
+            max_source_count: parse_env_or("MEMORY_IDLE_FORGETTING_MAX_SOURCES", 1),
+        }
+    }
+}
+
+impl Default for AlternateEmbeddingsConfig {
+    fn default() -> Self {
+        Self {
+            models: env::var("MEMORY_ALTERNATE_EMBEDDING_MODELS")
+                .map(|models| {
+                    models
+                        .split(',')
+                        .map(|model| model.trim().to_string())
+                        .filter(|model| !model.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            backfill_interval_secs: parse_env_or("MEMORY_ALTERNATE_EMBEDDING_INTERVAL_SECS", 300),
+            backfill_batch_size: parse_env_or("MEMORY_ALTERNATE_EMBEDDING_BATCH_SIZE", 64),
+            container_models: parse_container_embedding_models(),
+        }
+    }
+}
+
+impl Default for ExtractionConfig {
+    fn default() -> Self {
+        Self {
+            examples_path: env::var("EXTRACTION_EXAMPLES_PATH")
+                .ok()
+                .filter(|path| !path.trim().is_empty()),
+            examples_max_tokens: parse_env_or("EXTRACTION_EXAMPLES_MAX_TOKENS", 1000),
+            examples: Vec::new(),
         }
     }
 }
@@ -544,6 +1150,28 @@ mod tests {
         assert_eq!(config.mcp.project_header, "x-sm-project");
     }
 
+    #[test]
+    fn test_search_max_response_time_disabled_by_default() {
+        let _guard = RERANKER_TEST_MUTEX.lock().unwrap();
+        std::env::remove_var("SEARCH_MAX_RESPONSE_TIME_MS");
+        let config = Config::default();
+        assert!(config.search.max_response_time_ms.is_none());
+
+        std::env::set_var("SEARCH_MAX_RESPONSE_TIME_MS", "0");
+        let config = Config::default();
+        assert!(config.search.max_response_time_ms.is_none());
+        std::env::remove_var("SEARCH_MAX_RESPONSE_TIME_MS");
+    }
+
+    #[test]
+    fn test_search_max_response_time_from_env() {
+        let _guard = RERANKER_TEST_MUTEX.lock().unwrap();
+        std::env::set_var("SEARCH_MAX_RESPONSE_TIME_MS", "750");
+        let config = Config::default();
+        assert_eq!(config.search.max_response_time_ms, Some(750));
+        std::env::remove_var("SEARCH_MAX_RESPONSE_TIME_MS");
+    }
+
     #[test]
     fn test_mcp_path_normalized_from_env() {
         let _guard = RERANKER_TEST_MUTEX.lock().unwrap();
@@ -552,4 +1180,65 @@ mod tests {
         assert_eq!(config.mcp.path, "/custom-mcp");
         std::env::remove_var("MOMO_MCP_PATH");
     }
+
+    #[test]
+    fn test_pii_categories_from_env() {
+        let _guard = RERANKER_TEST_MUTEX.lock().unwrap();
+        assert_eq!(
+            Config::default().processing.pii_categories,
+            PiiCategory::ALL
+        );
+
+        std::env::set_var("PII_CATEGORIES", "email, ssn,passport");
+        let config = Config::default();
+        assert_eq!(
+            config.processing.pii_categories,
+            vec![PiiCategory::Email, PiiCategory::Ssn]
+        );
+        std::env::remove_var("PII_CATEGORIES");
+    }
+
+    #[test]
+    fn test_api_key_parse_list_scopes() {
+        let keys = ApiKey::parse_list(
+            "ops-key, reader:read,ci:read+write, legacy,bad:root,svc:eu:write,ro-key:read,admin",
+        );
+
+        assert_eq!(
+            keys,
+            vec![
+                ApiKey::unrestricted("ops-key"),
+                ApiKey {
+                    key: "reader".to_string(),
+                    scopes: vec![ApiScope::Read],
+                },
+                ApiKey {
+                    key: "ci".to_string(),
+                    scopes: vec![ApiScope::Read, ApiScope::Write],
+                },
+                ApiKey::unrestricted("legacy"),
+                ApiKey {
+                    key: "svc:eu".to_string(),
+                    scopes: vec![ApiScope::Write],
+                },
+                // The trailing bare `admin` does not widen `ro-key`.
+                ApiKey {
+                    key: "ro-key".to_string(),
+                    scopes: vec![ApiScope::Read],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_api_key_fingerprint_is_stable_and_hides_key() {
+        let fingerprint = ApiKey::unrestricted("super-secret").fingerprint();
+        assert!(fingerprint.starts_with("api_key_"));
+        assert!(!fingerprint.contains("super-secret"));
+        assert_eq!(fingerprint.len(), "api_key_".len() + 16);
+        assert_eq!(
+            fingerprint,
+            ApiKey::unrestricted("super-secret").fingerprint()
+        );
+    }
 }
@@ -1,22 +1,27 @@
 use crate::db::connection::Database;
 use crate::db::repository::{
-    ChunkRepository, DocumentRepository, MemoryRepository, MemorySourcesRepository,
+    ChunkRepository, ContradictionRepository, DocumentRepository, JobRepository, MemoryRepository,
+    MemorySourcesRepository, WebhookRepository,
 };
 use crate::db::traits::{
-    ChunkStore, DatabaseBackend, DocumentStore, EpisodeDecayCandidate, MemorySourceStore,
-    MemoryStore, MetadataStore,
+    ChunkStore, DatabaseBackend, DocumentStore, EpisodeDecayCandidate, IdempotentDocument,
+    JobStore, MemoryImportanceCandidate, MemorySourceStore, MemoryStore, MetadataStore,
 };
 use crate::db::MetadataRepository;
 use crate::error::Result;
 use crate::models::{
-    CachedProfile, Chunk, ChunkWithDocument, ContainerFilter, Document, DocumentSummary, GraphData,
-    GraphEdgeType, ListDocumentsRequest, Memory, MemoryRelationType, MemorySearchHit, MemorySource,
-    Pagination, ProcessingDocument, ProcessingStatus, UserProfile,
+    CachedProfile, Chunk, ChunkWithDocument, ChunkerType, ContainerEmbeddingModel, ContainerFilter,
+    ContainerSettings, ContradictionChange, ContradictionResolution, DeadLetterDocument, Document,
+    DocumentDeleteFilter, DocumentSummary, GraphData, GraphDirection, GraphEdgeType,
+    HighlightMarkers, Job, JobKind, ListDocumentsRequest, Memory, MemoryContradiction,
+    MemoryRelationType, MemorySearchHit, MemorySource, MemoryType, Pagination, ProcessingDocument,
+    ProcessingStats, ProcessingStatus, ProfileFactOptions, UserProfile, Webhook,
 };
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use libsql::params;
 use std::collections::HashMap;
+use std::time::Duration;
 
 pub struct LibSqlBackend {
     db: Database,
@@ -31,42 +36,66 @@ impl LibSqlBackend {
 #[async_trait]
 impl DocumentStore for LibSqlBackend {
     async fn create_document(&self, doc: &Document) -> Result<()> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire_write().await?;
         DocumentRepository::create(&conn, doc).await
     }
     async fn get_document_by_id(&self, id: &str) -> Result<Option<Document>> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire().await?;
         DocumentRepository::get_by_id(&conn, id).await
     }
     async fn get_documents_by_ids(&self, ids: &[String]) -> Result<Vec<Document>> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire().await?;
         DocumentRepository::get_by_ids(&conn, ids).await
     }
     async fn get_document_by_custom_id(&self, custom_id: &str) -> Result<Option<Document>> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire().await?;
         DocumentRepository::get_by_custom_id(&conn, custom_id).await
     }
+    async fn list_container_documents_after(
+        &self,
+        container_tag: &str,
+        after: Option<(DateTime<Utc>, &str)>,
+        limit: usize,
+    ) -> Result<Vec<Document>> {
+        let conn = self.db.acquire().await?;
+        DocumentRepository::list_by_container_after(&conn, container_tag, after, limit).await
+    }
     async fn update_document(&self, doc: &Document) -> Result<()> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire_write().await?;
         DocumentRepository::update(&conn, doc).await
     }
     async fn delete_document(&self, id: &str) -> Result<bool> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire_write().await?;
         DocumentRepository::delete(&conn, id).await
     }
     async fn delete_document_by_custom_id(&self, custom_id: &str) -> Result<bool> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire_write().await?;
         DocumentRepository::delete_by_custom_id(&conn, custom_id).await
     }
+    async fn delete_documents_matching(
+        &self,
+        filter: &DocumentDeleteFilter,
+    ) -> Result<Vec<String>> {
+        // A fresh connection per batch lets other writers in between batches.
+        let mut deleted = Vec::new();
+        loop {
+            let conn = self.db.acquire_write().await?;
+            let batch = DocumentRepository::delete_matching_batch(&conn, filter).await?;
+            if batch.is_empty() {
+                return Ok(deleted);
+            }
+            deleted.extend(batch);
+        }
+    }
     async fn list_documents(
         &self,
         req: &ListDocumentsRequest,
     ) -> Result<(Vec<DocumentSummary>, Pagination)> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire().await?;
         DocumentRepository::list(&conn, req).await
     }
     async fn get_processing_documents(&self) -> Result<Vec<ProcessingDocument>> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire().await?;
         DocumentRepository::get_processing(&conn).await
     }
     async fn update_document_status(
@@ -75,11 +104,11 @@ impl DocumentStore for LibSqlBackend {
         status: ProcessingStatus,
         error: Option<&str>,
     ) -> Result<()> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire_write().await?;
         DocumentRepository::update_status(&conn, id, status, error).await
     }
     async fn queue_all_documents_for_reprocessing(&self) -> Result<u64> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire_write().await?;
         let affected = conn
             .execute(
                 "UPDATE documents SET status = 'queued' WHERE status = 'done'",
@@ -88,34 +117,146 @@ impl DocumentStore for LibSqlBackend {
             .await?;
         Ok(affected)
     }
+    async fn requeue_stale_documents(&self, older_than: Duration) -> Result<u64> {
+        let conn = self.db.acquire_write().await?;
+        DocumentRepository::requeue_stale(&conn, older_than).await
+    }
+    async fn record_processing_run(
+        &self,
+        id: &str,
+        status: ProcessingStatus,
+        duration_ms: u64,
+    ) -> Result<()> {
+        let conn = self.db.acquire_write().await?;
+        DocumentRepository::record_processing_run(&conn, id, status, duration_ms).await
+    }
+    async fn get_processing_stats(&self) -> Result<ProcessingStats> {
+        let conn = self.db.acquire().await?;
+        DocumentRepository::processing_stats(&conn).await
+    }
+    async fn record_document_failure(
+        &self,
+        id: &str,
+        error: &str,
+        max_failures: u32,
+    ) -> Result<Option<ProcessingStatus>> {
+        let conn = self.db.acquire_write().await?;
+        DocumentRepository::record_failure(&conn, id, error, max_failures).await
+    }
+    async fn list_dead_letter_documents(&self) -> Result<Vec<DeadLetterDocument>> {
+        let conn = self.db.acquire().await?;
+        DocumentRepository::list_dead_letter(&conn).await
+    }
+    async fn requeue_dead_letter_document(&self, id: &str) -> Result<bool> {
+        let conn = self.db.acquire_write().await?;
+        DocumentRepository::requeue_dead_letter(&conn, id).await
+    }
+    async fn queue_document_for_reprocess(
+        &self,
+        id: &str,
+        extract_memories: Option<bool>,
+    ) -> Result<bool> {
+        let conn = self.db.acquire_write().await?;
+        DocumentRepository::queue_for_reprocess(&conn, id, extract_memories).await
+    }
 }
 
 #[async_trait]
 impl ChunkStore for LibSqlBackend {
+    #[tracing::instrument(name = "db.create_chunks_batch", skip_all)]
     async fn create_chunks_batch(&self, chunks: &[Chunk]) -> Result<()> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire_write().await?;
         ChunkRepository::create_batch(&conn, chunks).await
     }
+    #[tracing::instrument(name = "db.update_chunk_embeddings_batch", skip_all)]
     async fn update_chunk_embeddings_batch(&self, updates: &[(String, Vec<f32>)]) -> Result<()> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire_write().await?;
         ChunkRepository::update_embeddings_batch(&conn, updates).await
     }
+    async fn update_chunk_content(
+        &self,
+        document_id: &str,
+        chunk_id: &str,
+        content: &str,
+    ) -> Result<Option<Chunk>> {
+        let conn = self.db.acquire_write().await?;
+        ChunkRepository::update_content(&conn, document_id, chunk_id, content).await
+    }
+    async fn get_chunks_missing_embeddings(&self, limit: usize) -> Result<Vec<Chunk>> {
+        let conn = self.db.acquire().await?;
+        ChunkRepository::get_missing_embeddings(&conn, limit).await
+    }
     async fn delete_chunks_by_document_id(&self, document_id: &str) -> Result<()> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire_write().await?;
         ChunkRepository::delete_by_document_id(&conn, document_id).await
     }
+    async fn get_chunks_by_position_range(
+        &self,
+        document_id: &str,
+        start: i32,
+        end: i32,
+    ) -> Result<Vec<Chunk>> {
+        let conn = self.db.acquire().await?;
+        ChunkRepository::get_by_document_and_position_range(&conn, document_id, start, end).await
+    }
+    async fn get_chunks_by_positions(
+        &self,
+        document_id: &str,
+        positions: &[i32],
+    ) -> Result<Vec<Chunk>> {
+        let conn = self.db.acquire().await?;
+        ChunkRepository::get_by_document_and_positions(&conn, document_id, positions).await
+    }
+    #[tracing::instrument(name = "db.search_similar_chunks", skip_all)]
     async fn search_similar_chunks(
         &self,
         embedding: &[f32],
         limit: u32,
         threshold: f32,
         container_tags: Option<&[String]>,
+        chunker_types: Option<&[ChunkerType]>,
+        languages: Option<&[String]>,
     ) -> Result<Vec<ChunkWithDocument>> {
-        let conn = self.db.connect()?;
-        ChunkRepository::search_similar(&conn, embedding, limit, threshold, container_tags).await
+        let conn = self.db.acquire().await?;
+        ChunkRepository::search_similar(
+            &conn,
+            embedding,
+            limit,
+            threshold,
+            container_tags,
+            chunker_types,
+            languages,
+        )
+        .await
+    }
+    async fn get_chunk_embeddings(&self, ids: &[String]) -> Result<HashMap<String, Vec<f32>>> {
+        let conn = self.db.acquire().await?;
+        ChunkRepository::get_embeddings(&conn, ids).await
+    }
+    #[tracing::instrument(name = "db.search_keyword_chunks", skip_all)]
+    async fn search_keyword_chunks(
+        &self,
+        query: &str,
+        limit: u32,
+        container_tags: Option<&[String]>,
+        chunker_types: Option<&[ChunkerType]>,
+        languages: Option<&[String]>,
+        highlight: Option<&HighlightMarkers>,
+    ) -> Result<Vec<ChunkWithDocument>> {
+        let conn = self.db.acquire().await?;
+        ChunkRepository::search_keyword(
+            &conn,
+            query,
+            limit,
+            container_tags,
+            chunker_types,
+            languages,
+            highlight,
+        )
+        .await
     }
     async fn delete_all_chunks(&self) -> Result<u64> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire_write().await?;
         let affected = conn.execute("DELETE FROM chunks", ()).await?;
         Ok(affected)
     }
@@ -124,15 +265,36 @@ impl ChunkStore for LibSqlBackend {
 #[async_trait]
 impl MemoryStore for LibSqlBackend {
     async fn create_memory(&self, memory: &Memory) -> Result<()> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire_write().await?;
         MemoryRepository::create(&conn, memory).await
     }
     async fn get_memory_by_id(&self, id: &str) -> Result<Option<Memory>> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire().await?;
         MemoryRepository::get_by_id(&conn, id).await
     }
+    async fn get_memory_embedding(&self, id: &str) -> Result<Option<Vec<f32>>> {
+        let conn = self.db.acquire().await?;
+        MemoryRepository::get_embedding(&conn, id).await
+    }
+    async fn get_memory_embeddings(&self, ids: &[String]) -> Result<HashMap<String, Vec<f32>>> {
+        let conn = self.db.acquire().await?;
+        MemoryRepository::get_embeddings(&conn, ids).await
+    }
+    async fn list_container_memories_after(
+        &self,
+        container_tag: &str,
+        after: Option<(DateTime<Utc>, &str)>,
+        limit: usize,
+    ) -> Result<Vec<Memory>> {
+        let conn = self.db.acquire().await?;
+        MemoryRepository::list_by_container_after(&conn, container_tag, after, limit).await
+    }
+    async fn get_memory_by_id_including_forgotten(&self, id: &str) -> Result<Option<Memory>> {
+        let conn = self.db.acquire().await?;
+        MemoryRepository::get_by_id_including_forgotten(&conn, id).await
+    }
     async fn get_memories_by_ids(&self, ids: &[String]) -> Result<Vec<Memory>> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire().await?;
         MemoryRepository::get_by_ids(&conn, ids).await
     }
     async fn get_memory_by_content(
@@ -140,23 +302,31 @@ impl MemoryStore for LibSqlBackend {
         content: &str,
         container_tag: &str,
     ) -> Result<Option<Memory>> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire().await?;
         MemoryRepository::get_by_content(&conn, content, container_tag).await
     }
     async fn update_memory_to_not_latest(&self, id: &str) -> Result<()> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire_write().await?;
         MemoryRepository::update_to_not_latest(&conn, id).await
     }
+    async fn supersede_memory(&self, id: &str, expected_version: i32) -> Result<bool> {
+        let conn = self.db.acquire_write().await?;
+        MemoryRepository::supersede(&conn, id, expected_version).await
+    }
     async fn forget_memory(&self, id: &str, reason: Option<&str>) -> Result<()> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire_write().await?;
         MemoryRepository::forget(&conn, id, reason).await
     }
+    async fn restore_memory(&self, id: &str) -> Result<bool> {
+        let conn = self.db.acquire_write().await?;
+        MemoryRepository::restore(&conn, id).await
+    }
     async fn update_memory_last_accessed_batch(&self, ids: &[&str]) -> Result<u64> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire_write().await?;
         MemoryRepository::update_last_accessed_batch(&conn, ids).await
     }
     async fn update_memory_source_count(&self, id: &str, new_count: i32) -> Result<()> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire_write().await?;
         MemoryRepository::update_source_count(&conn, id, new_count).await
     }
     async fn update_memory_version_chain(
@@ -166,51 +336,130 @@ impl MemoryStore for LibSqlBackend {
         root_memory_id: &str,
         version: i32,
     ) -> Result<()> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire_write().await?;
         MemoryRepository::update_version_chain(&conn, id, parent_memory_id, root_memory_id, version)
             .await
     }
     async fn update_memory_embedding(&self, memory_id: &str, embedding: &[f32]) -> Result<()> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire_write().await?;
         MemoryRepository::update_embedding(&conn, memory_id, embedding).await
     }
+    #[tracing::instrument(name = "db.search_similar_memories", skip_all)]
     async fn search_similar_memories(
         &self,
         embedding: &[f32],
         limit: u32,
         threshold: f32,
         container_tag: Option<&str>,
+        language: Option<&str>,
         include_forgotten: bool,
     ) -> Result<Vec<MemorySearchHit>> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire().await?;
         MemoryRepository::search_similar(
             &conn,
             embedding,
             limit,
             threshold,
             container_tag,
+            language,
+            include_forgotten,
+        )
+        .await
+    }
+    #[tracing::instrument(name = "db.search_keyword_memories", skip_all)]
+    async fn search_keyword_memories(
+        &self,
+        query: &str,
+        limit: u32,
+        container_tag: Option<&str>,
+        language: Option<&str>,
+        include_forgotten: bool,
+    ) -> Result<Vec<MemorySearchHit>> {
+        let conn = self.db.acquire().await?;
+        MemoryRepository::search_keyword(
+            &conn,
+            query,
+            limit,
+            container_tag,
+            language,
+            include_forgotten,
+        )
+        .await
+    }
+    #[tracing::instrument(name = "db.search_similar_memories_by_model", skip_all)]
+    async fn search_similar_memories_by_model(
+        &self,
+        embedding_model: &str,
+        embedding: &[f32],
+        limit: u32,
+        threshold: f32,
+        container_tag: Option<&str>,
+        language: Option<&str>,
+        include_forgotten: bool,
+    ) -> Result<Vec<MemorySearchHit>> {
+        let conn = self.db.acquire().await?;
+        MemoryRepository::search_similar_by_model(
+            &conn,
+            embedding,
+            Some(embedding_model),
+            limit,
+            threshold,
+            container_tag,
+            language,
             include_forgotten,
         )
         .await
     }
-    async fn get_memory_children(&self, parent_id: &str) -> Result<Vec<Memory>> {
-        let conn = self.db.connect()?;
-        MemoryRepository::get_children(&conn, parent_id).await
+    async fn upsert_memory_model_embeddings(
+        &self,
+        model: &str,
+        embeddings: &[(String, Vec<f32>)],
+    ) -> Result<()> {
+        let conn = self.db.acquire_write().await?;
+        MemoryRepository::upsert_model_embeddings(&conn, model, embeddings).await
+    }
+    async fn get_memories_missing_model_embedding(
+        &self,
+        model: &str,
+        limit: usize,
+    ) -> Result<Vec<Memory>> {
+        let conn = self.db.acquire().await?;
+        MemoryRepository::get_missing_model_embeddings(&conn, model, limit).await
+    }
+    async fn get_memory_children_batch(
+        &self,
+        parent_ids: &[String],
+        per_parent: usize,
+    ) -> Result<Vec<Memory>> {
+        let conn = self.db.acquire().await?;
+        MemoryRepository::get_children_batch(&conn, parent_ids, per_parent).await
     }
-    async fn get_memory_parents(&self, root_id: &str) -> Result<Vec<Memory>> {
-        let conn = self.db.connect()?;
-        MemoryRepository::get_parents(&conn, root_id).await
+    async fn get_memory_parents_batch(
+        &self,
+        root_ids: &[String],
+        per_chain: usize,
+    ) -> Result<Vec<Memory>> {
+        let conn = self.db.acquire().await?;
+        MemoryRepository::get_parents_batch(&conn, root_ids, per_chain).await
     }
     async fn get_forgetting_candidates(&self, before: DateTime<Utc>) -> Result<Vec<Memory>> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire().await?;
         MemoryRepository::get_forgetting_candidates(&conn, before).await
     }
+    async fn get_idle_episodes(
+        &self,
+        accessed_before: DateTime<Utc>,
+        max_source_count: i32,
+    ) -> Result<Vec<Memory>> {
+        let conn = self.db.acquire().await?;
+        MemoryRepository::get_idle_episodes(&conn, accessed_before, max_source_count).await
+    }
     async fn get_seed_memories(&self, limit: usize) -> Result<Vec<Memory>> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire().await?;
         MemoryRepository::get_seed_memories(&conn, limit).await
     }
     async fn check_inference_exists(&self, source_ids: &[String]) -> Result<bool> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire().await?;
         MemoryRepository::check_inference_exists(&conn, source_ids).await
     }
     async fn get_user_profile(
@@ -218,16 +467,18 @@ impl MemoryStore for LibSqlBackend {
         container_tag: &str,
         include_dynamic: bool,
         limit: u32,
+        options: &ProfileFactOptions,
     ) -> Result<UserProfile> {
-        let conn = self.db.connect()?;
-        MemoryRepository::get_user_profile(&conn, container_tag, include_dynamic, limit).await
+        let conn = self.db.acquire().await?;
+        MemoryRepository::get_user_profile(&conn, container_tag, include_dynamic, limit, options)
+            .await
     }
     async fn update_memory_relations(
         &self,
         id: &str,
         new_relations: HashMap<String, MemoryRelationType>,
     ) -> Result<()> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire_write().await?;
         MemoryRepository::update_relations(&conn, id, new_relations).await
     }
     async fn add_memory_relation(
@@ -236,25 +487,48 @@ impl MemoryStore for LibSqlBackend {
         related_id: &str,
         relation_type: MemoryRelationType,
     ) -> Result<()> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire_write().await?;
         MemoryRepository::add_relation(&conn, id, related_id, relation_type).await
     }
+    #[tracing::instrument(name = "db.get_graph_neighborhood", skip_all)]
     async fn get_graph_neighborhood(
         &self,
         id: &str,
         depth: u32,
         max_nodes: u32,
         relation_types: Option<&[GraphEdgeType]>,
+        memory_types: Option<&[MemoryType]>,
+        direction: GraphDirection,
     ) -> Result<GraphData> {
-        let conn = self.db.connect()?;
-        MemoryRepository::get_graph_neighborhood(&conn, id, depth, max_nodes, relation_types).await
+        let conn = self.db.acquire().await?;
+        MemoryRepository::get_graph_neighborhood(
+            &conn,
+            id,
+            depth,
+            max_nodes,
+            relation_types,
+            memory_types,
+            direction,
+        )
+        .await
     }
+    #[tracing::instrument(name = "db.get_container_graph", skip_all)]
     async fn get_container_graph(&self, container_tag: &str, max_nodes: u32) -> Result<GraphData> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire().await?;
         MemoryRepository::get_container_graph(&conn, container_tag, max_nodes).await
     }
+    #[tracing::instrument(name = "db.find_memory_path", skip_all)]
+    async fn find_memory_path(
+        &self,
+        from: &str,
+        to: &str,
+        max_depth: u32,
+    ) -> Result<Option<GraphData>> {
+        let conn = self.db.acquire().await?;
+        MemoryRepository::find_path(&conn, from, to, max_depth).await
+    }
     async fn get_cached_profile(&self, container_tag: &str) -> Result<Option<CachedProfile>> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire().await?;
         MemoryRepository::get_cached_profile(&conn, container_tag).await
     }
     async fn upsert_cached_profile(
@@ -263,12 +537,12 @@ impl MemoryStore for LibSqlBackend {
         narrative: Option<&str>,
         summary: Option<&str>,
     ) -> Result<()> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire_write().await?;
         MemoryRepository::upsert_cached_profile(&conn, container_tag, narrative, summary).await
     }
 
     async fn get_episode_decay_candidates(&self) -> Result<Vec<EpisodeDecayCandidate>> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire().await?;
         let mut rows = conn
             .query(
                 "SELECT id, memory, space_id, last_accessed, created_at FROM memories WHERE is_forgotten = 0 AND is_static = 0 AND memory_type = 'episode' AND is_latest = 1",
@@ -290,7 +564,7 @@ impl MemoryStore for LibSqlBackend {
     }
 
     async fn set_memory_forget_after(&self, id: &str, forget_after: DateTime<Utc>) -> Result<u64> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire_write().await?;
         let ts = forget_after.to_rfc3339();
         let now = Utc::now().to_rfc3339();
 
@@ -304,8 +578,87 @@ impl MemoryStore for LibSqlBackend {
         Ok(res)
     }
 
+    async fn get_memory_importance_candidates(&self) -> Result<Vec<MemoryImportanceCandidate>> {
+        let conn = self.db.acquire().await?;
+        let mut rows = conn
+            .query(
+                "SELECT id, source_count, confidence, memory_relations, last_accessed, created_at FROM memories WHERE is_forgotten = 0 AND is_latest = 1",
+                (),
+            )
+            .await?;
+
+        let mut results = Vec::new();
+        while let Some(row) = rows.next().await? {
+            let relations: Option<String> = row.get(3)?;
+            let relation_count = relations
+                .and_then(|json| {
+                    serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&json).ok()
+                })
+                .map(|map| map.len())
+                .unwrap_or(0);
+            results.push(MemoryImportanceCandidate {
+                id: row.get(0)?,
+                source_count: row.get::<Option<i32>>(1)?.unwrap_or(0),
+                confidence: row.get(2)?,
+                relation_count,
+                last_accessed: row.get(4)?,
+                created_at: row.get(5)?,
+            });
+        }
+        Ok(results)
+    }
+
+    async fn update_memory_importance_batch(&self, updates: &[(String, f64)]) -> Result<()> {
+        if updates.is_empty() {
+            return Ok(());
+        }
+
+        let conn = self.db.acquire_write().await?;
+        let tx = conn.transaction().await?;
+        for (id, importance) in updates {
+            tx.execute(
+                "UPDATE memories SET importance = ?2 WHERE id = ?1",
+                params![id.as_str(), *importance],
+            )
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn count_active_memories(&self) -> Result<u64> {
+        let conn = self.db.acquire().await?;
+        let row = conn
+            .query(
+                "SELECT COUNT(*) FROM memories WHERE is_forgotten = 0 AND is_latest = 1",
+                (),
+            )
+            .await?
+            .next()
+            .await?;
+
+        Ok(row.map(|row| row.get::<i64>(0)).transpose()?.unwrap_or(0) as u64)
+    }
+
+    async fn get_least_important_memory_ids(&self, limit: usize) -> Result<Vec<String>> {
+        let conn = self.db.acquire().await?;
+        let mut rows = conn
+            .query(
+                "SELECT id FROM memories WHERE is_forgotten = 0 AND is_latest = 1 AND is_static = 0 ORDER BY importance IS NULL, importance ASC, created_at ASC LIMIT ?1",
+                params![limit as i64],
+            )
+            .await?;
+
+        let mut results = Vec::new();
+        while let Some(row) = rows.next().await? {
+            results.push(row.get(0)?);
+        }
+        Ok(results)
+    }
+
     async fn get_active_container_tags(&self) -> Result<Vec<String>> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire().await?;
         let mut rows = conn
             .query(
                 "SELECT DISTINCT container_tag FROM memories WHERE is_forgotten = 0 AND is_latest = 1 AND container_tag IS NOT NULL",
@@ -324,7 +677,7 @@ impl MemoryStore for LibSqlBackend {
         &self,
         container_tag: &str,
     ) -> Result<Option<DateTime<Utc>>> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire().await?;
         let row = conn
             .query(
                 "SELECT MAX(updated_at) FROM memories WHERE container_tag = ?1 AND is_forgotten = 0 AND is_latest = 1",
@@ -344,6 +697,50 @@ impl MemoryStore for LibSqlBackend {
         }
         Ok(None)
     }
+
+    async fn flag_memory_contradiction(
+        &self,
+        memory_id: &str,
+        conflicting_memory_id: &str,
+        container_tag: Option<&str>,
+        reasoning: Option<&str>,
+    ) -> Result<MemoryContradiction> {
+        let conn = self.db.acquire_write().await?;
+        ContradictionRepository::create(
+            &conn,
+            memory_id,
+            conflicting_memory_id,
+            container_tag,
+            reasoning,
+        )
+        .await
+    }
+
+    async fn get_memory_contradiction(&self, id: &str) -> Result<Option<MemoryContradiction>> {
+        let conn = self.db.acquire().await?;
+        ContradictionRepository::get_by_id(&conn, id).await
+    }
+
+    async fn list_memory_contradictions(
+        &self,
+        container_tag: Option<&str>,
+        include_resolved: bool,
+        limit: u32,
+        offset: u32,
+    ) -> Result<(Vec<MemoryContradiction>, u64)> {
+        let conn = self.db.acquire().await?;
+        ContradictionRepository::list(&conn, container_tag, include_resolved, limit, offset).await
+    }
+
+    async fn resolve_memory_contradiction(
+        &self,
+        id: &str,
+        resolution: ContradictionResolution,
+        change: &ContradictionChange,
+    ) -> Result<bool> {
+        let conn = self.db.acquire_write().await?;
+        ContradictionRepository::resolve(&conn, id, resolution, change).await
+    }
 }
 
 #[async_trait]
@@ -354,11 +751,11 @@ impl MemorySourceStore for LibSqlBackend {
         document_id: &str,
         chunk_id: Option<&str>,
     ) -> Result<MemorySource> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire_write().await?;
         MemorySourcesRepository::create(&conn, memory_id, document_id, chunk_id).await
     }
     async fn get_sources_by_memory(&self, memory_id: &str) -> Result<Vec<MemorySource>> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire().await?;
         MemorySourcesRepository::get_by_memory(&conn, memory_id).await
     }
 }
@@ -366,13 +763,128 @@ impl MemorySourceStore for LibSqlBackend {
 #[async_trait]
 impl MetadataStore for LibSqlBackend {
     async fn get_embedding_dimensions(&self) -> Result<Option<usize>> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire().await?;
         MetadataRepository::get_embedding_dimensions(&conn).await
     }
     async fn set_embedding_dimensions(&self, dims: usize) -> Result<()> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire_write().await?;
         MetadataRepository::set_embedding_dimensions(&conn, dims).await
     }
+    async fn get_container_embedding_model(
+        &self,
+        container_tag: &str,
+    ) -> Result<Option<ContainerEmbeddingModel>> {
+        let conn = self.db.acquire().await?;
+        MetadataRepository::get_container_embedding_model(&conn, container_tag).await
+    }
+    async fn set_container_embedding_model(&self, record: &ContainerEmbeddingModel) -> Result<()> {
+        let conn = self.db.acquire_write().await?;
+        MetadataRepository::set_container_embedding_model(&conn, record).await
+    }
+    async fn record_container_embedding_model(
+        &self,
+        record: &ContainerEmbeddingModel,
+    ) -> Result<()> {
+        let conn = self.db.acquire_write().await?;
+        MetadataRepository::record_container_embedding_model(&conn, record).await
+    }
+    async fn list_container_embedding_models(&self) -> Result<Vec<ContainerEmbeddingModel>> {
+        let conn = self.db.acquire().await?;
+        MetadataRepository::list_container_embedding_models(&conn).await
+    }
+    async fn get_container_settings(
+        &self,
+        container_tag: &str,
+    ) -> Result<Option<ContainerSettings>> {
+        let conn = self.db.acquire().await?;
+        MetadataRepository::get_container_settings(&conn, container_tag).await
+    }
+    async fn set_container_settings(&self, settings: &ContainerSettings) -> Result<()> {
+        let conn = self.db.acquire_write().await?;
+        MetadataRepository::set_container_settings(&conn, settings).await
+    }
+    async fn create_webhook(&self, webhook: &Webhook) -> Result<()> {
+        let conn = self.db.acquire_write().await?;
+        WebhookRepository::create(&conn, webhook).await
+    }
+    async fn list_webhooks(&self, container_tags: Option<&[String]>) -> Result<Vec<Webhook>> {
+        let conn = self.db.acquire().await?;
+        WebhookRepository::list(&conn, container_tags).await
+    }
+    async fn delete_webhook(&self, id: &str) -> Result<bool> {
+        let conn = self.db.acquire_write().await?;
+        WebhookRepository::delete(&conn, id).await
+    }
+    async fn get_idempotent_document(
+        &self,
+        api_key_id: &str,
+        idempotency_key: &str,
+    ) -> Result<Option<IdempotentDocument>> {
+        let conn = self.db.acquire().await?;
+        MetadataRepository::get_idempotent_document(&conn, api_key_id, idempotency_key).await
+    }
+    async fn claim_idempotency_key(
+        &self,
+        api_key_id: &str,
+        idempotency_key: &str,
+        document: &IdempotentDocument,
+        ttl: Duration,
+    ) -> Result<IdempotentDocument> {
+        let conn = self.db.acquire_write().await?;
+        MetadataRepository::claim_idempotency_key(&conn, api_key_id, idempotency_key, document, ttl)
+            .await
+    }
+    async fn release_idempotency_key(
+        &self,
+        api_key_id: &str,
+        idempotency_key: &str,
+        document_id: &str,
+    ) -> Result<()> {
+        let conn = self.db.acquire_write().await?;
+        MetadataRepository::release_idempotency_key(&conn, api_key_id, idempotency_key, document_id)
+            .await
+    }
+    async fn try_acquire_lease(&self, name: &str, ttl: Duration) -> Result<Option<String>> {
+        let conn = self.db.acquire_write().await?;
+        MetadataRepository::try_acquire_lease(&conn, name, ttl).await
+    }
+    async fn release_lease(&self, name: &str, token: &str) -> Result<()> {
+        let conn = self.db.acquire_write().await?;
+        MetadataRepository::release_lease(&conn, name, token).await
+    }
+    async fn get_latest_write_at(&self) -> Result<Option<DateTime<Utc>>> {
+        let conn = self.db.acquire().await?;
+        MetadataRepository::get_latest_write_at(&conn).await
+    }
+}
+
+#[async_trait]
+impl JobStore for LibSqlBackend {
+    async fn create_job(&self, kind: JobKind, total: Option<u64>) -> Result<Job> {
+        let conn = self.db.acquire_write().await?;
+        JobRepository::create(&conn, kind, total).await
+    }
+    async fn get_job(&self, id: &str) -> Result<Option<Job>> {
+        let conn = self.db.acquire().await?;
+        JobRepository::get_by_id(&conn, id).await
+    }
+    async fn start_job(&self, id: &str) -> Result<()> {
+        let conn = self.db.acquire_write().await?;
+        JobRepository::set_running(&conn, id).await
+    }
+    async fn update_job_progress(&self, id: &str, processed: u64) -> Result<()> {
+        let conn = self.db.acquire_write().await?;
+        JobRepository::update_progress(&conn, id, processed).await
+    }
+    async fn finish_job(
+        &self,
+        id: &str,
+        result: Option<&serde_json::Value>,
+        error: Option<&str>,
+    ) -> Result<()> {
+        let conn = self.db.acquire_write().await?;
+        JobRepository::finish(&conn, id, result, error).await
+    }
 }
 
 #[async_trait]
@@ -381,8 +893,28 @@ impl DatabaseBackend for LibSqlBackend {
         self.db.sync().await
     }
 
+    async fn ping(&self) -> Result<()> {
+        let conn = self.db.acquire().await?;
+        conn.query("SELECT 1", ()).await?.next().await?;
+        Ok(())
+    }
+
+    async fn keyword_indexes_ready(&self) -> Result<bool> {
+        let conn = self.db.acquire().await?;
+        let row = conn
+            .query(
+                "SELECT COUNT(*) FROM sqlite_master
+                 WHERE type = 'table' AND name IN ('chunks_fts', 'memories_fts')",
+                (),
+            )
+            .await?
+            .next()
+            .await?;
+        Ok(row.map(|row| row.get::<i64>(0)).transpose()? == Some(2))
+    }
+
     async fn get_container_filter(&self, tag: &str) -> Result<Option<ContainerFilter>> {
-        let conn = self.db.connect()?;
+        let conn = self.db.acquire().await?;
         let row = conn
             .query(
                 "SELECT tag, should_llm_filter, filter_prompt FROM container_tags WHERE tag = ?1",
@@ -428,6 +960,7 @@ mod tests {
             ),
             auth_token: None,
             local_path: None,
+            max_connections: 4,
         };
         let db = Database::new(&config)
             .await
@@ -515,4 +1048,56 @@ mod tests {
             "Extracting document should remain 'extracting'"
         );
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_document_reads_share_pool() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = DatabaseConfig {
+            url: format!("file:{}", temp_dir.path().join("pool.db").display()),
+            auth_token: None,
+            local_path: None,
+            max_connections: 4,
+        };
+        let backend =
+            std::sync::Arc::new(LibSqlBackend::new(Database::new(&config).await.unwrap()));
+        let now = chrono::Utc::now().to_rfc3339();
+        backend
+            .db
+            .connect()
+            .unwrap()
+            .execute(
+                "INSERT INTO documents (id, doc_type, status, created_at, updated_at) VALUES ('doc_pool', 'text', 'queued', ?1, ?1)",
+                params![now],
+            )
+            .await
+            .unwrap();
+
+        // Far more callers than pooled connections, with writes interleaved.
+        let tasks: Vec<_> = (0..200)
+            .map(|i| {
+                let backend = backend.clone();
+                tokio::spawn(async move {
+                    if i % 20 == 0 {
+                        backend
+                            .update_document_status("doc_pool", ProcessingStatus::Queued, None)
+                            .await
+                            .map(|_| None)
+                    } else {
+                        backend.get_document_by_id("doc_pool").await
+                    }
+                })
+            })
+            .collect();
+
+        let results =
+            tokio::time::timeout(Duration::from_secs(30), futures::future::join_all(tasks))
+                .await
+                .expect("concurrent reads should not deadlock");
+        for result in results {
+            let doc = result.unwrap().expect("read should not error under load");
+            if let Some(doc) = doc {
+                assert_eq!(doc.id, "doc_pool");
+            }
+        }
+    }
 }
@@ -4,10 +4,12 @@ use std::sync::Arc;
 use crate::config::DatabaseConfig;
 use crate::error::Result;
 
+use super::pool::{ConnectionPool, PooledConnection};
 use super::schema;
 
 pub struct Database {
     pub(crate) db: Arc<libsql::Database>,
+    pool: Arc<ConnectionPool>,
     pub(crate) busy_timeout_ms: u64,
     pub(crate) journal_mode: String,
     pub(crate) synchronous: String,
@@ -52,8 +54,15 @@ impl Database {
             Builder::new_local(path).build().await?
         };
 
+        let db = Arc::new(db);
+        let pool = Arc::new(ConnectionPool::new(
+            Arc::clone(&db),
+            config.max_connections,
+            busy_timeout_ms,
+        ));
         let database = Self {
-            db: Arc::new(db),
+            db,
+            pool,
             busy_timeout_ms,
             journal_mode,
             synchronous,
@@ -68,6 +77,17 @@ impl Database {
         Ok(self.db.connect()?)
     }
 
+    /// Pooled connection for queries that only read.
+    pub async fn acquire(&self) -> Result<PooledConnection> {
+        self.pool.acquire().await
+    }
+
+    /// Pooled connection for queries that write; writers are serialized to
+    /// avoid `SQLITE_BUSY` between them.
+    pub async fn acquire_write(&self) -> Result<PooledConnection> {
+        self.pool.acquire_write().await
+    }
+
     async fn configure_database(&self) -> Result<()> {
         let conn = self.connect()?;
 
@@ -134,11 +154,17 @@ impl Database {
         Ok(())
     }
 
+    /// Pull the latest frames from the remote primary. Local and remote-only
+    /// databases have nothing to sync and succeed without doing anything.
     pub async fn sync(&self) -> Result<()> {
-        if let Ok(sync) = self.db.sync().await {
-            tracing::info!("Database synced: {:?}", sync);
+        match self.db.sync().await {
+            Ok(sync) => {
+                tracing::debug!("Database synced: {:?}", sync);
+                Ok(())
+            }
+            Err(libsql::Error::SyncNotSupported(_)) => Ok(()),
+            Err(error) => Err(error.into()),
         }
-        Ok(())
     }
 }
 
@@ -146,6 +172,7 @@ impl Clone for Database {
     fn clone(&self) -> Self {
         Self {
             db: Arc::clone(&self.db),
+            pool: Arc::clone(&self.pool),
             busy_timeout_ms: self.busy_timeout_ms,
             journal_mode: self.journal_mode.clone(),
             synchronous: self.synchronous.clone(),
@@ -1,5 +1,9 @@
+use std::time::Duration;
+
+use crate::db::traits::IdempotentDocument;
 use crate::error::Result;
-use chrono::Utc;
+use crate::models::{ContainerEmbeddingModel, ContainerSettings};
+use chrono::{DateTime, SecondsFormat, Utc};
 use libsql::Connection;
 
 pub struct MetadataRepository;
@@ -38,4 +42,565 @@ impl MetadataRepository {
     pub async fn set_embedding_dimensions(conn: &Connection, dims: usize) -> Result<()> {
         Self::set(conn, "embedding_dimensions", &dims.to_string()).await
     }
+
+    pub async fn get_container_embedding_model(
+        conn: &Connection,
+        container_tag: &str,
+    ) -> Result<Option<ContainerEmbeddingModel>> {
+        let mut rows = conn
+            .query(
+                "SELECT container_tag, model, dimensions FROM container_embedding_models
+                 WHERE container_tag = ?1",
+                [container_tag],
+            )
+            .await?;
+
+        match rows.next().await? {
+            Some(row) => Ok(Some(row_to_container_model(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn set_container_embedding_model(
+        conn: &Connection,
+        record: &ContainerEmbeddingModel,
+    ) -> Result<()> {
+        conn.execute(
+            "INSERT INTO container_embedding_models (container_tag, model, dimensions, updated_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(container_tag) DO UPDATE SET
+                model = excluded.model,
+                dimensions = excluded.dimensions,
+                updated_at = excluded.updated_at",
+            libsql::params![
+                record.container_tag.as_str(),
+                record.model.as_str(),
+                record.dimensions as i64,
+                Utc::now().to_rfc3339(),
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Store `record` unless its container already has a recorded model, so
+    /// the first model to index a container stays its model.
+    pub async fn record_container_embedding_model(
+        conn: &Connection,
+        record: &ContainerEmbeddingModel,
+    ) -> Result<()> {
+        conn.execute(
+            "INSERT INTO container_embedding_models (container_tag, model, dimensions, updated_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(container_tag) DO NOTHING",
+            libsql::params![
+                record.container_tag.as_str(),
+                record.model.as_str(),
+                record.dimensions as i64,
+                Utc::now().to_rfc3339(),
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn list_container_embedding_models(
+        conn: &Connection,
+    ) -> Result<Vec<ContainerEmbeddingModel>> {
+        let mut rows = conn
+            .query(
+                "SELECT container_tag, model, dimensions FROM container_embedding_models
+                 ORDER BY container_tag",
+                (),
+            )
+            .await?;
+
+        let mut records = Vec::new();
+        while let Some(row) = rows.next().await? {
+            records.push(row_to_container_model(&row)?);
+        }
+        Ok(records)
+    }
+
+    pub async fn get_container_settings(
+        conn: &Connection,
+        container_tag: &str,
+    ) -> Result<Option<ContainerSettings>> {
+        let mut rows = conn
+            .query(
+                "SELECT container_tag, episode_decay_days, episode_decay_factor
+                 FROM container_settings WHERE container_tag = ?1",
+                [container_tag],
+            )
+            .await?;
+
+        match rows.next().await? {
+            Some(row) => Ok(Some(ContainerSettings {
+                container_tag: row.get(0)?,
+                episode_decay_days: row.get(1)?,
+                episode_decay_factor: row.get(2)?,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn set_container_settings(
+        conn: &Connection,
+        settings: &ContainerSettings,
+    ) -> Result<()> {
+        conn.execute(
+            "INSERT INTO container_settings
+                (container_tag, episode_decay_days, episode_decay_factor, updated_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(container_tag) DO UPDATE SET
+                episode_decay_days = excluded.episode_decay_days,
+                episode_decay_factor = excluded.episode_decay_factor,
+                updated_at = excluded.updated_at",
+            libsql::params![
+                settings.container_tag.as_str(),
+                settings.episode_decay_days,
+                settings.episode_decay_factor,
+                Utc::now().to_rfc3339(),
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Document created under `idempotency_key` by the given API key, if the
+    /// key has not expired yet.
+    pub async fn get_idempotent_document(
+        conn: &Connection,
+        api_key_id: &str,
+        idempotency_key: &str,
+    ) -> Result<Option<IdempotentDocument>> {
+        let now = Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true);
+        let mut rows = conn
+            .query(
+                "SELECT document_id, near_duplicate_of FROM idempotency_keys
+                 WHERE api_key_id = ?1 AND idempotency_key = ?2 AND expires_at > ?3",
+                [api_key_id, idempotency_key, now.as_str()],
+            )
+            .await?;
+
+        match rows.next().await? {
+            Some(row) => Ok(Some(IdempotentDocument {
+                document_id: row.get(0)?,
+                near_duplicate_of: row.get(1)?,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    /// Reserve `idempotency_key` for `document` for `ttl` and return the entry
+    /// that holds the key afterwards: `document` itself, or the document of an
+    /// unexpired earlier claim. Expired entries are pruned first.
+    pub async fn claim_idempotency_key(
+        conn: &Connection,
+        api_key_id: &str,
+        idempotency_key: &str,
+        document: &IdempotentDocument,
+        ttl: Duration,
+    ) -> Result<IdempotentDocument> {
+        let now = Utc::now();
+        let ttl = chrono::Duration::from_std(ttl).unwrap_or(chrono::Duration::days(1));
+        let expires_at = (now + ttl).to_rfc3339_opts(SecondsFormat::Millis, true);
+        let now = now.to_rfc3339_opts(SecondsFormat::Millis, true);
+
+        conn.execute(
+            "DELETE FROM idempotency_keys WHERE expires_at <= ?1",
+            [now.as_str()],
+        )
+        .await?;
+        let mut rows = conn
+            .query(
+                "INSERT INTO idempotency_keys
+                     (api_key_id, idempotency_key, document_id, near_duplicate_of, expires_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT(api_key_id, idempotency_key)
+                     DO UPDATE SET api_key_id = api_key_id
+                 RETURNING document_id, near_duplicate_of",
+                libsql::params![
+                    api_key_id,
+                    idempotency_key,
+                    document.document_id.as_str(),
+                    document.near_duplicate_of.as_deref(),
+                    expires_at.as_str(),
+                ],
+            )
+            .await?;
+
+        match rows.next().await? {
+            Some(row) => Ok(IdempotentDocument {
+                document_id: row.get(0)?,
+                near_duplicate_of: row.get(1)?,
+            }),
+            None => Ok(document.clone()),
+        }
+    }
+
+    /// Drop a claim made by `claim_idempotency_key` whose document was never
+    /// created, so a retry can try again.
+    pub async fn release_idempotency_key(
+        conn: &Connection,
+        api_key_id: &str,
+        idempotency_key: &str,
+        document_id: &str,
+    ) -> Result<()> {
+        conn.execute(
+            "DELETE FROM idempotency_keys
+             WHERE api_key_id = ?1 AND idempotency_key = ?2 AND document_id = ?3",
+            [api_key_id, idempotency_key, document_id],
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Try to take the named lease for `ttl`. Returns a token for `release_lease`,
+    /// or `None` while another holder's lease has not expired.
+    ///
+    /// The stored value is `<expiry>|<token>`; expiries share one RFC 3339 format,
+    /// so they compare correctly as strings.
+    pub async fn try_acquire_lease(
+        conn: &Connection,
+        name: &str,
+        ttl: Duration,
+    ) -> Result<Option<String>> {
+        let now = Utc::now();
+        let ttl = chrono::Duration::from_std(ttl).unwrap_or(chrono::Duration::hours(1));
+        let token = nanoid::nanoid!();
+        let value = format!(
+            "{}|{token}",
+            (now + ttl).to_rfc3339_opts(SecondsFormat::Millis, true)
+        );
+        let now = now.to_rfc3339_opts(SecondsFormat::Millis, true);
+
+        let acquired = conn
+            .execute(
+                "INSERT INTO momo_meta (key, value, updated_at) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at
+                 WHERE momo_meta.value < ?3",
+                [lease_key(name).as_str(), value.as_str(), now.as_str()],
+            )
+            .await?;
+
+        Ok((acquired > 0).then_some(value))
+    }
+
+    /// Release a lease taken with `try_acquire_lease`. A lease that has since
+    /// expired and been taken by someone else is left alone.
+    pub async fn release_lease(conn: &Connection, name: &str, token: &str) -> Result<()> {
+        conn.execute(
+            "DELETE FROM momo_meta WHERE key = ?1 AND value = ?2",
+            [lease_key(name).as_str(), token],
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Most recent `updated_at` across documents and memories, used to compare
+    /// how far a read replica trails the primary.
+    pub async fn get_latest_write_at(conn: &Connection) -> Result<Option<DateTime<Utc>>> {
+        let mut latest = None;
+        for sql in [
+            "SELECT MAX(updated_at) FROM documents",
+            "SELECT MAX(updated_at) FROM memories",
+        ] {
+            let mut rows = conn.query(sql, ()).await?;
+            let Some(row) = rows.next().await? else {
+                continue;
+            };
+            let parsed = row
+                .get::<Option<String>>(0)?
+                .and_then(|value| DateTime::parse_from_rfc3339(&value).ok())
+                .map(|dt| dt.with_timezone(&Utc));
+            latest = latest.max(parsed);
+        }
+        Ok(latest)
+    }
+}
+
+fn lease_key(name: &str) -> String {
+    format!("lease:{name}")
+}
+
+fn row_to_container_model(row: &libsql::Row) -> Result<ContainerEmbeddingModel> {
+    Ok(ContainerEmbeddingModel {
+        container_tag: row.get(0)?,
+        model: row.get(1)?,
+        dimensions: row.get::<i64>(2)?.max(0) as usize,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_test_db() -> Connection {
+        let conn = libsql::Builder::new_local(":memory:")
+            .build()
+            .await
+            .unwrap()
+            .connect()
+            .unwrap();
+
+        conn.execute(
+            "CREATE TABLE momo_meta (key TEXT PRIMARY KEY, value TEXT NOT NULL, updated_at TEXT NOT NULL)",
+            (),
+        )
+        .await
+        .unwrap();
+        conn.execute(
+            "CREATE TABLE container_embedding_models (
+                container_tag TEXT PRIMARY KEY,
+                model TEXT NOT NULL,
+                dimensions INTEGER NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+            (),
+        )
+        .await
+        .unwrap();
+        conn.execute(
+            "CREATE TABLE idempotency_keys (
+                api_key_id TEXT NOT NULL,
+                idempotency_key TEXT NOT NULL,
+                document_id TEXT NOT NULL,
+                near_duplicate_of TEXT,
+                expires_at TEXT NOT NULL,
+                PRIMARY KEY (api_key_id, idempotency_key)
+            )",
+            (),
+        )
+        .await
+        .unwrap();
+        conn.execute(
+            "CREATE TABLE container_settings (
+                container_tag TEXT PRIMARY KEY,
+                episode_decay_days REAL,
+                episode_decay_factor REAL,
+                updated_at TEXT NOT NULL
+            )",
+            (),
+        )
+        .await
+        .unwrap();
+
+        conn
+    }
+
+    #[tokio::test]
+    async fn test_container_embedding_models_upsert_and_list() {
+        let conn = setup_test_db().await;
+        let record = |tag: &str, model: &str, dimensions| ContainerEmbeddingModel {
+            container_tag: tag.to_string(),
+            model: model.to_string(),
+            dimensions,
+        };
+
+        MetadataRepository::set_container_embedding_model(&conn, &record("b", "small", 384))
+            .await
+            .unwrap();
+        MetadataRepository::set_container_embedding_model(&conn, &record("a", "small", 384))
+            .await
+            .unwrap();
+        MetadataRepository::set_container_embedding_model(&conn, &record("b", "base", 768))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            MetadataRepository::get_container_embedding_model(&conn, "b")
+                .await
+                .unwrap(),
+            Some(record("b", "base", 768))
+        );
+        assert!(
+            MetadataRepository::get_container_embedding_model(&conn, "c")
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(
+            MetadataRepository::list_container_embedding_models(&conn)
+                .await
+                .unwrap(),
+            vec![record("a", "small", 384), record("b", "base", 768)]
+        );
+
+        MetadataRepository::record_container_embedding_model(&conn, &record("b", "small", 384))
+            .await
+            .unwrap();
+        MetadataRepository::record_container_embedding_model(&conn, &record("c", "small", 384))
+            .await
+            .unwrap();
+        assert_eq!(
+            MetadataRepository::list_container_embedding_models(&conn)
+                .await
+                .unwrap(),
+            vec![
+                record("a", "small", 384),
+                record("b", "base", 768),
+                record("c", "small", 384)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_container_settings_upsert_and_clear() {
+        let conn = setup_test_db().await;
+        assert!(MetadataRepository::get_container_settings(&conn, "work")
+            .await
+            .unwrap()
+            .is_none());
+
+        let mut settings = ContainerSettings {
+            container_tag: "work".to_string(),
+            episode_decay_days: Some(7.0),
+            episode_decay_factor: Some(0.5),
+        };
+        MetadataRepository::set_container_settings(&conn, &settings)
+            .await
+            .unwrap();
+        assert_eq!(
+            MetadataRepository::get_container_settings(&conn, "work")
+                .await
+                .unwrap(),
+            Some(settings.clone())
+        );
+
+        settings.episode_decay_factor = None;
+        MetadataRepository::set_container_settings(&conn, &settings)
+            .await
+            .unwrap();
+        assert_eq!(
+            MetadataRepository::get_container_settings(&conn, "work")
+                .await
+                .unwrap(),
+            Some(settings)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_keys_are_scoped_per_api_key_and_expire() {
+        let conn = setup_test_db().await;
+        let ttl = Duration::from_secs(60);
+        let doc = |id: &str| IdempotentDocument {
+            document_id: id.to_string(),
+            near_duplicate_of: None,
+        };
+        let first = IdempotentDocument {
+            document_id: "doc1".to_string(),
+            near_duplicate_of: Some("doc0".to_string()),
+        };
+
+        let holder =
+            MetadataRepository::claim_idempotency_key(&conn, "tenant_a", "retry-1", &first, ttl)
+                .await
+                .unwrap();
+        assert_eq!(holder, first);
+        let holder = MetadataRepository::claim_idempotency_key(
+            &conn,
+            "tenant_a",
+            "retry-1",
+            &doc("doc2"),
+            ttl,
+        )
+        .await
+        .unwrap();
+        assert_eq!(holder, first);
+
+        assert_eq!(
+            MetadataRepository::get_idempotent_document(&conn, "tenant_a", "retry-1")
+                .await
+                .unwrap(),
+            Some(first)
+        );
+        assert!(
+            MetadataRepository::get_idempotent_document(&conn, "tenant_b", "retry-1")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        MetadataRepository::claim_idempotency_key(
+            &conn,
+            "tenant_b",
+            "retry-2",
+            &doc("doc3"),
+            Duration::ZERO,
+        )
+        .await
+        .unwrap();
+        assert!(
+            MetadataRepository::get_idempotent_document(&conn, "tenant_b", "retry-2")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        MetadataRepository::release_idempotency_key(&conn, "tenant_a", "retry-1", "doc1")
+            .await
+            .unwrap();
+        assert!(
+            MetadataRepository::get_idempotent_document(&conn, "tenant_a", "retry-1")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lease_is_exclusive_until_released() {
+        let conn = setup_test_db().await;
+        let ttl = Duration::from_secs(60);
+
+        let token = MetadataRepository::try_acquire_lease(&conn, "job", ttl)
+            .await
+            .unwrap()
+            .expect("first acquire succeeds");
+        assert!(MetadataRepository::try_acquire_lease(&conn, "job", ttl)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(MetadataRepository::try_acquire_lease(&conn, "other", ttl)
+            .await
+            .unwrap()
+            .is_some());
+
+        MetadataRepository::release_lease(&conn, "job", &token)
+            .await
+            .unwrap();
+        assert!(MetadataRepository::try_acquire_lease(&conn, "job", ttl)
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_expired_lease_can_be_taken_over() {
+        let conn = setup_test_db().await;
+
+        let stale = MetadataRepository::try_acquire_lease(&conn, "job", Duration::ZERO)
+            .await
+            .unwrap()
+            .unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+
+        let fresh = MetadataRepository::try_acquire_lease(&conn, "job", Duration::from_secs(60))
+            .await
+            .unwrap()
+            .expect("expired lease is taken over");
+
+        // Releasing with the stale token must not drop the new holder's lease
+        MetadataRepository::release_lease(&conn, "job", &stale)
+            .await
+            .unwrap();
+        assert!(
+            MetadataRepository::try_acquire_lease(&conn, "job", Duration::from_secs(60))
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert_ne!(stale, fresh);
+    }
 }
@@ -1,6 +1,8 @@
 pub mod backends;
 mod connection;
 mod metadata;
+mod pool;
+mod replica;
 pub mod repository;
 pub(crate) mod schema;
 pub mod traits;
@@ -8,4 +10,6 @@ pub mod traits;
 pub use backends::libsql::LibSqlBackend;
 pub use connection::Database;
 pub use metadata::MetadataRepository;
+pub use pool::PooledConnection;
+pub use replica::{ReplicaHealth, ReplicaSyncSnapshot};
 pub use traits::*;
@@ -0,0 +1,108 @@
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+
+use libsql::Connection;
+use tokio::sync::{OwnedMutexGuard, OwnedSemaphorePermit, Semaphore};
+
+use crate::error::Result;
+
+/// Bounded pool of connections to one libsql database.
+///
+/// Up to `max_connections` queries run at once. Writes additionally take a
+/// pool-wide lock so they never contend with each other for SQLite's write
+/// lock; reads are not blocked by it.
+pub(crate) struct ConnectionPool {
+    db: Arc<libsql::Database>,
+    busy_timeout_ms: u64,
+    idle: Arc<Mutex<Vec<Connection>>>,
+    permits: Arc<Semaphore>,
+    writes: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl ConnectionPool {
+    pub(crate) fn new(
+        db: Arc<libsql::Database>,
+        max_connections: usize,
+        busy_timeout_ms: u64,
+    ) -> Self {
+        Self {
+            db,
+            busy_timeout_ms,
+            idle: Arc::new(Mutex::new(Vec::new())),
+            permits: Arc::new(Semaphore::new(max_connections.max(1))),
+            writes: Arc::new(tokio::sync::Mutex::new(())),
+        }
+    }
+
+    /// A connection for reads, waiting while the pool is exhausted.
+    pub(crate) async fn acquire(&self) -> Result<PooledConnection> {
+        self.checkout(None).await
+    }
+
+    /// A connection for writes, held exclusively against other writers.
+    pub(crate) async fn acquire_write(&self) -> Result<PooledConnection> {
+        let write = Arc::clone(&self.writes).lock_owned().await;
+        self.checkout(Some(write)).await
+    }
+
+    async fn checkout(&self, write: Option<OwnedMutexGuard<()>>) -> Result<PooledConnection> {
+        let permit = Arc::clone(&self.permits)
+            .acquire_owned()
+            .await
+            .expect("connection pool semaphore is never closed");
+
+        let idle = self.idle.lock().unwrap_or_else(|e| e.into_inner()).pop();
+        let conn = match idle {
+            Some(conn) => conn,
+            None => self.open().await?,
+        };
+
+        Ok(PooledConnection {
+            conn: Some(conn),
+            idle: Arc::clone(&self.idle),
+            _write: write,
+            _permit: permit,
+        })
+    }
+
+    async fn open(&self) -> Result<Connection> {
+        let conn = self.db.connect()?;
+        // busy_timeout is per connection; remote databases don't support it.
+        let busy_timeout_sql = format!("PRAGMA busy_timeout = {}", self.busy_timeout_ms);
+        if let Err(error) = conn.execute_batch(&busy_timeout_sql).await {
+            tracing::debug!(error = %error, "Failed to set busy_timeout on pooled connection");
+        }
+        Ok(conn)
+    }
+}
+
+/// A connection checked out of a `ConnectionPool`, returned to it on drop.
+pub struct PooledConnection {
+    conn: Option<Connection>,
+    idle: Arc<Mutex<Vec<Connection>>>,
+    // Dropped after the connection is back in the idle list.
+    _write: Option<OwnedMutexGuard<()>>,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl Deref for PooledConnection {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn
+            .as_ref()
+            .expect("connection is present until drop")
+    }
+}
+
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        // A connection left inside a transaction is closed rather than reused.
+        if let Some(conn) = self.conn.take().filter(|conn| conn.is_autocommit()) {
+            self.idle
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(conn);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use std::sync::{Arc, RwLock};
+
+use chrono::{DateTime, Utc};
+
+use crate::error::Result;
+
+/// Outcome of the read-replica sync loop so far.
+#[derive(Debug, Clone, Default)]
+pub struct ReplicaSyncSnapshot {
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub consecutive_failures: u32,
+}
+
+/// Sync results shared between the replica sync loop and the API.
+///
+/// Doubles as a circuit breaker: after `fallback_after_failures` consecutive
+/// failed syncs, readers should use the primary until the replica syncs again.
+#[derive(Debug, Clone)]
+pub struct ReplicaHealth {
+    sync_interval_secs: u64,
+    fallback_after_failures: Option<u32>,
+    state: Arc<RwLock<ReplicaSyncSnapshot>>,
+}
+
+impl ReplicaHealth {
+    pub fn new(sync_interval_secs: u64) -> Self {
+        Self {
+            sync_interval_secs,
+            fallback_after_failures: None,
+            state: Arc::new(RwLock::new(ReplicaSyncSnapshot::default())),
+        }
+    }
+
+    /// Fall back to the primary after `failures` consecutive failed syncs.
+    pub fn with_fallback_after(mut self, failures: u32) -> Self {
+        self.fallback_after_failures = Some(failures.max(1));
+        self
+    }
+
+    pub fn sync_interval_secs(&self) -> u64 {
+        self.sync_interval_secs
+    }
+
+    /// Whether reads should go to the primary instead of the replica.
+    pub fn should_fall_back(&self) -> bool {
+        self.tripped(self.snapshot().consecutive_failures)
+    }
+
+    /// Record the result of one sync attempt.
+    pub fn record(&self, result: &Result<()>) {
+        let now = Utc::now();
+        let mut state = self.state.write().unwrap_or_else(|e| e.into_inner());
+        state.last_attempt_at = Some(now);
+        match result {
+            Ok(()) => {
+                if self.tripped(state.consecutive_failures) {
+                    tracing::info!("Read replica recovered; reads use the replica again");
+                }
+                state.last_success_at = Some(now);
+                state.last_error = None;
+                state.consecutive_failures = 0;
+            }
+            Err(e) => {
+                state.last_error = Some(e.to_string());
+                state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+                if self.fallback_after_failures == Some(state.consecutive_failures) {
+                    tracing::warn!(
+                        failures = state.consecutive_failures,
+                        "Read replica sync keeps failing; routing reads to the primary database"
+                    );
+                }
+            }
+        }
+    }
+
+    fn tripped(&self, consecutive_failures: u32) -> bool {
+        self.fallback_after_failures
+            .is_some_and(|threshold| consecutive_failures >= threshold)
+    }
+
+    pub fn snapshot(&self) -> ReplicaSyncSnapshot {
+        self.state.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::MomoError;
+
+    #[test]
+    fn test_record_tracks_failures_until_next_success() {
+        let health = ReplicaHealth::new(5);
+        assert!(health.snapshot().last_attempt_at.is_none());
+
+        health.record(&Err(MomoError::Internal("unreachable".to_string())));
+        health.record(&Err(MomoError::Internal("unreachable".to_string())));
+        let snapshot = health.snapshot();
+        assert_eq!(snapshot.consecutive_failures, 2);
+        assert!(snapshot.last_success_at.is_none());
+        assert!(snapshot.last_error.unwrap().contains("unreachable"));
+
+        health.record(&Ok(()));
+        let snapshot = health.snapshot();
+        assert_eq!(snapshot.consecutive_failures, 0);
+        assert!(snapshot.last_error.is_none());
+        assert_eq!(snapshot.last_success_at, snapshot.last_attempt_at);
+    }
+
+    #[test]
+    fn test_fallback_trips_after_threshold_and_resets() {
+        let strict = ReplicaHealth::new(5);
+        let health = ReplicaHealth::new(5).with_fallback_after(2);
+        let fail = || Err(MomoError::Internal("unreachable".to_string()));
+
+        health.record(&fail());
+        strict.record(&fail());
+        assert!(!health.should_fall_back());
+
+        health.record(&fail());
+        strict.record(&fail());
+        assert!(health.should_fall_back());
+        assert!(!strict.should_fall_back());
+
+        health.record(&Ok(()));
+        assert!(!health.should_fall_back());
+    }
+}
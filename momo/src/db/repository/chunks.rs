@@ -1,7 +1,13 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
 use libsql::{params, Connection};
 
 use crate::error::Result;
-use crate::models::{Chunk, ChunkWithDocument};
+use crate::models::{Chunk, ChunkWithDocument, ChunkerType, HighlightMarkers};
+
+use super::keyword;
+use super::vector::decode_f32_blob;
 
 /// Build parameterized LIKE clauses for container_tags filtering.
 /// Returns (sql_fragment, param_values) where sql_fragment uses positional
@@ -26,6 +32,12 @@ fn build_tag_filter(
     (clauses.join(" OR "), values)
 }
 
+/// Chunks stored before the chunker was recorded, or by an unknown chunker,
+/// have no type.
+fn parse_chunker(value: Option<String>) -> Option<ChunkerType> {
+    value.and_then(|value| value.parse().ok())
+}
+
 pub struct ChunkRepository;
 
 fn write_batch_size() -> usize {
@@ -48,8 +60,9 @@ impl ChunkRepository {
         conn.execute(
             r#"
             INSERT INTO chunks (
-                id, document_id, content, embedded_content, position, token_count, created_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                id, document_id, content, embedded_content, position, token_count, chunker,
+                language, created_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
             "#,
             params![
                 chunk.id.clone(),
@@ -58,6 +71,8 @@ impl ChunkRepository {
                 chunk.embedded_content.clone(),
                 chunk.position,
                 chunk.token_count,
+                chunk.chunker.map(|chunker| chunker.to_string()),
+                chunk.language.clone(),
                 chunk.created_at.to_rfc3339(),
             ],
         )
@@ -127,6 +142,86 @@ impl ChunkRepository {
         Ok(())
     }
 
+    /// Replace a chunk's content and clear its embedding so it is picked up for
+    /// re-embedding. Any contextual prefix in `embedded_content` is dropped since
+    /// it described the old text. Returns `None` if the document has no such chunk.
+    pub async fn update_content(
+        conn: &Connection,
+        document_id: &str,
+        chunk_id: &str,
+        content: &str,
+    ) -> Result<Option<Chunk>> {
+        let mut rows = conn
+            .query(
+                r#"
+                UPDATE chunks
+                SET content = ?3, embedded_content = NULL, embedding = NULL
+                WHERE id = ?1 AND document_id = ?2
+                RETURNING id, document_id, content, embedded_content, position, token_count,
+                          chunker, language, created_at
+                "#,
+                params![chunk_id, document_id, content],
+            )
+            .await?;
+
+        let Some(row) = rows.next().await? else {
+            return Ok(None);
+        };
+        Ok(Some(Chunk {
+            id: row.get(0)?,
+            document_id: row.get(1)?,
+            content: row.get(2)?,
+            embedded_content: row.get(3)?,
+            position: row.get(4)?,
+            token_count: row.get(5)?,
+            chunker: parse_chunker(row.get(6)?),
+            language: row.get(7)?,
+            created_at: DateTime::parse_from_rfc3339(&row.get::<String>(8)?)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        }))
+    }
+
+    /// Chunks of fully processed documents that have no embedding, oldest first.
+    ///
+    /// Chunks of documents still in the pipeline are skipped; the pipeline
+    /// embeds those itself.
+    pub async fn get_missing_embeddings(conn: &Connection, limit: usize) -> Result<Vec<Chunk>> {
+        let mut rows = conn
+            .query(
+                r#"
+                SELECT c.id, c.document_id, c.content, c.embedded_content, c.position,
+                       c.token_count, c.chunker, c.language, c.created_at
+                FROM chunks c
+                JOIN documents d ON c.document_id = d.id
+                WHERE c.embedding IS NULL AND d.status = 'done'
+                ORDER BY c.created_at ASC
+                LIMIT ?1
+                "#,
+                params![limit as i64],
+            )
+            .await?;
+
+        let mut chunks = Vec::new();
+        while let Some(row) = rows.next().await? {
+            chunks.push(Chunk {
+                id: row.get(0)?,
+                document_id: row.get(1)?,
+                content: row.get(2)?,
+                embedded_content: row.get(3)?,
+                position: row.get(4)?,
+                token_count: row.get(5)?,
+                chunker: parse_chunker(row.get(6)?),
+                language: row.get(7)?,
+                created_at: DateTime::parse_from_rfc3339(&row.get::<String>(8)?)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+            });
+        }
+
+        Ok(chunks)
+    }
+
     pub async fn delete_by_document_id(conn: &Connection, document_id: &str) -> Result<()> {
         conn.execute(
             "DELETE FROM chunks WHERE document_id = ?1",
@@ -137,68 +232,161 @@ impl ChunkRepository {
         Ok(())
     }
 
+    /// Chunks of `document_id` whose position lies in `start..=end`, in position order.
+    pub async fn get_by_document_and_position_range(
+        conn: &Connection,
+        document_id: &str,
+        start: i32,
+        end: i32,
+    ) -> Result<Vec<Chunk>> {
+        let mut rows = conn
+            .query(
+                r#"
+                SELECT id, document_id, content, embedded_content, position, token_count, chunker,
+                       language, created_at
+                FROM chunks
+                WHERE document_id = ?1 AND position BETWEEN ?2 AND ?3
+                ORDER BY position ASC
+                "#,
+                params![document_id, start, end],
+            )
+            .await?;
+
+        let mut chunks = Vec::new();
+        while let Some(row) = rows.next().await? {
+            chunks.push(Chunk {
+                id: row.get(0)?,
+                document_id: row.get(1)?,
+                content: row.get(2)?,
+                embedded_content: row.get(3)?,
+                position: row.get(4)?,
+                token_count: row.get(5)?,
+                chunker: parse_chunker(row.get(6)?),
+                language: row.get(7)?,
+                created_at: DateTime::parse_from_rfc3339(&row.get::<String>(8)?)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+            });
+        }
+
+        Ok(chunks)
+    }
+
+    /// Chunks of `document_id` at any of `positions`, in position order.
+    pub async fn get_by_document_and_positions(
+        conn: &Connection,
+        document_id: &str,
+        positions: &[i32],
+    ) -> Result<Vec<Chunk>> {
+        if positions.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // ?1 is the document id; positions start at ?2.
+        let placeholders = (2..=positions.len() + 1)
+            .map(|i| format!("?{i}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let sql = format!(
+            r#"
+            SELECT id, document_id, content, embedded_content, position, token_count, chunker,
+                   language, created_at
+            FROM chunks
+            WHERE document_id = ?1 AND position IN ({placeholders})
+            ORDER BY position ASC
+            "#
+        );
+        let mut params = vec![libsql::Value::from(document_id.to_string())];
+        params.extend(
+            positions
+                .iter()
+                .map(|&position| libsql::Value::from(position)),
+        );
+
+        let mut rows = conn.query(&sql, libsql::params_from_iter(params)).await?;
+
+        let mut chunks = Vec::new();
+        while let Some(row) = rows.next().await? {
+            chunks.push(Chunk {
+                id: row.get(0)?,
+                document_id: row.get(1)?,
+                content: row.get(2)?,
+                embedded_content: row.get(3)?,
+                position: row.get(4)?,
+                token_count: row.get(5)?,
+                chunker: parse_chunker(row.get(6)?),
+                language: row.get(7)?,
+                created_at: DateTime::parse_from_rfc3339(&row.get::<String>(8)?)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+            });
+        }
+
+        Ok(chunks)
+    }
+
     pub async fn search_similar(
         conn: &Connection,
         embedding: &[f32],
         limit: u32,
         threshold: f32,
         container_tags: Option<&[String]>,
+        chunker_types: Option<&[ChunkerType]>,
+        languages: Option<&[String]>,
     ) -> Result<Vec<ChunkWithDocument>> {
         let embedding_json = serde_json::to_string(embedding)?;
 
-        let has_tags = container_tags.map(|t| !t.is_empty()).unwrap_or(false);
-
-        let (query, tag_values) = if has_tags {
-            let tags = container_tags.unwrap();
-            // Fixed params: ?1=embedding, ?2=threshold, ?3=limit; tags start at ?4
+        // Fixed params: ?1=embedding, ?2=threshold, ?3=limit; filters start at ?4
+        let mut filters = String::new();
+        let mut filter_values = Vec::new();
+        if let Some(tags) = container_tags.filter(|t| !t.is_empty()) {
             let (tag_clause, tag_vals) = build_tag_filter(tags, 4, "d");
-            let q = format!(
-                r#"
-                SELECT 
-                    c.id as chunk_id,
-                    c.document_id,
-                    c.content as chunk_content,
-                    d.title as document_title,
-                    d.metadata as document_metadata,
-                    1 - vector_distance_cos(c.embedding, vector32(?1)) as score
-                FROM chunks c
-                JOIN documents d ON c.document_id = d.id
-                WHERE c.embedding IS NOT NULL
-                  AND (1 - vector_distance_cos(c.embedding, vector32(?1))) >= ?2
-                  AND ({tag_clause})
-                ORDER BY score DESC
-                LIMIT ?3
-                "#
-            );
-            (q, tag_vals)
-        } else {
-            (
-                r#"
-                SELECT 
-                    c.id as chunk_id,
-                    c.document_id,
-                    c.content as chunk_content,
-                    d.title as document_title,
-                    d.metadata as document_metadata,
-                    1 - vector_distance_cos(c.embedding, vector32(?1)) as score
-                FROM chunks c
-                JOIN documents d ON c.document_id = d.id
-                WHERE c.embedding IS NOT NULL
-                  AND (1 - vector_distance_cos(c.embedding, vector32(?1))) >= ?2
-                ORDER BY score DESC
-                LIMIT ?3
-                "#
-                .to_string(),
-                Vec::new(),
-            )
-        };
+            filters.push_str(&format!(" AND ({tag_clause})"));
+            filter_values.extend(tag_vals);
+        }
+        if let Some(types) = chunker_types.filter(|t| !t.is_empty()) {
+            let start_idx = 4 + filter_values.len();
+            let placeholders: Vec<String> = (0..types.len())
+                .map(|i| format!("?{}", start_idx + i))
+                .collect();
+            filters.push_str(&format!(" AND c.chunker IN ({})", placeholders.join(", ")));
+            filter_values.extend(types.iter().map(|t| libsql::Value::from(t.to_string())));
+        }
+        if let Some(languages) = languages.filter(|l| !l.is_empty()) {
+            let start_idx = 4 + filter_values.len();
+            let placeholders: Vec<String> = (0..languages.len())
+                .map(|i| format!("?{}", start_idx + i))
+                .collect();
+            filters.push_str(&format!(" AND c.language IN ({})", placeholders.join(", ")));
+            filter_values.extend(languages.iter().map(|l| libsql::Value::from(l.clone())));
+        }
+
+        let query = format!(
+            r#"
+            SELECT 
+                c.id as chunk_id,
+                c.document_id,
+                c.content as chunk_content,
+                d.title as document_title,
+                d.metadata as document_metadata,
+                1 - vector_distance_cos(c.embedding, vector32(?1)) as score,
+                c.position,
+                c.chunker
+            FROM chunks c
+            JOIN documents d ON c.document_id = d.id
+            WHERE c.embedding IS NOT NULL
+              AND (1 - vector_distance_cos(c.embedding, vector32(?1))) >= ?2{filters}
+            ORDER BY score DESC
+            LIMIT ?3
+            "#
+        );
 
         let mut param_values: Vec<libsql::Value> = vec![
             libsql::Value::from(embedding_json),
             libsql::Value::from(threshold as f64),
             libsql::Value::from(limit),
         ];
-        param_values.extend(tag_values);
+        param_values.extend(filter_values);
 
         let mut rows = conn
             .query(&query, libsql::params_from_iter(param_values))
@@ -215,6 +403,140 @@ impl ChunkRepository {
                 document_title: row.get(3)?,
                 document_metadata: serde_json::from_str(&row.get::<String>(4)?).unwrap_or_default(),
                 score,
+                position: row.get(6)?,
+                chunker: parse_chunker(row.get(7)?),
+                highlight: None,
+            });
+        }
+
+        Ok(results)
+    }
+
+    /// Stored embeddings of the given chunks, keyed by chunk id. Chunks without
+    /// an embedding are left out.
+    pub async fn get_embeddings(
+        conn: &Connection,
+        ids: &[String],
+    ) -> Result<HashMap<String, Vec<f32>>> {
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let placeholders: Vec<String> = (1..=ids.len()).map(|i| format!("?{i}")).collect();
+        let sql = format!(
+            "SELECT id, embedding FROM chunks WHERE embedding IS NOT NULL AND id IN ({})",
+            placeholders.join(", ")
+        );
+        let params: Vec<libsql::Value> = ids
+            .iter()
+            .map(|id| libsql::Value::from(id.clone()))
+            .collect();
+
+        let mut rows = conn.query(&sql, libsql::params_from_iter(params)).await?;
+        let mut embeddings = HashMap::new();
+        while let Some(row) = rows.next().await? {
+            let id: String = row.get(0)?;
+            embeddings.insert(id, decode_f32_blob(&row.get::<Vec<u8>>(1)?));
+        }
+        Ok(embeddings)
+    }
+
+    /// Chunks matching `query` in the keyword index, best first, with their
+    /// BM25 score negated so that higher is better. Filters as
+    /// [`Self::search_similar`].
+    pub async fn search_keyword(
+        conn: &Connection,
+        query: &str,
+        limit: u32,
+        container_tags: Option<&[String]>,
+        chunker_types: Option<&[ChunkerType]>,
+        languages: Option<&[String]>,
+        highlight: Option<&HighlightMarkers>,
+    ) -> Result<Vec<ChunkWithDocument>> {
+        let Some(match_query) = keyword::match_query(query) else {
+            return Ok(Vec::new());
+        };
+
+        // Fixed params: ?1=match query, ?2=limit; filters start at ?3
+        let mut filters = String::new();
+        let mut filter_values = Vec::new();
+        if let Some(tags) = container_tags.filter(|t| !t.is_empty()) {
+            let (tag_clause, tag_vals) = build_tag_filter(tags, 3, "d");
+            filters.push_str(&format!(" AND ({tag_clause})"));
+            filter_values.extend(tag_vals);
+        }
+        if let Some(types) = chunker_types.filter(|t| !t.is_empty()) {
+            let start_idx = 3 + filter_values.len();
+            let placeholders: Vec<String> = (0..types.len())
+                .map(|i| format!("?{}", start_idx + i))
+                .collect();
+            filters.push_str(&format!(" AND c.chunker IN ({})", placeholders.join(", ")));
+            filter_values.extend(types.iter().map(|t| libsql::Value::from(t.to_string())));
+        }
+        if let Some(languages) = languages.filter(|l| !l.is_empty()) {
+            let start_idx = 3 + filter_values.len();
+            let placeholders: Vec<String> = (0..languages.len())
+                .map(|i| format!("?{}", start_idx + i))
+                .collect();
+            filters.push_str(&format!(" AND c.language IN ({})", placeholders.join(", ")));
+            filter_values.extend(languages.iter().map(|l| libsql::Value::from(l.clone())));
+        }
+        // Markers go after the filters, so the filter placeholders stay put.
+        let highlight_column = match highlight {
+            Some(markers) => {
+                let open_idx = 3 + filter_values.len();
+                filter_values.push(libsql::Value::from(markers.open.clone()));
+                filter_values.push(libsql::Value::from(markers.close.clone()));
+                format!(
+                    "snippet(chunks_fts, 0, ?{open_idx}, ?{}, '…', {})",
+                    open_idx + 1,
+                    keyword::HIGHLIGHT_TOKENS
+                )
+            }
+            None => "NULL".to_string(),
+        };
+
+        let query = format!(
+            r#"
+            SELECT
+                c.id as chunk_id,
+                c.document_id,
+                c.content as chunk_content,
+                d.title as document_title,
+                d.metadata as document_metadata,
+                -bm25(chunks_fts) as score,
+                c.position,
+                c.chunker,
+                {highlight_column} as highlight
+            FROM chunks_fts
+            JOIN chunks c ON c.rowid = chunks_fts.rowid
+            JOIN documents d ON c.document_id = d.id
+            WHERE chunks_fts MATCH ?1{filters}
+            ORDER BY score DESC
+            LIMIT ?2
+            "#
+        );
+
+        let mut param_values: Vec<libsql::Value> =
+            vec![libsql::Value::from(match_query), libsql::Value::from(limit)];
+        param_values.extend(filter_values);
+
+        let mut rows = conn
+            .query(&query, libsql::params_from_iter(param_values))
+            .await?;
+
+        let mut results = Vec::new();
+        while let Some(row) = rows.next().await? {
+            results.push(ChunkWithDocument {
+                chunk_id: row.get(0)?,
+                document_id: row.get(1)?,
+                chunk_content: row.get(2)?,
+                document_title: row.get(3)?,
+                document_metadata: serde_json::from_str(&row.get::<String>(4)?).unwrap_or_default(),
+                score: row.get::<f64>(5)? as f32,
+                position: row.get(6)?,
+                chunker: parse_chunker(row.get(7)?),
+                highlight: row.get(8)?,
             });
         }
 
@@ -226,6 +548,419 @@ impl ChunkRepository {
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_get_by_document_position_range_and_positions() {
+        let conn = libsql::Builder::new_local(":memory:")
+            .build()
+            .await
+            .unwrap()
+            .connect()
+            .unwrap();
+        conn.execute(
+            r#"
+            CREATE TABLE chunks (
+                id TEXT PRIMARY KEY,
+                document_id TEXT NOT NULL,
+                content TEXT NOT NULL,
+                embedded_content TEXT,
+                position INTEGER NOT NULL,
+                token_count INTEGER,
+                chunker TEXT,
+                language TEXT,
+                created_at TEXT NOT NULL
+            )
+            "#,
+            (),
+        )
+        .await
+        .unwrap();
+
+        let mut chunks: Vec<Chunk> = (0..5)
+            .map(|i| {
+                Chunk::new(
+                    format!("chunk_{i}"),
+                    "doc_1".to_string(),
+                    format!("part {i}"),
+                    i,
+                )
+            })
+            .collect();
+        chunks.push(Chunk::new(
+            "other".to_string(),
+            "doc_2".to_string(),
+            "other doc".to_string(),
+            2,
+        ));
+        ChunkRepository::create_batch(&conn, &chunks).await.unwrap();
+
+        let range = ChunkRepository::get_by_document_and_position_range(&conn, "doc_1", 1, 3)
+            .await
+            .unwrap();
+        let positions: Vec<i32> = range.iter().map(|c| c.position).collect();
+        assert_eq!(positions, vec![1, 2, 3]);
+        assert!(range.iter().all(|c| c.document_id == "doc_1"));
+
+        let clipped = ChunkRepository::get_by_document_and_position_range(&conn, "doc_1", -2, 0)
+            .await
+            .unwrap();
+        assert_eq!(clipped.len(), 1);
+        assert_eq!(clipped[0].content, "part 0");
+
+        let found = ChunkRepository::get_by_document_and_positions(&conn, "doc_1", &[3, 1, 2])
+            .await
+            .unwrap();
+        let positions: Vec<i32> = found.iter().map(|c| c.position).collect();
+        assert_eq!(positions, vec![1, 2, 3]);
+        assert!(found.iter().all(|c| c.document_id == "doc_1"));
+
+        let sparse = ChunkRepository::get_by_document_and_positions(&conn, "doc_1", &[-1, 0, 4, 9])
+            .await
+            .unwrap();
+        let positions: Vec<i32> = sparse.iter().map(|c| c.position).collect();
+        assert_eq!(positions, vec![0, 4]);
+
+        assert!(
+            ChunkRepository::get_by_document_and_positions(&conn, "doc_1", &[])
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_content_marks_embedding_stale() {
+        let conn = libsql::Builder::new_local(":memory:")
+            .build()
+            .await
+            .unwrap()
+            .connect()
+            .unwrap();
+        conn.execute_batch(
+            r#"
+            CREATE TABLE documents (id TEXT PRIMARY KEY, status TEXT NOT NULL);
+            CREATE TABLE chunks (
+                id TEXT PRIMARY KEY,
+                document_id TEXT NOT NULL,
+                content TEXT NOT NULL,
+                embedded_content TEXT,
+                position INTEGER NOT NULL,
+                token_count INTEGER,
+                chunker TEXT,
+                language TEXT,
+                embedding F32_BLOB(3),
+                created_at TEXT NOT NULL
+            );
+            INSERT INTO documents (id, status) VALUES ('doc_done', 'done'), ('doc_busy', 'embedding');
+            "#,
+        )
+        .await
+        .unwrap();
+
+        let mut chunk = Chunk::new(
+            "chunk_1".to_string(),
+            "doc_done".to_string(),
+            "old text".to_string(),
+            0,
+        );
+        chunk.embedded_content = Some("context\n\nold text".to_string());
+        let pending = Chunk::new(
+            "chunk_2".to_string(),
+            "doc_busy".to_string(),
+            "in flight".to_string(),
+            0,
+        );
+        ChunkRepository::create_batch(&conn, &[chunk, pending])
+            .await
+            .unwrap();
+        ChunkRepository::update_embedding(&conn, "chunk_1", &[1.0, 0.0, 0.0])
+            .await
+            .unwrap();
+        assert!(ChunkRepository::get_missing_embeddings(&conn, 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let updated = ChunkRepository::update_content(&conn, "doc_done", "chunk_1", "new text")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.content, "new text");
+        assert!(
+            ChunkRepository::update_content(&conn, "doc_busy", "chunk_1", "wrong document")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let stale = ChunkRepository::get_missing_embeddings(&conn, 10)
+            .await
+            .unwrap();
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].id, "chunk_1");
+        assert_eq!(stale[0].content, "new text");
+        assert_eq!(stale[0].embedded_content, None);
+
+        ChunkRepository::update_embedding(&conn, "chunk_1", &[0.0, 1.0, 0.0])
+            .await
+            .unwrap();
+        assert!(ChunkRepository::get_missing_embeddings(&conn, 10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_keyword_filters_and_ranks_by_bm25() {
+        let conn = libsql::Builder::new_local(":memory:")
+            .build()
+            .await
+            .unwrap()
+            .connect()
+            .unwrap();
+        crate::db::schema::init_schema(&conn).await.unwrap();
+        conn.execute_batch(
+            r#"
+            INSERT INTO documents (id, container_tags, created_at, updated_at)
+                VALUES ('doc_1', '["team"]', 'now', 'now'), ('doc_2', '["other"]', 'now', 'now');
+            "#,
+        )
+        .await
+        .unwrap();
+
+        let contents = [
+            (
+                "doc_1",
+                "SKU X-1001 ships in blue. SKU X-1001 restocks monthly.",
+            ),
+            ("doc_1", "Order X-1001 was refunded"),
+            ("doc_1", "Nothing relevant here"),
+            ("doc_2", "SKU X-1001 is discontinued"),
+        ];
+        let chunks: Vec<Chunk> = contents
+            .iter()
+            .enumerate()
+            .map(|(i, (doc, content))| {
+                let mut chunk = Chunk::new(
+                    format!("chunk_{i}"),
+                    doc.to_string(),
+                    content.to_string(),
+                    i as i32,
+                );
+                chunk.chunker = Some(ChunkerType::Text);
+                chunk
+            })
+            .collect();
+        ChunkRepository::create_batch(&conn, &chunks).await.unwrap();
+
+        let team = ["team".to_string()];
+        let results =
+            ChunkRepository::search_keyword(&conn, "SKU X-1001", 10, Some(&team), None, None, None)
+                .await
+                .unwrap();
+        let ids: Vec<&str> = results.iter().map(|r| r.chunk_id.as_str()).collect();
+        assert_eq!(ids, vec!["chunk_0", "chunk_1"]);
+        assert!(results[0].score > results[1].score);
+        assert!(results[0].highlight.is_none());
+
+        let code_only = ChunkRepository::search_keyword(
+            &conn,
+            "X-1001",
+            10,
+            None,
+            Some(&[ChunkerType::Code]),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(code_only.is_empty());
+
+        let markers = HighlightMarkers {
+            open: "[[".to_string(),
+            close: "]]".to_string(),
+        };
+        let highlighted = ChunkRepository::search_keyword(
+            &conn,
+            "refunded",
+            10,
+            Some(&team),
+            None,
+            None,
+            Some(&markers),
+        )
+        .await
+        .unwrap();
+        assert_eq!(highlighted.len(), 1);
+        assert_eq!(
+            highlighted[0].highlight.as_deref(),
+            Some("Order X-1001 was [[refunded]]")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_similar_filters_by_chunker_type() {
+        let conn = libsql::Builder::new_local(":memory:")
+            .build()
+            .await
+            .unwrap()
+            .connect()
+            .unwrap();
+        conn.execute_batch(
+            r#"
+            CREATE TABLE documents (
+                id TEXT PRIMARY KEY,
+                title TEXT,
+                metadata TEXT DEFAULT '{}',
+                container_tags TEXT DEFAULT '[]'
+            );
+            CREATE TABLE chunks (
+                id TEXT PRIMARY KEY,
+                document_id TEXT NOT NULL,
+                content TEXT NOT NULL,
+                embedded_content TEXT,
+                position INTEGER NOT NULL,
+                token_count INTEGER,
+                chunker TEXT,
+                language TEXT,
+                embedding F32_BLOB(3),
+                created_at TEXT NOT NULL
+            );
+            INSERT INTO documents (id, container_tags) VALUES ('doc_1', '["team"]');
+            "#,
+        )
+        .await
+        .unwrap();
+
+        let chunkers = [Some(ChunkerType::Code), Some(ChunkerType::Markdown), None];
+        for (i, chunker) in chunkers.into_iter().enumerate() {
+            let mut chunk = Chunk::new(
+                format!("chunk_{i}"),
+                "doc_1".to_string(),
+                format!("part {i}"),
+                i as i32,
+            );
+            chunk.language = (chunker == Some(ChunkerType::Code)).then(|| "rust".to_string());
+            chunk.chunker = chunker;
+            ChunkRepository::create(&conn, &chunk).await.unwrap();
+            ChunkRepository::update_embedding(&conn, &chunk.id, &[1.0, 0.0, 0.0])
+                .await
+                .unwrap();
+        }
+
+        let all =
+            ChunkRepository::search_similar(&conn, &[1.0, 0.0, 0.0], 10, 0.0, None, None, None)
+                .await
+                .unwrap();
+        assert_eq!(all.len(), 3);
+
+        let tags = vec!["team".to_string()];
+        let code = ChunkRepository::search_similar(
+            &conn,
+            &[1.0, 0.0, 0.0],
+            10,
+            0.0,
+            Some(&tags),
+            Some(&[ChunkerType::Code]),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(code.len(), 1);
+        assert_eq!(code[0].chunk_id, "chunk_0");
+        assert_eq!(code[0].chunker, Some(ChunkerType::Code));
+
+        let prose = ChunkRepository::search_similar(
+            &conn,
+            &[1.0, 0.0, 0.0],
+            10,
+            0.0,
+            None,
+            Some(&[ChunkerType::Markdown, ChunkerType::Text]),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(prose.len(), 1);
+        assert_eq!(prose[0].chunk_id, "chunk_1");
+
+        let python = ChunkRepository::search_similar(
+            &conn,
+            &[1.0, 0.0, 0.0],
+            10,
+            0.0,
+            None,
+            None,
+            Some(&["python".to_string()]),
+        )
+        .await
+        .unwrap();
+        assert!(python.is_empty());
+
+        let rust = ChunkRepository::search_similar(
+            &conn,
+            &[1.0, 0.0, 0.0],
+            10,
+            0.0,
+            None,
+            None,
+            Some(&["rust".to_string()]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(rust.len(), 1);
+        assert_eq!(rust[0].chunk_id, "chunk_0");
+    }
+
+    #[tokio::test]
+    async fn test_get_embeddings_skips_chunks_without_embedding() {
+        let conn = libsql::Builder::new_local(":memory:")
+            .build()
+            .await
+            .unwrap()
+            .connect()
+            .unwrap();
+        conn.execute_batch(
+            r#"
+            CREATE TABLE chunks (
+                id TEXT PRIMARY KEY,
+                document_id TEXT NOT NULL,
+                content TEXT NOT NULL,
+                embedded_content TEXT,
+                position INTEGER NOT NULL,
+                token_count INTEGER,
+                chunker TEXT,
+                language TEXT,
+                embedding F32_BLOB(3),
+                created_at TEXT NOT NULL
+            );
+            "#,
+        )
+        .await
+        .unwrap();
+
+        for i in 0..2 {
+            let chunk = Chunk::new(
+                format!("chunk_{i}"),
+                "doc_1".to_string(),
+                format!("part {i}"),
+                i,
+            );
+            ChunkRepository::create(&conn, &chunk).await.unwrap();
+        }
+        ChunkRepository::update_embedding(&conn, "chunk_0", &[0.5, 0.0, 1.0])
+            .await
+            .unwrap();
+
+        let ids = vec!["chunk_0".to_string(), "chunk_1".to_string()];
+        let embeddings = ChunkRepository::get_embeddings(&conn, &ids).await.unwrap();
+        assert_eq!(embeddings.len(), 1);
+        assert_eq!(embeddings["chunk_0"], vec![0.5, 0.0, 1.0]);
+        assert!(ChunkRepository::get_embeddings(&conn, &[])
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
     #[test]
     fn test_build_tag_filter_single_tag() {
         let tags = vec!["mytag".to_string()];
@@ -0,0 +1,464 @@
+use chrono::{DateTime, Utc};
+use libsql::{params, Connection};
+use nanoid::nanoid;
+
+use super::MemoryRepository;
+use crate::error::Result;
+use crate::models::{
+    ContradictionChange, ContradictionResolution, ContradictionStatus, MemoryContradiction,
+    MemoryRelationType,
+};
+
+pub struct ContradictionRepository;
+
+const SELECT_COLUMNS: &str = r#"
+    SELECT id, memory_id, conflicting_memory_id, container_tag, reasoning,
+           status, resolution, created_at, resolved_at
+    FROM memory_contradictions
+"#;
+
+impl ContradictionRepository {
+    /// Flag a contradiction between two memories.
+    ///
+    /// If an open flag already exists for the pair (in either direction) it is
+    /// returned unchanged instead of creating a duplicate.
+    pub async fn create(
+        conn: &Connection,
+        memory_id: &str,
+        conflicting_memory_id: &str,
+        container_tag: Option<&str>,
+        reasoning: Option<&str>,
+    ) -> Result<MemoryContradiction> {
+        let mut rows = conn
+            .query(
+                &format!(
+                    r#"{SELECT_COLUMNS}
+                    WHERE status = 'open'
+                      AND ((memory_id = ?1 AND conflicting_memory_id = ?2)
+                        OR (memory_id = ?2 AND conflicting_memory_id = ?1))
+                    LIMIT 1
+                    "#
+                ),
+                params![memory_id, conflicting_memory_id],
+            )
+            .await?;
+
+        if let Some(row) = rows.next().await? {
+            return Self::row_to_contradiction(&row);
+        }
+
+        let id = nanoid!();
+        let created_at = Utc::now();
+
+        conn.execute(
+            r#"
+            INSERT INTO memory_contradictions (
+                id, memory_id, conflicting_memory_id, container_tag, reasoning,
+                status, created_at
+            ) VALUES (
+                ?1, ?2, ?3, ?4, ?5, 'open', ?6
+            )
+            "#,
+            params![
+                id.clone(),
+                memory_id,
+                conflicting_memory_id,
+                container_tag,
+                reasoning,
+                created_at.to_rfc3339(),
+            ],
+        )
+        .await?;
+
+        Ok(MemoryContradiction {
+            id,
+            memory_id: memory_id.to_string(),
+            conflicting_memory_id: conflicting_memory_id.to_string(),
+            container_tag: container_tag.map(|value| value.to_string()),
+            reasoning: reasoning.map(|value| value.to_string()),
+            status: ContradictionStatus::Open,
+            resolution: None,
+            created_at,
+            resolved_at: None,
+        })
+    }
+
+    pub async fn get_by_id(conn: &Connection, id: &str) -> Result<Option<MemoryContradiction>> {
+        let mut rows = conn
+            .query(&format!("{SELECT_COLUMNS} WHERE id = ?1"), params![id])
+            .await?;
+
+        match rows.next().await? {
+            Some(row) => Ok(Some(Self::row_to_contradiction(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// List a page of flagged contradictions, newest first, with the total
+    /// number matching the filters.
+    pub async fn list(
+        conn: &Connection,
+        container_tag: Option<&str>,
+        include_resolved: bool,
+        limit: u32,
+        offset: u32,
+    ) -> Result<(Vec<MemoryContradiction>, u64)> {
+        let mut rows = conn
+            .query(
+                r#"
+                SELECT COUNT(*) FROM memory_contradictions
+                WHERE (?1 IS NULL OR container_tag = ?1)
+                  AND (?2 = 1 OR status = 'open')
+                "#,
+                params![container_tag, include_resolved as i32],
+            )
+            .await?;
+        let total: i64 = match rows.next().await? {
+            Some(row) => row.get(0)?,
+            None => 0,
+        };
+
+        let mut rows = conn
+            .query(
+                &format!(
+                    r#"{SELECT_COLUMNS}
+                    WHERE (?1 IS NULL OR container_tag = ?1)
+                      AND (?2 = 1 OR status = 'open')
+                    ORDER BY created_at DESC, id
+                    LIMIT ?3 OFFSET ?4
+                    "#
+                ),
+                params![container_tag, include_resolved as i32, limit, offset],
+            )
+            .await?;
+
+        let mut results = Vec::new();
+        while let Some(row) = rows.next().await? {
+            results.push(Self::row_to_contradiction(&row)?);
+        }
+
+        Ok((results, total as u64))
+    }
+
+    /// Mark an open contradiction as resolved and apply `change` to its
+    /// memories in the same transaction. Returns `false`, changing nothing,
+    /// if no open contradiction with the given id exists.
+    pub async fn resolve(
+        conn: &Connection,
+        id: &str,
+        resolution: ContradictionResolution,
+        change: &ContradictionChange,
+    ) -> Result<bool> {
+        let tx = conn.transaction().await?;
+        let affected = tx
+            .execute(
+                r#"
+                UPDATE memory_contradictions
+                SET status = 'resolved', resolution = ?2, resolved_at = ?3
+                WHERE id = ?1 AND status = 'open'
+                "#,
+                params![id, resolution.to_string(), Utc::now().to_rfc3339()],
+            )
+            .await?;
+        if affected == 0 {
+            tx.rollback().await?;
+            return Ok(false);
+        }
+
+        match change {
+            ContradictionChange::None => {}
+            ContradictionChange::Supersede {
+                kept_id,
+                dropped_id,
+                root_id,
+                version,
+            } => {
+                MemoryRepository::update_to_not_latest(&tx, dropped_id).await?;
+                MemoryRepository::update_version_chain(&tx, kept_id, dropped_id, root_id, *version)
+                    .await?;
+                MemoryRepository::add_relation(
+                    &tx,
+                    kept_id,
+                    dropped_id,
+                    MemoryRelationType::Updates,
+                )
+                .await?;
+            }
+            ContradictionChange::Forget { dropped_id, reason } => {
+                MemoryRepository::forget(&tx, dropped_id, Some(reason)).await?;
+            }
+        }
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    fn row_to_contradiction(row: &libsql::Row) -> Result<MemoryContradiction> {
+        let status: String = row.get(5)?;
+        let resolution: Option<String> = row.get(6)?;
+        let resolved_at: Option<String> = row.get(8)?;
+
+        Ok(MemoryContradiction {
+            id: row.get(0)?,
+            memory_id: row.get(1)?,
+            conflicting_memory_id: row.get(2)?,
+            container_tag: row.get(3)?,
+            reasoning: row.get(4)?,
+            status: status.parse().unwrap_or_default(),
+            resolution: resolution.and_then(|value| value.parse().ok()),
+            created_at: DateTime::parse_from_rfc3339(&row.get::<String>(7)?)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+            resolved_at: resolved_at.and_then(|value| {
+                DateTime::parse_from_rfc3339(&value)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .ok()
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_test_db() -> Connection {
+        let conn = libsql::Builder::new_local(":memory:")
+            .build()
+            .await
+            .unwrap()
+            .connect()
+            .unwrap();
+
+        conn.execute(
+            r#"
+            CREATE TABLE memory_contradictions (
+                id TEXT PRIMARY KEY,
+                memory_id TEXT NOT NULL,
+                conflicting_memory_id TEXT NOT NULL,
+                container_tag TEXT,
+                reasoning TEXT,
+                status TEXT NOT NULL DEFAULT 'open',
+                resolution TEXT,
+                created_at TEXT NOT NULL,
+                resolved_at TEXT
+            )
+            "#,
+            (),
+        )
+        .await
+        .unwrap();
+
+        conn
+    }
+
+    #[tokio::test]
+    async fn test_create_and_get_by_id() {
+        let conn = setup_test_db().await;
+
+        let created = ContradictionRepository::create(
+            &conn,
+            "mem_new",
+            "mem_old",
+            Some("user_1"),
+            Some("Different cities"),
+        )
+        .await
+        .unwrap();
+
+        let fetched = ContradictionRepository::get_by_id(&conn, &created.id)
+            .await
+            .unwrap()
+            .expect("contradiction should exist");
+
+        assert_eq!(fetched.memory_id, "mem_new");
+        assert_eq!(fetched.conflicting_memory_id, "mem_old");
+        assert_eq!(fetched.container_tag.as_deref(), Some("user_1"));
+        assert_eq!(fetched.reasoning.as_deref(), Some("Different cities"));
+        assert_eq!(fetched.status, ContradictionStatus::Open);
+        assert!(fetched.resolution.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_create_dedupes_open_pair_in_either_direction() {
+        let conn = setup_test_db().await;
+
+        let first = ContradictionRepository::create(&conn, "a", "b", Some("user_1"), None)
+            .await
+            .unwrap();
+        let second = ContradictionRepository::create(&conn, "b", "a", Some("user_1"), None)
+            .await
+            .unwrap();
+
+        assert_eq!(first.id, second.id);
+        let (all, total) = ContradictionRepository::list(&conn, None, true, 100, 0)
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(total, 1);
+    }
+
+    #[tokio::test]
+    async fn test_list_filters_by_container_and_status() {
+        let conn = setup_test_db().await;
+
+        let resolved = ContradictionRepository::create(&conn, "a", "b", Some("user_1"), None)
+            .await
+            .unwrap();
+        ContradictionRepository::create(&conn, "c", "d", Some("user_1"), None)
+            .await
+            .unwrap();
+        ContradictionRepository::create(&conn, "e", "f", Some("user_2"), None)
+            .await
+            .unwrap();
+
+        assert!(ContradictionRepository::resolve(
+            &conn,
+            &resolved.id,
+            ContradictionResolution::KeepA,
+            &ContradictionChange::None
+        )
+        .await
+        .unwrap());
+
+        let (open, _) = ContradictionRepository::list(&conn, Some("user_1"), false, 100, 0)
+            .await
+            .unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].memory_id, "c");
+
+        let (with_resolved, _) = ContradictionRepository::list(&conn, Some("user_1"), true, 100, 0)
+            .await
+            .unwrap();
+        assert_eq!(with_resolved.len(), 2);
+
+        let (everything, _) = ContradictionRepository::list(&conn, None, false, 100, 0)
+            .await
+            .unwrap();
+        assert_eq!(everything.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_list_pages_through_matches() {
+        let conn = setup_test_db().await;
+
+        for (a, b) in [("a", "b"), ("c", "d"), ("e", "f")] {
+            ContradictionRepository::create(&conn, a, b, Some("user_1"), None)
+                .await
+                .unwrap();
+        }
+
+        let (first, total) = ContradictionRepository::list(&conn, Some("user_1"), false, 2, 0)
+            .await
+            .unwrap();
+        let (second, _) = ContradictionRepository::list(&conn, Some("user_1"), false, 2, 2)
+            .await
+            .unwrap();
+
+        assert_eq!(total, 3);
+        assert_eq!(first.len(), 2);
+        assert_eq!(second.len(), 1);
+        assert!(first.iter().all(|c| c.id != second[0].id));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_only_applies_once() {
+        let conn = setup_test_db().await;
+
+        let created = ContradictionRepository::create(&conn, "a", "b", None, None)
+            .await
+            .unwrap();
+
+        assert!(ContradictionRepository::resolve(
+            &conn,
+            &created.id,
+            ContradictionResolution::KeepBoth,
+            &ContradictionChange::None
+        )
+        .await
+        .unwrap());
+        assert!(!ContradictionRepository::resolve(
+            &conn,
+            &created.id,
+            ContradictionResolution::KeepA,
+            &ContradictionChange::None
+        )
+        .await
+        .unwrap());
+
+        let fetched = ContradictionRepository::get_by_id(&conn, &created.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.status, ContradictionStatus::Resolved);
+        assert_eq!(fetched.resolution, Some(ContradictionResolution::KeepBoth));
+        assert!(fetched.resolved_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_applies_change_only_for_the_first_resolution() {
+        let conn = libsql::Builder::new_local(":memory:")
+            .build()
+            .await
+            .unwrap()
+            .connect()
+            .unwrap();
+        crate::db::schema::init_schema(&conn).await.unwrap();
+        for id in ["old", "new"] {
+            let memory = crate::models::Memory::new(
+                id.to_string(),
+                format!("{id} fact"),
+                "space1".to_string(),
+            );
+            MemoryRepository::create(&conn, &memory).await.unwrap();
+        }
+        let created = ContradictionRepository::create(&conn, "new", "old", None, None)
+            .await
+            .unwrap();
+
+        let supersede = ContradictionChange::Supersede {
+            kept_id: "new".to_string(),
+            dropped_id: "old".to_string(),
+            root_id: "old".to_string(),
+            version: 2,
+        };
+        assert!(ContradictionRepository::resolve(
+            &conn,
+            &created.id,
+            ContradictionResolution::KeepA,
+            &supersede
+        )
+        .await
+        .unwrap());
+        let forget = ContradictionChange::Forget {
+            dropped_id: "new".to_string(),
+            reason: "Contradicted by memory old".to_string(),
+        };
+        assert!(!ContradictionRepository::resolve(
+            &conn,
+            &created.id,
+            ContradictionResolution::KeepB,
+            &forget
+        )
+        .await
+        .unwrap());
+
+        let old = MemoryRepository::get_by_id(&conn, "old")
+            .await
+            .unwrap()
+            .unwrap();
+        let new = MemoryRepository::get_by_id(&conn, "new")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!old.is_latest);
+        assert!(old.memory_relations.is_empty());
+        assert!(!new.is_forgotten);
+        assert_eq!(new.version, 2);
+        assert_eq!(new.parent_memory_id.as_deref(), Some("old"));
+        assert_eq!(
+            new.memory_relations.get("old"),
+            Some(&MemoryRelationType::Updates)
+        );
+    }
+}
@@ -1,12 +1,21 @@
+use std::time::Duration;
+
 use chrono::{DateTime, Utc};
 use libsql::{params, Connection};
 
-use crate::error::Result;
+use crate::error::{MomoError, Result};
 use crate::models::{
-    Document, DocumentSummary, DocumentType, ListDocumentsRequest, Pagination, ProcessingDocument,
-    ProcessingStatus,
+    DeadLetterDocument, Document, DocumentDeleteFilter, DocumentSummary, DocumentType,
+    ListDocumentsRequest, Pagination, ProcessingDocument, ProcessingStats, ProcessingStatus,
 };
 
+use super::{id_placeholders, ID_BATCH_SIZE};
+
+/// Documents removed per call to [`DocumentRepository::delete_matching_batch`].
+const DELETE_BATCH_SIZE: usize = 200;
+/// Processing runs kept for duration statistics; older ones are pruned.
+const PROCESSING_RUNS_KEPT: i64 = 1000;
+
 pub struct DocumentRepository;
 
 impl DocumentRepository {
@@ -59,30 +68,21 @@ impl DocumentRepository {
         }
     }
 
+    /// Documents with any of `ids`, looked up [`ID_BATCH_SIZE`] ids at a time.
     pub async fn get_by_ids(conn: &Connection, ids: &[String]) -> Result<Vec<Document>> {
-        if ids.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        let mut placeholders = String::new();
-        for i in 0..ids.len() {
-            if i > 0 {
-                placeholders.push_str(", ");
-            }
-            placeholders.push('?');
-            placeholders.push_str(&(i + 1).to_string());
-        }
-
-        let sql = format!("SELECT * FROM documents WHERE id IN ({placeholders})");
-        let params: Vec<libsql::Value> = ids
-            .iter()
-            .map(|id| libsql::Value::from(id.clone()))
-            .collect();
-
-        let mut rows = conn.query(&sql, libsql::params_from_iter(params)).await?;
         let mut results = Vec::new();
-        while let Some(row) = rows.next().await? {
-            results.push(Self::row_to_document(&row)?);
+        for batch in ids.chunks(ID_BATCH_SIZE) {
+            let placeholders = id_placeholders(batch.len());
+            let sql = format!("SELECT * FROM documents WHERE id IN ({placeholders})");
+            let params: Vec<libsql::Value> = batch
+                .iter()
+                .map(|id| libsql::Value::from(id.clone()))
+                .collect();
+
+            let mut rows = conn.query(&sql, libsql::params_from_iter(params)).await?;
+            while let Some(row) = rows.next().await? {
+                results.push(Self::row_to_document(&row)?);
+            }
         }
         Ok(results)
     }
@@ -162,6 +162,116 @@ impl DocumentRepository {
         Ok(rows_affected > 0)
     }
 
+    /// Delete up to [`DELETE_BATCH_SIZE`] documents matching `filter`,
+    /// together with their chunks, in one transaction so a failure never
+    /// leaves chunks behind for a deleted document.
+    ///
+    /// Returns the ids of the deleted documents; call again until none are
+    /// returned to delete every match. An empty filter is rejected.
+    pub async fn delete_matching_batch(
+        conn: &Connection,
+        filter: &DocumentDeleteFilter,
+    ) -> Result<Vec<String>> {
+        if filter.is_empty() {
+            return Err(MomoError::Validation(
+                "Bulk delete requires at least one filter".to_string(),
+            ));
+        }
+
+        let mut where_clauses = Vec::new();
+        let mut filter_params: Vec<libsql::Value> = Vec::new();
+        if let Some(ref tag) = filter.container_tag {
+            filter_params.push(libsql::Value::from(tag.clone()));
+            where_clauses.push(format!(
+                "EXISTS (SELECT 1 FROM json_each(documents.container_tags) WHERE value = ?{})",
+                filter_params.len()
+            ));
+        }
+        for (key, value) in &filter.metadata {
+            if key.is_empty() || key.contains('"') {
+                return Err(MomoError::Validation(format!(
+                    "Invalid metadata filter key: {key:?}"
+                )));
+            }
+            filter_params.push(libsql::Value::from(format!("$.\"{key}\"")));
+            let path_idx = filter_params.len();
+            filter_params.push(metadata_param(key, value)?);
+            where_clauses.push(format!(
+                "json_extract(metadata, ?{path_idx}) = ?{}",
+                filter_params.len()
+            ));
+        }
+
+        let select = format!(
+            "SELECT id FROM documents WHERE {} LIMIT {DELETE_BATCH_SIZE}",
+            where_clauses.join(" AND ")
+        );
+
+        let mut rows = conn
+            .query(&select, libsql::params_from_iter(filter_params))
+            .await?;
+        let mut ids: Vec<String> = Vec::new();
+        while let Some(row) = rows.next().await? {
+            ids.push(row.get(0)?);
+        }
+        if ids.is_empty() {
+            return Ok(ids);
+        }
+
+        let placeholders = (1..=ids.len())
+            .map(|i| format!("?{i}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let tx = conn.transaction().await?;
+        tx.execute(
+            &format!("DELETE FROM chunks WHERE document_id IN ({placeholders})"),
+            libsql::params_from_iter(ids.clone()),
+        )
+        .await?;
+        tx.execute(
+            &format!("DELETE FROM documents WHERE id IN ({placeholders})"),
+            libsql::params_from_iter(ids.clone()),
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(ids)
+    }
+
+    /// One page of the documents tagged with `container_tag`, oldest first.
+    /// `after` is the `(created_at, id)` of the last document on the previous
+    /// page.
+    pub async fn list_by_container_after(
+        conn: &Connection,
+        container_tag: &str,
+        after: Option<(DateTime<Utc>, &str)>,
+        limit: usize,
+    ) -> Result<Vec<Document>> {
+        let mut rows = conn
+            .query(
+                r#"
+                SELECT * FROM documents
+                WHERE EXISTS (SELECT 1 FROM json_each(documents.container_tags) WHERE value = ?1)
+                  AND (?2 IS NULL OR created_at > ?2 OR (created_at = ?2 AND id > ?3))
+                ORDER BY created_at, id
+                LIMIT ?4
+                "#,
+                params![
+                    container_tag,
+                    after.map(|(created_at, _)| created_at.to_rfc3339()),
+                    after.map(|(_, id)| id.to_string()),
+                    limit as i64
+                ],
+            )
+            .await?;
+
+        let mut results = Vec::new();
+        while let Some(row) = rows.next().await? {
+            results.push(Self::row_to_document(&row)?);
+        }
+        Ok(results)
+    }
+
     pub async fn list(
         conn: &Connection,
         req: &ListDocumentsRequest,
@@ -184,22 +294,34 @@ impl DocumentRepository {
             }
         );
 
-        let mut where_clauses = Vec::new();
+        let mut tag_clauses = Vec::new();
         let mut tag_params: Vec<libsql::Value> = Vec::new();
 
         if let Some(ref tags) = req.container_tags {
             if !tags.is_empty() {
                 for (i, tag) in tags.iter().enumerate() {
-                    where_clauses.push(format!("container_tags LIKE ?{}", i + 1));
+                    tag_clauses.push(format!("container_tags LIKE ?{}", i + 1));
                     tag_params.push(libsql::Value::from(format!("%\"{tag}%")));
                 }
             }
         }
 
+        let mut where_clauses = Vec::new();
+        if !tag_clauses.is_empty() {
+            where_clauses.push(format!("({})", tag_clauses.join(" OR ")));
+        }
+        if let Some(ref category) = req.category {
+            tag_params.push(libsql::Value::from(category.clone()));
+            where_clauses.push(format!(
+                "json_extract(metadata, '$.category') = ?{}",
+                tag_params.len()
+            ));
+        }
+
         let where_clause = if where_clauses.is_empty() {
             String::new()
         } else {
-            format!("WHERE {}", where_clauses.join(" OR "))
+            format!("WHERE {}", where_clauses.join(" AND "))
         };
 
         let count_query = format!("SELECT COUNT(*) FROM documents {where_clause}");
@@ -212,7 +334,7 @@ impl DocumentRepository {
             0
         };
 
-        // LIMIT and OFFSET params come after the tag params
+        // LIMIT and OFFSET params come after the filter params
         let limit_idx = tag_params.len() + 1;
         let offset_idx = tag_params.len() + 2;
         let query = format!(
@@ -244,7 +366,7 @@ impl DocumentRepository {
                 r#"
                 SELECT id, status, title, created_at 
                 FROM documents 
-                WHERE status NOT IN ('done', 'failed')
+                WHERE status NOT IN ('done', 'failed', 'dead_letter')
                 ORDER BY created_at ASC
                 "#,
                 (),
@@ -269,6 +391,28 @@ impl DocumentRepository {
         Ok(docs)
     }
 
+    /// Move documents left in an in-progress status (`extracting` through
+    /// `indexing`) and not updated for `older_than` back to `queued`, e.g.
+    /// after a worker was killed mid-document. Returns how many were requeued.
+    pub async fn requeue_stale(conn: &Connection, older_than: Duration) -> Result<u64> {
+        let older_than = chrono::Duration::from_std(older_than).unwrap_or(chrono::Duration::MAX);
+        let Some(cutoff) = Utc::now().checked_sub_signed(older_than) else {
+            return Ok(0);
+        };
+        let requeued = conn
+            .execute(
+                r#"
+                UPDATE documents
+                SET status = 'queued', updated_at = ?2
+                WHERE status IN ('extracting', 'chunking', 'embedding', 'indexing')
+                  AND updated_at < ?1
+                "#,
+                params![cutoff.to_rfc3339(), Utc::now().to_rfc3339()],
+            )
+            .await?;
+        Ok(requeued)
+    }
+
     pub async fn update_status(
         conn: &Connection,
         id: &str,
@@ -288,6 +432,194 @@ impl DocumentRepository {
         Ok(())
     }
 
+    /// Count a failed processing attempt for `id` and keep `error` as its last
+    /// error. The document moves to `dead_letter` once it has failed
+    /// `max_failures` times (`0` never dead-letters). Returns the resulting
+    /// status, or `None` if the document doesn't exist.
+    pub async fn record_failure(
+        conn: &Connection,
+        id: &str,
+        error: &str,
+        max_failures: u32,
+    ) -> Result<Option<ProcessingStatus>> {
+        conn.execute(
+            r#"
+            UPDATE documents
+            SET retry_count = retry_count + 1,
+                error_message = ?2,
+                status = CASE
+                    WHEN ?3 > 0 AND retry_count + 1 >= ?3 THEN 'dead_letter'
+                    ELSE status
+                END,
+                updated_at = ?4
+            WHERE id = ?1
+            "#,
+            params![id, error, max_failures, Utc::now().to_rfc3339()],
+        )
+        .await?;
+
+        let mut rows = conn
+            .query("SELECT status FROM documents WHERE id = ?1", [id])
+            .await?;
+        match rows.next().await? {
+            Some(row) => Ok(Some(
+                row.get::<String>(0)?
+                    .parse()
+                    .unwrap_or(ProcessingStatus::Unknown),
+            )),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn list_dead_letter(conn: &Connection) -> Result<Vec<DeadLetterDocument>> {
+        let mut rows = conn
+            .query(
+                r#"
+                SELECT id, title, retry_count, error_message, updated_at
+                FROM documents
+                WHERE status = 'dead_letter'
+                ORDER BY updated_at DESC
+                "#,
+                (),
+            )
+            .await?;
+
+        let mut docs = Vec::new();
+        while let Some(row) = rows.next().await? {
+            docs.push(DeadLetterDocument {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                retry_count: row.get::<i64>(2)?.max(0) as u32,
+                error_message: row.get(3)?,
+                updated_at: DateTime::parse_from_rfc3339(&row.get::<String>(4)?)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+            });
+        }
+
+        Ok(docs)
+    }
+
+    /// Queue a dead-lettered document again with a fresh retry budget.
+    /// Returns `false` if `id` is not dead-lettered.
+    pub async fn requeue_dead_letter(conn: &Connection, id: &str) -> Result<bool> {
+        let requeued = conn
+            .execute(
+                r#"
+                UPDATE documents
+                SET status = 'queued', retry_count = 0, error_message = NULL, updated_at = ?2
+                WHERE id = ?1 AND status = 'dead_letter'
+                "#,
+                params![id, Utc::now().to_rfc3339()],
+            )
+            .await?;
+        Ok(requeued > 0)
+    }
+
+    /// Queue a document whose processing has finished (`done` or `failed`)
+    /// to run again, setting `metadata.extract_memories` when given. Returns
+    /// `false` if `id` is queued, in flight or dead-lettered.
+    pub async fn queue_for_reprocess(
+        conn: &Connection,
+        id: &str,
+        extract_memories: Option<bool>,
+    ) -> Result<bool> {
+        let queued = conn
+            .execute(
+                r#"
+                UPDATE documents
+                SET status = 'queued', error_message = NULL, updated_at = ?2,
+                    metadata = CASE WHEN ?3 IS NULL THEN metadata
+                        ELSE json_set(metadata, '$.extract_memories', json(?3)) END
+                WHERE id = ?1 AND status IN ('done', 'failed')
+                "#,
+                params![
+                    id,
+                    Utc::now().to_rfc3339(),
+                    extract_memories.map(|value| value.to_string())
+                ],
+            )
+            .await?;
+        Ok(queued > 0)
+    }
+
+    /// Record how long processing `id` took, pruning runs beyond the most
+    /// recent [`PROCESSING_RUNS_KEPT`].
+    pub async fn record_processing_run(
+        conn: &Connection,
+        id: &str,
+        status: ProcessingStatus,
+        duration_ms: u64,
+    ) -> Result<()> {
+        conn.execute(
+            "INSERT INTO processing_runs (document_id, status, duration_ms, finished_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                id,
+                status.to_string(),
+                duration_ms as i64,
+                Utc::now().to_rfc3339()
+            ],
+        )
+        .await?;
+        conn.execute(
+            "DELETE FROM processing_runs
+             WHERE id <= (SELECT MAX(id) FROM processing_runs) - ?1",
+            params![PROCESSING_RUNS_KEPT],
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn processing_stats(conn: &Connection) -> Result<ProcessingStats> {
+        let mut stats = ProcessingStats::default();
+
+        let mut rows = conn
+            .query(
+                "SELECT status, COUNT(*) FROM documents GROUP BY status ORDER BY status",
+                (),
+            )
+            .await?;
+        while let Some(row) = rows.next().await? {
+            let status = row
+                .get::<String>(0)?
+                .parse()
+                .unwrap_or(ProcessingStatus::Unknown);
+            let count = row.get::<i64>(1)? as u64;
+            match stats.counts.iter_mut().find(|(s, _)| *s == status) {
+                Some((_, total)) => *total += count,
+                None => stats.counts.push((status, count)),
+            }
+        }
+
+        let mut rows = conn
+            .query(
+                "SELECT MIN(created_at) FROM documents WHERE status NOT IN ('done', 'failed', 'dead_letter')",
+                (),
+            )
+            .await?;
+        if let Some(row) = rows.next().await? {
+            stats.oldest_pending_at = row
+                .get::<Option<String>>(0)?
+                .and_then(|created_at| DateTime::parse_from_rfc3339(&created_at).ok())
+                .map(|dt| dt.with_timezone(&Utc));
+        }
+
+        let mut rows = conn
+            .query(
+                "SELECT duration_ms FROM processing_runs ORDER BY id DESC LIMIT ?1",
+                params![PROCESSING_RUNS_KEPT],
+            )
+            .await?;
+        while let Some(row) = rows.next().await? {
+            stats
+                .recent_durations_ms
+                .push(row.get::<i64>(0)?.max(0) as u64);
+        }
+
+        Ok(stats)
+    }
+
     fn row_to_document(row: &libsql::Row) -> Result<Document> {
         Ok(Document {
             id: row.get(0)?,
@@ -322,6 +654,21 @@ impl DocumentRepository {
     }
 }
 
+/// SQL value that `json_extract` yields for a scalar metadata value.
+fn metadata_param(key: &str, value: &serde_json::Value) -> Result<libsql::Value> {
+    match value {
+        serde_json::Value::String(s) => Ok(libsql::Value::from(s.clone())),
+        serde_json::Value::Bool(b) => Ok(libsql::Value::from(*b as i64)),
+        serde_json::Value::Number(n) => Ok(match n.as_i64() {
+            Some(i) => libsql::Value::from(i),
+            None => libsql::Value::from(n.as_f64().unwrap_or_default()),
+        }),
+        _ => Err(MomoError::Validation(format!(
+            "Metadata filter '{key}' must be a string, number or boolean"
+        ))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -354,7 +701,8 @@ mod tests {
                 word_count INTEGER,
                 error_message TEXT,
                 created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL
+                updated_at TEXT NOT NULL,
+                retry_count INTEGER NOT NULL DEFAULT 0
             )
             "#,
             (),
@@ -371,6 +719,288 @@ mod tests {
         doc
     }
 
+    async fn chunk_count(conn: &Connection, document_id: &str) -> i64 {
+        let mut rows = conn
+            .query(
+                "SELECT COUNT(*) FROM chunks WHERE document_id = ?1",
+                [document_id],
+            )
+            .await
+            .unwrap();
+        rows.next().await.unwrap().unwrap().get(0).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_requeue_stale_only_resets_old_in_progress_documents() {
+        let conn = setup_test_db().await;
+        let cases = [
+            ("stuck", ProcessingStatus::Embedding, 2),
+            ("recent", ProcessingStatus::Chunking, 0),
+            ("finished", ProcessingStatus::Done, 2),
+            ("waiting", ProcessingStatus::Queued, 2),
+        ];
+        for (id, status, hours_ago) in cases {
+            let mut doc = make_doc(id, vec![]);
+            doc.status = status;
+            doc.updated_at = Utc::now() - chrono::Duration::hours(hours_ago);
+            DocumentRepository::create(&conn, &doc).await.unwrap();
+        }
+
+        let requeued = DocumentRepository::requeue_stale(&conn, Duration::from_secs(3600))
+            .await
+            .unwrap();
+        assert_eq!(requeued, 1);
+
+        let expected = [
+            ("stuck", ProcessingStatus::Queued),
+            ("recent", ProcessingStatus::Chunking),
+            ("finished", ProcessingStatus::Done),
+        ];
+        for (id, status) in expected {
+            let doc = DocumentRepository::get_by_id(&conn, id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(doc.status, status, "{id}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_failure_dead_letters_after_cap_and_requeue_resets() {
+        let conn = setup_test_db().await;
+        let mut doc = make_doc("poison", vec![]);
+        doc.status = ProcessingStatus::Failed;
+        DocumentRepository::create(&conn, &doc).await.unwrap();
+
+        let status = DocumentRepository::record_failure(&conn, "poison", "bad input", 2)
+            .await
+            .unwrap();
+        assert_eq!(status, Some(ProcessingStatus::Failed));
+        assert!(!DocumentRepository::requeue_dead_letter(&conn, "poison")
+            .await
+            .unwrap());
+
+        let status = DocumentRepository::record_failure(&conn, "poison", "still bad", 2)
+            .await
+            .unwrap();
+        assert_eq!(status, Some(ProcessingStatus::DeadLetter));
+
+        let dead = DocumentRepository::list_dead_letter(&conn).await.unwrap();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].retry_count, 2);
+        assert_eq!(dead[0].error_message.as_deref(), Some("still bad"));
+        assert!(DocumentRepository::get_processing(&conn)
+            .await
+            .unwrap()
+            .is_empty());
+
+        assert!(DocumentRepository::requeue_dead_letter(&conn, "poison")
+            .await
+            .unwrap());
+        let doc = DocumentRepository::get_by_id(&conn, "poison")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(doc.status, ProcessingStatus::Queued);
+        assert!(doc.error_message.is_none());
+        assert!(DocumentRepository::list_dead_letter(&conn)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let missing = DocumentRepository::record_failure(&conn, "missing", "gone", 2)
+            .await
+            .unwrap();
+        assert_eq!(missing, None);
+    }
+
+    #[tokio::test]
+    async fn test_queue_for_reprocess_only_moves_finished_documents_once() {
+        let conn = setup_test_db().await;
+        let mut doc = make_doc("finished", vec![]);
+        doc.status = ProcessingStatus::Done;
+        doc.metadata
+            .insert("extract_memories".to_string(), serde_json::json!(false));
+        DocumentRepository::create(&conn, &doc).await.unwrap();
+        DocumentRepository::create(&conn, &make_doc("pending", vec![]))
+            .await
+            .unwrap();
+
+        assert!(
+            DocumentRepository::queue_for_reprocess(&conn, "finished", Some(true))
+                .await
+                .unwrap()
+        );
+        assert!(
+            !DocumentRepository::queue_for_reprocess(&conn, "finished", None)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !DocumentRepository::queue_for_reprocess(&conn, "pending", None)
+                .await
+                .unwrap()
+        );
+
+        let doc = DocumentRepository::get_by_id(&conn, "finished")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(doc.status, ProcessingStatus::Queued);
+        assert_eq!(
+            doc.metadata.get("extract_memories"),
+            Some(&serde_json::json!(true))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_processing_stats_counts_and_prunes_runs() {
+        let conn = setup_test_db().await;
+        conn.execute(
+            "CREATE TABLE processing_runs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                document_id TEXT NOT NULL,
+                status TEXT NOT NULL,
+                duration_ms INTEGER NOT NULL,
+                finished_at TEXT NOT NULL
+            )",
+            (),
+        )
+        .await
+        .unwrap();
+
+        let stats = DocumentRepository::processing_stats(&conn).await.unwrap();
+        assert!(stats.counts.is_empty());
+        assert!(stats.oldest_pending_at.is_none());
+
+        let mut oldest = make_doc("oldest", vec![]);
+        oldest.created_at = Utc::now() - chrono::Duration::hours(2);
+        DocumentRepository::create(&conn, &oldest).await.unwrap();
+        let mut done = make_doc("done", vec![]);
+        done.status = ProcessingStatus::Done;
+        done.created_at = Utc::now() - chrono::Duration::days(1);
+        DocumentRepository::create(&conn, &done).await.unwrap();
+        DocumentRepository::create(&conn, &make_doc("queued", vec![]))
+            .await
+            .unwrap();
+
+        for ms in 0..PROCESSING_RUNS_KEPT + 5 {
+            DocumentRepository::record_processing_run(
+                &conn,
+                "done",
+                ProcessingStatus::Done,
+                ms as u64,
+            )
+            .await
+            .unwrap();
+        }
+
+        let stats = DocumentRepository::processing_stats(&conn).await.unwrap();
+        assert_eq!(
+            stats.counts,
+            vec![(ProcessingStatus::Done, 1), (ProcessingStatus::Queued, 2)]
+        );
+        assert_eq!(
+            stats.oldest_pending_at.map(|at| at.timestamp()),
+            Some(oldest.created_at.timestamp())
+        );
+        assert_eq!(
+            stats.recent_durations_ms.len(),
+            PROCESSING_RUNS_KEPT as usize
+        );
+        assert_eq!(
+            stats.recent_durations_ms[0],
+            PROCESSING_RUNS_KEPT as u64 + 4
+        );
+    }
+
+    async fn delete_all_matching(conn: &Connection, filter: &DocumentDeleteFilter) -> u64 {
+        let mut deleted = 0;
+        loop {
+            match DocumentRepository::delete_matching_batch(conn, filter)
+                .await
+                .unwrap()
+                .len()
+            {
+                0 => return deleted,
+                batch => deleted += batch as u64,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delete_matching_cascades_chunks_in_batches() {
+        let conn = setup_test_db().await;
+        conn.execute(
+            "CREATE TABLE chunks (id TEXT PRIMARY KEY, document_id TEXT NOT NULL)",
+            (),
+        )
+        .await
+        .unwrap();
+
+        let total = DELETE_BATCH_SIZE + 50;
+        for i in 0..total {
+            let id = format!("old{i}");
+            DocumentRepository::create(&conn, &make_doc(&id, vec!["archive".to_string()]))
+                .await
+                .unwrap();
+            conn.execute(
+                "INSERT INTO chunks (id, document_id) VALUES (?1, ?2)",
+                [format!("c_{id}"), id],
+            )
+            .await
+            .unwrap();
+        }
+        let mut draft = make_doc("draft", vec!["work".to_string()]);
+        draft
+            .metadata
+            .insert("status".to_string(), serde_json::json!("draft"));
+        DocumentRepository::create(&conn, &draft).await.unwrap();
+        DocumentRepository::create(&conn, &make_doc("keep", vec!["work".to_string()]))
+            .await
+            .unwrap();
+        // Tag matching is exact, unlike the LIKE used for listing.
+        DocumentRepository::create(&conn, &make_doc("near", vec!["archived".to_string()]))
+            .await
+            .unwrap();
+
+        let by_tag = DocumentDeleteFilter {
+            container_tag: Some("archive".to_string()),
+            ..Default::default()
+        };
+        let first = DocumentRepository::delete_matching_batch(&conn, &by_tag)
+            .await
+            .unwrap()
+            .len() as u64;
+        assert_eq!(first, DELETE_BATCH_SIZE as u64);
+        let deleted = first + delete_all_matching(&conn, &by_tag).await;
+        assert_eq!(deleted, total as u64);
+        assert_eq!(chunk_count(&conn, "old0").await, 0);
+        assert!(DocumentRepository::get_by_id(&conn, "near")
+            .await
+            .unwrap()
+            .is_some());
+
+        let mut by_metadata = DocumentDeleteFilter {
+            container_tag: Some("work".to_string()),
+            ..Default::default()
+        };
+        by_metadata
+            .metadata
+            .insert("status".to_string(), serde_json::json!("draft"));
+        assert_eq!(delete_all_matching(&conn, &by_metadata).await, 1);
+        assert!(DocumentRepository::get_by_id(&conn, "keep")
+            .await
+            .unwrap()
+            .is_some());
+
+        assert!(
+            DocumentRepository::delete_matching_batch(&conn, &DocumentDeleteFilter::default())
+                .await
+                .is_err()
+        );
+    }
+
     #[tokio::test]
     async fn test_tag_filter_normal() {
         let conn = setup_test_db().await;
@@ -495,6 +1125,42 @@ mod tests {
         assert_eq!(all.len(), 1, "table must survive injection attempt");
     }
 
+    #[tokio::test]
+    async fn test_category_filter_combines_with_tags() {
+        let conn = setup_test_db().await;
+
+        let mut recipe = make_doc("d1", vec!["kitchen".to_string()]);
+        recipe
+            .metadata
+            .insert("category".to_string(), serde_json::json!("recipe"));
+        let mut other_recipe = make_doc("d2", vec!["garden".to_string()]);
+        other_recipe
+            .metadata
+            .insert("category".to_string(), serde_json::json!("recipe"));
+        let uncategorized = make_doc("d3", vec!["kitchen".to_string()]);
+        for doc in [&recipe, &other_recipe, &uncategorized] {
+            DocumentRepository::create(&conn, doc).await.unwrap();
+        }
+
+        let req = ListDocumentsRequest {
+            category: Some("recipe".to_string()),
+            ..Default::default()
+        };
+        let (results, pagination) = DocumentRepository::list(&conn, &req).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(pagination.total_items, 2);
+
+        let req = ListDocumentsRequest {
+            container_tags: Some(vec!["kitchen".to_string(), "pantry".to_string()]),
+            category: Some("recipe".to_string()),
+            ..Default::default()
+        };
+        let (results, pagination) = DocumentRepository::list(&conn, &req).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "d1");
+        assert_eq!(pagination.total_items, 1);
+    }
+
     #[tokio::test]
     async fn test_get_by_ids_returns_matching_documents() {
         let conn = setup_test_db().await;
@@ -559,4 +1225,57 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].id, "d1");
     }
+
+    #[tokio::test]
+    async fn test_get_by_ids_batches_past_sqlite_variable_limit() {
+        let conn = setup_test_db().await;
+
+        let ids: Vec<String> = (0..1500).map(|i| format!("d{i}")).collect();
+        // Every other id exists, spread across all batches.
+        for id in ids.iter().step_by(2) {
+            DocumentRepository::create(&conn, &make_doc(id, vec![]))
+                .await
+                .unwrap();
+        }
+
+        let results = DocumentRepository::get_by_ids(&conn, &ids).await.unwrap();
+        assert_eq!(results.len(), 750);
+        assert!(results.iter().any(|d| d.id == "d1498"));
+    }
+
+    #[tokio::test]
+    async fn test_list_by_container_after_pages_tagged_documents() {
+        let conn = setup_test_db().await;
+
+        let start = Utc::now() - chrono::Duration::hours(1);
+        let docs = [
+            ("d1", vec!["alpha".to_string()]),
+            ("d2", vec!["beta".to_string(), "alpha".to_string()]),
+            ("d3", vec!["beta".to_string()]),
+            ("d4", vec!["alpha".to_string()]),
+        ];
+        for (i, (id, tags)) in docs.into_iter().enumerate() {
+            let mut doc = make_doc(id, tags);
+            doc.created_at = start + chrono::Duration::minutes(i as i64);
+            DocumentRepository::create(&conn, &doc).await.unwrap();
+        }
+
+        let first = DocumentRepository::list_by_container_after(&conn, "alpha", None, 2)
+            .await
+            .unwrap();
+        let ids: Vec<&str> = first.iter().map(|d| d.id.as_str()).collect();
+        assert_eq!(ids, vec!["d1", "d2"]);
+
+        let last = first.last().unwrap();
+        let second = DocumentRepository::list_by_container_after(
+            &conn,
+            "alpha",
+            Some((last.created_at, last.id.as_str())),
+            2,
+        )
+        .await
+        .unwrap();
+        let ids: Vec<&str> = second.iter().map(|d| d.id.as_str()).collect();
+        assert_eq!(ids, vec!["d4"]);
+    }
 }
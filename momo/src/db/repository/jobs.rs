@@ -0,0 +1,230 @@
+use chrono::{DateTime, Utc};
+use libsql::{params, Connection};
+use nanoid::nanoid;
+
+use crate::error::Result;
+use crate::models::{Job, JobKind, JobStatus};
+
+pub struct JobRepository;
+
+const SELECT_COLUMNS: &str = r#"
+    SELECT id, kind, status, processed, total, result, error, created_at, updated_at
+    FROM jobs
+"#;
+
+impl JobRepository {
+    /// Record a new queued job.
+    pub async fn create(conn: &Connection, kind: JobKind, total: Option<u64>) -> Result<Job> {
+        let id = nanoid!();
+        let now = Utc::now();
+
+        conn.execute(
+            r#"
+            INSERT INTO jobs (id, kind, status, processed, total, created_at, updated_at)
+            VALUES (?1, ?2, 'queued', 0, ?3, ?4, ?4)
+            "#,
+            params![
+                id.clone(),
+                kind.to_string(),
+                total.map(|value| value as i64),
+                now.to_rfc3339(),
+            ],
+        )
+        .await?;
+
+        Ok(Job {
+            id,
+            kind,
+            status: JobStatus::Queued,
+            processed: 0,
+            total,
+            result: None,
+            error: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub async fn get_by_id(conn: &Connection, id: &str) -> Result<Option<Job>> {
+        let mut rows = conn
+            .query(&format!("{SELECT_COLUMNS} WHERE id = ?1"), params![id])
+            .await?;
+
+        match rows.next().await? {
+            Some(row) => Ok(Some(Self::row_to_job(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn set_running(conn: &Connection, id: &str) -> Result<()> {
+        conn.execute(
+            "UPDATE jobs SET status = 'running', updated_at = ?2 WHERE id = ?1",
+            params![id, Utc::now().to_rfc3339()],
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn update_progress(conn: &Connection, id: &str, processed: u64) -> Result<()> {
+        conn.execute(
+            "UPDATE jobs SET processed = ?2, updated_at = ?3 WHERE id = ?1",
+            params![id, processed as i64, Utc::now().to_rfc3339()],
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Mark a job as finished, successfully (`error` is `None`) or not.
+    pub async fn finish(
+        conn: &Connection,
+        id: &str,
+        result: Option<&serde_json::Value>,
+        error: Option<&str>,
+    ) -> Result<()> {
+        let status = if error.is_some() {
+            JobStatus::Failed
+        } else {
+            JobStatus::Done
+        };
+
+        conn.execute(
+            r#"
+            UPDATE jobs
+            SET status = ?2, result = ?3, error = ?4, updated_at = ?5
+            WHERE id = ?1
+            "#,
+            params![
+                id,
+                status.to_string(),
+                result.map(|value| value.to_string()),
+                error,
+                Utc::now().to_rfc3339(),
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    fn row_to_job(row: &libsql::Row) -> Result<Job> {
+        let kind: String = row.get(1)?;
+        let status: String = row.get(2)?;
+        let total: Option<i64> = row.get(4)?;
+        let result: Option<String> = row.get(5)?;
+
+        Ok(Job {
+            id: row.get(0)?,
+            kind: kind.parse().unwrap_or(JobKind::BatchImport),
+            status: status.parse().unwrap_or_default(),
+            processed: row.get::<i64>(3)?.max(0) as u64,
+            total: total.map(|value| value.max(0) as u64),
+            result: result.and_then(|value| serde_json::from_str(&value).ok()),
+            error: row.get(6)?,
+            created_at: Self::parse_timestamp(row.get::<String>(7)?),
+            updated_at: Self::parse_timestamp(row.get::<String>(8)?),
+        })
+    }
+
+    fn parse_timestamp(value: String) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(&value)
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_test_db() -> Connection {
+        let conn = libsql::Builder::new_local(":memory:")
+            .build()
+            .await
+            .unwrap()
+            .connect()
+            .unwrap();
+
+        conn.execute(
+            r#"
+            CREATE TABLE jobs (
+                id TEXT PRIMARY KEY,
+                kind TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'queued',
+                processed INTEGER NOT NULL DEFAULT 0,
+                total INTEGER,
+                result TEXT,
+                error TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )
+            "#,
+            (),
+        )
+        .await
+        .unwrap();
+
+        conn
+    }
+
+    #[tokio::test]
+    async fn test_job_lifecycle() {
+        let conn = setup_test_db().await;
+
+        let job = JobRepository::create(&conn, JobKind::BatchImport, Some(3))
+            .await
+            .unwrap();
+        let fetched = JobRepository::get_by_id(&conn, &job.id)
+            .await
+            .unwrap()
+            .expect("job should exist");
+        assert_eq!(fetched.kind, JobKind::BatchImport);
+        assert_eq!(fetched.status, JobStatus::Queued);
+        assert_eq!(fetched.total, Some(3));
+
+        JobRepository::set_running(&conn, &job.id).await.unwrap();
+        JobRepository::update_progress(&conn, &job.id, 2)
+            .await
+            .unwrap();
+        let running = JobRepository::get_by_id(&conn, &job.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(running.status, JobStatus::Running);
+        assert_eq!(running.processed, 2);
+
+        let result = serde_json::json!({ "failedDocumentIds": [] });
+        JobRepository::finish(&conn, &job.id, Some(&result), None)
+            .await
+            .unwrap();
+        let done = JobRepository::get_by_id(&conn, &job.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(done.status, JobStatus::Done);
+        assert_eq!(done.result, Some(result));
+        assert!(done.error.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_finish_with_error_marks_failed() {
+        let conn = setup_test_db().await;
+
+        let job = JobRepository::create(&conn, JobKind::ChunkReembed, None)
+            .await
+            .unwrap();
+        JobRepository::finish(&conn, &job.id, None, Some("model unavailable"))
+            .await
+            .unwrap();
+
+        let failed = JobRepository::get_by_id(&conn, &job.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(failed.status, JobStatus::Failed);
+        assert_eq!(failed.error.as_deref(), Some("model unavailable"));
+        assert!(failed.total.is_none());
+        assert!(JobRepository::get_by_id(&conn, "missing")
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
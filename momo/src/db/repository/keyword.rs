@@ -0,0 +1,29 @@
+/// Most tokens an FTS5 `snippet()` highlight keeps around the matched terms.
+pub(super) const HIGHLIGHT_TOKENS: u32 = 24;
+
+/// FTS5 `MATCH` expression for free text: every whitespace-separated term is
+/// quoted, so punctuation such as `-` or `:` in error codes and SKUs is matched
+/// literally instead of being parsed as query syntax, and terms are ORed so
+/// BM25 ranks documents by how many of them match. `None` when `text` has no
+/// terms.
+pub(super) fn match_query(text: &str) -> Option<String> {
+    let terms: Vec<String> = text
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" OR "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_query_quotes_terms() {
+        assert_eq!(
+            match_query("ERR-404 sku:\"A1\"").as_deref(),
+            Some(r#""ERR-404" OR "sku:""A1""""#)
+        );
+        assert_eq!(match_query("  \n"), None);
+    }
+}
@@ -1,15 +1,17 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use chrono::{DateTime, Utc};
 use libsql::{params, Connection};
 
 use crate::error::Result;
 use crate::models::{
-    CachedProfile, Document, GraphData, GraphEdge, GraphEdgeType, Memory, MemoryRelationType,
-    MemorySearchHit, ProfileFact, UserProfile,
+    CachedProfile, Document, GraphData, GraphDirection, GraphEdge, GraphEdgeType, Memory,
+    MemoryRelationType, MemorySearchHit, MemoryType, ProfileFact, ProfileFactOptions,
+    ProfileFactSort, UserProfile,
 };
 
-use super::DocumentRepository;
+use super::vector::decode_f32_blob;
+use super::{id_placeholders, keyword, DocumentRepository, ID_BATCH_SIZE};
 
 pub struct MemoryRepository;
 
@@ -74,36 +76,50 @@ impl MemoryRepository {
         }
     }
 
-    pub async fn get_by_ids(conn: &Connection, ids: &[String]) -> Result<Vec<Memory>> {
-        if ids.is_empty() {
-            return Ok(Vec::new());
-        }
+    /// Like [`get_by_id`](Self::get_by_id), but also returns forgotten memories.
+    pub async fn get_by_id_including_forgotten(
+        conn: &Connection,
+        id: &str,
+    ) -> Result<Option<Memory>> {
+        let mut rows = conn
+            .query(
+                "SELECT id, memory, space_id, container_tag, version, is_latest,
+                        parent_memory_id, root_memory_id, memory_relations, source_count,
+                        is_inference, is_forgotten, is_static, forget_after, forget_reason,
+                        memory_type, last_accessed, confidence, metadata, created_at, updated_at
+                 FROM memories WHERE id = ?1",
+                params![id],
+            )
+            .await?;
 
-        let mut placeholders = String::new();
-        for i in 0..ids.len() {
-            if i > 0 {
-                placeholders.push_str(", ");
-            }
-            placeholders.push('?');
-            placeholders.push_str(&(i + 1).to_string());
+        if let Some(row) = rows.next().await? {
+            Ok(Some(Self::row_to_memory(&row)?))
+        } else {
+            Ok(None)
         }
+    }
 
-        let sql = format!(
-            "SELECT id, memory, space_id, container_tag, version, is_latest, \
-                    parent_memory_id, root_memory_id, memory_relations, source_count, \
-                    is_inference, is_forgotten, is_static, forget_after, forget_reason, \
-                    memory_type, last_accessed, confidence, metadata, created_at, updated_at \
-             FROM memories WHERE id IN ({placeholders}) AND is_forgotten = 0"
-        );
-        let params: Vec<libsql::Value> = ids
-            .iter()
-            .map(|id| libsql::Value::from(id.clone()))
-            .collect();
-
-        let mut rows = conn.query(&sql, libsql::params_from_iter(params)).await?;
+    /// Memories with any of `ids`, looked up [`ID_BATCH_SIZE`] ids at a time.
+    pub async fn get_by_ids(conn: &Connection, ids: &[String]) -> Result<Vec<Memory>> {
         let mut results = Vec::new();
-        while let Some(row) = rows.next().await? {
-            results.push(Self::row_to_memory(&row)?);
+        for batch in ids.chunks(ID_BATCH_SIZE) {
+            let placeholders = id_placeholders(batch.len());
+            let sql = format!(
+                "SELECT id, memory, space_id, container_tag, version, is_latest, \
+                        parent_memory_id, root_memory_id, memory_relations, source_count, \
+                        is_inference, is_forgotten, is_static, forget_after, forget_reason, \
+                        memory_type, last_accessed, confidence, metadata, created_at, updated_at \
+                 FROM memories WHERE id IN ({placeholders}) AND is_forgotten = 0"
+            );
+            let params: Vec<libsql::Value> = batch
+                .iter()
+                .map(|id| libsql::Value::from(id.clone()))
+                .collect();
+
+            let mut rows = conn.query(&sql, libsql::params_from_iter(params)).await?;
+            while let Some(row) = rows.next().await? {
+                results.push(Self::row_to_memory(&row)?);
+            }
         }
         Ok(results)
     }
@@ -144,6 +160,22 @@ impl MemoryRepository {
         Ok(())
     }
 
+    /// Compare-and-set variant of [`Self::update_to_not_latest`]: only applies while
+    /// the memory is still latest and at `expected_version`.
+    pub async fn supersede(conn: &Connection, id: &str, expected_version: i32) -> Result<bool> {
+        let affected = conn
+            .execute(
+                r#"
+                UPDATE memories SET is_latest = 0, updated_at = ?3
+                WHERE id = ?1 AND is_latest = 1 AND version = ?2
+                "#,
+                params![id, expected_version, Utc::now().to_rfc3339()],
+            )
+            .await?;
+
+        Ok(affected > 0)
+    }
+
     pub async fn forget(conn: &Connection, id: &str, reason: Option<&str>) -> Result<()> {
         conn.execute(
             r#"
@@ -158,6 +190,23 @@ impl MemoryRepository {
         Ok(())
     }
 
+    /// Undo [`forget`](Self::forget): clear the forgotten flag, the reason and
+    /// any scheduled expiry. Returns `false` when no memory has this id.
+    pub async fn restore(conn: &Connection, id: &str) -> Result<bool> {
+        let affected = conn
+            .execute(
+                r#"
+                UPDATE memories
+                SET is_forgotten = 0, forget_reason = NULL, forget_after = NULL, updated_at = ?2
+                WHERE id = ?1
+                "#,
+                params![id, Utc::now().to_rfc3339()],
+            )
+            .await?;
+
+        Ok(affected > 0)
+    }
+
     pub async fn update_last_accessed_batch(conn: &Connection, ids: &[&str]) -> Result<u64> {
         if ids.is_empty() {
             return Ok(0);
@@ -238,12 +287,127 @@ impl MemoryRepository {
         Ok(())
     }
 
+    /// Stored embedding of a memory; `None` when the memory doesn't exist or
+    /// hasn't been embedded yet.
+    pub async fn get_embedding(conn: &Connection, id: &str) -> Result<Option<Vec<f32>>> {
+        let mut rows = conn
+            .query(
+                "SELECT embedding FROM memories WHERE id = ?1 AND embedding IS NOT NULL",
+                params![id],
+            )
+            .await?;
+
+        match rows.next().await? {
+            Some(row) => Ok(Some(decode_f32_blob(&row.get::<Vec<u8>>(0)?))),
+            None => Ok(None),
+        }
+    }
+
+    /// Stored embeddings of the given memories, keyed by id. Memories that
+    /// don't exist or haven't been embedded yet are left out.
+    pub async fn get_embeddings(
+        conn: &Connection,
+        ids: &[String],
+    ) -> Result<HashMap<String, Vec<f32>>> {
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let placeholders = (1..=ids.len())
+            .map(|i| format!("?{i}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let sql = format!(
+            "SELECT id, embedding FROM memories WHERE id IN ({placeholders}) AND embedding IS NOT NULL"
+        );
+        let params: Vec<libsql::Value> = ids
+            .iter()
+            .map(|id| libsql::Value::from(id.clone()))
+            .collect();
+
+        let mut rows = conn.query(&sql, libsql::params_from_iter(params)).await?;
+        let mut embeddings = HashMap::new();
+        while let Some(row) = rows.next().await? {
+            embeddings.insert(
+                row.get::<String>(0)?,
+                decode_f32_blob(&row.get::<Vec<u8>>(1)?),
+            );
+        }
+        Ok(embeddings)
+    }
+
+    /// One page of a container's memories, oldest first, including forgotten
+    /// and superseded versions. `after` is the `(created_at, id)` of the last
+    /// memory on the previous page.
+    pub async fn list_by_container_after(
+        conn: &Connection,
+        container_tag: &str,
+        after: Option<(DateTime<Utc>, &str)>,
+        limit: usize,
+    ) -> Result<Vec<Memory>> {
+        let mut rows = conn
+            .query(
+                r#"
+                SELECT id, memory, space_id, container_tag, version, is_latest,
+                       parent_memory_id, root_memory_id, memory_relations, source_count,
+                       is_inference, is_forgotten, is_static, forget_after, forget_reason,
+                       memory_type, last_accessed, confidence, metadata, created_at, updated_at
+                FROM memories
+                WHERE container_tag = ?1
+                  AND (?2 IS NULL OR created_at > ?2 OR (created_at = ?2 AND id > ?3))
+                ORDER BY created_at, id
+                LIMIT ?4
+                "#,
+                params![
+                    container_tag,
+                    after.map(|(created_at, _)| created_at.to_rfc3339()),
+                    after.map(|(_, id)| id.to_string()),
+                    limit as i64
+                ],
+            )
+            .await?;
+
+        let mut results = Vec::new();
+        while let Some(row) = rows.next().await? {
+            results.push(Self::row_to_memory(&row)?);
+        }
+
+        Ok(results)
+    }
+
     pub async fn search_similar(
         conn: &Connection,
         embedding: &[f32],
         limit: u32,
         threshold: f32,
         container_tag: Option<&str>,
+        language: Option<&str>,
+        include_forgotten: bool,
+    ) -> Result<Vec<MemorySearchHit>> {
+        Self::search_similar_by_model(
+            conn,
+            embedding,
+            None,
+            limit,
+            threshold,
+            container_tag,
+            language,
+            include_forgotten,
+        )
+        .await
+    }
+
+    /// Vector search against the primary `memories.embedding` column, or against a
+    /// model's embeddings in `memory_embeddings` when `embedding_model` is set.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn search_similar_by_model(
+        conn: &Connection,
+        embedding: &[f32],
+        embedding_model: Option<&str>,
+        limit: u32,
+        threshold: f32,
+        container_tag: Option<&str>,
+        language: Option<&str>,
         include_forgotten: bool,
     ) -> Result<Vec<MemorySearchHit>> {
         let embedding_json = serde_json::to_string(embedding)?;
@@ -259,69 +423,208 @@ impl MemoryRepository {
             "AND (m.forget_after IS NULL OR m.forget_after > datetime('now'))"
         };
 
-        let query = if container_tag.is_some() {
-            format!(
-                r#"
-                SELECT {columns},
-                       1 - vector_distance_cos(m.embedding, vector32(?1)) as score
-                FROM memories m
-                WHERE m.embedding IS NOT NULL
-                  AND m.is_latest = 1
-                  AND m.is_forgotten = 0
-                  AND m.container_tag = ?4
-                  AND (1 - vector_distance_cos(m.embedding, vector32(?1))) >= ?2
-                  {forget_after_filter}
-                ORDER BY score DESC
-                LIMIT ?3
-                "#
-            )
-        } else {
-            format!(
-                r#"
-                SELECT {columns},
-                       1 - vector_distance_cos(m.embedding, vector32(?1)) as score
-                FROM memories m
-                WHERE m.embedding IS NOT NULL
-                  AND m.is_latest = 1
-                  AND m.is_forgotten = 0
-                  AND (1 - vector_distance_cos(m.embedding, vector32(?1))) >= ?2
-                  {forget_after_filter}
-                ORDER BY score DESC
-                LIMIT ?3
-                "#
-            )
+        let mut param_values: Vec<libsql::Value> = vec![
+            libsql::Value::from(embedding_json),
+            libsql::Value::from(threshold as f64),
+            libsql::Value::from(limit),
+        ];
+
+        let (embedding_join, embedding_column) = match embedding_model {
+            Some(model) => {
+                param_values.push(libsql::Value::from(model.to_string()));
+                (
+                    format!(
+                        "JOIN memory_embeddings me ON me.memory_id = m.id AND me.model = ?{}",
+                        param_values.len()
+                    ),
+                    "me.embedding",
+                )
+            }
+            None => (String::new(), "m.embedding"),
         };
 
-        let mut rows = if container_tag.is_some() {
-            conn.query(
-                &query,
-                params![embedding_json, threshold, limit, container_tag],
-            )
-            .await?
+        let mut extra_filters = String::new();
+        if let Some(tag) = container_tag {
+            param_values.push(libsql::Value::from(tag.to_string()));
+            extra_filters.push_str(&format!("AND m.container_tag = ?{}\n", param_values.len()));
+        }
+        if let Some(language) = language {
+            param_values.push(libsql::Value::from(language.to_lowercase()));
+            extra_filters.push_str(&format!(
+                "AND lower(json_extract(m.metadata, '$.language')) = ?{}\n",
+                param_values.len()
+            ));
+        }
+
+        let query = format!(
+            r#"
+            SELECT {columns},
+                   1 - vector_distance_cos({embedding_column}, vector32(?1)) as score,
+                   m.importance
+            FROM memories m
+            {embedding_join}
+            WHERE {embedding_column} IS NOT NULL
+              AND m.is_latest = 1
+              AND m.is_forgotten = 0
+              {extra_filters}
+              AND (1 - vector_distance_cos({embedding_column}, vector32(?1))) >= ?2
+              {forget_after_filter}
+            ORDER BY score DESC
+            LIMIT ?3
+            "#
+        );
+
+        let mut rows = conn
+            .query(&query, libsql::params_from_iter(param_values))
+            .await?;
+
+        let mut results = Vec::new();
+        while let Some(row) = rows.next().await? {
+            let memory = Self::row_to_memory(&row)?;
+            let score = row.get::<f64>(21)? as f32;
+            let importance = row.get::<Option<f64>>(22)?;
+            results.push(MemorySearchHit {
+                memory,
+                score,
+                importance,
+            });
+        }
+
+        Ok(results)
+    }
+
+    /// Latest memories matching `query` in the keyword index, best first, with
+    /// their BM25 score negated so that higher is better. Filters as
+    /// [`Self::search_similar`].
+    pub async fn search_keyword(
+        conn: &Connection,
+        query: &str,
+        limit: u32,
+        container_tag: Option<&str>,
+        language: Option<&str>,
+        include_forgotten: bool,
+    ) -> Result<Vec<MemorySearchHit>> {
+        let Some(match_query) = keyword::match_query(query) else {
+            return Ok(Vec::new());
+        };
+
+        let columns = "m.id, m.memory, m.space_id, m.container_tag, m.version, m.is_latest,
+                       m.parent_memory_id, m.root_memory_id, m.memory_relations, m.source_count,
+                       m.is_inference, m.is_forgotten, m.is_static, m.forget_after, m.forget_reason,
+                       m.memory_type, m.last_accessed, m.confidence, m.metadata, m.created_at, m.updated_at";
+
+        let forget_after_filter = if include_forgotten {
+            ""
         } else {
-            conn.query(&query, params![embedding_json, threshold, limit])
-                .await?
+            "AND (m.forget_after IS NULL OR m.forget_after > datetime('now'))"
         };
 
+        let mut param_values: Vec<libsql::Value> =
+            vec![libsql::Value::from(match_query), libsql::Value::from(limit)];
+
+        let mut extra_filters = String::new();
+        if let Some(tag) = container_tag {
+            param_values.push(libsql::Value::from(tag.to_string()));
+            extra_filters.push_str(&format!("AND m.container_tag = ?{}\n", param_values.len()));
+        }
+        if let Some(language) = language {
+            param_values.push(libsql::Value::from(language.to_lowercase()));
+            extra_filters.push_str(&format!(
+                "AND lower(json_extract(m.metadata, '$.language')) = ?{}\n",
+                param_values.len()
+            ));
+        }
+
+        let query = format!(
+            r#"
+            SELECT {columns},
+                   -bm25(memories_fts) as score,
+                   m.importance
+            FROM memories_fts
+            JOIN memories m ON m.rowid = memories_fts.rowid
+            WHERE memories_fts MATCH ?1
+              AND m.is_latest = 1
+              AND m.is_forgotten = 0
+              {extra_filters}
+              {forget_after_filter}
+            ORDER BY score DESC
+            LIMIT ?2
+            "#
+        );
+
+        let mut rows = conn
+            .query(&query, libsql::params_from_iter(param_values))
+            .await?;
+
         let mut results = Vec::new();
         while let Some(row) = rows.next().await? {
             let memory = Self::row_to_memory(&row)?;
             let score = row.get::<f64>(21)? as f32;
-            results.push(MemorySearchHit { memory, score });
+            let importance = row.get::<Option<f64>>(22)?;
+            results.push(MemorySearchHit {
+                memory,
+                score,
+                importance,
+            });
         }
 
         Ok(results)
     }
 
-    pub async fn get_children(conn: &Connection, parent_id: &str) -> Result<Vec<Memory>> {
+    /// Store embeddings produced by `model` for the given memories, replacing any
+    /// previous embedding from the same model.
+    pub async fn upsert_model_embeddings(
+        conn: &Connection,
+        model: &str,
+        embeddings: &[(String, Vec<f32>)],
+    ) -> Result<()> {
+        if embeddings.is_empty() {
+            return Ok(());
+        }
+
+        let now = Utc::now().to_rfc3339();
+        let tx = conn.transaction().await?;
+        for (memory_id, embedding) in embeddings {
+            let embedding_json = serde_json::to_string(embedding)?;
+            tx.execute(
+                r#"
+                INSERT INTO memory_embeddings (memory_id, model, embedding, created_at)
+                VALUES (?1, ?2, vector32(?3), ?4)
+                ON CONFLICT(memory_id, model) DO UPDATE SET
+                    embedding = excluded.embedding,
+                    created_at = excluded.created_at
+                "#,
+                params![memory_id.as_str(), model, embedding_json, now.as_str()],
+            )
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Active memories that have no embedding from `model` yet, oldest first.
+    pub async fn get_missing_model_embeddings(
+        conn: &Connection,
+        model: &str,
+        limit: usize,
+    ) -> Result<Vec<Memory>> {
         let mut rows = conn
             .query(
-                "SELECT id, memory, space_id, container_tag, version, is_latest, 
-                        parent_memory_id, root_memory_id, memory_relations, source_count,
-                        is_inference, is_forgotten, is_static, forget_after, forget_reason,
-                        memory_type, last_accessed, confidence, metadata, created_at, updated_at 
-                 FROM memories WHERE parent_memory_id = ?1 ORDER BY version DESC",
-                params![parent_id],
+                r#"
+                SELECT m.id, m.memory, m.space_id, m.container_tag, m.version, m.is_latest,
+                       m.parent_memory_id, m.root_memory_id, m.memory_relations, m.source_count,
+                       m.is_inference, m.is_forgotten, m.is_static, m.forget_after, m.forget_reason,
+                       m.memory_type, m.last_accessed, m.confidence, m.metadata, m.created_at, m.updated_at
+                FROM memories m
+                LEFT JOIN memory_embeddings me ON me.memory_id = m.id AND me.model = ?1
+                WHERE me.memory_id IS NULL
+                  AND m.is_latest = 1
+                  AND m.is_forgotten = 0
+                ORDER BY m.created_at ASC
+                LIMIT ?2
+                "#,
+                params![model, limit as i64],
             )
             .await?;
 
@@ -333,7 +636,91 @@ impl MemoryRepository {
         Ok(results)
     }
 
-    pub async fn get_parents(conn: &Connection, root_id: &str) -> Result<Vec<Memory>> {
+    /// The `per_parent` newest children of each of `parent_ids`, newest
+    /// version first within each parent.
+    pub async fn get_children_batch(
+        conn: &Connection,
+        parent_ids: &[String],
+        per_parent: usize,
+    ) -> Result<Vec<Memory>> {
+        Self::get_newest_by_column_in(
+            conn,
+            "parent_memory_id",
+            parent_ids,
+            "",
+            per_parent,
+            "parent_memory_id, version DESC",
+        )
+        .await
+    }
+
+    /// The `per_chain` newest superseded versions in each of the chains rooted
+    /// at `root_ids`, oldest version first within each chain.
+    pub async fn get_parents_batch(
+        conn: &Connection,
+        root_ids: &[String],
+        per_chain: usize,
+    ) -> Result<Vec<Memory>> {
+        Self::get_newest_by_column_in(
+            conn,
+            "root_memory_id",
+            root_ids,
+            "AND is_latest = 0",
+            per_chain,
+            "root_memory_id, version ASC",
+        )
+        .await
+    }
+
+    /// Memories whose `column` is one of `values`, keeping only the
+    /// `per_value` highest versions for each value.
+    async fn get_newest_by_column_in(
+        conn: &Connection,
+        column: &str,
+        values: &[String],
+        extra_condition: &str,
+        per_value: usize,
+        order_by: &str,
+    ) -> Result<Vec<Memory>> {
+        if values.is_empty() || per_value == 0 {
+            return Ok(Vec::new());
+        }
+
+        let placeholders = (1..=values.len())
+            .map(|i| format!("?{i}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let limit_idx = values.len() + 1;
+        let sql = format!(
+            "SELECT id, memory, space_id, container_tag, version, is_latest, \
+                    parent_memory_id, root_memory_id, memory_relations, source_count, \
+                    is_inference, is_forgotten, is_static, forget_after, forget_reason, \
+                    memory_type, last_accessed, confidence, metadata, created_at, updated_at \
+             FROM ( \
+                 SELECT *, ROW_NUMBER() OVER (PARTITION BY {column} ORDER BY version DESC) AS rn \
+                 FROM memories WHERE {column} IN ({placeholders}) {extra_condition} \
+             ) \
+             WHERE rn <= ?{limit_idx} \
+             ORDER BY {order_by}"
+        );
+        let mut params: Vec<libsql::Value> = values
+            .iter()
+            .map(|value| libsql::Value::from(value.clone()))
+            .collect();
+        params.push(libsql::Value::from(per_value as i64));
+
+        let mut rows = conn.query(&sql, libsql::params_from_iter(params)).await?;
+        let mut results = Vec::new();
+        while let Some(row) = rows.next().await? {
+            results.push(Self::row_to_memory(&row)?);
+        }
+        Ok(results)
+    }
+
+    pub async fn get_forgetting_candidates(
+        conn: &Connection,
+        before: DateTime<Utc>,
+    ) -> Result<Vec<Memory>> {
         let mut rows = conn
             .query(
                 r#"
@@ -342,10 +729,11 @@ impl MemoryRepository {
                        is_inference, is_forgotten, is_static, forget_after, forget_reason,
                        memory_type, last_accessed, confidence, metadata, created_at, updated_at
                 FROM memories
-                WHERE root_memory_id = ?1 AND is_latest = 0
-                ORDER BY version ASC
+                WHERE forget_after IS NOT NULL
+                  AND forget_after < ?1
+                  AND is_forgotten = 0
                 "#,
-                params![root_id],
+                params![before.to_rfc3339()],
             )
             .await?;
 
@@ -357,9 +745,13 @@ impl MemoryRepository {
         Ok(results)
     }
 
-    pub async fn get_forgetting_candidates(
+    /// Active episodes not accessed (or, if never accessed, created) since
+    /// `accessed_before` that have at most `max_source_count` sources. Static
+    /// episodes and episodes an active inference derives from are excluded.
+    pub async fn get_idle_episodes(
         conn: &Connection,
-        before: DateTime<Utc>,
+        accessed_before: DateTime<Utc>,
+        max_source_count: i32,
     ) -> Result<Vec<Memory>> {
         let mut rows = conn
             .query(
@@ -368,12 +760,21 @@ impl MemoryRepository {
                        parent_memory_id, root_memory_id, memory_relations, source_count,
                        is_inference, is_forgotten, is_static, forget_after, forget_reason,
                        memory_type, last_accessed, confidence, metadata, created_at, updated_at
-                FROM memories
-                WHERE forget_after IS NOT NULL
-                  AND forget_after < ?1
+                FROM memories m
+                WHERE memory_type = 'episode'
                   AND is_forgotten = 0
+                  AND is_static = 0
+                  AND source_count <= ?2
+                  AND COALESCE(last_accessed, created_at) < ?1
+                  AND NOT EXISTS (
+                      SELECT 1 FROM memories inference, json_each(inference.memory_relations) r
+                      WHERE inference.is_inference = 1
+                        AND inference.is_forgotten = 0
+                        AND r.key = m.id
+                        AND r.value = 'derives'
+                  )
                 "#,
-                params![before.to_rfc3339()],
+                params![accessed_before.to_rfc3339(), max_source_count],
             )
             .await?;
 
@@ -461,54 +862,15 @@ impl MemoryRepository {
         container_tag: &str,
         include_dynamic: bool,
         limit: u32,
+        options: &ProfileFactOptions,
     ) -> Result<UserProfile> {
-        let static_query = r#"
-            SELECT memory, confidence, created_at
-            FROM memories
-            WHERE container_tag = ?1 AND is_static = 1 AND is_latest = 1 AND is_forgotten = 0
-            ORDER BY created_at DESC
-            LIMIT ?2
-        "#;
-
-        let mut static_rows = conn
-            .query(static_query, params![container_tag, limit])
-            .await?;
-
-        let mut static_facts = Vec::new();
-        while let Some(row) = static_rows.next().await? {
-            static_facts.push(ProfileFact {
-                memory: row.get(0)?,
-                confidence: row.get::<Option<f64>>(1)?,
-                created_at: DateTime::parse_from_rfc3339(&row.get::<String>(2)?)
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .unwrap_or_else(|_| Utc::now()),
-            });
-        }
-
-        let mut dynamic_facts = Vec::new();
-        if include_dynamic {
-            let dynamic_query = r#"
-                SELECT memory, confidence, created_at
-                FROM memories
-                WHERE container_tag = ?1 AND is_static = 0 AND is_latest = 1 AND is_forgotten = 0
-                ORDER BY created_at DESC
-                LIMIT ?2
-            "#;
-
-            let mut dynamic_rows = conn
-                .query(dynamic_query, params![container_tag, limit])
-                .await?;
-
-            while let Some(row) = dynamic_rows.next().await? {
-                dynamic_facts.push(ProfileFact {
-                    memory: row.get(0)?,
-                    confidence: row.get::<Option<f64>>(1)?,
-                    created_at: DateTime::parse_from_rfc3339(&row.get::<String>(2)?)
-                        .map(|dt| dt.with_timezone(&Utc))
-                        .unwrap_or_else(|_| Utc::now()),
-                });
-            }
-        }
+        let static_facts =
+            Self::get_profile_facts(conn, container_tag, true, limit, options).await?;
+        let dynamic_facts = if include_dynamic {
+            Self::get_profile_facts(conn, container_tag, false, limit, options).await?
+        } else {
+            Vec::new()
+        };
 
         let count_query = r#"
             SELECT COUNT(*) FROM memories
@@ -590,6 +952,56 @@ impl MemoryRepository {
         }
     }
 
+    /// Active static or dynamic facts of a container, filtered and ordered
+    /// by `options`.
+    async fn get_profile_facts(
+        conn: &Connection,
+        container_tag: &str,
+        is_static: bool,
+        limit: u32,
+        options: &ProfileFactOptions,
+    ) -> Result<Vec<ProfileFact>> {
+        let order_by = match options.sort {
+            ProfileFactSort::Recency => "created_at DESC",
+            ProfileFactSort::Confidence => "confidence IS NULL, confidence DESC, created_at DESC",
+        };
+        let query = format!(
+            r#"
+            SELECT memory, confidence, created_at
+            FROM memories
+            WHERE container_tag = ?1 AND is_static = ?2 AND is_latest = 1 AND is_forgotten = 0
+              AND (?3 IS NULL OR confidence >= ?3 OR (confidence IS NULL AND ?4 = 0))
+            ORDER BY {order_by}
+            LIMIT ?5
+        "#
+        );
+
+        let mut rows = conn
+            .query(
+                &query,
+                params![
+                    container_tag,
+                    is_static as i32,
+                    options.min_confidence,
+                    options.exclude_unscored as i32,
+                    limit
+                ],
+            )
+            .await?;
+
+        let mut facts = Vec::new();
+        while let Some(row) = rows.next().await? {
+            facts.push(ProfileFact {
+                memory: row.get(0)?,
+                confidence: row.get::<Option<f64>>(1)?,
+                created_at: DateTime::parse_from_rfc3339(&row.get::<String>(2)?)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+            });
+        }
+        Ok(facts)
+    }
+
     // get_memories_by_ids removed: function was unused. If needed in future, reintroduce with
     // parameterized query using libsql::params_from_iter to avoid SQL injection.
 
@@ -664,16 +1076,30 @@ impl MemoryRepository {
         Ok((documents, edges))
     }
 
+    /// Memories within `depth` hops of `id`, breadth first, following edges
+    /// in `direction`. With `memory_types`, other memories are neither
+    /// returned nor traversed (`id` itself always is), and only edges between
+    /// returned memories are kept.
     pub async fn get_graph_neighborhood(
         conn: &Connection,
         id: &str,
         depth: u32,
         max_nodes: u32,
         relation_types: Option<&[GraphEdgeType]>,
+        memory_types: Option<&[MemoryType]>,
+        direction: GraphDirection,
     ) -> Result<GraphData> {
+        let type_allowed = |memory: &Memory| {
+            memory_types.map_or(true, |types| types.contains(&memory.memory_type))
+        };
+        let follow_outbound = direction != GraphDirection::Inbound;
+        let follow_inbound = direction != GraphDirection::Outbound;
+
         let mut visited: HashSet<String> = HashSet::new();
         let mut all_memories: Vec<Memory> = Vec::new();
         let mut all_edges: Vec<GraphEdge> = Vec::new();
+        // With both directions, an edge can be found from either end.
+        let mut seen_edges: HashSet<(String, String)> = HashSet::new();
 
         let mut queue: VecDeque<(String, u32)> = VecDeque::new();
         queue.push_back((id.to_string(), 0));
@@ -682,18 +1108,23 @@ impl MemoryRepository {
             if visited.contains(&current_id) {
                 continue;
             }
-            if visited.len() >= max_nodes as usize {
+            if all_memories.len() >= max_nodes as usize {
                 break;
             }
 
             visited.insert(current_id.clone());
 
             let memory = match Self::get_by_id(conn, &current_id).await? {
-                Some(m) => m,
-                None => continue,
+                Some(m) if current_id == id || type_allowed(&m) => m,
+                _ => continue,
             };
 
-            for (related_id, relation_type) in &memory.memory_relations {
+            let outbound = if follow_outbound {
+                memory.memory_relations.iter().collect()
+            } else {
+                Vec::new()
+            };
+            for (related_id, relation_type) in outbound {
                 let edge_type = Self::relation_to_edge_type(relation_type);
 
                 // Skip edges that don't match the requested relation types
@@ -703,21 +1134,23 @@ impl MemoryRepository {
                     }
                 }
 
-                all_edges.push(GraphEdge::new(
-                    current_id.clone(),
-                    related_id.clone(),
-                    edge_type,
-                ));
+                if seen_edges.insert((current_id.clone(), related_id.clone())) {
+                    all_edges.push(GraphEdge::new(
+                        current_id.clone(),
+                        related_id.clone(),
+                        edge_type,
+                    ));
+                }
 
                 if current_depth < depth && !visited.contains(related_id) {
                     queue.push_back((related_id.clone(), current_depth + 1));
                 }
             }
 
-            if current_depth < depth {
+            if follow_inbound && current_depth < depth {
                 let referencing = Self::get_memories_referencing(conn, &current_id).await?;
                 for ref_memory in &referencing {
-                    if !visited.contains(&ref_memory.id) {
+                    if !visited.contains(&ref_memory.id) && type_allowed(ref_memory) {
                         if let Some(rel_type) = ref_memory.memory_relations.get(&current_id) {
                             let edge_type = Self::relation_to_edge_type(rel_type);
                             if let Some(types) = relation_types {
@@ -725,11 +1158,13 @@ impl MemoryRepository {
                                     continue;
                                 }
                             }
-                            all_edges.push(GraphEdge::new(
-                                ref_memory.id.clone(),
-                                current_id.clone(),
-                                edge_type,
-                            ));
+                            if seen_edges.insert((ref_memory.id.clone(), current_id.clone())) {
+                                all_edges.push(GraphEdge::new(
+                                    ref_memory.id.clone(),
+                                    current_id.clone(),
+                                    edge_type,
+                                ));
+                            }
                         }
                         queue.push_back((ref_memory.id.clone(), current_depth + 1));
                     }
@@ -740,6 +1175,11 @@ impl MemoryRepository {
         }
 
         let memory_ids: Vec<String> = all_memories.iter().map(|m| m.id.clone()).collect();
+        if memory_types.is_some() {
+            let returned: HashSet<&String> = memory_ids.iter().collect();
+            all_edges
+                .retain(|edge| returned.contains(&edge.source) && returned.contains(&edge.target));
+        }
         let (documents, doc_edges) = Self::get_source_documents(conn, &memory_ids).await?;
         all_edges.extend(doc_edges);
 
@@ -750,6 +1190,94 @@ impl MemoryRepository {
         })
     }
 
+    /// Shortest chain of relations from `from` to `to`, following them in
+    /// either direction, breadth first up to `max_depth` hops. Returns the
+    /// memories in path order with the edge linking each to the next, or
+    /// `None` when either memory is missing or no path is that short.
+    pub async fn find_path(
+        conn: &Connection,
+        from: &str,
+        to: &str,
+        max_depth: u32,
+    ) -> Result<Option<GraphData>> {
+        let Some(start) = Self::get_by_id(conn, from).await? else {
+            return Ok(None);
+        };
+
+        let mut found: HashMap<String, Memory> = HashMap::new();
+        // The memory each found memory was reached from, and the edge between them.
+        let mut reached_by: HashMap<String, (String, GraphEdge)> = HashMap::new();
+        let mut queue: VecDeque<(String, u32)> = VecDeque::new();
+        queue.push_back((start.id.clone(), 0));
+        found.insert(start.id.clone(), start);
+
+        while !found.contains_key(to) {
+            let Some((current_id, current_depth)) = queue.pop_front() else {
+                return Ok(None);
+            };
+            if current_depth >= max_depth {
+                continue;
+            }
+
+            let mut links: Vec<(String, GraphEdge, Option<Memory>)> = found[&current_id]
+                .memory_relations
+                .iter()
+                .map(|(related_id, relation_type)| {
+                    let edge = GraphEdge::new(
+                        current_id.clone(),
+                        related_id.clone(),
+                        Self::relation_to_edge_type(relation_type),
+                    );
+                    (related_id.clone(), edge, None)
+                })
+                .collect();
+            for ref_memory in Self::get_memories_referencing(conn, &current_id).await? {
+                if let Some(rel_type) = ref_memory.memory_relations.get(&current_id) {
+                    let edge = GraphEdge::new(
+                        ref_memory.id.clone(),
+                        current_id.clone(),
+                        Self::relation_to_edge_type(rel_type),
+                    );
+                    links.push((ref_memory.id.clone(), edge, Some(ref_memory)));
+                }
+            }
+
+            for (next_id, edge, memory) in links {
+                if found.contains_key(&next_id) {
+                    continue;
+                }
+                let memory = match memory {
+                    Some(memory) => memory,
+                    None => match Self::get_by_id(conn, &next_id).await? {
+                        Some(memory) => memory,
+                        None => continue,
+                    },
+                };
+                found.insert(next_id.clone(), memory);
+                reached_by.insert(next_id.clone(), (current_id.clone(), edge));
+                queue.push_back((next_id, current_depth + 1));
+            }
+        }
+
+        let mut memories = Vec::new();
+        let mut edges = Vec::new();
+        let mut current_id = to.to_string();
+        while let Some((previous_id, edge)) = reached_by.remove(&current_id) {
+            memories.extend(found.remove(&current_id));
+            edges.push(edge);
+            current_id = previous_id;
+        }
+        memories.extend(found.remove(&current_id));
+        memories.reverse();
+        edges.reverse();
+
+        Ok(Some(GraphData {
+            memories,
+            edges,
+            documents: Vec::new(),
+        }))
+    }
+
     pub async fn get_container_graph(
         conn: &Connection,
         container_tag: &str,
@@ -921,6 +1449,7 @@ mod tests {
                 memory_type TEXT NOT NULL DEFAULT 'fact',
                 last_accessed TEXT,
                 confidence REAL,
+                importance REAL,
                 metadata TEXT NOT NULL DEFAULT '{}',
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL,
@@ -932,7 +1461,195 @@ mod tests {
         .await
         .unwrap();
 
-        conn
+        conn.execute(
+            r#"
+            CREATE TABLE memory_embeddings (
+                memory_id TEXT NOT NULL,
+                model TEXT NOT NULL,
+                embedding F32_BLOB NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (memory_id, model)
+            )
+            "#,
+            (),
+        )
+        .await
+        .unwrap();
+
+        conn
+    }
+
+    #[tokio::test]
+    async fn test_get_embedding_returns_stored_vector() {
+        let conn = libsql::Builder::new_local(":memory:")
+            .build()
+            .await
+            .unwrap()
+            .connect()
+            .unwrap();
+        crate::db::schema::init_schema(&conn).await.unwrap();
+
+        for id in ["embedded", "pending"] {
+            let memory = Memory::new(
+                id.to_string(),
+                "Some fact".to_string(),
+                "space1".to_string(),
+            );
+            MemoryRepository::create(&conn, &memory).await.unwrap();
+        }
+        let embedding: Vec<f32> = (0..384).map(|i| i as f32 / 384.0).collect();
+        MemoryRepository::update_embedding(&conn, "embedded", &embedding)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            MemoryRepository::get_embedding(&conn, "embedded")
+                .await
+                .unwrap(),
+            Some(embedding)
+        );
+        assert_eq!(
+            MemoryRepository::get_embedding(&conn, "pending")
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            MemoryRepository::get_embedding(&conn, "missing")
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_keyword_ranks_exact_term_matches() {
+        let conn = libsql::Builder::new_local(":memory:")
+            .build()
+            .await
+            .unwrap()
+            .connect()
+            .unwrap();
+        crate::db::schema::init_schema(&conn).await.unwrap();
+
+        let mut memories = vec![
+            Memory::new(
+                "m1".to_string(),
+                "Checkout fails with ERR-4021 after the payment step".to_string(),
+                "space1".to_string(),
+            ),
+            Memory::new(
+                "m2".to_string(),
+                "Payment errors are retried once".to_string(),
+                "space1".to_string(),
+            ),
+            Memory::new(
+                "m3".to_string(),
+                "ERR-4021 also shows up for SKU A-77".to_string(),
+                "space1".to_string(),
+            ),
+        ];
+        memories[1].container_tag = Some("other".to_string());
+        memories[2].is_latest = false;
+        for memory in &memories {
+            MemoryRepository::create(&conn, memory).await.unwrap();
+        }
+
+        let hits = MemoryRepository::search_keyword(&conn, "ERR-4021", 10, None, None, false)
+            .await
+            .unwrap();
+        let ids: Vec<&str> = hits.iter().map(|hit| hit.memory.id.as_str()).collect();
+        assert_eq!(ids, vec!["m1"], "superseded versions are excluded");
+        assert!(hits[0].score > 0.0);
+
+        let hits =
+            MemoryRepository::search_keyword(&conn, "payment", 10, Some("other"), None, false)
+                .await
+                .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].memory.id, "m2");
+
+        assert!(
+            MemoryRepository::search_keyword(&conn, "  ", 10, None, None, false)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_children_and_parents_batch_cover_all_ids() {
+        let conn = setup_test_db().await;
+
+        for parent in ["p1", "p2"] {
+            MemoryRepository::create(
+                &conn,
+                &Memory::new(parent.to_string(), parent.to_string(), "space1".to_string()),
+            )
+            .await
+            .unwrap();
+        }
+        for i in 0..30 {
+            let mut child = Memory::new(
+                format!("p1_child_{i}"),
+                format!("child {i}"),
+                "space1".to_string(),
+            );
+            child.parent_memory_id = Some("p1".to_string());
+            child.root_memory_id = Some("p1".to_string());
+            child.version = i + 2;
+            child.is_latest = i == 29;
+            MemoryRepository::create(&conn, &child).await.unwrap();
+        }
+        let mut other_child = Memory::new(
+            "p2_child".to_string(),
+            "other".to_string(),
+            "space1".to_string(),
+        );
+        other_child.parent_memory_id = Some("p2".to_string());
+        MemoryRepository::create(&conn, &other_child).await.unwrap();
+
+        let children =
+            MemoryRepository::get_children_batch(&conn, &["p1".to_string(), "p2".to_string()], 100)
+                .await
+                .unwrap();
+        assert_eq!(children.len(), 31);
+        let p1_versions: Vec<i32> = children
+            .iter()
+            .filter(|m| m.parent_memory_id.as_deref() == Some("p1"))
+            .map(|m| m.version)
+            .collect();
+        assert_eq!(p1_versions.first(), Some(&31));
+        assert!(p1_versions.windows(2).all(|pair| pair[0] > pair[1]));
+
+        let parents = MemoryRepository::get_parents_batch(&conn, &["p1".to_string()], 100)
+            .await
+            .unwrap();
+        assert_eq!(parents.len(), 29);
+        assert_eq!(parents[0].version, 2);
+
+        // The per-id limit keeps the newest versions of each parent and chain.
+        let capped =
+            MemoryRepository::get_children_batch(&conn, &["p1".to_string(), "p2".to_string()], 3)
+                .await
+                .unwrap();
+        let p1_versions: Vec<i32> = capped
+            .iter()
+            .filter(|m| m.parent_memory_id.as_deref() == Some("p1"))
+            .map(|m| m.version)
+            .collect();
+        assert_eq!(p1_versions, vec![31, 30, 29]);
+        assert_eq!(capped.len(), 4);
+        let capped = MemoryRepository::get_parents_batch(&conn, &["p1".to_string()], 3)
+            .await
+            .unwrap();
+        let versions: Vec<i32> = capped.iter().map(|m| m.version).collect();
+        assert_eq!(versions, vec![28, 29, 30]);
+
+        assert!(MemoryRepository::get_children_batch(&conn, &[], 3)
+            .await
+            .unwrap()
+            .is_empty());
     }
 
     #[tokio::test]
@@ -969,6 +1686,39 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_supersede_rejects_stale_version() {
+        let conn = setup_test_db().await;
+
+        let memory = Memory::new(
+            "mem1".to_string(),
+            "Test memory".to_string(),
+            "space1".to_string(),
+        );
+        MemoryRepository::create(&conn, &memory).await.unwrap();
+
+        // A client holding the wrong version is rejected without changing anything
+        assert!(!MemoryRepository::supersede(&conn, "mem1", 2).await.unwrap());
+        assert!(
+            MemoryRepository::get_by_id(&conn, "mem1")
+                .await
+                .unwrap()
+                .unwrap()
+                .is_latest
+        );
+
+        // The first writer at version 1 wins; a second writer with the same
+        // (now stale) version conflicts instead of forking the chain
+        assert!(MemoryRepository::supersede(&conn, "mem1", 1).await.unwrap());
+        assert!(!MemoryRepository::supersede(&conn, "mem1", 1).await.unwrap());
+
+        let superseded = MemoryRepository::get_by_id(&conn, "mem1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!superseded.is_latest);
+    }
+
     #[tokio::test]
     async fn test_update_relations_first_write_wins() {
         let conn = setup_test_db().await;
@@ -1167,6 +1917,7 @@ mod tests {
             10,
             0.0,
             None,
+            None,
             false, // exclude forgotten
         )
         .await
@@ -1177,6 +1928,48 @@ mod tests {
         assert_eq!(results[0].memory.id, "valid");
     }
 
+    #[tokio::test]
+    async fn test_restored_memory_reappears_in_search_similar() {
+        let conn = setup_test_db().await;
+
+        let past_date = Utc::now() - chrono::Duration::days(1);
+        create_memory_with_embedding(&conn, "restored", Some(past_date)).await;
+        MemoryRepository::forget(&conn, "restored", Some("expired"))
+            .await
+            .unwrap();
+
+        let query_embedding = vec![1.0f32; 384];
+        let search = || async {
+            MemoryRepository::search_similar(&conn, &query_embedding, 10, 0.0, None, None, false)
+                .await
+                .unwrap()
+        };
+        assert!(search().await.is_empty());
+        assert!(MemoryRepository::get_by_id(&conn, "restored")
+            .await
+            .unwrap()
+            .is_none());
+        let forgotten = MemoryRepository::get_by_id_including_forgotten(&conn, "restored")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(forgotten.is_forgotten);
+
+        assert!(MemoryRepository::restore(&conn, "restored").await.unwrap());
+        assert!(!MemoryRepository::restore(&conn, "missing").await.unwrap());
+
+        let results = search().await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].memory.id, "restored");
+        let restored = MemoryRepository::get_by_id(&conn, "restored")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!restored.is_forgotten);
+        assert_eq!(restored.forget_reason, None);
+        assert_eq!(restored.forget_after, None);
+    }
+
     #[tokio::test]
     async fn test_search_similar_includes_null_forget_after() {
         let conn = setup_test_db().await;
@@ -1187,7 +1980,7 @@ mod tests {
         // Search
         let query_embedding = vec![1.0f32; 384];
         let results =
-            MemoryRepository::search_similar(&conn, &query_embedding, 10, 0.0, None, false)
+            MemoryRepository::search_similar(&conn, &query_embedding, 10, 0.0, None, None, false)
                 .await
                 .unwrap();
 
@@ -1212,6 +2005,7 @@ mod tests {
             10,
             0.0,
             None,
+            None,
             true, // include forgotten
         )
         .await
@@ -1222,6 +2016,139 @@ mod tests {
         assert_eq!(results[0].memory.id, "expired");
     }
 
+    #[tokio::test]
+    async fn test_search_similar_filters_by_language() {
+        let conn = setup_test_db().await;
+
+        let mut french = Memory::new(
+            "french".to_string(),
+            "L'utilisateur habite à Lyon".to_string(),
+            "space1".to_string(),
+        );
+        french
+            .metadata
+            .insert("language".to_string(), serde_json::json!("fra"));
+        MemoryRepository::create(&conn, &french).await.unwrap();
+        create_memory_with_embedding(&conn, "untagged", None).await;
+
+        let mut embedding = vec![0.0f32; 384];
+        embedding[0] = 1.0;
+        MemoryRepository::update_embedding(&conn, "french", &embedding)
+            .await
+            .unwrap();
+
+        let query_embedding = vec![1.0f32; 384];
+        let results = MemoryRepository::search_similar(
+            &conn,
+            &query_embedding,
+            10,
+            0.0,
+            None,
+            Some("FRA"),
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].memory.id, "french");
+
+        let all =
+            MemoryRepository::search_similar(&conn, &query_embedding, 10, 0.0, None, None, false)
+                .await
+                .unwrap();
+        assert_eq!(all.len(), 2);
+    }
+
+    fn unit_vector(dimensions: usize, index: usize) -> Vec<f32> {
+        let mut vector = vec![0.0f32; dimensions];
+        vector[index] = 1.0;
+        vector
+    }
+
+    #[tokio::test]
+    async fn test_search_similar_by_model_uses_each_models_embeddings() {
+        let conn = setup_test_db().await;
+        for id in ["alpha", "beta"] {
+            let memory = Memory::new(id.to_string(), id.to_string(), "space1".to_string());
+            MemoryRepository::create(&conn, &memory).await.unwrap();
+        }
+
+        // The two models disagree on which memory is closest to the query
+        MemoryRepository::upsert_model_embeddings(
+            &conn,
+            "model-a",
+            &[
+                ("alpha".to_string(), unit_vector(8, 0)),
+                ("beta".to_string(), unit_vector(8, 1)),
+            ],
+        )
+        .await
+        .unwrap();
+        MemoryRepository::upsert_model_embeddings(
+            &conn,
+            "model-b",
+            &[
+                ("alpha".to_string(), unit_vector(8, 1)),
+                ("beta".to_string(), unit_vector(8, 0)),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let query = unit_vector(8, 0);
+        let search = |model| {
+            let conn = &conn;
+            let query = &query;
+            async move {
+                MemoryRepository::search_similar_by_model(
+                    conn, query, model, 10, 0.5, None, None, false,
+                )
+                .await
+                .unwrap()
+            }
+        };
+
+        let model_a = search(Some("model-a")).await;
+        assert_eq!(model_a.len(), 1);
+        assert_eq!(model_a[0].memory.id, "alpha");
+
+        let model_b = search(Some("model-b")).await;
+        assert_eq!(model_b.len(), 1);
+        assert_eq!(model_b[0].memory.id, "beta");
+
+        // Neither memory has a primary embedding, and unknown models match nothing
+        assert!(search(None).await.is_empty());
+        assert!(search(Some("model-c")).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_missing_model_embeddings_skips_embedded_memories() {
+        let conn = setup_test_db().await;
+        for id in ["embedded", "pending"] {
+            let memory = Memory::new(id.to_string(), id.to_string(), "space1".to_string());
+            MemoryRepository::create(&conn, &memory).await.unwrap();
+        }
+        MemoryRepository::upsert_model_embeddings(
+            &conn,
+            "model-a",
+            &[("embedded".to_string(), unit_vector(8, 0))],
+        )
+        .await
+        .unwrap();
+
+        let missing = MemoryRepository::get_missing_model_embeddings(&conn, "model-a", 10)
+            .await
+            .unwrap();
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].id, "pending");
+
+        let missing_other = MemoryRepository::get_missing_model_embeddings(&conn, "model-b", 10)
+            .await
+            .unwrap();
+        assert_eq!(missing_other.len(), 2);
+    }
+
     #[tokio::test]
     async fn test_get_forgetting_candidates_returns_expired_memories() {
         let conn = setup_test_db().await;
@@ -1619,6 +2546,21 @@ mod tests {
         assert!(result_ids.contains(&"m3"));
     }
 
+    #[tokio::test]
+    async fn test_get_by_ids_batches_past_sqlite_variable_limit() {
+        let conn = setup_test_db().await;
+
+        let ids: Vec<String> = (0..1500).map(|i| format!("m{i}")).collect();
+        for id in ids.iter().step_by(3) {
+            let mem = Memory::new(id.clone(), format!("Memory {id}"), "space1".to_string());
+            MemoryRepository::create(&conn, &mem).await.unwrap();
+        }
+
+        let results = MemoryRepository::get_by_ids(&conn, &ids).await.unwrap();
+        assert_eq!(results.len(), 500);
+        assert!(results.iter().any(|m| m.id == "m1497"));
+    }
+
     #[tokio::test]
     async fn test_get_by_ids_empty_input() {
         let conn = setup_test_db().await;
@@ -1661,6 +2603,172 @@ mod tests {
         assert_eq!(results[0].id, "m2");
     }
 
+    /// `a` (fact) extends `b` (episode), which extends `d` (fact); `c` (fact)
+    /// updates `a`.
+    async fn setup_graph() -> Connection {
+        let conn = setup_test_db().await;
+        conn.execute(
+            "CREATE TABLE memory_sources (memory_id TEXT NOT NULL, document_id TEXT NOT NULL)",
+            (),
+        )
+        .await
+        .unwrap();
+
+        for (id, memory_type, relations) in [
+            (
+                "a",
+                MemoryType::Fact,
+                vec![("b", MemoryRelationType::Extends)],
+            ),
+            (
+                "b",
+                MemoryType::Episode,
+                vec![("d", MemoryRelationType::Extends)],
+            ),
+            (
+                "c",
+                MemoryType::Fact,
+                vec![("a", MemoryRelationType::Updates)],
+            ),
+            ("d", MemoryType::Fact, vec![]),
+        ] {
+            let mut memory = Memory::new(id.to_string(), id.to_string(), "space1".to_string());
+            memory.memory_type = memory_type;
+            memory.memory_relations = relations
+                .into_iter()
+                .map(|(related, relation)| (related.to_string(), relation))
+                .collect();
+            MemoryRepository::create(&conn, &memory).await.unwrap();
+        }
+        conn
+    }
+
+    async fn neighborhood(
+        conn: &Connection,
+        memory_types: Option<&[MemoryType]>,
+        direction: GraphDirection,
+    ) -> (Vec<String>, Vec<(String, String)>) {
+        let graph = MemoryRepository::get_graph_neighborhood(
+            conn,
+            "a",
+            2,
+            50,
+            None,
+            memory_types,
+            direction,
+        )
+        .await
+        .unwrap();
+        let mut ids: Vec<String> = graph.memories.into_iter().map(|m| m.id).collect();
+        ids.sort();
+        let mut edges: Vec<(String, String)> = graph
+            .edges
+            .into_iter()
+            .map(|edge| (edge.source, edge.target))
+            .collect();
+        edges.sort();
+        (ids, edges)
+    }
+
+    fn pairs(edges: &[(&str, &str)]) -> Vec<(String, String)> {
+        edges
+            .iter()
+            .map(|(source, target)| (source.to_string(), target.to_string()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_graph_neighborhood_outbound_follows_own_relations_only() {
+        let conn = setup_graph().await;
+
+        let (ids, edges) = neighborhood(&conn, None, GraphDirection::Outbound).await;
+
+        assert_eq!(ids, vec!["a", "b", "d"]);
+        assert_eq!(edges, pairs(&[("a", "b"), ("b", "d")]));
+    }
+
+    #[tokio::test]
+    async fn test_graph_neighborhood_inbound_follows_referencing_memories_only() {
+        let conn = setup_graph().await;
+
+        let (ids, edges) = neighborhood(&conn, None, GraphDirection::Inbound).await;
+
+        assert_eq!(ids, vec!["a", "c"]);
+        assert_eq!(edges, pairs(&[("c", "a")]));
+    }
+
+    #[tokio::test]
+    async fn test_graph_neighborhood_both_directions_by_default() {
+        let conn = setup_graph().await;
+
+        let (ids, edges) = neighborhood(&conn, None, GraphDirection::default()).await;
+
+        assert_eq!(ids, vec!["a", "b", "c", "d"]);
+        assert_eq!(edges, pairs(&[("a", "b"), ("b", "d"), ("c", "a")]));
+    }
+
+    #[tokio::test]
+    async fn test_graph_neighborhood_memory_types_stop_traversal() {
+        let conn = setup_graph().await;
+
+        // `d` is only reachable through the episode `b`
+        let (ids, edges) =
+            neighborhood(&conn, Some(&[MemoryType::Fact]), GraphDirection::Both).await;
+        assert_eq!(ids, vec!["a", "c"]);
+        assert_eq!(edges, pairs(&[("c", "a")]));
+
+        // The requested memory is returned whatever its type
+        let (ids, _) = neighborhood(
+            &conn,
+            Some(&[MemoryType::Episode]),
+            GraphDirection::Outbound,
+        )
+        .await;
+        assert_eq!(ids, vec!["a", "b"]);
+    }
+
+    async fn path(conn: &Connection, from: &str, to: &str, max_depth: u32) -> Option<Vec<String>> {
+        let graph = MemoryRepository::find_path(conn, from, to, max_depth)
+            .await
+            .unwrap()?;
+        assert_eq!(graph.edges.len() + 1, graph.memories.len());
+        Some(graph.memories.into_iter().map(|m| m.id).collect())
+    }
+
+    #[tokio::test]
+    async fn test_find_path_follows_relations_in_either_direction() {
+        let conn = setup_graph().await;
+
+        // `c` updates `a`, `a` extends `b`, `b` extends `d`
+        let graph = MemoryRepository::find_path(&conn, "d", "c", 3)
+            .await
+            .unwrap()
+            .expect("path");
+        let ids: Vec<String> = graph.memories.into_iter().map(|m| m.id).collect();
+        assert_eq!(ids, vec!["d", "b", "a", "c"]);
+        let edges: Vec<(String, String)> = graph
+            .edges
+            .into_iter()
+            .map(|edge| (edge.source, edge.target))
+            .collect();
+        assert_eq!(edges, pairs(&[("b", "d"), ("a", "b"), ("c", "a")]));
+
+        assert_eq!(path(&conn, "a", "a", 0).await.unwrap(), vec!["a"]);
+    }
+
+    #[tokio::test]
+    async fn test_find_path_none_beyond_max_depth_or_for_missing_memory() {
+        let conn = setup_graph().await;
+
+        assert_eq!(
+            path(&conn, "c", "d", 3).await.unwrap(),
+            vec!["c", "a", "b", "d"]
+        );
+        assert!(path(&conn, "c", "d", 2).await.is_none());
+        assert!(path(&conn, "c", "missing", 5).await.is_none());
+        assert!(path(&conn, "missing", "c", 5).await.is_none());
+    }
+
     #[tokio::test]
     async fn test_get_by_ids_nonexistent_ids() {
         let conn = setup_test_db().await;
@@ -1686,4 +2794,166 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].id, "m1");
     }
+
+    /// Static facts `high` (0.9, oldest), `unscored` and `low` (0.5, newest),
+    /// plus a dynamic fact.
+    async fn setup_profile() -> Connection {
+        let conn = setup_test_db().await;
+        let start = Utc::now() - chrono::Duration::hours(1);
+        for (i, (id, confidence, is_static)) in [
+            ("high", Some(0.9), true),
+            ("unscored", None, true),
+            ("low", Some(0.5), true),
+            ("episode", Some(1.0), false),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let mut mem = Memory::new(id.to_string(), id.to_string(), "s".to_string());
+            mem.container_tag = Some("user_1".to_string());
+            mem.confidence = confidence;
+            mem.is_static = is_static;
+            mem.created_at = start + chrono::Duration::minutes(i as i64);
+            MemoryRepository::create(&conn, &mem).await.unwrap();
+        }
+        conn
+    }
+
+    async fn profile_facts(conn: &Connection, options: ProfileFactOptions) -> Vec<String> {
+        let profile = MemoryRepository::get_user_profile(conn, "user_1", false, 10, &options)
+            .await
+            .unwrap();
+        profile.static_facts.into_iter().map(|f| f.memory).collect()
+    }
+
+    #[tokio::test]
+    async fn test_get_user_profile_sorts_by_recency_or_confidence() {
+        let conn = setup_profile().await;
+
+        let recent = profile_facts(&conn, ProfileFactOptions::default()).await;
+        assert_eq!(recent, vec!["low", "unscored", "high"]);
+
+        let confident = profile_facts(
+            &conn,
+            ProfileFactOptions {
+                sort: ProfileFactSort::Confidence,
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(confident, vec!["high", "low", "unscored"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_user_profile_min_confidence_keeps_or_excludes_unscored() {
+        let conn = setup_profile().await;
+
+        let kept = profile_facts(
+            &conn,
+            ProfileFactOptions {
+                min_confidence: Some(0.6),
+                sort: ProfileFactSort::Confidence,
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(kept, vec!["high", "unscored"]);
+
+        let excluded = profile_facts(
+            &conn,
+            ProfileFactOptions {
+                min_confidence: Some(0.6),
+                exclude_unscored: true,
+                sort: ProfileFactSort::Recency,
+            },
+        )
+        .await;
+        assert_eq!(excluded, vec!["high"]);
+
+        // Without a minimum, unscored facts are kept either way
+        let all = profile_facts(
+            &conn,
+            ProfileFactOptions {
+                exclude_unscored: true,
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(all.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_list_by_container_after_pages_in_creation_order() {
+        let conn = setup_test_db().await;
+
+        let start = Utc::now() - chrono::Duration::hours(1);
+        for (i, (id, tag)) in [
+            ("c", "user_1"),
+            ("a", "user_1"),
+            ("b", "user_1"),
+            ("x", "user_2"),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let mut mem = Memory::new(id.to_string(), format!("Memory {id}"), "s".to_string());
+            mem.container_tag = Some(tag.to_string());
+            // "a" and "b" share a timestamp, so they're ordered by id.
+            mem.created_at = start + chrono::Duration::minutes(i.min(1) as i64);
+            mem.is_forgotten = id == "b";
+            MemoryRepository::create(&conn, &mem).await.unwrap();
+        }
+
+        let first = MemoryRepository::list_by_container_after(&conn, "user_1", None, 2)
+            .await
+            .unwrap();
+        let ids: Vec<&str> = first.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["c", "a"]);
+
+        let last = first.last().unwrap();
+        let second = MemoryRepository::list_by_container_after(
+            &conn,
+            "user_1",
+            Some((last.created_at, last.id.as_str())),
+            2,
+        )
+        .await
+        .unwrap();
+        let ids: Vec<&str> = second.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["b"]);
+        assert!(second[0].is_forgotten);
+    }
+
+    #[tokio::test]
+    async fn test_get_embeddings_skips_unembedded_memories() {
+        let conn = libsql::Builder::new_local(":memory:")
+            .build()
+            .await
+            .unwrap()
+            .connect()
+            .unwrap();
+        crate::db::schema::init_schema(&conn).await.unwrap();
+
+        for id in ["embedded", "pending"] {
+            let memory = Memory::new(id.to_string(), "Some fact".to_string(), "s".to_string());
+            MemoryRepository::create(&conn, &memory).await.unwrap();
+        }
+        let embedding: Vec<f32> = (0..384).map(|i| i as f32 / 384.0).collect();
+        MemoryRepository::update_embedding(&conn, "embedded", &embedding)
+            .await
+            .unwrap();
+
+        let ids = vec![
+            "embedded".to_string(),
+            "pending".to_string(),
+            "missing".to_string(),
+        ];
+        let embeddings = MemoryRepository::get_embeddings(&conn, &ids).await.unwrap();
+        assert_eq!(embeddings.len(), 1);
+        assert_eq!(embeddings["embedded"], embedding);
+        assert!(MemoryRepository::get_embeddings(&conn, &[])
+            .await
+            .unwrap()
+            .is_empty());
+    }
 }
@@ -1,9 +1,29 @@
 mod chunks;
+mod contradictions;
 mod documents;
+mod jobs;
+mod keyword;
 mod memories;
 mod memory_sources;
+mod vector;
+mod webhooks;
+
+/// Ids bound per `IN (...)` lookup, below SQLite's default limit of 999
+/// variables per statement.
+const ID_BATCH_SIZE: usize = 500;
+
+/// `?1, ?2, ...` for `count` bound values.
+fn id_placeholders(count: usize) -> String {
+    (1..=count)
+        .map(|i| format!("?{i}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
 
 pub use chunks::ChunkRepository;
+pub use contradictions::ContradictionRepository;
 pub use documents::DocumentRepository;
+pub use jobs::JobRepository;
 pub use memories::MemoryRepository;
 pub use memory_sources::MemorySourcesRepository;
+pub use webhooks::WebhookRepository;
@@ -0,0 +1,20 @@
+/// Values of an `F32_BLOB` column, read from its little-endian bytes. Unlike
+/// `vector_extract`, which formats values as text, this is lossless.
+pub(super) fn decode_f32_blob(blob: &[u8]) -> Vec<f32> {
+    blob.chunks_exact(4)
+        .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_f32_blob_roundtrips_values() {
+        let values = [0.1f32, -2.5, 1.0 / 3.0];
+        let blob: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+        assert_eq!(decode_f32_blob(&blob), values);
+        assert!(decode_f32_blob(&[]).is_empty());
+    }
+}
@@ -0,0 +1,149 @@
+use chrono::{DateTime, Utc};
+use libsql::{params, Connection};
+
+use crate::error::Result;
+use crate::models::Webhook;
+
+pub struct WebhookRepository;
+
+const SELECT_COLUMNS: &str = "SELECT id, container_tag, url, secret, created_at FROM webhooks";
+
+impl WebhookRepository {
+    pub async fn create(conn: &Connection, webhook: &Webhook) -> Result<()> {
+        conn.execute(
+            "INSERT INTO webhooks (id, container_tag, url, secret, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                webhook.id.as_str(),
+                webhook.container_tag.as_str(),
+                webhook.url.as_str(),
+                webhook.secret.as_str(),
+                webhook.created_at.to_rfc3339(),
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Webhooks registered for any of `container_tags`, or every webhook when
+    /// `container_tags` is `None`. Oldest first.
+    pub async fn list(
+        conn: &Connection,
+        container_tags: Option<&[String]>,
+    ) -> Result<Vec<Webhook>> {
+        let mut rows = match container_tags {
+            Some([]) => return Ok(Vec::new()),
+            Some(tags) => {
+                let placeholders = (1..=tags.len())
+                    .map(|i| format!("?{i}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                conn.query(
+                    &format!(
+                        "{SELECT_COLUMNS} WHERE container_tag IN ({placeholders})
+                         ORDER BY created_at, id"
+                    ),
+                    libsql::params_from_iter(tags.to_vec()),
+                )
+                .await?
+            }
+            None => {
+                conn.query(&format!("{SELECT_COLUMNS} ORDER BY created_at, id"), ())
+                    .await?
+            }
+        };
+
+        let mut webhooks = Vec::new();
+        while let Some(row) = rows.next().await? {
+            webhooks.push(Self::row_to_webhook(&row)?);
+        }
+        Ok(webhooks)
+    }
+
+    pub async fn delete(conn: &Connection, id: &str) -> Result<bool> {
+        let rows_affected = conn
+            .execute("DELETE FROM webhooks WHERE id = ?1", params![id])
+            .await?;
+        Ok(rows_affected > 0)
+    }
+
+    fn row_to_webhook(row: &libsql::Row) -> Result<Webhook> {
+        let created_at: String = row.get(4)?;
+        Ok(Webhook {
+            id: row.get(0)?,
+            container_tag: row.get(1)?,
+            url: row.get(2)?,
+            secret: row.get(3)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_test_db() -> Connection {
+        let conn = libsql::Builder::new_local(":memory:")
+            .build()
+            .await
+            .unwrap()
+            .connect()
+            .unwrap();
+
+        conn.execute(
+            "CREATE TABLE webhooks (
+                id TEXT PRIMARY KEY,
+                container_tag TEXT NOT NULL,
+                url TEXT NOT NULL,
+                secret TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            )",
+            (),
+        )
+        .await
+        .unwrap();
+
+        conn
+    }
+
+    fn webhook(id: &str, tag: &str) -> Webhook {
+        Webhook {
+            id: id.to_string(),
+            container_tag: tag.to_string(),
+            url: format!("https://example.com/hooks/{id}"),
+            secret: "s3cret".to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_filters_by_container_tags() {
+        let conn = setup_test_db().await;
+        for (id, tag) in [("w1", "work"), ("w2", "home"), ("w3", "work")] {
+            WebhookRepository::create(&conn, &webhook(id, tag))
+                .await
+                .unwrap();
+        }
+
+        let ids = |hooks: Vec<Webhook>| hooks.into_iter().map(|w| w.id).collect::<Vec<_>>();
+        let work = WebhookRepository::list(&conn, Some(&["work".to_string()]))
+            .await
+            .unwrap();
+        assert_eq!(ids(work), vec!["w1", "w3"]);
+        assert_eq!(WebhookRepository::list(&conn, None).await.unwrap().len(), 3);
+        assert!(WebhookRepository::list(&conn, Some(&[]))
+            .await
+            .unwrap()
+            .is_empty());
+
+        assert!(WebhookRepository::delete(&conn, "w1").await.unwrap());
+        assert!(!WebhookRepository::delete(&conn, "w1").await.unwrap());
+        let work = WebhookRepository::list(&conn, Some(&["work".to_string()]))
+            .await
+            .unwrap();
+        assert_eq!(ids(work), vec!["w3"]);
+    }
+}
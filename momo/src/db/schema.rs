@@ -24,7 +24,8 @@ pub async fn init_schema(conn: &Connection) -> Result<()> {
             word_count INTEGER,
             error_message TEXT,
             created_at TEXT NOT NULL,
-            updated_at TEXT NOT NULL
+            updated_at TEXT NOT NULL,
+            retry_count INTEGER NOT NULL DEFAULT 0
         );
 
         CREATE INDEX IF NOT EXISTS idx_documents_custom_id ON documents(custom_id);
@@ -39,6 +40,8 @@ pub async fn init_schema(conn: &Connection) -> Result<()> {
             embedded_content TEXT,
             position INTEGER NOT NULL,
             token_count INTEGER,
+            chunker TEXT,
+            language TEXT,
             embedding F32_BLOB(384),
             created_at TEXT NOT NULL,
             FOREIGN KEY (document_id) REFERENCES documents(id) ON DELETE CASCADE
@@ -66,6 +69,7 @@ pub async fn init_schema(conn: &Connection) -> Result<()> {
             memory_type TEXT NOT NULL DEFAULT 'fact',
             last_accessed TEXT,
             confidence REAL,
+            importance REAL,
             metadata TEXT DEFAULT '{}',
             embedding F32_BLOB(384),
             created_at TEXT NOT NULL,
@@ -74,6 +78,16 @@ pub async fn init_schema(conn: &Connection) -> Result<()> {
             FOREIGN KEY (root_memory_id) REFERENCES memories(id)
         );
 
+        -- Embeddings from additional models, kept alongside the primary embedding
+        CREATE TABLE IF NOT EXISTS memory_embeddings (
+            memory_id TEXT NOT NULL,
+            model TEXT NOT NULL,
+            embedding F32_BLOB NOT NULL,
+            created_at TEXT NOT NULL,
+            PRIMARY KEY (memory_id, model),
+            FOREIGN KEY (memory_id) REFERENCES memories(id) ON DELETE CASCADE
+        );
+
         CREATE INDEX IF NOT EXISTS idx_memories_space_id ON memories(space_id);
         CREATE INDEX IF NOT EXISTS idx_memories_container_tag ON memories(container_tag);
         CREATE INDEX IF NOT EXISTS idx_memories_is_latest ON memories(is_latest);
@@ -99,6 +113,82 @@ pub async fn init_schema(conn: &Connection) -> Result<()> {
 
         CREATE INDEX IF NOT EXISTS idx_memory_sources_memory_id ON memory_sources(memory_id);
 
+        -- Contradictions flagged between memory pairs, awaiting review
+        CREATE TABLE IF NOT EXISTS memory_contradictions (
+            id TEXT PRIMARY KEY,
+            memory_id TEXT NOT NULL,
+            conflicting_memory_id TEXT NOT NULL,
+            container_tag TEXT,
+            reasoning TEXT,
+            status TEXT NOT NULL DEFAULT 'open',
+            resolution TEXT,
+            created_at TEXT NOT NULL,
+            resolved_at TEXT,
+            FOREIGN KEY (memory_id) REFERENCES memories(id) ON DELETE CASCADE,
+            FOREIGN KEY (conflicting_memory_id) REFERENCES memories(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_memory_contradictions_container_status
+            ON memory_contradictions(container_tag, status);
+
+        -- Embedding model each container's memories are searched with
+        CREATE TABLE IF NOT EXISTS container_embedding_models (
+            container_tag TEXT PRIMARY KEY,
+            model TEXT NOT NULL,
+            dimensions INTEGER NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        -- Per-container overrides of search settings; NULL uses the global value
+        CREATE TABLE IF NOT EXISTS container_settings (
+            container_tag TEXT PRIMARY KEY,
+            episode_decay_days REAL,
+            episode_decay_factor REAL,
+            updated_at TEXT NOT NULL
+        );
+
+        -- Endpoints notified when a container's documents finish processing
+        CREATE TABLE IF NOT EXISTS webhooks (
+            id TEXT PRIMARY KEY,
+            container_tag TEXT NOT NULL,
+            url TEXT NOT NULL,
+            secret TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_webhooks_container_tag ON webhooks(container_tag);
+
+        -- Documents created with an Idempotency-Key, per API key, until they expire
+        CREATE TABLE IF NOT EXISTS idempotency_keys (
+            api_key_id TEXT NOT NULL,
+            idempotency_key TEXT NOT NULL,
+            document_id TEXT NOT NULL,
+            near_duplicate_of TEXT,
+            expires_at TEXT NOT NULL,
+            PRIMARY KEY (api_key_id, idempotency_key)
+        );
+
+        -- Duration of recent document processing runs, pruned to the latest ones
+        CREATE TABLE IF NOT EXISTS processing_runs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            document_id TEXT NOT NULL,
+            status TEXT NOT NULL,
+            duration_ms INTEGER NOT NULL,
+            finished_at TEXT NOT NULL
+        );
+
+        -- Progress of long-running operations (batch imports, re-embedding, ...)
+        CREATE TABLE IF NOT EXISTS jobs (
+            id TEXT PRIMARY KEY,
+            kind TEXT NOT NULL,
+            status TEXT NOT NULL DEFAULT 'queued',
+            processed INTEGER NOT NULL DEFAULT 0,
+            total INTEGER,
+            result TEXT,
+            error TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
         -- Container tags metadata
         CREATE TABLE IF NOT EXISTS container_tags (
             tag TEXT PRIMARY KEY,
@@ -142,8 +232,12 @@ pub async fn init_schema(conn: &Connection) -> Result<()> {
     .await?;
 
     create_vector_indexes(conn).await?;
+    create_keyword_indexes(conn).await?;
     migrate_memory_type_column(conn).await?;
     migrate_container_tags_llm_filter(conn).await?;
+    migrate_chunks_chunker_column(conn).await?;
+    migrate_chunks_language_column(conn).await?;
+    migrate_documents_retry_count_column(conn).await?;
 
     Ok(())
 }
@@ -209,6 +303,25 @@ async fn migrate_memory_type_column(conn: &Connection) -> Result<()> {
         tracing::info!("Migration complete: confidence column added");
     }
 
+    // Check if importance column exists
+    let importance_exists: bool = conn
+        .query(
+            "SELECT COUNT(*) FROM pragma_table_info('memories') WHERE name='importance'",
+            (),
+        )
+        .await?
+        .next()
+        .await?
+        .map(|row| row.get::<i64>(0).unwrap_or(0) > 0)
+        .unwrap_or(false);
+
+    if !importance_exists {
+        tracing::info!("Migrating memories table: adding importance column");
+        conn.execute("ALTER TABLE memories ADD COLUMN importance REAL", ())
+            .await?;
+        tracing::info!("Migration complete: importance column added");
+    }
+
     // Ensure user_profiles table exists (for databases created before it was in the schema)
     conn.execute(
         r#"
@@ -272,6 +385,75 @@ async fn migrate_container_tags_llm_filter(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+async fn migrate_chunks_chunker_column(conn: &Connection) -> Result<()> {
+    let chunker_exists: bool = conn
+        .query(
+            "SELECT COUNT(*) FROM pragma_table_info('chunks') WHERE name='chunker'",
+            (),
+        )
+        .await?
+        .next()
+        .await?
+        .map(|row| row.get::<i64>(0).unwrap_or(0) > 0)
+        .unwrap_or(false);
+
+    if !chunker_exists {
+        tracing::info!("Migrating chunks table: adding chunker column");
+        conn.execute("ALTER TABLE chunks ADD COLUMN chunker TEXT", ())
+            .await?;
+        tracing::info!("Migration complete: chunker column added");
+    }
+
+    Ok(())
+}
+
+async fn migrate_chunks_language_column(conn: &Connection) -> Result<()> {
+    let language_exists: bool = conn
+        .query(
+            "SELECT COUNT(*) FROM pragma_table_info('chunks') WHERE name='language'",
+            (),
+        )
+        .await?
+        .next()
+        .await?
+        .map(|row| row.get::<i64>(0).unwrap_or(0) > 0)
+        .unwrap_or(false);
+
+    if !language_exists {
+        tracing::info!("Migrating chunks table: adding language column");
+        conn.execute("ALTER TABLE chunks ADD COLUMN language TEXT", ())
+            .await?;
+        tracing::info!("Migration complete: language column added");
+    }
+
+    Ok(())
+}
+
+async fn migrate_documents_retry_count_column(conn: &Connection) -> Result<()> {
+    let retry_count_exists: bool = conn
+        .query(
+            "SELECT COUNT(*) FROM pragma_table_info('documents') WHERE name='retry_count'",
+            (),
+        )
+        .await?
+        .next()
+        .await?
+        .map(|row| row.get::<i64>(0).unwrap_or(0) > 0)
+        .unwrap_or(false);
+
+    if !retry_count_exists {
+        tracing::info!("Migrating documents table: adding retry_count column");
+        conn.execute(
+            "ALTER TABLE documents ADD COLUMN retry_count INTEGER NOT NULL DEFAULT 0",
+            (),
+        )
+        .await?;
+        tracing::info!("Migration complete: retry_count column added");
+    }
+
+    Ok(())
+}
+
 async fn create_vector_indexes(conn: &Connection) -> Result<()> {
     let chunk_index_exists: bool = conn
         .query(
@@ -320,11 +502,113 @@ async fn create_vector_indexes(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+/// FTS5 indexes over chunk content and memory text for BM25 keyword search,
+/// kept in sync by triggers. Indexes created for an existing database are
+/// backfilled from the rows already stored.
+async fn create_keyword_indexes(conn: &Connection) -> Result<()> {
+    for (table, column) in [("chunks", "content"), ("memories", "memory")] {
+        let fts_table = format!("{table}_fts");
+        let fts_exists = conn
+            .query(
+                "SELECT 1 FROM sqlite_master WHERE type='table' AND name=?1",
+                [fts_table.as_str()],
+            )
+            .await?
+            .next()
+            .await?
+            .is_some();
+
+        conn.execute_batch(&format!(
+            r#"
+            CREATE VIRTUAL TABLE IF NOT EXISTS {fts_table}
+                USING fts5({column}, content='{table}', content_rowid='rowid');
+
+            CREATE TRIGGER IF NOT EXISTS {fts_table}_insert AFTER INSERT ON {table} BEGIN
+                INSERT INTO {fts_table}(rowid, {column}) VALUES (new.rowid, new.{column});
+            END;
+            CREATE TRIGGER IF NOT EXISTS {fts_table}_delete AFTER DELETE ON {table} BEGIN
+                INSERT INTO {fts_table}({fts_table}, rowid, {column})
+                    VALUES ('delete', old.rowid, old.{column});
+            END;
+            CREATE TRIGGER IF NOT EXISTS {fts_table}_update AFTER UPDATE OF {column} ON {table} BEGIN
+                INSERT INTO {fts_table}({fts_table}, rowid, {column})
+                    VALUES ('delete', old.rowid, old.{column});
+                INSERT INTO {fts_table}(rowid, {column}) VALUES (new.rowid, new.{column});
+            END;
+            "#
+        ))
+        .await?;
+
+        if !fts_exists {
+            tracing::info!("Building keyword index for {table}");
+            conn.execute(
+                &format!("INSERT INTO {fts_table}({fts_table}) VALUES ('rebuild')"),
+                (),
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use libsql::Builder;
 
+    #[tokio::test]
+    async fn test_keyword_index_follows_chunk_changes_and_backfills() {
+        let db = Builder::new_local(":memory:").build().await.unwrap();
+        let conn = db.connect().unwrap();
+        init_schema(&conn).await.unwrap();
+
+        let count_matches = |term: &'static str| {
+            let conn = conn.clone();
+            async move {
+                conn.query(
+                    "SELECT COUNT(*) FROM chunks_fts WHERE chunks_fts MATCH ?1",
+                    [term],
+                )
+                .await
+                .unwrap()
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .get::<i64>(0)
+                .unwrap()
+            }
+        };
+
+        conn.execute_batch(
+            r#"
+            INSERT INTO documents (id, created_at, updated_at) VALUES ('d1', 'now', 'now');
+            INSERT INTO chunks (id, document_id, content, position, created_at)
+                VALUES ('c1', 'd1', 'alpha beta', 0, 'now');
+            "#,
+        )
+        .await
+        .unwrap();
+        assert_eq!(count_matches("alpha").await, 1);
+
+        conn.execute("UPDATE chunks SET content = 'gamma' WHERE id = 'c1'", ())
+            .await
+            .unwrap();
+        assert_eq!(count_matches("alpha").await, 0);
+        assert_eq!(count_matches("gamma").await, 1);
+
+        // A database created before the index existed is backfilled on startup.
+        conn.execute("DROP TABLE chunks_fts", ()).await.unwrap();
+        init_schema(&conn).await.unwrap();
+        assert_eq!(count_matches("gamma").await, 1);
+
+        conn.execute("DELETE FROM chunks WHERE id = 'c1'", ())
+            .await
+            .unwrap();
+        assert_eq!(count_matches("gamma").await, 0);
+    }
+
     #[tokio::test]
     async fn test_container_tags_schema_with_llm_filter_fields() {
         let db = Builder::new_local(":memory:").build().await.unwrap();
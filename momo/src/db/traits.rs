@@ -1,13 +1,17 @@
 use std::collections::HashMap;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 
 use crate::error::Result;
 use crate::models::{
-    CachedProfile, Chunk, ChunkWithDocument, ContainerFilter, Document, DocumentSummary, GraphData,
-    GraphEdgeType, ListDocumentsRequest, Memory, MemoryRelationType, MemorySearchHit, MemorySource,
-    Pagination, ProcessingDocument, ProcessingStatus, UserProfile,
+    CachedProfile, Chunk, ChunkWithDocument, ChunkerType, ContainerEmbeddingModel, ContainerFilter,
+    ContainerSettings, ContradictionChange, ContradictionResolution, DeadLetterDocument, Document,
+    DocumentDeleteFilter, DocumentSummary, GraphData, GraphDirection, GraphEdgeType,
+    HighlightMarkers, Job, JobKind, ListDocumentsRequest, Memory, MemoryContradiction,
+    MemoryRelationType, MemorySearchHit, MemorySource, MemoryType, Pagination, ProcessingDocument,
+    ProcessingStats, ProcessingStatus, ProfileFactOptions, UserProfile, Webhook,
 };
 
 // ---------------------------------------------------------------------------
@@ -23,6 +27,25 @@ pub struct EpisodeDecayCandidate {
     pub created_at: String,
 }
 
+/// Signals used to compute a memory's importance score.
+#[derive(Debug, Clone)]
+pub struct MemoryImportanceCandidate {
+    pub id: String,
+    pub source_count: i32,
+    pub confidence: Option<f64>,
+    /// Number of entries in `memory_relations`.
+    pub relation_count: usize,
+    pub last_accessed: Option<String>,
+    pub created_at: String,
+}
+
+/// Document created under an Idempotency-Key, and what its creation reported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdempotentDocument {
+    pub document_id: String,
+    pub near_duplicate_of: Option<String>,
+}
+
 // ---------------------------------------------------------------------------
 // Individual store traits
 // ---------------------------------------------------------------------------
@@ -37,6 +60,18 @@ pub trait DocumentStore: Send + Sync {
     async fn update_document(&self, doc: &Document) -> Result<()>;
     async fn delete_document(&self, id: &str) -> Result<bool>;
     async fn delete_document_by_custom_id(&self, custom_id: &str) -> Result<bool>;
+    /// Delete all documents matching `filter` along with their chunks.
+    /// Returns the ids of the deleted documents.
+    async fn delete_documents_matching(&self, filter: &DocumentDeleteFilter)
+        -> Result<Vec<String>>;
+    /// A page of the documents tagged with `container_tag`, oldest first,
+    /// resuming after the `(created_at, id)` of the previous page's last one.
+    async fn list_container_documents_after(
+        &self,
+        container_tag: &str,
+        after: Option<(DateTime<Utc>, &str)>,
+        limit: usize,
+    ) -> Result<Vec<Document>>;
     async fn list_documents(
         &self,
         req: &ListDocumentsRequest,
@@ -49,6 +84,35 @@ pub trait DocumentStore: Send + Sync {
         error: Option<&str>,
     ) -> Result<()>;
     async fn queue_all_documents_for_reprocessing(&self) -> Result<u64>;
+    /// Requeue documents stuck mid-processing for longer than `older_than`.
+    async fn requeue_stale_documents(&self, older_than: Duration) -> Result<u64>;
+    /// Remember that processing document `id` took `duration_ms` and ended
+    /// in `status`.
+    async fn record_processing_run(
+        &self,
+        id: &str,
+        status: ProcessingStatus,
+        duration_ms: u64,
+    ) -> Result<()>;
+    async fn get_processing_stats(&self) -> Result<ProcessingStats>;
+    /// Count a failed processing attempt, dead-lettering the document after
+    /// `max_failures` failures. Returns the document's resulting status.
+    async fn record_document_failure(
+        &self,
+        id: &str,
+        error: &str,
+        max_failures: u32,
+    ) -> Result<Option<ProcessingStatus>>;
+    async fn list_dead_letter_documents(&self) -> Result<Vec<DeadLetterDocument>>;
+    /// Queue a dead-lettered document again; `false` if it isn't dead-lettered.
+    async fn requeue_dead_letter_document(&self, id: &str) -> Result<bool>;
+    /// Queue a `done` or `failed` document to be processed again, optionally
+    /// overriding its `extract_memories` flag; `false` if it's in any other state.
+    async fn queue_document_for_reprocess(
+        &self,
+        id: &str,
+        extract_memories: Option<bool>,
+    ) -> Result<bool>;
 }
 
 /// CRUD and vector-search operations for chunks.
@@ -56,13 +120,56 @@ pub trait DocumentStore: Send + Sync {
 pub trait ChunkStore: Send + Sync {
     async fn create_chunks_batch(&self, chunks: &[Chunk]) -> Result<()>;
     async fn update_chunk_embeddings_batch(&self, updates: &[(String, Vec<f32>)]) -> Result<()>;
+    /// Replace a chunk's content and mark its embedding stale (cleared) so it
+    /// gets re-embedded. Returns `None` if the document has no such chunk.
+    async fn update_chunk_content(
+        &self,
+        document_id: &str,
+        chunk_id: &str,
+        content: &str,
+    ) -> Result<Option<Chunk>>;
+    /// Chunks of processed documents whose embedding is stale or missing.
+    async fn get_chunks_missing_embeddings(&self, limit: usize) -> Result<Vec<Chunk>>;
     async fn delete_chunks_by_document_id(&self, document_id: &str) -> Result<()>;
+    /// Chunks of a document with positions in `start..=end`, in position order.
+    async fn get_chunks_by_position_range(
+        &self,
+        document_id: &str,
+        start: i32,
+        end: i32,
+    ) -> Result<Vec<Chunk>>;
+    /// Chunks of a document at any of `positions`, in position order.
+    async fn get_chunks_by_positions(
+        &self,
+        document_id: &str,
+        positions: &[i32],
+    ) -> Result<Vec<Chunk>>;
+    /// Chunks most similar to `embedding`, optionally restricted to documents
+    /// with one of `container_tags`, to chunks from one of `chunker_types`, and
+    /// to code chunks in one of `languages`.
     async fn search_similar_chunks(
         &self,
         embedding: &[f32],
         limit: u32,
         threshold: f32,
         container_tags: Option<&[String]>,
+        chunker_types: Option<&[ChunkerType]>,
+        languages: Option<&[String]>,
+    ) -> Result<Vec<ChunkWithDocument>>;
+    /// Stored embeddings of the given chunks, keyed by chunk id.
+    async fn get_chunk_embeddings(&self, ids: &[String]) -> Result<HashMap<String, Vec<f32>>>;
+    /// Chunks matching the terms of `query` by BM25 keyword score, best first.
+    /// Filters as [`Self::search_similar_chunks`].
+    /// BM25 keyword search over chunk content. With `highlight`, each hit
+    /// carries an FTS5 excerpt with the matched terms wrapped in its markers.
+    async fn search_keyword_chunks(
+        &self,
+        query: &str,
+        limit: u32,
+        container_tags: Option<&[String]>,
+        chunker_types: Option<&[ChunkerType]>,
+        languages: Option<&[String]>,
+        highlight: Option<&HighlightMarkers>,
     ) -> Result<Vec<ChunkWithDocument>>;
 
     /// Delete all chunks from the store.
@@ -74,14 +181,35 @@ pub trait ChunkStore: Send + Sync {
 pub trait MemoryStore: Send + Sync {
     async fn create_memory(&self, memory: &Memory) -> Result<()>;
     async fn get_memory_by_id(&self, id: &str) -> Result<Option<Memory>>;
+    /// Like `get_memory_by_id`, but also returns forgotten memories.
+    async fn get_memory_by_id_including_forgotten(&self, id: &str) -> Result<Option<Memory>>;
     async fn get_memories_by_ids(&self, ids: &[String]) -> Result<Vec<Memory>>;
+    /// Stored embedding of a memory, `None` until it has been embedded.
+    async fn get_memory_embedding(&self, id: &str) -> Result<Option<Vec<f32>>>;
+    /// Stored embeddings of the given memories, keyed by id.
+    async fn get_memory_embeddings(&self, ids: &[String]) -> Result<HashMap<String, Vec<f32>>>;
+    /// A page of a container's memories, oldest first and including forgotten
+    /// and superseded versions, resuming after the `(created_at, id)` of the
+    /// previous page's last one.
+    async fn list_container_memories_after(
+        &self,
+        container_tag: &str,
+        after: Option<(DateTime<Utc>, &str)>,
+        limit: usize,
+    ) -> Result<Vec<Memory>>;
     async fn get_memory_by_content(
         &self,
         content: &str,
         container_tag: &str,
     ) -> Result<Option<Memory>>;
     async fn update_memory_to_not_latest(&self, id: &str) -> Result<()>;
+    /// Mark a memory as superseded only if it is still the latest version of its
+    /// chain at `expected_version`. Returns `false` when that no longer holds.
+    async fn supersede_memory(&self, id: &str, expected_version: i32) -> Result<bool>;
     async fn forget_memory(&self, id: &str, reason: Option<&str>) -> Result<()>;
+    /// Clear a memory's forgotten flag, reason and expiry. Returns `false` when
+    /// no memory has this id.
+    async fn restore_memory(&self, id: &str) -> Result<bool>;
     async fn update_memory_last_accessed_batch(&self, ids: &[&str]) -> Result<u64>;
     async fn update_memory_source_count(&self, id: &str, new_count: i32) -> Result<()>;
     async fn update_memory_version_chain(
@@ -98,11 +226,65 @@ pub trait MemoryStore: Send + Sync {
         limit: u32,
         threshold: f32,
         container_tag: Option<&str>,
+        language: Option<&str>,
+        include_forgotten: bool,
+    ) -> Result<Vec<MemorySearchHit>>;
+    /// Memories matching the terms of `query` by BM25 keyword score, best first.
+    async fn search_keyword_memories(
+        &self,
+        query: &str,
+        limit: u32,
+        container_tag: Option<&str>,
+        language: Option<&str>,
+        include_forgotten: bool,
+    ) -> Result<Vec<MemorySearchHit>>;
+    /// Like `search_similar_memories`, but searches the embeddings stored for
+    /// `embedding_model` in the per-model side table.
+    #[allow(clippy::too_many_arguments)]
+    async fn search_similar_memories_by_model(
+        &self,
+        embedding_model: &str,
+        embedding: &[f32],
+        limit: u32,
+        threshold: f32,
+        container_tag: Option<&str>,
+        language: Option<&str>,
         include_forgotten: bool,
     ) -> Result<Vec<MemorySearchHit>>;
-    async fn get_memory_children(&self, parent_id: &str) -> Result<Vec<Memory>>;
-    async fn get_memory_parents(&self, root_id: &str) -> Result<Vec<Memory>>;
+    /// Store `(memory_id, embedding)` pairs produced by `model` in the side table.
+    async fn upsert_memory_model_embeddings(
+        &self,
+        model: &str,
+        embeddings: &[(String, Vec<f32>)],
+    ) -> Result<()>;
+    /// Active memories without an embedding from `model`, oldest first.
+    async fn get_memories_missing_model_embedding(
+        &self,
+        model: &str,
+        limit: usize,
+    ) -> Result<Vec<Memory>>;
+    /// The `per_parent` newest children of all `parent_ids` in one query,
+    /// newest version first per parent.
+    async fn get_memory_children_batch(
+        &self,
+        parent_ids: &[String],
+        per_parent: usize,
+    ) -> Result<Vec<Memory>>;
+    /// The `per_chain` newest superseded versions of all chains rooted at
+    /// `root_ids` in one query, oldest version first per chain.
+    async fn get_memory_parents_batch(
+        &self,
+        root_ids: &[String],
+        per_chain: usize,
+    ) -> Result<Vec<Memory>>;
     async fn get_forgetting_candidates(&self, before: DateTime<Utc>) -> Result<Vec<Memory>>;
+    /// Active, non-static episodes idle since `accessed_before` with at most
+    /// `max_source_count` sources that no active inference derives from.
+    async fn get_idle_episodes(
+        &self,
+        accessed_before: DateTime<Utc>,
+        max_source_count: i32,
+    ) -> Result<Vec<Memory>>;
     async fn get_seed_memories(&self, limit: usize) -> Result<Vec<Memory>>;
     async fn check_inference_exists(&self, source_ids: &[String]) -> Result<bool>;
     async fn get_user_profile(
@@ -110,6 +292,7 @@ pub trait MemoryStore: Send + Sync {
         container_tag: &str,
         include_dynamic: bool,
         limit: u32,
+        options: &ProfileFactOptions,
     ) -> Result<UserProfile>;
     async fn update_memory_relations(
         &self,
@@ -128,8 +311,18 @@ pub trait MemoryStore: Send + Sync {
         depth: u32,
         max_nodes: u32,
         relation_types: Option<&[GraphEdgeType]>,
+        memory_types: Option<&[MemoryType]>,
+        direction: GraphDirection,
     ) -> Result<GraphData>;
     async fn get_container_graph(&self, container_tag: &str, max_nodes: u32) -> Result<GraphData>;
+    /// Shortest chain of relations between two memories within `max_depth`
+    /// hops, as the memories in path order and the edges between them.
+    async fn find_memory_path(
+        &self,
+        from: &str,
+        to: &str,
+        max_depth: u32,
+    ) -> Result<Option<GraphData>>;
     async fn get_cached_profile(&self, container_tag: &str) -> Result<Option<CachedProfile>>;
     async fn upsert_cached_profile(
         &self,
@@ -147,6 +340,21 @@ pub trait MemoryStore: Send + Sync {
     /// Set `forget_after` on a memory (must not be forgotten or static).
     async fn set_memory_forget_after(&self, id: &str, forget_after: DateTime<Utc>) -> Result<u64>;
 
+    // -- Importance helpers -----------------------------------------------------
+
+    /// Return importance signals for all active (latest, not-forgotten) memories.
+    async fn get_memory_importance_candidates(&self) -> Result<Vec<MemoryImportanceCandidate>>;
+
+    /// Store computed importance scores as `(memory_id, importance)` pairs.
+    async fn update_memory_importance_batch(&self, updates: &[(String, f64)]) -> Result<()>;
+
+    /// Count active (latest, not-forgotten) memories.
+    async fn count_active_memories(&self) -> Result<u64>;
+
+    /// Return ids of the least important active, non-static memories, lowest first.
+    /// Memories without a score yet are returned last.
+    async fn get_least_important_memory_ids(&self, limit: usize) -> Result<Vec<String>>;
+
     // -- Profile refresh helpers -----------------------------------------------
 
     /// Return distinct `container_tag` values that have active (latest, not-forgotten) memories.
@@ -155,6 +363,36 @@ pub trait MemoryStore: Send + Sync {
     /// Return `MAX(updated_at)` for active memories with the given container_tag.
     async fn get_max_memory_updated_at(&self, container_tag: &str)
         -> Result<Option<DateTime<Utc>>>;
+
+    // -- Contradiction review ---------------------------------------------------
+
+    /// Record that `memory_id` contradicts `conflicting_memory_id`. An existing open
+    /// flag for the same pair is returned instead of creating a duplicate.
+    async fn flag_memory_contradiction(
+        &self,
+        memory_id: &str,
+        conflicting_memory_id: &str,
+        container_tag: Option<&str>,
+        reasoning: Option<&str>,
+    ) -> Result<MemoryContradiction>;
+    async fn get_memory_contradiction(&self, id: &str) -> Result<Option<MemoryContradiction>>;
+    /// A page of flagged contradictions, newest first, and the total matching.
+    async fn list_memory_contradictions(
+        &self,
+        container_tag: Option<&str>,
+        include_resolved: bool,
+        limit: u32,
+        offset: u32,
+    ) -> Result<(Vec<MemoryContradiction>, u64)>;
+
+    /// Mark an open contradiction resolved and apply `change` atomically.
+    /// Returns `false`, changing nothing, if it was not open.
+    async fn resolve_memory_contradiction(
+        &self,
+        id: &str,
+        resolution: ContradictionResolution,
+        change: &ContradictionChange,
+    ) -> Result<bool>;
 }
 
 /// CRUD operations for memory-to-source links.
@@ -174,6 +412,69 @@ pub trait MemorySourceStore: Send + Sync {
 pub trait MetadataStore: Send + Sync {
     async fn get_embedding_dimensions(&self) -> Result<Option<usize>>;
     async fn set_embedding_dimensions(&self, dims: usize) -> Result<()>;
+    async fn get_container_embedding_model(
+        &self,
+        container_tag: &str,
+    ) -> Result<Option<ContainerEmbeddingModel>>;
+    async fn set_container_embedding_model(&self, record: &ContainerEmbeddingModel) -> Result<()>;
+    /// Store `record` only if its container has no recorded model yet.
+    async fn record_container_embedding_model(
+        &self,
+        record: &ContainerEmbeddingModel,
+    ) -> Result<()>;
+    async fn list_container_embedding_models(&self) -> Result<Vec<ContainerEmbeddingModel>>;
+    async fn get_container_settings(
+        &self,
+        container_tag: &str,
+    ) -> Result<Option<ContainerSettings>>;
+    async fn set_container_settings(&self, settings: &ContainerSettings) -> Result<()>;
+    async fn create_webhook(&self, webhook: &Webhook) -> Result<()>;
+    /// Webhooks for any of `container_tags`, or all webhooks when `None`.
+    async fn list_webhooks(&self, container_tags: Option<&[String]>) -> Result<Vec<Webhook>>;
+    async fn delete_webhook(&self, id: &str) -> Result<bool>;
+    async fn get_idempotent_document(
+        &self,
+        api_key_id: &str,
+        idempotency_key: &str,
+    ) -> Result<Option<IdempotentDocument>>;
+    /// Atomically reserve an Idempotency-Key for `document`. Returns the entry
+    /// that holds the key, which belongs to an earlier request if it differs.
+    async fn claim_idempotency_key(
+        &self,
+        api_key_id: &str,
+        idempotency_key: &str,
+        document: &IdempotentDocument,
+        ttl: Duration,
+    ) -> Result<IdempotentDocument>;
+    async fn release_idempotency_key(
+        &self,
+        api_key_id: &str,
+        idempotency_key: &str,
+        document_id: &str,
+    ) -> Result<()>;
+    /// Take a named lease shared by every process using this database, for jobs
+    /// that must not run concurrently. Returns a token, or `None` if it is held.
+    async fn try_acquire_lease(&self, name: &str, ttl: Duration) -> Result<Option<String>>;
+    async fn release_lease(&self, name: &str, token: &str) -> Result<()>;
+    /// Most recent document or memory write, or `None` for an empty database.
+    async fn get_latest_write_at(&self) -> Result<Option<DateTime<Utc>>>;
+}
+
+/// Progress tracking for long-running operations.
+#[async_trait]
+pub trait JobStore: Send + Sync {
+    async fn create_job(&self, kind: JobKind, total: Option<u64>) -> Result<Job>;
+    async fn get_job(&self, id: &str) -> Result<Option<Job>>;
+    async fn start_job(&self, id: &str) -> Result<()>;
+    async fn update_job_progress(&self, id: &str, processed: u64) -> Result<()>;
+    /// Mark a job done with an operation-specific result, or failed when
+    /// `error` is set.
+    async fn finish_job(
+        &self,
+        id: &str,
+        result: Option<&serde_json::Value>,
+        error: Option<&str>,
+    ) -> Result<()>;
 }
 
 // ---------------------------------------------------------------------------
@@ -184,11 +485,17 @@ pub trait MetadataStore: Send + Sync {
 /// operations (initialization, sync).
 #[async_trait]
 pub trait DatabaseBackend:
-    DocumentStore + ChunkStore + MemoryStore + MemorySourceStore + MetadataStore
+    DocumentStore + ChunkStore + MemoryStore + MemorySourceStore + MetadataStore + JobStore
 {
     /// Sync with remote (e.g. Turso replication). No-op for local-only backends.
     async fn sync(&self) -> Result<()>;
 
+    /// Run a trivial query to confirm the database answers.
+    async fn ping(&self) -> Result<()>;
+
+    /// Whether the full-text indexes used for keyword search exist.
+    async fn keyword_indexes_ready(&self) -> Result<bool>;
+
     /// Get filter configuration for a container tag
     async fn get_container_filter(&self, tag: &str) -> Result<Option<ContainerFilter>>;
 }
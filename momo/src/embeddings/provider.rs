@@ -1,8 +1,11 @@
 use fastembed::{EmbeddingModel, InitOptions, TextEmbedding};
+use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 
 use crate::config::{parse_provider_model, EmbeddingsConfig};
 use crate::error::{MomoError, Result};
+use crate::models::ContainerEmbeddingModel;
+use crate::processing::TokenCounter;
 
 enum EmbeddingBackend {
     Local {
@@ -11,11 +14,45 @@ enum EmbeddingBackend {
         batch_size: usize,
         ingest_batch_size: usize,
         ingest_batch_pause_ms: u64,
+        pooling: PoolingStrategy,
+        max_passage_chars: usize,
     },
 }
 
+/// How the segment embeddings of an over-long passage are combined into one vector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum PoolingStrategy {
+    /// Element-wise average of the segment embeddings.
+    #[default]
+    Mean,
+    /// Element-wise maximum of the segment embeddings.
+    Max,
+    /// Embedding of the leading segment only, analogous to CLS pooling.
+    Cls,
+    /// Average weighted by each segment's length in characters.
+    Weighted,
+}
+
+impl FromStr for PoolingStrategy {
+    type Err = String;
+
+    fn from_str(raw: &str) -> std::result::Result<Self, Self::Err> {
+        match raw.trim().to_lowercase().as_str() {
+            "mean" => Ok(Self::Mean),
+            "max" => Ok(Self::Max),
+            "cls" | "first" => Ok(Self::Cls),
+            "weighted" | "length_weighted" => Ok(Self::Weighted),
+            other => Err(format!(
+                "Invalid pooling strategy '{other}'. Valid strategies: mean, max, cls, weighted"
+            )),
+        }
+    }
+}
+
 pub struct EmbeddingProvider {
     backend: EmbeddingBackend,
+    /// Model name as configured, the key container embedding records use.
+    model: String,
     dimensions: usize,
 }
 
@@ -33,6 +70,26 @@ impl EmbeddingProvider {
         Self::new_local(config, model_name)
     }
 
+    /// Build a provider for an additional local model. Unlike `new`, unknown model
+    /// names are rejected instead of falling back to the default model, and the
+    /// dimensions come from the model itself.
+    pub fn for_alternate_model(model: &str, batch_size: usize) -> Result<Self> {
+        let (_, model_name) = parse_provider_model(model);
+        let embedding_model = known_embedding_model(model_name)
+            .ok_or_else(|| MomoError::Embedding(format!("Unknown embedding model: {model}")))?;
+        let dimensions = TextEmbedding::get_model_info(&embedding_model)
+            .map_err(|e| MomoError::Embedding(e.to_string()))?
+            .dim;
+
+        Self::new(&EmbeddingsConfig {
+            model: model.to_string(),
+            dimensions,
+            batch_size,
+            query_cache_size: 0,
+            max_concurrent_batches: 4,
+        })
+    }
+
     fn new_local(config: &EmbeddingsConfig, model_name: &str) -> Result<Self> {
         let embedding_model = resolve_embedding_model(model_name);
 
@@ -46,6 +103,18 @@ impl EmbeddingProvider {
             .and_then(|raw| raw.parse::<u64>().ok())
             .unwrap_or(0);
 
+        let pooling = match std::env::var("EMBEDDING_POOLING") {
+            Ok(raw) => raw.parse::<PoolingStrategy>().unwrap_or_else(|error| {
+                tracing::warn!("{error}; falling back to mean pooling");
+                PoolingStrategy::Mean
+            }),
+            Err(_) => PoolingStrategy::Mean,
+        };
+        let max_passage_chars = std::env::var("EMBEDDING_MAX_PASSAGE_CHARS")
+            .ok()
+            .and_then(|raw| raw.parse::<usize>().ok())
+            .unwrap_or(0);
+
         let dual_model = std::env::var("EMBEDDING_DUAL_MODEL")
             .ok()
             .and_then(|raw| parse_bool(&raw))
@@ -65,7 +134,10 @@ impl EmbeddingProvider {
                 batch_size: config.batch_size,
                 ingest_batch_size,
                 ingest_batch_pause_ms,
+                pooling,
+                max_passage_chars,
             },
+            model: config.model.clone(),
             dimensions: config.dimensions,
         })
     }
@@ -74,6 +146,8 @@ impl EmbeddingProvider {
         self.embed_with_mode(texts, EmbeddingMode::Query).await
     }
 
+    #[tracing::instrument(name = "embeddings.embed", skip_all, fields(count = texts.len()))]
+
     async fn embed_with_mode(
         &self,
         texts: Vec<String>,
@@ -128,9 +202,37 @@ impl EmbeddingProvider {
         }
     }
 
-    pub async fn embed_passage(&self, passage: &str) -> Result<Vec<f32>> {
+    /// Embed several queries in one model call, in order.
+    pub async fn embed_queries(&self, queries: Vec<String>) -> Result<Vec<Vec<f32>>> {
         match &self.backend {
             EmbeddingBackend::Local { .. } => {
+                let prefixed = queries
+                    .iter()
+                    .map(|query| format!("query: {query}"))
+                    .collect();
+                self.embed(prefixed).await
+            }
+        }
+    }
+
+    pub async fn embed_passage(&self, passage: &str) -> Result<Vec<f32>> {
+        match &self.backend {
+            EmbeddingBackend::Local {
+                pooling,
+                max_passage_chars,
+                ..
+            } => {
+                let segments = split_passage(passage, *max_passage_chars);
+                if segments.len() > 1 {
+                    let weights: Vec<f32> =
+                        segments.iter().map(|s| s.chars().count() as f32).collect();
+                    let prefixed: Vec<String> =
+                        segments.iter().map(|s| format!("passage: {s}")).collect();
+                    let embedded = self.embed(prefixed).await?;
+                    return pool_embeddings(&embedded, &weights, *pooling)
+                        .ok_or_else(|| MomoError::Embedding("No embedding generated".to_string()));
+                }
+
                 // Local models use passage: prefix
                 let prefixed = format!("passage: {passage}");
                 self.embed_single(&prefixed).await
@@ -143,14 +245,28 @@ impl EmbeddingProvider {
             EmbeddingBackend::Local {
                 ingest_batch_size,
                 ingest_batch_pause_ms,
+                pooling,
+                max_passage_chars,
                 ..
             } => {
                 if passages.is_empty() {
                     return Ok(Vec::new());
                 }
 
-                let mut all_embeddings = Vec::with_capacity(passages.len());
-                for batch in passages.chunks(*ingest_batch_size) {
+                // Over-long passages are embedded as segments and pooled back
+                // into a single vector per passage afterwards.
+                let mut owners: Vec<usize> = Vec::with_capacity(passages.len());
+                let mut segments: Vec<String> = Vec::with_capacity(passages.len());
+                for (idx, passage) in passages.iter().enumerate() {
+                    for segment in split_passage(passage, *max_passage_chars) {
+                        owners.push(idx);
+                        segments.push(segment.to_string());
+                    }
+                }
+                let needs_pooling = segments.len() > passages.len();
+
+                let mut all_embeddings = Vec::with_capacity(segments.len());
+                for batch in segments.chunks(*ingest_batch_size) {
                     let prefixed: Vec<String> =
                         batch.iter().map(|p| format!("passage: {p}")).collect();
                     let mut embedded = self
@@ -166,7 +282,27 @@ impl EmbeddingProvider {
                     }
                 }
 
-                Ok(all_embeddings)
+                if !needs_pooling {
+                    return Ok(all_embeddings);
+                }
+
+                let mut grouped: Vec<(Vec<Vec<f32>>, Vec<f32>)> =
+                    vec![(Vec::new(), Vec::new()); passages.len()];
+                for ((owner, segment), embedding) in
+                    owners.into_iter().zip(segments.iter()).zip(all_embeddings)
+                {
+                    grouped[owner].0.push(embedding);
+                    grouped[owner].1.push(segment.chars().count() as f32);
+                }
+
+                grouped
+                    .into_iter()
+                    .map(|(embeddings, weights)| {
+                        pool_embeddings(&embeddings, &weights, *pooling).ok_or_else(|| {
+                            MomoError::Embedding("No embedding generated".to_string())
+                        })
+                    })
+                    .collect()
             }
         }
     }
@@ -174,6 +310,62 @@ impl EmbeddingProvider {
     pub fn dimensions(&self) -> usize {
         self.dimensions
     }
+
+    /// Record of this model having indexed `container_tag`.
+    pub fn container_model(&self, container_tag: &str) -> ContainerEmbeddingModel {
+        ContainerEmbeddingModel {
+            container_tag: container_tag.to_string(),
+            model: self.model.clone(),
+            dimensions: self.dimensions,
+        }
+    }
+
+    /// Token counter backed by the model's own tokenizer, for sizing chunks.
+    ///
+    /// Counts include special tokens and ignore the model's truncation limit, so
+    /// over-long text reports its real length. Returns `None` if the tokenizer
+    /// can't be prepared.
+    pub fn token_counter(&self) -> Option<Arc<dyn TokenCounter>> {
+        match &self.backend {
+            EmbeddingBackend::Local { ingest_model, .. } => {
+                let mut tokenizer = match ingest_model.lock() {
+                    Ok(model) => model.tokenizer.clone(),
+                    Err(error) => {
+                        tracing::warn!(error = %error, "Embedding model lock poisoned");
+                        return None;
+                    }
+                };
+                if let Err(error) = tokenizer.with_truncation(None) {
+                    tracing::warn!(error = %error, "Failed to disable tokenizer truncation");
+                    return None;
+                }
+                tokenizer.with_padding(None);
+
+                Some(Arc::new(move |text: &str| {
+                    tokenizer
+                        .encode(text, true)
+                        .map(|encoding| encoding.len())
+                        .unwrap_or_else(|_| text.len().div_ceil(4))
+                }))
+            }
+        }
+    }
+
+    /// Longest input, in tokens, the model embeds without truncating it.
+    ///
+    /// Read from the tokenizer's truncation settings; `None` if the model
+    /// doesn't declare a limit.
+    pub fn max_sequence_length(&self) -> Option<usize> {
+        match &self.backend {
+            EmbeddingBackend::Local { ingest_model, .. } => {
+                let model = ingest_model.lock().ok()?;
+                model
+                    .tokenizer
+                    .get_truncation()
+                    .map(|truncation| truncation.max_length)
+            }
+        }
+    }
 }
 
 impl Clone for EmbeddingProvider {
@@ -185,6 +377,8 @@ impl Clone for EmbeddingProvider {
                 batch_size,
                 ingest_batch_size,
                 ingest_batch_pause_ms,
+                pooling,
+                max_passage_chars,
             } => Self {
                 backend: EmbeddingBackend::Local {
                     query_model: Arc::clone(query_model),
@@ -192,7 +386,10 @@ impl Clone for EmbeddingProvider {
                     batch_size: *batch_size,
                     ingest_batch_size: *ingest_batch_size,
                     ingest_batch_pause_ms: *ingest_batch_pause_ms,
+                    pooling: *pooling,
+                    max_passage_chars: *max_passage_chars,
                 },
+                model: self.model.clone(),
                 dimensions: self.dimensions,
             },
         }
@@ -205,8 +402,106 @@ enum EmbeddingMode {
     Ingest,
 }
 
+/// Split `text` into segments of at most `max_chars` characters, preferring to
+/// break on whitespace. A limit of zero disables splitting.
+pub(crate) fn split_passage(text: &str, max_chars: usize) -> Vec<&str> {
+    if max_chars == 0 || text.chars().count() <= max_chars {
+        return vec![text];
+    }
+
+    let mut segments = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let limit = rest
+            .char_indices()
+            .nth(max_chars)
+            .map(|(idx, _)| idx)
+            .unwrap_or(rest.len());
+        let end = if limit < rest.len() {
+            rest[..limit]
+                .rfind(char::is_whitespace)
+                .filter(|idx| *idx > 0)
+                .unwrap_or(limit)
+        } else {
+            limit
+        };
+
+        let segment = rest[..end].trim();
+        if !segment.is_empty() {
+            segments.push(segment);
+        }
+        rest = rest[end..].trim_start();
+    }
+
+    if segments.is_empty() {
+        segments.push(text);
+    }
+    segments
+}
+
+/// Combine segment embeddings into a single L2-normalised vector.
+///
+/// `weights` is only used by [`PoolingStrategy::Weighted`]. Returns `None` when
+/// there is nothing to pool.
+pub(crate) fn pool_embeddings(
+    embeddings: &[Vec<f32>],
+    weights: &[f32],
+    strategy: PoolingStrategy,
+) -> Option<Vec<f32>> {
+    let first = embeddings.first()?;
+    if embeddings.len() == 1 {
+        return Some(first.clone());
+    }
+
+    let mut pooled = match strategy {
+        PoolingStrategy::Cls => first.clone(),
+        PoolingStrategy::Max => {
+            let mut pooled = first.clone();
+            for embedding in &embeddings[1..] {
+                for (value, other) in pooled.iter_mut().zip(embedding) {
+                    *value = value.max(*other);
+                }
+            }
+            pooled
+        }
+        PoolingStrategy::Mean | PoolingStrategy::Weighted => {
+            let mut pooled = vec![0.0f32; first.len()];
+            let mut total_weight = 0.0f32;
+            for (idx, embedding) in embeddings.iter().enumerate() {
+                let weight = match strategy {
+                    PoolingStrategy::Weighted => weights.get(idx).copied().unwrap_or(1.0),
+                    _ => 1.0,
+                };
+                total_weight += weight;
+                for (value, other) in pooled.iter_mut().zip(embedding) {
+                    *value += other * weight;
+                }
+            }
+            if total_weight > 0.0 {
+                for value in pooled.iter_mut() {
+                    *value /= total_weight;
+                }
+            }
+            pooled
+        }
+    };
+
+    let norm = pooled.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for value in pooled.iter_mut() {
+            *value /= norm;
+        }
+    }
+
+    Some(pooled)
+}
+
 fn resolve_embedding_model(model_name: &str) -> EmbeddingModel {
-    match model_name {
+    known_embedding_model(model_name).unwrap_or(EmbeddingModel::BGESmallENV15)
+}
+
+fn known_embedding_model(model_name: &str) -> Option<EmbeddingModel> {
+    let model = match model_name {
         "BAAI/bge-small-en-v1.5" | "bge-small-en-v1.5" => EmbeddingModel::BGESmallENV15,
         "BAAI/bge-base-en-v1.5" | "bge-base-en-v1.5" => EmbeddingModel::BGEBaseENV15,
         "BAAI/bge-large-en-v1.5" | "bge-large-en-v1.5" => EmbeddingModel::BGELargeENV15,
@@ -220,8 +515,9 @@ fn resolve_embedding_model(model_name: &str) -> EmbeddingModel {
         "nomic-embed-text-v1.5" | "nomic-ai/nomic-embed-text-v1.5" => {
             EmbeddingModel::NomicEmbedTextV15
         }
-        _ => EmbeddingModel::BGESmallENV15,
-    }
+        _ => return None,
+    };
+    Some(model)
 }
 
 fn build_model(embedding_model: EmbeddingModel) -> Result<TextEmbedding> {
@@ -93,6 +93,8 @@ impl RerankerProvider {
         self.backend.is_some()
     }
 
+    #[tracing::instrument(name = "reranker.rerank", skip_all, fields(count = documents.len()))]
+
     pub async fn rerank(
         &self,
         query: &str,
@@ -21,6 +21,7 @@ use wiremock::{Mock, MockServer, ResponseTemplate};
 use crate::config::parse_provider_model;
 use crate::db::MetadataRepository;
 use crate::embeddings::api::{ApiConfig, EmbeddingApiClient};
+use crate::embeddings::provider::{pool_embeddings, split_passage, PoolingStrategy};
 
 /// Helper to create a test ApiConfig pointing to a mock server
 fn test_config(base_url: &str) -> ApiConfig {
@@ -707,6 +708,65 @@ async fn test_metadata_multiple_keys() {
     );
 }
 
+// =============================================================================
+// Test 10: Passage Splitting and Pooling
+// =============================================================================
+
+#[test]
+fn test_split_passage_disabled_or_short_returns_whole_text() {
+    assert_eq!(split_passage("a long passage", 0), vec!["a long passage"]);
+    assert_eq!(split_passage("short", 100), vec!["short"]);
+}
+
+#[test]
+fn test_split_passage_breaks_on_whitespace() {
+    let segments = split_passage("alpha beta gamma delta", 11);
+    assert_eq!(segments, vec!["alpha beta", "gamma delta"]);
+    assert!(segments.iter().all(|s| s.chars().count() <= 11));
+}
+
+#[test]
+fn test_split_passage_handles_multibyte_without_whitespace() {
+    let segments = split_passage("ééééé", 2);
+    assert_eq!(segments, vec!["éé", "éé", "é"]);
+}
+
+#[test]
+fn test_pooling_strategy_parsing() {
+    assert_eq!("mean".parse::<PoolingStrategy>(), Ok(PoolingStrategy::Mean));
+    assert_eq!("MAX".parse::<PoolingStrategy>(), Ok(PoolingStrategy::Max));
+    assert_eq!("first".parse::<PoolingStrategy>(), Ok(PoolingStrategy::Cls));
+    assert_eq!(
+        "weighted".parse::<PoolingStrategy>(),
+        Ok(PoolingStrategy::Weighted)
+    );
+    assert!("median".parse::<PoolingStrategy>().is_err());
+    assert_eq!(PoolingStrategy::default(), PoolingStrategy::Mean);
+}
+
+#[test]
+fn test_pool_embeddings_strategies() {
+    let embeddings = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+    let weights = vec![3.0, 1.0];
+
+    let mean = pool_embeddings(&embeddings, &weights, PoolingStrategy::Mean).unwrap();
+    assert!((mean[0] - mean[1]).abs() < 1e-6);
+    assert!((mean[0] - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-6);
+
+    let max = pool_embeddings(&embeddings, &weights, PoolingStrategy::Max).unwrap();
+    assert!((max[0] - max[1]).abs() < 1e-6);
+
+    let cls = pool_embeddings(&embeddings, &weights, PoolingStrategy::Cls).unwrap();
+    assert_eq!(cls, vec![1.0, 0.0]);
+
+    let weighted = pool_embeddings(&embeddings, &weights, PoolingStrategy::Weighted).unwrap();
+    assert!(weighted[0] > weighted[1]);
+    let norm = weighted.iter().map(|v| v * v).sum::<f32>().sqrt();
+    assert!((norm - 1.0).abs() < 1e-6);
+
+    assert!(pool_embeddings(&[], &[], PoolingStrategy::Mean).is_none());
+}
+
 // =============================================================================
 // Additional Edge Case Tests
 // =============================================================================
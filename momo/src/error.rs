@@ -17,6 +17,9 @@ pub enum MomoError {
     #[error("Validation error: {0}")]
     Validation(String),
 
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
     #[error("Embedding error: {0}")]
     Embedding(String),
 
@@ -67,15 +70,39 @@ pub enum MomoError {
     #[error("LLM rate limit exceeded, retry after {retry_after:?} seconds")]
     LlmRateLimit { retry_after: Option<u64> },
 
+    #[error("LLM temporarily unavailable: {0}")]
+    LlmTransient(String),
+
     #[error("Reranker error: {0}")]
     Reranker(String),
 }
 
+impl MomoError {
+    /// Whether the failure is transient (timeout, connection error, 5xx or
+    /// rate limit) and the operation may succeed if retried.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            MomoError::Http(e) => {
+                e.is_timeout()
+                    || e.is_connect()
+                    || e.status().is_some_and(|status| {
+                        status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+                    })
+            }
+            MomoError::ApiRateLimit { .. }
+            | MomoError::LlmRateLimit { .. }
+            | MomoError::LlmTransient(_) => true,
+            _ => false,
+        }
+    }
+}
+
 impl IntoResponse for MomoError {
     fn into_response(self) -> Response {
         let (status, message) = match &self {
             MomoError::NotFound(msg) => (StatusCode::NOT_FOUND, msg.clone()),
             MomoError::Validation(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
+            MomoError::Conflict(msg) => (StatusCode::CONFLICT, msg.clone()),
             MomoError::Database(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
             MomoError::Embedding(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg.clone()),
             MomoError::Processing(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg.clone()),
@@ -94,6 +121,7 @@ impl IntoResponse for MomoError {
             }
             MomoError::Llm(msg) => (StatusCode::BAD_GATEWAY, msg.clone()),
             MomoError::LlmUnavailable(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg.clone()),
+            MomoError::LlmTransient(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg.clone()),
             MomoError::LlmRateLimit { retry_after } => (
                 StatusCode::TOO_MANY_REQUESTS,
                 format!("LLM rate limit exceeded, retry after {retry_after:?} seconds"),
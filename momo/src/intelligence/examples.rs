@@ -0,0 +1,152 @@
+//! User-supplied few-shot examples for memory extraction.
+//!
+//! Examples are loaded from the JSON file named by `EXTRACTION_EXAMPLES_PATH`, validated
+//! once at startup and injected into the extraction prompts by `MemoryExtractor`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::ExtractionConfig;
+use crate::error::{MomoError, Result};
+use crate::llm::prompts;
+use crate::models::MemoryType;
+
+/// One example pairing input text with the memories that should be extracted from it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtractionExample {
+    /// Example source text
+    pub input: String,
+    /// Memories the LLM is expected to extract from `input`. May be empty to show
+    /// content that should yield nothing.
+    #[serde(default)]
+    pub memories: Vec<ExampleMemory>,
+}
+
+/// An expected memory in an [`ExtractionExample`], in the extraction output format.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExampleMemory {
+    pub content: String,
+    pub memory_type: String,
+    #[serde(default = "default_example_confidence")]
+    pub confidence: f32,
+}
+
+fn default_example_confidence() -> f32 {
+    0.9
+}
+
+/// Load and validate the configured examples. Returns an empty list when no file is set.
+pub fn load_extraction_examples(config: &ExtractionConfig) -> Result<Vec<ExtractionExample>> {
+    let Some(path) = config.examples_path.as_deref() else {
+        return Ok(Vec::new());
+    };
+
+    let raw = std::fs::read_to_string(path).map_err(|e| {
+        MomoError::Validation(format!("Failed to read extraction examples '{path}': {e}"))
+    })?;
+    let examples: Vec<ExtractionExample> = serde_json::from_str(&raw)
+        .map_err(|e| MomoError::Validation(format!("Invalid extraction examples '{path}': {e}")))?;
+
+    validate_extraction_examples(&examples, config.examples_max_tokens)?;
+    Ok(examples)
+}
+
+/// Check example contents and that the rendered examples fit within `max_tokens`
+/// (estimated at ~4 characters per token).
+pub fn validate_extraction_examples(
+    examples: &[ExtractionExample],
+    max_tokens: usize,
+) -> Result<()> {
+    for (index, example) in examples.iter().enumerate() {
+        let position = index + 1;
+        if example.input.trim().is_empty() {
+            return Err(MomoError::Validation(format!(
+                "Extraction example {position} has empty input"
+            )));
+        }
+
+        for memory in &example.memories {
+            if memory.content.trim().is_empty() {
+                return Err(MomoError::Validation(format!(
+                    "Extraction example {position} has a memory with empty content"
+                )));
+            }
+            if memory.memory_type.parse::<MemoryType>().is_err() {
+                return Err(MomoError::Validation(format!(
+                    "Extraction example {position} has unknown memory_type '{}' (expected fact, preference or episode)",
+                    memory.memory_type
+                )));
+            }
+            if !(0.0..=1.0).contains(&memory.confidence) {
+                return Err(MomoError::Validation(format!(
+                    "Extraction example {position} has confidence {} outside 0.0-1.0",
+                    memory.confidence
+                )));
+            }
+        }
+    }
+
+    let estimated_tokens = prompts::extraction_examples_section(examples)
+        .len()
+        .div_ceil(4);
+    if estimated_tokens > max_tokens {
+        return Err(MomoError::Validation(format!(
+            "Extraction examples use ~{estimated_tokens} tokens, exceeding EXTRACTION_EXAMPLES_MAX_TOKENS ({max_tokens})"
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example(input: &str, memory_type: &str, confidence: f32) -> ExtractionExample {
+        ExtractionExample {
+            input: input.to_string(),
+            memories: vec![ExampleMemory {
+                content: "Client signed the NDA on 2024-03-01".to_string(),
+                memory_type: memory_type.to_string(),
+                confidence,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_parses_examples_with_default_confidence() {
+        let examples: Vec<ExtractionExample> = serde_json::from_str(
+            r#"[{"input": "Met Dr. Lee today.", "memories": [{"content": "User met Dr. Lee", "memory_type": "episode"}]}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].memories[0].confidence, 0.9);
+        assert!(validate_extraction_examples(&examples, 1000).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_invalid_examples() {
+        assert!(validate_extraction_examples(&[example("  ", "fact", 0.9)], 1000).is_err());
+        assert!(validate_extraction_examples(&[example("text", "opinion", 0.9)], 1000).is_err());
+        assert!(validate_extraction_examples(&[example("text", "fact", 1.5)], 1000).is_err());
+    }
+
+    #[test]
+    fn test_rejects_examples_over_token_budget() {
+        let examples = vec![example(&"clause ".repeat(200), "fact", 0.9)];
+
+        assert!(validate_extraction_examples(&examples, 1000).is_ok());
+        let err = validate_extraction_examples(&examples, 100).unwrap_err();
+        assert!(err.to_string().contains("EXTRACTION_EXAMPLES_MAX_TOKENS"));
+    }
+
+    #[test]
+    fn test_load_without_path_returns_empty() {
+        let config = ExtractionConfig {
+            examples_path: None,
+            examples_max_tokens: 1000,
+            examples: Vec::new(),
+        };
+        assert!(load_extraction_examples(&config).unwrap().is_empty());
+    }
+}
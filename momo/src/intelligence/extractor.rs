@@ -1,9 +1,13 @@
+use std::sync::Arc;
+
 use crate::db::DatabaseBackend;
 use crate::embeddings::EmbeddingProvider;
 use crate::error::{MomoError, Result};
 use crate::intelligence::contradiction::{ContradictionCheckResult, ContradictionDetector};
+use crate::intelligence::examples::ExtractionExample;
 use crate::llm::{prompts, LlmProvider};
 use crate::models::ConversationMessage;
+use crate::processing::RetryPolicy;
 
 use super::types::{ExtractedMemory, ExtractionResult};
 
@@ -16,6 +20,8 @@ struct MemoriesWrapper {
 pub struct MemoryExtractor {
     llm: LlmProvider,
     embeddings: EmbeddingProvider,
+    examples: Arc<[ExtractionExample]>,
+    retry: RetryPolicy,
 }
 
 impl Clone for MemoryExtractor {
@@ -23,13 +29,32 @@ impl Clone for MemoryExtractor {
         Self {
             llm: self.llm.clone(),
             embeddings: self.embeddings.clone(),
+            examples: Arc::clone(&self.examples),
+            retry: self.retry,
         }
     }
 }
 
 impl MemoryExtractor {
     pub fn new(llm: LlmProvider, embeddings: EmbeddingProvider) -> Self {
-        Self { llm, embeddings }
+        Self {
+            llm,
+            embeddings,
+            examples: Arc::from(Vec::new()),
+            retry: RetryPolicy::none(),
+        }
+    }
+
+    /// Inject few-shot examples into every extraction prompt.
+    pub fn with_examples(mut self, examples: Vec<ExtractionExample>) -> Self {
+        self.examples = Arc::from(examples);
+        self
+    }
+
+    /// Retry transient LLM failures before falling back to an empty result.
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
     }
 
     pub async fn extract(&self, content: &str) -> Result<ExtractionResult> {
@@ -43,10 +68,12 @@ impl MemoryExtractor {
             return Ok(empty_result(content.to_string()));
         }
 
-        let prompt = prompts::memory_extraction_prompt(content);
+        let prompt = prompts::memory_extraction_prompt(content, &self.examples);
         match self
-            .llm
-            .complete_structured::<MemoriesWrapper>(&prompt)
+            .retry
+            .run("memory_extraction", || {
+                self.llm.complete_structured::<MemoriesWrapper>(&prompt)
+            })
             .await
         {
             Ok(wrapper) => Ok(ExtractionResult {
@@ -82,7 +109,7 @@ impl MemoryExtractor {
             return Ok(empty_result(source_content));
         }
 
-        let prompt = prompts::conversation_extraction_prompt(messages);
+        let prompt = prompts::conversation_extraction_prompt(messages, &self.examples);
         match self
             .llm
             .complete_structured::<MemoriesWrapper>(&prompt)
@@ -107,7 +134,8 @@ impl MemoryExtractor {
     ///
     /// For each memory, embeds it, searches for similar existing memories in the same container,
     /// and runs the heuristic `ContradictionDetector` against each match. If a `Likely`
-    /// contradiction is found, sets `potential_contradiction = true` on the memory.
+    /// contradiction is found, sets `potential_contradiction = true` on the memory and records
+    /// the conflicting memory in `contradicted_memory_id`.
     ///
     /// This method never blocks memory creation — it only flags.
     pub async fn check_contradictions(
@@ -128,13 +156,14 @@ impl MemoryExtractor {
             };
 
             let similar = db
-                .search_similar_memories(&embedding, 5, 0.6, Some(container_tag), false)
+                .search_similar_memories(&embedding, 5, 0.6, Some(container_tag), None, false)
                 .await?;
 
             for hit in &similar {
                 let result = detector.check_contradiction(&hit.memory.memory, &memory.content);
                 if result == ContradictionCheckResult::Likely {
                     memory.potential_contradiction = true;
+                    memory.contradicted_memory_id = Some(hit.memory.id.clone());
                     tracing::info!(
                         new_memory = %memory.content,
                         existing_memory_id = %hit.memory.id,
@@ -172,6 +201,7 @@ impl MemoryExtractor {
                     1,
                     0.9,
                     Some(container_tag),
+                    None,
                     false, // exclude forgotten memories for deduplication
                 )
                 .await?;
@@ -219,6 +249,8 @@ mod tests {
             model: "BAAI/bge-small-en-v1.5".to_string(),
             dimensions: 384,
             batch_size: 8,
+            query_cache_size: 0,
+            max_concurrent_batches: 4,
         };
 
         EmbeddingProvider::new(&config).expect("failed to create test embeddings provider")
@@ -241,6 +273,8 @@ mod tests {
             enable_auto_relations: false,
             enable_contradiction_detection: false,
             filter_prompt: None,
+            max_calls_per_run: None,
+            max_tokens_per_run: None,
         };
 
         LlmProvider::new(Some(&config))
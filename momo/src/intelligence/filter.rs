@@ -1,6 +1,7 @@
 use crate::config::Config;
 use crate::error::{MomoError, Result};
 use crate::llm::{prompts, LlmProvider};
+use crate::processing::RetryPolicy;
 use serde::Deserialize;
 
 /// Decision about whether to include or skip a piece of content
@@ -94,9 +95,10 @@ impl LlmFilter {
         }
 
         let prompt = prompts::llm_filter_prompt(content, filter_prompt);
-        match self
-            .llm
-            .complete_structured::<FilterResponse>(&prompt)
+        match RetryPolicy::from_config(&self.config.processing)
+            .run("llm_filter", || {
+                self.llm.complete_structured::<FilterResponse>(&prompt)
+            })
             .await
         {
             Ok(response) => {
@@ -155,8 +157,12 @@ impl LlmFilter {
 mod tests {
     use super::*;
     use crate::config::{
-        DatabaseConfig, EmbeddingsConfig, InferenceConfig, McpConfig, MemoryConfig, OcrConfig,
-        ProcessingConfig, ServerConfig, TranscriptionConfig,
+        AlternateEmbeddingsConfig, DatabaseConfig, EmbeddingsConfig, ExtractionConfig,
+        IdleForgettingConfig, ImportanceConfig, InferenceConfig, McpConfig, MemoryConfig,
+        OcrConfig, ProcessingConfig, SearchConfig, ServerConfig, TranscriptionConfig,
+    };
+    use crate::models::{
+        ChunkStrategy, NearDuplicateAction, PiiAction, PiiCategory, StructuredDataOverflow,
     };
 
     fn test_config() -> Config {
@@ -165,21 +171,50 @@ mod tests {
                 host: "0.0.0.0".to_string(),
                 port: 3000,
                 api_keys: vec![],
+                api_key_container_tags: std::collections::HashMap::new(),
+                idempotency_key_ttl_secs: 86400,
+                document_events_idle_timeout_secs: 300,
+                max_request_body_bytes: 52428800,
             },
             mcp: McpConfig::default(),
             database: DatabaseConfig {
                 url: "file:test.db".to_string(),
                 auth_token: None,
                 local_path: None,
+                max_connections: 4,
             },
             embeddings: EmbeddingsConfig {
                 model: "BAAI/bge-small-en-v1.5".to_string(),
                 dimensions: 384,
                 batch_size: 8,
+                query_cache_size: 0,
+                max_concurrent_batches: 4,
             },
             processing: ProcessingConfig {
                 chunk_size: 512,
                 chunk_overlap: 50,
+                chunk_strategy: ChunkStrategy::Structural,
+                semantic_chunk_threshold: 0.3,
+                semantic_chunk_min_size: 128,
+                use_model_tokenizer: false,
+                auto_summary: false,
+                auto_summary_min_chars: 2000,
+                contextual_chunks: false,
+                near_duplicate_threshold: None,
+                near_duplicate_action: NearDuplicateAction::Warn,
+                record_stage_timings: false,
+                low_confidence_threshold: None,
+                chunk_reembed_interval_secs: 0,
+                chunk_reembed_batch_size: 64,
+                categorize_documents: false,
+                structured_data_max_chunks: 0,
+                structured_data_overflow: StructuredDataOverflow::Sample,
+                pii_detection: false,
+                pii_categories: PiiCategory::ALL.to_vec(),
+                pii_action: PiiAction::Redact,
+                retry_max_attempts: 1,
+                retry_base_delay_ms: 0,
+                dead_letter_after_failures: 5,
             },
             memory: MemoryConfig {
                 episode_decay_days: 30.0,
@@ -187,7 +222,10 @@ mod tests {
                 episode_decay_threshold: 0.3,
                 episode_forget_grace_days: 7,
                 forgetting_check_interval_secs: 3600,
+                forgetting_dry_run: false,
                 profile_refresh_interval_secs: 86400,
+                profile_exclude_unscored_facts: false,
+                language_detection: false,
                 inference: InferenceConfig {
                     enabled: false,
                     interval_secs: 86400,
@@ -197,6 +235,9 @@ mod tests {
                     seed_limit: 100,
                     exclude_episodes: false,
                 },
+                importance: ImportanceConfig::default(),
+                alternate_embeddings: AlternateEmbeddingsConfig::default(),
+                idle_forgetting: IdleForgettingConfig::default(),
             },
             ocr: OcrConfig {
                 model: "local/tesseract".to_string(),
@@ -206,6 +247,10 @@ mod tests {
                 timeout_secs: 60,
                 max_image_dimension: 4096,
                 min_image_dimension: 50,
+                max_concurrent_jobs: 2,
+                include_layout: false,
+                min_confidence: None,
+                min_document_confidence: None,
             },
             transcription: TranscriptionConfig {
                 model: "local/whisper".to_string(),
@@ -215,9 +260,18 @@ mod tests {
                 timeout_secs: 300,
                 max_file_size: 52428800,
                 max_duration_secs: 3600,
+                max_concurrent_jobs: 1,
+                upload_dir: None,
+                include_segments: false,
+                chunk_by_segment: false,
+                language: None,
+                default_language: "en".to_string(),
+                language_min_confidence: 0.5,
             },
             llm: None,
             reranker: None,
+            search: SearchConfig::default(),
+            extraction: ExtractionConfig::default(),
         }
     }
 
@@ -285,6 +339,8 @@ mod tests {
             enable_contradiction_detection: false,
 
             filter_prompt: Some("technical".to_string()),
+            max_calls_per_run: None,
+            max_tokens_per_run: None,
         });
         let llm = test_llm_unavailable();
         let filter = LlmFilter::new(llm, config);
@@ -390,6 +446,8 @@ mod tests {
             enable_contradiction_detection: false,
 
             filter_prompt: Some("technical documents only".to_string()),
+            max_calls_per_run: None,
+            max_tokens_per_run: None,
         });
 
         let llm = LlmProvider::new(config.llm.as_ref());
@@ -451,6 +509,8 @@ mod tests {
             enable_contradiction_detection: false,
 
             filter_prompt: Some("technical documents only".to_string()),
+            max_calls_per_run: None,
+            max_tokens_per_run: None,
         });
 
         let llm = LlmProvider::new(config.llm.as_ref());
@@ -506,6 +566,8 @@ mod tests {
             enable_contradiction_detection: false,
 
             filter_prompt: Some("technical documents only".to_string()),
+            max_calls_per_run: None,
+            max_tokens_per_run: None,
         });
 
         let llm = LlmProvider::new(config.llm.as_ref());
@@ -520,6 +582,72 @@ mod tests {
         assert!(result.reasoning.is_none());
     }
 
+    #[tokio::test]
+    async fn test_filter_retries_transient_llm_errors() {
+        use crate::config::LlmConfig;
+        use serde_json::json;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let llm_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(503).set_body_string("Service Unavailable"))
+            .up_to_n_times(2)
+            .mount(&llm_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "test",
+                "object": "chat.completion",
+                "created": 1,
+                "model": "gpt-4o-mini",
+                "choices": [{
+                    "index": 0,
+                    "message": {
+                        "role": "assistant",
+                        "content": r#"{"decision":"skip","reasoning":"Not technical"}"#
+                    },
+                    "finish_reason": "stop"
+                }],
+                "usage": {"prompt_tokens": 10, "completion_tokens": 20, "total_tokens": 30}
+            })))
+            .mount(&llm_server)
+            .await;
+
+        let mut config = test_config();
+        config.processing.retry_max_attempts = 3;
+        config.processing.retry_base_delay_ms = 10;
+        config.llm = Some(LlmConfig {
+            model: "openai/gpt-4o-mini".to_string(),
+            api_key: Some("test-key".to_string()),
+            base_url: Some(llm_server.uri()),
+            timeout_secs: 1,
+            max_retries: 0,
+            enable_query_rewrite: false,
+            query_rewrite_cache_size: 1000,
+            query_rewrite_timeout_secs: 2,
+            enable_auto_relations: false,
+            enable_contradiction_detection: false,
+
+            filter_prompt: Some("technical documents only".to_string()),
+            max_calls_per_run: None,
+            max_tokens_per_run: None,
+        });
+
+        let llm = LlmProvider::new(config.llm.as_ref());
+        let filter = LlmFilter::new(llm, config);
+
+        let result = filter
+            .filter_content("Any content", "user_123", "doc_123", None)
+            .await
+            .expect("filter should work");
+
+        assert_eq!(result.decision, FilterDecision::Skip);
+        assert_eq!(llm_server.received_requests().await.unwrap().len(), 3);
+    }
+
     #[tokio::test]
     async fn test_filter_with_invalid_llm_decision_defaults_to_include() {
         use crate::config::LlmConfig;
@@ -562,6 +690,8 @@ mod tests {
             enable_contradiction_detection: false,
 
             filter_prompt: Some("technical documents only".to_string()),
+            max_calls_per_run: None,
+            max_tokens_per_run: None,
         });
 
         let llm = LlmProvider::new(config.llm.as_ref());
@@ -593,6 +723,8 @@ mod tests {
             enable_contradiction_detection: false,
 
             filter_prompt: Some("global prompt".to_string()),
+            max_calls_per_run: None,
+            max_tokens_per_run: None,
         });
         let llm = test_llm_unavailable();
         let filter = LlmFilter::new(llm, config);
@@ -0,0 +1,170 @@
+use chrono::{DateTime, Utc};
+
+use crate::config::ImportanceConfig;
+use crate::db::MemoryImportanceCandidate;
+
+/// Scores how important a memory is to keep, from access recency, number of
+/// sources, extraction confidence and relation degree.
+#[derive(Debug, Clone)]
+pub struct ImportanceScorer {
+    config: ImportanceConfig,
+}
+
+impl ImportanceScorer {
+    pub fn new(config: ImportanceConfig) -> Self {
+        Self { config }
+    }
+
+    /// Weighted average of the individual signals, in `[0, 1]`.
+    ///
+    /// Recency halves every `recency_half_life_days` since the memory was last
+    /// accessed (or created). Source and relation counts saturate as `n / (n + 1)`.
+    /// Missing confidence counts as 0.5.
+    pub fn score(&self, candidate: &MemoryImportanceCandidate, now: DateTime<Utc>) -> f64 {
+        let last_seen = candidate
+            .last_accessed
+            .as_deref()
+            .and_then(parse_timestamp)
+            .or_else(|| parse_timestamp(&candidate.created_at))
+            .unwrap_or(now);
+        let age_days = ((now - last_seen).num_seconds() as f64 / 86_400.0).max(0.0);
+        let half_life = self.config.recency_half_life_days.max(f64::EPSILON);
+        let recency = 0.5_f64.powf(age_days / half_life);
+
+        let sources = saturate(candidate.source_count.max(0) as f64);
+        let confidence = candidate.confidence.unwrap_or(0.5).clamp(0.0, 1.0);
+        let relations = saturate(candidate.relation_count as f64);
+
+        let weights = [
+            (self.config.recency_weight.max(0.0), recency),
+            (self.config.source_weight.max(0.0), sources),
+            (self.config.confidence_weight.max(0.0), confidence),
+            (self.config.relation_weight.max(0.0), relations),
+        ];
+        let total_weight: f64 = weights.iter().map(|(weight, _)| weight).sum();
+        if total_weight <= 0.0 {
+            return 0.5;
+        }
+
+        weights
+            .iter()
+            .map(|(weight, signal)| weight * signal)
+            .sum::<f64>()
+            / total_weight
+    }
+}
+
+/// Boost a search score by a memory's stored importance:
+/// `score * (1 + boost * importance)`. Unscored memories are left unchanged.
+pub fn boost_score(score: f32, importance: Option<f64>, boost: f64) -> f32 {
+    match importance {
+        Some(importance) if boost > 0.0 => {
+            score * (1.0 + boost * importance.clamp(0.0, 1.0)) as f32
+        }
+        _ => score,
+    }
+}
+
+fn saturate(n: f64) -> f64 {
+    n / (n + 1.0)
+}
+
+fn parse_timestamp(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn config() -> ImportanceConfig {
+        ImportanceConfig {
+            enabled: true,
+            recency_weight: 0.4,
+            source_weight: 0.2,
+            confidence_weight: 0.2,
+            relation_weight: 0.2,
+            recency_half_life_days: 30.0,
+            search_boost: 0.5,
+            max_memories: None,
+        }
+    }
+
+    fn candidate(
+        days_since_access: i64,
+        sources: i32,
+        relations: usize,
+    ) -> MemoryImportanceCandidate {
+        let now = Utc::now();
+        MemoryImportanceCandidate {
+            id: "mem".to_string(),
+            source_count: sources,
+            confidence: Some(0.8),
+            relation_count: relations,
+            last_accessed: Some((now - Duration::days(days_since_access)).to_rfc3339()),
+            created_at: (now - Duration::days(365)).to_rfc3339(),
+        }
+    }
+
+    #[test]
+    fn test_recently_accessed_memory_scores_higher() {
+        let scorer = ImportanceScorer::new(config());
+        let now = Utc::now();
+
+        let fresh = scorer.score(&candidate(0, 1, 0), now);
+        let stale = scorer.score(&candidate(90, 1, 0), now);
+
+        assert!(fresh > stale);
+    }
+
+    #[test]
+    fn test_sources_and_relations_raise_score() {
+        let scorer = ImportanceScorer::new(config());
+        let now = Utc::now();
+
+        let isolated = scorer.score(&candidate(10, 0, 0), now);
+        let connected = scorer.score(&candidate(10, 4, 3), now);
+
+        assert!(connected > isolated);
+        assert!((0.0..=1.0).contains(&connected));
+    }
+
+    #[test]
+    fn test_recency_falls_back_to_created_at() {
+        let mut config = config();
+        config.source_weight = 0.0;
+        config.confidence_weight = 0.0;
+        config.relation_weight = 0.0;
+        let scorer = ImportanceScorer::new(config);
+        let now = Utc::now();
+
+        let mut never_accessed = candidate(0, 0, 0);
+        never_accessed.last_accessed = None;
+        never_accessed.created_at = (now - Duration::days(30)).to_rfc3339();
+
+        assert!((scorer.score(&never_accessed, now) - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_zero_weights_score_neutral() {
+        let scorer = ImportanceScorer::new(ImportanceConfig {
+            recency_weight: 0.0,
+            source_weight: 0.0,
+            confidence_weight: 0.0,
+            relation_weight: 0.0,
+            ..config()
+        });
+
+        assert_eq!(scorer.score(&candidate(5, 2, 2), Utc::now()), 0.5);
+    }
+
+    #[test]
+    fn test_boost_score() {
+        assert!((boost_score(0.8, Some(1.0), 0.5) - 1.2).abs() < 1e-6);
+        assert_eq!(boost_score(0.8, None, 0.5), 0.8);
+        assert_eq!(boost_score(0.8, Some(1.0), 0.0), 0.8);
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use nanoid::nanoid;
 use serde::{Deserialize, Serialize};
@@ -12,6 +13,12 @@ use crate::error::{MomoError, Result};
 use crate::llm::{prompts, LlmProvider};
 use crate::models::{Memory, MemoryRelationType, MemoryType};
 
+/// Lease name guarding against overlapping inference runs.
+const INFERENCE_RUN_LEASE: &str = "inference_run";
+
+/// Lease lifetime; a crashed run stops blocking new runs once it expires.
+const INFERENCE_RUN_LEASE_TTL: Duration = Duration::from_secs(3600);
+
 /// Statistics from a single inference run
 #[derive(Debug, Clone, Default)]
 pub struct InferenceStats {
@@ -25,6 +32,12 @@ pub struct InferenceStats {
     pub low_confidence_skipped: usize,
     /// Number of errors during inference generation
     pub errors: usize,
+    /// LLM requests made during the run
+    pub llm_calls: u64,
+    /// LLM tokens spent during the run, as reported by the provider
+    pub llm_tokens: u64,
+    /// Whether the run stopped early because its LLM budget ran out
+    pub budget_exhausted: bool,
 }
 
 /// LLM response for a generated inference
@@ -80,7 +93,44 @@ impl InferenceEngine {
     /// 2. For each seed, find related memories via vector search
     /// 3. Ask the LLM to synthesize an inference
     /// 4. If confidence passes threshold and not a duplicate, store it
+    ///
+    ///
+    /// Skipped (returning empty stats) when another run holds the inference lease.
     pub async fn run_once(&self) -> Result<InferenceStats> {
+        match self.try_run_once().await? {
+            Some(stats) => Ok(stats),
+            None => {
+                info!("Inference run already in progress, skipping");
+                Ok(InferenceStats::default())
+            }
+        }
+    }
+
+    /// Run a single pass while holding the inference lease. Returns `None` when
+    /// another run, in this or another process, already holds it.
+    pub async fn try_run_once(&self) -> Result<Option<InferenceStats>> {
+        let Some(lease) = self
+            .db
+            .try_acquire_lease(INFERENCE_RUN_LEASE, INFERENCE_RUN_LEASE_TTL)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        // Each run gets a fresh LLM budget.
+        let run = Self {
+            llm: self.llm.for_run(),
+            ..self.clone()
+        };
+        let result = run.run().await;
+        if let Err(e) = self.db.release_lease(INFERENCE_RUN_LEASE, &lease).await {
+            warn!(error = %e, "Failed to release inference lease");
+        }
+
+        result.map(Some)
+    }
+
+    async fn run(&self) -> Result<InferenceStats> {
         info!("Starting inference engine run");
 
         if !self.llm.is_available() {
@@ -108,6 +158,15 @@ impl InferenceEngine {
                 );
                 break;
             }
+            if self.llm.budget_exhausted() {
+                warn!(
+                    reason = "budget_exhausted",
+                    seeds_remaining = seed_count - stats.seeds_processed,
+                    "LLM budget for this inference run is exhausted, stopping early"
+                );
+                stats.budget_exhausted = true;
+                break;
+            }
 
             stats.seeds_processed += 1;
 
@@ -202,12 +261,20 @@ impl InferenceEngine {
             }
         }
 
+        if let Some(usage) = self.llm.usage() {
+            stats.llm_calls = usage.calls();
+            stats.llm_tokens = usage.tokens();
+        }
+
         info!(
             seeds_processed = stats.seeds_processed,
             inferences_created = stats.inferences_created,
             duplicates_skipped = stats.duplicates_skipped,
             low_confidence = stats.low_confidence_skipped,
             errors = stats.errors,
+            llm_calls = stats.llm_calls,
+            llm_tokens = stats.llm_tokens,
+            budget_exhausted = stats.budget_exhausted,
             "Inference engine run complete"
         );
 
@@ -252,6 +319,7 @@ impl InferenceEngine {
                 self.config.candidate_count as u32,
                 self.config.confidence_threshold,
                 container_tag,
+                None,
                 false,
             )
             .await?;
@@ -397,6 +465,8 @@ mod tests {
             model: "BAAI/bge-small-en-v1.5".to_string(),
             dimensions: 384,
             batch_size: 8,
+            query_cache_size: 0,
+            max_concurrent_batches: 4,
         };
 
         EmbeddingProvider::new(&config).expect("failed to create test embeddings provider")
@@ -419,6 +489,8 @@ mod tests {
             enable_auto_relations: false,
             enable_contradiction_detection: false,
             filter_prompt: None,
+            max_calls_per_run: None,
+            max_tokens_per_run: None,
         };
 
         LlmProvider::new(Some(&config))
@@ -501,6 +573,7 @@ mod tests {
             url: format!("file:{}", db_path.display()),
             auth_token: None,
             local_path: None,
+            max_connections: 4,
         };
 
         let db = Database::new(&config)
@@ -997,6 +1070,57 @@ mod tests {
         assert!(stats.inferences_created <= 1);
     }
 
+    #[tokio::test]
+    async fn test_run_once_stops_when_llm_budget_exhausted() {
+        let llm_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(llm_response(
+                r#"{"content": "Valid inference", "reasoning": "Good", "confidence": 0.95, "source_ids": ["mem_2"]}"#,
+            )))
+            .expect(1)
+            .mount(&llm_server)
+            .await;
+
+        let (conn, db, _temp_dir) = test_database().await;
+
+        for i in 1..=3 {
+            let mem = test_memory(
+                &format!("mem_{i}"),
+                &format!("Fact number {i}"),
+                Some("user_1"),
+            );
+            MemoryRepository::create(&conn, &mem).await.unwrap();
+            let embedding = vec![0.1_f32; 384];
+            MemoryRepository::update_embedding(&conn, &format!("mem_{i}"), &embedding)
+                .await
+                .unwrap();
+        }
+
+        let llm_config = LlmConfig {
+            max_calls_per_run: Some(1),
+            ..test_llm_provider(llm_server.uri())
+                .config()
+                .unwrap()
+                .clone()
+        };
+        let embeddings = test_embeddings_provider().await;
+        let engine = InferenceEngine::new(
+            db,
+            LlmProvider::new(Some(&llm_config)),
+            embeddings,
+            test_config(),
+        );
+
+        let stats = engine.run_once().await.expect("run_once should succeed");
+
+        assert!(stats.budget_exhausted);
+        assert_eq!(stats.seeds_processed, 1);
+        assert_eq!(stats.llm_calls, 1);
+        assert_eq!(stats.llm_tokens, 30);
+    }
+
     #[tokio::test]
     async fn test_interval_secs() {
         let config = InferenceConfig {
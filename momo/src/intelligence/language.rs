@@ -0,0 +1,74 @@
+//! Natural-language detection for memory content.
+//!
+//! Enabled with `MEMORY_LANGUAGE_DETECTION`. Detected languages are stored in
+//! memory metadata under [`LANGUAGE_METADATA_KEY`] as ISO 639-3 codes so that
+//! searches can be scoped to a single language.
+
+use crate::models::Metadata;
+
+/// Metadata key holding a memory's detected language.
+pub const LANGUAGE_METADATA_KEY: &str = "language";
+
+/// Detect the language of `text`, returning its ISO 639-3 code (e.g. `"eng"`,
+/// `"fra"`). Returns `None` when the detection is not reliable.
+pub fn detect_content_language(text: &str) -> Option<&'static str> {
+    whatlang::detect(text)
+        .filter(|info| info.is_reliable())
+        .map(|info| info.lang().code())
+}
+
+/// Record the detected language of `content` in `metadata`. A language that is
+/// already present (e.g. supplied by the client) is left untouched.
+pub fn tag_language(metadata: &mut Metadata, content: &str) {
+    if metadata.contains_key(LANGUAGE_METADATA_KEY) {
+        return;
+    }
+
+    if let Some(code) = detect_content_language(content) {
+        metadata.insert(
+            LANGUAGE_METADATA_KEY.to_string(),
+            serde_json::Value::String(code.to_string()),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_common_languages() {
+        assert_eq!(
+            detect_content_language("The user prefers to work from home on Fridays"),
+            Some("eng")
+        );
+        assert_eq!(
+            detect_content_language(
+                "L'utilisateur préfère travailler depuis la maison le vendredi"
+            ),
+            Some("fra")
+        );
+    }
+
+    #[test]
+    fn test_tag_language_keeps_existing_value() {
+        let mut metadata = Metadata::new();
+        metadata.insert(LANGUAGE_METADATA_KEY.to_string(), serde_json::json!("deu"));
+
+        tag_language(
+            &mut metadata,
+            "The user prefers to work from home on Fridays",
+        );
+
+        assert_eq!(metadata[LANGUAGE_METADATA_KEY], "deu");
+    }
+
+    #[test]
+    fn test_tag_language_skips_unreliable_detection() {
+        let mut metadata = Metadata::new();
+
+        tag_language(&mut metadata, "42");
+
+        assert!(!metadata.contains_key(LANGUAGE_METADATA_KEY));
+    }
+}
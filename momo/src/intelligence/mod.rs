@@ -1,7 +1,10 @@
 pub mod contradiction;
+pub mod examples;
 pub mod extractor;
 pub mod filter;
+pub mod importance;
 pub mod inference;
+pub mod language;
 pub mod profile;
 pub mod relationship;
 pub mod temporal;
@@ -11,6 +14,7 @@ pub mod utils;
 pub use contradiction::ContradictionDetector;
 pub use extractor::MemoryExtractor;
 pub use filter::LlmFilter;
+pub use importance::ImportanceScorer;
 pub use inference::InferenceEngine;
 pub use relationship::RelationshipDetector;
-pub use temporal::TemporalSearchRanker;
+pub use temporal::{blend_freshness, freshness, TemporalSearchRanker};
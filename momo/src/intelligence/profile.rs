@@ -110,6 +110,8 @@ mod tests {
             enable_auto_relations: false,
             enable_contradiction_detection: false,
             filter_prompt: None,
+            max_calls_per_run: None,
+            max_tokens_per_run: None,
         };
         LlmProvider::new(Some(&config))
     }
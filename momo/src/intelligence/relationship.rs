@@ -1,13 +1,40 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+
 use crate::db::DatabaseBackend;
 use crate::embeddings::EmbeddingProvider;
 use crate::error::{MomoError, Result};
 use crate::llm::{prompts, LlmProvider};
+use crate::models::{Memory, MemoryRelationType};
 
 use super::types::{
     DetectionResult, HeuristicContext, RelationshipClassification,
     RelationshipClassificationsResponse,
 };
 
+/// Similar memories classified against each memory.
+const CANDIDATE_LIMIT: u32 = 5;
+/// Minimum similarity for a memory to be classified as related.
+const CANDIDATE_THRESHOLD: f32 = 0.7;
+/// Memories read per query while loading a container.
+const CONTAINER_PAGE_SIZE: usize = 200;
+
+/// Statistics from detecting relations across a container
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RelationDetectionStats {
+    /// Number of latest memories whose candidates were looked up
+    pub memories_scanned: usize,
+    /// Number of memory pairs sent to the LLM for classification
+    pub pairs_evaluated: usize,
+    /// Number of similar pairs skipped because they are already related
+    pub pairs_skipped: usize,
+    /// Number of relations stored
+    pub relations_added: usize,
+    /// Whether pairs were left unevaluated because `max_pairs` was reached
+    pub max_pairs_reached: bool,
+}
+
 pub struct RelationshipDetector {
     llm: LlmProvider,
     embeddings: EmbeddingProvider,
@@ -54,7 +81,14 @@ impl RelationshipDetector {
         };
 
         let candidates = db
-            .search_similar_memories(&embedding, 5, 0.7, container_tag, false)
+            .search_similar_memories(
+                &embedding,
+                CANDIDATE_LIMIT,
+                CANDIDATE_THRESHOLD,
+                container_tag,
+                None,
+                false,
+            )
             .await?
             .into_iter()
             .filter(|hit| hit.memory.id != new_memory_id)
@@ -65,6 +99,151 @@ impl RelationshipDetector {
             return Ok(empty_result());
         }
 
+        Ok(self
+            .classify(new_memory_content, &candidates, heuristic_context)
+            .await)
+    }
+
+    /// Proposes relations between the latest memories of a container, newest
+    /// first, classifying each against the older memories most similar to it.
+    /// Pairs already related in either direction are skipped, and at most
+    /// `max_pairs` pairs are sent to the LLM. Relations found are stored on
+    /// both memories, as during ingestion, but `updates` does not supersede
+    /// the older memory.
+    pub async fn detect_in_container(
+        &self,
+        container_tag: &str,
+        db: &dyn DatabaseBackend,
+        max_pairs: usize,
+    ) -> Result<RelationDetectionStats> {
+        let mut stats = RelationDetectionStats::default();
+        if !self.llm.is_available() {
+            tracing::warn!("LLM unavailable, skipping relationship detection");
+            return Ok(stats);
+        }
+
+        let mut memories = Vec::new();
+        let mut after: Option<(DateTime<Utc>, String)> = None;
+        loop {
+            let page = db
+                .list_container_memories_after(
+                    container_tag,
+                    after.as_ref().map(|(at, id)| (*at, id.as_str())),
+                    CONTAINER_PAGE_SIZE,
+                )
+                .await?;
+            let Some(last) = page.last() else {
+                break;
+            };
+            after = Some((last.created_at, last.id.clone()));
+            let last_page = page.len() < CONTAINER_PAGE_SIZE;
+            memories.extend(
+                page.into_iter()
+                    .filter(|memory| memory.is_latest && !memory.is_forgotten),
+            );
+            if last_page {
+                break;
+            }
+        }
+        memories.reverse();
+
+        let position: HashMap<&str, usize> = memories
+            .iter()
+            .enumerate()
+            .map(|(i, memory)| (memory.id.as_str(), i))
+            .collect();
+        let mut related: HashSet<(String, String)> = HashSet::new();
+        for memory in &memories {
+            for related_id in memory.memory_relations.keys() {
+                related.insert((memory.id.clone(), related_id.clone()));
+                related.insert((related_id.clone(), memory.id.clone()));
+            }
+        }
+
+        for (i, memory) in memories.iter().enumerate() {
+            if stats.max_pairs_reached {
+                break;
+            }
+            let Some(embedding) = db.get_memory_embedding(&memory.id).await? else {
+                continue;
+            };
+            stats.memories_scanned += 1;
+
+            let hits = db
+                .search_similar_memories(
+                    &embedding,
+                    CANDIDATE_LIMIT,
+                    CANDIDATE_THRESHOLD,
+                    Some(container_tag),
+                    None,
+                    false,
+                )
+                .await?;
+            let mut candidates: Vec<Memory> = Vec::new();
+            for hit in hits {
+                // Each pair is classified once, from its newer memory.
+                if position
+                    .get(hit.memory.id.as_str())
+                    .map_or(true, |&j| j <= i)
+                {
+                    continue;
+                }
+                if related.contains(&(memory.id.clone(), hit.memory.id.clone())) {
+                    stats.pairs_skipped += 1;
+                    continue;
+                }
+                if stats.pairs_evaluated + candidates.len() >= max_pairs {
+                    stats.max_pairs_reached = true;
+                    break;
+                }
+                candidates.push(hit.memory);
+            }
+            if candidates.is_empty() {
+                continue;
+            }
+            stats.pairs_evaluated += candidates.len();
+
+            let detection = self.classify(&memory.memory, &candidates, None).await;
+            for classification in detection.classifications {
+                if classification.relation_type == "none"
+                    || !candidates.iter().any(|c| c.id == classification.memory_id)
+                {
+                    continue;
+                }
+                let relation_type = match classification.relation_type.parse::<MemoryRelationType>()
+                {
+                    Ok(relation_type) => relation_type,
+                    Err(error) => {
+                        tracing::warn!(error = %error, "Unknown relation type");
+                        continue;
+                    }
+                };
+
+                db.add_memory_relation(
+                    &memory.id,
+                    &classification.memory_id,
+                    relation_type.clone(),
+                )
+                .await?;
+                db.add_memory_relation(&classification.memory_id, &memory.id, relation_type)
+                    .await?;
+                related.insert((memory.id.clone(), classification.memory_id.clone()));
+                related.insert((classification.memory_id, memory.id.clone()));
+                stats.relations_added += 1;
+            }
+        }
+
+        Ok(stats)
+    }
+
+    /// Asks the LLM how `new_memory_content` relates to each of `candidates`,
+    /// keeping confident classifications.
+    async fn classify(
+        &self,
+        new_memory_content: &str,
+        candidates: &[Memory],
+        heuristic_context: Option<&HeuristicContext>,
+    ) -> DetectionResult {
         let prompt_candidates = candidates
             .iter()
             .map(|memory| (memory.id.as_str(), memory.memory.as_str()))
@@ -123,18 +302,18 @@ impl RelationshipDetector {
                     }
                 });
 
-                Ok(DetectionResult {
+                DetectionResult {
                     classifications: filtered,
                     heuristic_overridden,
-                })
+                }
             }
             Err(MomoError::LlmUnavailable(reason)) => {
                 tracing::warn!(%reason, "LLM unavailable during relationship detection");
-                Ok(empty_result())
+                empty_result()
             }
             Err(error) => {
                 tracing::error!(error = %error, "Failed to detect relationships");
-                Ok(empty_result())
+                empty_result()
             }
         }
     }
@@ -167,6 +346,8 @@ mod tests {
             model: "BAAI/bge-small-en-v1.5".to_string(),
             dimensions: 384,
             batch_size: 8,
+            query_cache_size: 0,
+            max_concurrent_batches: 4,
         };
 
         EmbeddingProvider::new(&config).expect("failed to create test embeddings provider")
@@ -189,6 +370,8 @@ mod tests {
             enable_auto_relations: false,
             enable_contradiction_detection: false,
             filter_prompt: None,
+            max_calls_per_run: None,
+            max_tokens_per_run: None,
         };
 
         LlmProvider::new(Some(&config))
@@ -261,6 +444,7 @@ mod tests {
             url: format!("file:{}", db_path.display()),
             auth_token: None,
             local_path: None,
+            max_connections: 4,
         };
 
         let db = Database::new(&config)
@@ -465,6 +649,76 @@ mod tests {
         assert_eq!(result.heuristic_overridden, Some(false));
     }
 
+    #[tokio::test]
+    async fn test_detect_in_container_adds_relations_once() {
+        let llm_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(llm_response(
+                r#"[{"memory_id":"mem_old","relation_type":"updates","confidence":0.9,"reasoning":"Changed preference"}]"#,
+            )))
+            .mount(&llm_server)
+            .await;
+
+        let embeddings = test_embeddings_provider().await;
+        let detector =
+            RelationshipDetector::new(test_llm_provider(llm_server.uri()), embeddings.clone());
+        let (db, _temp_dir) = test_database().await;
+
+        let conn = db.connect().expect("connect should work");
+        for (id, content, age_secs) in [
+            ("mem_old", "User prefers light mode", 60),
+            ("mem_new", "User prefers dark mode", 0),
+        ] {
+            let mut memory = test_memory(id, content, Some("user_123"));
+            memory.created_at = Utc::now() - chrono::Duration::seconds(age_secs);
+            MemoryRepository::create(&conn, &memory)
+                .await
+                .expect("memory create should succeed");
+            let embedding = embeddings
+                .embed_passage(content)
+                .await
+                .expect("embed should succeed");
+            MemoryRepository::update_embedding(&conn, id, &embedding)
+                .await
+                .expect("embedding update should succeed");
+        }
+
+        let backend = LibSqlBackend::new(db);
+        let capped = detector
+            .detect_in_container("user_123", &backend, 0)
+            .await
+            .expect("detection should not fail");
+        assert!(capped.max_pairs_reached);
+        assert_eq!(capped.relations_added, 0);
+
+        let stats = detector
+            .detect_in_container("user_123", &backend, 10)
+            .await
+            .expect("detection should not fail");
+        assert_eq!(stats.memories_scanned, 2);
+        assert_eq!(stats.pairs_evaluated, 1);
+        assert_eq!(stats.relations_added, 1);
+        assert!(!stats.max_pairs_reached);
+
+        let updated = MemoryRepository::get_by_id(&conn, "mem_new")
+            .await
+            .expect("get should succeed")
+            .expect("memory exists");
+        assert_eq!(
+            updated.memory_relations.get("mem_old"),
+            Some(&MemoryRelationType::Updates)
+        );
+
+        let rerun = detector
+            .detect_in_container("user_123", &backend, 10)
+            .await
+            .expect("detection should not fail");
+        assert_eq!(rerun.pairs_evaluated, 0);
+        assert_eq!(rerun.pairs_skipped, 1);
+        assert_eq!(rerun.relations_added, 0);
+    }
+
     #[tokio::test]
     async fn test_detect_no_heuristic_backward_compat() {
         let embeddings = test_embeddings_provider().await;
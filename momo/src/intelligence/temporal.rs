@@ -1,3 +1,5 @@
+use chrono::{DateTime, Utc};
+
 use crate::models::{Memory, MemoryType, SearchIncludeOptions};
 
 /// Temporal search ranker that applies episode decay scoring
@@ -21,13 +23,17 @@ impl TemporalSearchRanker {
     /// For Episode type memories: multiplies base_score by episode relevance
     /// For Fact/Preference types: returns base_score unchanged
     pub fn apply_episode_decay(&self, memory: &Memory, base_score: f32) -> f32 {
+        base_score * self.episode_decay_factor(memory)
+    }
+
+    /// Multiplier `apply_episode_decay` applies to a memory's score: its
+    /// episode relevance for episodes, 1.0 otherwise
+    pub fn episode_decay_factor(&self, memory: &Memory) -> f32 {
         match memory.memory_type {
             MemoryType::Episode => {
-                let relevance =
-                    memory.calculate_episode_relevance(self.decay_days, self.decay_factor);
-                base_score * (relevance as f32)
+                memory.calculate_episode_relevance(self.decay_days, self.decay_factor) as f32
             }
-            _ => base_score,
+            _ => 1.0,
         }
     }
 
@@ -44,10 +50,24 @@ impl Default for TemporalSearchRanker {
     }
 }
 
+/// Recency of `updated_at` between 0 and 1: 1.0 at `now`, halving every
+/// `half_life_days`. Timestamps in the future count as fresh.
+pub fn freshness(updated_at: DateTime<Utc>, now: DateTime<Utc>, half_life_days: f64) -> f64 {
+    let age_days = (now - updated_at).num_seconds().max(0) as f64 / 86_400.0;
+    if half_life_days <= 0.0 {
+        return if age_days > 0.0 { 0.0 } else { 1.0 };
+    }
+    0.5_f64.powf(age_days / half_life_days)
+}
+
+/// Blend a search score with recency: `score * (1 - weight) + freshness * weight`.
+pub fn blend_freshness(score: f32, freshness: f64, weight: f32) -> f32 {
+    score * (1.0 - weight) + freshness as f32 * weight
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Utc;
 
     fn create_test_memory(memory_type: MemoryType, days_ago: i64) -> Memory {
         let mut memory = Memory::new(
@@ -122,6 +142,7 @@ mod tests {
             summaries: None,
             related_memories: None,
             forgotten_memories: Some(true),
+            decay_debug: None,
         };
 
         assert!(ranker.should_include_forgotten(&opts));
@@ -135,6 +156,7 @@ mod tests {
             summaries: None,
             related_memories: None,
             forgotten_memories: None,
+            decay_debug: None,
         };
 
         assert!(!ranker.should_include_forgotten(&opts));
@@ -148,8 +170,36 @@ mod tests {
             summaries: None,
             related_memories: None,
             forgotten_memories: Some(false),
+            decay_debug: None,
         };
 
         assert!(!ranker.should_include_forgotten(&opts));
     }
+
+    #[test]
+    fn test_freshness_halves_every_half_life() {
+        let now = Utc::now();
+        assert_eq!(freshness(now, now, 30.0), 1.0);
+        assert!((freshness(now - chrono::Duration::days(30), now, 30.0) - 0.5).abs() < 1e-9);
+        assert!((freshness(now - chrono::Duration::days(60), now, 30.0) - 0.25).abs() < 1e-9);
+        assert_eq!(freshness(now + chrono::Duration::days(1), now, 30.0), 1.0);
+    }
+
+    #[test]
+    fn test_blend_freshness_reorders_as_weight_increases() {
+        let now = Utc::now();
+        // An older, more similar memory against a fresh, less similar one
+        let old = (0.9, freshness(now - chrono::Duration::days(90), now, 30.0));
+        let fresh = (0.7, freshness(now, now, 30.0));
+        let old_first = |weight: f32| {
+            blend_freshness(old.0, old.1, weight) > blend_freshness(fresh.0, fresh.1, weight)
+        };
+
+        assert!(old_first(0.0));
+        assert!(old_first(0.1));
+        assert!(!old_first(0.3));
+        assert!(!old_first(1.0));
+        assert_eq!(blend_freshness(0.9, 0.2, 0.0), 0.9);
+        assert_eq!(blend_freshness(0.9, 0.2, 1.0), 0.2);
+    }
 }
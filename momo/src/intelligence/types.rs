@@ -29,6 +29,9 @@ pub struct ExtractedMemory {
     /// Set by `MemoryExtractor::check_contradictions()` when enabled.
     #[serde(default)]
     pub potential_contradiction: bool,
+    /// ID of the existing memory this one was flagged against, if any.
+    #[serde(default)]
+    pub contradicted_memory_id: Option<String>,
 }
 
 /// Result of extracting memories from content
@@ -99,6 +102,7 @@ mod tests {
             confidence: 0.8,
             context: Some("Settings conversation".to_string()),
             potential_contradiction: false,
+            contradicted_memory_id: None,
         };
 
         let json = serde_json::to_string(&memory).unwrap();
@@ -131,6 +135,7 @@ mod tests {
             confidence: 0.9,
             context: None,
             potential_contradiction: false,
+            contradicted_memory_id: None,
         };
 
         let json = serde_json::to_string(&memory).unwrap();
@@ -148,6 +153,7 @@ mod tests {
                     confidence: 0.8,
                     context: None,
                     potential_contradiction: false,
+                    contradicted_memory_id: None,
                 },
                 ExtractedMemory {
                     content: "Second memory".to_string(),
@@ -155,6 +161,7 @@ mod tests {
                     confidence: 0.7,
                     context: Some("Context here".to_string()),
                     potential_contradiction: false,
+                    contradicted_memory_id: None,
                 },
             ],
             source_content: "Original content here".to_string(),
@@ -208,6 +215,7 @@ mod tests {
             confidence: 0.0,
             context: None,
             potential_contradiction: false,
+            contradicted_memory_id: None,
         };
         assert_eq!(memory_low.confidence, 0.0);
 
@@ -217,6 +225,7 @@ mod tests {
             confidence: 1.0,
             context: None,
             potential_contradiction: false,
+            contradicted_memory_id: None,
         };
         assert_eq!(memory_high.confidence, 1.0);
 
@@ -226,6 +235,7 @@ mod tests {
             confidence: 0.5,
             context: None,
             potential_contradiction: false,
+            contradicted_memory_id: None,
         };
         assert_eq!(memory_mid.confidence, 0.5);
     }
@@ -11,4 +11,5 @@ pub mod ocr;
 pub mod processing;
 pub mod search;
 pub mod services;
+pub mod telemetry;
 pub mod transcription;
@@ -1,3 +1,4 @@
+use std::sync::Arc;
 use std::time::Duration;
 
 use serde_json::Value;
@@ -16,7 +17,7 @@ use async_openai::{
 use crate::{
     config::{parse_llm_provider_model, LlmConfig},
     error::{MomoError, Result},
-    llm::provider::CompletionOptions,
+    llm::{provider::CompletionOptions, LlmUsage},
 };
 
 const OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
@@ -36,6 +37,7 @@ struct ApiConfig {
 pub struct LlmApiClient {
     client: Client<OpenAIConfig>,
     config: ApiConfig,
+    usage: Option<Arc<LlmUsage>>,
 }
 
 impl LlmApiClient {
@@ -81,9 +83,32 @@ impl LlmApiClient {
         Ok(Self {
             client,
             config: api_config,
+            usage: None,
         })
     }
 
+    /// Record every request, and the tokens responses report, in `usage`.
+    pub fn with_usage(mut self, usage: Option<Arc<LlmUsage>>) -> Self {
+        self.usage = usage;
+        self
+    }
+
+    #[tracing::instrument(name = "llm.chat", skip_all, fields(model = %request.model))]
+
+    async fn send(
+        &self,
+        request: CreateChatCompletionRequest,
+    ) -> std::result::Result<CreateChatCompletionResponse, OpenAIError> {
+        if let Some(usage) = &self.usage {
+            usage.record_call();
+        }
+        let response = self.client.chat().create(request).await?;
+        if let (Some(usage), Some(reported)) = (&self.usage, &response.usage) {
+            usage.record_tokens(u64::from(reported.total_tokens));
+        }
+        Ok(response)
+    }
+
     pub async fn complete(
         &self,
         prompt: &str,
@@ -104,7 +129,7 @@ impl LlmApiClient {
 
             let request = self.build_request(prompt, system_prompt, options)?;
 
-            match self.client.chat().create(request).await {
+            match self.send(request).await {
                 Ok(response) => return Self::extract_content(response),
                 Err(error) => {
                     if let Some(rate_limit_error) = Self::rate_limit_error(&error) {
@@ -151,7 +176,7 @@ impl LlmApiClient {
 
             let request = self.build_json_request(prompt, options)?;
 
-            match self.client.chat().create(request).await {
+            match self.send(request).await {
                 Ok(response) => {
                     let content = Self::extract_content(response)?;
                     tracing::debug!(response_len = content.len(), "LLM JSON response received");
@@ -359,6 +384,10 @@ impl LlmApiClient {
     }
 
     fn map_openai_error(error: OpenAIError) -> MomoError {
+        if Self::is_retryable(&error) {
+            return MomoError::LlmTransient(format!("LLM request failed: {error}"));
+        }
+
         match error {
             OpenAIError::Reqwest(reqwest_error) => {
                 MomoError::Llm(format!("LLM request failed: {reqwest_error}"))
@@ -428,6 +457,8 @@ mod tests {
             enable_auto_relations: false,
             enable_contradiction_detection: false,
             filter_prompt: None,
+            max_calls_per_run: None,
+            max_tokens_per_run: None,
         }
     }
 
@@ -0,0 +1,169 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::config::LlmConfig;
+
+/// Caps on LLM usage within a single background run. `None` means unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LlmBudget {
+    pub max_calls: Option<u64>,
+    /// Total (prompt + completion) tokens as reported in response `usage`.
+    pub max_tokens: Option<u64>,
+}
+
+impl From<&LlmConfig> for LlmBudget {
+    fn from(config: &LlmConfig) -> Self {
+        Self {
+            max_calls: config.max_calls_per_run,
+            max_tokens: config.max_tokens_per_run,
+        }
+    }
+}
+
+/// LLM calls and tokens spent so far in one run.
+///
+/// Shared by every clone of the run's provider, so usage from nested helpers
+/// (e.g. a profile generator) counts towards the same budget.
+#[derive(Debug, Default)]
+pub struct LlmUsage {
+    budget: LlmBudget,
+    calls: AtomicU64,
+    tokens: AtomicU64,
+}
+
+impl LlmUsage {
+    pub fn new(budget: LlmBudget) -> Self {
+        Self {
+            budget,
+            ..Self::default()
+        }
+    }
+
+    /// Requests sent, including retries.
+    pub fn calls(&self) -> u64 {
+        self.calls.load(Ordering::Relaxed)
+    }
+
+    pub fn tokens(&self) -> u64 {
+        self.tokens.load(Ordering::Relaxed)
+    }
+
+    /// Whether no further calls may be made. Token usage is only known after a
+    /// response arrives, so the call that crosses the token budget completes.
+    pub fn is_exhausted(&self) -> bool {
+        self.budget.max_calls.is_some_and(|max| self.calls() >= max)
+            || self
+                .budget
+                .max_tokens
+                .is_some_and(|max| self.tokens() >= max)
+    }
+
+    pub(super) fn record_call(&self) {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn record_tokens(&self, tokens: u64) {
+        self.tokens.fetch_add(tokens, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+    use crate::error::MomoError;
+    use crate::llm::LlmProvider;
+
+    fn test_llm_config(base_url: String) -> LlmConfig {
+        LlmConfig {
+            model: "openai/gpt-4o-mini".to_string(),
+            api_key: Some("test-key".to_string()),
+            base_url: Some(base_url),
+            timeout_secs: 5,
+            max_retries: 0,
+            enable_query_rewrite: false,
+            query_rewrite_cache_size: 1000,
+            query_rewrite_timeout_secs: 2,
+            enable_auto_relations: false,
+            enable_contradiction_detection: false,
+            filter_prompt: None,
+            max_calls_per_run: None,
+            max_tokens_per_run: None,
+        }
+    }
+
+    #[test]
+    fn test_usage_exhausted_by_either_limit() {
+        let unlimited = LlmUsage::default();
+        unlimited.record_call();
+        unlimited.record_tokens(1_000_000);
+        assert!(!unlimited.is_exhausted());
+
+        let calls = LlmUsage::new(LlmBudget {
+            max_calls: Some(2),
+            max_tokens: None,
+        });
+        calls.record_call();
+        assert!(!calls.is_exhausted());
+        calls.record_call();
+        assert!(calls.is_exhausted());
+
+        let tokens = LlmUsage::new(LlmBudget {
+            max_calls: None,
+            max_tokens: Some(100),
+        });
+        tokens.record_tokens(99);
+        assert!(!tokens.is_exhausted());
+        tokens.record_tokens(1);
+        assert!(tokens.is_exhausted());
+    }
+
+    #[tokio::test]
+    async fn test_run_provider_meters_usage_and_stops_at_budget() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "chatcmpl-test",
+                "object": "chat.completion",
+                "created": 1,
+                "model": "gpt-4o-mini",
+                "choices": [{
+                    "index": 0,
+                    "message": {"role": "assistant", "content": "ok"},
+                    "finish_reason": "stop"
+                }],
+                "usage": {"prompt_tokens": 10, "completion_tokens": 20, "total_tokens": 30}
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let config = LlmConfig {
+            max_tokens_per_run: Some(50),
+            ..test_llm_config(server.uri())
+        };
+        let llm = LlmProvider::new(Some(&config));
+        assert!(llm.usage().is_none());
+
+        let run = llm.for_run();
+        run.complete("first", None).await.unwrap();
+        assert!(!run.budget_exhausted());
+        run.complete("second", None).await.unwrap();
+        assert!(run.budget_exhausted());
+        assert!(matches!(
+            run.complete("third", None).await,
+            Err(MomoError::LlmUnavailable(_))
+        ));
+
+        let usage = run.usage().unwrap();
+        assert_eq!(usage.calls(), 2);
+        assert_eq!(usage.tokens(), 60);
+
+        // Each run starts with a fresh budget; the base provider is unmetered.
+        assert!(!llm.for_run().budget_exhausted());
+        assert!(!llm.budget_exhausted());
+    }
+}
@@ -1,5 +1,7 @@
 mod api;
+mod budget;
 pub mod prompts;
 mod provider;
 
+pub use budget::{LlmBudget, LlmUsage};
 pub use provider::{LlmBackend, LlmProvider};
@@ -3,6 +3,8 @@
 //! These templates use basic `format!()` interpolation for type safety.
 //! Missing variables will cause compile-time errors.
 
+use crate::intelligence::examples::ExtractionExample;
+
 /// Generate a prompt for extracting memories from content
 ///
 /// Returns a prompt that instructs the LLM to extract key facts, preferences,
@@ -10,6 +12,8 @@
 ///
 /// # Arguments
 /// * `content` - The text content to extract memories from
+/// * `examples` - Few-shot examples, rendered by [`extraction_examples_section`]
+///   ahead of the content
 ///
 /// # Returns
 /// A formatted prompt string ready for LLM completion
@@ -18,10 +22,11 @@
 /// ```
 /// use momo::llm::prompts::memory_extraction_prompt;
 ///
-/// let prompt = memory_extraction_prompt("User prefers dark mode and uses vim");
+/// let prompt = memory_extraction_prompt("User prefers dark mode and uses vim", &[]);
 /// assert!(prompt.contains("dark mode"));
 /// ```
-pub fn memory_extraction_prompt(content: &str) -> String {
+pub fn memory_extraction_prompt(content: &str, examples: &[ExtractionExample]) -> String {
+    let examples = extraction_examples_section(examples);
     format!(
         r#"Extract key facts, preferences, and memories from the following content.
 Return as a JSON array of memory objects with "content", "memory_type", and "confidence" fields.
@@ -32,7 +37,7 @@ Memory Types:
 - Episode: Events, experiences, or interactions the user has had
 
 Confidence: A score from 0.0 to 1.0 indicating how certain you are about this memory.
-
+{examples}
 Content:
 {content}
 
@@ -45,6 +50,34 @@ Respond with valid JSON only. Example format:
     )
 }
 
+/// Render few-shot extraction examples as a prompt section
+///
+/// Each example shows its input text followed by the expected JSON output.
+/// Returns an empty string when there are no examples.
+pub fn extraction_examples_section(examples: &[ExtractionExample]) -> String {
+    if examples.is_empty() {
+        return String::new();
+    }
+
+    let rendered = examples
+        .iter()
+        .enumerate()
+        .map(|(index, example)| {
+            let output =
+                serde_json::to_string(&example.memories).unwrap_or_else(|_| "[]".to_string());
+            format!(
+                "Example {}:\nInput:\n{}\nOutput:\n{}",
+                index + 1,
+                example.input.trim(),
+                output
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    format!("\nFollow the style of these examples:\n\n{rendered}\n")
+}
+
 /// Generate a prompt for extracting memories from a conversation
 ///
 /// Returns a prompt that instructs the LLM to extract key facts, preferences,
@@ -52,6 +85,7 @@ Respond with valid JSON only. Example format:
 ///
 /// # Arguments
 /// * `messages` - The conversation messages to extract memories from
+/// * `examples` - Few-shot examples, as for [`memory_extraction_prompt`]
 ///
 /// # Returns
 /// A formatted prompt string ready for LLM completion
@@ -69,10 +103,14 @@ Respond with valid JSON only. Example format:
 ///         timestamp: Some(Utc::now()),
 ///     },
 /// ];
-/// let prompt = conversation_extraction_prompt(&messages);
+/// let prompt = conversation_extraction_prompt(&messages, &[]);
 /// assert!(prompt.contains("dark mode"));
 /// ```
-pub fn conversation_extraction_prompt(messages: &[crate::models::ConversationMessage]) -> String {
+pub fn conversation_extraction_prompt(
+    messages: &[crate::models::ConversationMessage],
+    examples: &[ExtractionExample],
+) -> String {
+    let examples = extraction_examples_section(examples);
     let conversation = messages
         .iter()
         .map(|msg| format!("[{}]: {}", msg.role, msg.content))
@@ -89,7 +127,7 @@ Memory Types:
 - Episode: Events, experiences, or interactions the user has had
 
 Confidence: A score from 0.0 to 1.0 indicating how certain you are about this memory.
-
+{examples}
 Conversation:
 {conversation}
 
@@ -150,7 +188,6 @@ Respond with only the rewritten query, no explanation."#
 /// let prompt = summarize_prompt("Long article text...", 50);
 /// assert!(prompt.contains("50 words"));
 /// ```
-#[allow(dead_code)]
 pub fn summarize_prompt(content: &str, max_length: usize) -> String {
     format!(
         r#"Summarize the following content in {max_length} words or less.
@@ -163,6 +200,81 @@ Respond with only the summary, no preamble."#
     )
 }
 
+/// Generate a prompt that situates a document chunk within its document
+///
+/// The returned context is prepended to the chunk before embedding
+/// ("contextual retrieval"), so the prompt asks for a single sentence.
+///
+/// # Arguments
+/// * `document_overview` - Title and summary (or leading text) of the document
+/// * `surrounding` - Text immediately around the chunk, may be empty
+/// * `chunk` - The chunk to situate
+///
+/// # Example
+/// ```
+/// use momo::llm::prompts::chunk_context_prompt;
+///
+/// let prompt = chunk_context_prompt("Title: Rust Guide", "", "Borrowing rules...");
+/// assert!(prompt.contains("Rust Guide"));
+/// assert!(prompt.contains("Borrowing rules..."));
+/// ```
+pub fn chunk_context_prompt(document_overview: &str, surrounding: &str, chunk: &str) -> String {
+    let surrounding_section = if surrounding.trim().is_empty() {
+        String::new()
+    } else {
+        format!("\nSurrounding text:\n{surrounding}\n")
+    };
+
+    format!(
+        r#"Here is an overview of a document:
+{document_overview}
+{surrounding_section}
+Here is a chunk from that document:
+{chunk}
+
+Write one short sentence that situates this chunk within the overall document,
+to improve search retrieval of the chunk. Mention the document's subject and
+what this part covers.
+
+Respond with only the sentence, no preamble."#
+    )
+}
+
+/// Generate a prompt that classifies a document into one of a fixed set of categories
+///
+/// # Arguments
+/// * `title` - Document title, if known
+/// * `content` - Leading text of the document
+/// * `categories` - Allowed category labels; the LLM must answer with one of them
+///
+/// # Example
+/// ```
+/// use momo::llm::prompts::document_category_prompt;
+///
+/// let prompt = document_category_prompt(Some("Pancakes"), "2 eggs, 1 cup flour...", &["recipe", "other"]);
+/// assert!(prompt.contains("recipe, other"));
+/// assert!(prompt.contains("Pancakes"));
+/// ```
+pub fn document_category_prompt(title: Option<&str>, content: &str, categories: &[&str]) -> String {
+    let title_line = title
+        .filter(|title| !title.trim().is_empty())
+        .map(|title| format!("Title: {title}\n"))
+        .unwrap_or_default();
+    let categories = categories.join(", ");
+
+    format!(
+        r#"Classify the following document into exactly one of these categories:
+{categories}
+
+Use "other" if none of the categories fits.
+
+{title_line}Content:
+{content}
+
+Respond with only the category label, no preamble."#
+    )
+}
+
 /// Generate a prompt for detecting relationships between a new memory and existing memories
 ///
 /// Returns a prompt that instructs the LLM to classify relationships between
@@ -432,7 +544,7 @@ mod tests {
     #[test]
     fn test_memory_extraction_prompt_contains_content() {
         let content = "User prefers dark mode and uses vim";
-        let prompt = memory_extraction_prompt(content);
+        let prompt = memory_extraction_prompt(content, &[]);
 
         assert!(prompt.contains(content));
         assert!(prompt.contains("JSON"));
@@ -440,6 +552,34 @@ mod tests {
         assert!(prompt.contains("confidence"));
     }
 
+    #[test]
+    fn test_memory_extraction_prompt_includes_few_shot_examples() {
+        use crate::intelligence::examples::ExampleMemory;
+
+        let examples = vec![ExtractionExample {
+            input: "The lessee shall pay rent by the 5th of each month.".to_string(),
+            memories: vec![ExampleMemory {
+                content: "Rent is due by the 5th of each month".to_string(),
+                memory_type: "fact".to_string(),
+                confidence: 0.95,
+            }],
+        }];
+
+        let prompt = memory_extraction_prompt("Lease for unit 4B", &examples);
+
+        assert!(prompt.contains("Example 1:"));
+        assert!(prompt.contains("The lessee shall pay rent by the 5th of each month."));
+        assert!(prompt.contains(r#""content":"Rent is due by the 5th of each month""#));
+        let example_pos = prompt.find("Example 1:").unwrap();
+        let content_pos = prompt.find("Lease for unit 4B").unwrap();
+        assert!(
+            example_pos < content_pos,
+            "Examples should precede the content"
+        );
+
+        assert!(!memory_extraction_prompt("Lease", &[]).contains("Follow the style"));
+    }
+
     #[test]
     fn test_query_rewrite_prompt_contains_query() {
         let query = "how to use rust";
@@ -459,9 +599,35 @@ mod tests {
         assert!(prompt.contains("50 words"));
     }
 
+    #[test]
+    fn test_chunk_context_prompt_includes_overview_and_surrounding_text() {
+        let prompt =
+            chunk_context_prompt("Title: Rust Guide", "previous paragraph", "Borrowing rules");
+        assert!(prompt.contains("Title: Rust Guide"));
+        assert!(prompt.contains("Surrounding text:\nprevious paragraph"));
+        assert!(prompt.contains("Borrowing rules"));
+
+        let prompt = chunk_context_prompt("Title: Rust Guide", "  ", "Borrowing rules");
+        assert!(!prompt.contains("Surrounding text"));
+    }
+
+    #[test]
+    fn test_document_category_prompt_lists_categories() {
+        let prompt = document_category_prompt(
+            Some("Q3 Sync"),
+            "Attendees: ...",
+            &["meeting_notes", "other"],
+        );
+        assert!(prompt.contains("meeting_notes, other"));
+        assert!(prompt.contains("Title: Q3 Sync\nContent:"));
+
+        let prompt = document_category_prompt(None, "Attendees: ...", &["other"]);
+        assert!(!prompt.contains("Title:"));
+    }
+
     #[test]
     fn test_memory_extraction_prompt_format() {
-        let prompt = memory_extraction_prompt("test content");
+        let prompt = memory_extraction_prompt("test content", &[]);
 
         // Should have clear instructions
         assert!(prompt.contains("Extract"));
@@ -485,7 +651,7 @@ mod tests {
 
     #[test]
     fn test_memory_extraction_prompt_has_type_definitions() {
-        let prompt = memory_extraction_prompt("test content");
+        let prompt = memory_extraction_prompt("test content", &[]);
 
         // Should define what each type means
         assert!(prompt.contains("Fact"));
@@ -512,7 +678,7 @@ mod tests {
             },
         ];
 
-        let prompt = conversation_extraction_prompt(&messages);
+        let prompt = conversation_extraction_prompt(&messages, &[]);
 
         // Should contain conversation header
         assert!(prompt.contains("Conversation"));
@@ -6,6 +6,7 @@ use serde_json::Value;
 use crate::config::{parse_llm_provider_model, LlmConfig};
 use crate::error::{MomoError, Result};
 use crate::llm::api::LlmApiClient;
+use crate::llm::{LlmBudget, LlmUsage};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum LlmBackend {
@@ -29,6 +30,8 @@ pub struct CompletionOptions {
 pub struct LlmProvider {
     backend: LlmBackend,
     config: Option<Arc<LlmConfig>>,
+    /// Set on providers returned by [`LlmProvider::for_run`].
+    usage: Option<Arc<LlmUsage>>,
 }
 
 impl LlmProvider {
@@ -60,6 +63,7 @@ impl LlmProvider {
         Self {
             backend,
             config: Some(Arc::new(config.clone())),
+            usage: None,
         }
     }
 
@@ -69,6 +73,7 @@ impl LlmProvider {
                 reason: reason.to_string(),
             },
             config: None,
+            usage: None,
         }
     }
 
@@ -84,6 +89,26 @@ impl LlmProvider {
         self.config.as_deref()
     }
 
+    /// A provider for one background run: same backend, with calls and tokens
+    /// metered against the configured per-run budget. Once the budget is
+    /// exhausted, completions fail with [`MomoError::LlmUnavailable`].
+    pub fn for_run(&self) -> Self {
+        let budget = self.config().map(LlmBudget::from).unwrap_or_default();
+        Self {
+            usage: Some(Arc::new(LlmUsage::new(budget))),
+            ..self.clone()
+        }
+    }
+
+    /// Usage so far, for providers created with [`LlmProvider::for_run`].
+    pub fn usage(&self) -> Option<&LlmUsage> {
+        self.usage.as_deref()
+    }
+
+    pub fn budget_exhausted(&self) -> bool {
+        self.usage().is_some_and(LlmUsage::is_exhausted)
+    }
+
     pub async fn complete(
         &self,
         prompt: &str,
@@ -92,12 +117,17 @@ impl LlmProvider {
         if !self.is_available() {
             return Err(MomoError::LlmUnavailable(self.unavailable_reason()));
         }
+        if self.budget_exhausted() {
+            return Err(MomoError::LlmUnavailable(
+                "LLM budget for this run is exhausted".to_string(),
+            ));
+        }
 
         let config = self
             .config()
             .ok_or_else(|| MomoError::LlmUnavailable("No config available".to_string()))?;
 
-        let client = LlmApiClient::new(config)?;
+        let client = LlmApiClient::new(config)?.with_usage(self.usage.clone());
         client.complete(prompt, None, options).await
     }
 
@@ -109,12 +139,17 @@ impl LlmProvider {
         if !self.is_available() {
             return Err(MomoError::LlmUnavailable(self.unavailable_reason()));
         }
+        if self.budget_exhausted() {
+            return Err(MomoError::LlmUnavailable(
+                "LLM budget for this run is exhausted".to_string(),
+            ));
+        }
 
         let config = self
             .config()
             .ok_or_else(|| MomoError::LlmUnavailable("No config available".to_string()))?;
 
-        let client = LlmApiClient::new(config)?;
+        let client = LlmApiClient::new(config)?.with_usage(self.usage.clone());
         client.complete_json(prompt, options).await
     }
 
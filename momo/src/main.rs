@@ -12,10 +12,13 @@ mod ocr;
 mod processing;
 mod search;
 mod services;
+mod telemetry;
 mod transcription;
 
 use clap::Parser;
+use std::collections::HashMap;
 use std::process::Stdio;
+use std::time::Duration;
 use tokio_util::sync::CancellationToken;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -40,11 +43,14 @@ use std::sync::Arc;
 
 use crate::api::{create_router, AppState};
 use crate::config::Config;
-use crate::db::{Database, DatabaseBackend, LibSqlBackend};
+use crate::db::{Database, DatabaseBackend, LibSqlBackend, ReplicaHealth};
 use crate::embeddings::{EmbeddingProvider, RerankerProvider};
+use crate::intelligence::examples::load_extraction_examples;
 use crate::intelligence::InferenceEngine;
 use crate::llm::LlmProvider;
 use crate::ocr::OcrProvider;
+use crate::search::load_synonyms;
+use crate::services::search_probe::load_canary_queries;
 use crate::transcription::TranscriptionProvider;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -171,6 +177,7 @@ fn build_read_replica_settings(
             url: read_url.unwrap_or_else(|| write_config.url.clone()),
             auth_token: read_auth_token.or_else(|| write_config.auth_token.clone()),
             local_path: read_local_path.or_else(|| write_config.local_path.clone()),
+            max_connections: write_config.max_connections,
         },
         sync_interval_secs,
     })
@@ -197,7 +204,9 @@ fn build_child_command(
     command
 }
 
-async fn terminate_child(name: &str, child: &mut tokio::process::Child) {
+/// Stop `child`: ask it to shut down with SIGTERM, where supported, and kill
+/// it if it has not exited within `grace`.
+async fn terminate_child(name: &str, child: &mut tokio::process::Child, grace: Duration) {
     match child.try_wait() {
         Ok(Some(status)) => {
             tracing::info!(process = name, %status, "Subprocess already exited");
@@ -209,6 +218,28 @@ async fn terminate_child(name: &str, child: &mut tokio::process::Child) {
         }
     }
 
+    #[cfg(unix)]
+    if let Some(pid) = child.id() {
+        // SAFETY: `pid` is our own child, which has not been reaped yet.
+        if unsafe { libc::kill(pid as libc::pid_t, libc::SIGTERM) } == 0 {
+            if let Ok(status) = tokio::time::timeout(grace, child.wait()).await {
+                match status {
+                    Ok(status) => tracing::info!(process = name, %status, "Subprocess exited"),
+                    Err(error) => {
+                        tracing::warn!(process = name, error = %error, "Failed to wait for subprocess")
+                    }
+                }
+                return;
+            }
+            tracing::warn!(
+                process = name,
+                "Subprocess did not exit in time; killing it"
+            );
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = grace;
+
     match child.kill().await {
         Ok(()) => tracing::info!(process = name, "Subprocess terminated"),
         Err(error) => {
@@ -254,20 +285,24 @@ async fn run_subprocess_supervisor(args: &Args) -> anyhow::Result<()> {
     tokio::select! {
         _ = wait_for_shutdown_signal() => {
             tracing::info!("Shutdown signal received, terminating subprocesses...");
-            terminate_child("api", &mut api_child).await;
-            terminate_child("worker", &mut worker_child).await;
+            // Leave the worker time to drain, plus a margin for its own exit.
+            let grace = processing_drain_timeout() + Duration::from_secs(5);
+            tokio::join!(
+                terminate_child("api", &mut api_child, grace),
+                terminate_child("worker", &mut worker_child, grace),
+            );
             Ok(())
         }
         status = api_child.wait() => {
             let status = status?;
             tracing::error!(%status, "API subprocess exited unexpectedly");
-            terminate_child("worker", &mut worker_child).await;
+            terminate_child("worker", &mut worker_child, processing_drain_timeout()).await;
             Err(anyhow::anyhow!("API subprocess exited unexpectedly: {status}"))
         }
         status = worker_child.wait() => {
             let status = status?;
             tracing::error!(%status, "Worker subprocess exited unexpectedly");
-            terminate_child("api", &mut api_child).await;
+            terminate_child("api", &mut api_child, Duration::from_secs(5)).await;
             Err(anyhow::anyhow!("Worker subprocess exited unexpectedly: {status}"))
         }
     }
@@ -279,12 +314,15 @@ async fn main() -> anyhow::Result<()> {
 
     dotenvy::dotenv().ok();
 
+    // Held until `main` returns so buffered spans are flushed on exit.
+    let telemetry = telemetry::Telemetry::from_env()?;
     tracing_subscriber::registry()
         .with(
             tracing_subscriber::EnvFilter::try_from_default_env()
                 .unwrap_or_else(|_| "momo=info,tower_http=debug".into()),
         )
         .with(tracing_subscriber::fmt::layer())
+        .with(telemetry.as_ref().map(|telemetry| telemetry.layer()))
         .init();
 
     let runtime_mode = RuntimeMode::parse(args.mode.as_deref());
@@ -304,7 +342,28 @@ async fn main() -> anyhow::Result<()> {
         tracing::info!("Single-process all-mode enabled");
     }
 
-    let config = Config::from_env();
+    let mut config = Config::from_env();
+    config.extraction.examples = load_extraction_examples(&config.extraction)?;
+    if !config.extraction.examples.is_empty() {
+        tracing::info!(
+            count = config.extraction.examples.len(),
+            "Loaded few-shot memory extraction examples"
+        );
+    }
+    config.search.probe.canaries = load_canary_queries(&config.search.probe)?;
+    if !config.search.probe.canaries.is_empty() {
+        tracing::info!(
+            count = config.search.probe.canaries.len(),
+            "Loaded search probe canary queries"
+        );
+    }
+    config.search.synonyms = load_synonyms(&config.search)?;
+    if !config.search.synonyms.is_empty() {
+        tracing::info!(
+            terms = config.search.synonyms.len(),
+            "Loaded search synonym dictionary"
+        );
+    }
 
     if config.server.api_keys.is_empty() {
         tracing::warn!(
@@ -317,32 +376,44 @@ async fn main() -> anyhow::Result<()> {
     let write_db_backend = LibSqlBackend::new(write_raw_db);
     let write_db: Arc<dyn DatabaseBackend> = Arc::new(write_db_backend);
 
-    let (read_db, read_sync_interval_secs) =
-        if let Some(replica) = read_replica_settings(&config.database) {
-            tracing::info!(
-                url = %replica.database.url,
-                local_path = ?replica.database.local_path,
-                "Initializing dedicated read database"
-            );
-            let read_raw_db = Database::new(&replica.database).await?;
-            let read_backend: Arc<dyn DatabaseBackend> = Arc::new(LibSqlBackend::new(read_raw_db));
-            (read_backend, Some(replica.sync_interval_secs))
-        } else {
-            tracing::info!("Using primary database for reads and writes");
-            (write_db.clone(), None)
-        };
+    let (read_db, replica_health) = if let Some(replica) = read_replica_settings(&config.database) {
+        tracing::info!(
+            url = %replica.database.url,
+            local_path = ?replica.database.local_path,
+            "Initializing dedicated read database"
+        );
+        let read_raw_db = Database::new(&replica.database).await?;
+        let read_backend: Arc<dyn DatabaseBackend> = Arc::new(LibSqlBackend::new(read_raw_db));
+        let mut replica_health = ReplicaHealth::new(replica.sync_interval_secs);
+        // Reads fall back to the primary while the replica keeps failing to
+        // sync, unless strict replica reads were asked for.
+        if parse_env_bool("DATABASE_READ_FALLBACK", true) {
+            let after_failures =
+                parse_env_u64("DATABASE_READ_FALLBACK_AFTER_FAILURES", 3).min(u32::MAX as u64);
+            replica_health = replica_health.with_fallback_after(after_failures as u32);
+        }
+        (read_backend, Some(replica_health))
+    } else {
+        tracing::info!("Using primary database for reads and writes");
+        (write_db.clone(), None)
+    };
 
     tracing::info!("Loading embedding model: {}...", config.embeddings.model);
     let embeddings = EmbeddingProvider::new(&config.embeddings)?;
 
     // Pass &*write_db to dereference Arc<dyn DatabaseBackend> into &dyn DatabaseBackend
-    match migration::check_dimension_compatibility(&*write_db, &embeddings, args.rebuild_embeddings)
-        .await?
+    let primary_rebuilt = match migration::check_dimension_compatibility(
+        &*write_db,
+        &embeddings,
+        args.rebuild_embeddings,
+    )
+    .await?
     {
-        migration::MigrationDecision::NotNeeded => {}
+        migration::MigrationDecision::NotNeeded => false,
         migration::MigrationDecision::Approved => {
             migration::trigger_reembedding(&*write_db, embeddings.dimensions()).await?;
             tracing::info!("Migration started. Documents will be re-embedded in background.");
+            true
         }
         migration::MigrationDecision::Rejected => {
             tracing::error!("Migration rejected. Cannot start with dimension mismatch.");
@@ -350,7 +421,7 @@ async fn main() -> anyhow::Result<()> {
                 "Embedding dimension mismatch - use --rebuild-embeddings flag to force migration"
             ));
         }
-    }
+    };
 
     tracing::info!("Initializing OCR provider: {}...", config.ocr.model);
     let ocr = OcrProvider::new(&config.ocr)?;
@@ -398,7 +469,30 @@ async fn main() -> anyhow::Result<()> {
         None
     };
 
-    let state = AppState::new(
+    let alternate_embeddings = services::load_alternate_embeddings(&config)?;
+
+    let mut loaded_models: HashMap<String, usize> = alternate_embeddings
+        .iter()
+        .map(|(model, provider)| (model.clone(), provider.dimensions()))
+        .collect();
+    loaded_models.insert(config.embeddings.model.clone(), embeddings.dimensions());
+    for mismatch in migration::check_container_embedding_models(
+        &*write_db,
+        &config.embeddings.model,
+        &loaded_models,
+        &config.memory.alternate_embeddings.container_models,
+        primary_rebuilt,
+    )
+    .await?
+    {
+        tracing::warn!(
+            container_tag = %mismatch.container_tag,
+            "Embedding model mismatch: {mismatch}. Vector searches in this container will be rejected \
+             until the model is loaded (MEMORY_ALTERNATE_EMBEDDING_MODELS) or re-pinned"
+        );
+    }
+
+    let mut state = AppState::new(
         config.clone(),
         write_db,
         read_db,
@@ -407,10 +501,24 @@ async fn main() -> anyhow::Result<()> {
         ocr,
         transcription,
         llm,
-    );
+    )
+    .with_alternate_embeddings(alternate_embeddings.clone());
+    if let Some(replica_health) = replica_health {
+        state = state.with_replica_health(replica_health);
+    }
 
     let cancel_token = CancellationToken::new();
+    let mut processing_task = None;
     if runtime_mode.runs_worker() {
+        // Documents a previous worker was killed in the middle of would
+        // otherwise stay in an in-progress status.
+        let stale_after = Duration::from_secs(parse_env_u64("PROCESSING_STALE_AFTER_SECS", 300));
+        match state.db.requeue_stale_documents(stale_after).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!(count, "Requeued documents interrupted mid-processing"),
+            Err(e) => tracing::warn!(error = %e, "Failed to requeue interrupted documents"),
+        }
+
         let processing_interval_secs = parse_env_u64("PROCESSING_POLL_INTERVAL_SECS", 10).max(1);
         tracing::info!(
             interval_secs = processing_interval_secs,
@@ -418,7 +526,7 @@ async fn main() -> anyhow::Result<()> {
         );
         let pipeline = state.pipeline.clone();
         let token = cancel_token.child_token();
-        tokio::spawn(async move {
+        processing_task = Some(tokio::spawn(async move {
             loop {
                 tokio::select! {
                     _ = token.cancelled() => {
@@ -426,19 +534,22 @@ async fn main() -> anyhow::Result<()> {
                         break;
                     }
                     _ = tokio::time::sleep(tokio::time::Duration::from_secs(processing_interval_secs)) => {
-                        if let Err(e) = pipeline.process_pending().await {
+                        if let Err(e) = pipeline.process_pending(&token).await {
                             tracing::error!("Background processing error: {}", e);
                         }
                     }
                 }
             }
-        });
+        }));
 
         tracing::info!("Starting forgetting manager...");
         let manager = services::ForgettingManager::new(
             state.db.clone(),
             state.config.memory.forgetting_check_interval_secs,
-        );
+        )
+        .with_importance(state.config.memory.importance.clone())
+        .with_idle_forgetting(state.config.memory.idle_forgetting.clone())
+        .with_dry_run(state.config.memory.forgetting_dry_run);
         let token = cancel_token.child_token();
         tokio::spawn(async move {
             loop {
@@ -467,7 +578,8 @@ async fn main() -> anyhow::Result<()> {
             state.config.memory.episode_forget_grace_days,
             state.config.memory.episode_decay_days,
             state.config.memory.episode_decay_factor,
-        );
+        )
+        .with_dry_run(state.config.memory.forgetting_dry_run);
         let token = cancel_token.child_token();
         tokio::spawn(async move {
             loop {
@@ -543,12 +655,97 @@ async fn main() -> anyhow::Result<()> {
                 }
             });
         }
+
+        if !state.config.search.probe.canaries.is_empty() {
+            tracing::info!(
+                "Starting search quality probe... (interval={}s, canaries={})",
+                state.config.search.probe.interval_secs,
+                state.config.search.probe.canaries.len()
+            );
+            let search_probe = services::SearchQualityProbe::new(
+                state.search.clone(),
+                state.config.search.probe.clone(),
+            );
+            let token = cancel_token.child_token();
+            tokio::spawn(async move {
+                loop {
+                    tokio::select! {
+                        _ = token.cancelled() => {
+                            tracing::info!("Search quality probe shutting down...");
+                            break;
+                        }
+                        _ = tokio::time::sleep(tokio::time::Duration::from_secs(search_probe.interval_secs())) => {
+                            if let Err(e) = search_probe.run_once().await {
+                                tracing::error!("Search quality probe error: {}", e);
+                            }
+                        }
+                    }
+                }
+            });
+        }
+
+        if state.config.processing.chunk_reembed_interval_secs > 0 {
+            tracing::info!(
+                "Starting chunk re-embedding... (interval={}s)",
+                state.config.processing.chunk_reembed_interval_secs
+            );
+            let reembedder = services::ChunkReembedder::new(
+                state.db.clone(),
+                state.embeddings.clone(),
+                &state.config.processing,
+            );
+            let token = cancel_token.child_token();
+            tokio::spawn(async move {
+                loop {
+                    tokio::select! {
+                        _ = token.cancelled() => {
+                            tracing::info!("Chunk re-embedding shutting down...");
+                            break;
+                        }
+                        _ = tokio::time::sleep(tokio::time::Duration::from_secs(reembedder.interval_secs())) => {
+                            if let Err(e) = reembedder.run_once().await {
+                                tracing::error!("Chunk re-embedding error: {}", e);
+                            }
+                        }
+                    }
+                }
+            });
+        }
+
+        if !alternate_embeddings.is_empty() {
+            tracing::info!(
+                "Starting alternate embedding backfill... (models={})",
+                alternate_embeddings.len()
+            );
+            let backfill = services::MemoryEmbeddingBackfill::new(
+                state.db.clone(),
+                alternate_embeddings.clone(),
+                &state.config.memory.alternate_embeddings,
+            );
+            let token = cancel_token.child_token();
+            tokio::spawn(async move {
+                loop {
+                    tokio::select! {
+                        _ = token.cancelled() => {
+                            tracing::info!("Alternate embedding backfill shutting down...");
+                            break;
+                        }
+                        _ = tokio::time::sleep(tokio::time::Duration::from_secs(backfill.interval_secs())) => {
+                            if let Err(e) = backfill.run_once().await {
+                                tracing::error!("Alternate embedding backfill error: {}", e);
+                            }
+                        }
+                    }
+                }
+            });
+        }
     } else {
         tracing::info!("Worker tasks disabled in API-only mode");
     }
 
     if runtime_mode.runs_api() {
-        if let Some(interval_secs) = read_sync_interval_secs {
+        if let Some(replica_health) = state.replica.clone() {
+            let interval_secs = replica_health.sync_interval_secs();
             tracing::info!(interval_secs, "Starting read-replica sync loop");
             let read_db = state.read_db.clone();
             let token = cancel_token.child_token();
@@ -560,15 +757,18 @@ async fn main() -> anyhow::Result<()> {
                             break;
                         }
                         _ = tokio::time::sleep(tokio::time::Duration::from_secs(interval_secs)) => {
-                            if let Err(e) = read_db.sync().await {
+                            let result = read_db.sync().await;
+                            if let Err(e) = &result {
                                 tracing::warn!(error = %e, "Read-replica sync failed");
                             }
+                            replica_health.record(&result);
                         }
                     }
                 }
             });
         }
 
+        let readiness = state.readiness.clone();
         let app = create_router(state);
 
         let addr = format!("{}:{}", config.server.host, config.server.port);
@@ -581,18 +781,50 @@ async fn main() -> anyhow::Result<()> {
         }
 
         let listener = tokio::net::TcpListener::bind(&addr).await?;
+        // Database, embedding model and in-process workers are all up by now.
+        readiness.mark_ready();
+        let shutdown = async move {
+            shutdown_signal(cancel_token).await;
+            readiness.mark_shutting_down();
+        };
         axum::serve(listener, app)
-            .with_graceful_shutdown(shutdown_signal(cancel_token))
+            .with_graceful_shutdown(shutdown)
             .await?;
+        drain_processing(processing_task).await;
 
         return Ok(());
     }
 
     tracing::info!("Worker mode active; HTTP server disabled");
     shutdown_signal(cancel_token).await;
+    drain_processing(processing_task).await;
     Ok(())
 }
 
+/// How long shutdown waits for the document being processed to finish.
+fn processing_drain_timeout() -> Duration {
+    Duration::from_secs(parse_env_u64("PROCESSING_DRAIN_TIMEOUT_SECS", 30))
+}
+
+/// Wait, up to the drain timeout, for the processing loop to finish its
+/// current document after cancellation.
+async fn drain_processing(task: Option<tokio::task::JoinHandle<()>>) {
+    let Some(task) = task else {
+        return;
+    };
+    let timeout = processing_drain_timeout();
+    tracing::info!(
+        timeout_secs = timeout.as_secs(),
+        "Waiting for in-flight document processing to finish..."
+    );
+    match tokio::time::timeout(timeout, task).await {
+        Ok(_) => tracing::info!("Document processing drained"),
+        Err(_) => tracing::warn!(
+            "Drain timeout reached; interrupted documents are requeued on the next start"
+        ),
+    }
+}
+
 async fn shutdown_signal(cancel_token: CancellationToken) {
     wait_for_shutdown_signal().await;
     tracing::info!("Shutdown signal received, cancelling background tasks...");
@@ -637,6 +869,7 @@ mod tests {
             url: "file:momo.db".to_string(),
             auth_token: None,
             local_path: None,
+            max_connections: 4,
         };
 
         let settings = build_read_replica_settings(&write_cfg, None, None, None, 2);
@@ -649,6 +882,7 @@ mod tests {
             url: "libsql://primary.turso.io".to_string(),
             auth_token: Some("primary-token".to_string()),
             local_path: Some("primary-local.db".to_string()),
+            max_connections: 4,
         };
 
         let settings = build_read_replica_settings(
@@ -9,9 +9,10 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use serde_json::json;
-use sha2::{Digest, Sha256};
 
+use crate::api::v1::middleware::ApiKeyScopes;
 use crate::api::AppState;
+use crate::config::ApiScope;
 
 #[derive(Debug, Clone)]
 pub struct McpAuthContext {
@@ -19,6 +20,8 @@ pub struct McpAuthContext {
     pub email: Option<String>,
     pub name: Option<String>,
     pub container_tag: Option<String>,
+    /// Scopes granted to the authenticated key; every scope when auth is off.
+    pub scopes: ApiKeyScopes,
 }
 
 pub async fn mcp_auth_middleware(
@@ -36,6 +39,7 @@ pub async fn mcp_auth_middleware(
             email: None,
             name: None,
             container_tag,
+            scopes: ApiKeyScopes(ApiScope::ALL.to_vec()),
         });
         return next.run(request).await;
     }
@@ -59,17 +63,22 @@ pub async fn mcp_auth_middleware(
             "Unauthorized: Invalid authorization header format. Expected: Bearer <token>",
         );
     };
-    let token = token.to_string();
-
-    if !state.config.server.api_keys.iter().any(|key| key == &token) {
+    let Some(api_key) = state
+        .config
+        .server
+        .api_keys
+        .iter()
+        .find(|key| key.key == token)
+    else {
         return unauthorized_json_rpc("Unauthorized: Invalid or expired API key");
-    }
+    };
 
     request.extensions_mut().insert(McpAuthContext {
-        user_id: user_id_from_api_key(&token),
+        user_id: api_key.fingerprint(),
         email: None,
         name: None,
         container_tag,
+        scopes: ApiKeyScopes::from(api_key),
     });
 
     next.run(request).await
@@ -79,14 +88,6 @@ pub fn auth_context_from_parts(parts: &axum::http::request::Parts) -> Option<Mcp
     parts.extensions.get::<McpAuthContext>().cloned()
 }
 
-fn user_id_from_api_key(api_key: &str) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(api_key.as_bytes());
-    let digest = hasher.finalize();
-    let hash = format!("{digest:x}");
-    format!("api_key_{}", &hash[..16])
-}
-
 fn project_tag_from_headers(headers: &HeaderMap, configured_header: &str) -> Option<String> {
     let configured = HeaderName::from_bytes(configured_header.as_bytes())
         .map_err(|error| {
@@ -159,14 +160,6 @@ fn unauthorized_json_rpc(message: &str) -> Response {
 mod tests {
     use super::*;
 
-    #[test]
-    fn user_id_is_stable_and_hides_key() {
-        let user_id = user_id_from_api_key("super-secret");
-        assert!(user_id.starts_with("api_key_"));
-        assert_ne!(user_id, "super-secret");
-        assert_eq!(user_id.len(), "api_key_".len() + 16);
-    }
-
     #[test]
     fn project_tag_reads_custom_header() {
         let mut headers = HeaderMap::new();
@@ -25,8 +25,12 @@ use tokio::sync::RwLock;
 
 use crate::{
     api::AppState,
+    config::ApiScope,
     mcp::auth::{auth_context_from_parts, McpAuthContext},
-    models::{ForgetMemoryRequest, GetProfileRequest, MemoryType, SearchMemoriesRequest},
+    models::{
+        ForgetMemoryRequest, GetProfileRequest, MemoryType, ProfileFactOptions,
+        SearchMemoriesRequest,
+    },
 };
 
 const PROFILE_URI: &str = "supermemory://profile";
@@ -115,6 +119,25 @@ impl MomoMcpServer {
         self.request_parts(ctx).and_then(auth_context_from_parts)
     }
 
+    /// Reject the call unless the caller's API key grants `scope`.
+    fn require_scope(
+        &self,
+        ctx: &RequestContext<RoleServer>,
+        scope: ApiScope,
+    ) -> Result<(), McpError> {
+        let allowed = self
+            .auth_context(ctx)
+            .is_some_and(|auth| auth.scopes.allows(scope));
+        if allowed {
+            Ok(())
+        } else {
+            Err(McpError::invalid_request(
+                format!("Forbidden: API key lacks the '{scope}' scope"),
+                None,
+            ))
+        }
+    }
+
     fn session_id(&self, ctx: &RequestContext<RoleServer>) -> String {
         self.request_parts(ctx)
             .and_then(|parts| {
@@ -183,7 +206,7 @@ impl MomoMcpServer {
         let profile = self
             .state
             .db
-            .get_user_profile(&container_tag, true, 50)
+            .get_user_profile(&container_tag, true, 50, &ProfileFactOptions::default())
             .await
             .map_err(|error| Self::as_internal_error("Failed to fetch profile resource", error))?;
 
@@ -245,6 +268,8 @@ impl MomoMcpServer {
         Parameters(args): Parameters<MemoryArgs>,
         ctx: RequestContext<RoleServer>,
     ) -> Result<CallToolResult, McpError> {
+        self.require_scope(&ctx, ApiScope::Write)?;
+
         let content = args.content.trim();
         if content.is_empty() {
             return Err(McpError::invalid_params("content cannot be empty", None));
@@ -263,7 +288,14 @@ impl MomoMcpServer {
                 let created = self
                     .state
                     .memory
-                    .create_memory_with_type(content, &container_tag, false, MemoryType::Fact)
+                    .create_memory_with_type(
+                        content,
+                        &container_tag,
+                        false,
+                        MemoryType::Fact,
+                        None,
+                        None,
+                    )
                     .await
                     .map_err(|error| Self::as_internal_error("Failed to save memory", error))?;
 
@@ -285,6 +317,17 @@ impl MomoMcpServer {
                         limit: Some(5),
                         rerank: None,
                         rewrite_query: None,
+                        container_tag_weights: None,
+                        dedupe_version_chains: None,
+                        language: None,
+                        embedding_model: None,
+                        disable_decay: None,
+                        expand_synonyms: None,
+                        min_results: None,
+                        score_calibration: None,
+                        cursor: None,
+                        offset: None,
+                        freshness_weight: None,
                     })
                     .await
                     .map_err(|error| Self::as_internal_error("Failed to search memories", error))?;
@@ -413,6 +456,17 @@ impl MomoMcpServer {
                 limit: Some(10),
                 rerank: None,
                 rewrite_query: None,
+                container_tag_weights: None,
+                dedupe_version_chains: None,
+                language: None,
+                embedding_model: None,
+                disable_decay: None,
+                expand_synonyms: None,
+                min_results: None,
+                score_calibration: None,
+                cursor: None,
+                offset: None,
+                freshness_weight: None,
             })
             .await
             .map_err(|error| Self::as_internal_error("Failed to search memories", error))?;
@@ -520,7 +574,12 @@ impl MomoMcpServer {
         let profile = self
             .state
             .db
-            .get_user_profile(&container_tag, args.include_recent, 50)
+            .get_user_profile(
+                &container_tag,
+                args.include_recent,
+                50,
+                &ProfileFactOptions::default(),
+            )
             .await
             .map_err(|error| Self::as_internal_error("Failed to build context prompt", error))?;
 
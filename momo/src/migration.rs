@@ -1,8 +1,10 @@
+use std::collections::HashMap;
 use std::io::{self, Write};
 
 use crate::db::traits::DatabaseBackend;
 use crate::embeddings::EmbeddingProvider;
 use crate::error::Result;
+use crate::models::ContainerEmbeddingModel;
 
 pub enum MigrationDecision {
     NotNeeded,
@@ -87,3 +89,239 @@ pub async fn trigger_reembedding(db: &dyn DatabaseBackend, new_dimensions: usize
 
     Ok(())
 }
+
+/// A container whose recorded embedding model cannot be searched with the
+/// models loaded in this process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerModelMismatch {
+    pub container_tag: String,
+    pub recorded_model: String,
+    pub recorded_dimensions: usize,
+    /// Dimensions of the loaded model with the recorded name, if it is loaded.
+    pub loaded_dimensions: Option<usize>,
+}
+
+impl std::fmt::Display for ContainerModelMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.loaded_dimensions {
+            Some(loaded) => write!(
+                f,
+                "container '{}' was embedded with {} ({} dimensions), but that model now produces {} dimensions",
+                self.container_tag, self.recorded_model, self.recorded_dimensions, loaded
+            ),
+            None => write!(
+                f,
+                "container '{}' was embedded with {} ({} dimensions), which is not loaded",
+                self.container_tag, self.recorded_model, self.recorded_dimensions
+            ),
+        }
+    }
+}
+
+/// Record the embedding model of every container and report the containers
+/// whose recorded model does not match a loaded one.
+///
+/// `loaded_models` maps each loaded model name (primary and alternates) to its
+/// dimensions. Pinned containers are recorded with their pinned model; other
+/// containers with memories are recorded with the primary model the first
+/// time they are seen. After an approved rebuild of the primary index
+/// (`primary_rebuilt`), mismatched containers are moved to the primary model,
+/// since their primary embeddings are being regenerated with it.
+pub async fn check_container_embedding_models(
+    db: &dyn DatabaseBackend,
+    primary_model: &str,
+    loaded_models: &HashMap<String, usize>,
+    pins: &HashMap<String, String>,
+    primary_rebuilt: bool,
+) -> Result<Vec<ContainerModelMismatch>> {
+    let primary = |container_tag: &str| ContainerEmbeddingModel {
+        container_tag: container_tag.to_string(),
+        model: primary_model.to_string(),
+        dimensions: loaded_models
+            .get(primary_model)
+            .copied()
+            .unwrap_or_default(),
+    };
+
+    for (container_tag, model) in pins {
+        let Some(&dimensions) = loaded_models.get(model) else {
+            tracing::warn!(
+                container_tag = %container_tag,
+                model = %model,
+                "Pinned embedding model is not loaded; keeping the recorded model"
+            );
+            continue;
+        };
+        let pinned = ContainerEmbeddingModel {
+            container_tag: container_tag.clone(),
+            model: model.clone(),
+            dimensions,
+        };
+        if db
+            .get_container_embedding_model(container_tag)
+            .await?
+            .as_ref()
+            != Some(&pinned)
+        {
+            tracing::info!(container_tag = %container_tag, model = %model, "Pinning container embedding model");
+            db.set_container_embedding_model(&pinned).await?;
+        }
+    }
+
+    for container_tag in db.get_active_container_tags().await? {
+        if db
+            .get_container_embedding_model(&container_tag)
+            .await?
+            .is_none()
+        {
+            db.set_container_embedding_model(&primary(&container_tag))
+                .await?;
+        }
+    }
+
+    let mut records = db.list_container_embedding_models().await?;
+    if primary_rebuilt {
+        for record in records.iter_mut() {
+            if loaded_models.get(&record.model) != Some(&record.dimensions) {
+                *record = primary(&record.container_tag);
+                db.set_container_embedding_model(record).await?;
+            }
+        }
+    }
+
+    Ok(find_container_model_mismatches(&records, loaded_models))
+}
+
+/// Containers whose recorded model is not loaded, or is loaded with different
+/// dimensions than it was recorded with.
+pub fn find_container_model_mismatches(
+    records: &[ContainerEmbeddingModel],
+    loaded_models: &HashMap<String, usize>,
+) -> Vec<ContainerModelMismatch> {
+    records
+        .iter()
+        .filter_map(|record| {
+            let loaded = loaded_models.get(&record.model).copied();
+            (loaded != Some(record.dimensions)).then(|| ContainerModelMismatch {
+                container_tag: record.container_tag.clone(),
+                recorded_model: record.model.clone(),
+                recorded_dimensions: record.dimensions,
+                loaded_dimensions: loaded,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DatabaseConfig;
+    use crate::db::repository::MemoryRepository;
+    use crate::db::{Database, LibSqlBackend, MetadataStore};
+    use crate::models::Memory;
+
+    const SMALL: &str = "BAAI/bge-small-en-v1.5";
+    const BASE: &str = "BAAI/bge-base-en-v1.5";
+
+    fn loaded(models: &[(&str, usize)]) -> HashMap<String, usize> {
+        models
+            .iter()
+            .map(|(model, dims)| (model.to_string(), *dims))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_container_check_tracks_two_containers_on_different_dimensions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(&DatabaseConfig {
+            url: format!("file:{}", temp_dir.path().join("containers.db").display()),
+            auth_token: None,
+            local_path: None,
+            max_connections: 4,
+        })
+        .await
+        .unwrap();
+        let conn = db.connect().unwrap();
+        let backend = LibSqlBackend::new(db);
+
+        for (id, tag) in [("m_small", "tenant_small"), ("m_base", "tenant_base")] {
+            let mut memory = Memory::new(id.to_string(), "fact".to_string(), tag.to_string());
+            memory.container_tag = Some(tag.to_string());
+            MemoryRepository::create(&conn, &memory).await.unwrap();
+        }
+        let pins = HashMap::from([("tenant_base".to_string(), BASE.to_string())]);
+
+        let mismatches = check_container_embedding_models(
+            &backend,
+            SMALL,
+            &loaded(&[(SMALL, 384), (BASE, 768)]),
+            &pins,
+            false,
+        )
+        .await
+        .unwrap();
+        assert!(mismatches.is_empty());
+        let records = backend.list_container_embedding_models().await.unwrap();
+        assert_eq!(
+            records
+                .iter()
+                .map(|r| (r.container_tag.as_str(), r.model.as_str(), r.dimensions))
+                .collect::<Vec<_>>(),
+            vec![("tenant_base", BASE, 768), ("tenant_small", SMALL, 384)]
+        );
+
+        // Only the container on the missing model is reported.
+        let mismatches = check_container_embedding_models(
+            &backend,
+            SMALL,
+            &loaded(&[(SMALL, 384)]),
+            &HashMap::new(),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            mismatches,
+            vec![ContainerModelMismatch {
+                container_tag: "tenant_base".to_string(),
+                recorded_model: BASE.to_string(),
+                recorded_dimensions: 768,
+                loaded_dimensions: None,
+            }]
+        );
+
+        // A primary model producing different dimensions only affects the
+        // container recorded with it, until the primary index is rebuilt.
+        let mismatches = check_container_embedding_models(
+            &backend,
+            SMALL,
+            &loaded(&[(SMALL, 512), (BASE, 768)]),
+            &pins,
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].container_tag, "tenant_small");
+        assert_eq!(mismatches[0].loaded_dimensions, Some(512));
+
+        let mismatches = check_container_embedding_models(
+            &backend,
+            SMALL,
+            &loaded(&[(SMALL, 512), (BASE, 768)]),
+            &pins,
+            true,
+        )
+        .await
+        .unwrap();
+        assert!(mismatches.is_empty());
+        assert_eq!(
+            backend
+                .get_container_embedding_model("tenant_small")
+                .await
+                .unwrap()
+                .map(|r| r.dimensions),
+            Some(512)
+        );
+    }
+}
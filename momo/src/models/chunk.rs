@@ -3,6 +3,84 @@ use serde::{Deserialize, Serialize};
 
 use super::Metadata;
 
+/// Chunker that split a document into chunks.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChunkerType {
+    Text,
+    Code,
+    Markdown,
+    Webpage,
+    StructuredData,
+    /// Prose split where consecutive sentence embeddings drift apart.
+    Semantic,
+    /// Transcripts split between timed segments, keeping speaker turns whole.
+    Transcript,
+}
+
+impl std::fmt::Display for ChunkerType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Text => write!(f, "text"),
+            Self::Code => write!(f, "code"),
+            Self::Markdown => write!(f, "markdown"),
+            Self::Webpage => write!(f, "webpage"),
+            Self::StructuredData => write!(f, "structured_data"),
+            Self::Semantic => write!(f, "semantic"),
+            Self::Transcript => write!(f, "transcript"),
+        }
+    }
+}
+
+impl std::str::FromStr for ChunkerType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "code" => Ok(Self::Code),
+            "markdown" => Ok(Self::Markdown),
+            "webpage" => Ok(Self::Webpage),
+            "structured_data" => Ok(Self::StructuredData),
+            "semantic" => Ok(Self::Semantic),
+            "transcript" => Ok(Self::Transcript),
+            _ => Err(format!("Unknown chunker type: {s}")),
+        }
+    }
+}
+
+/// How prose is split into chunks.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ChunkStrategy {
+    /// Split by size and overlap, or by structure for markdown, code and data.
+    #[default]
+    Structural,
+    /// Split prose where the topic shifts, judged by sentence embeddings.
+    Semantic,
+}
+
+impl std::fmt::Display for ChunkStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Structural => write!(f, "structural"),
+            Self::Semantic => write!(f, "semantic"),
+        }
+    }
+}
+
+impl std::str::FromStr for ChunkStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "structural" => Ok(Self::Structural),
+            "semantic" => Ok(Self::Semantic),
+            _ => Err(format!("Unknown chunk strategy: {s}")),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Chunk {
     pub id: String,
@@ -11,6 +89,13 @@ pub struct Chunk {
     pub embedded_content: Option<String>,
     pub position: i32,
     pub token_count: Option<i32>,
+    /// Chunker that produced this chunk; `None` for chunks stored before it
+    /// was recorded.
+    #[serde(default)]
+    pub chunker: Option<ChunkerType>,
+    /// Programming language of code chunks, e.g. `rust`.
+    #[serde(default)]
+    pub language: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -24,6 +109,8 @@ impl Chunk {
             embedded_content: None,
             position,
             token_count: None,
+            chunker: None,
+            language: None,
             created_at: Utc::now(),
         }
     }
@@ -36,6 +123,28 @@ pub struct ChunkSearchResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rerank_score: Option<f32>,
     pub is_relevant: bool,
+    /// Position of the chunk within its document.
+    #[serde(default)]
+    pub position: i32,
+    /// Chunker that produced the chunk, when recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunker: Option<ChunkerType>,
+    /// Neighboring chunks preceding this one, in document order. Only filled
+    /// when context chunks are requested.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context_before: Vec<String>,
+    /// Neighboring chunks following this one, in document order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context_after: Vec<String>,
+    /// Excerpt around the sentence closest to the query, with query terms
+    /// wrapped in `<mark>`. Only filled when highlighting is requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+    /// FTS5 excerpt with the matched query terms wrapped in the configured
+    /// highlight markers. Only filled for keyword-matched chunks when
+    /// highlighting is requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,4 +155,30 @@ pub struct ChunkWithDocument {
     pub document_title: Option<String>,
     pub document_metadata: Metadata,
     pub score: f32,
+    pub position: i32,
+    pub chunker: Option<ChunkerType>,
+    /// FTS5 excerpt of a keyword hit searched with highlight markers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunker_type_round_trips_through_strings() {
+        for chunker in [
+            ChunkerType::Text,
+            ChunkerType::Code,
+            ChunkerType::Markdown,
+            ChunkerType::Webpage,
+            ChunkerType::StructuredData,
+            ChunkerType::Semantic,
+            ChunkerType::Transcript,
+        ] {
+            assert_eq!(chunker.to_string().parse::<ChunkerType>(), Ok(chunker));
+        }
+        assert!("tokenizer".parse::<ChunkerType>().is_err());
+    }
 }
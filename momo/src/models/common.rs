@@ -15,6 +15,8 @@ pub enum ProcessingStatus {
     Indexing,
     Done,
     Failed,
+    /// Failed too many times to be processed again without an admin requeue.
+    DeadLetter,
 }
 
 impl std::fmt::Display for ProcessingStatus {
@@ -28,6 +30,7 @@ impl std::fmt::Display for ProcessingStatus {
             Self::Indexing => write!(f, "indexing"),
             Self::Done => write!(f, "done"),
             Self::Failed => write!(f, "failed"),
+            Self::DeadLetter => write!(f, "dead_letter"),
         }
     }
 }
@@ -45,6 +48,7 @@ impl std::str::FromStr for ProcessingStatus {
             "indexing" => Ok(Self::Indexing),
             "done" => Ok(Self::Done),
             "failed" => Ok(Self::Failed),
+            "dead_letter" => Ok(Self::DeadLetter),
             _ => Err(format!("Unknown processing status: {s}")),
         }
     }
@@ -72,6 +76,10 @@ pub enum DocumentType {
     Docx,
     Pptx,
     Xlsx,
+    /// JSON or JSONL, flattened into `key.path: value` lines.
+    Json,
+    /// EPUB ebook, extracted as markdown with one heading per chapter.
+    Epub,
     Unknown,
 }
 
@@ -96,6 +104,8 @@ impl std::fmt::Display for DocumentType {
             Self::Docx => write!(f, "docx"),
             Self::Pptx => write!(f, "pptx"),
             Self::Xlsx => write!(f, "xlsx"),
+            Self::Json => write!(f, "json"),
+            Self::Epub => write!(f, "epub"),
             Self::Unknown => write!(f, "unknown"),
         }
     }
@@ -124,6 +134,8 @@ impl std::str::FromStr for DocumentType {
             "docx" => Ok(Self::Docx),
             "pptx" => Ok(Self::Pptx),
             "xlsx" => Ok(Self::Xlsx),
+            "json" | "jsonl" => Ok(Self::Json),
+            "epub" => Ok(Self::Epub),
             _ => Ok(Self::Unknown),
         }
     }
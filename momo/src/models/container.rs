@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Container filter configuration for LLM-powered filtering
@@ -34,6 +35,37 @@ impl ContainerFilter {
     }
 }
 
+/// Embedding model recorded for a container, so its memories are only ever
+/// compared against query embeddings from the same model.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContainerEmbeddingModel {
+    pub container_tag: String,
+    pub model: String,
+    pub dimensions: usize,
+}
+
+/// Per-container overrides of search settings. `None` fields fall back to the
+/// global configuration.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContainerSettings {
+    pub container_tag: String,
+    /// Overrides `EPISODE_DECAY_DAYS`.
+    pub episode_decay_days: Option<f64>,
+    /// Overrides `EPISODE_DECAY_FACTOR`.
+    pub episode_decay_factor: Option<f64>,
+}
+
+/// Endpoint notified when a document in `container_tag` finishes processing.
+/// Payloads are signed with `secret`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: String,
+    pub container_tag: String,
+    pub url: String,
+    pub secret: String,
+    pub created_at: DateTime<Utc>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
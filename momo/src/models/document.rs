@@ -54,6 +54,374 @@ impl Document {
     }
 }
 
+/// What to do when a new document is a near-duplicate of an existing one.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum NearDuplicateAction {
+    /// Log a warning and ingest the document as usual.
+    #[default]
+    Warn,
+    /// Refuse to ingest the document.
+    Reject,
+    /// Ingest the document and record the existing one as its previous revision.
+    Link,
+}
+
+impl std::fmt::Display for NearDuplicateAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Warn => write!(f, "warn"),
+            Self::Reject => write!(f, "reject"),
+            Self::Link => write!(f, "link"),
+        }
+    }
+}
+
+impl std::str::FromStr for NearDuplicateAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "warn" => Ok(Self::Warn),
+            "reject" => Ok(Self::Reject),
+            "link" => Ok(Self::Link),
+            _ => Err(format!("Unknown near-duplicate action: {s}")),
+        }
+    }
+}
+
+/// How structured data (CSV, XLSX) with more rows than fit in the chunk limit
+/// is indexed.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum StructuredDataOverflow {
+    /// Index rows sampled evenly across the whole file.
+    #[default]
+    Sample,
+    /// Index every row, packing more rows into each chunk.
+    Aggregate,
+    /// Index only the leading rows.
+    Truncate,
+}
+
+impl std::fmt::Display for StructuredDataOverflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Sample => write!(f, "sample"),
+            Self::Aggregate => write!(f, "aggregate"),
+            Self::Truncate => write!(f, "truncate"),
+        }
+    }
+}
+
+impl std::str::FromStr for StructuredDataOverflow {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "sample" => Ok(Self::Sample),
+            "aggregate" => Ok(Self::Aggregate),
+            "truncate" => Ok(Self::Truncate),
+            _ => Err(format!("Unknown structured data overflow strategy: {s}")),
+        }
+    }
+}
+
+/// Metadata key holding the [`StructuredDataOverflowReport`] of structured data
+/// that exceeded the chunk limit.
+pub const STRUCTURED_DATA_OVERFLOW_METADATA_KEY: &str = "structured_data_overflow";
+
+/// What was indexed of structured data that exceeded the chunk limit.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StructuredDataOverflowReport {
+    pub strategy: StructuredDataOverflow,
+    /// Data rows in the document, excluding the header.
+    pub total_rows: usize,
+    /// Data rows that made it into chunks.
+    pub indexed_rows: usize,
+}
+
+/// Kind of personally identifiable information detected at ingestion.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiCategory {
+    Email,
+    Phone,
+    CreditCard,
+    Ssn,
+}
+
+impl PiiCategory {
+    pub const ALL: [PiiCategory; 4] = [
+        PiiCategory::Email,
+        PiiCategory::Phone,
+        PiiCategory::CreditCard,
+        PiiCategory::Ssn,
+    ];
+}
+
+impl std::fmt::Display for PiiCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Email => write!(f, "email"),
+            Self::Phone => write!(f, "phone"),
+            Self::CreditCard => write!(f, "credit_card"),
+            Self::Ssn => write!(f, "ssn"),
+        }
+    }
+}
+
+impl std::str::FromStr for PiiCategory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "email" => Ok(Self::Email),
+            "phone" => Ok(Self::Phone),
+            "credit_card" => Ok(Self::CreditCard),
+            "ssn" => Ok(Self::Ssn),
+            _ => Err(format!("Unknown PII category: {s}")),
+        }
+    }
+}
+
+/// What happens to PII detected in ingested documents.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiAction {
+    /// Replace each match with a `[REDACTED_<CATEGORY>]` placeholder before
+    /// the content is stored, chunked and embedded.
+    #[default]
+    Redact,
+    /// Keep the content unchanged and only record what was found.
+    Flag,
+}
+
+impl std::fmt::Display for PiiAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Redact => write!(f, "redact"),
+            Self::Flag => write!(f, "flag"),
+        }
+    }
+}
+
+impl std::str::FromStr for PiiAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "redact" => Ok(Self::Redact),
+            "flag" => Ok(Self::Flag),
+            _ => Err(format!("Unknown PII action: {s}")),
+        }
+    }
+}
+
+/// Metadata key holding the [`PiiReport`] of documents in which PII was found.
+pub const PII_METADATA_KEY: &str = "pii";
+
+/// PII found in a document's extracted text.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PiiReport {
+    pub action: PiiAction,
+    /// Number of matches per category; categories without matches are omitted.
+    pub counts: std::collections::BTreeMap<PiiCategory, usize>,
+}
+
+/// Metadata key holding the author read from the source file's own metadata.
+pub const AUTHOR_METADATA_KEY: &str = "author";
+
+/// Metadata key overriding the configured chunk size for one document.
+pub const CHUNK_SIZE_METADATA_KEY: &str = "chunk_size";
+/// Metadata key overriding the configured chunk overlap for one document.
+pub const CHUNK_OVERLAP_METADATA_KEY: &str = "chunk_overlap";
+
+/// Chunk size and overlap a document sets in its metadata, each falling back
+/// to the configured default when absent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChunkSizeOverride {
+    pub chunk_size: Option<usize>,
+    pub chunk_overlap: Option<usize>,
+}
+
+impl ChunkSizeOverride {
+    /// Read the overrides from document metadata. Keys that are present must
+    /// hold non-negative integers.
+    pub fn from_metadata(metadata: &Metadata) -> std::result::Result<Self, String> {
+        let read = |key: &str| match metadata.get(key) {
+            None | Some(serde_json::Value::Null) => Ok(None),
+            Some(value) => value
+                .as_u64()
+                .map(|n| Some(n as usize))
+                .ok_or_else(|| format!("{key} must be a non-negative integer")),
+        };
+
+        Ok(Self {
+            chunk_size: read(CHUNK_SIZE_METADATA_KEY)?,
+            chunk_overlap: read(CHUNK_OVERLAP_METADATA_KEY)?,
+        })
+    }
+
+    /// Whether the document keeps the configured chunk sizes.
+    pub fn is_empty(&self) -> bool {
+        self.chunk_size.is_none() && self.chunk_overlap.is_none()
+    }
+}
+
+/// Metadata key holding the [`ExtractionConfidence`] of OCR or transcription output.
+pub const EXTRACTION_CONFIDENCE_METADATA_KEY: &str = "extraction_confidence";
+/// Metadata key set to `true` when extraction confidence fell below the
+/// configured threshold.
+pub const LOW_CONFIDENCE_METADATA_KEY: &str = "low_confidence";
+/// Metadata key holding the OCR text blocks of an image document, with
+/// bounding boxes, when `OCR_INCLUDE_LAYOUT` is enabled.
+pub const OCR_LAYOUT_METADATA_KEY: &str = "ocr_layout";
+/// Metadata key set to `true` when an image's OCR text was discarded for
+/// falling below `OCR_MIN_DOCUMENT_CONFIDENCE`.
+pub const OCR_DISCARDED_METADATA_KEY: &str = "ocr_discarded";
+/// Metadata key holding the timed, optionally speaker-labelled segments of an
+/// audio or video transcript, when `TRANSCRIPTION_INCLUDE_SEGMENTS` is enabled.
+pub const TRANSCRIPT_SEGMENTS_METADATA_KEY: &str = "transcript_segments";
+/// Metadata key holding the spoken language detected in an audio or video
+/// document, when `TRANSCRIPTION_LANGUAGE=auto` or the API reports it.
+pub const TRANSCRIPT_LANGUAGE_METADATA_KEY: &str = "transcript_language";
+
+/// Confidence reported by OCR or transcription for media-derived text, from
+/// 0.0 to 1.0, aggregated over words (OCR) or segments (transcription).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct ExtractionConfidence {
+    pub average: f32,
+    pub minimum: f32,
+}
+
+impl ExtractionConfidence {
+    /// Aggregate per-word or per-segment scores; `None` when there are none.
+    /// Scores are clamped to `0.0..=1.0` and non-finite ones ignored.
+    pub fn from_scores(scores: impl IntoIterator<Item = f32>) -> Option<Self> {
+        let (count, sum, minimum) = scores
+            .into_iter()
+            .filter(|score| score.is_finite())
+            .map(|score| score.clamp(0.0, 1.0))
+            .fold((0usize, 0.0f32, 1.0f32), |(count, sum, min), score| {
+                (count + 1, sum + score, min.min(score))
+            });
+
+        (count > 0).then(|| Self {
+            average: sum / count as f32,
+            minimum,
+        })
+    }
+
+    /// Read the confidence stored in document metadata, if any.
+    pub fn from_metadata(metadata: &Metadata) -> Option<Self> {
+        metadata
+            .get(EXTRACTION_CONFIDENCE_METADATA_KEY)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+}
+
+/// Whether document metadata flags the extracted text as low-confidence.
+pub fn is_low_confidence(metadata: &Metadata) -> bool {
+    metadata
+        .get(LOW_CONFIDENCE_METADATA_KEY)
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+}
+
+/// Metadata key holding a document's [`DocumentCategory`].
+pub const CATEGORY_METADATA_KEY: &str = "category";
+
+/// Finer-grained content category assigned by LLM classification. Unlike
+/// [`DocumentType`], which describes the source format, this describes what
+/// the document is about, and is stored in metadata under
+/// [`CATEGORY_METADATA_KEY`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentCategory {
+    Article,
+    Contract,
+    Documentation,
+    Email,
+    Invoice,
+    MeetingNotes,
+    PersonalNotes,
+    Recipe,
+    Report,
+    ResearchPaper,
+    Resume,
+    Transcript,
+    Other,
+}
+
+impl DocumentCategory {
+    pub const ALL: [Self; 13] = [
+        Self::Article,
+        Self::Contract,
+        Self::Documentation,
+        Self::Email,
+        Self::Invoice,
+        Self::MeetingNotes,
+        Self::PersonalNotes,
+        Self::Recipe,
+        Self::Report,
+        Self::ResearchPaper,
+        Self::Resume,
+        Self::Transcript,
+        Self::Other,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Article => "article",
+            Self::Contract => "contract",
+            Self::Documentation => "documentation",
+            Self::Email => "email",
+            Self::Invoice => "invoice",
+            Self::MeetingNotes => "meeting_notes",
+            Self::PersonalNotes => "personal_notes",
+            Self::Recipe => "recipe",
+            Self::Report => "report",
+            Self::ResearchPaper => "research_paper",
+            Self::Resume => "resume",
+            Self::Transcript => "transcript",
+            Self::Other => "other",
+        }
+    }
+}
+
+impl std::fmt::Display for DocumentCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for DocumentCategory {
+    type Err = String;
+
+    /// Accepts labels in any case, with spaces or hyphens in place of
+    /// underscores and surrounding quotes or punctuation, as LLMs tend to
+    /// return them.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s
+            .trim()
+            .trim_matches(|c: char| !c.is_alphanumeric())
+            .to_lowercase()
+            .replace([' ', '-'], "_");
+        Self::ALL
+            .into_iter()
+            .find(|category| category.as_str() == normalized)
+            .ok_or_else(|| format!("Unknown document category: {s}"))
+    }
+}
+
+/// An existing document found to be nearly identical to incoming content.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NearDuplicate {
+    pub document_id: String,
+    pub similarity: f32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 #[allow(dead_code)]
 pub struct CreateDocumentRequest {
@@ -65,7 +433,7 @@ pub struct CreateDocumentRequest {
     pub entity_context: Option<String>,
     pub metadata: Option<Metadata>,
     pub source_path: Option<String>,
-    /// Content type hint for base64-encoded files: "docx", "xlsx", "pptx", "csv", "pdf"
+    /// Content type hint for base64-encoded files: "docx", "xlsx", "pptx", "csv", "pdf", "epub" (or "json" for raw JSON/JSONL text)
     pub content_type: Option<String>,
     /// When true, extract memories from document content after processing
     #[serde(default)]
@@ -115,9 +483,26 @@ pub struct UpdateDocumentRequest {
     pub container_tags: Option<Vec<String>>,
 }
 
+/// Selects documents for bulk deletion. Conditions are ANDed; metadata values
+/// must be scalars and match `metadata.<key>` exactly.
+#[derive(Debug, Clone, Default)]
+pub struct DocumentDeleteFilter {
+    pub container_tag: Option<String>,
+    pub metadata: Metadata,
+}
+
+impl DocumentDeleteFilter {
+    /// True when the filter has no conditions and would match every document.
+    pub fn is_empty(&self) -> bool {
+        self.container_tag.is_none() && self.metadata.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ListDocumentsRequest {
     pub container_tags: Option<Vec<String>>,
+    /// Only list documents whose `metadata.category` matches.
+    pub category: Option<String>,
     pub filters: Option<String>,
     pub limit: Option<u32>,
     pub page: Option<u32>,
@@ -176,10 +561,142 @@ pub struct ProcessingDocument {
     pub created_at: DateTime<Utc>,
 }
 
+/// A document that stopped being retried after failing too many times.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetterDocument {
+    pub id: String,
+    pub title: Option<String>,
+    pub retry_count: u32,
+    /// Error from the last failed attempt.
+    pub error_message: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Processing backlog and recent throughput, used to size workers.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessingStats {
+    /// Number of documents in each status; statuses without documents are absent.
+    pub counts: Vec<(ProcessingStatus, u64)>,
+    /// Creation time of the oldest document not yet `done` or `failed`.
+    pub oldest_pending_at: Option<DateTime<Utc>>,
+    /// Durations of the most recent processing runs in milliseconds, newest first.
+    pub recent_durations_ms: Vec<u64>,
+}
+
+impl ProcessingStats {
+    pub fn average_duration_ms(&self) -> Option<u64> {
+        let count = self.recent_durations_ms.len() as u64;
+        (count > 0).then(|| self.recent_durations_ms.iter().sum::<u64>() / count)
+    }
+
+    /// 95th percentile run duration (nearest rank).
+    pub fn p95_duration_ms(&self) -> Option<u64> {
+        let mut durations = self.recent_durations_ms.clone();
+        if durations.is_empty() {
+            return None;
+        }
+        durations.sort_unstable();
+        let rank = (durations.len() * 95).div_ceil(100);
+        Some(durations[rank - 1])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn processing_stats_duration_summary() {
+        let mut stats = ProcessingStats::default();
+        assert_eq!(stats.average_duration_ms(), None);
+        assert_eq!(stats.p95_duration_ms(), None);
+
+        stats.recent_durations_ms = (1..=20).rev().map(|i| i * 100).collect();
+        assert_eq!(stats.average_duration_ms(), Some(1050));
+        assert_eq!(stats.p95_duration_ms(), Some(1900));
+
+        stats.recent_durations_ms = vec![40];
+        assert_eq!(stats.p95_duration_ms(), Some(40));
+    }
+
+    #[test]
+    fn test_extraction_confidence_from_scores() {
+        let confidence = ExtractionConfidence::from_scores([0.9, 0.5, f32::NAN, 1.4]).unwrap();
+        assert!((confidence.average - 0.8).abs() < 1e-6);
+        assert_eq!(confidence.minimum, 0.5);
+
+        assert!(ExtractionConfidence::from_scores(std::iter::empty()).is_none());
+    }
+
+    #[test]
+    fn test_extraction_confidence_metadata_round_trip() {
+        let mut metadata = Metadata::new();
+        assert!(ExtractionConfidence::from_metadata(&metadata).is_none());
+        assert!(!is_low_confidence(&metadata));
+
+        metadata.insert(
+            EXTRACTION_CONFIDENCE_METADATA_KEY.to_string(),
+            serde_json::json!({"average": 0.4, "minimum": 0.1}),
+        );
+        metadata.insert(
+            LOW_CONFIDENCE_METADATA_KEY.to_string(),
+            serde_json::json!(true),
+        );
+
+        let confidence = ExtractionConfidence::from_metadata(&metadata).unwrap();
+        assert_eq!(confidence.minimum, 0.1);
+        assert!(is_low_confidence(&metadata));
+    }
+
+    #[test]
+    fn test_chunk_size_override_from_metadata() {
+        let mut metadata = Metadata::new();
+        assert!(ChunkSizeOverride::from_metadata(&metadata)
+            .unwrap()
+            .is_empty());
+
+        metadata.insert(CHUNK_SIZE_METADATA_KEY.to_string(), serde_json::json!(256));
+        let overrides = ChunkSizeOverride::from_metadata(&metadata).unwrap();
+        assert_eq!(overrides.chunk_size, Some(256));
+        assert_eq!(overrides.chunk_overlap, None);
+
+        metadata.insert(
+            CHUNK_OVERLAP_METADATA_KEY.to_string(),
+            serde_json::json!("32"),
+        );
+        assert!(ChunkSizeOverride::from_metadata(&metadata).is_err());
+        metadata.insert(
+            CHUNK_OVERLAP_METADATA_KEY.to_string(),
+            serde_json::json!(-1),
+        );
+        assert!(ChunkSizeOverride::from_metadata(&metadata).is_err());
+    }
+
+    #[test]
+    fn test_document_category_parses_llm_labels() {
+        assert_eq!(
+            "meeting_notes".parse::<DocumentCategory>(),
+            Ok(DocumentCategory::MeetingNotes)
+        );
+        assert_eq!(
+            " \"Research Paper\".".parse::<DocumentCategory>(),
+            Ok(DocumentCategory::ResearchPaper)
+        );
+        assert_eq!(
+            "RECIPE".parse::<DocumentCategory>(),
+            Ok(DocumentCategory::Recipe)
+        );
+        assert!("spreadsheet".parse::<DocumentCategory>().is_err());
+
+        for category in DocumentCategory::ALL {
+            assert_eq!(category.as_str().parse::<DocumentCategory>(), Ok(category));
+            assert_eq!(
+                serde_json::to_value(category).unwrap(),
+                serde_json::json!(category.as_str())
+            );
+        }
+    }
+
     #[test]
     fn test_create_document_request_with_extract_memories_true() {
         let json = r#"{"content": "test content", "extract_memories": true}"#;
@@ -1,6 +1,10 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use quick_xml::escape::escape;
 use serde::{Deserialize, Serialize};
 
-use super::Metadata;
+use super::{GraphData, MemoryType, Metadata};
 
 /// Graph node types for D3.js visualization
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +25,19 @@ pub enum GraphEdgeType {
     Sources,
 }
 
+/// Which edges a graph traversal follows from each memory
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphDirection {
+    /// The memory's own relations to other memories
+    Outbound,
+    /// Relations other memories have to this one
+    Inbound,
+    /// Both directions
+    #[default]
+    Both,
+}
+
 /// A node in the knowledge graph
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GraphNode {
@@ -33,8 +50,28 @@ pub struct GraphNode {
 
     /// Additional metadata for visualization (title, color, size, etc.)
     pub metadata: Metadata,
+
+    /// Centrality score, when requested (see [`GraphResponse::score_centrality`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub centrality: Option<f64>,
 }
 
+/// How to score node centrality in a graph response
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphCentrality {
+    /// Nodes linked to the node in either direction, divided by the number
+    /// of other nodes
+    Degree,
+    /// PageRank over the directed links; scores sum to 1
+    PageRank,
+}
+
+/// PageRank damping factor
+const PAGE_RANK_DAMPING: f64 = 0.85;
+/// PageRank power iterations; plenty for graphs of a few hundred nodes
+const PAGE_RANK_ITERATIONS: usize = 20;
+
 /// An edge in the knowledge graph
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GraphEdge {
@@ -73,6 +110,34 @@ impl GraphResponse {
         Self { nodes, links }
     }
 
+    /// Set every node's `centrality` using `measure`. Links to ids that
+    /// aren't nodes of this graph, and self-links, are ignored.
+    pub fn score_centrality(&mut self, measure: GraphCentrality) {
+        let index: HashMap<&str, usize> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.id.as_str(), i))
+            .collect();
+        let edges: Vec<(usize, usize)> = self
+            .links
+            .iter()
+            .filter_map(|link| {
+                let source = *index.get(link.source.as_str())?;
+                let target = *index.get(link.target.as_str())?;
+                (source != target).then_some((source, target))
+            })
+            .collect();
+
+        let scores = match measure {
+            GraphCentrality::Degree => degree_centrality(self.nodes.len(), &edges),
+            GraphCentrality::PageRank => page_rank(self.nodes.len(), &edges),
+        };
+        for (node, score) in self.nodes.iter_mut().zip(scores) {
+            node.centrality = Some(score);
+        }
+    }
+
     /// Add a node to the graph
     #[allow(dead_code)] // Public API
     pub fn add_node(&mut self, node: GraphNode) {
@@ -100,6 +165,7 @@ impl GraphNode {
             id,
             node_type,
             metadata: Metadata::new(),
+            centrality: None,
         }
     }
 
@@ -109,6 +175,7 @@ impl GraphNode {
             id,
             node_type,
             metadata,
+            centrality: None,
         }
     }
 }
@@ -125,9 +192,254 @@ impl GraphEdge {
     }
 }
 
+impl GraphEdgeType {
+    /// Edge label in DOT and GEXF exports
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Updates => "updates",
+            Self::RelatesTo => "relatesto",
+            Self::ConflictsWith => "conflictswith",
+            Self::DerivedFrom => "derivedfrom",
+            Self::Sources => "sources",
+        }
+    }
+
+    /// Edge color in DOT and GEXF exports, as RGB
+    pub fn color(&self) -> (u8, u8, u8) {
+        match self {
+            Self::Updates => (0x1f, 0x77, 0xb4),
+            Self::RelatesTo => (0x7f, 0x7f, 0x7f),
+            Self::ConflictsWith => (0xd6, 0x27, 0x28),
+            Self::DerivedFrom => (0x94, 0x67, 0xbd),
+            Self::Sources => (0x2c, 0xa0, 0x2c),
+        }
+    }
+}
+
+/// Node fill color in DOT and GEXF exports, as RGB
+fn memory_type_color(memory_type: MemoryType) -> (u8, u8, u8) {
+    match memory_type {
+        MemoryType::Fact => (0xae, 0xc7, 0xe8),
+        MemoryType::Preference => (0xff, 0xbb, 0x78),
+        MemoryType::Episode => (0x98, 0xdf, 0x8a),
+    }
+}
+
+const DOCUMENT_COLOR: (u8, u8, u8) = (0xdd, 0xdd, 0xdd);
+
+/// Characters of memory content used as a node label
+const NODE_LABEL_CHARS: usize = 60;
+
+fn node_label(text: &str) -> String {
+    let mut chars = text.chars();
+    let mut label: String = chars.by_ref().take(NODE_LABEL_CHARS).collect();
+    if chars.next().is_some() {
+        label.push('…');
+    }
+    label
+}
+
+fn hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Quote `value` as a DOT string.
+fn dot_quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => {}
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+impl GraphData {
+    /// GraphViz DOT serialization. Memories are ellipses filled by memory
+    /// type, documents are boxes, and edges are labeled and colored by type.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph momo {\n");
+        dot.push_str("  node [style=filled];\n");
+
+        for memory in &self.memories {
+            let _ = writeln!(
+                dot,
+                "  {} [label={}, shape=ellipse, fillcolor={}, class={}];",
+                dot_quote(&memory.id),
+                dot_quote(&node_label(&memory.memory)),
+                dot_quote(&hex(memory_type_color(memory.memory_type))),
+                dot_quote(&memory.memory_type.to_string()),
+            );
+        }
+        for document in &self.documents {
+            let _ = writeln!(
+                dot,
+                "  {} [label={}, shape=box, fillcolor={}, class=\"document\"];",
+                dot_quote(&document.id),
+                dot_quote(&node_label(
+                    document.title.as_deref().unwrap_or(&document.id)
+                )),
+                dot_quote(&hex(DOCUMENT_COLOR)),
+            );
+        }
+        for edge in &self.edges {
+            let _ = writeln!(
+                dot,
+                "  {} -> {} [label={}, color={}];",
+                dot_quote(&edge.source),
+                dot_quote(&edge.target),
+                dot_quote(edge.edge_type.label()),
+                dot_quote(&hex(edge.edge_type.color())),
+            );
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// GEXF 1.3 serialization, with `kind`, `memory_type` and
+    /// `container_tag` node attributes and colors matching [`Self::to_dot`].
+    /// Edges to nodes outside the graph are left out, as GEXF requires both
+    /// ends to be declared.
+    pub fn to_gexf(&self) -> String {
+        let mut gexf = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<gexf xmlns=\"http://gexf.net/1.3\" xmlns:viz=\"http://gexf.net/1.3/viz\" version=\"1.3\">\n",
+            "  <graph defaultedgetype=\"directed\">\n",
+            "    <attributes class=\"node\">\n",
+            "      <attribute id=\"kind\" title=\"kind\" type=\"string\"/>\n",
+            "      <attribute id=\"memory_type\" title=\"memory_type\" type=\"string\"/>\n",
+            "      <attribute id=\"container_tag\" title=\"container_tag\" type=\"string\"/>\n",
+            "    </attributes>\n",
+            "    <nodes>\n",
+        ));
+
+        let mut node_ids: HashSet<&str> = HashSet::new();
+        for memory in &self.memories {
+            node_ids.insert(&memory.id);
+            let (r, g, b) = memory_type_color(memory.memory_type);
+            let _ = writeln!(
+                gexf,
+                "      <node id=\"{}\" label=\"{}\">",
+                escape(&memory.id),
+                escape(node_label(&memory.memory)),
+            );
+            gexf.push_str("        <attvalues>\n");
+            gexf.push_str("          <attvalue for=\"kind\" value=\"memory\"/>\n");
+            let _ = writeln!(
+                gexf,
+                "          <attvalue for=\"memory_type\" value=\"{}\"/>",
+                memory.memory_type
+            );
+            if let Some(ref tag) = memory.container_tag {
+                let _ = writeln!(
+                    gexf,
+                    "          <attvalue for=\"container_tag\" value=\"{}\"/>",
+                    escape(tag)
+                );
+            }
+            gexf.push_str("        </attvalues>\n");
+            let _ = writeln!(gexf, "        <viz:color r=\"{r}\" g=\"{g}\" b=\"{b}\"/>");
+            gexf.push_str("      </node>\n");
+        }
+        for document in &self.documents {
+            node_ids.insert(&document.id);
+            let (r, g, b) = DOCUMENT_COLOR;
+            let _ = writeln!(
+                gexf,
+                "      <node id=\"{}\" label=\"{}\">",
+                escape(&document.id),
+                escape(node_label(
+                    document.title.as_deref().unwrap_or(&document.id)
+                )),
+            );
+            gexf.push_str("        <attvalues>\n");
+            gexf.push_str("          <attvalue for=\"kind\" value=\"document\"/>\n");
+            gexf.push_str("        </attvalues>\n");
+            let _ = writeln!(gexf, "        <viz:color r=\"{r}\" g=\"{g}\" b=\"{b}\"/>");
+            gexf.push_str("      </node>\n");
+        }
+        gexf.push_str("    </nodes>\n    <edges>\n");
+
+        let edges = self.edges.iter().filter(|edge| {
+            node_ids.contains(edge.source.as_str()) && node_ids.contains(edge.target.as_str())
+        });
+        for (i, edge) in edges.enumerate() {
+            let (r, g, b) = edge.edge_type.color();
+            let _ = writeln!(
+                gexf,
+                "      <edge id=\"{i}\" source=\"{}\" target=\"{}\" label=\"{}\">",
+                escape(&edge.source),
+                escape(&edge.target),
+                edge.edge_type.label(),
+            );
+            let _ = writeln!(gexf, "        <viz:color r=\"{r}\" g=\"{g}\" b=\"{b}\"/>");
+            gexf.push_str("      </edge>\n");
+        }
+
+        gexf.push_str("    </edges>\n  </graph>\n</gexf>\n");
+        gexf
+    }
+}
+
+/// Number of distinct neighbors of each of `n` nodes over `edges`, in
+/// either direction, normalized by `n - 1`.
+fn degree_centrality(n: usize, edges: &[(usize, usize)]) -> Vec<f64> {
+    if n <= 1 {
+        return vec![0.0; n];
+    }
+    let pairs: HashSet<(usize, usize)> = edges
+        .iter()
+        .map(|&(source, target)| (source.min(target), source.max(target)))
+        .collect();
+    let mut degree = vec![0usize; n];
+    for (a, b) in pairs {
+        degree[a] += 1;
+        degree[b] += 1;
+    }
+    degree
+        .into_iter()
+        .map(|d| d as f64 / (n - 1) as f64)
+        .collect()
+}
+
+/// PageRank of each of `n` nodes over the directed `edges`. Rank of nodes
+/// without outgoing edges is spread evenly over all nodes.
+fn page_rank(n: usize, edges: &[(usize, usize)]) -> Vec<f64> {
+    if n == 0 {
+        return Vec::new();
+    }
+    let mut out_degree = vec![0usize; n];
+    for &(source, _) in edges {
+        out_degree[source] += 1;
+    }
+
+    let mut rank = vec![1.0 / n as f64; n];
+    for _ in 0..PAGE_RANK_ITERATIONS {
+        let dangling: f64 = (0..n)
+            .filter(|&i| out_degree[i] == 0)
+            .map(|i| rank[i])
+            .sum();
+        let base = (1.0 - PAGE_RANK_DAMPING + PAGE_RANK_DAMPING * dangling) / n as f64;
+        let mut next = vec![base; n];
+        for &(source, target) in edges {
+            next[target] += PAGE_RANK_DAMPING * rank[source] / out_degree[source] as f64;
+        }
+        rank = next;
+    }
+    rank
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::{Document, Memory};
     use serde_json::json;
 
     #[test]
@@ -228,4 +540,259 @@ mod tests {
             "JSON should NOT contain 'edges' key"
         );
     }
+
+    /// `hub` is linked from four spokes and links to one of them.
+    fn hub_graph() -> GraphResponse {
+        let nodes = ["hub", "s1", "s2", "s3", "s4"]
+            .iter()
+            .map(|id| GraphNode::new(id.to_string(), GraphNodeType::Memory))
+            .collect();
+        let mut links: Vec<GraphEdge> = ["s1", "s2", "s3", "s4"]
+            .iter()
+            .map(|id| GraphEdge::new(id.to_string(), "hub".to_string(), GraphEdgeType::RelatesTo))
+            .collect();
+        links.push(GraphEdge::new(
+            "hub".to_string(),
+            "s1".to_string(),
+            GraphEdgeType::Updates,
+        ));
+        // Ignored: the target isn't a node of the graph
+        links.push(GraphEdge::new(
+            "s2".to_string(),
+            "elsewhere".to_string(),
+            GraphEdgeType::Sources,
+        ));
+        GraphResponse::with_data(nodes, links)
+    }
+
+    fn scores(graph: &GraphResponse) -> HashMap<&str, f64> {
+        graph
+            .nodes
+            .iter()
+            .map(|node| (node.id.as_str(), node.centrality.unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_degree_centrality_ranks_hub_first() {
+        let mut graph = hub_graph();
+        graph.score_centrality(GraphCentrality::Degree);
+        let scores = scores(&graph);
+
+        // hub and s1 link both ways, which counts once
+        assert_eq!(scores["hub"], 1.0);
+        assert_eq!(scores["s1"], 0.25);
+        assert_eq!(scores["s2"], 0.25);
+    }
+
+    #[test]
+    fn test_page_rank_ranks_hub_first() {
+        let mut graph = hub_graph();
+        graph.score_centrality(GraphCentrality::PageRank);
+        let scores = scores(&graph);
+
+        let total: f64 = scores.values().sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        for spoke in ["s1", "s2", "s3", "s4"] {
+            assert!(scores["hub"] > scores[spoke]);
+        }
+        // The hub's only outgoing link lifts s1 above the other spokes
+        assert!(scores["s1"] > scores["s2"]);
+        assert!((scores["s2"] - scores["s3"]).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_centrality_serialized_only_when_scored() {
+        let mut graph = hub_graph();
+        let json = serde_json::to_string(&graph).expect("serialize");
+        assert!(!json.contains("centrality"));
+
+        graph.score_centrality(GraphCentrality::Degree);
+        let json = serde_json::to_value(&graph).expect("serialize");
+        assert_eq!(json["nodes"][0]["centrality"], json!(1.0));
+    }
+
+    fn export_graph() -> GraphData {
+        let mut quoted = Memory::new(
+            "mem_\"quoted\"".to_string(),
+            "Said \"hi\"\nthen left C:\\temp <early> & late".to_string(),
+            "default".to_string(),
+        );
+        quoted.memory_type = MemoryType::Preference;
+        quoted.container_tag = Some("user_1".to_string());
+        let plain = Memory::new(
+            "mem_plain".to_string(),
+            "x".repeat(100),
+            "default".to_string(),
+        );
+        let mut document = Document::new("doc_1".to_string());
+        document.title = Some("Notes".to_string());
+
+        GraphData {
+            memories: vec![quoted, plain],
+            edges: vec![
+                GraphEdge::new(
+                    "mem_plain".to_string(),
+                    "mem_\"quoted\"".to_string(),
+                    GraphEdgeType::Updates,
+                ),
+                GraphEdge::new(
+                    "mem_plain".to_string(),
+                    "doc_1".to_string(),
+                    GraphEdgeType::Sources,
+                ),
+            ],
+            documents: vec![document],
+        }
+    }
+
+    /// Splits DOT into identifiers, quoted strings (unescaped) and
+    /// punctuation, failing on unterminated strings or unknown characters.
+    fn dot_tokens(dot: &str) -> Vec<String> {
+        let mut tokens = Vec::new();
+        let mut chars = dot.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                c if c.is_whitespace() => {}
+                '"' => {
+                    let mut value = String::from('"');
+                    loop {
+                        match chars.next().expect("unterminated string") {
+                            '"' => break,
+                            '\\' => value.push(chars.next().expect("dangling escape")),
+                            '\n' | '\r' => panic!("raw newline in string"),
+                            c => value.push(c),
+                        }
+                    }
+                    tokens.push(value);
+                }
+                '-' => {
+                    assert_eq!(chars.next(), Some('>'), "expected ->");
+                    tokens.push("->".to_string());
+                }
+                '{' | '}' | '[' | ']' | '=' | ',' | ';' => tokens.push(c.to_string()),
+                c if c.is_ascii_alphanumeric() || c == '_' => {
+                    let mut ident = c.to_string();
+                    while let Some(&c) = chars.peek() {
+                        if !(c.is_ascii_alphanumeric() || c == '_') {
+                            break;
+                        }
+                        ident.push(c);
+                        chars.next();
+                    }
+                    tokens.push(ident);
+                }
+                c => panic!("unexpected character {c:?}"),
+            }
+        }
+        tokens
+    }
+
+    fn is_id(token: &str) -> bool {
+        token.starts_with('"') || token.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    }
+
+    /// Parses `digraph ID { stmt* }` where each statement is a node or edge
+    /// with an attribute list, returning node ids and edges.
+    fn parse_dot(dot: &str) -> (Vec<String>, Vec<(String, String)>) {
+        let tokens = dot_tokens(dot);
+        assert_eq!(&tokens[..3], ["digraph", "momo", "{"]);
+        assert_eq!(tokens.last().map(String::as_str), Some("}"));
+
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+        let mut rest = &tokens[3..tokens.len() - 1];
+        while !rest.is_empty() {
+            let end = rest
+                .iter()
+                .position(|t| t == ";")
+                .expect("statement ends with ;");
+            let stmt = &rest[..end];
+            rest = &rest[end + 1..];
+
+            let open = stmt.iter().position(|t| t == "[").expect("attribute list");
+            assert_eq!(stmt.last().map(String::as_str), Some("]"));
+            for (i, attr) in stmt[open + 1..stmt.len() - 1]
+                .split(|t| t == ",")
+                .enumerate()
+            {
+                assert!(
+                    attr.len() == 3 && is_id(&attr[0]) && attr[1] == "=" && is_id(&attr[2]),
+                    "attribute {i} malformed: {attr:?}"
+                );
+            }
+            match &stmt[..open] {
+                [keyword] if keyword == "node" => {}
+                [id] if is_id(id) => nodes.push(id[1..].to_string()),
+                [source, arrow, target] if arrow == "->" => {
+                    edges.push((source[1..].to_string(), target[1..].to_string()))
+                }
+                other => panic!("unexpected statement {other:?}"),
+            }
+        }
+        (nodes, edges)
+    }
+
+    #[test]
+    fn test_to_dot_is_valid_and_complete() {
+        let graph = export_graph();
+        let (nodes, edges) = parse_dot(&graph.to_dot());
+
+        assert_eq!(nodes, ["mem_\"quoted\"", "mem_plain", "doc_1"]);
+        assert_eq!(
+            edges,
+            [
+                ("mem_plain".to_string(), "mem_\"quoted\"".to_string()),
+                ("mem_plain".to_string(), "doc_1".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_dot_styles_nodes_and_edges() {
+        let dot = export_graph().to_dot();
+
+        assert!(dot.contains("label=\"Said \\\"hi\\\"\\nthen left C:\\\\temp"));
+        assert!(dot.contains(&format!("{}…", "x".repeat(NODE_LABEL_CHARS))));
+        assert!(dot.contains("class=\"preference\""));
+        assert!(dot.contains("\"doc_1\" [label=\"Notes\", shape=box"));
+        assert!(dot.contains("[label=\"sources\""));
+    }
+
+    #[test]
+    fn test_to_gexf_is_well_formed_and_complete() {
+        use quick_xml::events::Event;
+
+        let mut graph = export_graph();
+        graph.edges.push(GraphEdge::new(
+            "mem_plain".to_string(),
+            "mem_elsewhere".to_string(),
+            GraphEdgeType::RelatesTo,
+        ));
+        let gexf = graph.to_gexf();
+
+        let mut reader = quick_xml::Reader::from_str(&gexf);
+        let mut node_ids = Vec::new();
+        let mut edge_count = 0;
+        loop {
+            match reader.read_event().expect("well-formed GEXF") {
+                Event::Start(e) if e.name().as_ref() == b"node" => {
+                    let id = e
+                        .try_get_attribute("id")
+                        .expect("attribute")
+                        .expect("node id")
+                        .unescape_value()
+                        .expect("unescape")
+                        .into_owned();
+                    node_ids.push(id);
+                }
+                Event::Start(e) if e.name().as_ref() == b"edge" => edge_count += 1,
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        assert_eq!(node_ids, ["mem_\"quoted\"", "mem_plain", "doc_1"]);
+        assert_eq!(edge_count, 2, "edge to an unknown node is dropped");
+    }
 }
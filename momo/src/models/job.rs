@@ -0,0 +1,150 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Kind of long-running operation tracked by a [`Job`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    /// Processing of the documents created by a batch import.
+    BatchImport,
+    /// Re-embedding of chunks whose embedding is stale or missing.
+    ChunkReembed,
+    /// Processing of one document queued again by reprocess or requeue.
+    Reprocess,
+    /// Streaming export of a container's memories and documents.
+    ContainerExport,
+}
+
+impl std::fmt::Display for JobKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BatchImport => write!(f, "batch_import"),
+            Self::ChunkReembed => write!(f, "chunk_reembed"),
+            Self::Reprocess => write!(f, "reprocess"),
+            Self::ContainerExport => write!(f, "container_export"),
+        }
+    }
+}
+
+impl std::str::FromStr for JobKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "batch_import" => Ok(Self::BatchImport),
+            "chunk_reembed" => Ok(Self::ChunkReembed),
+            "reprocess" => Ok(Self::Reprocess),
+            "container_export" => Ok(Self::ContainerExport),
+            _ => Err(format!("Unknown job kind: {s}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    #[default]
+    Queued,
+    Running,
+    Done,
+    Failed,
+}
+
+impl std::fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Queued => write!(f, "queued"),
+            Self::Running => write!(f, "running"),
+            Self::Done => write!(f, "done"),
+            Self::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+impl std::str::FromStr for JobStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "queued" => Ok(Self::Queued),
+            "running" => Ok(Self::Running),
+            "done" => Ok(Self::Done),
+            "failed" => Ok(Self::Failed),
+            _ => Err(format!("Unknown job status: {s}")),
+        }
+    }
+}
+
+/// Progress record for a long-running operation, polled by clients.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: String,
+    pub kind: JobKind,
+    pub status: JobStatus,
+    /// Units of work completed so far (documents, chunks, ...).
+    pub processed: u64,
+    /// Total units of work, when known up front.
+    pub total: Option<u64>,
+    /// Operation-specific summary, set once the job is done.
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Job {
+    /// Fraction of the work completed, between 0 and 1, when the total is
+    /// known. Finished jobs always report 1.
+    pub fn progress(&self) -> Option<f64> {
+        if self.status == JobStatus::Done {
+            return Some(1.0);
+        }
+        match self.total {
+            Some(0) => Some(0.0),
+            Some(total) => Some((self.processed as f64 / total as f64).min(1.0)),
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(status: JobStatus, processed: u64, total: Option<u64>) -> Job {
+        Job {
+            id: "job_1".to_string(),
+            kind: JobKind::BatchImport,
+            status,
+            processed,
+            total,
+            result: None,
+            error: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn progress_is_fraction_of_known_total() {
+        assert_eq!(job(JobStatus::Running, 1, Some(4)).progress(), Some(0.25));
+        assert_eq!(job(JobStatus::Queued, 0, Some(0)).progress(), Some(0.0));
+        assert_eq!(job(JobStatus::Running, 10, None).progress(), None);
+        assert_eq!(job(JobStatus::Done, 10, None).progress(), Some(1.0));
+    }
+
+    #[test]
+    fn kind_and_status_round_trip_through_strings() {
+        for kind in [JobKind::BatchImport, JobKind::ChunkReembed] {
+            assert_eq!(kind.to_string().parse::<JobKind>().unwrap(), kind);
+        }
+        for status in [
+            JobStatus::Queued,
+            JobStatus::Running,
+            JobStatus::Done,
+            JobStatus::Failed,
+        ] {
+            assert_eq!(status.to_string().parse::<JobStatus>().unwrap(), status);
+        }
+    }
+}
@@ -94,6 +94,10 @@ pub struct UpdateMemoryRequest {
     pub new_content: String,
     pub metadata: Option<Metadata>,
     pub is_static: Option<bool>,
+    /// Version the client expects the memory to be at. When set, the update fails
+    /// with a conflict if the memory has since been superseded.
+    #[serde(default)]
+    pub expected_version: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -180,6 +184,29 @@ pub struct ProfileFact {
     pub created_at: DateTime<Utc>,
 }
 
+/// Order of the facts in a user profile.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileFactSort {
+    /// Newest first.
+    #[default]
+    Recency,
+    /// Most confident first, newest first among equals. Facts without a
+    /// confidence come last.
+    Confidence,
+}
+
+/// Filtering and ordering of the facts in a user profile.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ProfileFactOptions {
+    /// Leave out facts with a lower confidence.
+    pub min_confidence: Option<f64>,
+    /// With `min_confidence`, also leave out facts without a confidence.
+    /// Otherwise they are kept, ranked as with [`ProfileFactSort::Confidence`].
+    pub exclude_unscored: bool,
+    pub sort: ProfileFactSort,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetProfileRequest {
     #[serde(alias = "containerTag")]
@@ -211,6 +238,120 @@ pub struct ProfileResponse {
     pub narrative: Option<String>,
 }
 
+/// Review state of a flagged contradiction.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ContradictionStatus {
+    #[default]
+    Open,
+    Resolved,
+}
+
+impl std::fmt::Display for ContradictionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Open => write!(f, "open"),
+            Self::Resolved => write!(f, "resolved"),
+        }
+    }
+}
+
+impl std::str::FromStr for ContradictionStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "open" => Ok(Self::Open),
+            "resolved" => Ok(Self::Resolved),
+            _ => Err(format!("Unknown contradiction status: {s}")),
+        }
+    }
+}
+
+/// Decision applied to a flagged contradiction.
+///
+/// `A` is the memory that triggered the check (`memory_id`), `B` the existing
+/// memory it conflicts with (`conflicting_memory_id`).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ContradictionResolution {
+    KeepA,
+    KeepB,
+    KeepBoth,
+}
+
+impl std::fmt::Display for ContradictionResolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::KeepA => write!(f, "keep_a"),
+            Self::KeepB => write!(f, "keep_b"),
+            Self::KeepBoth => write!(f, "keep_both"),
+        }
+    }
+}
+
+impl std::str::FromStr for ContradictionResolution {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "keep_a" => Ok(Self::KeepA),
+            "keep_b" => Ok(Self::KeepB),
+            "keep_both" => Ok(Self::KeepBoth),
+            _ => Err(format!("Unknown contradiction resolution: {s}")),
+        }
+    }
+}
+
+/// Memory change applied in the same transaction that resolves a
+/// contradiction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContradictionChange {
+    /// Both memories stay as they are.
+    None,
+    /// `kept_id` becomes the next version of `dropped_id`'s chain and records
+    /// that it updates it.
+    Supersede {
+        kept_id: String,
+        dropped_id: String,
+        root_id: String,
+        version: i32,
+    },
+    /// `dropped_id` is newer than the memory kept, so it is forgotten.
+    Forget { dropped_id: String, reason: String },
+}
+
+/// A pair of memories flagged as contradicting each other.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryContradiction {
+    pub id: String,
+    pub memory_id: String,
+    pub conflicting_memory_id: String,
+    pub container_tag: Option<String>,
+    pub reasoning: Option<String>,
+    pub status: ContradictionStatus,
+    pub resolution: Option<ContradictionResolution>,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// A flagged contradiction together with the content of both memories.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContradictionDetail {
+    pub contradiction: MemoryContradiction,
+    pub memory: Memory,
+    pub conflicting_memory: Memory,
+}
+
+/// Outcome of resolving a flagged contradiction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolveContradictionResponse {
+    pub id: String,
+    pub resolution: ContradictionResolution,
+    pub kept_memory_ids: Vec<String>,
+    pub superseded_memory_id: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
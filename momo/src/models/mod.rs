@@ -3,6 +3,7 @@ mod common;
 mod container;
 mod document;
 mod graph;
+mod job;
 mod memory;
 mod repository_types;
 mod search;
@@ -12,6 +13,7 @@ pub use common::*;
 pub use container::*;
 pub use document::*;
 pub use graph::*;
+pub use job::*;
 pub use memory::*;
 pub use repository_types::*;
 pub use search::*;
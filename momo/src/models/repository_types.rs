@@ -8,6 +8,8 @@ use super::{Document, GraphEdge, Memory};
 pub struct MemorySearchHit {
     pub memory: Memory,
     pub score: f32,
+    /// Stored importance score, if it has been computed.
+    pub importance: Option<f64>,
 }
 
 /// Graph data returned from neighborhood/container graph queries.
@@ -1,20 +1,26 @@
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
 use serde::de::{self, Deserializer};
 use serde::{Deserialize, Serialize};
 
-use super::{ChunkSearchResult, DocumentType, MemoryContext, Metadata};
+use super::{ChunkSearchResult, ChunkerType, DocumentType, MemoryContext, Metadata};
 
 /// Mode for hybrid search to determine which indices to query
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum SearchMode {
-    /// Search both documents and memories (default)
+    /// Search both documents and memories, fusing vector and keyword matches
+    /// (default)
     #[default]
     Hybrid,
     /// Search documents only
     Documents,
     /// Search memories only
     Memories,
+    /// BM25 keyword search over documents and memories, without embedding
+    /// the query
+    Keyword,
 }
 
 impl<'de> Deserialize<'de> for SearchMode {
@@ -27,13 +33,54 @@ impl<'de> Deserialize<'de> for SearchMode {
             "hybrid" => Ok(SearchMode::Hybrid),
             "documents" => Ok(SearchMode::Documents),
             "memories" => Ok(SearchMode::Memories),
+            "keyword" => Ok(SearchMode::Keyword),
             _ => Err(de::Error::custom(format!(
-                "Invalid searchMode '{raw}'. Valid modes: hybrid, documents, memories"
+                "Invalid searchMode '{raw}'. Valid modes: hybrid, documents, memories, keyword"
             ))),
         }
     }
 }
 
+/// Optional search stage that can be dropped to stay within the response time budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchStage {
+    QueryRewrite,
+    Rerank,
+    /// Retries with a lowered threshold to reach `min_results`
+    ThresholdRelaxation,
+    /// Snippet selection for `highlight`
+    Highlight,
+}
+
+/// Markers keyword highlights wrap matched query terms in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HighlightMarkers {
+    pub open: String,
+    pub close: String,
+}
+
+/// Retrieval a search switched to after its vector search found nothing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchFallback {
+    /// Full-text keyword search over the same query
+    Lexical,
+}
+
+/// Transform spreading returned scores across 0–1, for clients that apply
+/// their own thresholds. Monotonic, so ranking is unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreCalibration {
+    /// Rescale so the best result scores 1.0 and the worst 0.0. Relative to
+    /// the result set, so scores are not comparable across queries.
+    MinMax,
+    /// Logistic curve around the configured midpoint. Comparable across
+    /// queries.
+    Sigmoid,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct SearchDocumentsRequest {
     pub q: String,
@@ -51,23 +98,129 @@ pub struct SearchDocumentsRequest {
     pub rerank_top_k: Option<usize>,
     #[serde(rename = "rewriteQuery")]
     pub rewrite_query: Option<bool>,
+    /// Score multipliers keyed by container tag, applied before final sorting.
+    /// Tags without an entry keep a weight of 1.0.
+    pub container_tag_weights: Option<HashMap<String, f32>>,
+    /// Number of neighboring chunks to attach before and after each returned
+    /// chunk, capped at `MAX_CONTEXT_CHUNKS`.
+    pub context_chunks: Option<u32>,
+    /// Drop documents flagged `low_confidence` after OCR or transcription.
+    pub exclude_low_confidence: Option<bool>,
+    /// Append synonyms from the configured dictionary to the query before
+    /// embedding it.
+    pub expand_synonyms: Option<bool>,
+    /// When fewer results than this come back, retry with progressively lower
+    /// thresholds down to the configured floor. Capped at `limit`.
+    pub min_results: Option<u32>,
+    /// Only match chunks produced by one of these chunkers.
+    pub chunker_types: Option<Vec<ChunkerType>>,
+    /// Only match code chunks in one of these languages (e.g. `rust`).
+    pub code_languages: Option<Vec<String>>,
+    /// Transform the returned scores; the originals are kept in the raw field.
+    pub score_calibration: Option<ScoreCalibration>,
+    /// Opaque token from a previous response's `next_cursor`; returns the page
+    /// after it. Cannot be combined with `offset`.
+    pub cursor: Option<String>,
+    /// Ranked results to skip when paging without a `cursor`.
+    pub offset: Option<u32>,
+    /// Re-select matching chunks by Maximal Marginal Relevance so that
+    /// near-duplicate chunks do not crowd out other results.
+    pub diversify: Option<bool>,
+    /// MMR trade-off between relevance (1.0) and novelty (0.0). Defaults to
+    /// `DEFAULT_DIVERSITY_LAMBDA`.
+    pub diversity_lambda: Option<f32>,
+    /// Fill each returned chunk's `snippet` with the passage that best
+    /// matches the query, and `highlight` of keyword-matched chunks with
+    /// their FTS5 excerpt.
+    pub highlight: Option<bool>,
 }
 
+/// Metadata filters, either a nested [`FilterExpr`] or the older flat form.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SearchFilters {
+#[serde(untagged)]
+pub enum SearchFilters {
+    Expr(FilterExpr),
+    /// Every `AND` condition and at least one `OR` condition must match.
+    Flat {
+        #[serde(rename = "AND")]
+        and: Option<Vec<FilterCondition>>,
+        #[serde(rename = "OR")]
+        or: Option<Vec<FilterCondition>>,
+    },
+}
+
+impl SearchFilters {
+    /// The filters as an expression tree; the flat form becomes an `AND` of
+    /// its `AND` and `OR` groups.
+    pub fn to_expr(&self) -> FilterExpr {
+        match self {
+            Self::Expr(expr) => expr.clone(),
+            Self::Flat { and, or } => {
+                let conditions = |conditions: &Vec<FilterCondition>| -> Vec<FilterExpr> {
+                    conditions
+                        .iter()
+                        .cloned()
+                        .map(FilterExpr::Condition)
+                        .collect()
+                };
+                let mut groups = Vec::new();
+                if let Some(and) = and {
+                    groups.push(FilterExpr::And(conditions(and)));
+                }
+                if let Some(or) = or {
+                    groups.push(FilterExpr::Or(conditions(or)));
+                }
+                FilterExpr::And(groups)
+            }
+        }
+    }
+}
+
+/// Boolean expression over metadata conditions, e.g.
+/// `{"OR": [{"AND": [a, b]}, {"NOT": c}]}` for `(a AND b) OR (NOT c)`.
+/// An empty `AND` matches everything and an empty `OR` nothing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum FilterExpr {
     #[serde(rename = "AND")]
-    pub and: Option<Vec<FilterCondition>>,
+    And(Vec<FilterExpr>),
     #[serde(rename = "OR")]
-    pub or: Option<Vec<FilterCondition>>,
+    Or(Vec<FilterExpr>),
+    #[serde(rename = "NOT")]
+    Not(Box<FilterExpr>),
+    #[serde(untagged)]
+    Condition(FilterCondition),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FilterCondition {
+    /// Metadata key, or a dot path into nested metadata (`author.name`, `tags.0`)
     pub key: String,
-    pub value: String,
+    /// Compared against the metadata value; ignored by `Exists`.
+    #[serde(default)]
+    pub value: serde_json::Value,
     pub negate: Option<bool>,
     pub filter_type: Option<String>,
-    pub numeric_operator: Option<String>,
+    /// Comparison to apply (default `Eq`)
+    pub operator: Option<FilterOperator>,
+}
+
+/// Comparison of a metadata value against a [`FilterCondition`] value.
+/// Numbers compare numerically and strings lexicographically; a condition
+/// whose value type differs from the metadata value never matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterOperator {
+    #[default]
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    /// Substring of a string value, or element of an array value
+    Contains,
+    /// Key is present with a non-null value
+    Exists,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -77,6 +230,22 @@ pub struct SearchDocumentsResponse {
     pub timing: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rewritten_query: Option<String>,
+    /// True when optional stages were skipped to honour the response time budget
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub degraded: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_stages: Vec<SearchStage>,
+    /// Threshold the results were found with, when it was lowered to satisfy
+    /// `min_results`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relaxed_threshold: Option<f32>,
+    /// Pass as `cursor` to fetch the next page; absent on the last page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Set when the vector search found nothing and these results come
+    /// from the configured fallback instead
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<SearchFallback>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -86,6 +255,9 @@ pub struct DocumentSearchResult {
     #[serde(rename = "type")]
     pub doc_type: Option<DocumentType>,
     pub score: f32,
+    /// Uncalibrated `score`, set when a score calibration was applied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_score: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rerank_score: Option<f32>,
     pub chunks: Vec<ChunkSearchResult>,
@@ -107,6 +279,35 @@ pub struct SearchMemoriesRequest {
     pub rerank: Option<bool>,
     #[serde(rename = "rewriteQuery")]
     pub rewrite_query: Option<bool>,
+    /// Keep only the newest version of each version chain across results and
+    /// related memories. Falls back to the server default when omitted.
+    pub dedupe_version_chains: Option<bool>,
+    /// Score multipliers keyed by container tag, applied before final sorting.
+    /// Tags without an entry keep a weight of 1.0.
+    pub container_tag_weights: Option<HashMap<String, f32>>,
+    /// Only return memories whose detected language (ISO 639-3 code) matches.
+    pub language: Option<String>,
+    /// Search this alternate model's stored embeddings instead of the primary
+    /// model's. Must be one of the configured alternate embedding models.
+    pub embedding_model: Option<String>,
+    /// Rank episode memories by raw similarity, without temporal decay.
+    pub disable_decay: Option<bool>,
+    /// Append synonyms from the configured dictionary to the query before
+    /// embedding it.
+    pub expand_synonyms: Option<bool>,
+    /// When fewer results than this come back, retry with progressively lower
+    /// thresholds down to the configured floor. Capped at `limit`.
+    pub min_results: Option<u32>,
+    /// Transform the returned scores; the originals are kept in the raw field.
+    pub score_calibration: Option<ScoreCalibration>,
+    /// Opaque token from a previous response's `next_cursor`; returns the page
+    /// after it. Cannot be combined with `offset`.
+    pub cursor: Option<String>,
+    /// Ranked results to skip when paging without a `cursor`.
+    pub offset: Option<u32>,
+    /// Weight (0–1) of `updated_at` recency in each memory's score, blended
+    /// in after episode decay and before reranking.
+    pub freshness_weight: Option<f32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -123,6 +324,49 @@ pub struct HybridSearchRequest {
     #[serde(default)]
     #[serde(rename = "searchMode", alias = "search_mode")]
     pub search_mode: SearchMode,
+    /// Keep only the newest version of each memory version chain. Falls back
+    /// to the server default when omitted; document chunks are unaffected.
+    pub dedupe_version_chains: Option<bool>,
+    /// Score multipliers keyed by container tag, applied before final sorting.
+    /// Tags without an entry keep a weight of 1.0.
+    pub container_tag_weights: Option<HashMap<String, f32>>,
+    /// Only return memories whose detected language (ISO 639-3 code) matches.
+    /// Document chunks are not filtered.
+    pub language: Option<String>,
+    /// Drop document chunks from documents flagged `low_confidence` after OCR
+    /// or transcription.
+    pub exclude_low_confidence: Option<bool>,
+    /// Rank episode memories by raw similarity, without temporal decay.
+    pub disable_decay: Option<bool>,
+    /// Append synonyms from the configured dictionary to the query before
+    /// embedding it.
+    pub expand_synonyms: Option<bool>,
+    /// When fewer results than this come back, retry with progressively lower
+    /// thresholds down to the configured floor. Capped at `limit`.
+    pub min_results: Option<u32>,
+    /// Only match chunks produced by one of these chunkers.
+    pub chunker_types: Option<Vec<ChunkerType>>,
+    /// Only match code chunks in one of these languages (e.g. `rust`).
+    pub code_languages: Option<Vec<String>>,
+    /// Transform the returned scores; the originals are kept in the raw field.
+    pub score_calibration: Option<ScoreCalibration>,
+    /// Opaque token from a previous response's `next_cursor`; returns the page
+    /// after it. Cannot be combined with `offset`.
+    pub cursor: Option<String>,
+    /// Ranked results to skip when paging without a `cursor`.
+    pub offset: Option<u32>,
+    /// Multiplier for memory scores before memories and document chunks are
+    /// ranked together. Defaults to 1.0.
+    pub memory_weight: Option<f32>,
+    /// Multiplier for document chunk scores before memories and document
+    /// chunks are ranked together. Defaults to 1.0.
+    pub document_weight: Option<f32>,
+    /// Fill `highlight` of keyword-matched chunks with their FTS5 excerpt.
+    pub highlight: Option<bool>,
+    /// Leave `last_accessed` of the returned memories unchanged, for internal
+    /// searches such as the quality probe. Never read from a request body.
+    #[serde(skip)]
+    pub skip_access_tracking: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -141,6 +385,9 @@ pub struct SearchIncludeOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "forgottenMemories")]
     pub forgotten_memories: Option<bool>,
+    /// Report how episode decay changed each memory's score.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decay_debug: Option<bool>,
 }
 
 #[cfg(test)]
@@ -204,6 +451,7 @@ mod tests {
             summaries: Some(false),
             related_memories: Some(true),
             forgotten_memories: Some(true),
+            decay_debug: None,
         };
 
         let v = to_value(&opts).expect("serialize");
@@ -218,6 +466,7 @@ mod tests {
             summaries: None,
             related_memories: None,
             forgotten_memories: None,
+            decay_debug: None,
         };
 
         let v = to_value(&opts).expect("serialize");
@@ -245,6 +494,7 @@ mod tests {
             metadata: HashMap::new(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            raw_score: None,
         };
 
         let v = to_value(&result).expect("serialize");
@@ -274,6 +524,7 @@ mod tests {
             metadata: HashMap::new(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            raw_score: None,
         };
 
         let v = to_value(&result).expect("serialize");
@@ -298,6 +549,8 @@ mod tests {
             updated_at: Utc::now(),
             context: None,
             documents: None,
+            raw_similarity: None,
+            decay_debug: None,
         };
 
         let v = to_value(&result).expect("serialize");
@@ -326,6 +579,8 @@ mod tests {
             updated_at: Utc::now(),
             context: None,
             documents: None,
+            raw_similarity: None,
+            decay_debug: None,
         };
 
         let v = to_value(&result).expect("serialize");
@@ -349,6 +604,9 @@ mod tests {
             rerank_score: Some(0.92),
             metadata: HashMap::new(),
             updated_at: now,
+            chunker: None,
+            highlight: None,
+            raw_similarity: None,
         };
 
         let v = to_value(&result).expect("serialize");
@@ -380,6 +638,9 @@ mod tests {
             rerank_score: None,
             metadata: HashMap::new(),
             updated_at: now,
+            chunker: None,
+            highlight: None,
+            raw_similarity: None,
         };
 
         let v = to_value(&result).expect("serialize");
@@ -407,6 +668,9 @@ mod tests {
             rerank_score: None,
             metadata: HashMap::new(),
             updated_at: now,
+            chunker: None,
+            highlight: None,
+            raw_similarity: None,
         };
 
         let json = serde_json::to_string(&result).expect("serialize to JSON string");
@@ -430,6 +694,9 @@ mod tests {
             rerank_score: Some(0.95),
             metadata: HashMap::new(),
             updated_at: now,
+            chunker: None,
+            highlight: None,
+            raw_similarity: None,
         };
 
         let v = to_value(&result).expect("serialize");
@@ -452,6 +719,29 @@ pub struct SearchMemoriesResponse {
     pub timing: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rewritten_query: Option<String>,
+    /// True when optional stages were skipped to honour the response time budget
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub degraded: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_stages: Vec<SearchStage>,
+    /// Threshold the results were found with, when it was lowered to satisfy
+    /// `min_results`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relaxed_threshold: Option<f32>,
+    /// Pass as `cursor` to fetch the next page; absent on the last page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Set when the vector search found nothing and these results come
+    /// from the configured fallback instead
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<SearchFallback>,
+}
+
+/// Totals of a streamed memory search, reported after its last result.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SearchStreamSummary {
+    pub total: u32,
+    pub timing: u64,
 }
 
 /// Unified result type for hybrid search that can represent either a memory or a document chunk.
@@ -477,9 +767,23 @@ pub struct HybridSearchResult {
     #[serde(rename = "documentId")]
     pub document_id: Option<String>,
 
+    /// Chunker that produced the chunk, when recorded (only for chunk results)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunker: Option<ChunkerType>,
+
+    /// Keyword excerpt with matched query terms marked (only for
+    /// keyword-matched chunks when highlighting is requested)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<String>,
+
     /// Vector similarity score
     pub similarity: f32,
 
+    /// Uncalibrated `similarity`, set when a score calibration was applied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "rawSimilarity")]
+    pub raw_similarity: Option<f32>,
+
     /// Optional reranking score (if reranking was applied)
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "rerankScore")]
@@ -503,6 +807,22 @@ pub enum HybridSearchResultVariant {
     Memory(MemorySearchResult),
 }
 
+/// Multipliers applied to memory and document chunk scores in hybrid search.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HybridWeights {
+    pub memory: f32,
+    pub document: f32,
+}
+
+impl Default for HybridWeights {
+    fn default() -> Self {
+        Self {
+            memory: 1.0,
+            document: 1.0,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HybridSearchResponse {
     pub results: Vec<HybridSearchResult>,
@@ -510,6 +830,21 @@ pub struct HybridSearchResponse {
     pub timing: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rewritten_query: Option<String>,
+    /// True when optional stages were skipped to honour the response time budget
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub degraded: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_stages: Vec<SearchStage>,
+    /// Threshold the results were found with, when it was lowered to satisfy
+    /// `min_results`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relaxed_threshold: Option<f32>,
+    /// Pass as `cursor` to fetch the next page; absent on the last page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Domain weights the results were ranked with
+    #[serde(default)]
+    pub weights: HybridWeights,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -519,12 +854,31 @@ pub struct MemorySearchResult {
     pub chunk: Option<String>,
     pub metadata: Metadata,
     pub similarity: f32,
+    /// Uncalibrated `similarity`, set when a score calibration was applied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_similarity: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rerank_score: Option<f32>,
     pub version: Option<i32>,
     pub updated_at: DateTime<Utc>,
     pub context: Option<MemoryContext>,
     pub documents: Option<Vec<RelatedDocument>>,
+    /// How episode decay changed the score, when `include.decay_debug` is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decay_debug: Option<MemoryDecayDebug>,
+}
+
+/// Scores of a memory search hit before and after episode decay.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MemoryDecayDebug {
+    /// Similarity returned by the database.
+    pub db_similarity: f32,
+    /// Multiplier applied for episode decay; 1.0 for facts, preferences and
+    /// when decay is disabled.
+    pub decay_factor: f32,
+    /// `db_similarity * decay_factor`, before importance, freshness, tag
+    /// weights, reranking and calibration.
+    pub decayed_similarity: f32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
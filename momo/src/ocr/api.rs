@@ -406,6 +406,10 @@ mod tests {
             timeout_secs: 60,
             max_image_dimension: 4096,
             min_image_dimension: 50,
+            max_concurrent_jobs: 2,
+            include_layout: false,
+            min_confidence: None,
+            min_document_confidence: None,
         }
     }
 
@@ -24,7 +24,7 @@
 //!
 //! ```rust,ignore
 //! let ocr = OcrProvider::new(&config.ocr)?;
-//! let text = ocr.ocr(image_bytes).await?;
+//! let text = ocr.ocr(image_bytes).await?.text;
 //! ```
 
 mod api;
@@ -32,4 +32,4 @@ mod preprocessing;
 mod provider;
 
 pub use preprocessing::preprocess_image;
-pub use provider::OcrProvider;
+pub use provider::{OcrBlock, OcrOutput, OcrProvider};
@@ -2,11 +2,13 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use leptess::LepTess;
+use serde::Serialize;
 use tokio::sync::Mutex;
 use tracing::{info, warn};
 
 use crate::config::OcrConfig;
 use crate::error::{MomoError, Result};
+use crate::models::ExtractionConfidence;
 
 use super::api::{DeepSeekOcrClient, MistralOcrClient, OpenAiVisionClient};
 
@@ -33,6 +35,153 @@ enum OcrBackend {
     Unavailable { reason: String },
 }
 
+/// Text recognized in an image, with the engine's confidence when it reports one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcrOutput {
+    pub text: String,
+    /// Per-word confidence from Tesseract; `None` for API backends.
+    pub confidence: Option<ExtractionConfidence>,
+}
+
+/// Pixel rectangle of a text block, origin at the image's top-left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct OcrBoundingBox {
+    pub left: u32,
+    pub top: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl OcrBoundingBox {
+    fn union(self, other: Self) -> Self {
+        let left = self.left.min(other.left);
+        let top = self.top.min(other.top);
+        let right = (self.left + self.width).max(other.left + other.width);
+        let bottom = (self.top + self.height).max(other.top + other.height);
+        Self {
+            left,
+            top,
+            width: right - left,
+            height: bottom - top,
+        }
+    }
+}
+
+/// One recognized line of text and where it sits in the image.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OcrBlock {
+    pub text: String,
+    /// `None` when the backend doesn't report geometry (API backends).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bbox: Option<OcrBoundingBox>,
+    /// Mean word confidence in `[0, 1]`; `None` when not reported.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+}
+
+/// OCR output together with the text blocks it was assembled from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcrLayout {
+    pub output: OcrOutput,
+    /// Blocks in reading order.
+    pub blocks: Vec<OcrBlock>,
+}
+
+/// A word row (level 5) of Tesseract TSV output.
+struct TsvWord<'a> {
+    /// Page, block, paragraph and line numbers identifying the word's line.
+    line: (&'a str, &'a str, &'a str, &'a str),
+    bbox: Option<OcrBoundingBox>,
+    confidence: f32,
+    text: &'a str,
+}
+
+/// Word rows of Tesseract TSV output that have text and a non-negative
+/// confidence, with the confidence scaled to `[0, 1]`.
+fn tesseract_tsv_words(tsv: &str) -> impl Iterator<Item = TsvWord<'_>> {
+    tsv.lines().filter_map(|line| {
+        let columns: Vec<&str> = line.split('\t').collect();
+        if columns.len() < 12 || columns[0] != "5" || columns[11].trim().is_empty() {
+            return None;
+        }
+        let confidence: f32 = columns[10].trim().parse().ok()?;
+        if confidence < 0.0 {
+            return None;
+        }
+        let dimension = |i: usize| columns[i].trim().parse::<u32>().ok();
+        let bbox = match (dimension(6), dimension(7), dimension(8), dimension(9)) {
+            (Some(left), Some(top), Some(width), Some(height)) => Some(OcrBoundingBox {
+                left,
+                top,
+                width,
+                height,
+            }),
+            _ => None,
+        };
+        Some(TsvWord {
+            line: (columns[1], columns[2], columns[3], columns[4]),
+            bbox,
+            confidence: confidence / 100.0,
+            text: columns[11].trim(),
+        })
+    })
+}
+
+/// Aggregate word confidences from Tesseract TSV output. Only word rows
+/// (level 5) with text and a non-negative confidence are counted.
+fn tesseract_tsv_confidence(tsv: &str) -> Option<ExtractionConfidence> {
+    ExtractionConfidence::from_scores(tesseract_tsv_words(tsv).map(|word| word.confidence))
+}
+
+/// Group Tesseract TSV words into one block per recognized line, with the
+/// union of the word boxes and their mean confidence. Words below
+/// `min_confidence` are dropped; lines left without words produce no block.
+fn tesseract_tsv_blocks(tsv: &str, min_confidence: Option<f32>) -> Vec<OcrBlock> {
+    let mut blocks: Vec<OcrBlock> = Vec::new();
+    let mut current_line = None;
+    let mut word_count = 0;
+    let words = tesseract_tsv_words(tsv)
+        .filter(|word| min_confidence.map_or(true, |min| word.confidence >= min));
+    for word in words {
+        match blocks.last_mut() {
+            Some(block) if current_line == Some(word.line) => {
+                block.text.push(' ');
+                block.text.push_str(word.text);
+                block.bbox = match (block.bbox, word.bbox) {
+                    (Some(a), Some(b)) => Some(a.union(b)),
+                    (a, b) => a.or(b),
+                };
+                let total = block.confidence.unwrap_or(0.0) * word_count as f32;
+                word_count += 1;
+                block.confidence = Some((total + word.confidence) / word_count as f32);
+            }
+            _ => {
+                current_line = Some(word.line);
+                word_count = 1;
+                blocks.push(OcrBlock {
+                    text: word.text.to_string(),
+                    bbox: word.bbox,
+                    confidence: Some(word.confidence),
+                });
+            }
+        }
+    }
+    blocks
+}
+
+/// Blocks for backends that only return text: one per non-empty line.
+fn text_blocks(text: &str) -> Vec<OcrBlock> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| OcrBlock {
+            text: line.to_string(),
+            bbox: None,
+            confidence: None,
+        })
+        .collect()
+}
+
 pub struct OcrProvider {
     backend: OcrBackend,
     config: OcrConfig,
@@ -112,7 +261,14 @@ impl OcrProvider {
         !matches!(self.backend, OcrBackend::Unavailable { .. })
     }
 
-    pub async fn ocr(&self, image_bytes: &[u8]) -> Result<String> {
+    pub async fn ocr(&self, image_bytes: &[u8]) -> Result<OcrOutput> {
+        Ok(self.ocr_with_layout(image_bytes).await?.output)
+    }
+
+    /// Like [`ocr`](Self::ocr), but also returns the recognized text blocks.
+    /// Tesseract reports a bounding box and confidence per line; API
+    /// backends only return text, so their blocks carry neither.
+    pub async fn ocr_with_layout(&self, image_bytes: &[u8]) -> Result<OcrLayout> {
         let timeout_duration = Duration::from_secs(self.config.timeout_secs);
 
         let result = tokio::time::timeout(timeout_duration, self.ocr_internal(image_bytes)).await;
@@ -126,25 +282,57 @@ impl OcrProvider {
         }
     }
 
-    async fn ocr_internal(&self, image_bytes: &[u8]) -> Result<String> {
+    async fn ocr_internal(&self, image_bytes: &[u8]) -> Result<OcrLayout> {
         match &self.backend {
             OcrBackend::Local { tesseract } => {
                 let bytes = image_bytes.to_vec();
                 let tesseract = Arc::clone(tesseract);
 
-                let text = tokio::task::spawn_blocking(move || {
+                let (text, tsv) = tokio::task::spawn_blocking(move || {
                     let mut lt = tesseract.blocking_lock();
                     lt.set_image_from_mem(&bytes)
                         .map_err(|e| MomoError::Ocr(format!("Failed to set image: {e}")))?;
-                    lt.get_utf8_text()
-                        .map_err(|e| MomoError::Ocr(format!("Failed to extract text: {e}")))
+                    let text = lt
+                        .get_utf8_text()
+                        .map_err(|e| MomoError::Ocr(format!("Failed to extract text: {e}")))?;
+                    // Confidence and layout are best-effort; a TSV failure
+                    // must not lose the text.
+                    let tsv = lt.get_tsv_text(0).ok();
+                    Ok::<_, MomoError>((text, tsv))
                 })
                 .await
                 .map_err(|e| MomoError::Ocr(format!("OCR task panicked: {e}")))??;
 
-                Ok(text.trim().to_string())
+                let tsv = tsv.unwrap_or_default();
+                let blocks = tesseract_tsv_blocks(&tsv, self.config.min_confidence);
+                let text = match self.config.min_confidence {
+                    // Rebuild the text from the words that cleared the threshold.
+                    Some(_) if !tsv.is_empty() => blocks
+                        .iter()
+                        .map(|block| block.text.as_str())
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                    _ => text.trim().to_string(),
+                };
+                Ok(OcrLayout {
+                    output: OcrOutput {
+                        text,
+                        // Scored over every word, so it reflects the whole image.
+                        confidence: tesseract_tsv_confidence(&tsv),
+                    },
+                    blocks,
+                })
+            }
+            OcrBackend::Api { client } => {
+                let text = client.ocr(image_bytes).await?;
+                Ok(OcrLayout {
+                    blocks: text_blocks(&text),
+                    output: OcrOutput {
+                        text,
+                        confidence: None,
+                    },
+                })
             }
-            OcrBackend::Api { client } => client.ocr(image_bytes).await,
             OcrBackend::Unavailable { reason } => Err(MomoError::OcrUnavailable(reason.clone())),
         }
     }
@@ -189,6 +377,10 @@ mod tests {
             timeout_secs: 60,
             max_image_dimension: 4096,
             min_image_dimension: 50,
+            max_concurrent_jobs: 2,
+            include_layout: false,
+            min_confidence: None,
+            min_document_confidence: None,
         };
 
         let result = OcrProvider::new(&config);
@@ -209,6 +401,10 @@ mod tests {
                 timeout_secs: 60,
                 max_image_dimension: 4096,
                 min_image_dimension: 50,
+                max_concurrent_jobs: 2,
+                include_layout: false,
+                min_confidence: None,
+                min_document_confidence: None,
             },
         };
 
@@ -216,6 +412,99 @@ mod tests {
         assert!(matches!(result, Err(MomoError::OcrUnavailable(_))));
     }
 
+    #[test]
+    fn test_tesseract_tsv_confidence_uses_word_rows() {
+        let tsv = [
+            "1\t1\t0\t0\t0\t0\t0\t0\t100\t50\t-1\t",
+            "4\t1\t1\t1\t1\t0\t5\t5\t90\t20\t-1\t",
+            "5\t1\t1\t1\t1\t1\t5\t5\t40\t20\t96.5\tHello",
+            "5\t1\t1\t1\t1\t2\t50\t5\t40\t20\t41.5\tw0rld",
+            "5\t1\t1\t1\t1\t3\t95\t5\t5\t20\t-1\t ",
+        ]
+        .join("\n");
+
+        let confidence = tesseract_tsv_confidence(&tsv).unwrap();
+        assert!((confidence.average - 0.69).abs() < 1e-6);
+        assert!((confidence.minimum - 0.415).abs() < 1e-6);
+
+        assert!(tesseract_tsv_confidence("").is_none());
+    }
+
+    #[test]
+    fn test_tesseract_tsv_blocks_group_words_by_line() {
+        let tsv = [
+            "4\t1\t1\t1\t1\t0\t10\t5\t90\t20\t-1\t",
+            "5\t1\t1\t1\t1\t1\t10\t5\t40\t20\t90\tTotal",
+            "5\t1\t1\t1\t1\t2\t60\t8\t40\t18\t70\t$4.20",
+            "4\t1\t1\t1\t2\t0\t10\t30\t50\t20\t-1\t",
+            "5\t1\t1\t1\t2\t1\t10\t30\t50\t20\t80\tThanks",
+            "5\t1\t1\t1\t2\t2\t70\t30\t5\t20\t-1\t ",
+        ]
+        .join("\n");
+
+        let blocks = tesseract_tsv_blocks(&tsv, None);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].text, "Total $4.20");
+        assert_eq!(
+            blocks[0].bbox,
+            Some(OcrBoundingBox {
+                left: 10,
+                top: 5,
+                width: 90,
+                height: 21,
+            })
+        );
+        assert!((blocks[0].confidence.unwrap() - 0.8).abs() < 1e-6);
+        assert_eq!(blocks[1].text, "Thanks");
+        assert_eq!(blocks[1].bbox.unwrap().left, 10);
+
+        assert!(tesseract_tsv_blocks("", None).is_empty());
+    }
+
+    #[test]
+    fn test_tesseract_tsv_blocks_drop_low_confidence_words() {
+        let tsv = [
+            "5\t1\t1\t1\t1\t1\t10\t5\t40\t20\t91\tInvoice",
+            "5\t1\t1\t1\t1\t2\t60\t5\t40\t20\t12\t~%#",
+            "5\t1\t1\t1\t2\t1\t10\t30\t20\t20\t8\tl|;",
+            "5\t1\t1\t1\t2\t2\t40\t30\t20\t20\t15\t.,'",
+            "5\t1\t1\t1\t3\t1\t10\t55\t50\t20\t88\tTotal",
+        ]
+        .join("\n");
+
+        let blocks = tesseract_tsv_blocks(&tsv, Some(0.5));
+        assert_eq!(
+            blocks.iter().map(|b| b.text.as_str()).collect::<Vec<_>>(),
+            vec!["Invoice", "Total"]
+        );
+        assert_eq!(
+            blocks[0].bbox,
+            Some(OcrBoundingBox {
+                left: 10,
+                top: 5,
+                width: 40,
+                height: 20,
+            })
+        );
+        assert!(blocks.iter().all(|b| b.confidence.unwrap() >= 0.5));
+
+        // Document confidence still counts the dropped words.
+        let confidence = tesseract_tsv_confidence(&tsv).unwrap();
+        assert!(confidence.average < 0.5);
+    }
+
+    #[test]
+    fn test_text_blocks_split_lines_without_geometry() {
+        let blocks = text_blocks("Receipt\n\n  Total: 4.20  \n");
+        assert_eq!(
+            blocks.iter().map(|b| b.text.as_str()).collect::<Vec<_>>(),
+            vec!["Receipt", "Total: 4.20"]
+        );
+        assert!(blocks
+            .iter()
+            .all(|b| b.bbox.is_none() && b.confidence.is_none()));
+    }
+
     fn make_config(model: &str, api_key: Option<&str>) -> OcrConfig {
         OcrConfig {
             model: model.to_string(),
@@ -225,6 +514,10 @@ mod tests {
             timeout_secs: 60,
             max_image_dimension: 4096,
             min_image_dimension: 50,
+            max_concurrent_jobs: 2,
+            include_layout: false,
+            min_confidence: None,
+            min_document_confidence: None,
         }
     }
 
@@ -1,6 +1,10 @@
+use std::sync::Arc;
+
+use text_splitter::ChunkSizer;
 use unicode_segmentation::UnicodeSegmentation;
 
 use crate::config::ProcessingConfig;
+use crate::models::ChunkerType;
 
 /// Context passed to chunkers for source file information
 #[derive(Debug, Clone, Default)]
@@ -10,13 +14,52 @@ pub struct ChunkContext {
 
 /// Trait for content chunking implementations
 pub trait ContentChunker: Send + Sync {
+    /// Which chunker this is, recorded on the chunks it produces
+    fn chunker_type(&self) -> ChunkerType;
+
     /// Chunk text content with optional context
     fn chunk(&self, text: &str, context: Option<&ChunkContext>) -> Vec<TextChunk>;
 }
 
+/// Counts tokens the way the embedding model will see them.
+///
+/// When a chunker has a counter, `chunk_size` and `chunk_overlap` are measured in
+/// model tokens; without one they are measured in bytes and token counts are estimated.
+pub trait TokenCounter: Send + Sync {
+    fn count_tokens(&self, text: &str) -> usize;
+}
+
+impl<F> TokenCounter for F
+where
+    F: Fn(&str) -> usize + Send + Sync,
+{
+    fn count_tokens(&self, text: &str) -> usize {
+        self(text)
+    }
+}
+
+/// Adapts a [`TokenCounter`] to `text_splitter`'s sizing API.
+pub(crate) struct TokenCounterSizer<'a>(pub &'a dyn TokenCounter);
+
+impl ChunkSizer for TokenCounterSizer<'_> {
+    fn size(&self, chunk: &str) -> usize {
+        self.0.count_tokens(chunk)
+    }
+}
+
+/// Token count reported for a chunk: exact when a counter is available,
+/// otherwise the ~4 bytes per token heuristic.
+pub(crate) fn count_chunk_tokens(counter: Option<&dyn TokenCounter>, text: &str) -> i32 {
+    match counter {
+        Some(counter) => counter.count_tokens(text) as i32,
+        None => (text.len() as f32 / 4.0).ceil() as i32,
+    }
+}
+
 pub struct TextChunker {
     chunk_size: usize,
     chunk_overlap: usize,
+    token_counter: Option<Arc<dyn TokenCounter>>,
 }
 
 impl TextChunker {
@@ -24,6 +67,20 @@ impl TextChunker {
         Self {
             chunk_size: config.chunk_size,
             chunk_overlap: config.chunk_overlap,
+            token_counter: None,
+        }
+    }
+
+    /// Measure chunk sizes with the given tokenizer instead of byte length.
+    pub fn with_token_counter(mut self, counter: Arc<dyn TokenCounter>) -> Self {
+        self.token_counter = Some(counter);
+        self
+    }
+
+    fn measure(&self, text: &str) -> usize {
+        match &self.token_counter {
+            Some(counter) => counter.count_tokens(text),
+            None => text.len(),
         }
     }
 
@@ -36,7 +93,7 @@ impl TextChunker {
         self.merge_sentences_into_chunks(sentences)
     }
 
-    fn split_into_sentences(&self, text: &str) -> Vec<String> {
+    pub(crate) fn split_into_sentences(&self, text: &str) -> Vec<String> {
         let mut sentences = Vec::new();
         let mut current = String::new();
 
@@ -99,41 +156,121 @@ impl TextChunker {
         let mut current_chunk = String::new();
         let mut current_sentences: Vec<String> = Vec::new();
 
+        // Sizes add up across sentences, so the current chunk's size is kept as
+        // a running total instead of re-measuring the whole chunk per sentence.
+        // A token counter's special tokens are counted once per text, and the
+        // joining space costs a byte but no token.
+        let (special_tokens, separator) = match self.token_counter {
+            Some(_) => (self.measure(""), 0),
+            None => (0, 1),
+        };
+        let size_of = |sentence: &str| self.measure(sentence).saturating_sub(special_tokens);
+        let joined_size = |sentences: &[String]| -> usize {
+            sentences.iter().map(|s| size_of(s)).sum::<usize>()
+                + separator * sentences.len().saturating_sub(1)
+        };
+        let mut current_size = 0;
+
+        // Only a token budget is a hard limit; byte budgets keep whole sentences.
+        let sentences: Vec<String> = if self.token_counter.is_some() {
+            sentences
+                .into_iter()
+                .flat_map(|sentence| self.split_oversized(sentence))
+                .collect()
+        } else {
+            sentences
+        };
         for sentence in sentences {
+            let sentence_size = size_of(&sentence);
             let potential_length = if current_chunk.is_empty() {
-                sentence.len()
+                special_tokens + sentence_size
             } else {
-                current_chunk.len() + 1 + sentence.len()
+                special_tokens + current_size + separator + sentence_size
             };
 
             if potential_length > self.chunk_size && !current_chunk.is_empty() {
                 chunks.push(TextChunk {
                     content: current_chunk.clone(),
-                    token_count: Self::estimate_tokens(&current_chunk),
+                    token_count: self.count_tokens(&current_chunk),
                 });
 
                 let overlap_sentences = self.get_overlap_sentences(&current_sentences);
                 current_chunk = overlap_sentences.join(" ");
+                current_size = joined_size(&overlap_sentences);
                 current_sentences = overlap_sentences;
+
+                // Drop the overlap when it would push the next chunk over the limit.
+                if self.token_counter.is_some()
+                    && !current_chunk.is_empty()
+                    && special_tokens + current_size + separator + sentence_size > self.chunk_size
+                {
+                    current_chunk.clear();
+                    current_sentences.clear();
+                    current_size = 0;
+                }
             }
 
             if !current_chunk.is_empty() {
                 current_chunk.push(' ');
+                current_size += separator;
             }
             current_chunk.push_str(&sentence);
+            current_size += sentence_size;
             current_sentences.push(sentence);
         }
 
         if !current_chunk.is_empty() {
             chunks.push(TextChunk {
                 content: current_chunk.clone(),
-                token_count: Self::estimate_tokens(&current_chunk),
+                token_count: self.count_tokens(&current_chunk),
             });
         }
 
         chunks
     }
 
+    /// Split a sentence that alone exceeds the token limit at word boundaries,
+    /// and words that alone exceed it at graphemes, so no chunk outgrows it.
+    fn split_oversized(&self, sentence: String) -> Vec<String> {
+        if self.measure(&sentence) <= self.chunk_size {
+            return vec![sentence];
+        }
+
+        let mut pieces = Vec::new();
+        let mut current = String::new();
+        for word in sentence.split_whitespace() {
+            let candidate = if current.is_empty() {
+                word.to_string()
+            } else {
+                format!("{current} {word}")
+            };
+            if self.measure(&candidate) <= self.chunk_size {
+                current = candidate;
+                continue;
+            }
+            if !current.is_empty() {
+                pieces.push(std::mem::take(&mut current));
+            }
+            if self.measure(word) <= self.chunk_size {
+                current = word.to_string();
+                continue;
+            }
+
+            for grapheme in word.graphemes(true) {
+                let candidate = format!("{current}{grapheme}");
+                if !current.is_empty() && self.measure(&candidate) > self.chunk_size {
+                    pieces.push(std::mem::replace(&mut current, grapheme.to_string()));
+                } else {
+                    current = candidate;
+                }
+            }
+        }
+        if !current.is_empty() {
+            pieces.push(current);
+        }
+        pieces
+    }
+
     fn get_overlap_sentences(&self, sentences: &[String]) -> Vec<String> {
         if sentences.is_empty() {
             return Vec::new();
@@ -143,12 +280,12 @@ impl TextChunker {
         let mut overlap_sentences = Vec::new();
 
         for sentence in sentences.iter().rev() {
-            if overlap_text_len + sentence.len() > self.chunk_overlap
-                && !overlap_sentences.is_empty()
+            let sentence_len = self.measure(sentence);
+            if overlap_text_len + sentence_len > self.chunk_overlap && !overlap_sentences.is_empty()
             {
                 break;
             }
-            overlap_text_len += sentence.len() + 1;
+            overlap_text_len += sentence_len + 1;
             overlap_sentences.push(sentence.clone());
         }
 
@@ -156,12 +293,16 @@ impl TextChunker {
         overlap_sentences
     }
 
-    fn estimate_tokens(text: &str) -> i32 {
-        (text.len() as f32 / 4.0).ceil() as i32
+    fn count_tokens(&self, text: &str) -> i32 {
+        count_chunk_tokens(self.token_counter.as_deref(), text)
     }
 }
 
 impl ContentChunker for TextChunker {
+    fn chunker_type(&self) -> ChunkerType {
+        ChunkerType::Text
+    }
+
     fn chunk(&self, text: &str, context: Option<&ChunkContext>) -> Vec<TextChunk> {
         // Ignore context for TextChunker (plain text doesn't need source path)
         let _ = context;
@@ -174,6 +315,7 @@ impl Default for TextChunker {
         Self {
             chunk_size: 512,
             chunk_overlap: 50,
+            token_counter: None,
         }
     }
 }
@@ -187,6 +329,10 @@ pub struct TextChunk {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::Config;
+    use crate::models::{
+        ChunkStrategy, NearDuplicateAction, PiiAction, PiiCategory, StructuredDataOverflow,
+    };
 
     #[test]
     fn test_text_chunker_basic() {
@@ -256,4 +402,127 @@ mod tests {
         };
         assert_eq!(context.source_path, Some("test.md".to_string()));
     }
+
+    fn word_counter() -> Arc<dyn TokenCounter> {
+        Arc::new(|text: &str| text.split_whitespace().count())
+    }
+
+    #[test]
+    fn test_text_chunker_sizes_chunks_with_token_counter() {
+        let config = ProcessingConfig {
+            chunk_size: 6,
+            chunk_overlap: 0,
+            chunk_strategy: ChunkStrategy::Structural,
+            semantic_chunk_threshold: 0.3,
+            semantic_chunk_min_size: 128,
+            use_model_tokenizer: true,
+            auto_summary: false,
+            auto_summary_min_chars: 2000,
+            contextual_chunks: false,
+            near_duplicate_threshold: None,
+            near_duplicate_action: NearDuplicateAction::Warn,
+            record_stage_timings: false,
+            low_confidence_threshold: None,
+            chunk_reembed_interval_secs: 0,
+            chunk_reembed_batch_size: 64,
+            categorize_documents: false,
+            structured_data_max_chunks: 0,
+            structured_data_overflow: StructuredDataOverflow::Sample,
+            pii_detection: false,
+            pii_categories: PiiCategory::ALL.to_vec(),
+            pii_action: PiiAction::Redact,
+            retry_max_attempts: 3,
+            retry_base_delay_ms: 500,
+            dead_letter_after_failures: 5,
+        };
+        let text = "One two three. Four five six. Seven eight nine.";
+
+        // Without a counter the 6 is a byte budget, so every sentence is its own chunk.
+        assert_eq!(TextChunker::new(&config).chunk(text, None).len(), 3);
+
+        let chunker = TextChunker::new(&config).with_token_counter(word_counter());
+        let chunks = chunker.chunk(text, None);
+
+        assert_eq!(chunks.len(), 2, "Two sentences fit in 6 tokens");
+        assert!(chunks.iter().all(|c| c.token_count <= 6));
+        assert_eq!(chunks[0].content, "One two three. Four five six.");
+    }
+
+    #[test]
+    fn test_text_chunker_never_exceeds_token_limit() {
+        // Rare words break into one token per character, like subword
+        // tokenizers do with identifiers, hashes and non-Latin script.
+        let counter: Arc<dyn TokenCounter> = Arc::new(|text: &str| {
+            text.split_whitespace()
+                .map(|word| {
+                    if word.chars().all(|c| c.is_ascii_lowercase()) {
+                        1
+                    } else {
+                        word.chars().count()
+                    }
+                })
+                .sum::<usize>()
+                + 2
+        });
+        let text = "Deploy 3f9a2c7be41d08f6 to Zürich-Ω-cluster. \
+            see abcdef0123456789abcdef0123456789 and ПРИВЕТмир \
+            with no sentence break for a very long while because logs rarely punctuate. \
+            Short one.";
+        let limit = 16;
+
+        let mut config = Config::default().processing;
+        config.chunk_size = limit;
+        config.chunk_overlap = 8;
+        let chunker = TextChunker::new(&config).with_token_counter(counter.clone());
+        let chunks = chunker.chunk(text, None);
+
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(
+                counter.count_tokens(&chunk.content) <= limit,
+                "chunk over the {limit} token limit: {:?}",
+                chunk.content
+            );
+            assert_eq!(
+                chunk.token_count as usize,
+                counter.count_tokens(&chunk.content)
+            );
+        }
+    }
+
+    #[test]
+    fn test_text_chunker_measures_each_sentence_a_bounded_number_of_times() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // Counts every word the chunker asks the tokenizer to look at.
+        let measured = Arc::new(AtomicUsize::new(0));
+        let counter: Arc<dyn TokenCounter> = {
+            let measured = measured.clone();
+            Arc::new(move |text: &str| {
+                let words = text.split_whitespace().count();
+                measured.fetch_add(words, Ordering::Relaxed);
+                words
+            })
+        };
+        let text = "One two three. ".repeat(2000);
+
+        let mut config = Config::default().processing;
+        config.chunk_size = 512;
+        config.chunk_overlap = 16;
+        let chunks = TextChunker::new(&config)
+            .with_token_counter(counter)
+            .chunk(&text, None);
+
+        assert!(chunks.len() > 1);
+        // Re-measuring the growing chunk per sentence would look at ~chunk_size
+        // words per sentence; a running count looks at each one a few times.
+        assert!(measured.load(Ordering::Relaxed) <= 6 * 3 * 2000);
+    }
+
+    #[test]
+    fn test_count_chunk_tokens_falls_back_to_heuristic() {
+        assert_eq!(count_chunk_tokens(None, "abcdefgh"), 2);
+        let counter = word_counter();
+        assert_eq!(count_chunk_tokens(Some(counter.as_ref()), "abcdefgh"), 1);
+    }
 }
@@ -1,9 +1,12 @@
+use std::sync::Arc;
+
 use crate::config::ProcessingConfig;
-use crate::models::DocumentType;
+use crate::error::{MomoError, Result};
+use crate::models::{ChunkSizeOverride, ChunkerType, DocumentType, StructuredDataOverflowReport};
 
 use super::{
-    detect_language, CodeChunker, ContentChunker, MarkdownChunker, StructuredDataChunker,
-    TextChunker, WebpageChunker,
+    detect_language, CodeChunker, ContentChunker, MarkdownChunker, SemanticChunker,
+    StructuredDataChunker, TextChunker, TokenCounter, TranscriptChunker, WebpageChunker,
 };
 
 /// Registry that routes documents to appropriate chunkers based on DocumentType.
@@ -15,6 +18,14 @@ pub struct ChunkerRegistry {
     markdown_chunker: MarkdownChunker,
     webpage_chunker: WebpageChunker,
     structured_data_chunker: StructuredDataChunker,
+    transcript_chunker: TranscriptChunker,
+    /// Replaces the text chunker for prose when the semantic strategy is enabled.
+    semantic_chunker: Option<SemanticChunker>,
+    /// Settings the chunkers were built from; `None` for the default registry.
+    config: Option<ProcessingConfig>,
+    token_counter: Option<Arc<dyn TokenCounter>>,
+    /// Largest chunk size a document may ask for, e.g. the model's token limit.
+    max_chunk_size: Option<usize>,
 }
 
 impl ChunkerRegistry {
@@ -25,10 +36,126 @@ impl ChunkerRegistry {
             code_chunker: CodeChunker::new(config),
             markdown_chunker: MarkdownChunker::new(config),
             webpage_chunker: WebpageChunker::new(config),
-            structured_data_chunker: StructuredDataChunker::default(),
+            structured_data_chunker: StructuredDataChunker::default().with_max_chunks(
+                config.structured_data_max_chunks,
+                config.structured_data_overflow,
+            ),
+            transcript_chunker: TranscriptChunker::new(config),
+            semantic_chunker: None,
+            config: Some(config.clone()),
+            token_counter: None,
+            max_chunk_size: None,
+        }
+    }
+
+    /// Split prose with `chunker` instead of the size-based text chunker.
+    pub fn with_semantic_chunker(self, chunker: SemanticChunker) -> Self {
+        Self {
+            semantic_chunker: Some(chunker),
+            ..self
         }
     }
 
+    /// Size text, code, markdown, webpage and transcript chunks in the embedding model's tokens.
+    /// Structured data is split by rows and is unaffected.
+    pub fn with_token_counter(self, counter: Arc<dyn TokenCounter>) -> Self {
+        Self {
+            text_chunker: self.text_chunker.with_token_counter(counter.clone()),
+            code_chunker: self.code_chunker.with_token_counter(counter.clone()),
+            markdown_chunker: self.markdown_chunker.with_token_counter(counter.clone()),
+            webpage_chunker: self.webpage_chunker.with_token_counter(counter.clone()),
+            structured_data_chunker: self.structured_data_chunker,
+            transcript_chunker: self.transcript_chunker.with_token_counter(counter.clone()),
+            semantic_chunker: self
+                .semantic_chunker
+                .map(|chunker| chunker.with_token_counter(counter.clone())),
+            token_counter: Some(counter),
+            ..self
+        }
+    }
+
+    /// Reject per-document chunk sizes above `max`.
+    pub fn with_max_chunk_size(self, max: usize) -> Self {
+        Self {
+            max_chunk_size: Some(max),
+            ..self
+        }
+    }
+
+    /// Chunking settings for a document: its overrides where set, the
+    /// configured defaults otherwise. A defaulted overlap shrinks to fit a
+    /// smaller chunk size; an explicit one must be smaller than the chunk size.
+    pub fn chunk_config(&self, overrides: ChunkSizeOverride) -> Result<ProcessingConfig> {
+        let config = self.config.as_ref().ok_or_else(|| {
+            MomoError::Validation("Chunk size overrides are not supported".to_string())
+        })?;
+
+        let chunk_size = overrides.chunk_size.unwrap_or(config.chunk_size);
+        if chunk_size == 0 {
+            return Err(MomoError::Validation(
+                "chunk_size must be greater than 0".to_string(),
+            ));
+        }
+        if let Some(max) = self.max_chunk_size.filter(|max| chunk_size > *max) {
+            return Err(MomoError::Validation(format!(
+                "chunk_size {chunk_size} exceeds the embedding model's limit of {max} tokens"
+            )));
+        }
+
+        let chunk_overlap = overrides
+            .chunk_overlap
+            .unwrap_or_else(|| config.chunk_overlap.min(chunk_size / 2));
+        if chunk_overlap >= chunk_size {
+            return Err(MomoError::Validation(format!(
+                "chunk_overlap {chunk_overlap} must be smaller than chunk_size {chunk_size}"
+            )));
+        }
+
+        Ok(ProcessingConfig {
+            chunk_size,
+            chunk_overlap,
+            ..config.clone()
+        })
+    }
+
+    /// A registry whose chunkers use a document's chunk size overrides.
+    pub fn with_chunk_sizes(&self, overrides: ChunkSizeOverride) -> Result<Self> {
+        let config = self.chunk_config(overrides)?;
+        let mut registry = Self::new(&config);
+        if let Some(semantic) = &self.semantic_chunker {
+            registry = registry.with_semantic_chunker(semantic.with_config(&config));
+        }
+        if let Some(counter) = &self.token_counter {
+            registry = registry.with_token_counter(counter.clone());
+        }
+        registry.max_chunk_size = self.max_chunk_size;
+        Ok(registry)
+    }
+
+    /// What will be indexed of structured data `text` that exceeds the chunk
+    /// limit; `None` when every row fits.
+    pub fn structured_data_overflow(&self, text: &str) -> Option<StructuredDataOverflowReport> {
+        self.structured_data_chunker.overflow(text)
+    }
+
+    /// Chunker for audio and video transcripts split on segment boundaries.
+    pub fn transcript_chunker(&self) -> &TranscriptChunker {
+        &self.transcript_chunker
+    }
+
+    /// The semantic chunker, if enabled and the document would otherwise be
+    /// split by the plain text chunker. Code, markdown, webpages and
+    /// structured data keep their structure-aware chunkers.
+    pub fn semantic_chunker(
+        &self,
+        doc_type: &DocumentType,
+        source_path: Option<&str>,
+    ) -> Option<&SemanticChunker> {
+        let chunker = self.semantic_chunker.as_ref()?;
+        (self.get_chunker(doc_type, source_path).chunker_type() == ChunkerType::Text)
+            .then_some(chunker)
+    }
+
     /// Get the appropriate chunker for a document type and optional source path.
     /// Returns a trait object reference for zero-cost dispatch.
     pub fn get_chunker(
@@ -45,10 +172,12 @@ impl ChunkerRegistry {
                 }
                 &self.text_chunker
             }
-            DocumentType::Markdown => &self.markdown_chunker,
+            DocumentType::Markdown | DocumentType::Epub => &self.markdown_chunker,
             DocumentType::Webpage => &self.webpage_chunker,
             DocumentType::Csv | DocumentType::Xlsx => &self.structured_data_chunker,
-            DocumentType::Pdf | DocumentType::Docx | DocumentType::Pptx => &self.text_chunker,
+            DocumentType::Pdf | DocumentType::Docx | DocumentType::Pptx | DocumentType::Json => {
+                &self.text_chunker
+            }
             DocumentType::Text
             | DocumentType::Unknown
             | DocumentType::Tweet
@@ -74,7 +203,10 @@ impl ChunkerRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::DocumentType;
+    use crate::models::{
+        ChunkStrategy, ChunkerType, DocumentType, NearDuplicateAction, PiiAction, PiiCategory,
+        StructuredDataOverflow,
+    };
 
     #[test]
     fn test_registry_routes_markdown() {
@@ -165,11 +297,157 @@ mod tests {
         assert!(!chunks.is_empty());
     }
 
+    #[test]
+    fn test_registry_reports_chunker_type() {
+        let registry = ChunkerRegistry::default();
+        let cases = [
+            (DocumentType::Markdown, None, ChunkerType::Markdown),
+            (DocumentType::Webpage, None, ChunkerType::Webpage),
+            (DocumentType::Csv, None, ChunkerType::StructuredData),
+            (DocumentType::Code, Some("main.rs"), ChunkerType::Code),
+            (DocumentType::Code, Some("notes.xyz"), ChunkerType::Text),
+            (DocumentType::Pdf, None, ChunkerType::Text),
+            (DocumentType::Json, Some("export.json"), ChunkerType::Text),
+            (DocumentType::Epub, None, ChunkerType::Markdown),
+        ];
+        for (doc_type, path, expected) in cases {
+            assert_eq!(
+                registry.get_chunker(&doc_type, path).chunker_type(),
+                expected
+            );
+        }
+    }
+
+    struct UniformEmbedder;
+
+    #[async_trait::async_trait]
+    impl crate::processing::SentenceEmbedder for UniformEmbedder {
+        async fn embed_sentences(
+            &self,
+            sentences: Vec<String>,
+        ) -> crate::error::Result<Vec<Vec<f32>>> {
+            Ok(vec![vec![1.0]; sentences.len()])
+        }
+    }
+
+    #[test]
+    fn test_semantic_chunker_only_replaces_text_chunker() {
+        let config = crate::config::Config::default().processing;
+        assert!(ChunkerRegistry::new(&config)
+            .semantic_chunker(&DocumentType::Text, None)
+            .is_none());
+
+        let registry = ChunkerRegistry::new(&config)
+            .with_semantic_chunker(SemanticChunker::new(&config, Arc::new(UniformEmbedder)));
+        for (doc_type, path) in [
+            (DocumentType::Text, None),
+            (DocumentType::Pdf, None),
+            (DocumentType::Code, Some("notes.xyz")),
+        ] {
+            assert!(registry.semantic_chunker(&doc_type, path).is_some());
+        }
+        for (doc_type, path) in [
+            (DocumentType::Markdown, None),
+            (DocumentType::Webpage, None),
+            (DocumentType::Csv, None),
+            (DocumentType::Code, Some("main.rs")),
+        ] {
+            assert!(registry.semantic_chunker(&doc_type, path).is_none());
+        }
+    }
+
+    #[test]
+    fn test_chunk_config_applies_and_validates_overrides() {
+        let mut config = crate::config::Config::default().processing;
+        config.chunk_size = 512;
+        config.chunk_overlap = 50;
+        let registry = ChunkerRegistry::new(&config).with_max_chunk_size(1024);
+        let sizes = |chunk_size, chunk_overlap| {
+            registry
+                .chunk_config(ChunkSizeOverride {
+                    chunk_size,
+                    chunk_overlap,
+                })
+                .map(|c| (c.chunk_size, c.chunk_overlap))
+        };
+
+        assert_eq!(sizes(None, None).unwrap(), (512, 50));
+        assert_eq!(sizes(Some(200), Some(20)).unwrap(), (200, 20));
+        assert_eq!(
+            sizes(Some(60), None).unwrap(),
+            (60, 30),
+            "Default overlap shrinks"
+        );
+        assert!(matches!(
+            sizes(Some(0), None),
+            Err(MomoError::Validation(_))
+        ));
+        assert!(matches!(
+            sizes(Some(2048), None),
+            Err(MomoError::Validation(_))
+        ));
+        assert!(matches!(
+            sizes(Some(100), Some(100)),
+            Err(MomoError::Validation(_))
+        ));
+        assert!(matches!(
+            sizes(None, Some(600)),
+            Err(MomoError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_with_chunk_sizes_resizes_chunkers() {
+        let config = crate::config::Config::default().processing;
+        let registry = ChunkerRegistry::new(&config);
+        let text = "Short sentence number one. Short sentence number two. \
+            Short sentence number three. Short sentence number four.";
+
+        let default_chunks = registry
+            .get_chunker(&DocumentType::Text, None)
+            .chunk(text, None);
+        assert_eq!(default_chunks.len(), 1);
+
+        let small = registry
+            .with_chunk_sizes(ChunkSizeOverride {
+                chunk_size: Some(60),
+                chunk_overlap: Some(0),
+            })
+            .unwrap();
+        let chunks = small
+            .get_chunker(&DocumentType::Text, None)
+            .chunk(text, None);
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|c| c.content.len() <= 60));
+    }
+
     #[test]
     fn test_registry_new_with_config() {
         let config = ProcessingConfig {
             chunk_size: 1024,
             chunk_overlap: 100,
+            chunk_strategy: ChunkStrategy::Structural,
+            semantic_chunk_threshold: 0.3,
+            semantic_chunk_min_size: 128,
+            use_model_tokenizer: false,
+            auto_summary: false,
+            auto_summary_min_chars: 2000,
+            contextual_chunks: false,
+            near_duplicate_threshold: None,
+            near_duplicate_action: NearDuplicateAction::Warn,
+            record_stage_timings: false,
+            low_confidence_threshold: None,
+            chunk_reembed_interval_secs: 0,
+            chunk_reembed_batch_size: 64,
+            categorize_documents: false,
+            structured_data_max_chunks: 0,
+            structured_data_overflow: StructuredDataOverflow::Sample,
+            pii_detection: false,
+            pii_categories: PiiCategory::ALL.to_vec(),
+            pii_action: PiiAction::Redact,
+            retry_max_attempts: 3,
+            retry_base_delay_ms: 500,
+            dead_letter_after_failures: 5,
         };
         let registry = ChunkerRegistry::new(&config);
         let chunker = registry.get_chunker(&DocumentType::Text, None);
@@ -1,13 +1,40 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use tree_sitter::{Node, Parser};
+
 use crate::config::ProcessingConfig;
-use text_splitter::{ChunkConfig, CodeSplitter};
+use crate::models::ChunkerType;
 
+use super::chunker::count_chunk_tokens;
 use super::language::SupportedLanguage;
-use super::{ChunkContext, ContentChunker, TextChunk, TextChunker};
+use super::{ChunkContext, ContentChunker, TextChunk, TextChunker, TokenCounter};
+
+/// Declarations whose members are chunked separately when the whole
+/// declaration doesn't fit: impl blocks, traits, modules, classes, interfaces.
+const CONTAINER_KINDS: [&str; 12] = [
+    "impl_item",
+    "trait_item",
+    "mod_item",
+    "class_definition",
+    "class_declaration",
+    "abstract_class_declaration",
+    "interface_declaration",
+    "enum_declaration",
+    "record_declaration",
+    "class_specifier",
+    "struct_specifier",
+    "namespace_definition",
+];
+
+/// Fields wrapping the actual declaration: Python decorators and JS/TS exports.
+const WRAPPER_FIELDS: [&str; 2] = ["definition", "declaration"];
 
 pub struct CodeChunker {
     chunk_size: usize,
     chunk_overlap: usize,
     fallback_chunker: TextChunker,
+    token_counter: Option<Arc<dyn TokenCounter>>,
 }
 
 impl CodeChunker {
@@ -16,9 +43,125 @@ impl CodeChunker {
             chunk_size: config.chunk_size,
             chunk_overlap: config.chunk_overlap,
             fallback_chunker: TextChunker::new(config),
+            token_counter: None,
+        }
+    }
+
+    /// Measure chunk sizes with the given tokenizer instead of character count.
+    pub fn with_token_counter(mut self, counter: Arc<dyn TokenCounter>) -> Self {
+        self.fallback_chunker = self.fallback_chunker.with_token_counter(counter.clone());
+        self.token_counter = Some(counter);
+        self
+    }
+
+    fn measure(&self, text: &str) -> usize {
+        match &self.token_counter {
+            Some(counter) => counter.count_tokens(text),
+            None => text.chars().count(),
         }
     }
 
+    fn fits(&self, text: &str) -> bool {
+        self.measure(text) <= self.chunk_size
+    }
+
+    /// Split `source` along its top-level declarations, packing consecutive
+    /// declarations into chunks while they fit. Containers that don't fit are
+    /// split between their members; any other declaration that doesn't fit
+    /// is split by lines.
+    fn declaration_ranges(&self, source: &str, root: Node<'_>) -> Vec<Range<usize>> {
+        self.split_members(source, root, 0..source.len())
+    }
+
+    fn split_members(
+        &self,
+        source: &str,
+        container: Node<'_>,
+        range: Range<usize>,
+    ) -> Vec<Range<usize>> {
+        // Each piece runs from the start of one member (including the comments
+        // and attributes above it) to the start of the next. The first piece
+        // holds whatever precedes the first member, e.g. `impl Foo {`.
+        let mut cuts = vec![(range.start, None)];
+        let mut leading_start = None;
+        let mut cursor = container.walk();
+        for child in container.named_children(&mut cursor) {
+            let start = leading_start.take().unwrap_or(child.start_byte());
+            if is_leading(&child) {
+                leading_start = Some(start);
+                continue;
+            }
+            let cut = line_start(source, start);
+            match cuts.last_mut() {
+                Some((last, _)) if cut > *last && cut < range.end => {
+                    cuts.push((cut, Some(child)));
+                }
+                // A member starting on the first line owns the first piece.
+                Some((_, member @ None)) if cut <= range.start => *member = Some(child),
+                _ => {}
+            }
+        }
+
+        let mut ranges = Vec::new();
+        let mut current: Option<Range<usize>> = None;
+        for (i, &(start, member)) in cuts.iter().enumerate() {
+            let end = cuts.get(i + 1).map_or(range.end, |(cut, _)| *cut);
+            let piece = start..end;
+
+            let candidate = current.as_ref().map_or(piece.clone(), |c| c.start..end);
+            if self.fits(&source[candidate.clone()]) || source[piece.clone()].trim().is_empty() {
+                current = Some(candidate);
+                continue;
+            }
+            ranges.extend(current.take());
+            if self.fits(&source[piece.clone()]) {
+                current = Some(piece);
+                continue;
+            }
+
+            match member.and_then(container_body) {
+                Some(body) => ranges.extend(self.split_members(source, body, piece)),
+                None => ranges.extend(self.split_lines(source, piece)),
+            }
+        }
+        ranges.extend(current);
+        ranges
+    }
+
+    /// Split an oversized declaration into runs of whole lines, repeating up
+    /// to `chunk_overlap` of trailing lines at the start of the next run.
+    fn split_lines(&self, source: &str, range: Range<usize>) -> Vec<Range<usize>> {
+        let mut lines = Vec::new();
+        let mut offset = range.start;
+        for line in source[range.clone()].split_inclusive('\n') {
+            lines.push(offset..offset + line.len());
+            offset += line.len();
+        }
+
+        let mut ranges = Vec::new();
+        let mut first = 0;
+        for (i, line) in lines.iter().enumerate() {
+            if i == first || self.fits(&source[lines[first].start..line.end]) {
+                continue;
+            }
+            ranges.push(lines[first].start..lines[i - 1].end);
+
+            let mut next = i;
+            while next > first + 1
+                && self.measure(&source[lines[next - 1].start..lines[i - 1].end])
+                    <= self.chunk_overlap
+                && self.fits(&source[lines[next - 1].start..line.end])
+            {
+                next -= 1;
+            }
+            first = next;
+        }
+        if let (Some(start), Some(end)) = (lines.get(first), lines.last()) {
+            ranges.push(start.start..end.end);
+        }
+        ranges
+    }
+
     fn get_tree_sitter_language(&self, lang: SupportedLanguage) -> tree_sitter::Language {
         match lang {
             SupportedLanguage::Rust => tree_sitter_rust::LANGUAGE.into(),
@@ -133,6 +276,10 @@ impl CodeChunker {
 }
 
 impl ContentChunker for CodeChunker {
+    fn chunker_type(&self) -> ChunkerType {
+        ChunkerType::Code
+    }
+
     fn chunk(&self, text: &str, context: Option<&ChunkContext>) -> Vec<TextChunk> {
         let context = context.cloned().unwrap_or_default();
 
@@ -149,50 +296,69 @@ impl ContentChunker for CodeChunker {
             }
         };
 
-        let ts_lang = self.get_tree_sitter_language(lang);
-
-        let chunk_config = ChunkConfig::new(self.chunk_size)
-            .with_overlap(self.chunk_overlap)
-            .expect("Invalid chunk config");
-
-        let splitter = match CodeSplitter::new(ts_lang, chunk_config) {
-            Ok(s) => s,
-            Err(e) => {
-                tracing::warn!(
-                    "Failed to create CodeSplitter for {:?}: {}, falling back",
-                    lang,
-                    e
-                );
-                return self.fallback_chunker.chunk(text, Some(&context));
-            }
-        };
-
-        let chunks: Vec<&str> = splitter.chunks(text).collect();
-
-        if chunks.is_empty() {
-            tracing::debug!("CodeSplitter returned no chunks, falling back to TextChunker");
+        let mut parser = Parser::new();
+        if let Err(e) = parser.set_language(&self.get_tree_sitter_language(lang)) {
+            tracing::warn!("Failed to load grammar for {:?}: {}, falling back", lang, e);
             return self.fallback_chunker.chunk(text, Some(&context));
         }
+        let Some(tree) = parser.parse(text, None) else {
+            tracing::debug!(
+                "Failed to parse {:?} source, falling back to TextChunker",
+                lang
+            );
+            return self.fallback_chunker.chunk(text, Some(&context));
+        };
 
+        let mut chunks = Vec::new();
+        for range in self.declaration_ranges(text, tree.root_node()) {
+            let chunk_text = text[range].trim_end().trim_start_matches(['\n', '\r']);
+            if chunk_text.trim().is_empty() {
+                continue;
+            }
+            if !self.fits(chunk_text) {
+                // A single line longer than a chunk, e.g. minified code.
+                chunks.extend(self.fallback_chunker.chunk(chunk_text, Some(&context)));
+                continue;
+            }
+            chunks.push(TextChunk {
+                content: self.add_context_prefix(chunk_text, text, &context),
+                token_count: count_chunk_tokens(self.token_counter.as_deref(), chunk_text),
+            });
+        }
         chunks
-            .iter()
-            .map(|chunk_text| {
-                let enriched_content = self.add_context_prefix(chunk_text, text, &context);
-                TextChunk {
-                    content: enriched_content,
-                    token_count: (chunk_text.len() as f32 / 4.0).ceil() as i32,
-                }
-            })
-            .collect()
     }
 }
 
+/// Comments and attributes belong to the declaration that follows them.
+fn is_leading(node: &Node<'_>) -> bool {
+    let kind = node.kind();
+    kind.contains("comment") || matches!(kind, "attribute_item" | "decorator")
+}
+
+/// The body holding a container declaration's members, looking through
+/// decorators and exports.
+fn container_body(node: Node<'_>) -> Option<Node<'_>> {
+    let declaration = WRAPPER_FIELDS
+        .iter()
+        .find_map(|field| node.child_by_field_name(field))
+        .unwrap_or(node);
+    CONTAINER_KINDS
+        .contains(&declaration.kind())
+        .then(|| declaration.child_by_field_name("body"))
+        .flatten()
+}
+
+fn line_start(source: &str, offset: usize) -> usize {
+    source[..offset].rfind('\n').map_or(0, |i| i + 1)
+}
+
 impl Default for CodeChunker {
     fn default() -> Self {
         Self {
             chunk_size: 512,
             chunk_overlap: 50,
             fallback_chunker: TextChunker::default(),
+            token_counter: None,
         }
     }
 }
@@ -392,6 +558,7 @@ fn other() {
             chunk_size: 60,
             chunk_overlap: 10,
             fallback_chunker: TextChunker::default(),
+            token_counter: None,
         };
         let context = ChunkContext {
             source_path: Some("src/lib.rs".to_string()),
@@ -422,6 +589,140 @@ fn gamma() {
         }
     }
 
+    fn small_chunker(chunk_size: usize) -> CodeChunker {
+        CodeChunker {
+            chunk_size,
+            chunk_overlap: 0,
+            fallback_chunker: TextChunker::default(),
+            token_counter: None,
+        }
+    }
+
+    /// Chunk `code` so that only one definition fits per chunk, and assert
+    /// every definition ends up whole in a single chunk.
+    fn assert_definitions_intact(path: &str, code: &str, definitions: &[&str]) {
+        let longest = definitions.iter().map(|d| d.chars().count()).max().unwrap();
+        let chunker = small_chunker(longest + 8);
+        let context = ChunkContext {
+            source_path: Some(path.to_string()),
+        };
+
+        let chunks = chunker.chunk(code, Some(&context));
+        assert!(chunks.len() >= definitions.len(), "{path}: {chunks:#?}");
+        for definition in definitions {
+            assert!(
+                chunks.iter().any(|c| c.content.contains(definition)),
+                "{path}: definition split across chunks: {definition}\n{chunks:#?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_keeps_rust_definitions_intact() {
+        let definitions = [
+            "/// Adds two numbers.\nfn add(a: i32, b: i32) -> i32 {\n    let sum = a + b;\n    sum\n}",
+            "#[derive(Debug)]\nstruct Point {\n    x: i32,\n    y: i32,\n}",
+            "fn greet(name: &str) {\n    let message = format!(\"hi {name}\");\n    println!(\"{message}\");\n}",
+        ];
+        let code = format!("use std::fmt;\n\n{}\n", definitions.join("\n\n"));
+        assert_definitions_intact("src/lib.rs", &code, &definitions);
+    }
+
+    #[test]
+    fn test_keeps_python_definitions_intact() {
+        let definitions = [
+            "@cache\ndef fib(n):\n    if n < 2:\n        return n\n    return fib(n - 1) + fib(n - 2)",
+            "def greet(name):\n    message = f\"hello {name}\"\n    print(message)\n    return message",
+            "class Counter:\n    def __init__(self):\n        self.count = 0",
+        ];
+        let code = format!("import os\n\n\n{}\n", definitions.join("\n\n\n"));
+        assert_definitions_intact("app/util.py", &code, &definitions);
+    }
+
+    #[test]
+    fn test_keeps_javascript_definitions_intact() {
+        let definitions = [
+            "function add(a, b) {\n  const sum = a + b;\n  return sum;\n}",
+            "export function greet(name) {\n  const message = `hi ${name}`;\n  console.log(message);\n}",
+            "const double = (x) => {\n  const result = x * 2;\n  return result;\n};",
+        ];
+        let code = format!("import fs from 'fs';\n\n{}\n", definitions.join("\n\n"));
+        assert_definitions_intact("src/util.js", &code, &definitions);
+    }
+
+    #[test]
+    fn test_keeps_typescript_definitions_intact() {
+        let definitions = [
+            "interface User {\n  id: number;\n  name: string;\n  email: string;\n}",
+            "export function greet(user: User): string {\n  return `hi ${user.name}`;\n}",
+            "function total(items: number[]): number {\n  return items.reduce((a, b) => a + b, 0);\n}",
+        ];
+        let code = format!(
+            "import {{ x }} from './x';\n\n{}\n",
+            definitions.join("\n\n")
+        );
+        assert_definitions_intact("src/user.ts", &code, &definitions);
+    }
+
+    #[test]
+    fn test_keeps_go_definitions_intact() {
+        let definitions = [
+            "// Add returns the sum.\nfunc Add(a int, b int) int {\n\tsum := a + b\n\treturn sum\n}",
+            "type Point struct {\n\tX int\n\tY int\n}",
+            "func (p Point) Norm() int {\n\treturn p.X*p.X + p.Y*p.Y\n}",
+        ];
+        let code = format!(
+            "package geo\n\nimport \"fmt\"\n\n{}\n",
+            definitions.join("\n\n")
+        );
+        assert_definitions_intact("geo/point.go", &code, &definitions);
+    }
+
+    #[test]
+    fn test_splits_oversized_java_class_between_methods() {
+        let methods = [
+            "    public int add(int a, int b) {\n        int sum = a + b;\n        return sum;\n    }",
+            "    public String greet(String name) {\n        return \"hi \" + name;\n    }",
+            "    private void log(String message) {\n        System.out.println(message);\n    }",
+        ];
+        let code = format!(
+            "package demo;\n\npublic class Calculator {{\n{}\n}}\n",
+            methods.join("\n\n")
+        );
+        assert_definitions_intact("src/Calculator.java", &code, &methods);
+    }
+
+    #[test]
+    fn test_splits_oversized_rust_impl_between_methods() {
+        let methods = [
+            "    pub fn new() -> Self {\n        Self { count: 0 }\n    }",
+            "    pub fn increment(&mut self) {\n        self.count += 1;\n    }",
+            "    pub fn count(&self) -> usize {\n        self.count\n    }",
+        ];
+        let code = format!("impl Counter {{\n{}\n}}\n", methods.join("\n\n"));
+        assert_definitions_intact("src/counter.rs", &code, &methods);
+    }
+
+    #[test]
+    fn test_oversized_function_falls_back_to_lines() {
+        let body: String = (0..30)
+            .map(|i| format!("    let value_{i} = {i} * 2;\n"))
+            .collect();
+        let code = format!("fn long() {{\n{body}}}\n");
+        let chunker = small_chunker(100);
+        let context = ChunkContext {
+            source_path: Some("src/long.rs".to_string()),
+        };
+
+        let chunks = chunker.chunk(&code, Some(&context));
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            let code_part = chunk.content.rsplit("\n\n").next().unwrap();
+            assert!(code_part.chars().count() <= 100, "{code_part}");
+            assert!(code_part.lines().all(|line| code.contains(line)));
+        }
+    }
+
     #[test]
     fn test_extract_imports() {
         let source = r#"use std::io;
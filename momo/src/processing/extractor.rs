@@ -1,7 +1,6 @@
-use scraper::{Html, Selector};
 use url::Url;
 
-use crate::error::{MomoError, Result};
+use crate::error::Result;
 use crate::models::DocumentType;
 use crate::processing::extractors::{self, ExtractedContent};
 use crate::processing::language::detect_language;
@@ -160,6 +159,8 @@ impl ContentExtractor {
             self.extract_from_url(content).await
         } else if Self::looks_like_html(content) {
             self.extract_from_html(content)
+        } else if extractors::JsonExtractor::looks_like_json(content) {
+            self.extract_from_json(content.as_bytes())
         } else {
             let doc_type = if Self::looks_like_code(content) {
                 DocumentType::Code
@@ -169,10 +170,16 @@ impl ContentExtractor {
             Ok(ExtractedContent {
                 text: content.to_string(),
                 title: None,
+                author: None,
                 doc_type,
                 url: None,
                 word_count: Self::count_words(content),
                 source_path: None,
+                confidence: None,
+                ocr_layout: None,
+                ocr_discarded: false,
+                transcript_segments: None,
+                transcript_language: None,
             })
         }
     }
@@ -225,6 +232,20 @@ impl ContentExtractor {
                 extracted.source_path = source_path;
                 Ok(extracted)
             }
+            DocumentType::Epub => {
+                let bytes = response.bytes().await?;
+                let mut extracted = self.extract_from_epub(&bytes)?;
+                extracted.url = Some(url_str.to_string());
+                extracted.source_path = source_path;
+                Ok(extracted)
+            }
+            DocumentType::Json => {
+                let bytes = response.bytes().await?;
+                let mut extracted = self.extract_from_json(&bytes)?;
+                extracted.url = Some(url_str.to_string());
+                extracted.source_path = source_path;
+                Ok(extracted)
+            }
             _ => {
                 let text = response.text().await?;
 
@@ -235,10 +256,16 @@ impl ContentExtractor {
                         return Ok(ExtractedContent {
                             text,
                             title: None,
+                            author: None,
                             doc_type: DocumentType::Code,
                             url: Some(url_str.to_string()),
                             word_count,
                             source_path,
+                            confidence: None,
+                            ocr_layout: None,
+                            ocr_discarded: false,
+                            transcript_segments: None,
+                            transcript_language: None,
                         });
                     }
                 }
@@ -265,42 +292,27 @@ impl ContentExtractor {
     }
 
     pub fn extract_from_html(&self, html: &str) -> Result<ExtractedContent> {
-        let document = Html::parse_document(html);
-
-        let title = Self::extract_title(&document);
-        let text = Self::extract_text(&document);
-        let word_count = Self::count_words(&text);
-
-        Ok(ExtractedContent {
-            text,
-            title,
-            doc_type: DocumentType::Webpage,
-            url: None,
-            word_count,
-            source_path: None,
-        })
+        extractors::HtmlExtractor::extract(html)
     }
 
     pub fn extract_from_pdf(&self, bytes: &[u8], url: Option<&str>) -> Result<ExtractedContent> {
-        let text = pdf_extract::extract_text_from_mem(bytes)
-            .map_err(|e| MomoError::Processing(format!("PDF extraction failed: {e}")))?;
-
-        let word_count = Self::count_words(&text);
-
-        Ok(ExtractedContent {
-            text,
-            title: None,
-            doc_type: DocumentType::Pdf,
-            url: url.map(String::from),
-            word_count,
-            source_path: None,
-        })
+        let mut extracted = extractors::PdfExtractor::extract(bytes)?;
+        extracted.url = url.map(String::from);
+        Ok(extracted)
     }
 
     pub fn extract_from_csv(&self, bytes: &[u8]) -> Result<ExtractedContent> {
         extractors::CsvExtractor::extract(bytes)
     }
 
+    pub fn extract_from_epub(&self, bytes: &[u8]) -> Result<ExtractedContent> {
+        extractors::EpubExtractor::extract(bytes)
+    }
+
+    pub fn extract_from_json(&self, bytes: &[u8]) -> Result<ExtractedContent> {
+        extractors::JsonExtractor::extract(bytes)
+    }
+
     pub fn extract_from_docx(&self, bytes: &[u8]) -> Result<ExtractedContent> {
         extractors::DocxExtractor::extract(bytes)
     }
@@ -313,84 +325,6 @@ impl ContentExtractor {
         extractors::PptxExtractor::extract(bytes)
     }
 
-    fn extract_title(document: &Html) -> Option<String> {
-        let title_selector = Selector::parse("title").ok()?;
-        document
-            .select(&title_selector)
-            .next()
-            .map(|el| el.text().collect::<String>().trim().to_string())
-            .filter(|s| !s.is_empty())
-    }
-
-    fn extract_text(document: &Html) -> String {
-        let selectors_to_remove = [
-            "script", "style", "noscript", "nav", "header", "footer", "aside", "iframe", "svg",
-        ];
-
-        let body_selector = Selector::parse("body").unwrap();
-        let article_selector = Selector::parse("article, main, .content, #content").unwrap();
-
-        let content_root = document
-            .select(&article_selector)
-            .next()
-            .or_else(|| document.select(&body_selector).next());
-
-        let Some(root) = content_root else {
-            return document.root_element().text().collect::<String>();
-        };
-
-        let mut text = String::new();
-
-        for node in root.descendants() {
-            if let Some(element) = node.value().as_element() {
-                let tag_name = element.name();
-                if selectors_to_remove.contains(&tag_name) {
-                    continue;
-                }
-            }
-
-            if let Some(text_node) = node.value().as_text() {
-                let content = text_node.trim();
-                if !content.is_empty() {
-                    if !text.is_empty() && !text.ends_with(' ') && !text.ends_with('\n') {
-                        text.push(' ');
-                    }
-                    text.push_str(content);
-                }
-            }
-        }
-
-        Self::clean_text(&text)
-    }
-
-    fn clean_text(text: &str) -> String {
-        let mut result = String::with_capacity(text.len());
-        let mut prev_was_whitespace = false;
-        let mut consecutive_newlines = 0;
-
-        for c in text.chars() {
-            if c == '\n' {
-                consecutive_newlines += 1;
-                if consecutive_newlines <= 2 {
-                    result.push(c);
-                }
-                prev_was_whitespace = true;
-            } else if c.is_whitespace() {
-                consecutive_newlines = 0;
-                if !prev_was_whitespace {
-                    result.push(' ');
-                    prev_was_whitespace = true;
-                }
-            } else {
-                consecutive_newlines = 0;
-                result.push(c);
-                prev_was_whitespace = false;
-            }
-        }
-
-        result.trim().to_string()
-    }
-
     /// Heuristic to detect if plain text content looks like source code.
     /// Checks for common code patterns: function definitions, import statements,
     /// braces/semicolons density, and language-specific keywords.
@@ -531,11 +465,20 @@ impl ContentExtractor {
             || url.ends_with(".pptx")
         {
             DocumentType::Pptx
+        } else if content_type.contains("application/epub+zip") || url.ends_with(".epub") {
+            DocumentType::Epub
         } else if content_type.contains("text/csv")
             || content_type.contains("application/csv")
             || url.ends_with(".csv")
         {
             DocumentType::Csv
+        } else if content_type.contains("application/json")
+            || content_type.contains("application/x-ndjson")
+            || content_type.contains("application/jsonl")
+            || url.ends_with(".json")
+            || url.ends_with(".jsonl")
+        {
+            DocumentType::Json
         } else if detect_language(url).is_some() {
             DocumentType::Code
         } else {
@@ -562,6 +505,9 @@ impl ContentExtractor {
 
         if bytes.starts_with(&[0x50, 0x4B, 0x03, 0x04]) {
             if let Ok(mut archive) = zip::ZipArchive::new(std::io::Cursor::new(bytes)) {
+                if Self::is_epub(&mut archive) {
+                    return DocumentType::Epub;
+                }
                 if archive.by_name("[Content_Types].xml").is_ok() {
                     if archive.by_name("word/document.xml").is_ok() {
                         return DocumentType::Docx;
@@ -577,6 +523,11 @@ impl ContentExtractor {
             return DocumentType::Unknown;
         }
 
+        // Before CSV: JSONL lines are full of commas.
+        if std::str::from_utf8(bytes).is_ok_and(extractors::JsonExtractor::looks_like_json) {
+            return DocumentType::Json;
+        }
+
         if Self::looks_like_csv(bytes) {
             return DocumentType::Csv;
         }
@@ -612,6 +563,16 @@ impl ContentExtractor {
         DocumentType::Unknown
     }
 
+    /// EPUBs store their media type in an uncompressed `mimetype` entry.
+    fn is_epub(archive: &mut zip::ZipArchive<std::io::Cursor<&[u8]>>) -> bool {
+        let Ok(mut file) = archive.by_name("mimetype") else {
+            return false;
+        };
+        let mut mimetype = String::new();
+        std::io::Read::read_to_string(&mut file, &mut mimetype).is_ok()
+            && mimetype.trim() == "application/epub+zip"
+    }
+
     pub fn detect_type_from_upload(
         bytes: &[u8],
         file_name: Option<&str>,
@@ -633,6 +594,21 @@ impl ContentExtractor {
             if ct_lower.starts_with("video/") {
                 return DocumentType::Video;
             }
+            if ct_lower.starts_with("text/html") || ct_lower.starts_with("application/xhtml+xml") {
+                return DocumentType::Webpage;
+            }
+            if ct_lower.starts_with("application/epub+zip") {
+                return DocumentType::Epub;
+            }
+            if ct_lower.starts_with("application/pdf") {
+                return DocumentType::Pdf;
+            }
+            if ct_lower.starts_with("application/json")
+                || ct_lower.starts_with("application/x-ndjson")
+                || ct_lower.starts_with("application/jsonl")
+            {
+                return DocumentType::Json;
+            }
         }
 
         if let Some(name) = file_name {
@@ -653,6 +629,18 @@ impl ContentExtractor {
             {
                 return DocumentType::Video;
             }
+            if lower.ends_with(".json") || lower.ends_with(".jsonl") {
+                return DocumentType::Json;
+            }
+            if lower.ends_with(".epub") {
+                return DocumentType::Epub;
+            }
+            if lower.ends_with(".pdf") {
+                return DocumentType::Pdf;
+            }
+            if lower.ends_with(".html") || lower.ends_with(".htm") || lower.ends_with(".xhtml") {
+                return DocumentType::Webpage;
+            }
         }
 
         by_bytes
@@ -834,6 +822,55 @@ mod tests {
             ),
             DocumentType::Video
         );
+
+        assert_eq!(
+            ContentExtractor::detect_type_from_upload(
+                b"<div>Fragment</div>",
+                Some("page.htm"),
+                None
+            ),
+            DocumentType::Webpage
+        );
+    }
+
+    #[test]
+    fn test_detect_json_types() {
+        let jsonl = b"{\"a\": 1, \"b\": 2}\n{\"a\": 3, \"b\": 4}\n";
+        assert_eq!(
+            ContentExtractor::detect_type_from_bytes(jsonl),
+            DocumentType::Json
+        );
+        assert_eq!(
+            ContentExtractor::detect_type_from_upload(b"not json", Some("export.jsonl"), None),
+            DocumentType::Json
+        );
+        assert_eq!(
+            ContentExtractor::detect_type_from_content_type(
+                "application/json; charset=utf-8",
+                "https://example.com/api/items"
+            ),
+            DocumentType::Json
+        );
+        assert_eq!(
+            ContentExtractor::detect_type_from_content_type(
+                "text/plain",
+                "https://example.com/data.json"
+            ),
+            DocumentType::Json
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extract_routes_json_text() {
+        let extractor = ContentExtractor::new();
+        let extracted = extractor
+            .extract(r#"{"title": "Release notes", "version": "1.2"}"#)
+            .await
+            .unwrap();
+
+        assert_eq!(extracted.doc_type, DocumentType::Json);
+        assert_eq!(extracted.title.as_deref(), Some("Release notes"));
+        assert!(extracted.text.contains("version: 1.2"));
     }
 
     #[test]
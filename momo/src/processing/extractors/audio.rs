@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use crate::config::TranscriptionConfig;
 use crate::error::{MomoError, Result};
 use crate::models::DocumentType;
@@ -59,18 +61,48 @@ impl AudioExtractor {
 
         // Provider handles preprocessing: Local backend decodes/resamples for Whisper,
         // API backend uploads original bytes to cloud provider.
-        let text = provider.transcribe(bytes).await?;
-        let word_count = text.split_whitespace().count() as i32;
+        let output = provider.transcribe(bytes).await?;
+        let word_count = output.text.split_whitespace().count() as i32;
 
         Ok(ExtractedContent {
-            text,
+            text: output.text,
             title: None,
+            author: None,
             doc_type: DocumentType::Audio,
             url: None,
             word_count,
             source_path: None,
+            confidence: output.confidence,
+            ocr_layout: None,
+            ocr_discarded: false,
+            transcript_segments: (!output.segments.is_empty()).then_some(output.segments),
+            transcript_language: output.language,
         })
     }
+
+    /// Like [`extract`](Self::extract), for audio streamed to disk by a media
+    /// upload. The size limit is checked before the file is read.
+    pub async fn extract_file(
+        path: &Path,
+        provider: &TranscriptionProvider,
+        config: &TranscriptionConfig,
+    ) -> Result<ExtractedContent> {
+        let size = tokio::fs::metadata(path)
+            .await
+            .map_err(|e| MomoError::Transcription(format!("Uploaded audio file unavailable: {e}")))?
+            .len();
+        if size > config.max_file_size {
+            return Err(MomoError::Transcription(format!(
+                "Audio file size {} exceeds limit {}",
+                size, config.max_file_size
+            )));
+        }
+
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(|e| MomoError::Transcription(format!("Failed to read uploaded audio: {e}")))?;
+        Self::extract(&bytes, provider, config).await
+    }
 }
 
 #[cfg(test)]
@@ -134,6 +166,31 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_extract_file_rejects_oversized_file_before_reading() {
+        let config = TranscriptionConfig {
+            max_file_size: 100,
+            ..TranscriptionConfig::default()
+        };
+        let provider = TranscriptionProvider::unavailable("test");
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), vec![0u8; 200]).unwrap();
+
+        let err = AudioExtractor::extract_file(file.path(), &provider, &config)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("exceeds"),
+            "Error should mention size limit: {err}"
+        );
+
+        let missing = file.path().with_extension("missing");
+        assert!(AudioExtractor::extract_file(&missing, &provider, &config)
+            .await
+            .is_err());
+    }
+
     fn create_wav_bytes(duration_secs: u32, sample_rate: u32) -> Vec<u8> {
         let num_samples = duration_secs * sample_rate;
         let data_size = num_samples * 2; // 16-bit mono
@@ -50,10 +50,16 @@ impl CsvExtractor {
         Ok(ExtractedContent {
             text: markdown,
             title: None,
+            author: None,
             doc_type: DocumentType::Csv,
             url: None,
             word_count,
             source_path: None,
+            confidence: None,
+            ocr_layout: None,
+            ocr_discarded: false,
+            transcript_segments: None,
+            transcript_language: None,
         })
     }
 }
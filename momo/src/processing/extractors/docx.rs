@@ -51,10 +51,16 @@ impl DocxExtractor {
         Ok(ExtractedContent {
             text,
             title,
+            author: None,
             doc_type: DocumentType::Docx,
             url: None,
             word_count,
             source_path: None,
+            confidence: None,
+            ocr_layout: None,
+            ocr_discarded: false,
+            transcript_segments: None,
+            transcript_language: None,
         })
     }
 
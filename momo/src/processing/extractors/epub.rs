@@ -0,0 +1,364 @@
+//! EPUB extractor using zip + quick-xml for the package and scraper for chapters
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use scraper::{ElementRef, Html, Selector};
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+use zip::ZipArchive;
+
+use super::ExtractedContent;
+use crate::error::{MomoError, Result};
+use crate::models::DocumentType;
+
+const CONTAINER_PATH: &str = "META-INF/container.xml";
+const BLOCK_TAGS: [&str; 10] = [
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "p",
+    "li",
+    "blockquote",
+    "pre",
+];
+
+/// Title, authors and reading order read from the OPF package document.
+#[derive(Debug, Default)]
+struct Package {
+    title: Option<String>,
+    creators: Vec<String>,
+    /// Manifest item id -> href, relative to the OPF file.
+    manifest: HashMap<String, String>,
+    /// Manifest item ids in reading order.
+    spine: Vec<String>,
+}
+
+pub struct EpubExtractor;
+
+impl EpubExtractor {
+    /// Extract an EPUB as markdown, one section per spine item in reading
+    /// order, each starting with its chapter heading.
+    pub fn extract(bytes: &[u8]) -> Result<ExtractedContent> {
+        let cursor = Cursor::new(bytes);
+        let mut archive = ZipArchive::new(cursor)
+            .map_err(|e| MomoError::Processing(format!("EPUB parse error: {e}")))?;
+
+        let opf_path = Self::find_package_path(&mut archive)?;
+        let opf = Self::read_file_from_archive(&mut archive, &opf_path)?;
+        let package = Self::parse_package(&opf)?;
+        let base_dir = opf_path.rsplit_once('/').map_or("", |(dir, _)| dir);
+
+        let mut chapters = Vec::new();
+        for idref in &package.spine {
+            let Some(href) = package.manifest.get(idref) else {
+                continue;
+            };
+            let path = resolve_href(base_dir, href);
+            // Missing spine files are skipped rather than failing the whole book.
+            let Ok(xhtml) = Self::read_file_from_archive(&mut archive, &path) else {
+                tracing::debug!(path = %path, "EPUB spine item not found in archive");
+                continue;
+            };
+            let chapter = Self::chapter_markdown(&xhtml);
+            if !chapter.is_empty() {
+                chapters.push(chapter);
+            }
+        }
+
+        let text = chapters.join("\n\n");
+        let word_count = Self::count_words(&text);
+        let author = (!package.creators.is_empty()).then(|| package.creators.join(", "));
+
+        Ok(ExtractedContent {
+            text,
+            title: package.title,
+            author,
+            doc_type: DocumentType::Epub,
+            url: None,
+            word_count,
+            source_path: None,
+            confidence: None,
+            ocr_layout: None,
+            ocr_discarded: false,
+            transcript_segments: None,
+            transcript_language: None,
+        })
+    }
+
+    /// Locate the OPF package via `META-INF/container.xml`, falling back to
+    /// the first `.opf` file in the archive.
+    fn find_package_path(archive: &mut ZipArchive<Cursor<&[u8]>>) -> Result<String> {
+        if let Ok(container) = Self::read_file_from_archive(archive, CONTAINER_PATH) {
+            let mut reader = Reader::from_str(&container);
+            let mut buf = Vec::new();
+            loop {
+                match reader.read_event_into(&mut buf) {
+                    Ok(Event::Empty(e)) | Ok(Event::Start(e))
+                        if e.local_name().as_ref() == b"rootfile" =>
+                    {
+                        let full_path = e
+                            .attributes()
+                            .flatten()
+                            .find(|attr| attr.key.as_ref() == b"full-path")
+                            .and_then(|attr| {
+                                std::str::from_utf8(&attr.value).ok().map(String::from)
+                            });
+                        if let Some(path) = full_path {
+                            return Ok(path);
+                        }
+                    }
+                    Ok(Event::Eof) | Err(_) => break,
+                    _ => {}
+                }
+                buf.clear();
+            }
+        }
+
+        archive
+            .file_names()
+            .find(|name| name.ends_with(".opf"))
+            .map(String::from)
+            .ok_or_else(|| MomoError::Processing("EPUB parse error: no OPF package".to_string()))
+    }
+
+    fn parse_package(xml: &str) -> Result<Package> {
+        // Not trimming text events: entity references split text into
+        // several events and trimming would drop the spaces around them.
+        let mut reader = Reader::from_str(xml);
+
+        let mut package = Package::default();
+        let mut current: Option<&'static str> = None;
+        let mut value = String::new();
+        let mut buf = Vec::new();
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                    b"title" => current = Some("title"),
+                    b"creator" => current = Some("creator"),
+                    _ => {}
+                },
+                Ok(Event::Empty(e)) => match e.local_name().as_ref() {
+                    b"item" => {
+                        let mut id = None;
+                        let mut href = None;
+                        for attr in e.attributes().flatten() {
+                            let val = std::str::from_utf8(&attr.value).ok().map(unescape);
+                            match attr.key.as_ref() {
+                                b"id" => id = val,
+                                b"href" => href = val,
+                                _ => {}
+                            }
+                        }
+                        if let (Some(id), Some(href)) = (id, href) {
+                            package.manifest.insert(id, href);
+                        }
+                    }
+                    b"itemref" => {
+                        let idref = e
+                            .attributes()
+                            .flatten()
+                            .find(|attr| attr.key.as_ref() == b"idref")
+                            .and_then(|attr| std::str::from_utf8(&attr.value).ok().map(unescape));
+                        if let Some(idref) = idref {
+                            package.spine.push(idref);
+                        }
+                    }
+                    _ => {}
+                },
+                Ok(Event::Text(e)) if current.is_some() => {
+                    if let Ok(text) = e.decode() {
+                        value.push_str(&text);
+                    }
+                }
+                Ok(Event::GeneralRef(e)) if current.is_some() => {
+                    if let Ok(Some(ch)) = e.resolve_char_ref() {
+                        value.push(ch);
+                    } else if let Some(resolved) = e
+                        .decode()
+                        .ok()
+                        .and_then(|name| quick_xml::escape::resolve_predefined_entity(&name))
+                    {
+                        value.push_str(resolved);
+                    }
+                }
+                Ok(Event::End(e)) => {
+                    let field = match e.local_name().as_ref() {
+                        b"title" => Some("title"),
+                        b"creator" => Some("creator"),
+                        _ => None,
+                    };
+                    if field.is_some() && field == current {
+                        let text = value.trim().to_string();
+                        if !text.is_empty() {
+                            if field == Some("title") {
+                                package.title.get_or_insert(text);
+                            } else {
+                                package.creators.push(text);
+                            }
+                        }
+                        current = None;
+                        value.clear();
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => {
+                    return Err(MomoError::Processing(format!(
+                        "Error parsing EPUB package: {e}"
+                    )))
+                }
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(package)
+    }
+
+    /// Convert one XHTML content document to markdown. Headings keep their
+    /// level; a chapter without any heading gets its `<title>` as one.
+    fn chapter_markdown(xhtml: &str) -> String {
+        let document = Html::parse_document(xhtml);
+        let blocks = Selector::parse(&BLOCK_TAGS.join(", ")).expect("valid selector");
+
+        let mut parts = Vec::new();
+        let mut has_heading = false;
+        for element in document.select(&blocks) {
+            // Nested blocks (a <p> inside an <li>) are covered by their outermost block.
+            if element
+                .ancestors()
+                .filter_map(ElementRef::wrap)
+                .any(|ancestor| BLOCK_TAGS.contains(&ancestor.value().name()))
+            {
+                continue;
+            }
+
+            let name = element.value().name();
+            let text = if name == "pre" {
+                element.text().collect::<String>().trim_end().to_string()
+            } else {
+                collapse_whitespace(&element.text().collect::<String>())
+            };
+            if text.is_empty() {
+                continue;
+            }
+
+            match heading_level(name) {
+                Some(level) => {
+                    has_heading = true;
+                    parts.push(format!("{} {text}", "#".repeat(level)));
+                }
+                None if name == "li" => parts.push(format!("- {text}")),
+                None => parts.push(text),
+            }
+        }
+
+        if parts.is_empty() {
+            // Content not wrapped in block elements at all.
+            let body = Selector::parse("body").expect("valid selector");
+            if let Some(body) = document.select(&body).next() {
+                let text = collapse_whitespace(&body.text().collect::<String>());
+                if !text.is_empty() {
+                    parts.push(text);
+                }
+            }
+        }
+
+        if !has_heading && !parts.is_empty() {
+            let title = Selector::parse("title").expect("valid selector");
+            if let Some(title) = document
+                .select(&title)
+                .next()
+                .map(|el| collapse_whitespace(&el.text().collect::<String>()))
+                .filter(|title| !title.is_empty())
+            {
+                parts.insert(0, format!("# {title}"));
+            }
+        }
+
+        parts.join("\n\n")
+    }
+
+    fn read_file_from_archive(
+        archive: &mut ZipArchive<Cursor<&[u8]>>,
+        path: &str,
+    ) -> Result<String> {
+        let mut file = archive
+            .by_name(path)
+            .map_err(|e| MomoError::Processing(format!("Failed to read {path} from EPUB: {e}")))?;
+
+        let mut content = String::new();
+        file.read_to_string(&mut content)
+            .map_err(|e| MomoError::Processing(format!("Failed to read {path} content: {e}")))?;
+
+        Ok(content)
+    }
+
+    fn count_words(text: &str) -> i32 {
+        text.split_whitespace().count() as i32
+    }
+}
+
+fn heading_level(tag: &str) -> Option<usize> {
+    tag.strip_prefix('h')?
+        .parse::<usize>()
+        .ok()
+        .filter(|level| (1..=6).contains(level))
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn unescape(text: &str) -> String {
+    quick_xml::escape::unescape(text)
+        .map(|unescaped| unescaped.into_owned())
+        .unwrap_or_else(|_| text.to_string())
+}
+
+/// Resolve a manifest href against the OPF directory, dropping any fragment.
+fn resolve_href(base_dir: &str, href: &str) -> String {
+    let href = href.split('#').next().unwrap_or(href);
+    let mut segments: Vec<&str> = base_dir.split('/').filter(|s| !s.is_empty()).collect();
+    for segment in href.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_href() {
+        assert_eq!(
+            resolve_href("OEBPS", "text/ch1.xhtml"),
+            "OEBPS/text/ch1.xhtml"
+        );
+        assert_eq!(
+            resolve_href("OEBPS/text", "../ch1.xhtml#top"),
+            "OEBPS/ch1.xhtml"
+        );
+        assert_eq!(resolve_href("", "ch1.xhtml"), "ch1.xhtml");
+    }
+
+    #[test]
+    fn test_chapter_without_heading_uses_title() {
+        let xhtml =
+            "<html><head><title>Prologue</title></head><body><p>It began.</p></body></html>";
+        assert_eq!(
+            EpubExtractor::chapter_markdown(xhtml),
+            "# Prologue\n\nIt began."
+        );
+    }
+}
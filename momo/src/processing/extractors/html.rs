@@ -0,0 +1,267 @@
+//! HTML extractor: readability-style main content selection, converted to markdown
+
+use htmd::options::{BulletListMarker, Options};
+use htmd::HtmlToMarkdown;
+use scraper::{ElementRef, Html, Selector};
+
+use super::ExtractedContent;
+use crate::error::Result;
+use crate::models::DocumentType;
+
+/// Elements that never carry main content.
+const BOILERPLATE_TAGS: [&str; 13] = [
+    "head", "script", "style", "noscript", "template", "nav", "header", "footer", "aside",
+    "iframe", "svg", "form", "button",
+];
+
+/// Elements that explicitly mark the main content of a page.
+const MAIN_CONTENT_SELECTOR: &str = r#"article, main, [role="main"], #content, .content"#;
+
+pub struct HtmlExtractor;
+
+impl HtmlExtractor {
+    /// Extract the main content of an HTML document as markdown.
+    ///
+    /// Navigation, headers, footers and scripts are dropped. If conversion
+    /// yields nothing usable, falls back to the page's tag-stripped text.
+    pub fn extract(html: &str) -> Result<ExtractedContent> {
+        let document = Html::parse_document(html);
+
+        let title = Self::title(&document);
+        let url = Self::canonical_url(&document);
+        let text = Self::main_content(&document)
+            .and_then(|root| Self::to_markdown(&root.html()))
+            .unwrap_or_else(|| Self::strip_tags(&document));
+        let word_count = text.split_whitespace().count() as i32;
+
+        Ok(ExtractedContent {
+            text,
+            title,
+            author: None,
+            doc_type: DocumentType::Webpage,
+            url,
+            word_count,
+            source_path: None,
+            confidence: None,
+            ocr_layout: None,
+            ocr_discarded: false,
+            transcript_segments: None,
+            transcript_language: None,
+        })
+    }
+
+    /// `<title>`, then `og:title`, then the first `<h1>`.
+    fn title(document: &Html) -> Option<String> {
+        let text_of = |selector: &str| {
+            let selector = Selector::parse(selector).ok()?;
+            document
+                .select(&selector)
+                .map(|el| collapse_whitespace(&el.text().collect::<String>()))
+                .find(|text| !text.is_empty())
+        };
+        let og_title = || {
+            let selector = Selector::parse(r#"meta[property="og:title"]"#).ok()?;
+            document
+                .select(&selector)
+                .filter_map(|el| el.value().attr("content"))
+                .map(collapse_whitespace)
+                .find(|text| !text.is_empty())
+        };
+
+        text_of("title").or_else(og_title).or_else(|| text_of("h1"))
+    }
+
+    /// Absolute `<link rel="canonical">` href, if present.
+    fn canonical_url(document: &Html) -> Option<String> {
+        let selector = Selector::parse(r#"link[rel~="canonical"]"#).ok()?;
+        document
+            .select(&selector)
+            .filter_map(|el| el.value().attr("href"))
+            .find_map(|href| url::Url::parse(href.trim()).ok())
+            .map(String::from)
+    }
+
+    /// Pick the element holding the main content: an explicit content
+    /// landmark if there is one, otherwise the container whose direct
+    /// paragraphs carry the most text, otherwise `<body>`.
+    fn main_content(document: &Html) -> Option<ElementRef<'_>> {
+        let landmark = Selector::parse(MAIN_CONTENT_SELECTOR).ok()?;
+        if let Some(root) = document
+            .select(&landmark)
+            .find(|el| !is_inside_boilerplate(el) && !el.text().all(|t| t.trim().is_empty()))
+        {
+            return Some(root);
+        }
+
+        let containers = Selector::parse("div, section, td").ok()?;
+        let best = document
+            .select(&containers)
+            .filter(|el| !is_inside_boilerplate(el))
+            .map(|el| (paragraph_score(&el), el))
+            .filter(|(score, _)| *score > 0)
+            .max_by_key(|(score, _)| *score)
+            .map(|(_, el)| el);
+        if best.is_some() {
+            return best;
+        }
+
+        let body = Selector::parse("body").ok()?;
+        document.select(&body).next()
+    }
+
+    fn to_markdown(html: &str) -> Option<String> {
+        let converter = HtmlToMarkdown::builder()
+            .options(Options {
+                bullet_list_marker: BulletListMarker::Dash,
+                ..Default::default()
+            })
+            .skip_tags(BOILERPLATE_TAGS.to_vec())
+            .build();
+
+        let markdown = converter.convert(html).ok()?;
+        let markdown = collapse_blank_lines(&markdown);
+        (!markdown.is_empty()).then_some(markdown)
+    }
+
+    /// Last resort for pages the markdown conversion can't make sense of.
+    fn strip_tags(document: &Html) -> String {
+        let text = document
+            .root_element()
+            .descendants()
+            .filter_map(|node| {
+                let text = node.value().as_text()?;
+                let parent = node.parent().and_then(ElementRef::wrap)?;
+                let skip = matches!(
+                    parent.value().name(),
+                    "script" | "style" | "noscript" | "template"
+                );
+                (!skip).then_some(&**text)
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        collapse_whitespace(&text)
+    }
+}
+
+fn is_inside_boilerplate(element: &ElementRef<'_>) -> bool {
+    element
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .any(|ancestor| BOILERPLATE_TAGS.contains(&ancestor.value().name()))
+}
+
+/// Text length of an element's direct `<p>` children.
+fn paragraph_score(element: &ElementRef<'_>) -> usize {
+    element
+        .children()
+        .filter_map(ElementRef::wrap)
+        .filter(|child| child.value().name() == "p")
+        .map(|p| p.text().map(|t| t.trim().len()).sum::<usize>())
+        .sum()
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn collapse_blank_lines(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut blank_run = 0;
+    for line in text.trim().lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            blank_run += 1;
+            if blank_run > 1 {
+                continue;
+            }
+        } else {
+            blank_run = 0;
+        }
+        result.push_str(line);
+        result.push('\n');
+    }
+    result.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extracts_main_content_as_markdown() {
+        let html = r#"<!DOCTYPE html>
+<html>
+<head>
+    <title>Release Notes</title>
+    <link rel="canonical" href="https://example.com/releases/1.2">
+    <script>trackVisitor();</script>
+</head>
+<body>
+    <nav><a href="/">Home</a><a href="/docs">Docs</a></nav>
+    <article>
+        <h1>Version 1.2</h1>
+        <p>Faster imports and smaller indexes.</p>
+        <h2>Fixes</h2>
+        <ul><li>Search no longer times out</li></ul>
+    </article>
+    <footer>Copyright 2024</footer>
+</body>
+</html>"#;
+        let extracted = HtmlExtractor::extract(html).unwrap();
+
+        assert_eq!(extracted.doc_type, DocumentType::Webpage);
+        assert_eq!(extracted.title.as_deref(), Some("Release Notes"));
+        assert_eq!(
+            extracted.url.as_deref(),
+            Some("https://example.com/releases/1.2")
+        );
+        assert!(extracted.text.contains("# Version 1.2"));
+        assert!(extracted.text.contains("## Fixes"));
+        assert!(extracted
+            .text
+            .lines()
+            .any(|line| line.starts_with('-') && line.ends_with("Search no longer times out")));
+        assert!(!extracted.text.contains("Home"));
+        assert!(!extracted.text.contains("Copyright"));
+        assert!(!extracted.text.contains("trackVisitor"));
+    }
+
+    #[test]
+    fn test_picks_densest_container_without_landmarks() {
+        let html = r#"<html><body>
+            <div class="sidebar"><p>Related links</p></div>
+            <div class="post">
+                <p>The first paragraph of the actual post is fairly long.</p>
+                <p>And the second paragraph adds even more substance to it.</p>
+            </div>
+        </body></html>"#;
+        let extracted = HtmlExtractor::extract(html).unwrap();
+
+        assert!(extracted.text.contains("actual post"));
+        assert!(!extracted.text.contains("Related links"));
+    }
+
+    #[test]
+    fn test_title_falls_back_to_heading() {
+        let extracted =
+            HtmlExtractor::extract("<html><body><h1>Heading</h1></body></html>").unwrap();
+        assert_eq!(extracted.title.as_deref(), Some("Heading"));
+        assert!(extracted.url.is_none());
+    }
+
+    #[test]
+    fn test_malformed_html_still_yields_text() {
+        let html = "<html><body><p>Unclosed <b>bold</i> and</span> <p>stray tail";
+        let extracted = HtmlExtractor::extract(html).unwrap();
+
+        assert!(extracted.text.contains("Unclosed"));
+        assert!(extracted.text.contains("tail"));
+    }
+
+    #[test]
+    fn test_ignores_relative_canonical() {
+        let html =
+            r#"<html><head><link rel="canonical" href="/page"></head><body><p>x</p></body></html>"#;
+        assert!(HtmlExtractor::extract(html).unwrap().url.is_none());
+    }
+}
@@ -2,7 +2,7 @@ use super::ExtractedContent;
 use crate::config::OcrConfig;
 use crate::error::Result;
 use crate::models::DocumentType;
-use crate::ocr::{preprocess_image, OcrProvider};
+use crate::ocr::{preprocess_image, OcrOutput, OcrProvider};
 
 pub struct ImageExtractor;
 
@@ -12,26 +12,47 @@ impl ImageExtractor {
     /// # Arguments
     /// * `bytes` - Raw image bytes (PNG, JPEG, etc.)
     /// * `ocr_provider` - OCR provider instance for text extraction
-    /// * `config` - OCR configuration for preprocessing
+    /// * `config` - OCR configuration for preprocessing and layout output
     ///
     /// # Returns
-    /// ExtractedContent with extracted text and metadata
+    /// ExtractedContent with extracted text and metadata, plus the OCR text
+    /// blocks when `config.include_layout` is set
     pub async fn extract(
         bytes: &[u8],
         ocr_provider: &OcrProvider,
         config: &OcrConfig,
     ) -> Result<ExtractedContent> {
         let processed = preprocess_image(bytes, config)?;
-        let text = ocr_provider.ocr(&processed).await?;
-        let word_count = text.split_whitespace().count() as i32;
+        let (mut output, mut ocr_layout) = if config.include_layout {
+            let layout = ocr_provider.ocr_with_layout(&processed).await?;
+            (layout.output, Some(layout.blocks))
+        } else {
+            (ocr_provider.ocr(&processed).await?, None)
+        };
+        let ocr_discarded = below_confidence_floor(&output, config.min_document_confidence);
+        if ocr_discarded {
+            tracing::warn!(
+                confidence = ?output.confidence,
+                "OCR confidence below document floor; discarding extracted text"
+            );
+            output.text.clear();
+            ocr_layout = ocr_layout.map(|_| Vec::new());
+        }
+        let word_count = output.text.split_whitespace().count() as i32;
 
         Ok(ExtractedContent {
-            text,
+            text: output.text,
             title: None,
+            author: None,
             doc_type: DocumentType::Image,
             url: None,
             word_count,
             source_path: None,
+            confidence: output.confidence,
+            ocr_layout,
+            ocr_discarded,
+            transcript_segments: None,
+            transcript_language: None,
         })
     }
 
@@ -48,20 +69,35 @@ impl ImageExtractor {
     /// ExtractedContent with extracted text and metadata
     #[allow(dead_code)]
     pub async fn extract_raw(bytes: &[u8], ocr_provider: &OcrProvider) -> Result<ExtractedContent> {
-        let text = ocr_provider.ocr(bytes).await?;
-        let word_count = text.split_whitespace().count() as i32;
+        let output = ocr_provider.ocr(bytes).await?;
+        let word_count = output.text.split_whitespace().count() as i32;
 
         Ok(ExtractedContent {
-            text,
+            text: output.text,
             title: None,
+            author: None,
             doc_type: DocumentType::Image,
             url: None,
             word_count,
             source_path: None,
+            confidence: output.confidence,
+            ocr_layout: None,
+            ocr_discarded: false,
+            transcript_segments: None,
+            transcript_language: None,
         })
     }
 }
 
+/// Whether the average OCR confidence falls below `floor`. Output without a
+/// confidence (API backends) is never discarded.
+fn below_confidence_floor(output: &OcrOutput, floor: Option<f32>) -> bool {
+    match (output.confidence, floor) {
+        (Some(confidence), Some(floor)) => confidence.average < floor,
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -77,6 +113,10 @@ mod tests {
             timeout_secs: 60,
             max_image_dimension: 4096,
             min_image_dimension: 50,
+            max_concurrent_jobs: 2,
+            include_layout: false,
+            min_confidence: None,
+            min_document_confidence: None,
         }
     }
 
@@ -169,4 +209,20 @@ mod tests {
             "Word count should be non-negative"
         );
     }
+
+    #[test]
+    fn test_below_confidence_floor_discards_low_confidence_output() {
+        let output = |confidence: Option<f32>| OcrOutput {
+            text: "l|; ~%# .,'".to_string(),
+            confidence: confidence.map(|average| crate::models::ExtractionConfidence {
+                average,
+                minimum: average / 2.0,
+            }),
+        };
+
+        assert!(below_confidence_floor(&output(Some(0.2)), Some(0.5)));
+        assert!(!below_confidence_floor(&output(Some(0.8)), Some(0.5)));
+        assert!(!below_confidence_floor(&output(Some(0.2)), None));
+        assert!(!below_confidence_floor(&output(None), Some(0.5)));
+    }
 }
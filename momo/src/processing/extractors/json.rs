@@ -0,0 +1,192 @@
+use serde_json::Value;
+
+use super::ExtractedContent;
+use crate::error::{MomoError, Result};
+use crate::models::DocumentType;
+
+/// Extractor for JSON and JSONL documents that flattens them into
+/// `key.path: value` lines.
+///
+/// A top-level array or a JSONL file is treated as a list of records, each
+/// flattened on its own and separated by a blank line so records stay
+/// together when chunked.
+pub struct JsonExtractor;
+
+impl JsonExtractor {
+    /// Extract content from JSON or JSONL bytes.
+    pub fn extract(bytes: &[u8]) -> Result<ExtractedContent> {
+        let text = std::str::from_utf8(strip_bom(bytes))
+            .map_err(|e| MomoError::Processing(format!("JSON is not valid UTF-8: {e}")))?
+            .trim();
+
+        if text.is_empty() {
+            return Err(MomoError::Processing("Empty JSON document".to_string()));
+        }
+
+        let (records, title) = match serde_json::from_str::<Value>(text) {
+            Ok(Value::Array(items)) => (items, None),
+            Ok(value) => {
+                let title = title_of(&value);
+                (vec![value], title)
+            }
+            Err(_) => (parse_lines(text)?, None),
+        };
+
+        let text = records
+            .iter()
+            .map(flatten)
+            .filter(|record| !record.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let word_count = text.split_whitespace().count() as i32;
+
+        Ok(ExtractedContent {
+            text,
+            title,
+            author: None,
+            doc_type: DocumentType::Json,
+            url: None,
+            word_count,
+            source_path: None,
+            confidence: None,
+            ocr_layout: None,
+            ocr_discarded: false,
+            transcript_segments: None,
+            transcript_language: None,
+        })
+    }
+
+    /// Whether `text` is a JSON object/array or JSONL of objects, as opposed
+    /// to prose that merely starts with a brace.
+    pub fn looks_like_json(text: &str) -> bool {
+        let trimmed = text.trim();
+        if !(trimmed.starts_with('{') || trimmed.starts_with('[')) {
+            return false;
+        }
+        serde_json::from_str::<Value>(trimmed).is_ok()
+            || parse_lines(trimmed).is_ok_and(|records| records.len() > 1)
+    }
+}
+
+/// Parse JSONL, skipping blank lines.
+fn parse_lines(text: &str) -> Result<Vec<Value>> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|e| {
+                MomoError::Processing(format!("Invalid JSON on line {}: {e}", index + 1))
+            })
+        })
+        .collect()
+}
+
+/// Top-level `title` or `name` string, if any.
+fn title_of(value: &Value) -> Option<String> {
+    ["title", "name"]
+        .iter()
+        .filter_map(|key| value.get(key)?.as_str())
+        .map(str::trim)
+        .find(|title| !title.is_empty())
+        .map(str::to_string)
+}
+
+fn flatten(value: &Value) -> String {
+    let mut lines = Vec::new();
+    flatten_into(value, String::new(), &mut lines);
+    lines.join("\n")
+}
+
+fn flatten_into(value: &Value, path: String, lines: &mut Vec<String>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, child) in map {
+                let child_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                flatten_into(child, child_path, lines);
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            for (index, child) in items.iter().enumerate() {
+                flatten_into(child, format!("{path}[{index}]"), lines);
+            }
+        }
+        leaf => {
+            let rendered = match leaf {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            if path.is_empty() {
+                lines.push(rendered);
+            } else {
+                lines.push(format!("{path}: {rendered}"));
+            }
+        }
+    }
+}
+
+/// Strip UTF-8 BOM if present
+fn strip_bom(bytes: &[u8]) -> &[u8] {
+    bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flattens_nested_object_with_title() {
+        let json = br#"{"title": "Q3 export", "owner": {"name": "Alice"}, "tags": ["a", "b"], "count": 2, "archived": null}"#;
+        let extracted = JsonExtractor::extract(json).unwrap();
+
+        assert_eq!(extracted.title.as_deref(), Some("Q3 export"));
+        assert_eq!(extracted.doc_type, DocumentType::Json);
+        assert!(extracted.text.contains("title: Q3 export"));
+        assert!(extracted.text.contains("owner.name: Alice"));
+        assert!(extracted.text.contains("tags[0]: a"));
+        assert!(extracted.text.contains("tags[1]: b"));
+        assert!(extracted.text.contains("count: 2"));
+        assert!(extracted.text.contains("archived: null"));
+    }
+
+    #[test]
+    fn test_falls_back_to_name_for_title() {
+        let extracted = JsonExtractor::extract(br#"{"name": "Project X"}"#).unwrap();
+        assert_eq!(extracted.title.as_deref(), Some("Project X"));
+    }
+
+    #[test]
+    fn test_jsonl_lines_become_separate_records() {
+        let jsonl = b"{\"role\": \"user\", \"text\": \"hi\"}\n\n{\"role\": \"assistant\", \"text\": \"hello\"}\n";
+        let extracted = JsonExtractor::extract(jsonl).unwrap();
+
+        assert!(extracted.title.is_none());
+        assert_eq!(
+            extracted.text,
+            "role: user\ntext: hi\n\nrole: assistant\ntext: hello"
+        );
+    }
+
+    #[test]
+    fn test_top_level_array_is_treated_as_records() {
+        let extracted = JsonExtractor::extract(br#"[{"id": 1}, {"id": 2}]"#).unwrap();
+        assert_eq!(extracted.text, "id: 1\n\nid: 2");
+    }
+
+    #[test]
+    fn test_invalid_json_is_an_error() {
+        assert!(JsonExtractor::extract(b"{\"a\": 1}\nnot json").is_err());
+        assert!(JsonExtractor::extract(b"   ").is_err());
+    }
+
+    #[test]
+    fn test_looks_like_json() {
+        assert!(JsonExtractor::looks_like_json(r#"{"a": 1}"#));
+        assert!(JsonExtractor::looks_like_json("{\"a\": 1}\n{\"a\": 2}"));
+        assert!(!JsonExtractor::looks_like_json("{braces} in prose"));
+        assert!(!JsonExtractor::looks_like_json("plain text"));
+    }
+}
@@ -1,19 +1,38 @@
-use crate::models::DocumentType;
+use crate::models::{DocumentType, ExtractionConfidence};
+use crate::ocr::OcrBlock;
+use crate::transcription::{TranscriptLanguage, TranscriptSegment};
 
 #[derive(Debug)]
 pub struct ExtractedContent {
     pub text: String,
     pub title: Option<String>,
+    /// Author recorded in the file's own metadata (e.g. EPUB `dc:creator`).
+    pub author: Option<String>,
     pub doc_type: DocumentType,
     pub url: Option<String>,
     pub word_count: i32,
     pub source_path: Option<String>,
+    /// OCR or transcription confidence for media-derived text.
+    pub confidence: Option<ExtractionConfidence>,
+    /// OCR text blocks with bounding boxes, when layout output is enabled.
+    pub ocr_layout: Option<Vec<OcrBlock>>,
+    /// Set when the OCR text fell below `OCR_MIN_DOCUMENT_CONFIDENCE` and
+    /// was discarded.
+    pub ocr_discarded: bool,
+    /// Timed transcript segments, when the transcription backend reports them.
+    pub transcript_segments: Option<Vec<TranscriptSegment>>,
+    /// Spoken language detected while transcribing.
+    pub transcript_language: Option<TranscriptLanguage>,
 }
 
 pub mod audio;
 pub mod csv;
 pub mod docx;
+pub mod epub;
+pub mod html;
 pub mod image;
+pub mod json;
+pub mod pdf;
 pub mod pptx;
 pub mod video;
 pub mod xlsx;
@@ -21,7 +40,11 @@ pub mod xlsx;
 pub use audio::AudioExtractor;
 pub use csv::CsvExtractor;
 pub use docx::DocxExtractor;
+pub use epub::EpubExtractor;
+pub use html::HtmlExtractor;
 pub use image::ImageExtractor;
+pub use json::JsonExtractor;
+pub use pdf::PdfExtractor;
 pub use pptx::PptxExtractor;
 pub use video::VideoExtractor;
 pub use xlsx::XlsxExtractor;
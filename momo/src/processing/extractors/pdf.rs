@@ -0,0 +1,287 @@
+//! PDF extractor: the embedded text layer page by page, with OCR of the page
+//! scan for pages that have little or no text.
+
+use std::io::{Cursor, Read};
+
+use flate2::read::ZlibDecoder;
+use lopdf::xobject::PdfImage;
+use lopdf::{Document, ObjectId};
+
+use super::{ExtractedContent, ImageExtractor};
+use crate::config::OcrConfig;
+use crate::error::{MomoError, Result};
+use crate::models::{DocumentType, ExtractionConfidence};
+use crate::ocr::OcrProvider;
+
+/// Pages whose text layer has fewer words than this are treated as scanned.
+const MIN_TEXT_LAYER_WORDS: usize = 5;
+/// A first line with more words than this is body text, not a title.
+const MAX_TITLE_WORDS: usize = 15;
+
+pub struct PdfExtractor;
+
+impl PdfExtractor {
+    /// Extract the text layer as one `## Page N` section per page. The title
+    /// comes from the PDF's Info dictionary, else the first line of text.
+    pub fn extract(bytes: &[u8]) -> Result<ExtractedContent> {
+        let pages = Self::text_pages(bytes)?;
+        let (title, author) = Document::load_mem(bytes)
+            .map(|document| info_metadata(&document))
+            .unwrap_or_default();
+
+        Ok(Self::assemble(&pages, title, author, None))
+    }
+
+    /// Like [`extract`](Self::extract), but pages with little or no text
+    /// layer are OCRed from the scan image embedded in them. Pages whose
+    /// image can't be decoded (CCITT, JBIG2, JPEG 2000) keep their text layer.
+    pub async fn extract_with_ocr(
+        bytes: &[u8],
+        ocr_provider: &OcrProvider,
+        config: &OcrConfig,
+    ) -> Result<ExtractedContent> {
+        let mut pages = Self::text_pages(bytes)?;
+        let (title, author, scans) = match Document::load_mem(bytes) {
+            Ok(document) => {
+                let (title, author) = info_metadata(&document);
+                (title, author, scanned_pages(&document, &pages))
+            }
+            Err(_) => (None, None, Vec::new()),
+        };
+
+        let mut confidences = Vec::new();
+        for (index, image) in scans {
+            match ImageExtractor::extract(&image, ocr_provider, config).await {
+                Ok(ocr) => {
+                    let text_layer_words = pages[index].split_whitespace().count();
+                    if ocr.word_count as usize > text_layer_words {
+                        pages[index] = ocr.text;
+                    }
+                    confidences.extend(ocr.confidence);
+                }
+                Err(e) => {
+                    tracing::warn!(page = index + 1, error = %e, "OCR failed for scanned PDF page");
+                }
+            }
+        }
+
+        let confidence = (!confidences.is_empty()).then(|| ExtractionConfidence {
+            average: confidences.iter().map(|c| c.average).sum::<f32>() / confidences.len() as f32,
+            minimum: confidences
+                .iter()
+                .map(|c| c.minimum)
+                .fold(f32::INFINITY, f32::min),
+        });
+
+        Ok(Self::assemble(&pages, title, author, confidence))
+    }
+
+    fn text_pages(bytes: &[u8]) -> Result<Vec<String>> {
+        pdf_extract::extract_text_from_mem_by_pages(bytes)
+            .map_err(|e| MomoError::Processing(format!("PDF extraction failed: {e}")))
+    }
+
+    fn assemble(
+        pages: &[String],
+        title: Option<String>,
+        author: Option<String>,
+        confidence: Option<ExtractionConfidence>,
+    ) -> ExtractedContent {
+        let mut text = String::new();
+        for (index, page) in pages.iter().enumerate() {
+            let page = page.trim();
+            if page.is_empty() {
+                continue;
+            }
+            if !text.is_empty() {
+                text.push_str("\n\n");
+            }
+            // Numbered after the PDF's pages, so skipped blank pages leave gaps.
+            text.push_str(&format!("## Page {}\n\n{page}", index + 1));
+        }
+
+        let title = title.or_else(|| first_heading(pages));
+        let word_count = pages
+            .iter()
+            .map(|page| page.split_whitespace().count())
+            .sum::<usize>() as i32;
+
+        ExtractedContent {
+            text,
+            title,
+            author,
+            doc_type: DocumentType::Pdf,
+            url: None,
+            word_count,
+            source_path: None,
+            confidence,
+            ocr_layout: None,
+            ocr_discarded: false,
+            transcript_segments: None,
+            transcript_language: None,
+        }
+    }
+}
+
+/// Title and author from the document Info dictionary.
+fn info_metadata(document: &Document) -> (Option<String>, Option<String>) {
+    let info = document
+        .trailer
+        .get(b"Info")
+        .and_then(|info| document.dereference(info))
+        .and_then(|(_, info)| info.as_dict())
+        .ok();
+    let field = |key: &[u8]| {
+        info?
+            .get(key)
+            .and_then(lopdf::decode_text_string)
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    (field(b"Title"), field(b"Author"))
+}
+
+/// First line of the first page with text, when it's short enough to be a
+/// heading.
+fn first_heading(pages: &[String]) -> Option<String> {
+    let line = pages
+        .iter()
+        .flat_map(|page| page.lines())
+        .map(str::trim)
+        .find(|line| !line.is_empty())?;
+    (line.split_whitespace().count() <= MAX_TITLE_WORDS).then(|| line.to_string())
+}
+
+/// Page index and scan image of every page whose text layer is too sparse.
+fn scanned_pages(document: &Document, pages: &[String]) -> Vec<(usize, Vec<u8>)> {
+    document
+        .get_pages()
+        .into_values()
+        .enumerate()
+        .filter(|(index, _)| {
+            pages
+                .get(*index)
+                .is_some_and(|text| text.split_whitespace().count() < MIN_TEXT_LAYER_WORDS)
+        })
+        .filter_map(|(index, page_id)| page_image(document, page_id).map(|image| (index, image)))
+        .collect()
+}
+
+/// The largest image drawn on a page, encoded so the OCR provider can read it.
+fn page_image(document: &Document, page_id: ObjectId) -> Option<Vec<u8>> {
+    let images = document.get_page_images(page_id).ok()?;
+    let image = images
+        .iter()
+        .max_by_key(|image| image.width * image.height)?;
+    encode_image(image)
+}
+
+fn encode_image(image: &PdfImage) -> Option<Vec<u8>> {
+    let filters = image.filters.as_deref().unwrap_or_default();
+    let raw = match filters {
+        [filter] if filter == "DCTDecode" => return Some(image.content.to_vec()),
+        [] => image.content.to_vec(),
+        // Predictors only appear with DecodeParms; those streams are skipped.
+        [filter] if filter == "FlateDecode" && image.origin_dict.get(b"DecodeParms").is_err() => {
+            let mut raw = Vec::new();
+            ZlibDecoder::new(image.content).read_to_end(&mut raw).ok()?;
+            raw
+        }
+        _ => return None,
+    };
+
+    if image.bits_per_component != Some(8) {
+        return None;
+    }
+    let width = u32::try_from(image.width).ok()?;
+    let height = u32::try_from(image.height).ok()?;
+    let decoded = match image.color_space.as_deref() {
+        Some("DeviceGray") => {
+            image::DynamicImage::ImageLuma8(image::GrayImage::from_raw(width, height, raw)?)
+        }
+        Some("DeviceRGB") => {
+            image::DynamicImage::ImageRgb8(image::RgbImage::from_raw(width, height, raw)?)
+        }
+        _ => return None,
+    };
+
+    let mut png = Vec::new();
+    decoded
+        .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+        .ok()?;
+    Some(png)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::{dictionary, Object, Stream};
+
+    /// A one-page document whose only content is an 8x4 grayscale image.
+    fn scanned_document(filter: Option<&str>) -> (Document, ObjectId) {
+        let mut doc = Document::with_version("1.5");
+        let mut image_dict = dictionary! {
+            "Type" => "XObject",
+            "Subtype" => "Image",
+            "Width" => 8,
+            "Height" => 4,
+            "ColorSpace" => "DeviceGray",
+            "BitsPerComponent" => 8,
+        };
+        if let Some(filter) = filter {
+            image_dict.set("Filter", Object::Name(filter.as_bytes().to_vec()));
+        }
+        let image_id = doc.add_object(Stream::new(image_dict, vec![200; 32]));
+        let page_id = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Resources" => dictionary! {
+                "XObject" => dictionary! { "Im1" => image_id },
+            },
+        });
+        (doc, page_id)
+    }
+
+    #[test]
+    fn test_page_image_encodes_raw_scan_as_png() {
+        let (doc, page_id) = scanned_document(None);
+
+        let png = page_image(&doc, page_id).expect("raw grayscale image");
+        let decoded = image::load_from_memory(&png).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (8, 4));
+    }
+
+    #[test]
+    fn test_page_image_skips_undecodable_filters() {
+        let (doc, page_id) = scanned_document(Some("CCITTFaxDecode"));
+        assert!(page_image(&doc, page_id).is_none());
+    }
+
+    #[test]
+    fn test_first_heading_skips_blank_lines_and_long_lines() {
+        let pages = vec![
+            "\n\n".to_string(),
+            "\n  Quarterly Report  \nRevenue grew.".to_string(),
+        ];
+        assert_eq!(first_heading(&pages).as_deref(), Some("Quarterly Report"));
+
+        let body = vec!["This opening line runs on for far too many words to be a plausible heading of any document".to_string()];
+        assert!(first_heading(&body).is_none());
+    }
+
+    #[test]
+    fn test_assemble_numbers_pages_and_skips_blank_ones() {
+        let pages = vec![
+            "First page".to_string(),
+            "  \n".to_string(),
+            "Third page".to_string(),
+        ];
+        let extracted = PdfExtractor::assemble(&pages, None, None, None);
+        assert_eq!(
+            extracted.text,
+            "## Page 1\n\nFirst page\n\n## Page 3\n\nThird page"
+        );
+        assert_eq!(extracted.title.as_deref(), Some("First page"));
+        assert_eq!(extracted.word_count, 4);
+    }
+}
@@ -24,10 +24,16 @@ impl PptxExtractor {
             return Ok(ExtractedContent {
                 text: String::new(),
                 title: None,
+                author: None,
                 doc_type: DocumentType::Pptx,
                 url: None,
                 word_count: 0,
                 source_path: None,
+                confidence: None,
+                ocr_layout: None,
+                ocr_discarded: false,
+                transcript_segments: None,
+                transcript_language: None,
             });
         }
 
@@ -71,10 +77,16 @@ impl PptxExtractor {
         Ok(ExtractedContent {
             text,
             title,
+            author: None,
             doc_type: DocumentType::Pptx,
             url: None,
             word_count,
             source_path: None,
+            confidence: None,
+            ocr_layout: None,
+            ocr_discarded: false,
+            transcript_segments: None,
+            transcript_language: None,
         })
     }
 
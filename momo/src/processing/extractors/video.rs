@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use crate::config::TranscriptionConfig;
 use crate::error::{MomoError, Result};
 use crate::models::DocumentType;
@@ -84,8 +86,8 @@ impl VideoExtractor {
             wav_bytes.len()
         );
 
-        let text = provider.transcribe(&wav_bytes).await?;
-        let word_count = text.split_whitespace().count() as i32;
+        let output = provider.transcribe(&wav_bytes).await?;
+        let word_count = output.text.split_whitespace().count() as i32;
 
         tracing::info!(
             "Video transcription complete: {} words from {} samples",
@@ -94,15 +96,45 @@ impl VideoExtractor {
         );
 
         Ok(ExtractedContent {
-            text,
+            text: output.text,
             title: None,
+            author: None,
             doc_type: DocumentType::Video,
             url: None,
             word_count,
             source_path: None,
+            confidence: output.confidence,
+            ocr_layout: None,
+            ocr_discarded: false,
+            transcript_segments: (!output.segments.is_empty()).then_some(output.segments),
+            transcript_language: output.language,
         })
     }
 
+    /// Like [`extract`](Self::extract), for video streamed to disk by a media
+    /// upload. The size limit is checked before the file is read.
+    pub async fn extract_file(
+        path: &Path,
+        provider: &TranscriptionProvider,
+        config: &TranscriptionConfig,
+    ) -> Result<ExtractedContent> {
+        let size = tokio::fs::metadata(path)
+            .await
+            .map_err(|e| MomoError::Transcription(format!("Uploaded video file unavailable: {e}")))?
+            .len();
+        if size > config.max_file_size {
+            return Err(MomoError::Transcription(format!(
+                "Video file size {} exceeds limit {}",
+                size, config.max_file_size
+            )));
+        }
+
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(|e| MomoError::Transcription(format!("Failed to read uploaded video: {e}")))?;
+        Self::extract(&bytes, provider, config).await
+    }
+
     /// Encode f32 PCM samples (16kHz mono) into a WAV byte buffer.
     fn encode_pcm_to_wav(samples: &[f32]) -> Result<Vec<u8>> {
         const SAMPLE_RATE: u32 = 16000;
@@ -91,10 +91,16 @@ impl XlsxExtractor {
         Ok(ExtractedContent {
             text,
             title: None,
+            author: None,
             doc_type: DocumentType::Xlsx,
             url: None,
             word_count,
             source_path: None,
+            confidence: None,
+            ocr_layout: None,
+            ocr_discarded: false,
+            transcript_segments: None,
+            transcript_language: None,
         })
     }
 
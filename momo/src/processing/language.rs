@@ -13,6 +13,23 @@ pub enum SupportedLanguage {
     Cpp,
 }
 
+impl std::fmt::Display for SupportedLanguage {
+    /// Name recorded on code chunks; TSX counts as TypeScript.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Rust => "rust",
+            Self::Python => "python",
+            Self::JavaScript => "javascript",
+            Self::TypeScript | Self::TypeScriptTsx => "typescript",
+            Self::Go => "go",
+            Self::Java => "java",
+            Self::C => "c",
+            Self::Cpp => "cpp",
+        };
+        f.write_str(name)
+    }
+}
+
 /// Detect programming language from file path extension.
 /// Returns None for unsupported or unknown extensions.
 pub fn detect_language(source_path: &str) -> Option<SupportedLanguage> {
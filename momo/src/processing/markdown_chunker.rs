@@ -1,12 +1,17 @@
+use std::sync::Arc;
+
 use crate::config::ProcessingConfig;
-use text_splitter::{ChunkConfig, MarkdownSplitter};
+use crate::models::ChunkerType;
+use text_splitter::{ChunkConfig, ChunkSizer, MarkdownSplitter};
 
-use super::{ChunkContext, ContentChunker, TextChunk, TextChunker};
+use super::chunker::{count_chunk_tokens, TokenCounterSizer};
+use super::{ChunkContext, ContentChunker, TextChunk, TextChunker, TokenCounter};
 
 pub struct MarkdownChunker {
     chunk_size: usize,
     chunk_overlap: usize,
     fallback_chunker: TextChunker,
+    token_counter: Option<Arc<dyn TokenCounter>>,
 }
 
 impl MarkdownChunker {
@@ -15,11 +20,33 @@ impl MarkdownChunker {
             chunk_size: config.chunk_size,
             chunk_overlap: config.chunk_overlap,
             fallback_chunker: TextChunker::new(config),
+            token_counter: None,
         }
     }
+
+    /// Measure chunk sizes with the given tokenizer instead of character count.
+    pub fn with_token_counter(mut self, counter: Arc<dyn TokenCounter>) -> Self {
+        self.fallback_chunker = self.fallback_chunker.with_token_counter(counter.clone());
+        self.token_counter = Some(counter);
+        self
+    }
+
+    fn split<S: ChunkSizer>(&self, text: &str, chunk_config: ChunkConfig<S>) -> Vec<TextChunk> {
+        MarkdownSplitter::new(chunk_config)
+            .chunks(text)
+            .map(|chunk_text| TextChunk {
+                content: chunk_text.to_string(),
+                token_count: count_chunk_tokens(self.token_counter.as_deref(), chunk_text),
+            })
+            .collect()
+    }
 }
 
 impl ContentChunker for MarkdownChunker {
+    fn chunker_type(&self) -> ChunkerType {
+        ChunkerType::Markdown
+    }
+
     fn chunk(&self, text: &str, context: Option<&ChunkContext>) -> Vec<TextChunk> {
         if text.is_empty() {
             return Vec::new();
@@ -31,20 +58,16 @@ impl ContentChunker for MarkdownChunker {
             Err(_) => return self.fallback_chunker.chunk(text, context),
         };
 
-        let splitter = MarkdownSplitter::new(chunk_config);
-        let chunks: Vec<&str> = splitter.chunks(text).collect();
+        let chunks = match self.token_counter.as_deref() {
+            Some(counter) => self.split(text, chunk_config.with_sizer(TokenCounterSizer(counter))),
+            None => self.split(text, chunk_config),
+        };
 
         if chunks.is_empty() {
             return self.fallback_chunker.chunk(text, context);
         }
 
         chunks
-            .iter()
-            .map(|chunk_text| TextChunk {
-                content: chunk_text.to_string(),
-                token_count: (chunk_text.len() as f32 / 4.0).ceil() as i32,
-            })
-            .collect()
     }
 }
 
@@ -54,6 +77,7 @@ impl Default for MarkdownChunker {
             chunk_size: 512,
             chunk_overlap: 50,
             fallback_chunker: TextChunker::default(),
+            token_counter: None,
         }
     }
 }
@@ -61,6 +85,9 @@ impl Default for MarkdownChunker {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::{
+        ChunkStrategy, NearDuplicateAction, PiiAction, PiiCategory, StructuredDataOverflow,
+    };
 
     #[test]
     fn test_markdown_chunker_basic() {
@@ -110,6 +137,28 @@ mod tests {
         let config = ProcessingConfig {
             chunk_size: 256,
             chunk_overlap: 25,
+            chunk_strategy: ChunkStrategy::Structural,
+            semantic_chunk_threshold: 0.3,
+            semantic_chunk_min_size: 128,
+            use_model_tokenizer: false,
+            auto_summary: false,
+            auto_summary_min_chars: 2000,
+            contextual_chunks: false,
+            near_duplicate_threshold: None,
+            near_duplicate_action: NearDuplicateAction::Warn,
+            record_stage_timings: false,
+            low_confidence_threshold: None,
+            chunk_reembed_interval_secs: 0,
+            chunk_reembed_batch_size: 64,
+            categorize_documents: false,
+            structured_data_max_chunks: 0,
+            structured_data_overflow: StructuredDataOverflow::Sample,
+            pii_detection: false,
+            pii_categories: PiiCategory::ALL.to_vec(),
+            pii_action: PiiAction::Redact,
+            retry_max_attempts: 3,
+            retry_base_delay_ms: 500,
+            dead_letter_after_failures: 5,
         };
         let chunker = MarkdownChunker::new(&config);
 
@@ -174,4 +223,52 @@ More text after the code block.
         let chunks = chunker.chunk(md, None);
         assert!(!chunks.is_empty());
     }
+
+    #[test]
+    fn test_markdown_chunker_uses_token_counter() {
+        let config = ProcessingConfig {
+            chunk_size: 4,
+            chunk_overlap: 0,
+            chunk_strategy: ChunkStrategy::Structural,
+            semantic_chunk_threshold: 0.3,
+            semantic_chunk_min_size: 128,
+            use_model_tokenizer: true,
+            auto_summary: false,
+            auto_summary_min_chars: 2000,
+            contextual_chunks: false,
+            near_duplicate_threshold: None,
+            near_duplicate_action: NearDuplicateAction::Warn,
+            record_stage_timings: false,
+            low_confidence_threshold: None,
+            chunk_reembed_interval_secs: 0,
+            chunk_reembed_batch_size: 64,
+            categorize_documents: false,
+            structured_data_max_chunks: 0,
+            structured_data_overflow: StructuredDataOverflow::Sample,
+            pii_detection: false,
+            pii_categories: PiiCategory::ALL.to_vec(),
+            pii_action: PiiAction::Redact,
+            retry_max_attempts: 3,
+            retry_base_delay_ms: 500,
+            dead_letter_after_failures: 5,
+        };
+        let counter: Arc<dyn TokenCounter> = Arc::new(|text: &str| text.split_whitespace().count());
+        let chunker = MarkdownChunker::new(&config).with_token_counter(counter);
+
+        let md = "# Title\n\nalpha beta gamma delta epsilon zeta eta theta";
+        let chunks = chunker.chunk(md, None);
+
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(
+                chunk.token_count <= 4,
+                "chunk too large: {:?}",
+                chunk.content
+            );
+            assert_eq!(
+                chunk.token_count as usize,
+                chunk.content.split_whitespace().count()
+            );
+        }
+    }
 }
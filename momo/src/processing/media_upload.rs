@@ -0,0 +1,97 @@
+//! Audio and video uploaded through `documents:uploadMedia` are streamed to a
+//! file in the upload directory instead of being stored base64-encoded. The
+//! document's `content` holds an `upload:<file name>` reference, which the
+//! pipeline resolves and transcribes from disk. The file is deleted once the
+//! transcript has replaced the reference, or when the document is
+//! dead-lettered or deleted, so failed runs can still be retried.
+
+use std::path::{Path, PathBuf};
+
+use crate::config::TranscriptionConfig;
+
+/// Prefix marking a document's content as a reference to an uploaded file.
+pub const MEDIA_UPLOAD_PREFIX: &str = "upload:";
+
+/// Directory uploaded media is streamed into before it's transcribed.
+pub fn upload_dir(config: &TranscriptionConfig) -> PathBuf {
+    config
+        .upload_dir
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| std::env::temp_dir().join("momo-uploads"))
+}
+
+/// The `content` stored for a document whose media lives in `file_name`.
+pub fn reference(file_name: &str) -> String {
+    format!("{MEDIA_UPLOAD_PREFIX}{file_name}")
+}
+
+/// Path of the uploaded file `content` refers to, or `None` when `content`
+/// isn't a reference. Only bare file names inside `dir` resolve, so a
+/// document created with a crafted reference can't reach other files.
+pub fn resolve(content: &str, dir: &Path) -> Option<PathBuf> {
+    let name = content.strip_prefix(MEDIA_UPLOAD_PREFIX)?;
+    let is_bare_name =
+        !name.is_empty() && name != "." && name != ".." && !name.contains(['/', '\\']);
+    is_bare_name.then(|| dir.join(name))
+}
+
+/// Delete the file uploaded for `doc_id`, if it's still there. Upload files
+/// are named after their document's id.
+pub async fn remove_for_document(config: &TranscriptionConfig, doc_id: &str) {
+    if let Some(path) = resolve(&reference(doc_id), &upload_dir(config)) {
+        remove(&path).await;
+    }
+}
+
+/// Delete an uploaded file once it's no longer needed.
+pub async fn remove(path: &Path) {
+    if let Err(e) = tokio::fs::remove_file(path).await {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            tracing::warn!(path = %path.display(), error = %e, "Failed to remove uploaded media file");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_accepts_references_to_bare_file_names() {
+        let dir = Path::new("/var/uploads");
+        assert_eq!(
+            resolve(&reference("abc123"), dir),
+            Some(PathBuf::from("/var/uploads/abc123"))
+        );
+    }
+
+    #[test]
+    fn test_resolve_ignores_payloads_and_rejects_paths() {
+        let dir = Path::new("/var/uploads");
+        assert_eq!(resolve("SUQzBAAAAAAAI1RTU0U=", dir), None);
+        for content in [
+            "upload:",
+            "upload:..",
+            "upload:../etc/passwd",
+            "upload:a\\b",
+        ] {
+            assert_eq!(resolve(content, dir), None, "{content}");
+        }
+    }
+
+    #[test]
+    fn test_upload_dir_defaults_under_temp_dir() {
+        let config = TranscriptionConfig::default();
+        assert_eq!(
+            upload_dir(&config),
+            std::env::temp_dir().join("momo-uploads")
+        );
+
+        let config = TranscriptionConfig {
+            upload_dir: Some("/srv/momo/uploads".to_string()),
+            ..TranscriptionConfig::default()
+        };
+        assert_eq!(upload_dir(&config), PathBuf::from("/srv/momo/uploads"));
+    }
+}
@@ -4,18 +4,29 @@ mod code_chunker;
 mod extractor;
 mod language;
 mod markdown_chunker;
+pub mod media_upload;
+mod pii;
 mod pipeline;
+mod retry;
+mod semantic_chunker;
+mod status_events;
 mod structured_data_chunker;
+mod transcript_chunker;
 mod webpage_chunker;
 
 pub mod extractors;
 
-pub use chunker::{ChunkContext, ContentChunker, TextChunk, TextChunker};
+pub use chunker::{ChunkContext, ContentChunker, TextChunk, TextChunker, TokenCounter};
 pub use chunker_registry::ChunkerRegistry;
 pub use code_chunker::CodeChunker;
 pub use extractor::ContentExtractor;
 pub use language::detect_language;
 pub use markdown_chunker::MarkdownChunker;
+pub use pii::PiiRedactor;
 pub use pipeline::ProcessingPipeline;
+pub use retry::RetryPolicy;
+pub use semantic_chunker::{SemanticChunker, SentenceEmbedder};
+pub use status_events::{DocumentStatusEvent, StatusEvents};
 pub use structured_data_chunker::StructuredDataChunker;
+pub use transcript_chunker::TranscriptChunker;
 pub use webpage_chunker::WebpageChunker;
@@ -0,0 +1,195 @@
+use std::collections::BTreeMap;
+
+use regex::Regex;
+
+use crate::models::{PiiAction, PiiCategory, PiiReport};
+
+/// Detects PII in extracted text with regexes, confirming card numbers and
+/// SSNs with validators to keep false positives down, and optionally replaces
+/// it with placeholders.
+#[derive(Clone)]
+pub struct PiiRedactor {
+    action: PiiAction,
+    /// Detectors in priority order: when matches overlap, the earlier wins.
+    detectors: Vec<(PiiCategory, Regex)>,
+}
+
+impl PiiRedactor {
+    pub fn new(categories: &[PiiCategory], action: PiiAction) -> Self {
+        // Cards are matched before phones so long digit runs aren't split into
+        // phone numbers.
+        let priority = [
+            PiiCategory::Email,
+            PiiCategory::CreditCard,
+            PiiCategory::Ssn,
+            PiiCategory::Phone,
+        ];
+        let detectors = priority
+            .into_iter()
+            .filter(|category| categories.contains(category))
+            .map(|category| (category, pattern(category)))
+            .collect();
+        Self { action, detectors }
+    }
+
+    /// Scan `text`, redacting matches in place when the action is
+    /// [`PiiAction::Redact`]. Returns `None` when no PII was found.
+    pub fn apply(&self, text: &mut String) -> Option<PiiReport> {
+        let mut matches: Vec<(usize, usize, PiiCategory)> = Vec::new();
+        for (category, regex) in &self.detectors {
+            for m in regex.find_iter(text) {
+                let overlaps = matches
+                    .iter()
+                    .any(|(start, end, _)| m.start() < *end && *start < m.end());
+                if !overlaps && is_valid(*category, text, m.start(), m.as_str()) {
+                    matches.push((m.start(), m.end(), *category));
+                }
+            }
+        }
+        if matches.is_empty() {
+            return None;
+        }
+
+        let mut counts = BTreeMap::new();
+        for (_, _, category) in &matches {
+            *counts.entry(*category).or_insert(0) += 1;
+        }
+
+        if self.action == PiiAction::Redact {
+            matches.sort_by_key(|(start, _, _)| *start);
+            let mut redacted = String::with_capacity(text.len());
+            let mut last = 0;
+            for (start, end, category) in &matches {
+                redacted.push_str(&text[last..*start]);
+                redacted.push_str(&format!(
+                    "[REDACTED_{}]",
+                    category.to_string().to_uppercase()
+                ));
+                last = *end;
+            }
+            redacted.push_str(&text[last..]);
+            *text = redacted;
+        }
+
+        Some(PiiReport {
+            action: self.action,
+            counts,
+        })
+    }
+}
+
+fn pattern(category: PiiCategory) -> Regex {
+    let pattern = match category {
+        PiiCategory::Email => r"(?i)\b[a-z0-9._%+-]+@[a-z0-9-]+(?:\.[a-z0-9-]+)*\.[a-z]{2,}\b",
+        PiiCategory::CreditCard => r"\b\d(?:[ -]?\d){12,18}\b",
+        PiiCategory::Ssn => r"\b\d{3}-\d{2}-\d{4}\b",
+        PiiCategory::Phone => r"(?:\+\d{1,3}[ .-]?)?(?:\(\d{3}\)|\d{3})[ .-]?\d{3}[ .-]\d{4}\b",
+    };
+    Regex::new(pattern).expect("PII patterns are valid")
+}
+
+fn is_valid(category: PiiCategory, text: &str, start: usize, matched: &str) -> bool {
+    let digits: Vec<u32> = matched.chars().filter_map(|c| c.to_digit(10)).collect();
+    match category {
+        PiiCategory::Email => true,
+        PiiCategory::CreditCard => (13..=19).contains(&digits.len()) && luhn_valid(&digits),
+        PiiCategory::Ssn => {
+            let area = digits[0] * 100 + digits[1] * 10 + digits[2];
+            let group = digits[3] * 10 + digits[4];
+            let serial = digits[5..].iter().fold(0, |acc, d| acc * 10 + d);
+            area != 0 && area != 666 && area < 900 && group != 0 && serial != 0
+        }
+        // The pattern can't anchor before `+` or `(`, so reject matches that
+        // are the tail of a longer number or word.
+        PiiCategory::Phone => !text[..start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric()),
+    }
+}
+
+fn luhn_valid(digits: &[u32]) -> bool {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum % 10 == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redact(text: &str) -> (String, Option<PiiReport>) {
+        let mut text = text.to_string();
+        let report = PiiRedactor::new(&PiiCategory::ALL, PiiAction::Redact).apply(&mut text);
+        (text, report)
+    }
+
+    #[test]
+    fn test_redacts_each_category() {
+        let (text, report) = redact(
+            "Mail jane.doe@example.co.uk or call (555) 123-4567. \
+             Card 4111 1111 1111 1111, SSN 123-45-6789.",
+        );
+
+        assert_eq!(
+            text,
+            "Mail [REDACTED_EMAIL] or call [REDACTED_PHONE]. \
+             Card [REDACTED_CREDIT_CARD], SSN [REDACTED_SSN]."
+        );
+        let report = report.unwrap();
+        assert_eq!(report.action, PiiAction::Redact);
+        assert_eq!(
+            report.counts,
+            BTreeMap::from([
+                (PiiCategory::Email, 1),
+                (PiiCategory::Phone, 1),
+                (PiiCategory::CreditCard, 1),
+                (PiiCategory::Ssn, 1),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_validators_reject_lookalikes() {
+        // Fails the Luhn check, invalid SSN area, and a number embedded in a word.
+        let input = "Order 4111 1111 1111 1112, ref 000-12-3456, id X555-123-4567.";
+        let (text, report) = redact(input);
+
+        assert_eq!(text, input);
+        assert!(report.is_none());
+    }
+
+    #[test]
+    fn test_flag_keeps_text_and_respects_categories() {
+        let input = "Contact a@b.io or +1 555 123 4567";
+        let mut text = input.to_string();
+        let report = PiiRedactor::new(&[PiiCategory::Email], PiiAction::Flag)
+            .apply(&mut text)
+            .unwrap();
+
+        assert_eq!(text, input);
+        assert_eq!(report.action, PiiAction::Flag);
+        assert_eq!(report.counts, BTreeMap::from([(PiiCategory::Email, 1)]));
+    }
+
+    #[test]
+    fn test_international_phone() {
+        let (text, _) = redact("Call +44 207-946-0958 today");
+        assert_eq!(text, "Call [REDACTED_PHONE] today");
+    }
+}
@@ -1,20 +1,235 @@
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 
 use chrono::Utc;
+use futures::{StreamExt, TryStreamExt};
 use nanoid::nanoid;
+use serde::Serialize;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
 
-use crate::config::Config;
+use crate::config::{Config, ProcessingConfig};
 use crate::db::DatabaseBackend;
 use crate::embeddings::EmbeddingProvider;
-use crate::error::Result;
-use crate::intelligence::{LlmFilter, MemoryExtractor};
+use crate::error::{MomoError, Result};
+use crate::intelligence::{language, LlmFilter, MemoryExtractor};
+use crate::llm::prompts::{chunk_context_prompt, document_category_prompt, summarize_prompt};
 use crate::llm::LlmProvider;
-use crate::models::{Chunk, DocumentType, Memory, MemoryType, ProcessingStatus};
+use crate::models::{
+    Chunk, ChunkSizeOverride, ChunkStrategy, ChunkerType, DocumentCategory, DocumentType,
+    ExtractionConfidence, Memory, MemoryType, Metadata, NearDuplicate, NearDuplicateAction,
+    ProcessingStatus, AUTHOR_METADATA_KEY, CATEGORY_METADATA_KEY,
+    EXTRACTION_CONFIDENCE_METADATA_KEY, LOW_CONFIDENCE_METADATA_KEY, OCR_DISCARDED_METADATA_KEY,
+    OCR_LAYOUT_METADATA_KEY, PII_METADATA_KEY, STRUCTURED_DATA_OVERFLOW_METADATA_KEY,
+    TRANSCRIPT_LANGUAGE_METADATA_KEY, TRANSCRIPT_SEGMENTS_METADATA_KEY,
+};
 use crate::ocr::OcrProvider;
-use crate::transcription::TranscriptionProvider;
+use crate::services::WebhookNotifier;
+use crate::transcription::{TranscriptLanguage, TranscriptionProvider};
+
+use super::extractors::{
+    AudioExtractor, ExtractedContent, ImageExtractor, PdfExtractor, VideoExtractor,
+};
+use super::{
+    detect_language, media_upload, ChunkContext, ChunkerRegistry, ContentExtractor, PiiRedactor,
+    RetryPolicy, SemanticChunker, SentenceEmbedder, StatusEvents,
+};
+
+/// Target length of auto-generated document summaries, in words.
+const AUTO_SUMMARY_MAX_WORDS: usize = 100;
+/// Only the leading part of very long documents is sent to the LLM for summarization.
+const AUTO_SUMMARY_MAX_INPUT_CHARS: usize = 12_000;
+/// Leading text embedded for the near-duplicate check, roughly one chunk's worth
+/// so it compares like-for-like against the opening chunk of existing documents.
+const NEAR_DUPLICATE_MAX_INPUT_CHARS: usize = 2_000;
+/// Leading text used as the document overview for contextual chunks when the
+/// document has no summary.
+const CONTEXTUAL_OVERVIEW_MAX_CHARS: usize = 4_000;
+/// Text taken from each neighbouring chunk when generating a chunk's context.
+const CONTEXTUAL_SURROUNDING_MAX_CHARS: usize = 500;
+/// Leading text sent to the LLM for document categorization.
+const CATEGORY_MAX_INPUT_CHARS: usize = 4_000;
+
+/// Metadata key holding a document's [`StageTimings`].
+pub const PROCESSING_TIMINGS_METADATA_KEY: &str = "processing_timings";
+
+/// Outcome of processing a single document.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProcessingResult {
+    /// Whether an LLM summary was generated and stored on the document.
+    pub summary_generated: bool,
+    pub timings: StageTimings,
+}
+
+/// Wall-clock time spent in each processing stage, in milliseconds. Optional
+/// stages are `None` when they did not run.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct StageTimings {
+    pub extraction_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter_ms: Option<u64>,
+    pub chunking_ms: u64,
+    pub embedding_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub categorization_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_extraction_ms: Option<u64>,
+    pub total_ms: u64,
+}
+
+fn elapsed_ms(start: Instant) -> u64 {
+    start.elapsed().as_millis() as u64
+}
+
+/// Embed `contents` in groups of `batch_size`, with up to `concurrency` groups
+/// in flight at once. Embeddings come back in input order, whichever group
+/// finishes first.
+async fn embed_in_batches<F, Fut>(
+    contents: Vec<String>,
+    batch_size: usize,
+    concurrency: usize,
+    embed: F,
+) -> Result<Vec<Vec<f32>>>
+where
+    F: Fn(Vec<String>) -> Fut,
+    Fut: std::future::Future<Output = Result<Vec<Vec<f32>>>>,
+{
+    let batches: Vec<Vec<String>> = contents
+        .chunks(batch_size.max(1))
+        .map(<[String]>::to_vec)
+        .collect();
+    let embedded: Vec<Vec<Vec<f32>>> = futures::stream::iter(batches)
+        .map(|batch| {
+            let expected = batch.len();
+            let embedding = embed(batch);
+            async move {
+                let embeddings = embedding.await?;
+                if embeddings.len() != expected {
+                    return Err(MomoError::Embedding(format!(
+                        "Expected {expected} embeddings, got {}",
+                        embeddings.len()
+                    )));
+                }
+                Ok(embeddings)
+            }
+        })
+        .buffered(concurrency.max(1))
+        .try_collect()
+        .await?;
+    Ok(embedded.into_iter().flatten().collect())
+}
+
+/// Store OCR/transcription confidence in document metadata, flagging the
+/// document when the average falls below `threshold`. Stale values from a
+/// previous extraction are cleared. Returns whether the document was flagged.
+fn record_extraction_confidence(
+    metadata: &mut Metadata,
+    confidence: Option<ExtractionConfidence>,
+    threshold: Option<f32>,
+) -> bool {
+    metadata.remove(EXTRACTION_CONFIDENCE_METADATA_KEY);
+    metadata.remove(LOW_CONFIDENCE_METADATA_KEY);
+
+    let Some(confidence) = confidence else {
+        return false;
+    };
+    if let Ok(value) = serde_json::to_value(confidence) {
+        metadata.insert(EXTRACTION_CONFIDENCE_METADATA_KEY.to_string(), value);
+    }
+
+    let low = threshold.is_some_and(|threshold| confidence.average < threshold);
+    if low {
+        metadata.insert(
+            LOW_CONFIDENCE_METADATA_KEY.to_string(),
+            serde_json::Value::Bool(true),
+        );
+    }
+    low
+}
+
+/// Store the spoken language detected while transcribing in document
+/// metadata, clearing a stale value from a previous extraction.
+fn record_transcript_language(metadata: &mut Metadata, language: Option<TranscriptLanguage>) {
+    metadata.remove(TRANSCRIPT_LANGUAGE_METADATA_KEY);
+    if let Some(value) = language.and_then(|language| serde_json::to_value(language).ok()) {
+        metadata.insert(TRANSCRIPT_LANGUAGE_METADATA_KEY.to_string(), value);
+    }
+}
+
+/// Title plus summary (or leading text) describing a document to the LLM.
+fn document_overview(title: Option<&str>, summary: Option<&str>, text: &str) -> String {
+    let mut overview = String::new();
+    if let Some(title) = title.filter(|t| !t.trim().is_empty()) {
+        overview.push_str(&format!("Title: {}\n", title.trim()));
+    }
+    match summary.filter(|s| !s.trim().is_empty()) {
+        Some(summary) => overview.push_str(&format!("Summary: {}", summary.trim())),
+        None => {
+            let leading: String = text.chars().take(CONTEXTUAL_OVERVIEW_MAX_CHARS).collect();
+            overview.push_str(&format!("Beginning of document:\n{leading}"));
+        }
+    }
+    overview
+}
+
+/// The tail of the previous chunk and head of the next one, for chunk `index`.
+fn surrounding_text(chunks: &[Chunk], index: usize) -> String {
+    let mut parts = Vec::new();
+    if let Some(prev) = index.checked_sub(1).and_then(|i| chunks.get(i)) {
+        let chars: Vec<char> = prev.content.chars().collect();
+        let start = chars.len().saturating_sub(CONTEXTUAL_SURROUNDING_MAX_CHARS);
+        parts.push(chars[start..].iter().collect::<String>());
+    }
+    if let Some(next) = chunks.get(index + 1) {
+        parts.push(
+            next.content
+                .chars()
+                .take(CONTEXTUAL_SURROUNDING_MAX_CHARS)
+                .collect::<String>(),
+        );
+    }
+    parts.join("\n...\n")
+}
+
+/// Text embedded for a chunk that has a generated context.
+fn contextualized_content(context: &str, content: &str) -> String {
+    format!("{context}\n\n{content}")
+}
+
+/// Caps how many jobs of one kind (OCR, transcription) run at once across
+/// all clones of the pipeline.
+#[derive(Clone)]
+struct JobLimiter {
+    name: &'static str,
+    /// `None` when the limit is disabled.
+    permits: Option<Arc<Semaphore>>,
+}
+
+impl JobLimiter {
+    /// `max_concurrent` of 0 means unlimited.
+    fn new(name: &'static str, max_concurrent: usize) -> Self {
+        Self {
+            name,
+            permits: (max_concurrent > 0).then(|| Arc::new(Semaphore::new(max_concurrent))),
+        }
+    }
+
+    /// Wait for a free slot. The returned permit releases it when dropped.
+    async fn acquire(&self, doc_id: &str) -> Option<OwnedSemaphorePermit> {
+        let permits = self.permits.as_ref()?;
+        if let Ok(permit) = Arc::clone(permits).try_acquire_owned() {
+            return Some(permit);
+        }
 
-use super::extractors::{AudioExtractor, ExtractedContent, ImageExtractor, VideoExtractor};
-use super::{ChunkContext, ChunkerRegistry, ContentExtractor};
+        tracing::info!(doc_id = %doc_id, "Waiting for a free {} slot", self.name);
+        // The semaphore is never closed, so acquiring cannot fail.
+        Arc::clone(permits).acquire_owned().await.ok()
+    }
+}
 
 pub struct ProcessingPipeline {
     db: Arc<dyn DatabaseBackend>,
@@ -25,10 +240,35 @@ pub struct ProcessingPipeline {
     extractor: ContentExtractor,
     memory_extractor: MemoryExtractor,
     llm_filter: LlmFilter,
-    registry: ChunkerRegistry,
+    registry: Arc<ChunkerRegistry>,
     ocr_config: crate::config::OcrConfig,
     transcription_config: crate::config::TranscriptionConfig,
+    ocr_limiter: JobLimiter,
+    transcription_limiter: JobLimiter,
     enable_contradiction_detection: bool,
+    /// Minimum text length for auto-summaries; `None` when the feature is disabled.
+    auto_summary_min_chars: Option<usize>,
+    contextual_chunks: bool,
+    detect_memory_language: bool,
+    /// Near-duplicate similarity threshold; `None` when the check is disabled.
+    near_duplicate_threshold: Option<f32>,
+    near_duplicate_action: NearDuplicateAction,
+    record_stage_timings: bool,
+    /// Extraction confidence below which documents are flagged; `None` disables flagging.
+    low_confidence_threshold: Option<f32>,
+    categorize_documents: bool,
+    /// Scans extracted text for PII; `None` when detection is disabled.
+    pii_redactor: Option<PiiRedactor>,
+    webhooks: WebhookNotifier,
+    status_events: StatusEvents,
+    /// Chunks per embedding call.
+    embedding_batch_size: usize,
+    /// Embedding calls for one document in flight at once.
+    embedding_concurrency: usize,
+    /// Applied to embedding calls that fail transiently.
+    retry: RetryPolicy,
+    /// Failed runs after which a document is dead-lettered; `0` never does.
+    dead_letter_after_failures: u32,
 }
 
 impl ProcessingPipeline {
@@ -38,15 +278,49 @@ impl ProcessingPipeline {
         ocr: OcrProvider,
         transcription: TranscriptionProvider,
         llm: LlmProvider,
+        status_events: StatusEvents,
         config: &Config,
     ) -> Self {
-        let memory_extractor = MemoryExtractor::new(llm.clone(), embeddings.clone());
+        let retry = RetryPolicy::from_config(&config.processing);
+        let memory_extractor = MemoryExtractor::new(llm.clone(), embeddings.clone())
+            .with_examples(config.extraction.examples.clone())
+            .with_retry(retry);
         let llm_filter = LlmFilter::new(llm.clone(), config.clone());
         let enable_contradiction_detection = config
             .llm
             .as_ref()
             .is_some_and(|l| l.enable_contradiction_detection);
+        let token_counter = if config.processing.use_model_tokenizer {
+            let counter = embeddings.token_counter();
+            if counter.is_none() {
+                tracing::warn!(
+                    "Embedding tokenizer unavailable; chunk sizes fall back to character counts"
+                );
+            }
+            counter
+        } else {
+            None
+        };
+        let max_tokens = token_counter.as_ref().and(embeddings.max_sequence_length());
+        let processing = match max_tokens {
+            Some(max_tokens) => Self::cap_chunk_size(&config.processing, max_tokens),
+            None => config.processing.clone(),
+        };
+
+        let mut registry = ChunkerRegistry::new(&processing);
+        if processing.chunk_strategy == ChunkStrategy::Semantic {
+            let embedder: Arc<dyn SentenceEmbedder> = Arc::new(embeddings.clone());
+            registry = registry.with_semantic_chunker(SemanticChunker::new(&processing, embedder));
+        }
+        if let Some(counter) = token_counter {
+            registry = registry.with_token_counter(counter);
+        }
+        if let Some(max_tokens) = max_tokens {
+            registry = registry.with_max_chunk_size(max_tokens);
+        }
         Self {
+            webhooks: WebhookNotifier::new(db.clone()),
+            status_events,
             db,
             embeddings,
             ocr,
@@ -55,34 +329,235 @@ impl ProcessingPipeline {
             extractor: ContentExtractor::new(),
             memory_extractor,
             llm_filter,
-            registry: ChunkerRegistry::new(&config.processing),
+            registry: Arc::new(registry),
             ocr_config: config.ocr.clone(),
             transcription_config: config.transcription.clone(),
+            ocr_limiter: JobLimiter::new("OCR", config.ocr.max_concurrent_jobs),
+            transcription_limiter: JobLimiter::new(
+                "transcription",
+                config.transcription.max_concurrent_jobs,
+            ),
+            embedding_batch_size: config.embeddings.batch_size,
+            embedding_concurrency: config.embeddings.max_concurrent_batches,
             enable_contradiction_detection,
+            auto_summary_min_chars: config
+                .processing
+                .auto_summary
+                .then_some(config.processing.auto_summary_min_chars),
+            contextual_chunks: config.processing.contextual_chunks,
+            detect_memory_language: config.memory.language_detection,
+            near_duplicate_threshold: config.processing.near_duplicate_threshold,
+            near_duplicate_action: config.processing.near_duplicate_action,
+            record_stage_timings: config.processing.record_stage_timings,
+            low_confidence_threshold: config.processing.low_confidence_threshold,
+            categorize_documents: config.processing.categorize_documents,
+            pii_redactor: config.processing.pii_detection.then(|| {
+                PiiRedactor::new(
+                    &config.processing.pii_categories,
+                    config.processing.pii_action,
+                )
+            }),
+            retry,
+            dead_letter_after_failures: config.processing.dead_letter_after_failures,
+        }
+    }
+
+    /// Chunk settings with `chunk_size` lowered to the model's token limit, so
+    /// no chunk is truncated before it's embedded.
+    fn cap_chunk_size(processing: &ProcessingConfig, max_tokens: usize) -> ProcessingConfig {
+        let mut capped = processing.clone();
+        if processing.chunk_size > max_tokens {
+            tracing::warn!(
+                chunk_size = processing.chunk_size,
+                max_tokens,
+                "CHUNK_SIZE exceeds the embedding model's token limit; capping it"
+            );
+            capped.chunk_size = max_tokens;
+            capped.chunk_overlap = processing.chunk_overlap.min(max_tokens / 2);
+        }
+        capped
+    }
+
+    /// Validate the chunk size overrides in document `metadata`, if any.
+    pub fn check_chunk_overrides(&self, metadata: &Metadata) -> Result<()> {
+        let overrides =
+            ChunkSizeOverride::from_metadata(metadata).map_err(MomoError::Validation)?;
+        if !overrides.is_empty() {
+            self.registry.chunk_config(overrides)?;
+        }
+        Ok(())
+    }
+
+    /// The chunker registry for `doc`: the shared one, or one sized by the
+    /// document's chunk size overrides. Invalid overrides are ignored.
+    fn registry_for(&self, doc: &crate::models::Document) -> Arc<ChunkerRegistry> {
+        let resized = ChunkSizeOverride::from_metadata(&doc.metadata)
+            .map_err(MomoError::Validation)
+            .and_then(|overrides| {
+                (!overrides.is_empty())
+                    .then(|| self.registry.with_chunk_sizes(overrides))
+                    .transpose()
+            });
+        match resized {
+            Ok(Some(registry)) => Arc::new(registry),
+            Ok(None) => Arc::clone(&self.registry),
+            Err(e) => {
+                tracing::warn!(
+                    doc_id = %doc.id,
+                    error = %e,
+                    "Ignoring invalid chunk size overrides"
+                );
+                Arc::clone(&self.registry)
+            }
+        }
+    }
+
+    /// Action configured for near-duplicate documents.
+    pub fn near_duplicate_action(&self) -> NearDuplicateAction {
+        self.near_duplicate_action
+    }
+
+    /// Find an existing document whose indexed content is nearly identical to
+    /// `content`, within `container_tags`.
+    ///
+    /// Embeds the leading part of `content` and searches existing chunks, so only
+    /// documents that have finished processing are considered. Returns `None`
+    /// when the check is disabled, `container_tags` is empty (an unscoped search
+    /// could match another tenant's document), or nothing scores above the
+    /// threshold.
+    pub async fn find_near_duplicate(
+        &self,
+        content: &str,
+        container_tags: &[String],
+    ) -> Result<Option<NearDuplicate>> {
+        let Some(threshold) = self.near_duplicate_threshold else {
+            return Ok(None);
+        };
+        if container_tags.is_empty() {
+            return Ok(None);
+        }
+
+        let input: String = content
+            .chars()
+            .take(NEAR_DUPLICATE_MAX_INPUT_CHARS)
+            .collect();
+        if input.trim().is_empty() {
+            return Ok(None);
+        }
+
+        let embedding = self.embeddings.embed_passage(&input).await?;
+        let hits = self
+            .db
+            .search_similar_chunks(&embedding, 1, threshold, Some(container_tags), None, None)
+            .await?;
+
+        Ok(hits.into_iter().next().map(|hit| NearDuplicate {
+            document_id: hit.document_id,
+            similarity: hit.score,
+        }))
+    }
+
+    /// Run ingestion for `doc_id`, record how long it took, then notify the
+    /// container's webhooks of the final status. Failures count towards the
+    /// document's dead-letter cap.
+    #[tracing::instrument(skip(self))]
+    pub async fn process_document(&self, doc_id: &str) -> Result<ProcessingResult> {
+        let started = Instant::now();
+        let result = self.run_document(doc_id).await;
+        let status = match &result {
+            Ok(_) => ProcessingStatus::Done,
+            Err(e) => {
+                self.record_failure(doc_id, e).await;
+                ProcessingStatus::Failed
+            }
+        };
+        if let Err(e) = self
+            .db
+            .record_processing_run(doc_id, status, elapsed_ms(started))
+            .await
+        {
+            tracing::warn!(doc_id = %doc_id, error = %e, "Failed to record processing run");
+        }
+        self.webhooks.document_processed(doc_id).await;
+        result
+    }
+
+    /// Persist `doc_id`'s status and announce the change to subscribers.
+    async fn record_failure(&self, doc_id: &str, error: &MomoError) {
+        let message = error.to_string();
+        match self
+            .db
+            .record_document_failure(doc_id, &message, self.dead_letter_after_failures)
+            .await
+        {
+            Ok(Some(ProcessingStatus::DeadLetter)) => {
+                tracing::warn!(
+                    doc_id = %doc_id,
+                    failures = self.dead_letter_after_failures,
+                    error = %message,
+                    "Document dead-lettered after repeated failures"
+                );
+                self.status_events
+                    .publish(doc_id, ProcessingStatus::DeadLetter, Some(&message));
+                media_upload::remove_for_document(&self.transcription_config, doc_id).await;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::warn!(doc_id = %doc_id, error = %e, "Failed to record processing failure")
+            }
+        }
+    }
+
+    /// Record the embedding model of containers getting their first embeddings.
+    async fn record_container_models(&self, container_tags: &[String]) {
+        for tag in container_tags {
+            if let Err(error) = self
+                .db
+                .record_container_embedding_model(&self.embeddings.container_model(tag))
+                .await
+            {
+                tracing::warn!(
+                    container_tag = %tag,
+                    error = %error,
+                    "Failed to record container embedding model (non-blocking)"
+                );
+            }
         }
     }
 
-    pub async fn process_document(&self, doc_id: &str) -> Result<()> {
+    async fn set_status(
+        &self,
+        doc_id: &str,
+        status: ProcessingStatus,
+        error: Option<&str>,
+    ) -> Result<()> {
+        self.db
+            .update_document_status(doc_id, status.clone(), error)
+            .await?;
+        self.status_events.publish(doc_id, status, error);
+        Ok(())
+    }
+
+    async fn run_document(&self, doc_id: &str) -> Result<ProcessingResult> {
+        let started = Instant::now();
+        let mut timings = StageTimings::default();
+
         let doc = self.db.get_document_by_id(doc_id).await?.ok_or_else(|| {
             crate::error::MomoError::NotFound(format!("Document {doc_id} not found"))
         })?;
 
-        self.db
-            .update_document_status(doc_id, ProcessingStatus::Extracting, None)
+        self.set_status(doc_id, ProcessingStatus::Extracting, None)
             .await?;
 
         let content = doc.content.as_deref().unwrap_or("");
+        let is_media_upload = content == media_upload::reference(doc_id);
 
-        let extracted = if doc.doc_type == DocumentType::Image {
+        let stage_started = Instant::now();
+        let mut extracted = if doc.doc_type == DocumentType::Image {
             match self.extract_image(doc_id, content).await {
                 Ok(e) => e,
                 Err(e) => {
-                    self.db
-                        .update_document_status(
-                            doc_id,
-                            ProcessingStatus::Failed,
-                            Some(&e.to_string()),
-                        )
+                    self.set_status(doc_id, ProcessingStatus::Failed, Some(&e.to_string()))
                         .await?;
                     return Err(e);
                 }
@@ -91,12 +566,7 @@ impl ProcessingPipeline {
             match self.extract_audio(doc_id, content).await {
                 Ok(e) => e,
                 Err(e) => {
-                    self.db
-                        .update_document_status(
-                            doc_id,
-                            ProcessingStatus::Failed,
-                            Some(&e.to_string()),
-                        )
+                    self.set_status(doc_id, ProcessingStatus::Failed, Some(&e.to_string()))
                         .await?;
                     return Err(e);
                 }
@@ -105,38 +575,95 @@ impl ProcessingPipeline {
             match self.extract_video(doc_id, content).await {
                 Ok(e) => e,
                 Err(e) => {
-                    self.db
-                        .update_document_status(
-                            doc_id,
-                            ProcessingStatus::Failed,
-                            Some(&e.to_string()),
-                        )
+                    self.set_status(doc_id, ProcessingStatus::Failed, Some(&e.to_string()))
+                        .await?;
+                    return Err(e);
+                }
+            }
+        } else if doc.doc_type == DocumentType::Webpage
+            && !content.starts_with("http://")
+            && !content.starts_with("https://")
+        {
+            // Uploaded HTML files, which may be fragments without an <html> tag.
+            match self.extractor.extract_from_html(content) {
+                Ok(e) => e,
+                Err(e) => {
+                    self.set_status(doc_id, ProcessingStatus::Failed, Some(&e.to_string()))
+                        .await?;
+                    return Err(e);
+                }
+            }
+        } else if doc.doc_type == DocumentType::Pdf
+            && !content.starts_with("http://")
+            && !content.starts_with("https://")
+        {
+            match self.extract_pdf(doc_id, content).await {
+                Ok(e) => e,
+                Err(e) => {
+                    self.set_status(doc_id, ProcessingStatus::Failed, Some(&e.to_string()))
+                        .await?;
+                    return Err(e);
+                }
+            }
+        } else if doc.doc_type == DocumentType::Epub {
+            match self.extract_epub(content) {
+                Ok(e) => e,
+                Err(e) => {
+                    self.set_status(doc_id, ProcessingStatus::Failed, Some(&e.to_string()))
                         .await?;
                     return Err(e);
                 }
             }
         } else {
-            match self.extractor.extract(content).await {
+            match self
+                .extractor
+                .extract(content)
+                .instrument(tracing::info_span!("extract_text"))
+                .await
+            {
                 Ok(e) => e,
                 Err(e) => {
-                    self.db
-                        .update_document_status(
-                            doc_id,
-                            ProcessingStatus::Failed,
-                            Some(&e.to_string()),
-                        )
+                    self.set_status(doc_id, ProcessingStatus::Failed, Some(&e.to_string()))
                         .await?;
                     return Err(e);
                 }
             }
         };
 
+        timings.extraction_ms = elapsed_ms(stage_started);
+
+        // Redact before anything downstream (LLM filter, chunks, embeddings,
+        // memories) sees the text.
+        let pii_report = self
+            .pii_redactor
+            .as_ref()
+            .and_then(|redactor| redactor.apply(&mut extracted.text));
+        // Segments repeat the transcript text, so they're redacted the same
+        // way; the report above already counts what they contain.
+        if let (Some(redactor), Some(segments)) = (
+            self.pii_redactor.as_ref(),
+            extracted.transcript_segments.as_mut(),
+        ) {
+            for segment in segments {
+                redactor.apply(&mut segment.text);
+            }
+        }
+        if let Some(report) = &pii_report {
+            tracing::info!(
+                doc_id = %doc_id,
+                action = %report.action,
+                counts = ?report.counts,
+                "PII detected in document"
+            );
+        }
+
         // LLM Filter Step: Check if document should be filtered
         let container_tag = doc.container_tags.first().map(|s| s.as_str()).unwrap_or("");
 
         if !container_tag.is_empty() {
             use crate::intelligence::filter::FilterDecision;
 
+            let stage_started = Instant::now();
             let container_filter = self.db.get_container_filter(container_tag).await?;
             let override_prompt = container_filter
                 .as_ref()
@@ -147,6 +674,7 @@ impl ProcessingPipeline {
                 .llm_filter
                 .filter_content(&extracted.text, container_tag, doc_id, override_prompt)
                 .await?;
+            timings.filter_ms = Some(elapsed_ms(stage_started));
 
             match filter_result.decision {
                 FilterDecision::Skip => {
@@ -164,15 +692,10 @@ impl ProcessingPipeline {
                         "Document filtered out by LLM"
                     );
 
-                    self.db
-                        .update_document_status(
-                            doc_id,
-                            ProcessingStatus::Done,
-                            Some(&error_message),
-                        )
+                    self.set_status(doc_id, ProcessingStatus::Done, Some(&error_message))
                         .await?;
 
-                    return Ok(());
+                    return Ok(ProcessingResult::default());
                 }
                 FilterDecision::Include => {
                     tracing::info!(
@@ -186,9 +709,9 @@ impl ProcessingPipeline {
             }
         }
 
-        self.db
-            .update_document_status(doc_id, ProcessingStatus::Chunking, None)
+        self.set_status(doc_id, ProcessingStatus::Chunking, None)
             .await?;
+        let stage_started = Instant::now();
 
         // Create chunk context with source_path and doc_type from ExtractedContent
         let chunk_context = ChunkContext {
@@ -196,19 +719,75 @@ impl ProcessingPipeline {
         };
 
         // Use registry to route to appropriate chunker based on document type
-        let chunker = self
-            .registry
-            .get_chunker(&extracted.doc_type, extracted.source_path.as_deref());
-
-        tracing::debug!(
-            "Using {:?} chunker for document type {:?}",
-            std::any::type_name::<dyn crate::processing::chunker::ContentChunker>(),
-            extracted.doc_type
-        );
+        let registry = self.registry_for(&doc);
+        let source_path = extracted.source_path.as_deref();
+        // Transcripts split on segment boundaries when asked to, whatever
+        // strategy prose would otherwise use.
+        let transcript_segments = extracted
+            .transcript_segments
+            .as_deref()
+            .filter(|_| self.transcription_config.chunk_by_segment);
+        let (chunker_type, text_chunks) = if let Some(segments) = transcript_segments {
+            tracing::debug!("Using transcript chunker for {} segments", segments.len());
+            let text_chunks = tracing::info_span!("chunk", chunker = %ChunkerType::Transcript)
+                .in_scope(|| registry.transcript_chunker().chunk_segments(segments));
+            (ChunkerType::Transcript, text_chunks)
+        } else {
+            match registry.semantic_chunker(&extracted.doc_type, source_path) {
+                Some(chunker) => {
+                    let chunker_type = chunker.chunker_type();
+                    tracing::debug!(
+                        "Using {} chunker for document type {:?}",
+                        chunker_type,
+                        extracted.doc_type
+                    );
+                    match self
+                        .retry
+                        .run("semantic_chunk", || chunker.chunk(&extracted.text))
+                        .instrument(tracing::info_span!("chunk", chunker = %chunker_type))
+                        .await
+                    {
+                        Ok(text_chunks) => (chunker_type, text_chunks),
+                        Err(e) => {
+                            self.set_status(doc_id, ProcessingStatus::Failed, Some(&e.to_string()))
+                                .await?;
+                            return Err(e);
+                        }
+                    }
+                }
+                None => {
+                    let chunker = registry.get_chunker(&extracted.doc_type, source_path);
+                    let chunker_type = chunker.chunker_type();
+                    tracing::debug!(
+                        "Using {} chunker for document type {:?}",
+                        chunker_type,
+                        extracted.doc_type
+                    );
+                    let text_chunks = tracing::info_span!("chunk", chunker = %chunker_type)
+                        .in_scope(|| chunker.chunk(&extracted.text, Some(&chunk_context)));
+                    (chunker_type, text_chunks)
+                }
+            }
+        };
+        let structured_data_overflow = (chunker_type == ChunkerType::StructuredData)
+            .then(|| registry.structured_data_overflow(&extracted.text))
+            .flatten();
+        if let Some(report) = structured_data_overflow {
+            tracing::warn!(
+                doc_id = %doc_id,
+                strategy = %report.strategy,
+                total_rows = report.total_rows,
+                indexed_rows = report.indexed_rows,
+                "Structured data exceeds the chunk limit"
+            );
+        }
 
-        let text_chunks = chunker.chunk(&extracted.text, Some(&chunk_context));
+        let code_language = (chunker_type == ChunkerType::Code)
+            .then(|| extracted.source_path.as_deref().and_then(detect_language))
+            .flatten()
+            .map(|language| language.to_string());
 
-        let chunks: Vec<Chunk> = text_chunks
+        let mut chunks: Vec<Chunk> = text_chunks
             .iter()
             .enumerate()
             .map(|(i, tc)| Chunk {
@@ -218,24 +797,50 @@ impl ProcessingPipeline {
                 embedded_content: Some(tc.content.clone()),
                 position: i as i32,
                 token_count: Some(tc.token_count),
+                chunker: Some(chunker_type),
+                language: code_language.clone(),
                 created_at: Utc::now(),
             })
             .collect();
 
+        if self.contextual_chunks {
+            let title = extracted.title.as_deref().or(doc.title.as_deref());
+            self.contextualize_chunks(&doc, title, &extracted.text, &mut chunks)
+                .await;
+        }
+
         self.db.delete_chunks_by_document_id(doc_id).await?;
         self.db.create_chunks_batch(&chunks).await?;
+        timings.chunking_ms = elapsed_ms(stage_started);
 
-        self.db
-            .update_document_status(doc_id, ProcessingStatus::Embedding, None)
+        self.set_status(doc_id, ProcessingStatus::Embedding, None)
             .await?;
 
-        let chunk_contents: Vec<String> = chunks.iter().map(|c| c.content.clone()).collect();
+        let stage_started = Instant::now();
+        let chunk_contents: Vec<String> = chunks
+            .iter()
+            .map(|c| {
+                c.embedded_content
+                    .clone()
+                    .unwrap_or_else(|| c.content.clone())
+            })
+            .collect();
 
-        let embeddings = match self.embeddings.embed_passages(chunk_contents).await {
+        let embeddings = match embed_in_batches(
+            chunk_contents,
+            self.embedding_batch_size,
+            self.embedding_concurrency,
+            |batch| {
+                self.retry.run("embed_passages", move || {
+                    self.embeddings.embed_passages(batch.clone())
+                })
+            },
+        )
+        .await
+        {
             Ok(e) => e,
             Err(e) => {
-                self.db
-                    .update_document_status(doc_id, ProcessingStatus::Failed, Some(&e.to_string()))
+                self.set_status(doc_id, ProcessingStatus::Failed, Some(&e.to_string()))
                     .await?;
                 return Err(e);
             }
@@ -248,9 +853,10 @@ impl ProcessingPipeline {
             .collect();
 
         self.db.update_chunk_embeddings_batch(&updates).await?;
+        self.record_container_models(&doc.container_tags).await;
+        timings.embedding_ms = elapsed_ms(stage_started);
 
-        self.db
-            .update_document_status(doc_id, ProcessingStatus::Indexing, None)
+        self.set_status(doc_id, ProcessingStatus::Indexing, None)
             .await?;
 
         let mut updated_doc = doc.clone();
@@ -267,80 +873,375 @@ impl ProcessingPipeline {
             _ => extracted.doc_type,
         };
         updated_doc.url = extracted.url.or(doc.url);
+        if record_extraction_confidence(
+            &mut updated_doc.metadata,
+            extracted.confidence,
+            self.low_confidence_threshold,
+        ) {
+            tracing::warn!(
+                doc_id = %doc_id,
+                confidence = ?extracted.confidence,
+                "Extracted text has low confidence; document flagged low_confidence"
+            );
+        }
+        if let Some(author) = extracted.author {
+            // Caller-supplied metadata wins over what the file claims.
+            updated_doc
+                .metadata
+                .entry(AUTHOR_METADATA_KEY.to_string())
+                .or_insert(serde_json::Value::String(author));
+        }
+        updated_doc.metadata.remove(OCR_DISCARDED_METADATA_KEY);
+        if extracted.ocr_discarded {
+            updated_doc.metadata.insert(
+                OCR_DISCARDED_METADATA_KEY.to_string(),
+                serde_json::Value::Bool(true),
+            );
+        }
+        updated_doc.metadata.remove(OCR_LAYOUT_METADATA_KEY);
+        if let Some(value) = extracted
+            .ocr_layout
+            .and_then(|blocks| serde_json::to_value(blocks).ok())
+        {
+            updated_doc
+                .metadata
+                .insert(OCR_LAYOUT_METADATA_KEY.to_string(), value);
+        }
+        record_transcript_language(&mut updated_doc.metadata, extracted.transcript_language);
+        updated_doc
+            .metadata
+            .remove(TRANSCRIPT_SEGMENTS_METADATA_KEY);
+        if let Some(value) = extracted
+            .transcript_segments
+            .filter(|_| self.transcription_config.include_segments)
+            .and_then(|segments| serde_json::to_value(segments).ok())
+        {
+            updated_doc
+                .metadata
+                .insert(TRANSCRIPT_SEGMENTS_METADATA_KEY.to_string(), value);
+        }
+        updated_doc.metadata.remove(PII_METADATA_KEY);
+        if let Some(value) = pii_report.and_then(|report| serde_json::to_value(report).ok()) {
+            updated_doc
+                .metadata
+                .insert(PII_METADATA_KEY.to_string(), value);
+        }
+        updated_doc
+            .metadata
+            .remove(STRUCTURED_DATA_OVERFLOW_METADATA_KEY);
+        if let Some(value) =
+            structured_data_overflow.and_then(|report| serde_json::to_value(report).ok())
+        {
+            updated_doc
+                .metadata
+                .insert(STRUCTURED_DATA_OVERFLOW_METADATA_KEY.to_string(), value);
+        }
         updated_doc.word_count = Some(extracted.word_count);
         updated_doc.chunk_count = chunks.len() as i32;
         updated_doc.token_count = Some(chunks.iter().filter_map(|c| c.token_count).sum());
+
+        // Client-provided categories, and ones from a previous run, are kept.
+        if self.categorize_documents && !updated_doc.metadata.contains_key(CATEGORY_METADATA_KEY) {
+            let stage_started = Instant::now();
+            if let Some(category) = self
+                .classify_category(doc_id, updated_doc.title.as_deref(), &extracted.text)
+                .await
+            {
+                updated_doc.metadata.insert(
+                    CATEGORY_METADATA_KEY.to_string(),
+                    serde_json::Value::String(category.to_string()),
+                );
+            }
+            timings.categorization_ms = Some(elapsed_ms(stage_started));
+        }
+
         updated_doc.status = ProcessingStatus::Done;
         updated_doc.updated_at = Utc::now();
+        timings.total_ms = elapsed_ms(started);
+        self.record_timings(&mut updated_doc, &timings);
 
         self.db.update_document(&updated_doc).await?;
+        self.status_events
+            .publish(doc_id, ProcessingStatus::Done, None);
+        if is_media_upload {
+            // The transcript has replaced the upload reference in `content`.
+            media_upload::remove_for_document(&self.transcription_config, doc_id).await;
+        }
+
+        // The summary is generated once the document is done, so it is
+        // searchable without waiting on the LLM.
+        let mut summary_generated = false;
+        let summarize = updated_doc.summary.is_none() && self.auto_summary_min_chars.is_some();
+        if summarize {
+            let stage_started = Instant::now();
+            if let Some(summary) = self.generate_summary(doc_id, &extracted.text).await {
+                updated_doc.summary = Some(summary);
+                summary_generated = true;
+            }
+            timings.summary_ms = Some(elapsed_ms(stage_started));
+        }
 
         // After document is done, check for extract_memories flag
-        if updated_doc
+        let extract_memories = updated_doc
             .metadata
             .get("extract_memories")
             .and_then(|value| value.as_bool())
-            .unwrap_or(false)
-        {
+            .unwrap_or(false);
+        if extract_memories {
+            let stage_started = Instant::now();
             if let Err(error) = self
                 .extract_memories_from_document(&updated_doc, &extracted.text)
                 .await
             {
                 tracing::warn!(doc_id = %doc_id, error = %error, "Memory extraction failed (non-blocking)");
             }
+            timings.memory_extraction_ms = Some(elapsed_ms(stage_started));
+        }
+
+        if summarize || extract_memories {
+            timings.total_ms = elapsed_ms(started);
+            let timings_changed = self.record_timings(&mut updated_doc, &timings);
+            if summary_generated || timings_changed {
+                self.db.update_document(&updated_doc).await?;
+            }
         }
 
         tracing::info!(
-            "Document {} processed: {} chunks, {} tokens",
-            doc_id,
-            updated_doc.chunk_count,
-            updated_doc.token_count.unwrap_or(0)
+            doc_id = %doc_id,
+            chunks = updated_doc.chunk_count,
+            tokens = updated_doc.token_count.unwrap_or(0),
+            extraction_ms = timings.extraction_ms,
+            filter_ms = ?timings.filter_ms,
+            chunking_ms = timings.chunking_ms,
+            embedding_ms = timings.embedding_ms,
+            summary_ms = ?timings.summary_ms,
+            categorization_ms = ?timings.categorization_ms,
+            memory_extraction_ms = ?timings.memory_extraction_ms,
+            total_ms = timings.total_ms,
+            "Document processed"
         );
 
-        Ok(())
+        Ok(ProcessingResult {
+            summary_generated,
+            timings,
+        })
     }
 
-    async fn extract_memories_from_document(
-        &self,
-        doc: &crate::models::Document,
-        content: &str,
-    ) -> Result<()> {
-        let container_tag = match doc.container_tags.first() {
-            Some(tag) => tag.as_str(),
-            None => {
-                tracing::warn!(doc_id = %doc.id, "No container tag available for memory extraction");
-                return Ok(());
-            }
-        };
+    /// Store `timings` in the document's metadata when stage timings are enabled.
+    /// Returns whether the metadata was changed.
+    fn record_timings(&self, doc: &mut crate::models::Document, timings: &StageTimings) -> bool {
+        if !self.record_stage_timings {
+            return false;
+        }
 
-        let extraction_result = self.memory_extractor.extract(content).await?;
+        let Ok(value) = serde_json::to_value(timings) else {
+            return false;
+        };
+        doc.metadata
+            .insert(PROCESSING_TIMINGS_METADATA_KEY.to_string(), value);
+        true
+    }
 
-        if extraction_result.memories.is_empty() {
-            tracing::debug!(doc_id = %doc.id, "No memories extracted from document");
-            return Ok(());
+    /// Summarize long documents with the LLM when auto-summaries are enabled.
+    ///
+    /// Returns `None` for short documents, when the LLM is unavailable, or when
+    /// generation fails; failures never block document processing.
+    #[tracing::instrument(skip_all)]
+    async fn generate_summary(&self, doc_id: &str, text: &str) -> Option<String> {
+        let min_chars = self.auto_summary_min_chars?;
+        if !self.llm.is_available() || text.chars().count() < min_chars {
+            return None;
         }
 
-        let total_extracted = extraction_result.memories.len();
-
-        let memories = if self.enable_contradiction_detection {
-            self.memory_extractor
-                .check_contradictions(extraction_result.memories, container_tag, self.db.as_ref())
-                .await?
-        } else {
-            extraction_result.memories
-        };
+        let input: String = text.chars().take(AUTO_SUMMARY_MAX_INPUT_CHARS).collect();
+        let prompt = summarize_prompt(&input, AUTO_SUMMARY_MAX_WORDS);
 
-        let unique_memories = self
-            .memory_extractor
-            .deduplicate(memories, container_tag, self.db.as_ref())
-            .await?;
+        match self.llm.complete(&prompt, None).await {
+            Ok(summary) => {
+                let summary = summary.trim();
+                if summary.is_empty() {
+                    None
+                } else {
+                    tracing::debug!(doc_id = %doc_id, "Generated document summary");
+                    Some(summary.to_string())
+                }
+            }
+            Err(error) => {
+                tracing::warn!(doc_id = %doc_id, error = %error, "Summary generation failed (non-blocking)");
+                None
+            }
+        }
+    }
 
-        tracing::info!(
-            doc_id = %doc.id,
-            total_extracted,
-            unique_count = unique_memories.len(),
-            "Memory extraction complete"
-        );
+    /// Classify a document into a [`DocumentCategory`] with the LLM.
+    ///
+    /// Returns `None` when the LLM is unavailable, the text is empty, or the
+    /// LLM fails or answers with an unknown label; failures never block
+    /// document processing.
+    #[tracing::instrument(skip_all)]
+    async fn classify_category(
+        &self,
+        doc_id: &str,
+        title: Option<&str>,
+        text: &str,
+    ) -> Option<DocumentCategory> {
+        if !self.llm.is_available() || text.trim().is_empty() {
+            return None;
+        }
+
+        let input: String = text.chars().take(CATEGORY_MAX_INPUT_CHARS).collect();
+        let labels: Vec<&str> = DocumentCategory::ALL
+            .iter()
+            .map(|category| category.as_str())
+            .collect();
+        let prompt = document_category_prompt(title, &input, &labels);
+
+        match self.llm.complete(&prompt, None).await {
+            Ok(answer) => match answer.parse::<DocumentCategory>() {
+                Ok(category) => {
+                    tracing::debug!(doc_id = %doc_id, category = %category, "Classified document");
+                    Some(category)
+                }
+                Err(error) => {
+                    tracing::warn!(doc_id = %doc_id, error = %error, "Document categorization returned an unknown label (non-blocking)");
+                    None
+                }
+            },
+            Err(error) => {
+                tracing::warn!(doc_id = %doc_id, error = %error, "Document categorization failed (non-blocking)");
+                None
+            }
+        }
+    }
+
+    /// Prefix each chunk's `embedded_content` with an LLM-generated sentence that
+    /// situates it within the document, leaving `content` untouched.
+    ///
+    /// The document overview is built once per document, and contexts from a
+    /// previous run are reused for chunks whose content has not changed. Chunks
+    /// whose context cannot be generated are embedded as-is.
+    #[tracing::instrument(skip_all)]
+    async fn contextualize_chunks(
+        &self,
+        doc: &crate::models::Document,
+        title: Option<&str>,
+        text: &str,
+        chunks: &mut [Chunk],
+    ) {
+        if !self.llm.is_available() || chunks.is_empty() {
+            return;
+        }
+
+        let overview = document_overview(title, doc.summary.as_deref(), text);
+        let mut cached = self.previous_chunk_contexts(&doc.id).await;
+        let mut generated = 0usize;
+
+        for i in 0..chunks.len() {
+            let context = match cached.get(&chunks[i].content) {
+                Some(context) => context.clone(),
+                None => {
+                    let surrounding = surrounding_text(chunks, i);
+                    let prompt = chunk_context_prompt(&overview, &surrounding, &chunks[i].content);
+                    match self.llm.complete(&prompt, None).await {
+                        Ok(context) if !context.trim().is_empty() => {
+                            let context = context.trim().to_string();
+                            generated += 1;
+                            cached.insert(chunks[i].content.clone(), context.clone());
+                            context
+                        }
+                        Ok(_) => continue,
+                        Err(error) => {
+                            tracing::warn!(doc_id = %doc.id, error = %error, "Chunk context generation failed (non-blocking)");
+                            continue;
+                        }
+                    }
+                }
+            };
+            chunks[i].embedded_content = Some(contextualized_content(&context, &chunks[i].content));
+        }
+
+        tracing::debug!(
+            doc_id = %doc.id,
+            chunks = chunks.len(),
+            generated,
+            "Generated chunk contexts"
+        );
+    }
+
+    /// Contexts stored for a document's current chunks, keyed by chunk content.
+    async fn previous_chunk_contexts(&self, doc_id: &str) -> HashMap<String, String> {
+        let previous = match self
+            .db
+            .get_chunks_by_position_range(doc_id, 0, i32::MAX)
+            .await
+        {
+            Ok(chunks) => chunks,
+            Err(error) => {
+                tracing::debug!(doc_id = %doc_id, error = %error, "Could not load previous chunk contexts");
+                return HashMap::new();
+            }
+        };
+
+        previous
+            .into_iter()
+            .filter_map(|chunk| {
+                let embedded = chunk.embedded_content?;
+                let context = embedded
+                    .strip_suffix(chunk.content.as_str())?
+                    .strip_suffix("\n\n")?
+                    .to_string();
+                (!context.is_empty()).then_some((chunk.content, context))
+            })
+            .collect()
+    }
+
+    #[tracing::instrument(skip_all)]
+
+    async fn extract_memories_from_document(
+        &self,
+        doc: &crate::models::Document,
+        content: &str,
+    ) -> Result<()> {
+        let container_tag = match doc.container_tags.first() {
+            Some(tag) => tag.as_str(),
+            None => {
+                tracing::warn!(doc_id = %doc.id, "No container tag available for memory extraction");
+                return Ok(());
+            }
+        };
+
+        let extraction_result = self.memory_extractor.extract(content).await?;
+
+        if extraction_result.memories.is_empty() {
+            tracing::debug!(doc_id = %doc.id, "No memories extracted from document");
+            return Ok(());
+        }
+
+        let total_extracted = extraction_result.memories.len();
+
+        let memories = if self.enable_contradiction_detection {
+            self.memory_extractor
+                .check_contradictions(extraction_result.memories, container_tag, self.db.as_ref())
+                .await?
+        } else {
+            extraction_result.memories
+        };
+
+        let unique_memories = self
+            .memory_extractor
+            .deduplicate(memories, container_tag, self.db.as_ref())
+            .await?;
+
+        tracing::info!(
+            doc_id = %doc.id,
+            total_extracted,
+            unique_count = unique_memories.len(),
+            "Memory extraction complete"
+        );
+        if !unique_memories.is_empty() {
+            self.record_container_models(&[container_tag.to_string()])
+                .await;
+        }
 
         for extracted in unique_memories {
             let mut metadata = crate::models::Metadata::new();
@@ -359,8 +1260,12 @@ impl ProcessingPipeline {
             if let Some(context) = extracted.context.clone() {
                 metadata.insert("context".to_string(), serde_json::Value::String(context));
             }
+            if self.detect_memory_language {
+                language::tag_language(&mut metadata, &extracted.content);
+            }
 
             let embedding = self.embeddings.embed_passage(&extracted.content).await?;
+            let contradicted_memory_id = extracted.contradicted_memory_id.clone();
 
             let parsed_memory_type = extracted
                 .memory_type
@@ -411,11 +1316,33 @@ impl ProcessingPipeline {
             self.db
                 .update_memory_embedding(&memory.id, &embedding)
                 .await?;
+
+            if let Some(conflicting_id) = contradicted_memory_id {
+                if let Err(error) = self
+                    .db
+                    .flag_memory_contradiction(
+                        &memory.id,
+                        &conflicting_id,
+                        Some(container_tag),
+                        Some("Heuristic pattern match during document extraction"),
+                    )
+                    .await
+                {
+                    tracing::warn!(
+                        memory_id = %memory.id,
+                        conflicting_memory_id = %conflicting_id,
+                        error = %error,
+                        "Failed to flag contradiction (non-blocking)"
+                    );
+                }
+            }
         }
 
         Ok(())
     }
 
+    #[tracing::instrument(skip_all)]
+
     async fn extract_image(&self, doc_id: &str, content: &str) -> Result<ExtractedContent> {
         if !self.ocr.is_available() {
             tracing::warn!(
@@ -434,6 +1361,7 @@ impl ProcessingPipeline {
                 crate::error::MomoError::Processing(format!("Failed to decode base64 image: {e}"))
             })?;
 
+        let _permit = self.ocr_limiter.acquire(doc_id).await;
         let extracted = ImageExtractor::extract(&bytes, &self.ocr, &self.ocr_config).await?;
 
         tracing::info!(
@@ -445,6 +1373,8 @@ impl ProcessingPipeline {
         Ok(extracted)
     }
 
+    #[tracing::instrument(skip_all)]
+
     async fn extract_audio(&self, doc_id: &str, content: &str) -> Result<ExtractedContent> {
         if !self.transcription.is_available() {
             tracing::warn!(
@@ -458,14 +1388,23 @@ impl ProcessingPipeline {
 
         tracing::info!(doc_id = %doc_id, "Processing audio document with transcription");
 
-        let bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, content)
-            .map_err(|e| {
-                crate::error::MomoError::Processing(format!("Failed to decode base64 audio: {e}"))
-            })?;
-
-        let extracted =
-            AudioExtractor::extract(&bytes, &self.transcription, &self.transcription_config)
-                .await?;
+        let upload_dir = media_upload::upload_dir(&self.transcription_config);
+        let extracted = if let Some(path) = media_upload::resolve(content, &upload_dir) {
+            let _permit = self.transcription_limiter.acquire(doc_id).await;
+            // The file stays until the transcript is stored, so a failed run can be retried.
+            AudioExtractor::extract_file(&path, &self.transcription, &self.transcription_config)
+                .await?
+        } else {
+            let bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, content)
+                .map_err(|e| {
+                    crate::error::MomoError::Processing(format!(
+                        "Failed to decode base64 audio: {e}"
+                    ))
+                })?;
+
+            let _permit = self.transcription_limiter.acquire(doc_id).await;
+            AudioExtractor::extract(&bytes, &self.transcription, &self.transcription_config).await?
+        };
 
         tracing::info!(
             doc_id = %doc_id,
@@ -476,6 +1415,8 @@ impl ProcessingPipeline {
         Ok(extracted)
     }
 
+    #[tracing::instrument(skip_all)]
+
     async fn extract_video(&self, doc_id: &str, content: &str) -> Result<ExtractedContent> {
         if !self.transcription.is_available() {
             tracing::warn!(
@@ -489,32 +1430,87 @@ impl ProcessingPipeline {
 
         tracing::info!(doc_id = %doc_id, "Processing video document with transcription");
 
+        let upload_dir = media_upload::upload_dir(&self.transcription_config);
+        let extracted = if let Some(path) = media_upload::resolve(content, &upload_dir) {
+            let _permit = self.transcription_limiter.acquire(doc_id).await;
+            // The file stays until the transcript is stored, so a failed run can be retried.
+            VideoExtractor::extract_file(&path, &self.transcription, &self.transcription_config)
+                .await?
+        } else {
+            let bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, content)
+                .map_err(|e| {
+                    crate::error::MomoError::Processing(format!(
+                        "Failed to decode base64 video: {e}"
+                    ))
+                })?;
+
+            let _permit = self.transcription_limiter.acquire(doc_id).await;
+            VideoExtractor::extract(&bytes, &self.transcription, &self.transcription_config).await?
+        };
+
+        tracing::info!(
+            doc_id = %doc_id,
+            word_count = extracted.word_count,
+            "Video transcription complete"
+        );
+
+        Ok(extracted)
+    }
+
+    /// PDFs arrive base64-encoded. Scanned pages are OCRed when a provider is
+    /// available; otherwise only the text layer is kept.
+    #[tracing::instrument(skip_all)]
+    async fn extract_pdf(&self, doc_id: &str, content: &str) -> Result<ExtractedContent> {
         let bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, content)
             .map_err(|e| {
-                crate::error::MomoError::Processing(format!("Failed to decode base64 video: {e}"))
+                crate::error::MomoError::Processing(format!("Failed to decode base64 PDF: {e}"))
             })?;
 
-        let extracted =
-            VideoExtractor::extract(&bytes, &self.transcription, &self.transcription_config)
-                .await?;
+        if !self.ocr.is_available() {
+            return self.extractor.extract_from_pdf(&bytes, None);
+        }
+
+        let _permit = self.ocr_limiter.acquire(doc_id).await;
+        let extracted = PdfExtractor::extract_with_ocr(&bytes, &self.ocr, &self.ocr_config).await?;
 
         tracing::info!(
             doc_id = %doc_id,
             word_count = extracted.word_count,
-            "Video transcription complete"
+            "PDF extraction complete"
         );
 
         Ok(extracted)
     }
 
-    pub async fn process_pending(&self) -> Result<()> {
+    /// EPUBs arrive base64-encoded, like other binary uploads.
+    fn extract_epub(&self, content: &str) -> Result<ExtractedContent> {
+        let bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, content)
+            .map_err(|e| {
+                crate::error::MomoError::Processing(format!("Failed to decode base64 EPUB: {e}"))
+            })?;
+
+        self.extractor.extract_from_epub(&bytes)
+    }
+
+    /// Process every queued or unfinished document in creation order. Once
+    /// `cancel` fires, the document in progress is finished and the rest are
+    /// left for the next run.
+    pub async fn process_pending(&self, cancel: &CancellationToken) -> Result<()> {
         let pending = self.db.get_processing_documents().await?;
+        let total = pending.len();
 
-        for doc in pending {
+        for (processed, doc) in pending.into_iter().enumerate() {
+            if cancel.is_cancelled() {
+                tracing::info!(
+                    remaining = total - processed,
+                    "Shutting down; leaving remaining documents for the next run"
+                );
+                break;
+            }
             let mut attempts = 0;
             loop {
                 match self.process_document(&doc.id).await {
-                    Ok(()) => break,
+                    Ok(_) => break,
                     Err(e) if is_database_locked_error(&e) && attempts < 3 => {
                         attempts += 1;
                         let delay_ms = 100 * attempts as u64;
@@ -558,10 +1554,27 @@ impl Clone for ProcessingPipeline {
             extractor: ContentExtractor::new(),
             memory_extractor: self.memory_extractor.clone(),
             llm_filter: self.llm_filter.clone(),
-            registry: ChunkerRegistry::default(),
+            registry: Arc::clone(&self.registry),
             ocr_config: self.ocr_config.clone(),
             transcription_config: self.transcription_config.clone(),
+            ocr_limiter: self.ocr_limiter.clone(),
+            transcription_limiter: self.transcription_limiter.clone(),
+            embedding_batch_size: self.embedding_batch_size,
+            embedding_concurrency: self.embedding_concurrency,
             enable_contradiction_detection: self.enable_contradiction_detection,
+            auto_summary_min_chars: self.auto_summary_min_chars,
+            contextual_chunks: self.contextual_chunks,
+            detect_memory_language: self.detect_memory_language,
+            near_duplicate_threshold: self.near_duplicate_threshold,
+            near_duplicate_action: self.near_duplicate_action,
+            record_stage_timings: self.record_stage_timings,
+            low_confidence_threshold: self.low_confidence_threshold,
+            categorize_documents: self.categorize_documents,
+            pii_redactor: self.pii_redactor.clone(),
+            webhooks: self.webhooks.clone(),
+            status_events: self.status_events.clone(),
+            retry: RetryPolicy::none(),
+            dead_letter_after_failures: 0,
         }
     }
 }
@@ -602,6 +1615,83 @@ mod tests {
         })
     }
 
+    fn test_embeddings() -> EmbeddingProvider {
+        EmbeddingProvider::new(&EmbeddingsConfig {
+            model: "BAAI/bge-small-en-v1.5".to_string(),
+            dimensions: 384,
+            batch_size: 8,
+            query_cache_size: 0,
+            max_concurrent_batches: 4,
+        })
+        .expect("failed to create embeddings provider")
+    }
+
+    fn test_llm_config(base_url: String) -> LlmConfig {
+        LlmConfig {
+            model: "openai/gpt-4o-mini".to_string(),
+            api_key: Some("test-key".to_string()),
+            base_url: Some(base_url),
+            timeout_secs: 5,
+            max_retries: 0,
+            enable_query_rewrite: false,
+            query_rewrite_cache_size: 1000,
+            query_rewrite_timeout_secs: 2,
+            enable_auto_relations: false,
+            enable_contradiction_detection: false,
+            filter_prompt: None,
+            max_calls_per_run: None,
+            max_tokens_per_run: None,
+        }
+    }
+
+    /// A pipeline, its backend and a connection to the same database, and the
+    /// temp dir holding that database (dropping it deletes the database).
+    type TestPipeline = (
+        ProcessingPipeline,
+        Arc<dyn DatabaseBackend>,
+        libsql::Connection,
+        tempfile::TempDir,
+    );
+
+    /// Pipeline over a fresh database in a temp dir, with the OCR and
+    /// transcription providers `config` describes.
+    async fn test_pipeline(config: &Config, llm: LlmProvider) -> TestPipeline {
+        let transcription = TranscriptionProvider::new(&config.transcription)
+            .expect("failed to create transcription provider");
+        test_pipeline_with_transcription(config, llm, transcription).await
+    }
+
+    async fn test_pipeline_with_transcription(
+        config: &Config,
+        llm: LlmProvider,
+        transcription: TranscriptionProvider,
+    ) -> TestPipeline {
+        let ocr = OcrProvider::new(&config.ocr).expect("failed to create ocr provider");
+
+        let temp_dir = tempdir().expect("failed to create temp dir");
+        let db = Database::new(&DatabaseConfig {
+            url: format!("file:{}", temp_dir.path().join("pipeline.db").display()),
+            auth_token: None,
+            local_path: None,
+            max_connections: 4,
+        })
+        .await
+        .expect("failed to create database");
+        let conn = db.connect().expect("failed to connect to database");
+        let backend: Arc<dyn DatabaseBackend> = Arc::new(LibSqlBackend::new(db));
+
+        let pipeline = ProcessingPipeline::new(
+            backend.clone(),
+            test_embeddings(),
+            ocr,
+            transcription,
+            llm,
+            StatusEvents::new(),
+            config,
+        );
+        (pipeline, backend, conn, temp_dir)
+    }
+
     #[tokio::test]
     async fn pipeline_memory_sources() {
         let mock_server = MockServer::start().await;
@@ -627,67 +1717,13 @@ mod tests {
             .mount(&mock_server)
             .await;
 
-        let embeddings_config = EmbeddingsConfig {
-            model: "BAAI/bge-small-en-v1.5".to_string(),
-            dimensions: 384,
-            batch_size: 8,
-        };
-
-        let embeddings = EmbeddingProvider::new(&embeddings_config)
-            .expect("failed to create embeddings provider");
-        let memory_embeddings = EmbeddingProvider::new(&embeddings_config)
-            .expect("failed to create memory embeddings provider");
-
-        let llm_config = LlmConfig {
-            model: "openai/gpt-4o-mini".to_string(),
-            api_key: Some("test-key".to_string()),
-            base_url: Some(mock_server.uri()),
-            timeout_secs: 5,
-            max_retries: 0,
-            enable_query_rewrite: false,
-            query_rewrite_cache_size: 1000,
-            query_rewrite_timeout_secs: 2,
-            enable_auto_relations: false,
-            enable_contradiction_detection: false,
-            filter_prompt: None,
-        };
+        let llm_config = test_llm_config(mock_server.uri());
 
         let llm = LlmProvider::new(Some(&llm_config));
 
         let config = Config::default();
-        let ocr = OcrProvider::new(&config.ocr).expect("failed to create ocr provider");
-        let transcription = TranscriptionProvider::new(&config.transcription)
-            .expect("failed to create transcription provider");
+        let (pipeline, _, conn, _temp_dir) = test_pipeline(&config, llm).await;
 
-        let temp_dir = tempdir().expect("failed to create temp dir");
-        let db_path = temp_dir.path().join("pipeline_memory_sources.db");
-        let db_config = DatabaseConfig {
-            url: format!("file:{}", db_path.display()),
-            auth_token: None,
-            local_path: None,
-        };
-
-        let db = Database::new(&db_config)
-            .await
-            .expect("failed to create database");
-        let backend: Arc<dyn DatabaseBackend> = Arc::new(LibSqlBackend::new(db.clone()));
-
-        let pipeline = ProcessingPipeline {
-            db: backend,
-            embeddings,
-            ocr,
-            transcription,
-            llm: llm.clone(),
-            extractor: ContentExtractor::new(),
-            memory_extractor: MemoryExtractor::new(llm.clone(), memory_embeddings),
-            llm_filter: LlmFilter::new(llm, config.clone()),
-            registry: ChunkerRegistry::new(&config.processing),
-            ocr_config: config.ocr.clone(),
-            transcription_config: config.transcription.clone(),
-            enable_contradiction_detection: false,
-        };
-
-        let conn = db.connect().expect("failed to connect to database");
         let mut doc = Document::new("doc-1".to_string());
         doc.content = Some("User prefers dark mode".to_string());
         doc.container_tags = vec!["user-123".to_string()];
@@ -736,62 +1772,133 @@ mod tests {
             .mount(&mock_server)
             .await;
 
-        let embeddings_config = EmbeddingsConfig {
-            model: "BAAI/bge-small-en-v1.5".to_string(),
-            dimensions: 384,
-            batch_size: 8,
+        let llm_config = LlmConfig {
+            filter_prompt: Some("technical documents only".to_string()),
+            ..test_llm_config(mock_server.uri())
         };
 
-        let embeddings = EmbeddingProvider::new(&embeddings_config)
-            .expect("failed to create embeddings provider");
+        let llm = LlmProvider::new(Some(&llm_config));
+
+        let mut config = Config::default();
+        config.llm = Some(llm_config.clone());
+        let (pipeline, backend, conn, _temp_dir) = test_pipeline(&config, llm).await;
+
+        let mut doc = Document::new("doc-filter-skip".to_string());
+        doc.content = Some("This is marketing content about our products".to_string());
+        doc.container_tags = vec!["user-123".to_string()];
+
+        DocumentRepository::create(&conn, &doc)
+            .await
+            .expect("failed to create document");
+
+        pipeline
+            .process_document(&doc.id)
+            .await
+            .expect("pipeline processing should succeed");
+
+        let updated_doc = backend
+            .get_document_by_id(&doc.id)
+            .await
+            .expect("failed to get document")
+            .expect("document should exist");
+
+        assert_eq!(updated_doc.status, ProcessingStatus::Done);
+        assert!(updated_doc.error_message.is_some());
+        assert!(updated_doc.error_message.unwrap().contains("Filtered:"));
+        assert_eq!(updated_doc.chunk_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_filter_integration_include() {
+        let mock_server = MockServer::start().await;
+
+        let embedding = vec![0.1_f32; 384];
+        Mock::given(method("POST"))
+            .and(path("/embeddings"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": [{"embedding": embedding}]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(llm_response(
+                r#"{"decision":"include","reasoning":"Content matches technical criteria"}"#,
+            )))
+            .mount(&mock_server)
+            .await;
 
         let llm_config = LlmConfig {
-            model: "openai/gpt-4o-mini".to_string(),
-            api_key: Some("test-key".to_string()),
-            base_url: Some(mock_server.uri()),
-            timeout_secs: 5,
-            max_retries: 0,
-            enable_query_rewrite: false,
-            query_rewrite_cache_size: 1000,
-            query_rewrite_timeout_secs: 2,
-            enable_auto_relations: false,
-            enable_contradiction_detection: false,
             filter_prompt: Some("technical documents only".to_string()),
+            ..test_llm_config(mock_server.uri())
         };
 
         let llm = LlmProvider::new(Some(&llm_config));
 
         let mut config = Config::default();
         config.llm = Some(llm_config.clone());
-        let ocr = OcrProvider::new(&config.ocr).expect("failed to create ocr provider");
-        let transcription = TranscriptionProvider::new(&config.transcription)
-            .expect("failed to create transcription provider");
+        let (pipeline, backend, conn, _temp_dir) = test_pipeline(&config, llm).await;
 
-        let temp_dir = tempdir().expect("failed to create temp dir");
-        let db_path = temp_dir.path().join("test_filter_skip.db");
-        let db_config = DatabaseConfig {
-            url: format!("file:{}", db_path.display()),
-            auth_token: None,
-            local_path: None,
-        };
+        let mut doc = Document::new("doc-filter-include".to_string());
+        doc.content = Some("This is technical content about Rust programming".to_string());
+        doc.container_tags = vec!["user-123".to_string()];
 
-        let db = Database::new(&db_config)
+        DocumentRepository::create(&conn, &doc)
             .await
-            .expect("failed to create database");
-        let backend: Arc<dyn DatabaseBackend> = Arc::new(LibSqlBackend::new(db.clone()));
+            .expect("failed to create document");
 
-        let pipeline = ProcessingPipeline::new(
-            backend.clone(),
-            embeddings,
-            ocr,
-            transcription,
-            llm,
-            &config,
-        );
+        pipeline
+            .process_document(&doc.id)
+            .await
+            .expect("pipeline processing should succeed");
 
-        let conn = db.connect().expect("failed to connect to database");
-        let mut doc = Document::new("doc-filter-skip".to_string());
-        doc.content = Some("This is marketing content about our products".to_string());
+        let updated_doc = backend
+            .get_document_by_id(&doc.id)
+            .await
+            .expect("failed to get document")
+            .expect("document should exist");
+
+        assert_eq!(updated_doc.status, ProcessingStatus::Done);
+        assert!(updated_doc.error_message.is_none());
+        assert!(updated_doc.chunk_count > 0);
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_retries_transient_llm_failures() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(503).set_body_string("Service Unavailable"))
+            .up_to_n_times(2)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(llm_response(
+                r#"{"decision":"skip","reasoning":"Not technical"}"#,
+            )))
+            .mount(&mock_server)
+            .await;
+
+        let llm_config = LlmConfig {
+            timeout_secs: 1,
+            filter_prompt: Some("technical documents only".to_string()),
+            ..test_llm_config(mock_server.uri())
+        };
+
+        let llm = LlmProvider::new(Some(&llm_config));
+
+        let mut config = Config::default();
+        config.llm = Some(llm_config.clone());
+        config.processing.retry_max_attempts = 3;
+        config.processing.retry_base_delay_ms = 10;
+        let (pipeline, backend, conn, _temp_dir) = test_pipeline(&config, llm).await;
+
+        let mut doc = Document::new("doc-retry-transient".to_string());
+        doc.content = Some("Grocery list: eggs, milk, bread".to_string());
         doc.container_tags = vec!["user-123".to_string()];
 
         DocumentRepository::create(&conn, &doc)
@@ -809,109 +1916,384 @@ mod tests {
             .expect("failed to get document")
             .expect("document should exist");
 
+        // The skip decision only comes from the third response, so reaching it
+        // means both 503s were retried instead of falling back to Include.
         assert_eq!(updated_doc.status, ProcessingStatus::Done);
-        assert!(updated_doc.error_message.is_some());
-        assert!(updated_doc.error_message.unwrap().contains("Filtered:"));
-        assert_eq!(updated_doc.chunk_count, 0);
+        assert!(updated_doc
+            .error_message
+            .is_some_and(|message| message.contains("Not technical")));
+
+        let requests = mock_server.received_requests().await.unwrap_or_default();
+        assert_eq!(requests.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_generates_summary_for_long_documents() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(llm_response("A guide to ownership and borrowing in Rust."))
+                    .set_delay(std::time::Duration::from_millis(300)),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let llm_config = test_llm_config(mock_server.uri());
+
+        let llm = LlmProvider::new(Some(&llm_config));
+
+        let mut config = Config::default();
+        config.llm = Some(llm_config);
+        config.processing.auto_summary = true;
+        config.processing.auto_summary_min_chars = 100;
+        config.processing.record_stage_timings = true;
+        let (pipeline, backend, conn, _temp_dir) = test_pipeline(&config, llm).await;
+
+        let mut long_doc = Document::new("doc-summary-long".to_string());
+        long_doc.content = Some("Rust ownership rules keep memory safe. ".repeat(10));
+        let mut short_doc = Document::new("doc-summary-short".to_string());
+        short_doc.content = Some("Short note about Rust".to_string());
+
+        DocumentRepository::create(&conn, &long_doc)
+            .await
+            .expect("failed to create document");
+        DocumentRepository::create(&conn, &short_doc)
+            .await
+            .expect("failed to create document");
+
+        // The document is marked done before the summary call returns.
+        let mut events = pipeline.status_events.subscribe();
+        let summary_when_done = async {
+            while events.recv().await.expect("status event").status != ProcessingStatus::Done {}
+            backend
+                .get_document_by_id(&long_doc.id)
+                .await
+                .expect("failed to get document")
+                .expect("document should exist")
+                .summary
+        };
+        let (long_result, summary_when_done) =
+            tokio::join!(pipeline.process_document(&long_doc.id), summary_when_done);
+        let long_result = long_result.expect("pipeline processing should succeed");
+        assert!(summary_when_done.is_none());
+
+        let short_result = pipeline
+            .process_document(&short_doc.id)
+            .await
+            .expect("pipeline processing should succeed");
+
+        assert!(long_result.summary_generated);
+        assert!(!short_result.summary_generated);
+        assert!(long_result.timings.summary_ms.is_some());
+        assert!(long_result.timings.memory_extraction_ms.is_none());
+
+        let updated_long = backend
+            .get_document_by_id(&long_doc.id)
+            .await
+            .expect("failed to get document")
+            .expect("document should exist");
+        assert_eq!(
+            updated_long.summary.as_deref(),
+            Some("A guide to ownership and borrowing in Rust.")
+        );
+        let stored_timings = &updated_long.metadata[PROCESSING_TIMINGS_METADATA_KEY];
+        assert!(stored_timings["embedding_ms"].is_u64());
+        assert!(stored_timings["total_ms"].as_u64() >= stored_timings["chunking_ms"].as_u64());
+
+        let updated_short = backend
+            .get_document_by_id(&short_doc.id)
+            .await
+            .expect("failed to get document")
+            .expect("document should exist");
+        assert!(updated_short.summary.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_categorizes_documents_without_category() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(llm_response("Recipe")))
+            .mount(&mock_server)
+            .await;
+
+        let llm_config = test_llm_config(mock_server.uri());
+        let llm = LlmProvider::new(Some(&llm_config));
+
+        let mut config = Config::default();
+        config.llm = Some(llm_config);
+        config.processing.categorize_documents = true;
+        let (pipeline, backend, conn, _temp_dir) = test_pipeline(&config, llm).await;
+
+        let mut recipe = Document::new("doc-category-recipe".to_string());
+        recipe.content = Some("Whisk 2 eggs with 1 cup of flour and fry in butter.".to_string());
+        let mut labelled = Document::new("doc-category-labelled".to_string());
+        labelled.content = Some("Agenda: budget review".to_string());
+        labelled.metadata.insert(
+            CATEGORY_METADATA_KEY.to_string(),
+            serde_json::json!("meeting_notes"),
+        );
+        for doc in [&recipe, &labelled] {
+            DocumentRepository::create(&conn, doc)
+                .await
+                .expect("failed to create document");
+        }
+
+        let recipe_result = pipeline
+            .process_document(&recipe.id)
+            .await
+            .expect("pipeline processing should succeed");
+        let labelled_result = pipeline
+            .process_document(&labelled.id)
+            .await
+            .expect("pipeline processing should succeed");
+
+        assert!(recipe_result.timings.categorization_ms.is_some());
+        assert!(labelled_result.timings.categorization_ms.is_none());
+
+        let stored = |id: String| {
+            let backend = backend.clone();
+            async move {
+                backend
+                    .get_document_by_id(&id)
+                    .await
+                    .expect("failed to get document")
+                    .expect("document should exist")
+                    .metadata
+            }
+        };
+        assert_eq!(stored(recipe.id).await[CATEGORY_METADATA_KEY], "recipe");
+        assert_eq!(
+            stored(labelled.id).await[CATEGORY_METADATA_KEY],
+            "meeting_notes"
+        );
+    }
+
+    #[test]
+    fn test_cap_chunk_size_respects_model_limit() {
+        let mut processing = Config::default().processing;
+        processing.chunk_size = 1000;
+        processing.chunk_overlap = 400;
+
+        let capped = ProcessingPipeline::cap_chunk_size(&processing, 512);
+        assert_eq!(capped.chunk_size, 512);
+        assert_eq!(capped.chunk_overlap, 256);
+
+        let untouched = ProcessingPipeline::cap_chunk_size(&capped, 8192);
+        assert_eq!(untouched.chunk_size, 512);
+        assert_eq!(untouched.chunk_overlap, 256);
+    }
+
+    #[test]
+    fn test_stage_timings_omit_stages_that_did_not_run() {
+        let timings = StageTimings {
+            extraction_ms: 120,
+            chunking_ms: 4,
+            embedding_ms: 80,
+            total_ms: 210,
+            ..Default::default()
+        };
+
+        let value = serde_json::to_value(&timings).unwrap();
+
+        assert_eq!(value["extraction_ms"], 120);
+        assert!(value.get("filter_ms").is_none());
+        assert!(value.get("summary_ms").is_none());
+        assert!(value.get("memory_extraction_ms").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_embed_in_batches_preserves_order_under_concurrency() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let contents: Vec<String> = (0..10).map(|i| i.to_string()).collect();
+
+        let embeddings = embed_in_batches(contents, 3, 2, |batch| {
+            let in_flight = in_flight.clone();
+            let peak = peak.clone();
+            async move {
+                let running = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(running, Ordering::SeqCst);
+                // Later batches finish first.
+                let first: u64 = batch[0].parse().unwrap();
+                tokio::time::sleep(std::time::Duration::from_millis(50 - first * 5)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok(batch
+                    .iter()
+                    .map(|content| vec![content.parse::<f32>().unwrap()])
+                    .collect())
+            }
+        })
+        .await
+        .unwrap();
+
+        let expected: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32]).collect();
+        assert_eq!(embeddings, expected);
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_embed_in_batches_rejects_missing_embeddings() {
+        let contents = vec!["a".to_string(), "b".to_string()];
+        let result = embed_in_batches(contents, 2, 1, |_| async { Ok(vec![vec![0.0]]) }).await;
+        assert!(matches!(result, Err(MomoError::Embedding(_))));
     }
 
-    #[tokio::test]
-    async fn test_pipeline_filter_integration_include() {
-        let mock_server = MockServer::start().await;
+    #[test]
+    fn test_record_extraction_confidence_flags_low_confidence() {
+        let mut metadata = Metadata::new();
+        let confidence = ExtractionConfidence {
+            average: 0.42,
+            minimum: 0.1,
+        };
 
-        let embedding = vec![0.1_f32; 384];
-        Mock::given(method("POST"))
-            .and(path("/embeddings"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-                "data": [{"embedding": embedding}]
-            })))
-            .mount(&mock_server)
-            .await;
+        assert!(record_extraction_confidence(
+            &mut metadata,
+            Some(confidence),
+            Some(0.6)
+        ));
+        assert_eq!(
+            metadata[EXTRACTION_CONFIDENCE_METADATA_KEY],
+            json!({"average": 0.42f32, "minimum": 0.1f32})
+        );
+        assert_eq!(metadata[LOW_CONFIDENCE_METADATA_KEY], json!(true));
 
-        Mock::given(method("POST"))
-            .and(path("/chat/completions"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(llm_response(
-                r#"{"decision":"include","reasoning":"Content matches technical criteria"}"#,
-            )))
-            .mount(&mock_server)
-            .await;
+        assert!(!record_extraction_confidence(
+            &mut metadata,
+            Some(confidence),
+            None
+        ));
+        assert!(!metadata.contains_key(LOW_CONFIDENCE_METADATA_KEY));
 
-        let embeddings_config = EmbeddingsConfig {
-            model: "BAAI/bge-small-en-v1.5".to_string(),
-            dimensions: 384,
-            batch_size: 8,
-        };
+        assert!(!record_extraction_confidence(
+            &mut metadata,
+            None,
+            Some(0.6)
+        ));
+        assert!(!metadata.contains_key(EXTRACTION_CONFIDENCE_METADATA_KEY));
+    }
 
-        let embeddings = EmbeddingProvider::new(&embeddings_config)
-            .expect("failed to create embeddings provider");
+    #[test]
+    fn test_record_transcript_language_populates_metadata() {
+        let mut metadata = Metadata::new();
+        record_transcript_language(
+            &mut metadata,
+            Some(TranscriptLanguage {
+                code: "de".to_string(),
+                confidence: Some(0.75),
+                fallback: false,
+            }),
+        );
+        assert_eq!(
+            metadata[TRANSCRIPT_LANGUAGE_METADATA_KEY],
+            json!({"code": "de", "confidence": 0.75f32, "fallback": false})
+        );
 
-        let llm_config = LlmConfig {
-            model: "openai/gpt-4o-mini".to_string(),
-            api_key: Some("test-key".to_string()),
-            base_url: Some(mock_server.uri()),
-            timeout_secs: 5,
-            max_retries: 0,
-            enable_query_rewrite: false,
-            query_rewrite_cache_size: 1000,
-            query_rewrite_timeout_secs: 2,
-            enable_auto_relations: false,
-            enable_contradiction_detection: false,
-            filter_prompt: Some("technical documents only".to_string()),
-        };
+        record_transcript_language(&mut metadata, None);
+        assert!(!metadata.contains_key(TRANSCRIPT_LANGUAGE_METADATA_KEY));
+    }
 
-        let llm = LlmProvider::new(Some(&llm_config));
+    #[test]
+    fn test_document_overview_prefers_summary_over_leading_text() {
+        let text = "x".repeat(CONTEXTUAL_OVERVIEW_MAX_CHARS + 100);
 
-        let mut config = Config::default();
-        config.llm = Some(llm_config.clone());
-        let ocr = OcrProvider::new(&config.ocr).expect("failed to create ocr provider");
-        let transcription = TranscriptionProvider::new(&config.transcription)
-            .expect("failed to create transcription provider");
+        let with_summary = document_overview(Some("Rust Guide"), Some("About ownership."), &text);
+        assert_eq!(with_summary, "Title: Rust Guide\nSummary: About ownership.");
 
-        let temp_dir = tempdir().expect("failed to create temp dir");
-        let db_path = temp_dir.path().join("test_filter_include.db");
-        let db_config = DatabaseConfig {
-            url: format!("file:{}", db_path.display()),
-            auth_token: None,
-            local_path: None,
-        };
+        let without_summary = document_overview(None, Some("  "), &text);
+        assert!(without_summary.starts_with("Beginning of document:\n"));
+        assert_eq!(
+            without_summary.chars().filter(|c| *c == 'x').count(),
+            CONTEXTUAL_OVERVIEW_MAX_CHARS
+        );
+    }
 
-        let db = Database::new(&db_config)
-            .await
-            .expect("failed to create database");
-        let backend: Arc<dyn DatabaseBackend> = Arc::new(LibSqlBackend::new(db.clone()));
+    #[test]
+    fn test_surrounding_text_uses_neighbouring_chunks() {
+        let chunk = |content: &str| Chunk {
+            id: nanoid!(),
+            document_id: "doc".to_string(),
+            content: content.to_string(),
+            embedded_content: None,
+            position: 0,
+            token_count: None,
+            created_at: Utc::now(),
+            chunker: None,
+            language: None,
+        };
+        let chunks = vec![chunk("first"), chunk("second"), chunk("third")];
+
+        assert_eq!(surrounding_text(&chunks, 0), "second");
+        assert_eq!(surrounding_text(&chunks, 1), "first\n...\nthird");
+        assert_eq!(surrounding_text(&chunks, 2), "second");
+        assert_eq!(
+            contextualized_content("From the Rust guide.", "second"),
+            "From the Rust guide.\n\nsecond"
+        );
+    }
 
-        let pipeline = ProcessingPipeline::new(
-            backend.clone(),
-            embeddings,
-            ocr,
-            transcription,
-            llm,
-            &config,
+    #[tokio::test]
+    async fn test_find_near_duplicate_matches_near_identical_document() {
+        let mut config = Config::default();
+        config.llm = None;
+        config.processing.near_duplicate_threshold = Some(0.9);
+        config.processing.near_duplicate_action = NearDuplicateAction::Reject;
+        let (pipeline, _, conn, _temp_dir) = test_pipeline(&config, LlmProvider::new(None)).await;
+        assert_eq!(
+            pipeline.near_duplicate_action(),
+            NearDuplicateAction::Reject
         );
 
-        let conn = db.connect().expect("failed to connect to database");
-        let mut doc = Document::new("doc-filter-include".to_string());
-        doc.content = Some("This is technical content about Rust programming".to_string());
-        doc.container_tags = vec!["user-123".to_string()];
+        let original = "Quarterly report: revenue grew 12% driven by enterprise renewals. \
+            Churn fell to 3% after the onboarding redesign. Hiring remains paused until Q3.";
+        let edited = "Quarterly report: revenue grew 12% driven by enterprise renewals. \
+            Churn fell to 3% after the onboarding redesign. Hiring remains paused until Q4.";
 
+        let mut doc = Document::new("doc-original".to_string());
+        doc.content = Some(original.to_string());
+        doc.container_tags = vec!["team-a".to_string()];
         DocumentRepository::create(&conn, &doc)
             .await
             .expect("failed to create document");
-
         pipeline
             .process_document(&doc.id)
             .await
             .expect("pipeline processing should succeed");
 
-        let updated_doc = backend
-            .get_document_by_id(&doc.id)
+        let duplicate = pipeline
+            .find_near_duplicate(edited, &["team-a".to_string()])
             .await
-            .expect("failed to get document")
-            .expect("document should exist");
+            .expect("near-duplicate check should succeed")
+            .expect("edited document should match the original");
+        assert_eq!(duplicate.document_id, "doc-original");
+        assert!(duplicate.similarity >= 0.9);
 
-        assert_eq!(updated_doc.status, ProcessingStatus::Done);
-        assert!(updated_doc.error_message.is_none());
-        assert!(updated_doc.chunk_count > 0);
+        let other_container = pipeline
+            .find_near_duplicate(edited, &["team-b".to_string()])
+            .await
+            .expect("near-duplicate check should succeed");
+        assert!(other_container.is_none());
+
+        let untagged = pipeline
+            .find_near_duplicate(edited, &[])
+            .await
+            .expect("near-duplicate check should succeed");
+        assert!(untagged.is_none());
+
+        let unrelated = pipeline
+            .find_near_duplicate(
+                "Recipe: whisk two eggs with flour and milk, then fry in butter.",
+                &["team-a".to_string()],
+            )
+            .await
+            .expect("near-duplicate check should succeed");
+        assert!(unrelated.is_none());
     }
 
     #[tokio::test]
@@ -927,60 +2309,14 @@ mod tests {
             .mount(&mock_server)
             .await;
 
-        let embeddings_config = EmbeddingsConfig {
-            model: "BAAI/bge-small-en-v1.5".to_string(),
-            dimensions: 384,
-            batch_size: 8,
-        };
-
-        let embeddings = EmbeddingProvider::new(&embeddings_config)
-            .expect("failed to create embeddings provider");
-
-        let llm_config = LlmConfig {
-            model: "openai/gpt-4o-mini".to_string(),
-            api_key: Some("test-key".to_string()),
-            base_url: Some(mock_server.uri()),
-            timeout_secs: 5,
-            max_retries: 0,
-            enable_query_rewrite: false,
-            query_rewrite_cache_size: 1000,
-            query_rewrite_timeout_secs: 2,
-            enable_auto_relations: false,
-            enable_contradiction_detection: false,
-            filter_prompt: None,
-        };
+        let llm_config = test_llm_config(mock_server.uri());
 
         let llm = LlmProvider::new(Some(&llm_config));
 
         let mut config = Config::default();
         config.llm = Some(llm_config.clone());
-        let ocr = OcrProvider::new(&config.ocr).expect("failed to create ocr provider");
-        let transcription = TranscriptionProvider::new(&config.transcription)
-            .expect("failed to create transcription provider");
-
-        let temp_dir = tempdir().expect("failed to create temp dir");
-        let db_path = temp_dir.path().join("test_filter_disabled.db");
-        let db_config = DatabaseConfig {
-            url: format!("file:{}", db_path.display()),
-            auth_token: None,
-            local_path: None,
-        };
-
-        let db = Database::new(&db_config)
-            .await
-            .expect("failed to create database");
-        let backend: Arc<dyn DatabaseBackend> = Arc::new(LibSqlBackend::new(db.clone()));
+        let (pipeline, backend, conn, _temp_dir) = test_pipeline(&config, llm).await;
 
-        let pipeline = ProcessingPipeline::new(
-            backend.clone(),
-            embeddings,
-            ocr,
-            transcription,
-            llm,
-            &config,
-        );
-
-        let conn = db.connect().expect("failed to connect to database");
         let mut doc = Document::new("doc-no-filter".to_string());
         doc.content = Some("Any content should be included".to_string());
         doc.container_tags = vec!["user-123".to_string()];
@@ -1018,46 +2354,12 @@ mod tests {
             .mount(&mock_server)
             .await;
 
-        let embeddings_config = EmbeddingsConfig {
-            model: "BAAI/bge-small-en-v1.5".to_string(),
-            dimensions: 384,
-            batch_size: 8,
-        };
-
-        let embeddings = EmbeddingProvider::new(&embeddings_config)
-            .expect("failed to create embeddings provider");
-
         let llm = LlmProvider::unavailable("test unavailable");
 
         let mut config = Config::default();
         config.llm = None;
-        let ocr = OcrProvider::new(&config.ocr).expect("failed to create ocr provider");
-        let transcription = TranscriptionProvider::new(&config.transcription)
-            .expect("failed to create transcription provider");
-
-        let temp_dir = tempdir().expect("failed to create temp dir");
-        let db_path = temp_dir.path().join("test_filter_no_llm.db");
-        let db_config = DatabaseConfig {
-            url: format!("file:{}", db_path.display()),
-            auth_token: None,
-            local_path: None,
-        };
-
-        let db = Database::new(&db_config)
-            .await
-            .expect("failed to create database");
-        let backend: Arc<dyn DatabaseBackend> = Arc::new(LibSqlBackend::new(db.clone()));
-
-        let pipeline = ProcessingPipeline::new(
-            backend.clone(),
-            embeddings,
-            ocr,
-            transcription,
-            llm,
-            &config,
-        );
+        let (pipeline, backend, conn, _temp_dir) = test_pipeline(&config, llm).await;
 
-        let conn = db.connect().expect("failed to connect to database");
         let mut doc = Document::new("doc-no-llm".to_string());
         doc.content = Some("Any content should be included when LLM unavailable".to_string());
         doc.container_tags = vec!["user-123".to_string()];
@@ -1112,60 +2414,16 @@ mod tests {
             .mount(&mock_server)
             .await;
 
-        let embeddings_config = EmbeddingsConfig {
-            model: "BAAI/bge-small-en-v1.5".to_string(),
-            dimensions: 384,
-            batch_size: 8,
-        };
-
-        let embeddings = EmbeddingProvider::new(&embeddings_config)
-            .expect("failed to create embeddings provider");
-
         let llm_config = LlmConfig {
-            model: "openai/gpt-4o-mini".to_string(),
-            api_key: Some("test-key".to_string()),
-            base_url: Some(mock_server.uri()),
-            timeout_secs: 5,
-            max_retries: 0,
-            enable_query_rewrite: false,
-            query_rewrite_cache_size: 1000,
-            query_rewrite_timeout_secs: 2,
-            enable_auto_relations: false,
-            enable_contradiction_detection: false,
             filter_prompt: Some("technical documents only".to_string()),
+            ..test_llm_config(mock_server.uri())
         };
 
         let llm = LlmProvider::new(Some(&llm_config));
 
         let mut config = Config::default();
         config.llm = Some(llm_config.clone());
-        let ocr = OcrProvider::new(&config.ocr).expect("failed to create ocr provider");
-        let transcription = TranscriptionProvider::new(&config.transcription)
-            .expect("failed to create transcription provider");
-
-        let temp_dir = tempdir().expect("failed to create temp dir");
-        let db_path = temp_dir.path().join("test_filter_multiple.db");
-        let db_config = DatabaseConfig {
-            url: format!("file:{}", db_path.display()),
-            auth_token: None,
-            local_path: None,
-        };
-
-        let db = Database::new(&db_config)
-            .await
-            .expect("failed to create database");
-        let backend: Arc<dyn DatabaseBackend> = Arc::new(LibSqlBackend::new(db.clone()));
-
-        let pipeline = ProcessingPipeline::new(
-            backend.clone(),
-            embeddings,
-            ocr,
-            transcription,
-            llm,
-            &config,
-        );
-
-        let conn = db.connect().expect("failed to connect to database");
+        let (pipeline, backend, conn, _temp_dir) = test_pipeline(&config, llm).await;
 
         let mut doc1 = Document::new("doc-multi-1".to_string());
         doc1.content = Some("Marketing content about our amazing products".to_string());
@@ -1232,39 +2490,10 @@ mod tests {
 
         let config = Config::default();
 
-        let embeddings_config = EmbeddingsConfig {
-            model: "BAAI/bge-small-en-v1.5".to_string(),
-            dimensions: 384,
-            batch_size: 8,
-        };
-        let embeddings = EmbeddingProvider::new(&embeddings_config)
-            .expect("failed to create embedding provider");
-
-        let ocr = OcrProvider::new(&config.ocr).expect("failed to create ocr provider");
         let transcription = TranscriptionProvider::unavailable("test unavailable");
         let llm = LlmProvider::unavailable("test unavailable");
-
-        let temp_dir = tempdir().expect("failed to create temp dir");
-        let db_path = temp_dir.path().join("test_video_unavailable.db");
-        let db_config = DatabaseConfig {
-            url: format!("file:{}", db_path.display()),
-            auth_token: None,
-            local_path: None,
-        };
-
-        let db = Database::new(&db_config)
-            .await
-            .expect("failed to create database");
-        let backend: Arc<dyn DatabaseBackend> = Arc::new(LibSqlBackend::new(db.clone()));
-
-        let pipeline = ProcessingPipeline::new(
-            backend.clone(),
-            embeddings,
-            ocr,
-            transcription,
-            llm,
-            &config,
-        );
+        let (pipeline, _, _, _temp_dir) =
+            test_pipeline_with_transcription(&config, llm, transcription).await;
 
         let video_base64 = base64::Engine::encode(
             &base64::engine::general_purpose::STANDARD,
@@ -1296,39 +2525,10 @@ mod tests {
 
         let config = Config::default();
 
-        let embeddings_config = EmbeddingsConfig {
-            model: "BAAI/bge-small-en-v1.5".to_string(),
-            dimensions: 384,
-            batch_size: 8,
-        };
-        let embeddings = EmbeddingProvider::new(&embeddings_config)
-            .expect("failed to create embedding provider");
-
-        let ocr = OcrProvider::new(&config.ocr).expect("failed to create ocr provider");
         let transcription = TranscriptionProvider::unavailable("test");
         let llm = LlmProvider::unavailable("test unavailable");
-
-        let temp_dir = tempdir().expect("failed to create temp dir");
-        let db_path = temp_dir.path().join("test_video_invalid_base64.db");
-        let db_config = DatabaseConfig {
-            url: format!("file:{}", db_path.display()),
-            auth_token: None,
-            local_path: None,
-        };
-
-        let db = Database::new(&db_config)
-            .await
-            .expect("failed to create database");
-        let backend: Arc<dyn DatabaseBackend> = Arc::new(LibSqlBackend::new(db.clone()));
-
-        let pipeline = ProcessingPipeline::new(
-            backend.clone(),
-            embeddings,
-            ocr,
-            transcription,
-            llm,
-            &config,
-        );
+        let (pipeline, _, _, _temp_dir) =
+            test_pipeline_with_transcription(&config, llm, transcription).await;
 
         let invalid_base64 = "not-valid-base64!!!";
 
@@ -1350,43 +2550,12 @@ mod tests {
             .mount(&mock_server)
             .await;
 
-        let embeddings_config = EmbeddingsConfig {
-            model: "BAAI/bge-small-en-v1.5".to_string(),
-            dimensions: 384,
-            batch_size: 8,
-        };
-
-        let embeddings = EmbeddingProvider::new(&embeddings_config)
-            .expect("failed to create embeddings provider");
-
         let config = Config::default();
-        let ocr = OcrProvider::new(&config.ocr).expect("failed to create ocr provider");
         let transcription = TranscriptionProvider::unavailable("test - integration test");
         let llm = LlmProvider::unavailable("test unavailable");
+        let (pipeline, backend, conn, _temp_dir) =
+            test_pipeline_with_transcription(&config, llm, transcription).await;
 
-        let temp_dir = tempdir().expect("failed to create temp dir");
-        let db_path = temp_dir.path().join("test_video_e2e.db");
-        let db_config = DatabaseConfig {
-            url: format!("file:{}", db_path.display()),
-            auth_token: None,
-            local_path: None,
-        };
-
-        let db = Database::new(&db_config)
-            .await
-            .expect("failed to create database");
-        let backend: Arc<dyn DatabaseBackend> = Arc::new(LibSqlBackend::new(db.clone()));
-
-        let pipeline = ProcessingPipeline::new(
-            backend.clone(),
-            embeddings,
-            ocr,
-            transcription,
-            llm,
-            &config,
-        );
-
-        let conn = db.connect().expect("failed to connect to database");
         let mut doc = Document::new("video-doc-e2e".to_string());
         doc.doc_type = DocumentType::Video;
         doc.content = Some(base64::Engine::encode(
@@ -1420,4 +2589,39 @@ mod tests {
             "Error message should indicate transcription unavailability: {error_msg}"
         );
     }
+
+    #[tokio::test]
+    async fn test_job_limiter_caps_concurrent_jobs() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let limiter = JobLimiter::new("OCR", 2);
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..6)
+            .map(|i| {
+                let limiter = limiter.clone();
+                let running = Arc::clone(&running);
+                let peak = Arc::clone(&peak);
+                tokio::spawn(async move {
+                    let _permit = limiter.acquire(&format!("doc-{i}")).await;
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_job_limiter_zero_is_unlimited() {
+        let limiter = JobLimiter::new("transcription", 0);
+        assert!(limiter.acquire("doc-1").await.is_none());
+    }
 }
@@ -0,0 +1,144 @@
+use std::future::Future;
+use std::time::Duration;
+
+use crate::config::ProcessingConfig;
+use crate::error::Result;
+
+/// Retries transient embedding/LLM failures with exponential backoff.
+///
+/// Only errors for which [`MomoError::is_retryable`] holds are retried; the
+/// last error is returned once `max_attempts` is reached.
+///
+/// [`MomoError::is_retryable`]: crate::error::MomoError::is_retryable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first one. `1` disables retrying.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled for every further retry.
+    pub base_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// A policy that makes a single attempt.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            base_delay: Duration::ZERO,
+        }
+    }
+
+    pub fn from_config(config: &ProcessingConfig) -> Self {
+        Self {
+            max_attempts: config.retry_max_attempts.max(1),
+            base_delay: Duration::from_millis(config.retry_base_delay_ms),
+        }
+    }
+
+    fn delay_before(&self, attempt: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2_u32.saturating_pow(attempt.saturating_sub(2)))
+    }
+
+    /// Run `operation` until it succeeds, fails permanently, or runs out of attempts.
+    pub async fn run<T, F, Fut>(&self, name: &str, mut operation: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match operation().await {
+                Err(error) if error.is_retryable() && attempt < self.max_attempts => {
+                    attempt += 1;
+                    let delay = self.delay_before(attempt);
+                    tracing::warn!(
+                        operation = name,
+                        attempt,
+                        max_attempts = self.max_attempts,
+                        delay_ms = delay.as_millis() as u64,
+                        error = %error,
+                        "Transient failure, retrying"
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+    use crate::error::MomoError;
+
+    fn policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_transient_errors_until_success() {
+        let calls = AtomicU32::new(0);
+        let result = policy(3)
+            .run("test", || async {
+                match calls.fetch_add(1, Ordering::SeqCst) {
+                    0 | 1 => Err(MomoError::LlmTransient("503".to_string())),
+                    _ => Ok("done"),
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), "done");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let calls = AtomicU32::new(0);
+        let result: Result<()> = policy(2)
+            .run("test", || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(MomoError::LlmRateLimit { retry_after: None })
+            })
+            .await;
+
+        assert!(matches!(result, Err(MomoError::LlmRateLimit { .. })));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_permanent_errors() {
+        let calls = AtomicU32::new(0);
+        let result: Result<()> = policy(5)
+            .run("test", || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(MomoError::Validation("invalid input".to_string()))
+            })
+            .await;
+
+        assert!(matches!(result, Err(MomoError::Validation(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn delay_doubles_per_retry() {
+        let policy = RetryPolicy {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(100),
+        };
+        assert_eq!(policy.delay_before(2), Duration::from_millis(100));
+        assert_eq!(policy.delay_before(3), Duration::from_millis(200));
+        assert_eq!(policy.delay_before(4), Duration::from_millis(400));
+    }
+}
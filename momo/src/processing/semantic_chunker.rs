@@ -0,0 +1,250 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::config::ProcessingConfig;
+use crate::embeddings::EmbeddingProvider;
+use crate::error::{MomoError, Result};
+use crate::models::ChunkerType;
+use crate::search::mmr::cosine_similarity;
+
+use super::chunker::count_chunk_tokens;
+use super::{ContentChunker, TextChunk, TextChunker, TokenCounter};
+
+/// Embeds the sentences the [`SemanticChunker`] compares.
+#[async_trait]
+pub trait SentenceEmbedder: Send + Sync {
+    async fn embed_sentences(&self, sentences: Vec<String>) -> Result<Vec<Vec<f32>>>;
+}
+
+#[async_trait]
+impl SentenceEmbedder for EmbeddingProvider {
+    async fn embed_sentences(&self, sentences: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        self.embed_passages(sentences).await
+    }
+}
+
+/// Splits prose where the topic shifts: a new chunk starts when the cosine
+/// distance between consecutive sentence embeddings exceeds the threshold.
+///
+/// Chunks never grow past `chunk_size`, and breakpoints are ignored until a
+/// chunk reaches `semantic_chunk_min_size`. Unlike the other chunkers this one
+/// is async, since it has to embed every sentence.
+pub struct SemanticChunker {
+    embedder: Arc<dyn SentenceEmbedder>,
+    /// Splits text into sentences, and sentences too long for one chunk by size.
+    splitter: TextChunker,
+    breakpoint_threshold: f32,
+    min_chunk_size: usize,
+    max_chunk_size: usize,
+    token_counter: Option<Arc<dyn TokenCounter>>,
+}
+
+impl SemanticChunker {
+    pub fn new(config: &ProcessingConfig, embedder: Arc<dyn SentenceEmbedder>) -> Self {
+        Self {
+            embedder,
+            splitter: TextChunker::new(config),
+            breakpoint_threshold: config.semantic_chunk_threshold,
+            min_chunk_size: config.semantic_chunk_min_size,
+            max_chunk_size: config.chunk_size,
+            token_counter: None,
+        }
+    }
+
+    /// Measure chunk sizes with the given tokenizer instead of byte length.
+    pub fn with_token_counter(self, counter: Arc<dyn TokenCounter>) -> Self {
+        Self {
+            splitter: self.splitter.with_token_counter(counter.clone()),
+            token_counter: Some(counter),
+            ..self
+        }
+    }
+
+    /// A chunker with the same embedder, sized by `config`. The token counter
+    /// is not carried over.
+    pub fn with_config(&self, config: &ProcessingConfig) -> Self {
+        Self::new(config, Arc::clone(&self.embedder))
+    }
+
+    pub fn chunker_type(&self) -> ChunkerType {
+        ChunkerType::Semantic
+    }
+
+    pub async fn chunk(&self, text: &str) -> Result<Vec<TextChunk>> {
+        let sentences = self.splitter.split_into_sentences(text);
+        if sentences.len() < 2 {
+            return Ok(self.group(sentences, &[]));
+        }
+
+        let embeddings = self.embedder.embed_sentences(sentences.clone()).await?;
+        if embeddings.len() != sentences.len() {
+            return Err(MomoError::Embedding(format!(
+                "Expected {} sentence embeddings, got {}",
+                sentences.len(),
+                embeddings.len()
+            )));
+        }
+
+        Ok(self.group(sentences, &embeddings))
+    }
+
+    fn group(&self, sentences: Vec<String>, embeddings: &[Vec<f32>]) -> Vec<TextChunk> {
+        let mut chunks = Vec::new();
+        let mut current = String::new();
+
+        for (i, sentence) in sentences.into_iter().enumerate() {
+            if self.measure(&sentence) > self.max_chunk_size {
+                self.flush(&mut current, &mut chunks);
+                chunks.extend(self.splitter.chunk(&sentence, None));
+                continue;
+            }
+
+            if !current.is_empty() {
+                let topic_shift = i
+                    .checked_sub(1)
+                    .and_then(|prev| embeddings.get(prev).zip(embeddings.get(i)))
+                    .is_some_and(|(prev, next)| {
+                        1.0 - cosine_similarity(prev, next) > self.breakpoint_threshold
+                    });
+                let too_big = self.measure(&format!("{current} {sentence}")) > self.max_chunk_size;
+                if too_big || (topic_shift && self.measure(&current) >= self.min_chunk_size) {
+                    self.flush(&mut current, &mut chunks);
+                }
+            }
+
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(&sentence);
+        }
+
+        self.flush(&mut current, &mut chunks);
+        chunks
+    }
+
+    fn flush(&self, current: &mut String, chunks: &mut Vec<TextChunk>) {
+        if current.is_empty() {
+            return;
+        }
+        let content = std::mem::take(current);
+        chunks.push(TextChunk {
+            token_count: count_chunk_tokens(self.token_counter.as_deref(), &content),
+            content,
+        });
+    }
+
+    fn measure(&self, text: &str) -> usize {
+        match &self.token_counter {
+            Some(counter) => counter.count_tokens(text),
+            None => text.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        ChunkStrategy, NearDuplicateAction, PiiAction, PiiCategory, StructuredDataOverflow,
+    };
+
+    /// Embeds each sentence along the axes of the topic keywords it mentions.
+    struct TopicEmbedder;
+
+    #[async_trait]
+    impl SentenceEmbedder for TopicEmbedder {
+        async fn embed_sentences(&self, sentences: Vec<String>) -> Result<Vec<Vec<f32>>> {
+            Ok(sentences
+                .iter()
+                .map(|sentence| {
+                    let lower = sentence.to_lowercase();
+                    ["cat", "rust", "bread"]
+                        .iter()
+                        .map(|topic| if lower.contains(topic) { 1.0 } else { 0.0 })
+                        .collect()
+                })
+                .collect())
+        }
+    }
+
+    fn config(chunk_size: usize, min_size: usize) -> ProcessingConfig {
+        ProcessingConfig {
+            chunk_size,
+            chunk_overlap: 0,
+            chunk_strategy: ChunkStrategy::Semantic,
+            semantic_chunk_threshold: 0.5,
+            semantic_chunk_min_size: min_size,
+            use_model_tokenizer: false,
+            auto_summary: false,
+            auto_summary_min_chars: 2000,
+            contextual_chunks: false,
+            near_duplicate_threshold: None,
+            near_duplicate_action: NearDuplicateAction::Warn,
+            record_stage_timings: false,
+            low_confidence_threshold: None,
+            chunk_reembed_interval_secs: 0,
+            chunk_reembed_batch_size: 64,
+            categorize_documents: false,
+            structured_data_max_chunks: 0,
+            structured_data_overflow: StructuredDataOverflow::Sample,
+            pii_detection: false,
+            pii_categories: PiiCategory::ALL.to_vec(),
+            pii_action: PiiAction::Redact,
+            retry_max_attempts: 3,
+            retry_base_delay_ms: 500,
+            dead_letter_after_failures: 5,
+        }
+    }
+
+    fn chunker(chunk_size: usize, min_size: usize) -> SemanticChunker {
+        SemanticChunker::new(&config(chunk_size, min_size), Arc::new(TopicEmbedder))
+    }
+
+    const THREE_TOPICS: &str = "My cat sleeps all day. The cat purrs loudly. \
+        Rust has a borrow checker. Rust compiles to native code. \
+        Bread needs yeast. Bread rises overnight.";
+
+    #[tokio::test]
+    async fn test_topic_shifts_start_new_chunks() {
+        let chunks = chunker(1000, 0).chunk(THREE_TOPICS).await.unwrap();
+        let contents: Vec<&str> = chunks.iter().map(|c| c.content.as_str()).collect();
+
+        assert_eq!(
+            contents,
+            vec![
+                "My cat sleeps all day. The cat purrs loudly.",
+                "Rust has a borrow checker. Rust compiles to native code.",
+                "Bread needs yeast. Bread rises overnight.",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_min_size_suppresses_early_breakpoints() {
+        let chunks = chunker(1000, 60).chunk(THREE_TOPICS).await.unwrap();
+
+        // The cat chunk is too small to close at the first shift, so it runs on
+        // through the Rust sentences until the next shift.
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks[0].content.starts_with("My cat"));
+        assert!(chunks[0].content.ends_with("Rust compiles to native code."));
+        assert!(chunks[1].content.starts_with("Bread"));
+    }
+
+    #[tokio::test]
+    async fn test_max_size_splits_within_a_topic() {
+        let text = "The cat naps. The cat eats. The cat plays. The cat sleeps.";
+        let chunks = chunker(30, 0).chunk(text).await.unwrap();
+
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|c| c.content.len() <= 30));
+    }
+
+    #[tokio::test]
+    async fn test_single_sentence_skips_embedding() {
+        let chunks = chunker(1000, 0).chunk("Just one sentence").await.unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert!(chunker(1000, 0).chunk("").await.unwrap().is_empty());
+    }
+}
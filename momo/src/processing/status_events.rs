@@ -0,0 +1,82 @@
+use tokio::sync::broadcast;
+
+use crate::models::ProcessingStatus;
+
+/// Events buffered per subscriber before slow receivers start missing some.
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// A document moved to a new processing status.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentStatusEvent {
+    pub document_id: String,
+    pub status: ProcessingStatus,
+    pub error_message: Option<String>,
+}
+
+impl DocumentStatusEvent {
+    /// Whether processing has finished and no further events follow.
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self.status,
+            ProcessingStatus::Done | ProcessingStatus::Failed | ProcessingStatus::DeadLetter
+        )
+    }
+}
+
+/// Broadcasts status changes from the pipeline to any number of listeners.
+///
+/// Publishing never blocks and is a no-op when nobody is subscribed.
+#[derive(Clone)]
+pub struct StatusEvents {
+    sender: broadcast::Sender<DocumentStatusEvent>,
+}
+
+impl StatusEvents {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn publish(&self, document_id: &str, status: ProcessingStatus, error: Option<&str>) {
+        // Only fails when there are no receivers.
+        let _ = self.sender.send(DocumentStatusEvent {
+            document_id: document_id.to_string(),
+            status,
+            error_message: error.map(str::to_string),
+        });
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<DocumentStatusEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for StatusEvents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn subscribers_receive_published_events() {
+        let events = StatusEvents::new();
+        events.publish("ignored", ProcessingStatus::Chunking, None);
+
+        let mut rx = events.subscribe();
+        events.publish("doc1", ProcessingStatus::Embedding, None);
+        events.publish("doc1", ProcessingStatus::Failed, Some("boom"));
+
+        let first = rx.recv().await.unwrap();
+        assert_eq!(first.document_id, "doc1");
+        assert_eq!(first.status, ProcessingStatus::Embedding);
+        assert!(!first.is_final());
+
+        let last = rx.recv().await.unwrap();
+        assert_eq!(last.error_message.as_deref(), Some("boom"));
+        assert!(last.is_final());
+    }
+}
@@ -1,16 +1,59 @@
+use crate::models::{ChunkerType, StructuredDataOverflow, StructuredDataOverflowReport};
+
 use super::{ChunkContext, ContentChunker, TextChunk};
 
 /// Chunker for structured data formats like CSV and XLSX.
 /// Preserves header rows in each chunk for context.
 pub struct StructuredDataChunker {
     rows_per_chunk: usize,
+    /// Most chunks per document; 0 means unlimited.
+    max_chunks: usize,
+    overflow: StructuredDataOverflow,
 }
 
 impl StructuredDataChunker {
     /// Create a new chunker with specified rows per chunk
     #[allow(dead_code)]
     pub fn new(rows_per_chunk: usize) -> Self {
-        Self { rows_per_chunk }
+        Self {
+            rows_per_chunk,
+            ..Self::default()
+        }
+    }
+
+    /// Cap the chunks produced per document, handling extra rows with `overflow`.
+    /// A `max_chunks` of 0 means unlimited.
+    pub fn with_max_chunks(self, max_chunks: usize, overflow: StructuredDataOverflow) -> Self {
+        Self {
+            max_chunks,
+            overflow,
+            ..self
+        }
+    }
+
+    /// What will be indexed of `text` when it has more rows than fit in the
+    /// chunk limit; `None` when every row fits.
+    pub fn overflow(&self, text: &str) -> Option<StructuredDataOverflowReport> {
+        let total_rows = text.lines().count().saturating_sub(1);
+        let capacity = self.row_capacity()?;
+        if total_rows <= capacity {
+            return None;
+        }
+
+        let indexed_rows = match self.overflow {
+            StructuredDataOverflow::Aggregate => total_rows,
+            StructuredDataOverflow::Sample | StructuredDataOverflow::Truncate => capacity,
+        };
+        Some(StructuredDataOverflowReport {
+            strategy: self.overflow,
+            total_rows,
+            indexed_rows,
+        })
+    }
+
+    /// Data rows that fit in `max_chunks` chunks, or `None` when unlimited.
+    fn row_capacity(&self) -> Option<usize> {
+        (self.max_chunks > 0).then(|| self.max_chunks.saturating_mul(self.rows_per_chunk))
     }
 
     /// Estimate token count from text length
@@ -19,7 +62,16 @@ impl StructuredDataChunker {
     }
 }
 
+/// `count` rows spread evenly over `rows`, keeping their order.
+fn sample_rows<'a>(rows: &[&'a str], count: usize) -> Vec<&'a str> {
+    (0..count).map(|i| rows[i * rows.len() / count]).collect()
+}
+
 impl ContentChunker for StructuredDataChunker {
+    fn chunker_type(&self) -> ChunkerType {
+        ChunkerType::StructuredData
+    }
+
     fn chunk(&self, text: &str, _context: Option<&ChunkContext>) -> Vec<TextChunk> {
         if text.is_empty() {
             return Vec::new();
@@ -32,7 +84,7 @@ impl ContentChunker for StructuredDataChunker {
 
         // First line is header
         let header = lines.first().copied().unwrap_or("");
-        let data_rows = &lines[1..];
+        let mut data_rows = lines[1..].to_vec();
 
         if data_rows.is_empty() {
             // Only header, return single chunk
@@ -42,9 +94,22 @@ impl ContentChunker for StructuredDataChunker {
             }];
         }
 
+        let mut rows_per_chunk = self.rows_per_chunk;
+        if let Some(report) = self.overflow(text) {
+            match report.strategy {
+                StructuredDataOverflow::Sample => {
+                    data_rows = sample_rows(&data_rows, report.indexed_rows);
+                }
+                StructuredDataOverflow::Truncate => data_rows.truncate(report.indexed_rows),
+                StructuredDataOverflow::Aggregate => {
+                    rows_per_chunk = data_rows.len().div_ceil(self.max_chunks);
+                }
+            }
+        }
+
         let mut chunks = Vec::new();
 
-        for chunk_rows in data_rows.chunks(self.rows_per_chunk) {
+        for chunk_rows in data_rows.chunks(rows_per_chunk) {
             let mut chunk_content = String::new();
             chunk_content.push_str(header);
             chunk_content.push('\n');
@@ -65,7 +130,11 @@ impl ContentChunker for StructuredDataChunker {
 
 impl Default for StructuredDataChunker {
     fn default() -> Self {
-        Self { rows_per_chunk: 50 } // Default 50 rows per chunk
+        Self {
+            rows_per_chunk: 50, // Default 50 rows per chunk
+            max_chunks: 0,
+            overflow: StructuredDataOverflow::default(),
+        }
     }
 }
 
@@ -168,4 +237,67 @@ mod tests {
         let chunks = chunker.chunk(csv_text, Some(&context));
         assert!(!chunks.is_empty());
     }
+
+    fn oversized_csv(rows: usize) -> String {
+        let mut csv = String::from("id,value\n");
+        for i in 0..rows {
+            csv.push_str(&format!("{i},row{i}\n"));
+        }
+        csv
+    }
+
+    fn chunk_rows(chunks: &[TextChunk]) -> Vec<String> {
+        chunks
+            .iter()
+            .flat_map(|chunk| chunk.content.lines().skip(1).map(str::to_string))
+            .collect()
+    }
+
+    #[test]
+    fn test_structured_chunker_oversized_csv_strategies() {
+        let csv = oversized_csv(1000);
+
+        let sample =
+            StructuredDataChunker::new(10).with_max_chunks(5, StructuredDataOverflow::Sample);
+        let chunks = sample.chunk(&csv, None);
+        let rows = chunk_rows(&chunks);
+        assert_eq!(chunks.len(), 5);
+        assert_eq!(rows.len(), 50);
+        assert_eq!(rows.first().map(String::as_str), Some("0,row0"));
+        assert_eq!(rows.last().map(String::as_str), Some("980,row980"));
+        assert!(chunks.iter().all(|c| c.content.starts_with("id,value")));
+
+        let truncate =
+            StructuredDataChunker::new(10).with_max_chunks(5, StructuredDataOverflow::Truncate);
+        let rows = chunk_rows(&truncate.chunk(&csv, None));
+        assert_eq!(rows.len(), 50);
+        assert_eq!(rows.last().map(String::as_str), Some("49,row49"));
+
+        let aggregate =
+            StructuredDataChunker::new(10).with_max_chunks(5, StructuredDataOverflow::Aggregate);
+        let chunks = aggregate.chunk(&csv, None);
+        assert_eq!(chunks.len(), 5);
+        assert_eq!(chunk_rows(&chunks).len(), 1000);
+    }
+
+    #[test]
+    fn test_structured_chunker_overflow_report() {
+        let chunker =
+            StructuredDataChunker::new(10).with_max_chunks(5, StructuredDataOverflow::Sample);
+        assert_eq!(
+            chunker.overflow(&oversized_csv(1000)),
+            Some(StructuredDataOverflowReport {
+                strategy: StructuredDataOverflow::Sample,
+                total_rows: 1000,
+                indexed_rows: 50,
+            })
+        );
+        // Small files are fully indexed.
+        assert_eq!(chunker.overflow(&oversized_csv(50)), None);
+        assert_eq!(chunker.chunk(&oversized_csv(50), None).len(), 5);
+
+        let unlimited = StructuredDataChunker::new(10);
+        assert_eq!(unlimited.overflow(&oversized_csv(1000)), None);
+        assert_eq!(unlimited.chunk(&oversized_csv(1000), None).len(), 100);
+    }
 }
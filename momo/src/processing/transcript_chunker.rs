@@ -0,0 +1,188 @@
+use std::sync::Arc;
+
+use crate::config::ProcessingConfig;
+use crate::transcription::TranscriptSegment;
+
+use super::chunker::count_chunk_tokens;
+use super::{TextChunk, TokenCounter};
+
+/// Splits a transcript between its timed segments rather than by text size.
+/// Consecutive segments by one speaker form a turn, written as one line
+/// prefixed with the speaker's label. Turns are packed into chunks whole; a
+/// turn longer than a chunk is split between its segments.
+pub struct TranscriptChunker {
+    chunk_size: usize,
+    token_counter: Option<Arc<dyn TokenCounter>>,
+}
+
+impl TranscriptChunker {
+    pub fn new(config: &ProcessingConfig) -> Self {
+        Self {
+            chunk_size: config.chunk_size,
+            token_counter: None,
+        }
+    }
+
+    /// Measure chunk sizes with the given tokenizer instead of character count.
+    pub fn with_token_counter(mut self, counter: Arc<dyn TokenCounter>) -> Self {
+        self.token_counter = Some(counter);
+        self
+    }
+
+    pub fn chunk_segments(&self, segments: &[TranscriptSegment]) -> Vec<TextChunk> {
+        let mut lines: Vec<String> = Vec::new();
+        for turn in turns(segments) {
+            let mut line = String::new();
+            for segment in turn {
+                let text = segment.text.trim();
+                if text.is_empty() {
+                    continue;
+                }
+                let candidate = join_segment(&line, segment.speaker.as_deref(), text);
+                if !line.is_empty() && self.size(&candidate) > self.chunk_size {
+                    lines.push(std::mem::take(&mut line));
+                    line = join_segment("", segment.speaker.as_deref(), text);
+                } else {
+                    line = candidate;
+                }
+            }
+            if !line.is_empty() {
+                lines.push(line);
+            }
+        }
+
+        let mut chunks = Vec::new();
+        let mut current = String::new();
+        for line in lines {
+            let candidate = if current.is_empty() {
+                line.clone()
+            } else {
+                format!("{current}\n{line}")
+            };
+            if !current.is_empty() && self.size(&candidate) > self.chunk_size {
+                chunks.push(self.text_chunk(std::mem::replace(&mut current, line)));
+            } else {
+                current = candidate;
+            }
+        }
+        if !current.is_empty() {
+            chunks.push(self.text_chunk(current));
+        }
+        chunks
+    }
+
+    fn size(&self, text: &str) -> usize {
+        match self.token_counter.as_deref() {
+            Some(counter) => counter.count_tokens(text),
+            None => text.chars().count(),
+        }
+    }
+
+    fn text_chunk(&self, content: String) -> TextChunk {
+        TextChunk {
+            token_count: count_chunk_tokens(self.token_counter.as_deref(), &content),
+            content,
+        }
+    }
+}
+
+impl Default for TranscriptChunker {
+    fn default() -> Self {
+        Self {
+            chunk_size: 512,
+            token_counter: None,
+        }
+    }
+}
+
+/// Runs of consecutive segments with the same speaker.
+fn turns(segments: &[TranscriptSegment]) -> Vec<&[TranscriptSegment]> {
+    let mut turns = Vec::new();
+    let mut start = 0;
+    for i in 1..=segments.len() {
+        if i == segments.len() || segments[i].speaker != segments[start].speaker {
+            turns.push(&segments[start..i]);
+            start = i;
+        }
+    }
+    turns
+}
+
+/// Append `text` to a turn's line, starting it with the speaker's label.
+fn join_segment(line: &str, speaker: Option<&str>, text: &str) -> String {
+    match (line.is_empty(), speaker) {
+        (true, Some(speaker)) => format!("{speaker}: {text}"),
+        (true, None) => text.to_string(),
+        (false, _) => format!("{line} {text}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start: f64, speaker: Option<&str>, text: &str) -> TranscriptSegment {
+        TranscriptSegment {
+            start,
+            end: start + 1.0,
+            text: text.to_string(),
+            speaker: speaker.map(str::to_string),
+        }
+    }
+
+    fn chunker(chunk_size: usize) -> TranscriptChunker {
+        TranscriptChunker {
+            chunk_size,
+            token_counter: None,
+        }
+    }
+
+    #[test]
+    fn test_turns_are_labelled_and_packed_together() {
+        let segments = vec![
+            segment(0.0, Some("A"), "Shall we start?"),
+            segment(1.0, Some("A"), "We have three items."),
+            segment(2.0, Some("B"), "Go ahead."),
+        ];
+
+        let chunks = chunker(512).chunk_segments(&segments);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(
+            chunks[0].content,
+            "A: Shall we start? We have three items.\nB: Go ahead."
+        );
+    }
+
+    #[test]
+    fn test_chunks_break_between_turns_before_splitting_one() {
+        let segments = vec![
+            segment(0.0, Some("A"), "First speaker talks here."),
+            segment(1.0, Some("B"), "Second speaker answers."),
+        ];
+
+        let chunks = chunker(30).chunk_segments(&segments);
+        let contents: Vec<&str> = chunks.iter().map(|c| c.content.as_str()).collect();
+        assert_eq!(
+            contents,
+            vec!["A: First speaker talks here.", "B: Second speaker answers."]
+        );
+    }
+
+    #[test]
+    fn test_long_turn_splits_between_segments_without_labels() {
+        let segments = vec![
+            segment(0.0, None, "one two three"),
+            segment(1.0, None, "four five six"),
+            segment(2.0, None, " "),
+            segment(3.0, None, "seven eight"),
+        ];
+
+        let chunks = chunker(20).chunk_segments(&segments);
+        let contents: Vec<&str> = chunks.iter().map(|c| c.content.as_str()).collect();
+        assert_eq!(
+            contents,
+            vec!["one two three", "four five six", "seven eight"]
+        );
+        assert!(chunker(20).chunk_segments(&[]).is_empty());
+    }
+}
@@ -1,6 +1,9 @@
+use std::sync::Arc;
+
 use crate::config::ProcessingConfig;
+use crate::models::ChunkerType;
 
-use super::{ChunkContext, ContentChunker, MarkdownChunker, TextChunk, TextChunker};
+use super::{ChunkContext, ContentChunker, MarkdownChunker, TextChunk, TextChunker, TokenCounter};
 
 #[derive(Default)]
 pub struct WebpageChunker {
@@ -15,14 +18,32 @@ impl WebpageChunker {
             fallback_chunker: TextChunker::new(config),
         }
     }
+
+    /// Measure chunk sizes with the given tokenizer instead of character count.
+    pub fn with_token_counter(self, counter: Arc<dyn TokenCounter>) -> Self {
+        Self {
+            markdown_chunker: self.markdown_chunker.with_token_counter(counter.clone()),
+            fallback_chunker: self.fallback_chunker.with_token_counter(counter),
+        }
+    }
 }
 
 impl ContentChunker for WebpageChunker {
+    fn chunker_type(&self) -> ChunkerType {
+        ChunkerType::Webpage
+    }
+
     fn chunk(&self, text: &str, context: Option<&ChunkContext>) -> Vec<TextChunk> {
         if text.is_empty() {
             return Vec::new();
         }
 
+        // Text from HtmlExtractor is already markdown; converting it again
+        // would escape its headings.
+        if !text.trim_start().starts_with('<') {
+            return self.markdown_chunker.chunk(text, context);
+        }
+
         // Convert HTML to Markdown using htmd
         let markdown = htmd::convert(text);
 
@@ -41,6 +62,9 @@ impl ContentChunker for WebpageChunker {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::{
+        ChunkStrategy, NearDuplicateAction, PiiAction, PiiCategory, StructuredDataOverflow,
+    };
 
     #[test]
     fn test_webpage_chunker_basic() {
@@ -74,6 +98,15 @@ mod tests {
         assert!(!chunks.is_empty()); // Should fallback gracefully
     }
 
+    #[test]
+    fn test_webpage_chunker_keeps_extracted_markdown_headings() {
+        let chunker = WebpageChunker::default();
+        let markdown = "# Title\n\nIntro paragraph.\n\n## Section\n\nSection body.";
+        let chunks = chunker.chunk(markdown, None);
+        assert!(chunks.iter().any(|c| c.content.contains("# Title")));
+        assert!(!chunks.iter().any(|c| c.content.contains("\\#")));
+    }
+
     #[test]
     fn test_webpage_chunker_nested_html() {
         let chunker = WebpageChunker::default();
@@ -98,6 +131,28 @@ mod tests {
         let config = ProcessingConfig {
             chunk_size: 256,
             chunk_overlap: 25,
+            chunk_strategy: ChunkStrategy::Structural,
+            semantic_chunk_threshold: 0.3,
+            semantic_chunk_min_size: 128,
+            use_model_tokenizer: false,
+            auto_summary: false,
+            auto_summary_min_chars: 2000,
+            contextual_chunks: false,
+            near_duplicate_threshold: None,
+            near_duplicate_action: NearDuplicateAction::Warn,
+            record_stage_timings: false,
+            low_confidence_threshold: None,
+            chunk_reembed_interval_secs: 0,
+            chunk_reembed_batch_size: 64,
+            categorize_documents: false,
+            structured_data_max_chunks: 0,
+            structured_data_overflow: StructuredDataOverflow::Sample,
+            pii_detection: false,
+            pii_categories: PiiCategory::ALL.to_vec(),
+            pii_action: PiiAction::Redact,
+            retry_max_attempts: 3,
+            retry_base_delay_ms: 500,
+            dead_letter_after_failures: 5,
         };
         let chunker = WebpageChunker::new(&config);
 
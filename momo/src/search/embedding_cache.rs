@@ -0,0 +1,91 @@
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Model name and normalized query.
+type CacheKey = (String, String);
+
+/// Thread-safe LRU cache of query embeddings, so repeated queries skip the
+/// embedding model.
+///
+/// Entries are keyed by model name and the query with surrounding and repeated
+/// whitespace removed, which does not change its tokens.
+#[derive(Clone)]
+pub struct QueryEmbeddingCache {
+    cache: Arc<Mutex<LruCache<CacheKey, Vec<f32>>>>,
+    misses: Arc<AtomicU64>,
+}
+
+impl QueryEmbeddingCache {
+    /// Create a cache holding up to `capacity` embeddings; `None` when
+    /// `capacity` is 0, which disables caching.
+    pub fn new(capacity: usize) -> Option<Self> {
+        let capacity = NonZeroUsize::new(capacity)?;
+        Some(Self {
+            cache: Arc::new(Mutex::new(LruCache::new(capacity))),
+            misses: Arc::new(AtomicU64::new(0)),
+        })
+    }
+
+    /// Cached embedding of `query` by `model`, if any.
+    pub fn get(&self, model: &str, query: &str) -> Option<Vec<f32>> {
+        let mut cache = self.cache.lock().unwrap();
+        let embedding = cache.get(&Self::key(model, query)).cloned();
+        if embedding.is_none() {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+        embedding
+    }
+
+    /// Number of lookups that found no cached embedding.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Store the embedding of `query` by `model`, evicting the least recently
+    /// used entry when full.
+    pub fn put(&self, model: &str, query: &str, embedding: Vec<f32>) {
+        let mut cache = self.cache.lock().unwrap();
+        cache.put(Self::key(model, query), embedding);
+    }
+
+    fn key(model: &str, query: &str) -> CacheKey {
+        let normalized = query.split_whitespace().collect::<Vec<_>>().join(" ");
+        (model.to_string(), normalized)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hit_ignores_whitespace_but_not_model() {
+        let cache = QueryEmbeddingCache::new(10).unwrap();
+        cache.put("small", "rust  search ", vec![1.0, 0.0]);
+
+        assert_eq!(cache.get("small", " rust search"), Some(vec![1.0, 0.0]));
+        assert_eq!(cache.get("base", "rust search"), None);
+        assert_eq!(cache.get("small", "Rust search"), None);
+        assert_eq!(cache.misses(), 2);
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let cache = QueryEmbeddingCache::new(2).unwrap();
+        cache.put("m", "a", vec![1.0]);
+        cache.put("m", "b", vec![2.0]);
+        cache.get("m", "a");
+        cache.put("m", "c", vec![3.0]);
+
+        assert!(cache.get("m", "a").is_some());
+        assert!(cache.get("m", "b").is_none());
+        assert!(cache.get("m", "c").is_some());
+    }
+
+    #[test]
+    fn test_zero_capacity_disables_cache() {
+        assert!(QueryEmbeddingCache::new(0).is_none());
+    }
+}
@@ -0,0 +1,108 @@
+//! Query-highlighted snippets of matched chunks.
+
+use std::collections::HashSet;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::mmr::cosine_similarity;
+
+/// Opening marker placed before each query term in a snippet.
+pub const HIGHLIGHT_START: &str = "<mark>";
+/// Closing marker placed after each query term in a snippet.
+pub const HIGHLIGHT_END: &str = "</mark>";
+
+/// Sentences of `text`, trimmed, without blank ones.
+pub fn split_sentences(text: &str) -> Vec<&str> {
+    text.split_sentence_bounds()
+        .map(str::trim)
+        .filter(|sentence| !sentence.is_empty())
+        .collect()
+}
+
+/// Index of the sentence embedding closest to the query; `None` when there
+/// are no sentences.
+pub fn best_sentence(query_embedding: &[f32], sentence_embeddings: &[Vec<f32>]) -> Option<usize> {
+    sentence_embeddings
+        .iter()
+        .map(|embedding| cosine_similarity(query_embedding, embedding))
+        .enumerate()
+        .fold(
+            None,
+            |best: Option<(usize, f32)>, (i, similarity)| match best {
+                Some((_, best_similarity)) if best_similarity >= similarity => best,
+                _ => Some((i, similarity)),
+            },
+        )
+        .map(|(i, _)| i)
+}
+
+/// The sentence at `best` with one sentence of context on each side, query
+/// terms marked as by [`mark_terms`].
+pub fn build_snippet(sentences: &[&str], best: usize, query: &str) -> String {
+    let start = best.saturating_sub(1);
+    let end = (best + 2).min(sentences.len());
+    mark_terms(&sentences[start..end].join(" "), query)
+}
+
+/// Wrap each word of `text` that also occurs in `query`, ignoring case, in
+/// [`HIGHLIGHT_START`]/[`HIGHLIGHT_END`]. Single-character terms are not
+/// marked.
+pub fn mark_terms(text: &str, query: &str) -> String {
+    let terms: HashSet<String> = query
+        .unicode_words()
+        .filter(|word| word.chars().count() > 1)
+        .map(str::to_lowercase)
+        .collect();
+
+    let mut marked = String::with_capacity(text.len());
+    for word in text.split_word_bounds() {
+        if terms.contains(&word.to_lowercase()) {
+            marked.push_str(HIGHLIGHT_START);
+            marked.push_str(word);
+            marked.push_str(HIGHLIGHT_END);
+        } else {
+            marked.push_str(word);
+        }
+    }
+    marked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mark_terms_is_case_insensitive_and_whole_word() {
+        assert_eq!(
+            mark_terms("Rust makes trusted code.", "rust code"),
+            "<mark>Rust</mark> makes trusted <mark>code</mark>."
+        );
+        assert_eq!(mark_terms("a cat", "a cat"), "a <mark>cat</mark>");
+    }
+
+    #[test]
+    fn test_build_snippet_keeps_one_sentence_of_context() {
+        let text = "Intro here. Setup steps follow. Deploy with cargo. Then relax. The end.";
+        let sentences = split_sentences(text);
+        assert_eq!(sentences.len(), 5);
+
+        let embeddings = vec![
+            vec![0.0, 1.0],
+            vec![0.2, 1.0],
+            vec![1.0, 0.1],
+            vec![0.1, 1.0],
+            vec![0.0, 1.0],
+        ];
+        let best = best_sentence(&[1.0, 0.0], &embeddings).unwrap();
+        assert_eq!(best, 2);
+        assert_eq!(
+            build_snippet(&sentences, best, "deploy"),
+            "Setup steps follow. <mark>Deploy</mark> with cargo. Then relax."
+        );
+        assert_eq!(
+            build_snippet(&sentences, 0, "none"),
+            "Intro here. Setup steps follow."
+        );
+        assert_eq!(best_sentence(&[1.0, 0.0], &[]), None);
+    }
+}
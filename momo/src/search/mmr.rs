@@ -0,0 +1,88 @@
+//! Maximal Marginal Relevance (MMR) selection for diversifying search results.
+
+/// Default trade-off between relevance (1.0) and novelty (0.0).
+pub const DEFAULT_DIVERSITY_LAMBDA: f32 = 0.5;
+
+/// Pick up to `k` candidates greedily by MMR, most relevant first.
+///
+/// Each step takes the candidate maximising
+/// `lambda * relevance - (1 - lambda) * max_similarity_to_already_picked`, so
+/// a near-duplicate of a picked candidate loses out to a less relevant but
+/// novel one. Candidates without an embedding are never penalised. Returns
+/// indices into `relevance`, in pick order.
+pub fn mmr_select(
+    relevance: &[f32],
+    embeddings: &[Option<&[f32]>],
+    k: usize,
+    lambda: f32,
+) -> Vec<usize> {
+    let mut remaining: Vec<usize> = (0..relevance.len()).collect();
+    let mut picked: Vec<usize> = Vec::with_capacity(k.min(relevance.len()));
+    // Highest similarity of each candidate to anything picked so far.
+    let mut redundancy = vec![0.0f32; relevance.len()];
+
+    while picked.len() < k && !remaining.is_empty() {
+        let (slot, _) = remaining
+            .iter()
+            .enumerate()
+            .map(|(slot, &i)| (slot, lambda * relevance[i] - (1.0 - lambda) * redundancy[i]))
+            .fold((0, f32::NEG_INFINITY), |best, candidate| {
+                if candidate.1 > best.1 {
+                    candidate
+                } else {
+                    best
+                }
+            });
+        let chosen = remaining.remove(slot);
+        picked.push(chosen);
+
+        if let Some(chosen_embedding) = embeddings.get(chosen).copied().flatten() {
+            for &i in &remaining {
+                if let Some(embedding) = embeddings.get(i).copied().flatten() {
+                    redundancy[i] =
+                        redundancy[i].max(cosine_similarity(chosen_embedding, embedding));
+                }
+            }
+        }
+    }
+
+    picked
+}
+
+pub(crate) fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mmr_skips_duplicate_in_favour_of_novel_candidate() {
+        let relevance = [0.9, 0.9, 0.7];
+        let a = [1.0, 0.0];
+        let b = [0.0, 1.0];
+        let embeddings = [Some(&a[..]), Some(&a[..]), Some(&b[..])];
+
+        assert_eq!(mmr_select(&relevance, &embeddings, 2, 0.5), vec![0, 2]);
+        // Pure relevance ignores redundancy.
+        assert_eq!(mmr_select(&relevance, &embeddings, 2, 1.0), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_mmr_without_embeddings_keeps_relevance_order() {
+        let relevance = [0.5, 0.8, 0.6];
+        assert_eq!(
+            mmr_select(&relevance, &[None, None, None], 5, 0.5),
+            vec![1, 2, 0]
+        );
+        assert!(mmr_select(&[], &[], 3, 0.5).is_empty());
+    }
+}
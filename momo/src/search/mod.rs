@@ -1,3 +1,11 @@
+pub mod embedding_cache;
+pub mod highlight;
+pub mod mmr;
 pub mod rewrite_cache;
+pub mod synonyms;
 
+pub use embedding_cache::QueryEmbeddingCache;
+pub use highlight::{best_sentence, build_snippet, split_sentences};
+pub use mmr::{mmr_select, DEFAULT_DIVERSITY_LAMBDA};
 pub use rewrite_cache::QueryRewriteCache;
+pub use synonyms::{expand_query, load_synonyms, SynonymMap};
@@ -0,0 +1,174 @@
+//! LLM-free query expansion from a configured synonym dictionary.
+
+use std::collections::HashMap;
+
+use crate::config::SearchConfig;
+use crate::error::{MomoError, Result};
+
+/// Lowercased term or phrase -> synonyms appended to queries containing it.
+pub type SynonymMap = HashMap<String, Vec<String>>;
+
+/// Load and validate the synonym dictionary. Returns an empty map when no
+/// file is set.
+///
+/// The file is a JSON object mapping each term to its synonyms, e.g.
+/// `{"car": ["automobile", "vehicle"], "machine learning": ["ml"]}`. Keys are
+/// matched case-insensitively on whole words.
+pub fn load_synonyms(config: &SearchConfig) -> Result<SynonymMap> {
+    let Some(path) = config.synonyms_path.as_deref() else {
+        return Ok(SynonymMap::new());
+    };
+
+    let raw = std::fs::read_to_string(path).map_err(|e| {
+        MomoError::Validation(format!("Failed to read search synonyms '{path}': {e}"))
+    })?;
+    let entries: HashMap<String, Vec<String>> = serde_json::from_str(&raw)
+        .map_err(|e| MomoError::Validation(format!("Invalid search synonyms '{path}': {e}")))?;
+
+    let mut synonyms = SynonymMap::new();
+    for (term, values) in entries {
+        let term = normalize(&term);
+        if term.is_empty() {
+            return Err(MomoError::Validation(format!(
+                "Search synonyms '{path}' contain an empty term"
+            )));
+        }
+        let values = values
+            .iter()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+        synonyms.entry(term).or_default().extend(values);
+    }
+
+    Ok(synonyms)
+}
+
+/// Append the synonyms of every dictionary term found in `query`, in the
+/// order the terms appear. Synonyms already in the query are skipped.
+/// Returns `None` when nothing was added.
+pub fn expand_query(query: &str, synonyms: &SynonymMap) -> Option<String> {
+    if synonyms.is_empty() {
+        return None;
+    }
+
+    let words = words_of(query);
+    let mut matches: Vec<(usize, &str)> = synonyms
+        .keys()
+        .filter_map(|term| find_phrase(&words, &words_of(term)).map(|pos| (pos, term.as_str())))
+        .collect();
+    // Earliest match first; longer terms first when they start at the same word.
+    matches.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.len().cmp(&a.1.len())));
+
+    let mut seen: Vec<Vec<String>> = Vec::new();
+    let mut additions: Vec<&str> = Vec::new();
+    for (_, term) in matches {
+        for synonym in &synonyms[term] {
+            let synonym_words = words_of(synonym);
+            if find_phrase(&words, &synonym_words).is_some() || seen.contains(&synonym_words) {
+                continue;
+            }
+            seen.push(synonym_words);
+            additions.push(synonym);
+        }
+    }
+
+    if additions.is_empty() {
+        return None;
+    }
+    Some(format!("{} {}", query.trim_end(), additions.join(" ")))
+}
+
+fn normalize(text: &str) -> String {
+    words_of(text).join(" ")
+}
+
+fn words_of(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Index of the first word where `phrase` occurs in `words`.
+fn find_phrase(words: &[String], phrase: &[String]) -> Option<usize> {
+    if phrase.is_empty() || phrase.len() > words.len() {
+        return None;
+    }
+    words
+        .windows(phrase.len())
+        .position(|window| window == phrase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn synonyms() -> SynonymMap {
+        SynonymMap::from([
+            (
+                "car".to_string(),
+                vec!["automobile".to_string(), "vehicle".to_string()],
+            ),
+            ("machine learning".to_string(), vec!["ML".to_string()]),
+            ("buy".to_string(), vec!["purchase".to_string()]),
+        ])
+    }
+
+    #[test]
+    fn appends_synonyms_in_query_order() {
+        assert_eq!(
+            expand_query("Where did I buy my car?", &synonyms()).as_deref(),
+            Some("Where did I buy my car? purchase automobile vehicle")
+        );
+    }
+
+    #[test]
+    fn matches_phrases_and_whole_words_case_insensitively() {
+        assert_eq!(
+            expand_query("Machine Learning notes", &synonyms()).as_deref(),
+            Some("Machine Learning notes ML")
+        );
+        assert_eq!(expand_query("carpet cleaning", &synonyms()), None);
+    }
+
+    #[test]
+    fn skips_synonyms_already_in_query() {
+        assert_eq!(
+            expand_query("car or vehicle", &synonyms()).as_deref(),
+            Some("car or vehicle automobile")
+        );
+        assert_eq!(expand_query("purchase", &synonyms()), None);
+    }
+
+    #[test]
+    fn empty_dictionary_leaves_query_alone() {
+        assert_eq!(expand_query("car", &SynonymMap::new()), None);
+    }
+
+    #[test]
+    fn load_normalizes_terms() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(
+            file.path(),
+            r#"{" Machine   Learning ": ["ML", " "], "CAR": ["automobile"]}"#,
+        )
+        .unwrap();
+        let config = SearchConfig {
+            synonyms_path: Some(file.path().display().to_string()),
+            ..crate::config::Config::default().search
+        };
+
+        let loaded = load_synonyms(&config).unwrap();
+        assert_eq!(loaded["machine learning"], vec!["ML".to_string()]);
+        assert_eq!(loaded["car"], vec!["automobile".to_string()]);
+    }
+
+    #[test]
+    fn load_without_path_is_empty() {
+        let config = SearchConfig {
+            synonyms_path: None,
+            ..crate::config::Config::default().search
+        };
+        assert!(load_synonyms(&config).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,180 @@
+use std::sync::Arc;
+
+use tracing::{debug, info};
+
+use crate::config::ProcessingConfig;
+use crate::db::DatabaseBackend;
+use crate::embeddings::EmbeddingProvider;
+use crate::error::Result;
+use crate::services::JobTracker;
+
+/// Background job that re-embeds chunks whose embedding went stale, e.g.
+/// because their content was edited after the document was processed.
+#[derive(Clone)]
+pub struct ChunkReembedder {
+    db: Arc<dyn DatabaseBackend>,
+    embeddings: EmbeddingProvider,
+    interval_secs: u64,
+    batch_size: usize,
+}
+
+impl ChunkReembedder {
+    pub fn new(
+        db: Arc<dyn DatabaseBackend>,
+        embeddings: EmbeddingProvider,
+        config: &ProcessingConfig,
+    ) -> Self {
+        Self {
+            db,
+            embeddings,
+            interval_secs: config.chunk_reembed_interval_secs,
+            batch_size: config.chunk_reembed_batch_size.max(1),
+        }
+    }
+
+    pub fn interval_secs(&self) -> u64 {
+        self.interval_secs
+    }
+
+    /// Embed up to one batch of chunks with stale or missing embeddings.
+    /// Returns the number of chunks re-embedded.
+    pub async fn run_once(&self) -> Result<u64> {
+        let chunks = self
+            .db
+            .get_chunks_missing_embeddings(self.batch_size)
+            .await?;
+        if chunks.is_empty() {
+            return Ok(0);
+        }
+
+        let texts: Vec<String> = chunks
+            .iter()
+            .map(|chunk| {
+                chunk
+                    .embedded_content
+                    .clone()
+                    .unwrap_or_else(|| chunk.content.clone())
+            })
+            .collect();
+        let vectors = self.embeddings.embed_passages(texts).await?;
+
+        let updates: Vec<(String, Vec<f32>)> = chunks
+            .into_iter()
+            .map(|chunk| chunk.id)
+            .zip(vectors)
+            .collect();
+        self.db.update_chunk_embeddings_batch(&updates).await?;
+
+        debug!(count = updates.len(), "Stored re-embedded chunks");
+        info!(reembedded = updates.len(), "Chunk re-embedding complete");
+
+        Ok(updates.len() as u64)
+    }
+
+    /// Re-embed batches until no stale chunks remain, reporting progress on
+    /// `job`. Returns the total number of chunks re-embedded.
+    pub async fn run_job(&self, job: &JobTracker) -> Result<u64> {
+        job.start().await;
+        let mut reembedded = 0;
+        loop {
+            match self.run_once().await {
+                Ok(0) => break,
+                Ok(count) => {
+                    reembedded += count;
+                    job.progress(reembedded).await;
+                }
+                Err(e) => {
+                    job.fail(&e.to_string()).await;
+                    return Err(e);
+                }
+            }
+        }
+        job.complete(serde_json::json!({ "chunksReembedded": reembedded }))
+            .await;
+        Ok(reembedded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Config, DatabaseConfig, EmbeddingsConfig};
+    use crate::db::repository::{ChunkRepository, DocumentRepository};
+    use crate::db::{Database, LibSqlBackend};
+    use crate::models::{Chunk, Document, ProcessingStatus};
+
+    #[tokio::test]
+    async fn edited_chunk_is_reembedded() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::new(&DatabaseConfig {
+            url: format!("file:{}", temp_dir.path().join("reembed.db").display()),
+            auth_token: None,
+            local_path: None,
+            max_connections: 4,
+        })
+        .await
+        .unwrap();
+        let conn = db.connect().unwrap();
+        let backend: Arc<dyn DatabaseBackend> = Arc::new(LibSqlBackend::new(db));
+
+        let embeddings = EmbeddingProvider::new(&EmbeddingsConfig {
+            model: "BAAI/bge-small-en-v1.5".to_string(),
+            dimensions: 384,
+            batch_size: 2,
+            query_cache_size: 0,
+            max_concurrent_batches: 4,
+        })
+        .expect("failed to create embeddings provider");
+
+        let mut doc = Document::new("doc_1".to_string());
+        doc.status = ProcessingStatus::Done;
+        DocumentRepository::create(&conn, &doc).await.unwrap();
+        let chunk = Chunk::new(
+            "chunk_1".to_string(),
+            "doc_1".to_string(),
+            "The original text".to_string(),
+            0,
+        );
+        ChunkRepository::create(&conn, &chunk).await.unwrap();
+        let original = embeddings.embed_passage(&chunk.content).await.unwrap();
+        ChunkRepository::update_embedding(&conn, &chunk.id, &original)
+            .await
+            .unwrap();
+
+        assert!(backend
+            .update_chunk_content("doc_1", "chunk_1", "Completely rewritten text")
+            .await
+            .unwrap()
+            .is_some());
+        assert_eq!(
+            backend
+                .get_chunks_missing_embeddings(10)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let reembedder = ChunkReembedder::new(
+            backend.clone(),
+            embeddings.clone(),
+            &Config::default().processing,
+        );
+        assert_eq!(reembedder.run_once().await.unwrap(), 1);
+        assert!(backend
+            .get_chunks_missing_embeddings(10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let query = embeddings
+            .embed_query("Completely rewritten text")
+            .await
+            .unwrap();
+        let results = backend
+            .search_similar_chunks(&query, 1, 0.0, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(results[0].chunk_content, "Completely rewritten text");
+    }
+}
@@ -3,9 +3,17 @@ use std::sync::Arc;
 use crate::db::DatabaseBackend;
 use crate::error::Result;
 use crate::models::Memory;
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use tracing::{debug, error, info};
 
+/// An episode a decay run would schedule for forgetting.
+#[derive(Debug, Clone)]
+pub struct PlannedDecay {
+    pub memory: Memory,
+    pub relevance: f64,
+    pub forget_after: DateTime<Utc>,
+}
+
 /// Manager responsible for scheduling low-relevance Episode memories for forgetting
 #[derive(Clone)]
 pub struct EpisodeDecayManager {
@@ -14,6 +22,7 @@ pub struct EpisodeDecayManager {
     grace_days: u32,
     decay_days: f64,
     decay_factor: f64,
+    dry_run: bool,
 }
 
 impl EpisodeDecayManager {
@@ -31,21 +40,78 @@ impl EpisodeDecayManager {
             grace_days,
             decay_days,
             decay_factor,
+            dry_run: false,
         }
     }
 
+    /// Only log what each run would schedule, without changing anything.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
     /// Run a single pass of the decay process. Finds episode memories with relevance below
     /// threshold and schedules them for forgetting by setting forget_after to now + grace_days.
+    /// In dry-run mode, returns how many would have been scheduled.
     pub async fn run_once(&self) -> Result<u64> {
         info!(
             threshold = self.threshold,
+            dry_run = self.dry_run,
             "Starting episode decay run_once"
         );
 
-        let candidates = self.db.get_episode_decay_candidates().await?;
+        let planned = self.preview().await?;
+
+        if self.dry_run {
+            for plan in &planned {
+                info!(
+                    id = plan.memory.id.as_str(),
+                    relevance = plan.relevance,
+                    forget_after = %plan.forget_after,
+                    "Dry run: would schedule episode for forgetting"
+                );
+            }
+            info!(scheduled = planned.len(), "Episode decay dry run complete");
+            return Ok(planned.len() as u64);
+        }
 
         let mut scheduled = 0u64;
 
+        for plan in planned {
+            match self
+                .db
+                .set_memory_forget_after(&plan.memory.id, plan.forget_after)
+                .await
+            {
+                Ok(affected) => {
+                    if affected > 0 {
+                        scheduled += 1;
+                        info!(
+                            id = plan.memory.id.as_str(),
+                            "Scheduled episode for forgetting"
+                        );
+                    }
+                }
+                Err(e) => {
+                    error!(
+                        id = plan.memory.id.as_str(),
+                        "Failed to schedule forget_after: {}", e
+                    );
+                }
+            }
+        }
+
+        info!(scheduled, "Episode decay run complete");
+        Ok(scheduled)
+    }
+
+    /// Episodes the next run would schedule for forgetting, without changing anything.
+    pub async fn preview(&self) -> Result<Vec<PlannedDecay>> {
+        let candidates = self.db.get_episode_decay_candidates().await?;
+        let forget_after = Utc::now() + Duration::days(self.grace_days as i64);
+
+        let mut planned = Vec::new();
+
         for candidate in candidates {
             // Build a minimal Memory to use existing relevance calculation
             let mut m = Memory::new(candidate.id.clone(), candidate.memory, candidate.space_id);
@@ -71,33 +137,15 @@ impl EpisodeDecayManager {
             );
 
             if relevance < self.threshold {
-                let forget_after = Utc::now() + Duration::days(self.grace_days as i64);
-                match self
-                    .db
-                    .set_memory_forget_after(&candidate.id, forget_after)
-                    .await
-                {
-                    Ok(affected) => {
-                        if affected > 0 {
-                            scheduled += 1;
-                            info!(
-                                id = candidate.id.as_str(),
-                                "Scheduled episode for forgetting"
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        error!(
-                            id = candidate.id.as_str(),
-                            "Failed to schedule forget_after: {}", e
-                        );
-                    }
-                }
+                planned.push(PlannedDecay {
+                    memory: m,
+                    relevance,
+                    forget_after,
+                });
             }
         }
 
-        info!(scheduled, "Episode decay run complete");
-        Ok(scheduled)
+        Ok(planned)
     }
 
     /// Get the configured interval in seconds
@@ -312,4 +360,42 @@ mod tests {
             .with_timezone(&Utc);
         assert!(dt > Utc::now());
     }
+
+    #[tokio::test]
+    async fn test_dry_run_leaves_forget_after_unset() {
+        let (conn, db, _tmp) = setup_test_db().await;
+
+        let past = (Utc::now() - Duration::days(365)).to_rfc3339();
+        insert_memory(
+            &conn,
+            "e4",
+            "ancient episode",
+            "episode",
+            Some(&past),
+            false,
+            false,
+        )
+        .await;
+
+        let mgr = EpisodeDecayManager::new(Arc::clone(&db), 0.5, 10, 30.0, 0.9).with_dry_run(true);
+        let planned = mgr.preview().await.unwrap();
+        assert_eq!(planned.len(), 1);
+        assert_eq!(planned[0].memory.id, "e4");
+        assert!(planned[0].relevance < 0.5);
+
+        let scheduled = mgr.run_once().await.unwrap();
+        assert_eq!(scheduled, 1);
+
+        let row = conn
+            .query("SELECT forget_after FROM memories WHERE id = 'e4'", ())
+            .await
+            .unwrap()
+            .next()
+            .await
+            .unwrap()
+            .unwrap();
+
+        let f: Option<String> = row.get(0).unwrap();
+        assert!(f.is_none());
+    }
 }
@@ -1,71 +1,265 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 
+use crate::config::{IdleForgettingConfig, ImportanceConfig};
 use crate::db::DatabaseBackend;
 use crate::error::Result;
-use chrono::Utc;
+use crate::intelligence::ImportanceScorer;
+use crate::models::Memory;
+use chrono::{Duration, Utc};
 use tracing::{debug, error, info};
 
+/// A memory a forgetting run would forget, with the reason it would record.
+#[derive(Debug, Clone)]
+pub struct PlannedForget {
+    pub memory: Memory,
+    pub reason: String,
+}
+
 /// Manager responsible for automatic forgetting of expired memories
 #[derive(Clone)]
 pub struct ForgettingManager {
     db: Arc<dyn DatabaseBackend>,
     interval_secs: u64,
+    importance: Option<ImportanceConfig>,
+    idle: Option<IdleForgettingConfig>,
+    dry_run: bool,
 }
 
 impl ForgettingManager {
     /// Create a new ForgettingManager
     pub fn new(db: Arc<dyn DatabaseBackend>, interval_secs: u64) -> Self {
-        Self { db, interval_secs }
+        Self {
+            db,
+            interval_secs,
+            importance: None,
+            idle: None,
+            dry_run: false,
+        }
+    }
+
+    /// Recompute importance scores on each run and, when `max_memories` is set,
+    /// forget the least important memories above that limit.
+    pub fn with_importance(mut self, config: ImportanceConfig) -> Self {
+        if config.enabled {
+            self.importance = Some(config);
+        }
+        self
+    }
+
+    /// Also forget episodes that haven't been accessed for
+    /// `config.idle_days` and have few sources.
+    pub fn with_idle_forgetting(mut self, config: IdleForgettingConfig) -> Self {
+        if config.enabled {
+            self.idle = Some(config);
+        }
+        self
+    }
+
+    /// Only log what each run would forget, without changing anything.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
     }
 
     /// Run a single pass of the forgetting process
     ///
-    /// Gets all expired memories and marks them as forgotten.
+    /// Marks expired memories as forgotten, then idle episodes when idle
+    /// forgetting is enabled, then refreshes importance scores and prunes down
+    /// to the memory limit when importance scoring is enabled.
     /// Continues processing even if individual forgets fail.
-    /// Returns the number of memories successfully forgotten.
+    /// Returns the number of memories successfully forgotten; in dry-run mode,
+    /// the number that would have been.
     pub async fn run_once(&self) -> Result<u64> {
+        if self.dry_run {
+            let planned = self.preview().await?;
+            for plan in &planned {
+                info!(
+                    memory_id = %plan.memory.id,
+                    reason = %plan.reason,
+                    "Dry run: would forget memory"
+                );
+            }
+            info!(
+                "Dry run complete: {} memories would be forgotten",
+                planned.len()
+            );
+            return Ok(planned.len() as u64);
+        }
+
         info!("Starting forgetting process");
+        let mut forgotten_count = self.forget_all(self.plan_expired().await?).await;
 
-        let now = Utc::now();
+        if let Some(ref config) = self.idle {
+            forgotten_count += self.forget_all(self.plan_idle(config).await?).await;
+        }
+
+        if let Some(ref config) = self.importance {
+            self.refresh_importance(config).await?;
+            if let Some(max_memories) = config.max_memories {
+                let planned = self.plan_prune(max_memories, &[]).await?;
+                forgotten_count += self.forget_all(planned).await;
+            }
+        }
 
-        // Get candidates
-        let candidates = self.db.get_forgetting_candidates(now).await?;
-        let count = candidates.len();
+        Ok(forgotten_count)
+    }
+
+    /// Memories the next run would forget, without changing anything.
+    /// Importance pruning is planned from the stored importance scores, which
+    /// the run itself refreshes first.
+    pub async fn preview(&self) -> Result<Vec<PlannedForget>> {
+        let mut planned = self.plan_expired().await?;
+
+        if let Some(ref config) = self.idle {
+            let ids: HashSet<String> = planned.iter().map(|p| p.memory.id.clone()).collect();
+            planned.extend(
+                self.plan_idle(config)
+                    .await?
+                    .into_iter()
+                    .filter(|p| !ids.contains(&p.memory.id)),
+            );
+        }
+
+        if let Some(max_memories) = self.importance.as_ref().and_then(|c| c.max_memories) {
+            let pruned = self.plan_prune(max_memories, &planned).await?;
+            planned.extend(pruned);
+        }
+
+        Ok(planned)
+    }
 
-        if count == 0 {
+    async fn plan_expired(&self) -> Result<Vec<PlannedForget>> {
+        let candidates = self.db.get_forgetting_candidates(Utc::now()).await?;
+        if candidates.is_empty() {
             info!("No expired memories to forget");
-            return Ok(0);
+        } else {
+            debug!("Found {} expired memories to forget", candidates.len());
         }
 
-        debug!("Found {} expired memories to forget", count);
+        Ok(candidates
+            .into_iter()
+            .map(|memory| PlannedForget {
+                memory,
+                reason: "auto-forgotten: expired".to_string(),
+            })
+            .collect())
+    }
+
+    /// Episodes idle for longer than `config.idle_days` that have at most
+    /// `config.max_source_count` sources.
+    async fn plan_idle(&self, config: &IdleForgettingConfig) -> Result<Vec<PlannedForget>> {
+        let accessed_before = Utc::now() - Duration::days(i64::from(config.idle_days));
+        let candidates = self
+            .db
+            .get_idle_episodes(accessed_before, config.max_source_count)
+            .await?;
+        debug!("Found {} idle episodes to forget", candidates.len());
+
+        let reason = format!("auto-forgotten: not accessed in {} days", config.idle_days);
+        Ok(candidates
+            .into_iter()
+            .map(|memory| PlannedForget {
+                memory,
+                reason: reason.clone(),
+            })
+            .collect())
+    }
 
+    /// The least important non-static memories to forget so that at most
+    /// `max_memories` active memories remain once `pending` are forgotten too.
+    async fn plan_prune(
+        &self,
+        max_memories: u64,
+        pending: &[PlannedForget],
+    ) -> Result<Vec<PlannedForget>> {
+        let active = self
+            .db
+            .count_active_memories()
+            .await?
+            .saturating_sub(pending.len() as u64);
+        if active <= max_memories {
+            return Ok(Vec::new());
+        }
+
+        let excess = (active - max_memories) as usize;
+        let pending_ids: HashSet<&str> = pending.iter().map(|p| p.memory.id.as_str()).collect();
+        let ids: Vec<String> = self
+            .db
+            .get_least_important_memory_ids(excess + pending.len())
+            .await?
+            .into_iter()
+            .filter(|id| !pending_ids.contains(id.as_str()))
+            .take(excess)
+            .collect();
+
+        // Keep the least-important-first order of `ids`.
+        let mut memories = self.db.get_memories_by_ids(&ids).await?;
+        memories.sort_by_key(|m| ids.iter().position(|id| *id == m.id));
+        Ok(memories
+            .into_iter()
+            .map(|memory| PlannedForget {
+                memory,
+                reason: "auto-forgotten: low importance".to_string(),
+            })
+            .collect())
+    }
+
+    /// Forget each planned memory with its reason. Returns how many were
+    /// forgotten; failures are logged and skipped.
+    async fn forget_all(&self, planned: Vec<PlannedForget>) -> u64 {
         let mut forgotten_count = 0u64;
         let mut error_count = 0;
 
-        for memory in candidates {
-            debug!("Forgetting memory: id={}", memory.id);
-
+        for plan in &planned {
             match self
                 .db
-                .forget_memory(&memory.id, Some("auto-forgotten: expired"))
+                .forget_memory(&plan.memory.id, Some(&plan.reason))
                 .await
             {
                 Ok(_) => {
+                    info!(
+                        memory_id = %plan.memory.id,
+                        reason = %plan.reason,
+                        last_accessed = ?plan.memory.last_accessed,
+                        source_count = plan.memory.source_count,
+                        "Forgot memory"
+                    );
                     forgotten_count += 1;
                 }
                 Err(e) => {
-                    error!("Failed to forget memory {}: {}", memory.id, e);
+                    error!("Failed to forget memory {}: {}", plan.memory.id, e);
                     error_count += 1;
                 }
             }
         }
 
-        info!(
-            "Forgetting process complete: {} forgotten, {} errors out of {} candidates",
-            forgotten_count, error_count, count
-        );
+        if !planned.is_empty() {
+            info!(
+                "Forgetting complete: {} forgotten, {} errors out of {} candidates",
+                forgotten_count,
+                error_count,
+                planned.len()
+            );
+        }
 
-        Ok(forgotten_count)
+        forgotten_count
+    }
+
+    /// Recompute and store the importance score of every active memory.
+    async fn refresh_importance(&self, config: &ImportanceConfig) -> Result<()> {
+        let candidates = self.db.get_memory_importance_candidates().await?;
+        let scorer = ImportanceScorer::new(config.clone());
+        let now = Utc::now();
+
+        let updates: Vec<(String, f64)> = candidates
+            .iter()
+            .map(|candidate| (candidate.id.clone(), scorer.score(candidate, now)))
+            .collect();
+        self.db.update_memory_importance_batch(&updates).await?;
+
+        debug!("Refreshed importance for {} memories", updates.len());
+        Ok(())
     }
 
     /// Get the configured interval in seconds
@@ -111,6 +305,7 @@ mod tests {
                 memory_type TEXT NOT NULL DEFAULT 'episodic',
                 last_accessed TEXT,
                 confidence REAL,
+                importance REAL,
                 metadata TEXT NOT NULL DEFAULT '{}',
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL,
@@ -311,6 +506,217 @@ mod tests {
         assert_eq!(reason, "auto-forgotten: expired");
     }
 
+    #[tokio::test]
+    async fn test_run_once_prunes_least_important_memories_over_limit() {
+        // Given three memories where mem1 has no sources and the others have several
+        let (conn, db, _temp) = setup_test_db().await;
+        insert_memory(&conn, "mem1", "weak memory", None, false).await;
+        insert_memory(&conn, "mem2", "supported memory", None, false).await;
+        insert_memory(&conn, "mem3", "supported memory", None, false).await;
+        conn.execute(
+            "UPDATE memories SET source_count = 5 WHERE id IN ('mem2', 'mem3')",
+            (),
+        )
+        .await
+        .unwrap();
+
+        let config = ImportanceConfig {
+            enabled: true,
+            recency_weight: 0.4,
+            source_weight: 0.2,
+            confidence_weight: 0.2,
+            relation_weight: 0.2,
+            recency_half_life_days: 30.0,
+            search_boost: 0.0,
+            max_memories: Some(2),
+        };
+        let manager = ForgettingManager::new(db.clone(), 3600).with_importance(config);
+
+        // When run_once is called
+        let forgotten = manager.run_once().await.unwrap();
+
+        // Then only the least important memory is forgotten
+        assert_eq!(forgotten, 1);
+        let mut rows = conn
+            .query(
+                "SELECT id, forget_reason, importance FROM memories WHERE is_forgotten = 1",
+                (),
+            )
+            .await
+            .unwrap();
+        let row = rows.next().await.unwrap().unwrap();
+        assert_eq!(row.get::<String>(0).unwrap(), "mem1");
+        assert_eq!(
+            row.get::<String>(1).unwrap(),
+            "auto-forgotten: low importance"
+        );
+        assert!(row.get::<Option<f64>>(2).unwrap().is_some());
+        assert!(rows.next().await.unwrap().is_none());
+    }
+
+    async fn insert_episode(conn: &Connection, id: &str, idle_days: i64, source_count: i32) {
+        insert_memory(conn, id, "episode", None, false).await;
+        let last_accessed = (Utc::now() - Duration::days(idle_days)).to_rfc3339();
+        conn.execute(
+            "UPDATE memories SET memory_type = 'episode', last_accessed = ?2, source_count = ?3 WHERE id = ?1",
+            (id, last_accessed.as_str(), source_count),
+        )
+        .await
+        .unwrap();
+    }
+
+    async fn forgotten_ids(conn: &Connection) -> Vec<String> {
+        let mut rows = conn
+            .query(
+                "SELECT id FROM memories WHERE is_forgotten = 1 ORDER BY id",
+                (),
+            )
+            .await
+            .unwrap();
+        let mut ids = Vec::new();
+        while let Some(row) = rows.next().await.unwrap() {
+            ids.push(row.get::<String>(0).unwrap());
+        }
+        ids
+    }
+
+    fn idle_config(idle_days: u32) -> IdleForgettingConfig {
+        IdleForgettingConfig {
+            enabled: true,
+            idle_days,
+            max_source_count: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_idle_forgetting_respects_access_age_threshold() {
+        let (conn, db, _temp) = setup_test_db().await;
+        insert_episode(&conn, "stale", 45, 1).await;
+        insert_episode(&conn, "recent", 15, 0).await;
+        insert_episode(&conn, "well_sourced", 45, 4).await;
+        insert_episode(&conn, "never_accessed", 0, 0).await;
+        conn.execute(
+            "UPDATE memories SET last_accessed = NULL, created_at = ?1 WHERE id = 'never_accessed'",
+            [(Utc::now() - Duration::days(60)).to_rfc3339()],
+        )
+        .await
+        .unwrap();
+
+        let manager = ForgettingManager::new(db, 3600).with_idle_forgetting(idle_config(30));
+        let forgotten = manager.run_once().await.unwrap();
+
+        assert_eq!(forgotten, 2);
+        assert_eq!(forgotten_ids(&conn).await, vec!["never_accessed", "stale"]);
+        let reason: String = conn
+            .query("SELECT forget_reason FROM memories WHERE id = 'stale'", ())
+            .await
+            .unwrap()
+            .next()
+            .await
+            .unwrap()
+            .unwrap()
+            .get(0)
+            .unwrap();
+        assert_eq!(reason, "auto-forgotten: not accessed in 30 days");
+    }
+
+    #[tokio::test]
+    async fn test_idle_forgetting_exempts_static_and_inference_sources() {
+        let (conn, db, _temp) = setup_test_db().await;
+        insert_episode(&conn, "pinned", 100, 0).await;
+        insert_episode(&conn, "derived_from", 100, 0).await;
+        insert_episode(&conn, "plain", 100, 0).await;
+        conn.execute("UPDATE memories SET is_static = 1 WHERE id = 'pinned'", ())
+            .await
+            .unwrap();
+        insert_memory(&conn, "inference", "inferred fact", None, false).await;
+        conn.execute(
+            r#"UPDATE memories SET is_inference = 1, memory_type = 'fact',
+               memory_relations = '{"derived_from":"derives"}' WHERE id = 'inference'"#,
+            (),
+        )
+        .await
+        .unwrap();
+
+        let manager = ForgettingManager::new(db, 3600).with_idle_forgetting(idle_config(30));
+        manager.run_once().await.unwrap();
+
+        assert_eq!(forgotten_ids(&conn).await, vec!["plain"]);
+    }
+
+    #[tokio::test]
+    async fn test_idle_forgetting_is_opt_in() {
+        let (conn, db, _temp) = setup_test_db().await;
+        insert_episode(&conn, "stale", 100, 0).await;
+
+        let disabled = IdleForgettingConfig {
+            enabled: false,
+            ..idle_config(30)
+        };
+        ForgettingManager::new(db.clone(), 3600)
+            .with_idle_forgetting(disabled)
+            .run_once()
+            .await
+            .unwrap();
+        ForgettingManager::new(db, 3600).run_once().await.unwrap();
+
+        assert!(forgotten_ids(&conn).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_preview_lists_each_memory_once_with_its_reason() {
+        let (conn, db, _temp) = setup_test_db().await;
+        let past = (Utc::now() - Duration::hours(1)).to_rfc3339();
+        insert_memory(&conn, "expired", "expired memory", Some(&past), false).await;
+        insert_episode(&conn, "stale", 100, 0).await;
+        conn.execute(
+            "UPDATE memories SET forget_after = ?1 WHERE id = 'stale'",
+            [past.as_str()],
+        )
+        .await
+        .unwrap();
+        insert_episode(&conn, "idle", 100, 0).await;
+
+        let manager = ForgettingManager::new(db, 3600).with_idle_forgetting(idle_config(30));
+        let mut planned: Vec<(String, String)> = manager
+            .preview()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|p| (p.memory.id, p.reason))
+            .collect();
+        planned.sort();
+
+        assert_eq!(
+            planned,
+            vec![
+                ("expired".to_string(), "auto-forgotten: expired".to_string()),
+                (
+                    "idle".to_string(),
+                    "auto-forgotten: not accessed in 30 days".to_string()
+                ),
+                ("stale".to_string(), "auto-forgotten: expired".to_string()),
+            ]
+        );
+        assert!(forgotten_ids(&conn).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_counts_without_forgetting() {
+        let (conn, db, _temp) = setup_test_db().await;
+        let past = (Utc::now() - Duration::hours(1)).to_rfc3339();
+        insert_memory(&conn, "mem1", "expired memory", Some(&past), false).await;
+        insert_episode(&conn, "stale", 100, 0).await;
+
+        let manager = ForgettingManager::new(db, 3600)
+            .with_idle_forgetting(idle_config(30))
+            .with_dry_run(true);
+        let forgotten = manager.run_once().await.unwrap();
+
+        assert_eq!(forgotten, 2);
+        assert!(forgotten_ids(&conn).await.is_empty());
+    }
+
     #[tokio::test]
     async fn test_manager_clone() {
         // Given a ForgettingManager
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use tracing::warn;
+
+use crate::db::DatabaseBackend;
+use crate::error::Result;
+use crate::models::JobKind;
+
+/// Handle used by a background task to report progress on its job record.
+///
+/// Bookkeeping failures are logged rather than returned so they never abort
+/// the operation being tracked.
+#[derive(Clone)]
+pub struct JobTracker {
+    db: Arc<dyn DatabaseBackend>,
+    job_id: String,
+}
+
+impl JobTracker {
+    /// Record a new queued job and return a tracker for it.
+    pub async fn create(
+        db: Arc<dyn DatabaseBackend>,
+        kind: JobKind,
+        total: Option<u64>,
+    ) -> Result<Self> {
+        let job = db.create_job(kind, total).await?;
+        Ok(Self { db, job_id: job.id })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.job_id
+    }
+
+    pub async fn start(&self) {
+        if let Err(e) = self.db.start_job(&self.job_id).await {
+            warn!(job_id = %self.job_id, error = %e, "Failed to mark job as running");
+        }
+    }
+
+    pub async fn progress(&self, processed: u64) {
+        if let Err(e) = self.db.update_job_progress(&self.job_id, processed).await {
+            warn!(job_id = %self.job_id, error = %e, "Failed to update job progress");
+        }
+    }
+
+    pub async fn complete(&self, result: serde_json::Value) {
+        if let Err(e) = self.db.finish_job(&self.job_id, Some(&result), None).await {
+            warn!(job_id = %self.job_id, error = %e, "Failed to mark job as done");
+        }
+    }
+
+    pub async fn fail(&self, error: &str) {
+        if let Err(e) = self.db.finish_job(&self.job_id, None, Some(error)).await {
+            warn!(job_id = %self.job_id, error = %e, "Failed to mark job as failed");
+        }
+    }
+}
@@ -8,28 +8,38 @@ use crate::config::Config;
 use crate::db::DatabaseBackend;
 use crate::embeddings::EmbeddingProvider;
 use crate::error::{MomoError, Result};
+use crate::intelligence::contradiction::ContradictionCheckResult;
+use crate::intelligence::language;
 use crate::intelligence::profile::ProfileGenerator;
 use crate::intelligence::types::HeuristicContext;
 use crate::intelligence::{ContradictionDetector, RelationshipDetector};
 use crate::llm::LlmProvider;
 use crate::models::{
+    ContradictionChange, ContradictionDetail, ContradictionResolution, ContradictionStatus,
     ForgetMemoryRequest, ForgetMemoryResponse, GetProfileRequest, HybridSearchRequest, Memory,
-    MemoryRelationType, MemoryType, ProfileFact, ProfileResponse, UpdateMemoryRequest,
-    UpdateMemoryResponse, UserProfileData,
+    MemoryRelationType, MemoryType, ProfileFact, ProfileFactOptions, ProfileResponse,
+    ResolveContradictionResponse, UpdateMemoryRequest, UpdateMemoryResponse, UserProfile,
+    UserProfileData,
 };
 use crate::services::search::SearchService;
 
+/// Facts a narrative is generated from, matching the background profile refresh.
+const NARRATIVE_FACT_LIMIT: u32 = 200;
+
 pub struct MemoryService {
     db: Arc<dyn DatabaseBackend>,
     embeddings: EmbeddingProvider,
     default_space_id: String,
     profile_generator: ProfileGenerator,
+    detect_language: bool,
+    /// One lock per container tag, held while its narrative is regenerated.
+    narrative_refreshes: Arc<std::sync::Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>>,
 }
 
 impl MemoryService {
     pub fn new(db: Arc<dyn DatabaseBackend>, embeddings: EmbeddingProvider) -> Self {
-        let llm_config = Config::from_env().llm;
-        let llm_provider = LlmProvider::new(llm_config.as_ref());
+        let config = Config::from_env();
+        let llm_provider = LlmProvider::new(config.llm.as_ref());
         let profile_generator = ProfileGenerator::new(llm_provider);
 
         Self {
@@ -37,6 +47,8 @@ impl MemoryService {
             embeddings,
             default_space_id: "default".to_string(),
             profile_generator,
+            detect_language: config.memory.language_detection,
+            narrative_refreshes: Default::default(),
         }
     }
 
@@ -54,6 +66,8 @@ impl MemoryService {
             false,
             None,
             MemoryType::Fact,
+            None,
+            None,
         )
         .await
     }
@@ -74,6 +88,8 @@ impl MemoryService {
             true,
             Some(metadata),
             MemoryType::Fact,
+            None,
+            None,
         )
         .await
     }
@@ -84,11 +100,36 @@ impl MemoryService {
         container_tag: &str,
         is_static: bool,
         memory_type: MemoryType,
+        confidence: Option<f64>,
+        forget_after: Option<DateTime<Utc>>,
     ) -> Result<Memory> {
-        self.create_memory_internal(content, container_tag, is_static, false, None, memory_type)
-            .await
+        if let Some(forget_after) = forget_after {
+            if forget_after <= Utc::now() {
+                return Err(MomoError::Validation(
+                    "forgetAfter must be in the future".to_string(),
+                ));
+            }
+            if is_static {
+                return Err(MomoError::Validation(
+                    "A static memory can't have forgetAfter".to_string(),
+                ));
+            }
+        }
+
+        self.create_memory_internal(
+            content,
+            container_tag,
+            is_static,
+            false,
+            None,
+            memory_type,
+            confidence,
+            forget_after,
+        )
+        .await
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn create_memory_internal(
         &self,
         content: &str,
@@ -97,9 +138,16 @@ impl MemoryService {
         is_inference: bool,
         metadata: Option<std::collections::HashMap<String, serde_json::Value>>,
         memory_type: MemoryType,
+        confidence: Option<f64>,
+        forget_after: Option<DateTime<Utc>>,
     ) -> Result<Memory> {
         let embedding = self.embeddings.embed_passage(content).await?;
 
+        let mut metadata = metadata.unwrap_or_default();
+        if self.detect_language {
+            language::tag_language(&mut metadata, content);
+        }
+
         let memory = Memory {
             id: nanoid!(),
             memory: content.to_string(),
@@ -114,7 +162,7 @@ impl MemoryService {
             is_inference,
             is_forgotten: false,
             is_static,
-            forget_after: None,
+            forget_after,
             forget_reason: None,
             memory_type,
             last_accessed: if memory_type == MemoryType::Episode {
@@ -122,8 +170,8 @@ impl MemoryService {
             } else {
                 None
             },
-            confidence: None,
-            metadata: metadata.unwrap_or_default(),
+            confidence,
+            metadata,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -132,6 +180,17 @@ impl MemoryService {
         self.db
             .update_memory_embedding(&memory.id, &embedding)
             .await?;
+        if let Err(error) = self
+            .db
+            .record_container_embedding_model(&self.embeddings.container_model(container_tag))
+            .await
+        {
+            tracing::warn!(
+                container_tag = %container_tag,
+                error = %error,
+                "Failed to record container embedding model (non-blocking)"
+            );
+        }
 
         let llm_config = Config::from_env().llm;
         if llm_config
@@ -149,7 +208,8 @@ impl MemoryService {
                 .as_ref()
                 .map(|c| c.enable_contradiction_detection)
                 .unwrap_or(false);
-            let llm_provider = LlmProvider::new(llm_config.as_ref());
+            // Each detection is its own run with a fresh LLM budget.
+            let llm_provider = LlmProvider::new(llm_config.as_ref()).for_run();
 
             tokio::spawn(async move {
                 let heuristic_ctx = if enable_contradiction {
@@ -190,6 +250,34 @@ impl MemoryService {
                     }
                 };
 
+                if let Some(ctx) = heuristic_ctx.as_ref().filter(|ctx| {
+                    ctx.heuristic_result == ContradictionCheckResult::Likely
+                        && detection.heuristic_overridden != Some(true)
+                }) {
+                    // The LLM did not apply an update for a likely contradiction, so the
+                    // pair stays unresolved until someone reviews it.
+                    let reasoning = detection
+                        .classifications
+                        .iter()
+                        .find(|c| c.memory_id == ctx.candidate_memory_id)
+                        .and_then(|c| c.reasoning.clone())
+                        .unwrap_or_else(|| {
+                            "Heuristic pattern match not confirmed by relationship detection"
+                                .to_string()
+                        });
+                    if let Err(error) = db
+                        .flag_memory_contradiction(
+                            &memory_id,
+                            &ctx.candidate_memory_id,
+                            container_tag.as_deref(),
+                            Some(&reasoning),
+                        )
+                        .await
+                    {
+                        tracing::error!(error = %error, "Failed to flag memory contradiction");
+                    }
+                }
+
                 for classification in detection.classifications {
                     if classification.relation_type == "none" {
                         continue;
@@ -227,15 +315,23 @@ impl MemoryService {
                     }
 
                     if relation_type == MemoryRelationType::Updates {
-                        if let Err(error) = db
-                            .update_memory_to_not_latest(&classification.memory_id)
-                            .await
-                        {
-                            tracing::error!(error = %error, "Failed to mark related memory not latest");
-                        }
-
                         match db.get_memory_by_id(&classification.memory_id).await {
                             Ok(Some(old)) => {
+                                match db.supersede_memory(&old.id, old.version).await {
+                                    Ok(true) => {}
+                                    Ok(false) => {
+                                        tracing::warn!(
+                                            old_id = %old.id,
+                                            "Related memory was superseded concurrently, skipping version chain"
+                                        );
+                                        continue;
+                                    }
+                                    Err(error) => {
+                                        tracing::error!(error = %error, "Failed to mark related memory not latest");
+                                        continue;
+                                    }
+                                }
+
                                 let root_id =
                                     old.root_memory_id.clone().unwrap_or_else(|| old.id.clone());
                                 let new_version = old.version + 1;
@@ -284,16 +380,46 @@ impl MemoryService {
 
         let existing =
             existing.ok_or_else(|| MomoError::NotFound("Memory not found".to_string()))?;
-
-        self.db.update_memory_to_not_latest(&existing.id).await?;
+        if existing.is_forgotten {
+            return Err(MomoError::Conflict(format!(
+                "Memory {} has been forgotten and can't be edited",
+                existing.id
+            )));
+        }
 
         let new_embedding = self.embeddings.embed_passage(&req.new_content).await?;
 
+        match req.expected_version {
+            Some(expected_version) => {
+                if !self
+                    .db
+                    .supersede_memory(&existing.id, expected_version)
+                    .await?
+                {
+                    return Err(MomoError::Conflict(format!(
+                        "Memory {} is no longer the latest version {expected_version}; refetch and retry",
+                        existing.id
+                    )));
+                }
+            }
+            None => self.db.update_memory_to_not_latest(&existing.id).await?,
+        }
+
         let root_id = existing
             .root_memory_id
             .clone()
             .unwrap_or_else(|| existing.id.clone());
 
+        let inherits_metadata = req.metadata.is_none();
+        let mut metadata = req.metadata.unwrap_or_else(|| existing.metadata.clone());
+        if self.detect_language {
+            if inherits_metadata {
+                // The inherited tag describes the previous content; detect it afresh.
+                metadata.remove(language::LANGUAGE_METADATA_KEY);
+            }
+            language::tag_language(&mut metadata, &req.new_content);
+        }
+
         let new_memory = Memory {
             id: nanoid!(),
             memory: req.new_content.clone(),
@@ -320,7 +446,7 @@ impl MemoryService {
             memory_type: existing.memory_type,
             last_accessed: existing.last_accessed,
             confidence: existing.confidence,
-            metadata: req.metadata.unwrap_or(existing.metadata),
+            metadata,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -366,6 +492,260 @@ impl MemoryService {
         })
     }
 
+    /// Bring back a forgotten memory so searches include it again. Also clears
+    /// a scheduled expiry on a memory that hasn't been forgotten yet.
+    pub async fn restore_memory(&self, id: &str) -> Result<Memory> {
+        if self
+            .db
+            .get_memory_by_id_including_forgotten(id)
+            .await?
+            .is_none()
+        {
+            return Err(MomoError::NotFound(format!("Memory {id} not found")));
+        }
+
+        self.db.restore_memory(id).await?;
+        self.db
+            .get_memory_by_id(id)
+            .await?
+            .ok_or_else(|| MomoError::NotFound(format!("Memory {id} not found")))
+    }
+
+    /// List flagged contradictions together with the content of both memories.
+    pub async fn list_contradictions(
+        &self,
+        container_tag: Option<&str>,
+        include_resolved: bool,
+        limit: u32,
+        offset: u32,
+    ) -> Result<(Vec<ContradictionDetail>, u64)> {
+        let (contradictions, total) = self
+            .db
+            .list_memory_contradictions(container_tag, include_resolved, limit, offset)
+            .await?;
+
+        let ids: Vec<String> = contradictions
+            .iter()
+            .flat_map(|c| [c.memory_id.clone(), c.conflicting_memory_id.clone()])
+            .collect();
+        let memories: HashMap<String, Memory> = self
+            .db
+            .get_memories_by_ids(&ids)
+            .await?
+            .into_iter()
+            .map(|m| (m.id.clone(), m))
+            .collect();
+
+        let details = contradictions
+            .into_iter()
+            .filter_map(|contradiction| {
+                let memory = memories.get(&contradiction.memory_id)?.clone();
+                let conflicting_memory =
+                    memories.get(&contradiction.conflicting_memory_id)?.clone();
+                Some(ContradictionDetail {
+                    contradiction,
+                    memory,
+                    conflicting_memory,
+                })
+            })
+            .collect();
+        Ok((details, total))
+    }
+
+    /// Apply a decision to a flagged contradiction.
+    ///
+    /// The kept memory supersedes the dropped one: if it is the newer of the two it
+    /// becomes the next version in the dropped memory's chain, otherwise the newer
+    /// dropped memory is forgotten. `KeepBoth` only closes the flag.
+    pub async fn resolve_contradiction(
+        &self,
+        id: &str,
+        resolution: ContradictionResolution,
+    ) -> Result<ResolveContradictionResponse> {
+        let contradiction = self
+            .db
+            .get_memory_contradiction(id)
+            .await?
+            .ok_or_else(|| MomoError::NotFound("Contradiction not found".to_string()))?;
+
+        if contradiction.status == ContradictionStatus::Resolved {
+            return Err(MomoError::Validation(
+                "Contradiction is already resolved".to_string(),
+            ));
+        }
+
+        let (kept_id, dropped_id) = match resolution {
+            ContradictionResolution::KeepA => (
+                &contradiction.memory_id,
+                Some(&contradiction.conflicting_memory_id),
+            ),
+            ContradictionResolution::KeepB => (
+                &contradiction.conflicting_memory_id,
+                Some(&contradiction.memory_id),
+            ),
+            ContradictionResolution::KeepBoth => (&contradiction.memory_id, None),
+        };
+
+        let change = match dropped_id {
+            Some(dropped_id) => {
+                let kept = self
+                    .db
+                    .get_memory_by_id(kept_id)
+                    .await?
+                    .ok_or_else(|| MomoError::NotFound("Memory not found".to_string()))?;
+                let dropped = self
+                    .db
+                    .get_memory_by_id(dropped_id)
+                    .await?
+                    .ok_or_else(|| MomoError::NotFound("Memory not found".to_string()))?;
+
+                if kept.created_at >= dropped.created_at {
+                    ContradictionChange::Supersede {
+                        root_id: dropped
+                            .root_memory_id
+                            .clone()
+                            .unwrap_or_else(|| dropped.id.clone()),
+                        version: dropped.version + 1,
+                        kept_id: kept.id,
+                        dropped_id: dropped.id,
+                    }
+                } else {
+                    ContradictionChange::Forget {
+                        reason: format!("Contradicted by memory {}", kept.id),
+                        dropped_id: dropped.id,
+                    }
+                }
+            }
+            None => ContradictionChange::None,
+        };
+
+        // Claiming the open flag and changing the memories happen in one
+        // transaction, so concurrent resolutions can't both apply.
+        if !self
+            .db
+            .resolve_memory_contradiction(&contradiction.id, resolution, &change)
+            .await?
+        {
+            return Err(MomoError::Validation(
+                "Contradiction is already resolved".to_string(),
+            ));
+        }
+
+        let superseded_memory_id = match change {
+            ContradictionChange::Supersede { dropped_id, .. }
+            | ContradictionChange::Forget { dropped_id, .. } => Some(dropped_id),
+            ContradictionChange::None => None,
+        };
+
+        let kept_memory_ids = match resolution {
+            ContradictionResolution::KeepBoth => vec![
+                contradiction.memory_id.clone(),
+                contradiction.conflicting_memory_id.clone(),
+            ],
+            _ => vec![kept_id.clone()],
+        };
+
+        Ok(ResolveContradictionResponse {
+            id: contradiction.id,
+            resolution,
+            kept_memory_ids,
+            superseded_memory_id,
+        })
+    }
+
+    /// A container's profile with its cached narrative, or with a narrative
+    /// regenerated from its current memories when `refresh` is set. Without a
+    /// cached narrative, or when regeneration fails or no LLM is configured,
+    /// the profile has only its facts. Only one refresh of a container runs
+    /// at a time: requests arriving during one wait for it and return the
+    /// narrative it produced.
+    pub async fn get_profile_narrative(
+        &self,
+        container_tag: &str,
+        include_dynamic: bool,
+        limit: u32,
+        options: &ProfileFactOptions,
+        refresh: bool,
+    ) -> Result<UserProfile> {
+        let mut profile = self
+            .db
+            .get_user_profile(container_tag, include_dynamic, limit, options)
+            .await?;
+
+        if refresh {
+            let lock = self
+                .narrative_refreshes
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .entry(container_tag.to_string())
+                .or_default()
+                .clone();
+
+            let refreshed = match lock.try_lock() {
+                Ok(_guard) => self.refresh_narrative(container_tag).await,
+                Err(_) => {
+                    let _guard = lock.lock().await;
+                    Ok(None)
+                }
+            };
+            match refreshed {
+                Ok(Some(narrative)) => {
+                    profile.narrative = Some(narrative);
+                    return Ok(profile);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!(container_tag, error = %e, "Failed to refresh profile narrative");
+                }
+            }
+        }
+
+        profile.narrative = self
+            .db
+            .get_cached_profile(container_tag)
+            .await?
+            .and_then(|cached| cached.narrative);
+        Ok(profile)
+    }
+
+    /// Generate a narrative from a container's current facts and cache it,
+    /// keeping the cached summary. `None` when nothing was generated.
+    async fn refresh_narrative(&self, container_tag: &str) -> Result<Option<String>> {
+        let profile = self
+            .db
+            .get_user_profile(
+                container_tag,
+                true,
+                NARRATIVE_FACT_LIMIT,
+                &ProfileFactOptions::default(),
+            )
+            .await?;
+        let all_facts: Vec<&str> = profile
+            .static_facts
+            .iter()
+            .chain(profile.dynamic_facts.iter())
+            .map(|f| f.memory.as_str())
+            .collect();
+
+        let narrative = self
+            .profile_generator
+            .generate_narrative(&all_facts)
+            .await?;
+        if narrative.is_empty() {
+            return Ok(None);
+        }
+
+        let summary = self
+            .db
+            .get_cached_profile(container_tag)
+            .await?
+            .and_then(|cached| cached.summary);
+        self.db
+            .upsert_cached_profile(container_tag, Some(&narrative), summary.as_deref())
+            .await?;
+        Ok(Some(narrative))
+    }
+
     pub async fn get_profile(
         &self,
         req: GetProfileRequest,
@@ -379,6 +759,7 @@ impl MemoryService {
                 &req.container_tag,
                 req.include_dynamic.unwrap_or(true),
                 req.limit.unwrap_or(50),
+                &ProfileFactOptions::default(),
             )
             .await?;
 
@@ -497,6 +878,8 @@ impl Clone for MemoryService {
             embeddings: self.embeddings.clone(),
             default_space_id: self.default_space_id.clone(),
             profile_generator: ProfileGenerator::new(llm_provider),
+            detect_language: self.detect_language,
+            narrative_refreshes: self.narrative_refreshes.clone(),
         }
     }
 }
@@ -523,7 +906,7 @@ async fn build_heuristic_context(
     container_tag: Option<&str>,
 ) -> Result<Option<HeuristicContext>> {
     let candidates = db
-        .search_similar_memories(embedding, 5, 0.7, container_tag, false)
+        .search_similar_memories(embedding, 5, 0.7, container_tag, None, false)
         .await?
         .into_iter()
         .filter(|hit| hit.memory.id != new_memory_id)
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tracing::{debug, info, warn};
+
+use crate::config::{AlternateEmbeddingsConfig, Config};
+use crate::db::DatabaseBackend;
+use crate::embeddings::EmbeddingProvider;
+use crate::error::Result;
+
+/// Embedding providers for the configured alternate models, keyed by model name.
+pub type AlternateEmbeddings = Arc<HashMap<String, EmbeddingProvider>>;
+
+/// Load a provider for every configured alternate model, including models
+/// containers are pinned to. Models equal to the primary embedding model are
+/// skipped since their embeddings already live in the memories table.
+pub fn load_alternate_embeddings(config: &Config) -> Result<AlternateEmbeddings> {
+    let alternate = &config.memory.alternate_embeddings;
+    let mut providers = HashMap::new();
+    for model in alternate
+        .models
+        .iter()
+        .chain(alternate.container_models.values())
+    {
+        if *model == config.embeddings.model || providers.contains_key(model) {
+            continue;
+        }
+        info!("Loading alternate embedding model: {}...", model);
+        let provider = EmbeddingProvider::for_alternate_model(model, config.embeddings.batch_size)?;
+        providers.insert(model.clone(), provider);
+    }
+
+    Ok(Arc::new(providers))
+}
+
+/// Background job that embeds memories with each alternate model, so searches
+/// can be pointed at any of them without re-embedding the primary index.
+#[derive(Clone)]
+pub struct MemoryEmbeddingBackfill {
+    db: Arc<dyn DatabaseBackend>,
+    providers: AlternateEmbeddings,
+    interval_secs: u64,
+    batch_size: usize,
+}
+
+impl MemoryEmbeddingBackfill {
+    pub fn new(
+        db: Arc<dyn DatabaseBackend>,
+        providers: AlternateEmbeddings,
+        config: &AlternateEmbeddingsConfig,
+    ) -> Self {
+        Self {
+            db,
+            providers,
+            interval_secs: config.backfill_interval_secs,
+            batch_size: config.backfill_batch_size.max(1),
+        }
+    }
+
+    pub fn interval_secs(&self) -> u64 {
+        self.interval_secs
+    }
+
+    /// Embed up to one batch of missing memories per model.
+    /// Returns the number of embeddings stored.
+    pub async fn run_once(&self) -> Result<u64> {
+        let mut stored = 0u64;
+
+        for (model, provider) in self.providers.iter() {
+            let memories = self
+                .db
+                .get_memories_missing_model_embedding(model, self.batch_size)
+                .await?;
+            if memories.is_empty() {
+                continue;
+            }
+
+            let texts: Vec<String> = memories.iter().map(|m| m.memory.clone()).collect();
+            let vectors = match provider.embed_passages(texts).await {
+                Ok(vectors) => vectors,
+                Err(e) => {
+                    warn!(model = %model, error = %e, "Failed to embed memories for alternate model");
+                    continue;
+                }
+            };
+
+            let embeddings: Vec<(String, Vec<f32>)> =
+                memories.into_iter().map(|m| m.id).zip(vectors).collect();
+            self.db
+                .upsert_memory_model_embeddings(model, &embeddings)
+                .await?;
+
+            debug!(model = %model, count = embeddings.len(), "Stored alternate memory embeddings");
+            stored += embeddings.len() as u64;
+        }
+
+        if stored > 0 {
+            info!(stored, "Alternate embedding backfill complete");
+        }
+
+        Ok(stored)
+    }
+}
@@ -1,11 +1,23 @@
+mod chunk_reembedding;
 mod episode_decay;
 mod forgetting;
+mod jobs;
 mod memory;
+mod memory_embeddings;
 pub mod profile_refresh;
 mod search;
+pub mod search_probe;
+mod webhooks;
 
-pub use episode_decay::EpisodeDecayManager;
-pub use forgetting::ForgettingManager;
+pub use chunk_reembedding::ChunkReembedder;
+pub use episode_decay::{EpisodeDecayManager, PlannedDecay};
+pub use forgetting::{ForgettingManager, PlannedForget};
+pub use jobs::JobTracker;
 pub use memory::MemoryService;
+pub use memory_embeddings::{
+    load_alternate_embeddings, AlternateEmbeddings, MemoryEmbeddingBackfill,
+};
 pub use profile_refresh::ProfileRefreshManager;
-pub use search::SearchService;
+pub use search::{SearchService, MAX_BATCH_QUERIES, MAX_CONTEXT_CHUNKS};
+pub use search_probe::SearchQualityProbe;
+pub use webhooks::WebhookNotifier;
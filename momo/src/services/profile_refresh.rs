@@ -6,6 +6,7 @@ use crate::db::DatabaseBackend;
 use crate::error::Result;
 use crate::intelligence::profile::ProfileGenerator;
 use crate::llm::LlmProvider;
+use crate::models::ProfileFactOptions;
 
 use tracing::{debug, error, info, warn};
 
@@ -57,11 +58,22 @@ impl ProfileRefreshManager {
             tags.len()
         );
 
-        let profile_generator = ProfileGenerator::new(self.llm.clone());
+        // Each run gets a fresh LLM budget.
+        let llm = self.llm.for_run();
+        let profile_generator = ProfileGenerator::new(llm.clone());
         let mut refreshed_count = 0u64;
         let mut error_count = 0u64;
 
-        for tag in &tags {
+        for (checked_count, tag) in tags.iter().enumerate() {
+            if llm.budget_exhausted() {
+                warn!(
+                    reason = "budget_exhausted",
+                    tags_remaining = tags.len() - checked_count,
+                    "LLM budget for this profile refresh run is exhausted, stopping early"
+                );
+                break;
+            }
+
             match self.refresh_tag(tag, &profile_generator).await {
                 Ok(true) => {
                     refreshed_count += 1;
@@ -87,7 +99,13 @@ impl ProfileRefreshManager {
             }
         }
 
+        let (llm_calls, llm_tokens) = llm
+            .usage()
+            .map_or((0, 0), |usage| (usage.calls(), usage.tokens()));
         info!(
+            llm_calls,
+            llm_tokens,
+            budget_exhausted = llm.budget_exhausted(),
             "Profile refresh complete: {} refreshed, {} errors out of {} tags",
             refreshed_count,
             error_count,
@@ -130,7 +148,10 @@ impl ProfileRefreshManager {
         }
 
         // Fetch all active memories for this tag to generate profile
-        let profile = self.db.get_user_profile(container_tag, true, 200).await?;
+        let profile = self
+            .db
+            .get_user_profile(container_tag, true, 200, &ProfileFactOptions::default())
+            .await?;
 
         let all_facts: Vec<&str> = profile
             .static_facts
@@ -253,6 +274,7 @@ mod tests {
                 memory_type TEXT NOT NULL DEFAULT 'fact',
                 last_accessed TEXT,
                 confidence REAL,
+                importance REAL,
                 metadata TEXT NOT NULL DEFAULT '{}',
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL,
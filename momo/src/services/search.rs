@@ -1,19 +1,59 @@
-use std::collections::{HashMap, HashSet};
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-use crate::db::DatabaseBackend;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use crate::db::{DatabaseBackend, ReplicaHealth};
 use crate::embeddings::{EmbeddingProvider, RerankerProvider};
 use crate::error::{MomoError, Result};
-use crate::intelligence::TemporalSearchRanker;
+use crate::intelligence::importance::boost_score;
+use crate::intelligence::{blend_freshness, freshness, TemporalSearchRanker};
 use crate::llm::LlmProvider;
 use crate::models::{
-    ChunkSearchResult, Document, DocumentSearchResult, HybridSearchRequest, HybridSearchResponse,
-    HybridSearchResult, Memory, MemoryContext, MemoryRelationInfo, MemorySearchResult,
-    SearchDocumentsRequest, SearchDocumentsResponse, SearchFilters, SearchMemoriesRequest,
-    SearchMemoriesResponse, SearchMode,
+    is_low_confidence, ChunkSearchResult, ChunkWithDocument, ContainerEmbeddingModel, Document,
+    DocumentSearchResult, FilterCondition, FilterExpr, FilterOperator, HighlightMarkers,
+    HybridSearchRequest, HybridSearchResponse, HybridSearchResult, HybridWeights, Memory,
+    MemoryContext, MemoryDecayDebug, MemoryRelationInfo, MemoryRelationType, MemorySearchHit,
+    MemorySearchResult, Metadata, ScoreCalibration, SearchDocumentsRequest,
+    SearchDocumentsResponse, SearchFallback, SearchFilters, SearchMemoriesRequest,
+    SearchMemoriesResponse, SearchMode, SearchStage, SearchStreamSummary,
+};
+use crate::search::{
+    best_sentence, build_snippet, expand_query, mmr_select, split_sentences, QueryEmbeddingCache,
+    QueryRewriteCache, SynonymMap, DEFAULT_DIVERSITY_LAMBDA,
 };
-use crate::search::QueryRewriteCache;
+use crate::services::AlternateEmbeddings;
+
+/// Upper bound on neighboring chunks attached on each side of a matched chunk.
+pub const MAX_CONTEXT_CHUNKS: u32 = 5;
+
+/// Retries allowed when relaxing the threshold to reach `min_results`.
+const MAX_RELAXATION_STEPS: usize = 3;
+
+/// Rank offset damping the weight of top ranks in reciprocal rank fusion.
+const RRF_K: f32 = 60.0;
+
+/// Deepest ranked result reachable by paging with `cursor` or `offset`.
+const MAX_SEARCH_DEPTH: u32 = 1000;
+
+/// Hits fetched by the first query of a streamed memory search; each later
+/// query doubles it.
+const STREAM_FIRST_DEPTH: u32 = 10;
+
+/// Upper bound on queries in one batch search.
+pub const MAX_BATCH_QUERIES: usize = 20;
+
+/// Searches of a batch that run concurrently.
+const BATCH_SEARCH_CONCURRENCY: usize = 4;
 
 #[derive(Clone)]
 pub struct SearchService {
@@ -23,8 +63,68 @@ pub struct SearchService {
     reranker: Option<RerankerProvider>,
     llm: LlmProvider,
     rewrite_cache: Option<QueryRewriteCache>,
+    query_embedding_cache: Option<QueryEmbeddingCache>,
     episode_decay_days: f64,
     episode_decay_factor: f64,
+    max_response_time: Option<Duration>,
+    dedupe_version_chains: bool,
+    /// Cap on parents, children and related memories attached to each hit.
+    max_context_memories: usize,
+    importance_boost: f64,
+    embedding_model: String,
+    alternate_embeddings: AlternateEmbeddings,
+    synonyms: Arc<SynonymMap>,
+    min_results_threshold_floor: f32,
+    lexical_fallback: bool,
+    lexical_fallback_limit: u32,
+    highlight_markers: HighlightMarkers,
+    score_sigmoid_midpoint: f32,
+    score_sigmoid_steepness: f32,
+    freshness_half_life_days: f64,
+    /// Replica sync health; reads go to `write_db` while it is tripped.
+    replica: Option<ReplicaHealth>,
+}
+
+/// Overall deadline for a single search request.
+///
+/// Mandatory stages (embedding, vector search) always run; optional stages
+/// check the deadline first and are skipped once the budget is spent.
+#[derive(Debug, Clone, Copy)]
+struct SearchDeadline {
+    deadline: Option<Instant>,
+}
+
+impl SearchDeadline {
+    fn new(start: Instant, budget: Option<Duration>) -> Self {
+        Self {
+            deadline: budget.map(|budget| start + budget),
+        }
+    }
+
+    fn is_exceeded(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Clamp a per-stage timeout so it never runs past the request deadline.
+    fn cap(&self, timeout: Duration) -> Duration {
+        match self.deadline {
+            Some(deadline) => timeout.min(deadline.saturating_duration_since(Instant::now())),
+            None => timeout,
+        }
+    }
+
+    /// Run an optional stage, returning `None` if the deadline hits first.
+    async fn run<F: Future>(&self, stage: F) -> Option<F::Output> {
+        match self.deadline {
+            Some(deadline) => {
+                tokio::time::timeout_at(tokio::time::Instant::from_std(deadline), stage)
+                    .await
+                    .ok()
+            }
+            None => Some(stage.await),
+        }
+    }
 }
 
 fn apply_metadata_filters(
@@ -35,56 +135,698 @@ fn apply_metadata_filters(
         return results;
     };
 
+    let expr = filters.to_expr();
     results
         .into_iter()
-        .filter(|result| {
-            let matches_and = if let Some(ref conditions) = filters.and {
-                conditions.iter().all(|cond| {
-                    let matches = result
-                        .metadata
-                        .get(&cond.key)
-                        .and_then(|value| value.as_str())
-                        .is_some_and(|value| value == cond.value);
-                    if cond.negate.unwrap_or(false) {
-                        !matches
-                    } else {
-                        matches
-                    }
-                })
+        .filter(|result| expr_matches(&expr, &result.metadata))
+        .collect()
+}
+
+/// Whether `metadata` satisfies the filter expression `expr`.
+fn expr_matches(expr: &FilterExpr, metadata: &Metadata) -> bool {
+    match expr {
+        FilterExpr::And(exprs) => exprs.iter().all(|expr| expr_matches(expr, metadata)),
+        FilterExpr::Or(exprs) => exprs.iter().any(|expr| expr_matches(expr, metadata)),
+        FilterExpr::Not(expr) => !expr_matches(expr, metadata),
+        FilterExpr::Condition(cond) => condition_matches(cond, metadata),
+    }
+}
+
+/// Whether `metadata` satisfies `cond`, honouring `negate`.
+fn condition_matches(cond: &FilterCondition, metadata: &Metadata) -> bool {
+    let value = resolve_metadata_key(metadata, &cond.key).filter(|value| !value.is_null());
+    let matches = match (cond.operator.unwrap_or_default(), value) {
+        (FilterOperator::Exists, value) => value.is_some(),
+        (_, None) => false,
+        (FilterOperator::Contains, Some(serde_json::Value::String(value))) => cond
+            .value
+            .as_str()
+            .is_some_and(|needle| value.contains(needle)),
+        (FilterOperator::Contains, Some(serde_json::Value::Array(items))) => items
+            .iter()
+            .any(|item| compare_values(item, &cond.value) == Some(std::cmp::Ordering::Equal)),
+        (FilterOperator::Contains, Some(_)) => false,
+        (operator, Some(value)) => {
+            compare_values(value, &cond.value).is_some_and(|ordering| match operator {
+                FilterOperator::Eq => ordering.is_eq(),
+                FilterOperator::Ne => ordering.is_ne(),
+                FilterOperator::Gt => ordering.is_gt(),
+                FilterOperator::Gte => ordering.is_ge(),
+                FilterOperator::Lt => ordering.is_lt(),
+                FilterOperator::Lte => ordering.is_le(),
+                FilterOperator::Contains | FilterOperator::Exists => unreachable!(),
+            })
+        }
+    };
+
+    if cond.negate.unwrap_or(false) {
+        !matches
+    } else {
+        matches
+    }
+}
+
+/// Metadata value at `key`. A key that isn't present as-is is read as a
+/// dot path through nested objects and arrays, e.g. `author.name` or `tags.0`;
+/// a missing step resolves to `None`.
+fn resolve_metadata_key<'a>(metadata: &'a Metadata, key: &str) -> Option<&'a serde_json::Value> {
+    if let Some(value) = metadata.get(key) {
+        return Some(value);
+    }
+
+    let mut segments = key.split('.');
+    let mut value = metadata.get(segments.next()?)?;
+    for segment in segments {
+        value = match value {
+            serde_json::Value::Object(map) => map.get(segment)?,
+            serde_json::Value::Array(items) => items.get(segment.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+/// Order of a metadata value relative to a filter value of the same JSON
+/// type; `None` for mismatched or unordered types.
+fn compare_values(
+    value: &serde_json::Value,
+    filter: &serde_json::Value,
+) -> Option<std::cmp::Ordering> {
+    match (value, filter) {
+        (serde_json::Value::Number(value), serde_json::Value::Number(filter)) => {
+            value.as_f64()?.partial_cmp(&filter.as_f64()?)
+        }
+        (serde_json::Value::String(value), serde_json::Value::String(filter)) => {
+            Some(value.cmp(filter))
+        }
+        (serde_json::Value::Bool(value), serde_json::Value::Bool(filter)) => {
+            Some(value.cmp(filter))
+        }
+        _ => None,
+    }
+}
+
+/// Split the chunks around `position` into the `window` chunks before and after
+/// it, in document order. Missing positions (document edges) are skipped.
+fn neighbor_context(
+    neighbors: &HashMap<i32, String>,
+    position: i32,
+    window: i32,
+) -> (Vec<String>, Vec<String>) {
+    let collect = |range: std::ops::RangeInclusive<i32>| {
+        range
+            .filter_map(|pos| neighbors.get(&pos).cloned())
+            .collect::<Vec<_>>()
+    };
+
+    (
+        collect(position.saturating_sub(window)..=position - 1),
+        collect(position + 1..=position.saturating_add(window)),
+    )
+}
+
+/// Score multiplier for a result carrying `tags`: the highest weight among its
+/// weighted tags, or 1.0 when none of them are weighted.
+fn container_tag_weight(weights: &HashMap<String, f32>, tags: &[String]) -> f32 {
+    tags.iter()
+        .filter_map(|tag| weights.get(tag).copied())
+        .reduce(f32::max)
+        .unwrap_or(1.0)
+}
+
+/// Number of results a search asking for `min_results` should reach, capped at
+/// the effective `limit`. `None` when relaxation was not requested.
+fn min_results_target(min_results: Option<u32>, limit: Option<u32>) -> Option<usize> {
+    let limit = limit.unwrap_or(10).min(100);
+    min_results
+        .filter(|min| *min > 0)
+        .map(|min| min.min(limit) as usize)
+}
+
+/// Thresholds to retry a sparse search with, evenly spaced from just below
+/// `threshold` down to `floor`. Empty when `threshold` is already at the floor.
+fn relaxed_thresholds(threshold: f32, floor: f32) -> Vec<f32> {
+    if threshold <= floor {
+        return Vec::new();
+    }
+    let step = (threshold - floor) / MAX_RELAXATION_STEPS as f32;
+    (1..=MAX_RELAXATION_STEPS)
+        .map(|i| {
+            if i == MAX_RELAXATION_STEPS {
+                floor
             } else {
-                true
-            };
+                threshold - step * i as f32
+            }
+        })
+        .collect()
+}
 
-            let matches_or = if let Some(ref conditions) = filters.or {
-                conditions.iter().any(|cond| {
-                    let matches = result
-                        .metadata
-                        .get(&cond.key)
-                        .and_then(|value| value.as_str())
-                        .is_some_and(|value| value == cond.value);
-                    if cond.negate.unwrap_or(false) {
-                        !matches
+/// `scores` transformed by `calibration`, in the same order.
+fn calibrated_scores(
+    scores: &[f32],
+    calibration: ScoreCalibration,
+    sigmoid_midpoint: f32,
+    sigmoid_steepness: f32,
+) -> Vec<f32> {
+    match calibration {
+        ScoreCalibration::MinMax => {
+            let min = scores.iter().copied().fold(f32::INFINITY, f32::min);
+            let max = scores.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+            let range = max - min;
+            scores
+                .iter()
+                .map(|score| {
+                    // A single result, or all tied, counts as the best match.
+                    if range > f32::EPSILON {
+                        (score - min) / range
                     } else {
-                        matches
+                        1.0
                     }
                 })
-            } else {
-                true
-            };
+                .collect()
+        }
+        ScoreCalibration::Sigmoid => scores
+            .iter()
+            .map(|score| 1.0 / (1.0 + (-sigmoid_steepness * (score - sigmoid_midpoint)).exp()))
+            .collect(),
+    }
+}
+
+/// Order of ranked results: best score first, ties broken by id so every page
+/// of a query sees the same sequence.
+fn rank_order(a: (f32, &str), b: (f32, &str)) -> std::cmp::Ordering {
+    b.0.partial_cmp(&a.0)
+        .unwrap_or(std::cmp::Ordering::Equal)
+        .then_with(|| a.1.cmp(b.1))
+}
+
+/// Last result of a previous page, as carried by the opaque `cursor` token.
+///
+/// Holds the final ranking score (after reranking and weighting) rather than
+/// the raw vector score, so the page boundary matches what the client saw.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct SearchCursor {
+    /// Results returned before the next page.
+    position: usize,
+    score: f32,
+    id: String,
+}
+
+impl SearchCursor {
+    fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        URL_SAFE_NO_PAD.encode(json)
+    }
+
+    fn decode(token: &str) -> Result<Self> {
+        URL_SAFE_NO_PAD
+            .decode(token)
+            .ok()
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .ok_or_else(|| MomoError::Validation("Invalid search cursor".to_string()))
+    }
+}
+
+/// Where the requested page starts within the ranked results.
+#[derive(Debug, Default)]
+struct PageStart {
+    position: usize,
+    after: Option<SearchCursor>,
+}
+
+impl PageStart {
+    fn from_request(cursor: Option<&str>, offset: Option<u32>) -> Result<Self> {
+        match (cursor, offset) {
+            (Some(_), Some(_)) => Err(MomoError::Validation(
+                "Use either cursor or offset, not both".to_string(),
+            )),
+            (Some(token), None) => {
+                let after = SearchCursor::decode(token)?;
+                Ok(Self {
+                    position: after.position,
+                    after: Some(after),
+                })
+            }
+            (None, offset) => Ok(Self {
+                position: offset.unwrap_or(0) as usize,
+                after: None,
+            }),
+        }
+    }
+
+    /// Ranked results to fetch to fill a page of `limit` and tell whether
+    /// another follows, capped at [`MAX_SEARCH_DEPTH`].
+    fn depth(&self, limit: u32) -> u32 {
+        (self.position as u32)
+            .saturating_add(limit)
+            .saturating_add(1)
+            .min(MAX_SEARCH_DEPTH)
+    }
+}
+
+/// Search hit that can be paged through.
+trait RankedResult {
+    /// Final ranking score, after reranking and weighting, and the id that
+    /// breaks ties.
+    fn ranking_key(&self) -> (f32, &str);
+}
+
+impl RankedResult for DocumentSearchResult {
+    fn ranking_key(&self) -> (f32, &str) {
+        (self.rerank_score.unwrap_or(self.score), &self.document_id)
+    }
+}
+
+impl RankedResult for MemorySearchResult {
+    fn ranking_key(&self) -> (f32, &str) {
+        (self.rerank_score.unwrap_or(self.similarity), &self.id)
+    }
+}
+
+impl RankedResult for HybridSearchResult {
+    fn ranking_key(&self) -> (f32, &str) {
+        (self.rerank_score.unwrap_or(self.similarity), &self.id)
+    }
+}
+
+/// Cut the page starting at `start` out of `results`, which must be sorted by
+/// [`rank_order`]. Returns the cursor for the following page, or `None` when
+/// this page is the last.
+fn take_page<T: RankedResult>(
+    results: &mut Vec<T>,
+    start: &PageStart,
+    limit: usize,
+) -> Option<String> {
+    let skip = match &start.after {
+        // Seek past the previous page's last result. If it has since dropped
+        // out of the results, skip everything ranking at or before it.
+        Some(after) => results
+            .iter()
+            .position(|result| result.ranking_key().1 == after.id)
+            .map(|index| index + 1)
+            .unwrap_or_else(|| {
+                results
+                    .iter()
+                    .take_while(|result| {
+                        rank_order(result.ranking_key(), (after.score, &after.id)).is_le()
+                    })
+                    .count()
+            }),
+        None => start.position,
+    };
+    results.drain(..skip.min(results.len()));
+
+    let has_more = results.len() > limit;
+    results.truncate(limit);
+    let last = results.last().filter(|_| has_more)?;
+    let (score, id) = last.ranking_key();
+    Some(
+        SearchCursor {
+            position: start.position + results.len(),
+            score,
+            id: id.to_string(),
+        }
+        .encode(),
+    )
+}
+
+/// MMR lambda to diversify document search results with, or `None` when
+/// diversification was not requested. Rejects lambdas outside 0–1.
+fn diversity_lambda(diversify: Option<bool>, lambda: Option<f32>) -> Result<Option<f32>> {
+    if !diversify.unwrap_or(false) {
+        return Ok(None);
+    }
+    match lambda {
+        None => Ok(Some(DEFAULT_DIVERSITY_LAMBDA)),
+        Some(lambda) if (0.0..=1.0).contains(&lambda) => Ok(Some(lambda)),
+        Some(lambda) => Err(MomoError::Validation(format!(
+            "Diversity lambda must be between 0 and 1, got {lambda}"
+        ))),
+    }
+}
+
+/// Validated freshness weight, or `None` when it has no effect. Rejects
+/// weights outside 0–1.
+fn freshness_weight(weight: Option<f32>) -> Result<Option<f32>> {
+    match weight {
+        None => Ok(None),
+        Some(0.0) => Ok(None),
+        Some(weight) if (0.0..=1.0).contains(&weight) => Ok(Some(weight)),
+        Some(weight) => Err(MomoError::Validation(format!(
+            "Freshness weight must be between 0 and 1, got {weight}"
+        ))),
+    }
+}
+
+/// Reject a requested embedding model whose vectors can't be compared with
+/// the ones `record`'s container was indexed with.
+fn check_requested_model_dimensions(
+    record: &ContainerEmbeddingModel,
+    model: &str,
+    dimensions: usize,
+) -> Result<()> {
+    if dimensions == record.dimensions {
+        return Ok(());
+    }
+    Err(MomoError::Validation(format!(
+        "Embedding model '{model}' produces {dimensions} dimensions, but container '{}' was indexed with '{}' at {} dimensions",
+        record.container_tag, record.model, record.dimensions
+    )))
+}
+
+/// Reject container tag weights that are negative, infinite or NaN.
+fn check_container_tag_weights(weights: Option<&HashMap<String, f32>>) -> Result<()> {
+    let invalid = weights
+        .into_iter()
+        .flatten()
+        .find(|(_, weight)| !weight.is_finite() || **weight < 0.0);
+    match invalid {
+        None => Ok(()),
+        Some((tag, weight)) => Err(MomoError::Validation(format!(
+            "Container tag weight for '{tag}' must be a finite, non-negative number, got {weight}"
+        ))),
+    }
+}
+
+/// Effective hybrid search weights, defaulting to 1.0. Negative, infinite and
+/// NaN weights are rejected.
+fn hybrid_weights(memory: Option<f32>, document: Option<f32>) -> Result<HybridWeights> {
+    let validate = |weight: Option<f32>, domain: &str| match weight {
+        None => Ok(1.0),
+        Some(weight) if weight.is_finite() && weight >= 0.0 => Ok(weight),
+        Some(weight) => Err(MomoError::Validation(format!(
+            "{domain} weight must be a finite, non-negative number, got {weight}"
+        ))),
+    };
+    Ok(HybridWeights {
+        memory: validate(memory, "Memory")?,
+        document: validate(document, "Document")?,
+    })
+}
+
+/// Scale keyword (BM25) scores so the best hit scores 1.0, putting them on
+/// the 0–1 scale of vector similarities.
+fn normalize_keyword_scores<'a>(scores: impl Iterator<Item = &'a mut f32>) {
+    let scores: Vec<&mut f32> = scores.collect();
+    let best = scores.iter().map(|score| **score).fold(0.0f32, f32::max);
+    if best > 0.0 {
+        for score in scores {
+            *score /= best;
+        }
+    }
+}
+
+/// Merge best-first vector and keyword hits by reciprocal rank fusion. A hit's
+/// fused score sums `1 / (RRF_K + rank)` over the lists it appears in, scaled
+/// so that ranking first in both scores 1.0. When either list is empty the
+/// other is returned unchanged, keeping its own scores.
+fn fuse_ranked<T>(
+    vector: Vec<T>,
+    keyword: Vec<T>,
+    id: fn(&T) -> &str,
+    set_score: fn(&mut T, f32),
+) -> Vec<T> {
+    if keyword.is_empty() {
+        return vector;
+    }
+    if vector.is_empty() {
+        return keyword;
+    }
 
-            matches_and && matches_or
+    let mut fused: Vec<(T, f32)> = Vec::with_capacity(vector.len() + keyword.len());
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for hits in [vector, keyword] {
+        for (rank, hit) in hits.into_iter().enumerate() {
+            let contribution = 1.0 / (RRF_K + rank as f32 + 1.0);
+            match positions.get(id(&hit)) {
+                Some(&position) => fused[position].1 += contribution,
+                None => {
+                    positions.insert(id(&hit).to_string(), fused.len());
+                    fused.push((hit, contribution));
+                }
+            }
+        }
+    }
+
+    let scale = (RRF_K + 1.0) / 2.0;
+    fused.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    fused
+        .into_iter()
+        .map(|(mut hit, score)| {
+            set_score(&mut hit, score * scale);
+            hit
         })
         .collect()
 }
 
+/// Fields of a search response touched by threshold relaxation.
+struct RelaxationFields<'a> {
+    rewritten_query: &'a mut Option<String>,
+    degraded: &'a mut bool,
+    skipped_stages: &'a mut Vec<SearchStage>,
+    relaxed_threshold: &'a mut Option<f32>,
+}
+
+/// Search response that can be re-run with a relaxed threshold.
+trait RelaxableResponse {
+    fn result_count(&self) -> usize;
+    fn relaxation_fields(&mut self) -> RelaxationFields<'_>;
+}
+
+impl RelaxableResponse for SearchDocumentsResponse {
+    fn result_count(&self) -> usize {
+        self.results.len()
+    }
+
+    fn relaxation_fields(&mut self) -> RelaxationFields<'_> {
+        RelaxationFields {
+            rewritten_query: &mut self.rewritten_query,
+            degraded: &mut self.degraded,
+            skipped_stages: &mut self.skipped_stages,
+            relaxed_threshold: &mut self.relaxed_threshold,
+        }
+    }
+}
+
+impl RelaxableResponse for SearchMemoriesResponse {
+    fn result_count(&self) -> usize {
+        self.results.len()
+    }
+
+    fn relaxation_fields(&mut self) -> RelaxationFields<'_> {
+        RelaxationFields {
+            rewritten_query: &mut self.rewritten_query,
+            degraded: &mut self.degraded,
+            skipped_stages: &mut self.skipped_stages,
+            relaxed_threshold: &mut self.relaxed_threshold,
+        }
+    }
+}
+
+impl RelaxableResponse for HybridSearchResponse {
+    fn result_count(&self) -> usize {
+        self.results.len()
+    }
+
+    fn relaxation_fields(&mut self) -> RelaxationFields<'_> {
+        RelaxationFields {
+            rewritten_query: &mut self.rewritten_query,
+            degraded: &mut self.degraded,
+            skipped_stages: &mut self.skipped_stages,
+            relaxed_threshold: &mut self.relaxed_threshold,
+        }
+    }
+}
+
+/// Collapse memories from the same version chain so each chain appears once in the
+/// whole response, as its newest version.
+///
+/// Top-level results take precedence over related context entries; version
+/// history (`parents`/`children`) is left untouched. `chain_roots` maps memory ids
+/// to their chain root, and unknown ids are treated as their own root.
+fn dedupe_version_chains(
+    results: Vec<MemorySearchResult>,
+    chain_roots: &HashMap<String, String>,
+) -> Vec<MemorySearchResult> {
+    let root_of = |id: &str| -> String {
+        chain_roots
+            .get(id)
+            .cloned()
+            .unwrap_or_else(|| id.to_string())
+    };
+
+    let mut newest: HashMap<String, (String, i32)> = HashMap::new();
+    for result in &results {
+        let version = result.version.unwrap_or(0);
+        let entry = newest
+            .entry(root_of(&result.id))
+            .or_insert_with(|| (result.id.clone(), version));
+        if version > entry.1 {
+            *entry = (result.id.clone(), version);
+        }
+    }
+
+    let mut results: Vec<MemorySearchResult> = results
+        .into_iter()
+        .filter(|result| {
+            newest
+                .get(&root_of(&result.id))
+                .is_some_and(|(id, _)| *id == result.id)
+        })
+        .collect();
+
+    let mut newest_related: HashMap<String, (String, i32)> = HashMap::new();
+    for related in results
+        .iter()
+        .filter_map(|result| result.context.as_ref())
+        .flat_map(|context| context.related.iter())
+    {
+        let root = root_of(&related.id);
+        if newest.contains_key(&root) {
+            continue;
+        }
+        let version = related.version.unwrap_or(0);
+        let entry = newest_related
+            .entry(root)
+            .or_insert_with(|| (related.id.clone(), version));
+        if version > entry.1 {
+            *entry = (related.id.clone(), version);
+        }
+    }
+
+    let mut emitted: HashSet<String> = HashSet::new();
+    for context in results
+        .iter_mut()
+        .filter_map(|result| result.context.as_mut())
+    {
+        context.related.retain(|related| {
+            let root = root_of(&related.id);
+            newest_related
+                .get(&root)
+                .is_some_and(|(id, _)| *id == related.id)
+                && emitted.insert(root)
+        });
+    }
+
+    results
+}
+
+/// Group context memories by `key` (e.g. root or parent id), preserving order.
+fn group_memories_by(
+    memories: Vec<Memory>,
+    key: impl Fn(&Memory) -> Option<&String>,
+) -> HashMap<String, Vec<Memory>> {
+    let mut grouped: HashMap<String, Vec<Memory>> = HashMap::new();
+    for memory in memories {
+        if let Some(key) = key(&memory).cloned() {
+            grouped.entry(key).or_default().push(memory);
+        }
+    }
+    grouped
+}
+
+fn relation_info(memory: &Memory, relation: MemoryRelationType) -> MemoryRelationInfo {
+    MemoryRelationInfo {
+        id: memory.id.clone(),
+        relation,
+        version: Some(memory.version),
+        memory: memory.memory.clone(),
+        metadata: Some(memory.metadata.clone()),
+        updated_at: memory.updated_at,
+    }
+}
+
+/// Context for a memory search hit, with at most `cap` parents, children and
+/// related memories each. The most recent ones are kept: the newest parents
+/// (still returned oldest first), the newest children and the most recently
+/// updated related memories.
+fn build_memory_context(
+    memory: &Memory,
+    parents_by_root: &HashMap<String, Vec<Memory>>,
+    children_by_parent: &HashMap<String, Vec<Memory>>,
+    related_map: &HashMap<String, Memory>,
+    cap: usize,
+) -> MemoryContext {
+    let parents = memory
+        .root_memory_id
+        .as_ref()
+        .and_then(|root_id| parents_by_root.get(root_id))
+        .map(|parents| {
+            parents[parents.len().saturating_sub(cap)..]
+                .iter()
+                .map(|m| relation_info(m, MemoryRelationType::Updates))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let children = children_by_parent
+        .get(&memory.id)
+        .map(|children| {
+            children
+                .iter()
+                .take(cap)
+                .map(|m| relation_info(m, MemoryRelationType::Extends))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut related: Vec<(&Memory, &MemoryRelationType)> = memory
+        .memory_relations
+        .iter()
+        .filter_map(|(related_id, relation_type)| {
+            related_map
+                .get(related_id)
+                .map(|related_memory| (related_memory, relation_type))
+        })
+        .collect();
+    related.sort_by_key(|(m, _)| std::cmp::Reverse(m.updated_at));
+    let related = related
+        .into_iter()
+        .take(cap)
+        .map(|(m, relation_type)| relation_info(m, relation_type.clone()))
+        .collect();
+
+    MemoryContext {
+        parents,
+        children,
+        related,
+    }
+}
+
+/// Memory score before importance boosting. `disable_decay` passes the raw
+/// database similarity through, so old episodes rank like any other memory.
 fn apply_memory_similarity(
     ranker: &TemporalSearchRanker,
     memory: &Memory,
     db_similarity: f32,
+    disable_decay: bool,
 ) -> f32 {
+    if disable_decay {
+        return db_similarity;
+    }
     ranker.apply_episode_decay(memory, db_similarity)
 }
 
+/// The scores `apply_memory_similarity` works from and produces, for
+/// `include.decay_debug`.
+fn memory_decay_debug(
+    ranker: &TemporalSearchRanker,
+    memory: &Memory,
+    db_similarity: f32,
+    disable_decay: bool,
+) -> MemoryDecayDebug {
+    let decay_factor = if disable_decay {
+        1.0
+    } else {
+        ranker.episode_decay_factor(memory)
+    };
+    MemoryDecayDebug {
+        db_similarity,
+        decay_factor,
+        decayed_similarity: apply_memory_similarity(ranker, memory, db_similarity, disable_decay),
+    }
+}
+
 impl SearchService {
     pub fn new(
         read_db: Arc<dyn DatabaseBackend>,
@@ -111,13 +853,286 @@ impl SearchService {
             reranker,
             llm,
             rewrite_cache,
+            query_embedding_cache: QueryEmbeddingCache::new(config.embeddings.query_cache_size),
             episode_decay_days: config.memory.episode_decay_days,
             episode_decay_factor: config.memory.episode_decay_factor,
+            max_response_time: config
+                .search
+                .max_response_time_ms
+                .map(Duration::from_millis),
+            dedupe_version_chains: config.search.dedupe_version_chains,
+            max_context_memories: config.search.max_context_memories,
+            importance_boost: config.memory.importance.search_boost,
+            embedding_model: config.embeddings.model.clone(),
+            alternate_embeddings: AlternateEmbeddings::default(),
+            synonyms: Arc::new(config.search.synonyms.clone()),
+            min_results_threshold_floor: config.search.min_results_threshold_floor,
+            lexical_fallback: config.search.lexical_fallback,
+            lexical_fallback_limit: config.search.lexical_fallback_limit,
+            highlight_markers: HighlightMarkers {
+                open: config.search.highlight_open_marker.clone(),
+                close: config.search.highlight_close_marker.clone(),
+            },
+            score_sigmoid_midpoint: config.search.score_sigmoid_midpoint,
+            score_sigmoid_steepness: config.search.score_sigmoid_steepness,
+            freshness_half_life_days: config.search.freshness_half_life_days,
+            replica: None,
+        }
+    }
+
+    /// Allow memory searches to target the given alternate embedding models.
+    pub fn with_alternate_embeddings(mut self, alternate_embeddings: AlternateEmbeddings) -> Self {
+        self.alternate_embeddings = alternate_embeddings;
+        self
+    }
+
+    /// Route reads to the write backend while the read replica is unhealthy.
+    pub fn with_replica_health(mut self, replica: ReplicaHealth) -> Self {
+        self.replica = Some(replica);
+        self
+    }
+
+    /// Backend to read from: the read replica, or the write backend once the
+    /// replica's sync has failed too many times in a row.
+    fn reader(&self) -> &Arc<dyn DatabaseBackend> {
+        match &self.replica {
+            Some(replica) if replica.should_fall_back() => &self.write_db,
+            _ => &self.read_db,
+        }
+    }
+
+    /// Reject a search of the primary model's vectors in a container indexed
+    /// at other dimensions, whose stored vectors can't be compared with them.
+    async fn check_primary_model(&self, container_tags: &[String]) -> Result<()> {
+        for tag in container_tags {
+            if let Some(record) = self.reader().get_container_embedding_model(tag).await? {
+                check_requested_model_dimensions(
+                    &record,
+                    &self.embedding_model,
+                    self.embeddings.dimensions(),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Query embedding for a memory search, and the alternate model whose
+    /// stored embeddings to search (`None` for the primary model).
+    ///
+    /// Without an explicit model, a container is searched with the model its
+    /// memories were recorded with, so vectors from different models are
+    /// never compared. An explicit model must at least match the container's
+    /// dimensions.
+    #[tracing::instrument(skip_all)]
+    async fn memory_query_embedding(
+        &self,
+        req: &SearchMemoriesRequest,
+        precomputed_embedding: Option<&[f32]>,
+    ) -> Result<(Vec<f32>, Option<String>)> {
+        let container_model = match &req.container_tag {
+            Some(tag) => self.reader().get_container_embedding_model(tag).await?,
+            None => None,
+        };
+        let (embeddings, alternate_model) = match (&req.embedding_model, &container_model) {
+            (Some(model), Some(record)) => {
+                let resolved = self.memory_embeddings_for(Some(model))?;
+                check_requested_model_dimensions(record, model, resolved.0.dimensions())?;
+                resolved
+            }
+            (None, Some(record)) => {
+                let resolved = self
+                    .memory_embeddings_for(Some(&record.model))
+                    .map_err(|_| {
+                        MomoError::Validation(format!(
+                            "Container '{}' was embedded with '{}', which is not loaded. Configure it in MEMORY_ALTERNATE_EMBEDDING_MODELS",
+                            record.container_tag, record.model
+                        ))
+                    })?;
+                if resolved.0.dimensions() != record.dimensions {
+                    return Err(MomoError::Validation(format!(
+                        "Container '{}' was embedded with '{}' at {} dimensions, but the loaded model produces {}",
+                        record.container_tag,
+                        record.model,
+                        record.dimensions,
+                        resolved.0.dimensions()
+                    )));
+                }
+                resolved
+            }
+            (model, None) => self.memory_embeddings_for(model.as_deref())?,
+        };
+        let query_embedding = match precomputed_embedding {
+            Some(embedding) if alternate_model.is_none() => embedding.to_vec(),
+            _ => {
+                self.embed_query_cached(
+                    embeddings,
+                    alternate_model.unwrap_or(&self.embedding_model),
+                    &self.query_for_embedding(&req.q, req.expand_synonyms),
+                )
+                .await?
+            }
+        };
+
+        Ok((query_embedding, alternate_model.map(str::to_string)))
+    }
+
+    /// Memories most similar to `query_embedding` for `req`, best first, from
+    /// `alternate_model`'s stored embeddings or the primary model's.
+    #[tracing::instrument(skip_all)]
+    async fn similar_memory_hits(
+        &self,
+        req: &SearchMemoriesRequest,
+        alternate_model: Option<&str>,
+        query_embedding: &[f32],
+        depth: u32,
+    ) -> Result<Vec<MemorySearchHit>> {
+        let threshold = req.threshold.unwrap_or(0.6);
+        let include_forgotten = req
+            .include
+            .as_ref()
+            .and_then(|i| i.forgotten_memories)
+            .unwrap_or(false);
+
+        match alternate_model {
+            Some(model) => {
+                self.reader()
+                    .search_similar_memories_by_model(
+                        model,
+                        query_embedding,
+                        depth,
+                        threshold,
+                        req.container_tag.as_deref(),
+                        req.language.as_deref(),
+                        include_forgotten,
+                    )
+                    .await
+            }
+            None => {
+                self.reader()
+                    .search_similar_memories(
+                        query_embedding,
+                        depth,
+                        threshold,
+                        req.container_tag.as_deref(),
+                        req.language.as_deref(),
+                        include_forgotten,
+                    )
+                    .await
+            }
+        }
+    }
+
+    /// Score of a memory search hit: episode decay (unless disabled), the
+    /// importance boost, then the freshness blend when requested.
+    fn memory_hit_score(
+        &self,
+        ranker: &TemporalSearchRanker,
+        hit: &MemorySearchHit,
+        disable_decay: bool,
+        freshness_weight: Option<f32>,
+        now: DateTime<Utc>,
+    ) -> f32 {
+        let similarity = boost_score(
+            apply_memory_similarity(ranker, &hit.memory, hit.score, disable_decay),
+            hit.importance,
+            self.importance_boost,
+        );
+        match freshness_weight {
+            Some(weight) => {
+                let recency = freshness(hit.memory.updated_at, now, self.freshness_half_life_days);
+                blend_freshness(similarity, recency, weight)
+            }
+            None => similarity,
+        }
+    }
+
+    /// Episode decay ranker for `container_tag`, using its stored overrides
+    /// where set and the global settings otherwise.
+    async fn episode_ranker(&self, container_tag: Option<&str>) -> Result<TemporalSearchRanker> {
+        let settings = match container_tag {
+            Some(tag) => self.reader().get_container_settings(tag).await?,
+            None => None,
+        }
+        .unwrap_or_default();
+
+        Ok(TemporalSearchRanker::new(
+            settings
+                .episode_decay_days
+                .unwrap_or(self.episode_decay_days),
+            settings
+                .episode_decay_factor
+                .unwrap_or(self.episode_decay_factor),
+        ))
+    }
+
+    /// Embed `query` with `embeddings`, the provider for `model`, reusing a
+    /// cached embedding of the same query and model when there is one.
+    #[tracing::instrument(skip_all)]
+    async fn embed_query_cached(
+        &self,
+        embeddings: &EmbeddingProvider,
+        model: &str,
+        query: &str,
+    ) -> Result<Vec<f32>> {
+        if let Some(ref cache) = self.query_embedding_cache {
+            if let Some(embedding) = cache.get(model, query) {
+                return Ok(embedding);
+            }
+        }
+
+        let embedding = embeddings.embed_query(query).await?;
+        if let Some(ref cache) = self.query_embedding_cache {
+            cache.put(model, query, embedding.clone());
+        }
+        Ok(embedding)
+    }
+
+    /// Text to embed for `query`: with `expand_synonyms`, the query followed by
+    /// synonyms from the configured dictionary. Runs after any query rewrite.
+    fn query_for_embedding<'a>(
+        &self,
+        query: &'a str,
+        expand_synonyms: Option<bool>,
+    ) -> Cow<'a, str> {
+        if !expand_synonyms.unwrap_or(false) {
+            return Cow::Borrowed(query);
+        }
+        match expand_query(query, &self.synonyms) {
+            Some(expanded) => {
+                tracing::debug!(query = %query, expanded = %expanded, "Expanded query with synonyms");
+                Cow::Owned(expanded)
+            }
+            None => Cow::Borrowed(query),
+        }
+    }
+
+    /// Resolve the provider for a requested embedding model. Returns the alternate
+    /// model name when its side-table embeddings should be searched, or `None`
+    /// for the primary model.
+    fn memory_embeddings_for<'a>(
+        &'a self,
+        model: Option<&'a str>,
+    ) -> Result<(&'a EmbeddingProvider, Option<&'a str>)> {
+        match model {
+            None => Ok((&self.embeddings, None)),
+            Some(model) if model == self.embedding_model => Ok((&self.embeddings, None)),
+            Some(model) => match self.alternate_embeddings.get(model) {
+                Some(provider) => Ok((provider, Some(model))),
+                None => Err(MomoError::Validation(format!(
+                    "Unknown embedding model '{model}'. Configure it in MEMORY_ALTERNATE_EMBEDDING_MODELS"
+                ))),
+            },
         }
     }
 
-    async fn maybe_rewrite_query(&self, req: &SearchDocumentsRequest) -> Option<String> {
-        if !req.rewrite_query.unwrap_or(false) {
+    #[tracing::instrument(skip_all)]
+
+    async fn maybe_rewrite_query(
+        &self,
+        req: &SearchDocumentsRequest,
+        deadline: &SearchDeadline,
+    ) -> Option<String> {
+        if !req.rewrite_query.unwrap_or(false) || deadline.is_exceeded() {
             return None;
         }
 
@@ -147,6 +1162,7 @@ impl SearchService {
         } else {
             std::time::Duration::from_secs(5)
         };
+        let timeout = deadline.cap(timeout);
 
         let prompt = crate::llm::prompts::query_rewrite_prompt(&req.q);
         let llm_call = self.llm.complete(&prompt, None);
@@ -185,8 +1201,14 @@ impl SearchService {
         }
     }
 
-    async fn maybe_rewrite_memory_query(&self, req: &SearchMemoriesRequest) -> Option<String> {
-        if !req.rewrite_query.unwrap_or(false) {
+    #[tracing::instrument(skip_all)]
+
+    async fn maybe_rewrite_memory_query(
+        &self,
+        req: &SearchMemoriesRequest,
+        deadline: &SearchDeadline,
+    ) -> Option<String> {
+        if !req.rewrite_query.unwrap_or(false) || deadline.is_exceeded() {
             return None;
         }
 
@@ -216,6 +1238,7 @@ impl SearchService {
         } else {
             std::time::Duration::from_secs(5)
         };
+        let timeout = deadline.cap(timeout);
 
         let prompt = crate::llm::prompts::query_rewrite_prompt(&req.q);
         let llm_call = self.llm.complete(&prompt, None);
@@ -254,32 +1277,121 @@ impl SearchService {
         }
     }
 
+    #[tracing::instrument(skip_all)]
+
     pub async fn search_documents(
         &self,
         mut req: SearchDocumentsRequest,
     ) -> Result<SearchDocumentsResponse> {
         let start = Instant::now();
+        let response = self.search_documents_once(&mut req, start, false).await?;
+
+        // Retries reuse the query rewritten by the first pass.
+        req.rewrite_query = None;
+        let threshold = req.chunk_threshold.unwrap_or(0.5);
+        let min_results = min_results_target(req.min_results, req.limit);
+        let mut response = self
+            .relax_threshold(start, response, min_results, threshold, |relaxed| {
+                let mut req = SearchDocumentsRequest {
+                    chunk_threshold: Some(relaxed),
+                    ..req.clone()
+                };
+                async move { self.search_documents_once(&mut req, start, false).await }
+            })
+            .await?;
+
+        if self.should_fall_back_to_lexical(&response.results, req.cursor.as_deref(), req.offset) {
+            response = self.search_documents_once(&mut req, start, true).await?;
+            response.fallback = Some(SearchFallback::Lexical);
+        }
+
+        self.apply_score_calibration(
+            req.score_calibration,
+            response
+                .results
+                .iter_mut()
+                .map(|result| (&mut result.score, &mut result.raw_score))
+                .collect(),
+        );
+        Ok(response)
+    }
+
+    /// One search pass at the request's threshold, or over keyword hits
+    /// instead of vector hits when `lexical` is set.
+    async fn search_documents_once(
+        &self,
+        req: &mut SearchDocumentsRequest,
+        start: Instant,
+        lexical: bool,
+    ) -> Result<SearchDocumentsResponse> {
+        let page = PageStart::from_request(req.cursor.as_deref(), req.offset)?;
+        let diversity_lambda = diversity_lambda(req.diversify, req.diversity_lambda)?;
+        check_container_tag_weights(req.container_tag_weights.as_ref())?;
+        let deadline = SearchDeadline::new(start, self.max_response_time);
+        let mut skipped_stages = Vec::new();
 
         // Try to rewrite query if requested
         let original_query = req.q.clone();
-        if let Some(rewritten) = self.maybe_rewrite_query(&req).await {
+        if let Some(rewritten) = self.maybe_rewrite_query(req, &deadline).await {
             req.q = rewritten;
+        } else if req.rewrite_query.unwrap_or(false) && deadline.is_exceeded() {
+            skipped_stages.push(SearchStage::QueryRewrite);
         }
 
-        let query_embedding = self.embeddings.embed_query(&req.q).await?;
+        self.check_primary_model(req.container_tags.as_deref().unwrap_or_default())
+            .await?;
+        let search_text = self.query_for_embedding(&req.q, req.expand_synonyms);
+        let query_embedding = self
+            .embed_query_cached(&self.embeddings, &self.embedding_model, &search_text)
+            .await?;
 
         let threshold = req.chunk_threshold.unwrap_or(0.5);
         let limit = req.limit.unwrap_or(10).min(100);
 
-        let chunk_results = self
-            .read_db
-            .search_similar_chunks(
-                &query_embedding,
-                limit * 3,
-                threshold,
-                req.container_tags.as_deref(),
-            )
-            .await?;
+        let chunk_results = if lexical {
+            let mut hits = self
+                .reader()
+                .search_keyword_chunks(
+                    &search_text,
+                    self.lexical_fallback_limit,
+                    req.container_tags.as_deref(),
+                    req.chunker_types.as_deref(),
+                    req.code_languages.as_deref(),
+                    self.highlight_markers(req.highlight),
+                )
+                .await?;
+            normalize_keyword_scores(hits.iter_mut().map(|hit| &mut hit.score));
+            hits
+        } else {
+            self.reader()
+                .search_similar_chunks(
+                    &query_embedding,
+                    page.depth(limit) * 3,
+                    threshold,
+                    req.container_tags.as_deref(),
+                    req.chunker_types.as_deref(),
+                    req.code_languages.as_deref(),
+                )
+                .await?
+        };
+        let chunk_results = match diversity_lambda {
+            Some(lambda) => {
+                self.diversify_chunks(chunk_results, page.depth(limit) as usize, lambda)
+                    .await?
+            }
+            None => chunk_results,
+        };
+        // With diversification, documents keep the order in which MMR first
+        // picked one of their chunks.
+        let mut diversified_order: HashMap<String, usize> = HashMap::new();
+        if diversity_lambda.is_some() {
+            for chunk in &chunk_results {
+                let next = diversified_order.len();
+                diversified_order
+                    .entry(chunk.document_id.clone())
+                    .or_insert(next);
+            }
+        }
 
         let mut doc_chunks: HashMap<String, Vec<_>> = HashMap::new();
         for chunk in chunk_results {
@@ -292,7 +1404,7 @@ impl SearchService {
         let mut results: Vec<DocumentSearchResult> = Vec::new();
 
         let doc_ids: Vec<String> = doc_chunks.keys().cloned().collect();
-        let docs = self.read_db.get_documents_by_ids(&doc_ids).await?;
+        let docs = self.reader().get_documents_by_ids(&doc_ids).await?;
         let doc_map: HashMap<String, Document> =
             docs.into_iter().map(|d| (d.id.clone(), d)).collect();
 
@@ -306,7 +1418,13 @@ impl SearchService {
                         content: c.chunk_content.clone(),
                         score: c.score,
                         rerank_score: None,
-                        is_relevant: c.score >= threshold,
+                        is_relevant: lexical || c.score >= threshold,
+                        position: c.position,
+                        chunker: c.chunker,
+                        context_before: Vec::new(),
+                        context_after: Vec::new(),
+                        snippet: None,
+                        highlight: c.highlight.clone(),
                     })
                     .collect();
 
@@ -337,11 +1455,15 @@ impl SearchService {
                     metadata: doc.metadata.clone(),
                     created_at: doc.created_at,
                     updated_at: doc.updated_at,
+                    raw_score: None,
                 });
             }
         }
 
-        if req.rerank.unwrap_or(false) {
+        if req.rerank.unwrap_or(false) && deadline.is_exceeded() {
+            tracing::debug!("Search time budget exhausted, skipping reranking");
+            skipped_stages.push(SearchStage::Rerank);
+        } else if req.rerank.unwrap_or(false) {
             if let Some(ref reranker) = self.reranker {
                 if reranker.is_enabled() {
                     let rerank_level = req.rerank_level.as_deref().unwrap_or("auto");
@@ -357,54 +1479,333 @@ impl SearchService {
                         }
                     };
 
-                    let config_top_k = self.reranker.as_ref().map(|_| 100).unwrap_or(100);
-                    let rerank_top_k = req.rerank_top_k.unwrap_or(config_top_k);
+                    let config_top_k = self.reranker.as_ref().map(|_| 100).unwrap_or(100);
+                    let rerank_top_k = req.rerank_top_k.unwrap_or(config_top_k);
+
+                    match deadline
+                        .run(self.apply_reranking(
+                            &req.q,
+                            &mut results,
+                            use_chunk_level,
+                            rerank_top_k,
+                        ))
+                        .await
+                    {
+                        Some(Ok(_)) => {
+                            tracing::debug!("Reranking applied successfully");
+                        }
+                        Some(Err(e)) => {
+                            tracing::warn!("Reranking failed, falling back to base scores: {}", e);
+                        }
+                        None => {
+                            tracing::debug!(
+                                "Reranking exceeded search time budget, using base scores"
+                            );
+                            skipped_stages.push(SearchStage::Rerank);
+                        }
+                    }
+                }
+            } else {
+                tracing::debug!("Reranking requested but reranker not available");
+            }
+        }
+
+        if let Some(ref weights) = req.container_tag_weights {
+            for result in results.iter_mut() {
+                if let Some(doc) = doc_map.get(&result.document_id) {
+                    let weight = container_tag_weight(weights, &doc.container_tags);
+                    result.score *= weight;
+                    result.rerank_score = result.rerank_score.map(|score| score * weight);
+                }
+            }
+        }
+
+        if diversified_order.is_empty() {
+            results.sort_by(|a, b| rank_order(a.ranking_key(), b.ranking_key()));
+        } else {
+            results.sort_by_key(|result| diversified_order.get(&result.document_id).copied());
+        }
+
+        let mut results = apply_metadata_filters(results, &req.filters);
+        if req.exclude_low_confidence.unwrap_or(false) {
+            results.retain(|result| !is_low_confidence(&result.metadata));
+        }
+        let next_cursor = take_page(&mut results, &page, limit as usize);
+
+        if let Some(context_chunks) = req.context_chunks {
+            self.attach_context_chunks(&mut results, context_chunks)
+                .await?;
+        }
+
+        if req.highlight.unwrap_or(false) && deadline.is_exceeded() {
+            tracing::debug!("Search time budget exhausted, skipping highlighting");
+            skipped_stages.push(SearchStage::Highlight);
+        } else if req.highlight.unwrap_or(false) {
+            match deadline
+                .run(self.attach_snippets(&mut results, &query_embedding, &original_query))
+                .await
+            {
+                Some(Ok(())) => {}
+                Some(Err(e)) => {
+                    tracing::warn!(
+                        "Highlighting failed, returning chunks without snippets: {}",
+                        e
+                    );
+                }
+                None => {
+                    tracing::debug!("Highlighting exceeded search time budget, skipping snippets");
+                    skipped_stages.push(SearchStage::Highlight);
+                }
+            }
+        }
+
+        let total = results.len() as u32;
+        let timing = start.elapsed().as_millis() as u64;
+
+        let rewritten_query = if req.q != original_query {
+            Some(original_query)
+        } else {
+            None
+        };
+
+        Ok(SearchDocumentsResponse {
+            results,
+            total,
+            timing,
+            rewritten_query,
+            degraded: !skipped_stages.is_empty(),
+            skipped_stages,
+            relaxed_threshold: None,
+            next_cursor,
+            fallback: None,
+        })
+    }
+
+    /// Markers for keyword highlights, when the request asks for highlighting.
+    fn highlight_markers(&self, highlight: Option<bool>) -> Option<&HighlightMarkers> {
+        highlight
+            .unwrap_or(false)
+            .then_some(&self.highlight_markers)
+    }
+
+    /// Whether a search whose vector pass returned `results` should fall back
+    /// to keyword search: only when enabled, nothing was found, and this is
+    /// the first page, so pages of the two searches are never mixed.
+    fn should_fall_back_to_lexical<T>(
+        &self,
+        results: &[T],
+        cursor: Option<&str>,
+        offset: Option<u32>,
+    ) -> bool {
+        self.lexical_fallback && results.is_empty() && cursor.is_none() && offset.unwrap_or(0) == 0
+    }
+
+    /// Re-run a search whose first pass found fewer than `min_results` hits
+    /// with progressively lower thresholds, down to the configured floor.
+    ///
+    /// Stops early, flagging [`SearchStage::ThresholdRelaxation`], once the
+    /// response time budget is spent.
+    #[tracing::instrument(skip_all)]
+    async fn relax_threshold<R, F, Fut>(
+        &self,
+        start: Instant,
+        mut response: R,
+        min_results: Option<usize>,
+        threshold: f32,
+        mut search: F,
+    ) -> Result<R>
+    where
+        R: RelaxableResponse,
+        F: FnMut(f32) -> Fut,
+        Fut: Future<Output = Result<R>>,
+    {
+        let Some(min_results) = min_results else {
+            return Ok(response);
+        };
+        let deadline = SearchDeadline::new(start, self.max_response_time);
+
+        for relaxed in relaxed_thresholds(threshold, self.min_results_threshold_floor) {
+            if response.result_count() >= min_results {
+                break;
+            }
+            if deadline.is_exceeded() {
+                tracing::debug!("Search time budget exhausted, stopping threshold relaxation");
+                let fields = response.relaxation_fields();
+                fields.skipped_stages.push(SearchStage::ThresholdRelaxation);
+                *fields.degraded = true;
+                break;
+            }
+
+            tracing::debug!(
+                found = response.result_count(),
+                min_results,
+                threshold = relaxed,
+                "Too few search results, relaxing threshold"
+            );
+            let mut retry = search(relaxed).await?;
+
+            // Keep what only the first pass knows: the query rewrite and any
+            // stages it skipped.
+            let previous = response.relaxation_fields();
+            let fields = retry.relaxation_fields();
+            *fields.relaxed_threshold = Some(relaxed);
+            *fields.rewritten_query = previous.rewritten_query.take();
+            for stage in previous.skipped_stages.drain(..) {
+                if !fields.skipped_stages.contains(&stage) {
+                    fields.skipped_stages.push(stage);
+                }
+            }
+            *fields.degraded = !fields.skipped_stages.is_empty();
+            response = retry;
+        }
+
+        Ok(response)
+    }
+
+    /// Replace each score with its calibrated value, keeping the original in
+    /// the paired raw field. The final step of a search, so thresholds and
+    /// ranking always work on raw scores.
+    fn apply_score_calibration(
+        &self,
+        calibration: Option<ScoreCalibration>,
+        mut scores: Vec<(&mut f32, &mut Option<f32>)>,
+    ) {
+        let Some(calibration) = calibration else {
+            return;
+        };
+        let raw: Vec<f32> = scores.iter().map(|(score, _)| **score).collect();
+        let calibrated = calibrated_scores(
+            &raw,
+            calibration,
+            self.score_sigmoid_midpoint,
+            self.score_sigmoid_steepness,
+        );
+        for ((score, raw_score), calibrated) in scores.iter_mut().zip(calibrated) {
+            **raw_score = Some(**score);
+            **score = calibrated;
+        }
+    }
+
+    /// Keep the `k` chunks picked by Maximal Marginal Relevance over their
+    /// stored embeddings, in pick order.
+    #[tracing::instrument(skip_all)]
+    async fn diversify_chunks(
+        &self,
+        chunks: Vec<ChunkWithDocument>,
+        k: usize,
+        lambda: f32,
+    ) -> Result<Vec<ChunkWithDocument>> {
+        let ids: Vec<String> = chunks.iter().map(|c| c.chunk_id.clone()).collect();
+        let embeddings = self.reader().get_chunk_embeddings(&ids).await?;
+
+        let relevance: Vec<f32> = chunks.iter().map(|c| c.score).collect();
+        let chunk_embeddings: Vec<Option<&[f32]>> = ids
+            .iter()
+            .map(|id| embeddings.get(id).map(Vec::as_slice))
+            .collect();
+        let picked = mmr_select(&relevance, &chunk_embeddings, k, lambda);
+
+        let mut chunks: Vec<Option<ChunkWithDocument>> = chunks.into_iter().map(Some).collect();
+        Ok(picked
+            .into_iter()
+            .filter_map(|index| chunks[index].take())
+            .collect())
+    }
+
+    /// Attach up to `context_chunks` neighboring chunks (capped at
+    /// [`MAX_CONTEXT_CHUNKS`]) before and after every returned chunk. Ranking is
+    /// not affected.
+    #[tracing::instrument(skip_all)]
+    async fn attach_context_chunks(
+        &self,
+        results: &mut [DocumentSearchResult],
+        context_chunks: u32,
+    ) -> Result<()> {
+        let window = context_chunks.min(MAX_CONTEXT_CHUNKS) as i32;
+        if window == 0 {
+            return Ok(());
+        }
 
-                    match self
-                        .apply_reranking(&req.q, &mut results, use_chunk_level, rerank_top_k)
-                        .await
-                    {
-                        Ok(_) => {
-                            tracing::debug!("Reranking applied successfully");
-                        }
-                        Err(e) => {
-                            tracing::warn!("Reranking failed, falling back to base scores: {}", e);
-                        }
-                    }
-                }
-            } else {
-                tracing::debug!("Reranking requested but reranker not available");
+        for result in results.iter_mut() {
+            // Only the window around each hit, not everything between hits.
+            let positions: Vec<i32> = result
+                .chunks
+                .iter()
+                .flat_map(|chunk| {
+                    chunk.position.saturating_sub(window)..=chunk.position.saturating_add(window)
+                })
+                .filter(|position| *position >= 0)
+                .collect::<BTreeSet<i32>>()
+                .into_iter()
+                .collect();
+            if positions.is_empty() {
+                continue;
             }
-        }
 
-        results.sort_by(|a, b| {
-            let a_score = a.rerank_score.unwrap_or(a.score);
-            let b_score = b.rerank_score.unwrap_or(b.score);
-            b_score
-                .partial_cmp(&a_score)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+            let neighbors: HashMap<i32, String> = self
+                .reader()
+                .get_chunks_by_positions(&result.document_id, &positions)
+                .await?
+                .into_iter()
+                .map(|chunk| (chunk.position, chunk.content))
+                .collect();
 
-        let mut results = apply_metadata_filters(results, &req.filters);
-        results.truncate(limit as usize);
+            for chunk in result.chunks.iter_mut() {
+                (chunk.context_before, chunk.context_after) =
+                    neighbor_context(&neighbors, chunk.position, window);
+            }
+        }
 
-        let total = results.len() as u32;
-        let timing = start.elapsed().as_millis() as u64;
+        Ok(())
+    }
 
-        let rewritten_query = if req.q != original_query {
-            Some(original_query)
+    /// Set each returned chunk's snippet to its sentence closest to the query,
+    /// by embedding similarity, with a sentence of context on each side and
+    /// the terms of `query` marked. Sentences of all chunks are embedded in one
+    /// batch.
+    #[tracing::instrument(skip_all)]
+    async fn attach_snippets(
+        &self,
+        results: &mut [DocumentSearchResult],
+        query_embedding: &[f32],
+        query: &str,
+    ) -> Result<()> {
+        let mut passages = Vec::new();
+        for chunk in results.iter().flat_map(|result| &result.chunks) {
+            let sentences = split_sentences(&chunk.content);
+            if sentences.len() > 1 {
+                passages.extend(sentences.into_iter().map(str::to_string));
+            }
+        }
+        let mut sentence_embeddings = if passages.is_empty() {
+            Vec::new()
         } else {
-            None
-        };
+            self.embeddings.embed_passages(passages).await?
+        }
+        .into_iter();
+
+        for chunk in results
+            .iter_mut()
+            .flat_map(|result| result.chunks.iter_mut())
+        {
+            let sentences = split_sentences(&chunk.content);
+            let best = match sentences.len() {
+                0 => continue,
+                1 => 0,
+                count => {
+                    let embeddings: Vec<Vec<f32>> =
+                        sentence_embeddings.by_ref().take(count).collect();
+                    best_sentence(query_embedding, &embeddings).unwrap_or(0)
+                }
+            };
+            chunk.snippet = Some(build_snippet(&sentences, best, query));
+        }
 
-        Ok(SearchDocumentsResponse {
-            results,
-            total,
-            timing,
-            rewritten_query,
-        })
+        Ok(())
     }
 
+    #[tracing::instrument(skip_all)]
+
     async fn apply_reranking(
         &self,
         query: &str,
@@ -491,44 +1892,319 @@ impl SearchService {
     }
 
     pub async fn search_memories(
+        &self,
+        req: SearchMemoriesRequest,
+    ) -> Result<SearchMemoriesResponse> {
+        self.search_memories_with(req, None).await
+    }
+
+    /// Run several memory searches, returning their responses in request
+    /// order. Queries served by the primary model are embedded in a single
+    /// call; the searches then run concurrently, and each response reports its
+    /// own timing. Fails as a whole if any search fails.
+    pub async fn search_memories_batch(
+        &self,
+        reqs: Vec<SearchMemoriesRequest>,
+    ) -> Result<Vec<SearchMemoriesResponse>> {
+        if reqs.len() > MAX_BATCH_QUERIES {
+            return Err(MomoError::Validation(format!(
+                "A batch can hold at most {MAX_BATCH_QUERIES} queries, got {}",
+                reqs.len()
+            )));
+        }
+
+        // Rewritten queries and other models need their own embedding call.
+        let shared: Vec<bool> = reqs
+            .iter()
+            .map(|req| req.embedding_model.is_none() && !req.rewrite_query.unwrap_or(false))
+            .collect();
+        let mut query_embeddings: Vec<Option<Vec<f32>>> = reqs
+            .iter()
+            .zip(&shared)
+            .map(|(req, shared)| {
+                let cache = self.query_embedding_cache.as_ref().filter(|_| *shared)?;
+                let query = self.query_for_embedding(&req.q, req.expand_synonyms);
+                cache.get(&self.embedding_model, &query)
+            })
+            .collect();
+        let misses: Vec<(usize, String)> = reqs
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| shared[*i] && query_embeddings[*i].is_none())
+            .map(|(i, req)| {
+                let query = self.query_for_embedding(&req.q, req.expand_synonyms);
+                (i, query.into_owned())
+            })
+            .collect();
+        let queries = misses.iter().map(|(_, query)| query.clone()).collect();
+        let embedded = self.embeddings.embed_queries(queries).await?;
+        for ((i, query), embedding) in misses.into_iter().zip(embedded) {
+            if let Some(ref cache) = self.query_embedding_cache {
+                cache.put(&self.embedding_model, &query, embedding.clone());
+            }
+            query_embeddings[i] = Some(embedding);
+        }
+
+        futures::stream::iter(reqs.into_iter().zip(query_embeddings))
+            .map(|(req, query_embedding)| self.search_memories_with(req, query_embedding))
+            .buffered(BATCH_SEARCH_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+
+    /// [`Self::search_memories`], reusing `query_embedding` when it was
+    /// computed with the primary model for the request's query.
+    #[tracing::instrument(skip_all)]
+    async fn search_memories_with(
         &self,
         mut req: SearchMemoriesRequest,
+        query_embedding: Option<Vec<f32>>,
+    ) -> Result<SearchMemoriesResponse> {
+        let start = Instant::now();
+        let query_embedding = query_embedding.as_deref();
+        let response = self
+            .search_memories_once(&mut req, start, query_embedding, false)
+            .await?;
+
+        // Retries reuse the query rewritten by the first pass.
+        req.rewrite_query = None;
+        let threshold = req.threshold.unwrap_or(0.6);
+        let min_results = min_results_target(req.min_results, req.limit);
+        let mut response = self
+            .relax_threshold(start, response, min_results, threshold, |relaxed| {
+                let mut req = SearchMemoriesRequest {
+                    threshold: Some(relaxed),
+                    ..req.clone()
+                };
+                async move {
+                    self.search_memories_once(&mut req, start, query_embedding, false)
+                        .await
+                }
+            })
+            .await?;
+
+        if self.should_fall_back_to_lexical(&response.results, req.cursor.as_deref(), req.offset) {
+            response = self
+                .search_memories_once(&mut req, start, query_embedding, true)
+                .await?;
+            response.fallback = Some(SearchFallback::Lexical);
+        }
+
+        self.apply_score_calibration(
+            req.score_calibration,
+            response
+                .results
+                .iter_mut()
+                .map(|result| (&mut result.similarity, &mut result.raw_similarity))
+                .collect(),
+        );
+        Ok(response)
+    }
+
+    /// Stream memory search results into `results` as they are scored,
+    /// instead of assembling the whole response first.
+    ///
+    /// Hits are fetched in doubling windows (10, 20, 40, ... up to `limit`,
+    /// at most 1000), so the first results go out after a small query, and
+    /// each window only sends hits not sent before. Results arrive best first
+    /// by similarity and carry the usual decay, importance, freshness and
+    /// container tag weighting, but they are not reranked or re-sorted after
+    /// weighting; related memories, query rewriting, version chain dedupe and
+    /// score calibration are not applied. Stops early once `results` is
+    /// closed.
+    pub async fn stream_memories(
+        &self,
+        req: SearchMemoriesRequest,
+        results: mpsc::Sender<MemorySearchResult>,
+    ) -> Result<SearchStreamSummary> {
+        let start = Instant::now();
+        let limit = req.limit.unwrap_or(10).clamp(1, MAX_SEARCH_DEPTH);
+        let freshness_weight = freshness_weight(req.freshness_weight)?;
+        check_container_tag_weights(req.container_tag_weights.as_ref())?;
+        let (query_embedding, alternate_model) = self.memory_query_embedding(&req, None).await?;
+        let ranker = self.episode_ranker(req.container_tag.as_deref()).await?;
+        let disable_decay = req.disable_decay.unwrap_or(false);
+        let now = Utc::now();
+
+        let mut sent = HashSet::new();
+        let mut depth = STREAM_FIRST_DEPTH.min(limit);
+        'windows: loop {
+            let hits = self
+                .similar_memory_hits(&req, alternate_model.as_deref(), &query_embedding, depth)
+                .await?;
+            let exhausted = hits.len() < depth as usize;
+
+            for hit in hits {
+                if sent.contains(&hit.memory.id) {
+                    continue;
+                }
+                let mut similarity =
+                    self.memory_hit_score(&ranker, &hit, disable_decay, freshness_weight, now);
+                if let Some(ref weights) = req.container_tag_weights {
+                    similarity *=
+                        container_tag_weight(weights, hit.memory.container_tag.as_slice());
+                }
+
+                let memory = hit.memory;
+                let id = memory.id.clone();
+                let result = MemorySearchResult {
+                    id: memory.id,
+                    memory: Some(memory.memory),
+                    chunk: None,
+                    metadata: memory.metadata,
+                    similarity,
+                    rerank_score: None,
+                    version: Some(memory.version),
+                    updated_at: memory.updated_at,
+                    context: None,
+                    documents: None,
+                    raw_similarity: None,
+                    decay_debug: None,
+                };
+                if results.send(result).await.is_err() {
+                    break 'windows;
+                }
+                sent.insert(id);
+            }
+
+            if exhausted || depth >= limit {
+                break;
+            }
+            depth = (depth * 2).min(limit);
+        }
+
+        Ok(SearchStreamSummary {
+            total: sent.len() as u32,
+            timing: start.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// Memories most similar to the memory `source_id`, using its stored
+    /// `embedding` as the query ("more like this"). The source memory itself
+    /// and forgotten memories are left out.
+    #[tracing::instrument(skip_all)]
+    pub async fn search_similar_to_memory(
+        &self,
+        source_id: &str,
+        embedding: &[f32],
+        limit: Option<u32>,
+        threshold: Option<f32>,
+        container_tag: Option<&str>,
     ) -> Result<SearchMemoriesResponse> {
         let start = Instant::now();
+        let threshold = threshold.unwrap_or(0.6);
+        let limit = limit.unwrap_or(10).min(100);
+
+        if let Some(tag) = container_tag {
+            self.check_primary_model(&[tag.to_string()]).await?;
+        }
+        let hits = self
+            .reader()
+            .search_similar_memories(embedding, limit + 1, threshold, container_tag, None, false)
+            .await?;
+
+        let mut results: Vec<MemorySearchResult> = hits
+            .into_iter()
+            .filter(|hit| hit.memory.id != source_id)
+            .map(|hit| MemorySearchResult {
+                id: hit.memory.id,
+                memory: Some(hit.memory.memory),
+                chunk: None,
+                metadata: hit.memory.metadata,
+                similarity: hit.score,
+                rerank_score: None,
+                version: Some(hit.memory.version),
+                updated_at: hit.memory.updated_at,
+                context: None,
+                documents: None,
+                raw_similarity: None,
+                decay_debug: None,
+            })
+            .collect();
+        results.sort_by(|a, b| rank_order(a.ranking_key(), b.ranking_key()));
+        results.truncate(limit as usize);
+
+        Ok(SearchMemoriesResponse {
+            total: results.len() as u32,
+            results,
+            timing: start.elapsed().as_millis() as u64,
+            rewritten_query: None,
+            degraded: false,
+            skipped_stages: Vec::new(),
+            relaxed_threshold: None,
+            next_cursor: None,
+            fallback: None,
+        })
+    }
+
+    /// One search pass at the request's threshold, or over keyword hits
+    /// instead of vector hits when `lexical` is set.
+    async fn search_memories_once(
+        &self,
+        req: &mut SearchMemoriesRequest,
+        start: Instant,
+        precomputed_embedding: Option<&[f32]>,
+        lexical: bool,
+    ) -> Result<SearchMemoriesResponse> {
+        let page = PageStart::from_request(req.cursor.as_deref(), req.offset)?;
+        check_container_tag_weights(req.container_tag_weights.as_ref())?;
+        let deadline = SearchDeadline::new(start, self.max_response_time);
+        let mut skipped_stages = Vec::new();
 
         // Try to rewrite query if requested
         let original_query = req.q.clone();
-        if let Some(rewritten) = self.maybe_rewrite_memory_query(&req).await {
+        if let Some(rewritten) = self.maybe_rewrite_memory_query(req, &deadline).await {
             req.q = rewritten;
+        } else if req.rewrite_query.unwrap_or(false) && deadline.is_exceeded() {
+            skipped_stages.push(SearchStage::QueryRewrite);
         }
 
-        let query_embedding = self.embeddings.embed_query(&req.q).await?;
+        let (query_embedding, alternate_model) = self
+            .memory_query_embedding(req, precomputed_embedding)
+            .await?;
 
-        let threshold = req.threshold.unwrap_or(0.6);
         let limit = req.limit.unwrap_or(10).min(100);
-
-        let include_opts = req.include.as_ref();
-        let include_forgotten = include_opts
-            .and_then(|i| i.forgotten_memories)
-            .unwrap_or(false);
-
-        let memories = self
-            .read_db
-            .search_similar_memories(
+        let memories = if lexical {
+            let include_forgotten = req
+                .include
+                .as_ref()
+                .and_then(|i| i.forgotten_memories)
+                .unwrap_or(false);
+            let mut hits = self
+                .reader()
+                .search_keyword_memories(
+                    &self.query_for_embedding(&req.q, req.expand_synonyms),
+                    self.lexical_fallback_limit,
+                    req.container_tag.as_deref(),
+                    req.language.as_deref(),
+                    include_forgotten,
+                )
+                .await?;
+            normalize_keyword_scores(hits.iter_mut().map(|hit| &mut hit.score));
+            hits
+        } else {
+            self.similar_memory_hits(
+                req,
+                alternate_model.as_deref(),
                 &query_embedding,
-                limit,
-                threshold,
-                req.container_tag.as_deref(),
-                include_forgotten,
+                page.depth(limit),
             )
-            .await?;
+            .await?
+        };
 
-        let include_opts = req.include.unwrap_or_default();
-        let ranker = TemporalSearchRanker::new(self.episode_decay_days, self.episode_decay_factor);
+        let include_opts = req.include.clone().unwrap_or_default();
+        let ranker = self.episode_ranker(req.container_tag.as_deref()).await?;
+        let disable_decay = req.disable_decay.unwrap_or(false);
+        let freshness_weight = freshness_weight(req.freshness_weight)?;
+        let now = Utc::now();
 
         let mut results: Vec<MemorySearchResult> = Vec::new();
+        let mut tag_weights: Vec<f32> = Vec::new();
+        let mut chain_roots: HashMap<String, String> = HashMap::new();
 
-        let all_related_ids: Vec<String> = if include_opts.related_memories.unwrap_or(false) {
+        let include_related = include_opts.related_memories.unwrap_or(false);
+        let include_decay_debug = include_opts.decay_debug.unwrap_or(false);
+        let all_related_ids: Vec<String> = if include_related {
             memories
                 .iter()
                 .flat_map(|hit| hit.memory.memory_relations.keys().cloned())
@@ -540,7 +2216,7 @@ impl SearchService {
         };
 
         let related_map: HashMap<String, Memory> = if !all_related_ids.is_empty() {
-            self.read_db
+            self.reader()
                 .get_memories_by_ids(&all_related_ids)
                 .await?
                 .into_iter()
@@ -550,65 +2226,75 @@ impl SearchService {
             HashMap::new()
         };
 
+        // Parents and children of all hits are fetched in two queries rather
+        // than two per hit, each already capped per hit.
+        let (parents_by_root, children_by_parent) = if include_related {
+            let root_ids: Vec<String> = memories
+                .iter()
+                .filter_map(|hit| hit.memory.root_memory_id.clone())
+                .collect::<HashSet<String>>()
+                .into_iter()
+                .collect();
+            let hit_ids: Vec<String> = memories.iter().map(|hit| hit.memory.id.clone()).collect();
+            (
+                group_memories_by(
+                    self.reader()
+                        .get_memory_parents_batch(&root_ids, self.max_context_memories)
+                        .await?,
+                    |m| m.root_memory_id.as_ref(),
+                ),
+                group_memories_by(
+                    self.reader()
+                        .get_memory_children_batch(&hit_ids, self.max_context_memories)
+                        .await?,
+                    |m| m.parent_memory_id.as_ref(),
+                ),
+            )
+        } else {
+            (HashMap::new(), HashMap::new())
+        };
+
+        for related_memory in related_map.values() {
+            chain_roots.insert(
+                related_memory.id.clone(),
+                related_memory
+                    .root_memory_id
+                    .clone()
+                    .unwrap_or_else(|| related_memory.id.clone()),
+            );
+        }
+
         for hit in memories {
-            let db_similarity = hit.score;
+            let similarity =
+                self.memory_hit_score(&ranker, &hit, disable_decay, freshness_weight, now);
+            let decay_debug = include_decay_debug
+                .then(|| memory_decay_debug(&ranker, &hit.memory, hit.score, disable_decay));
             let memory = hit.memory;
-            let similarity = apply_memory_similarity(&ranker, &memory, db_similarity);
-
-            let context = if include_opts.related_memories.unwrap_or(false) {
-                let parents = if let Some(ref root_id) = memory.root_memory_id {
-                    self.read_db.get_memory_parents(root_id).await?
-                } else {
-                    Vec::new()
-                };
 
-                let children = self.read_db.get_memory_children(&memory.id).await?;
-
-                let mut related = Vec::new();
-                for (related_id, relation_type) in &memory.memory_relations {
-                    if let Some(related_memory) = related_map.get(related_id) {
-                        related.push(MemoryRelationInfo {
-                            id: related_memory.id.clone(),
-                            relation: relation_type.clone(),
-                            version: Some(related_memory.version),
-                            memory: related_memory.memory.clone(),
-                            metadata: Some(related_memory.metadata.clone()),
-                            updated_at: related_memory.updated_at,
-                        });
-                    }
-                }
+            chain_roots.insert(
+                memory.id.clone(),
+                memory
+                    .root_memory_id
+                    .clone()
+                    .unwrap_or_else(|| memory.id.clone()),
+            );
 
-                Some(MemoryContext {
-                    parents: parents
-                        .into_iter()
-                        .map(|m| MemoryRelationInfo {
-                            id: m.id.clone(),
-                            relation: crate::models::MemoryRelationType::Updates,
-                            version: Some(m.version),
-                            memory: m.memory,
-                            metadata: Some(m.metadata),
-                            updated_at: m.updated_at,
-                        })
-                        .collect(),
-                    children: children
-                        .into_iter()
-                        .map(|m| MemoryRelationInfo {
-                            id: m.id.clone(),
-                            relation: crate::models::MemoryRelationType::Extends,
-                            version: Some(m.version),
-                            memory: m.memory,
-                            metadata: Some(m.metadata),
-                            updated_at: m.updated_at,
-                        })
-                        .collect(),
-                    related,
-                })
-            } else {
-                None
-            };
+            let context = include_related.then(|| {
+                build_memory_context(
+                    &memory,
+                    &parents_by_root,
+                    &children_by_parent,
+                    &related_map,
+                    self.max_context_memories,
+                )
+            });
 
             let documents = None;
 
+            tag_weights.push(req.container_tag_weights.as_ref().map_or(1.0, |weights| {
+                container_tag_weight(weights, memory.container_tag.as_slice())
+            }));
+
             results.push(MemorySearchResult {
                 id: memory.id,
                 memory: Some(memory.memory.clone()),
@@ -620,11 +2306,16 @@ impl SearchService {
                 updated_at: memory.updated_at,
                 context,
                 documents,
+                raw_similarity: None,
+                decay_debug,
             });
         }
 
         // Apply reranking AFTER temporal decay if requested
-        if req.rerank.unwrap_or(false) {
+        if req.rerank.unwrap_or(false) && deadline.is_exceeded() {
+            tracing::debug!("Search time budget exhausted, skipping memory reranking");
+            skipped_stages.push(SearchStage::Rerank);
+        } else if req.rerank.unwrap_or(false) {
             if let Some(ref reranker) = self.reranker {
                 if reranker.is_enabled() {
                     // Gather memory texts for reranking
@@ -635,8 +2326,11 @@ impl SearchService {
                         let config_top_k = 100; // Default from RerankerConfig
                         let rerank_top_k = memory_texts.len().min(config_top_k);
 
-                        match reranker.rerank(&req.q, memory_texts, rerank_top_k).await {
-                            Ok(rerank_results) => {
+                        match deadline
+                            .run(reranker.rerank(&req.q, memory_texts, rerank_top_k))
+                            .await
+                        {
+                            Some(Ok(rerank_results)) => {
                                 // Store rerank scores in results
                                 for rerank_result in rerank_results {
                                     if rerank_result.index < results.len() {
@@ -646,12 +2340,18 @@ impl SearchService {
                                 }
                                 tracing::debug!("Memory reranking applied successfully");
                             }
-                            Err(e) => {
+                            Some(Err(e)) => {
                                 tracing::warn!(
                                     "Memory reranking failed, using temporally-decayed scores: {}",
                                     e
                                 );
                             }
+                            None => {
+                                tracing::debug!(
+                                    "Memory reranking exceeded search time budget, using temporally-decayed scores"
+                                );
+                                skipped_stages.push(SearchStage::Rerank);
+                            }
                         }
                     }
                 }
@@ -660,14 +2360,21 @@ impl SearchService {
             }
         }
 
+        for (result, weight) in results.iter_mut().zip(tag_weights) {
+            result.similarity *= weight;
+            result.rerank_score = result.rerank_score.map(|score| score * weight);
+        }
+
         // Sort by rerank_score when available, otherwise by similarity (with temporal decay)
-        results.sort_by(|a, b| {
-            let a_score = a.rerank_score.unwrap_or(a.similarity);
-            let b_score = b.rerank_score.unwrap_or(b.similarity);
-            b_score
-                .partial_cmp(&a_score)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+        results.sort_by(|a, b| rank_order(a.ranking_key(), b.ranking_key()));
+
+        if req
+            .dedupe_version_chains
+            .unwrap_or(self.dedupe_version_chains)
+        {
+            results = dedupe_version_chains(results, &chain_roots);
+        }
+        let next_cursor = take_page(&mut results, &page, limit as usize);
 
         // After finalizing results (temporal decay and optional reranking applied),
         // update last_accessed for returned episode memories in batch.
@@ -703,14 +2410,59 @@ impl SearchService {
             total,
             timing,
             rewritten_query,
+            degraded: !skipped_stages.is_empty(),
+            skipped_stages,
+            relaxed_threshold: None,
+            next_cursor,
+            fallback: None,
         })
     }
 
+    #[tracing::instrument(skip_all)]
+
     pub async fn search_hybrid(
         &self,
         mut req: HybridSearchRequest,
     ) -> Result<HybridSearchResponse> {
         let start = Instant::now();
+        let response = self.search_hybrid_once(&mut req, start).await?;
+
+        // Retries reuse the query rewritten by the first pass.
+        req.rewrite_query = None;
+        let threshold = req.threshold.unwrap_or(0.6);
+        let min_results = min_results_target(req.min_results, req.limit);
+        let mut response = self
+            .relax_threshold(start, response, min_results, threshold, |relaxed| {
+                let mut req = HybridSearchRequest {
+                    threshold: Some(relaxed),
+                    ..req.clone()
+                };
+                async move { self.search_hybrid_once(&mut req, start).await }
+            })
+            .await?;
+
+        self.apply_score_calibration(
+            req.score_calibration,
+            response
+                .results
+                .iter_mut()
+                .map(|result| (&mut result.similarity, &mut result.raw_similarity))
+                .collect(),
+        );
+        Ok(response)
+    }
+
+    /// One search pass at the request's threshold.
+    async fn search_hybrid_once(
+        &self,
+        req: &mut HybridSearchRequest,
+        start: Instant,
+    ) -> Result<HybridSearchResponse> {
+        let page = PageStart::from_request(req.cursor.as_deref(), req.offset)?;
+        let weights = hybrid_weights(req.memory_weight, req.document_weight)?;
+        check_container_tag_weights(req.container_tag_weights.as_ref())?;
+        let deadline = SearchDeadline::new(start, self.max_response_time);
+        let mut skipped_stages = Vec::new();
 
         let original_query = req.q.clone();
         let rewrite_request = SearchMemoriesRequest {
@@ -722,18 +2474,48 @@ impl SearchService {
             limit: req.limit,
             rerank: req.rerank,
             rewrite_query: req.rewrite_query,
+            container_tag_weights: None,
+            dedupe_version_chains: None,
+            language: req.language.clone(),
+            embedding_model: None,
+            disable_decay: req.disable_decay,
+            expand_synonyms: req.expand_synonyms,
+            min_results: None,
+            score_calibration: None,
+            cursor: None,
+            offset: None,
+            freshness_weight: None,
         };
 
-        if let Some(rewritten) = self.maybe_rewrite_memory_query(&rewrite_request).await {
+        if let Some(rewritten) = self
+            .maybe_rewrite_memory_query(&rewrite_request, &deadline)
+            .await
+        {
             req.q = rewritten;
+        } else if req.rewrite_query.unwrap_or(false) && deadline.is_exceeded() {
+            skipped_stages.push(SearchStage::QueryRewrite);
         }
 
-        let query_embedding = self.embeddings.embed_query(&req.q).await?;
+        let search_mode = req.search_mode;
+        let search_text = self.query_for_embedding(&req.q, req.expand_synonyms);
+        let query_embedding = match search_mode {
+            SearchMode::Keyword => None,
+            _ => {
+                self.check_primary_model(req.container_tag.as_slice())
+                    .await?;
+                Some(
+                    self.embed_query_cached(&self.embeddings, &self.embedding_model, &search_text)
+                        .await?,
+                )
+            }
+        };
+        let keyword_search = matches!(search_mode, SearchMode::Hybrid | SearchMode::Keyword);
+        let highlight_markers = self.highlight_markers(req.highlight);
 
         let limit = req.limit.unwrap_or(10).min(100);
         let threshold = req.threshold.unwrap_or(0.6);
         let rerank_enabled = req.rerank.unwrap_or(false);
-        let search_mode = req.search_mode;
+        let rerank_skipped = AtomicBool::new(false);
 
         let include_opts = req.include.clone().unwrap_or_default();
         let include_forgotten = include_opts.forgotten_memories.unwrap_or(false);
@@ -741,6 +2523,14 @@ impl SearchService {
         let container_tag = req.container_tag.clone();
         let container_tags = container_tag.as_ref().map(|tag| vec![tag.clone()]);
         let filters = req.filters.clone();
+        let exclude_low_confidence = req.exclude_low_confidence.unwrap_or(false);
+        let dedupe_chains = req
+            .dedupe_version_chains
+            .unwrap_or(self.dedupe_version_chains);
+        let container_tag_weights = req.container_tag_weights.clone();
+        let language = req.language.clone();
+        let chunker_types = req.chunker_types.clone();
+        let code_languages = req.code_languages.clone();
         let query = req.q.clone();
 
         let document_query = query.clone();
@@ -751,17 +2541,53 @@ impl SearchService {
                 return Ok(Vec::new());
             }
 
-            let doc_limit = limit.saturating_mul(3);
-
-            let chunk_results = self
-                .read_db
-                .search_similar_chunks(
-                    &query_embedding,
-                    doc_limit,
-                    threshold,
-                    document_container_tags.as_deref(),
-                )
-                .await?;
+            let doc_limit = page.depth(limit).saturating_mul(3);
+
+            let vector_hits = match &query_embedding {
+                Some(embedding) => {
+                    self.reader()
+                        .search_similar_chunks(
+                            embedding,
+                            doc_limit,
+                            threshold,
+                            document_container_tags.as_deref(),
+                            chunker_types.as_deref(),
+                            code_languages.as_deref(),
+                        )
+                        .await?
+                }
+                None => Vec::new(),
+            };
+            let keyword_hits = if keyword_search {
+                self.reader()
+                    .search_keyword_chunks(
+                        &search_text,
+                        doc_limit,
+                        document_container_tags.as_deref(),
+                        chunker_types.as_deref(),
+                        code_languages.as_deref(),
+                        highlight_markers,
+                    )
+                    .await?
+            } else {
+                Vec::new()
+            };
+            // Fusion keeps the vector copy of a chunk found by both searches.
+            let mut highlights: HashMap<String, String> = keyword_hits
+                .iter()
+                .filter_map(|hit| Some((hit.chunk_id.clone(), hit.highlight.clone()?)))
+                .collect();
+            let mut chunk_results = fuse_ranked(
+                vector_hits,
+                keyword_hits,
+                |hit| hit.chunk_id.as_str(),
+                |hit, score| hit.score = score,
+            );
+            for hit in &mut chunk_results {
+                if hit.highlight.is_none() {
+                    hit.highlight = highlights.remove(&hit.chunk_id);
+                }
+            }
 
             let mut doc_chunks: HashMap<String, Vec<_>> = HashMap::new();
             for chunk in chunk_results {
@@ -775,7 +2601,7 @@ impl SearchService {
             let mut chunk_ids_by_doc: HashMap<String, Vec<String>> = HashMap::new();
 
             let doc_ids: Vec<String> = doc_chunks.keys().cloned().collect();
-            let docs = self.read_db.get_documents_by_ids(&doc_ids).await?;
+            let docs = self.reader().get_documents_by_ids(&doc_ids).await?;
             let doc_map: HashMap<String, Document> =
                 docs.into_iter().map(|d| (d.id.clone(), d)).collect();
 
@@ -792,6 +2618,12 @@ impl SearchService {
                             score: chunk.score,
                             rerank_score: None,
                             is_relevant: chunk.score >= threshold,
+                            position: chunk.position,
+                            chunker: chunk.chunker,
+                            context_before: Vec::new(),
+                            context_after: Vec::new(),
+                            snippet: None,
+                            highlight: chunk.highlight.clone(),
                         });
                         chunk_ids.push(chunk.chunk_id.clone());
                     }
@@ -810,24 +2642,36 @@ impl SearchService {
                         metadata: doc.metadata.clone(),
                         created_at: doc.created_at,
                         updated_at: doc.updated_at,
+                        raw_score: None,
                     });
                 }
             }
 
-            if rerank_enabled {
+            if rerank_enabled && deadline.is_exceeded() {
+                rerank_skipped.store(true, Ordering::Relaxed);
+            } else if rerank_enabled {
                 if let Some(ref reranker) = self.reranker {
                     if reranker.is_enabled() {
                         let total_chunks: usize = results.iter().map(|r| r.chunks.len()).sum();
                         if total_chunks > 0 {
                             let rerank_top_k = total_chunks.min(100);
-                            if let Err(error) = self
-                                .apply_reranking(&document_query, &mut results, true, rerank_top_k)
+                            match deadline
+                                .run(self.apply_reranking(
+                                    &document_query,
+                                    &mut results,
+                                    true,
+                                    rerank_top_k,
+                                ))
                                 .await
                             {
-                                tracing::warn!(
-                                    "Hybrid document reranking failed, using base scores: {}",
-                                    error
-                                );
+                                Some(Ok(())) => {}
+                                Some(Err(error)) => {
+                                    tracing::warn!(
+                                        "Hybrid document reranking failed, using base scores: {}",
+                                        error
+                                    );
+                                }
+                                None => rerank_skipped.store(true, Ordering::Relaxed),
                             }
                         }
                     }
@@ -838,7 +2682,10 @@ impl SearchService {
                 }
             }
 
-            let results = apply_metadata_filters(results, &filters);
+            let mut results = apply_metadata_filters(results, &filters);
+            if exclude_low_confidence {
+                results.retain(|result| !is_low_confidence(&result.metadata));
+            }
             let mut hybrid_results = Vec::new();
 
             for doc in results {
@@ -846,6 +2693,16 @@ impl SearchService {
                     continue;
                 };
 
+                let weight = match (
+                    container_tag_weights.as_ref(),
+                    doc_map.get(&doc.document_id),
+                ) {
+                    (Some(weights), Some(source)) => {
+                        container_tag_weight(weights, &source.container_tags)
+                    }
+                    _ => 1.0,
+                };
+
                 for (idx, chunk) in doc.chunks.iter().enumerate() {
                     let Some(chunk_id) = chunk_ids.get(idx) else {
                         continue;
@@ -856,10 +2713,13 @@ impl SearchService {
                         memory: None,
                         chunk: Some(chunk.content.clone()),
                         document_id: Some(doc.document_id.clone()),
-                        similarity: chunk.score,
-                        rerank_score: chunk.rerank_score,
+                        chunker: chunk.chunker,
+                        highlight: chunk.highlight.clone(),
+                        similarity: chunk.score * weight,
+                        rerank_score: chunk.rerank_score.map(|score| score * weight),
                         metadata: doc.metadata.clone(),
                         updated_at: doc.updated_at,
+                        raw_similarity: None,
                     });
                 }
             }
@@ -875,27 +2735,68 @@ impl SearchService {
                 return Ok(Vec::new());
             }
 
-            let memory_limit = limit.saturating_mul(3);
-
-            let memories = self
-                .read_db
-                .search_similar_memories(
-                    &query_embedding,
-                    memory_limit,
-                    threshold,
-                    memory_container_tag.as_deref(),
-                    include_forgotten,
-                )
-                .await?;
+            let memory_limit = page.depth(limit).saturating_mul(3);
+
+            let vector_hits = match &query_embedding {
+                Some(embedding) => {
+                    self.reader()
+                        .search_similar_memories(
+                            embedding,
+                            memory_limit,
+                            threshold,
+                            memory_container_tag.as_deref(),
+                            language.as_deref(),
+                            include_forgotten,
+                        )
+                        .await?
+                }
+                None => Vec::new(),
+            };
+            let keyword_hits = if keyword_search {
+                self.reader()
+                    .search_keyword_memories(
+                        &search_text,
+                        memory_limit,
+                        memory_container_tag.as_deref(),
+                        language.as_deref(),
+                        include_forgotten,
+                    )
+                    .await?
+            } else {
+                Vec::new()
+            };
+            let memories = fuse_ranked(
+                vector_hits,
+                keyword_hits,
+                |hit| hit.memory.id.as_str(),
+                |hit, score| hit.score = score,
+            );
 
-            let ranker =
-                TemporalSearchRanker::new(self.episode_decay_days, self.episode_decay_factor);
+            let ranker = self.episode_ranker(memory_container_tag.as_deref()).await?;
+            let disable_decay = req.disable_decay.unwrap_or(false);
             let mut results: Vec<MemorySearchResult> = Vec::new();
+            let mut tag_weights: Vec<f32> = Vec::new();
+            let mut chain_roots: HashMap<String, String> = HashMap::new();
 
             for hit in memories {
                 let db_similarity = hit.score;
                 let memory = hit.memory;
-                let similarity = apply_memory_similarity(&ranker, &memory, db_similarity);
+                chain_roots.insert(
+                    memory.id.clone(),
+                    memory
+                        .root_memory_id
+                        .clone()
+                        .unwrap_or_else(|| memory.id.clone()),
+                );
+                let similarity = boost_score(
+                    apply_memory_similarity(&ranker, &memory, db_similarity, disable_decay),
+                    hit.importance,
+                    self.importance_boost,
+                );
+
+                tag_weights.push(container_tag_weights.as_ref().map_or(1.0, |weights| {
+                    container_tag_weight(weights, memory.container_tag.as_slice())
+                }));
 
                 results.push(MemorySearchResult {
                     id: memory.id,
@@ -908,10 +2809,14 @@ impl SearchService {
                     updated_at: memory.updated_at,
                     context: None,
                     documents: None,
+                    raw_similarity: None,
+                    decay_debug: None,
                 });
             }
 
-            if rerank_enabled {
+            if rerank_enabled && deadline.is_exceeded() {
+                rerank_skipped.store(true, Ordering::Relaxed);
+            } else if rerank_enabled {
                 if let Some(ref reranker) = self.reranker {
                     if reranker.is_enabled() {
                         let memory_texts: Vec<String> =
@@ -921,11 +2826,11 @@ impl SearchService {
                             let config_top_k = 100;
                             let rerank_top_k = memory_texts.len().min(config_top_k);
 
-                            match reranker
-                                .rerank(&memory_query, memory_texts, rerank_top_k)
+                            match deadline
+                                .run(reranker.rerank(&memory_query, memory_texts, rerank_top_k))
                                 .await
                             {
-                                Ok(rerank_results) => {
+                                Some(Ok(rerank_results)) => {
                                     for rerank_result in rerank_results {
                                         if rerank_result.index < results.len() {
                                             results[rerank_result.index].rerank_score =
@@ -934,12 +2839,13 @@ impl SearchService {
                                     }
                                     tracing::debug!("Hybrid memory reranking applied successfully");
                                 }
-                                Err(e) => {
+                                Some(Err(e)) => {
                                     tracing::warn!(
                                         "Hybrid memory reranking failed, using temporally-decayed scores: {}",
                                         e
                                     );
                                 }
+                                None => rerank_skipped.store(true, Ordering::Relaxed),
                             }
                         }
                     }
@@ -948,11 +2854,25 @@ impl SearchService {
                 }
             }
 
+            for (result, weight) in results.iter_mut().zip(tag_weights) {
+                result.similarity *= weight;
+                result.rerank_score = result.rerank_score.map(|score| score * weight);
+            }
+
+            if dedupe_chains {
+                results = dedupe_version_chains(results, &chain_roots);
+            }
+
             Ok(results)
         };
 
         let (document_results, memory_results) = tokio::join!(documents_future, memories_future);
 
+        if rerank_skipped.load(Ordering::Relaxed) {
+            tracing::debug!("Hybrid reranking dropped to stay within search time budget");
+            skipped_stages.push(SearchStage::Rerank);
+        }
+
         let (mut chunk_results, document_error) = match document_results {
             Ok(results) => (results, None),
             Err(error) => {
@@ -980,7 +2900,7 @@ impl SearchService {
             let mut memory_doc_ids: HashSet<String> = HashSet::new();
 
             for memory in &memory_results {
-                match self.read_db.get_sources_by_memory(&memory.id).await {
+                match self.reader().get_sources_by_memory(&memory.id).await {
                     Ok(sources) => {
                         for source in sources {
                             if !source.document_id.is_empty() {
@@ -1013,24 +2933,25 @@ impl SearchService {
                 memory: memory.memory,
                 chunk: None,
                 document_id: None,
-                similarity: memory.similarity,
-                rerank_score: memory.rerank_score,
+                similarity: memory.similarity * weights.memory,
+                rerank_score: memory.rerank_score.map(|score| score * weights.memory),
                 metadata: memory.metadata,
                 updated_at: memory.updated_at,
+                chunker: None,
+                highlight: None,
+                raw_similarity: None,
             })
             .collect();
 
-        results.extend(chunk_results);
+        results.extend(chunk_results.into_iter().map(|mut chunk| {
+            chunk.similarity *= weights.document;
+            chunk.rerank_score = chunk.rerank_score.map(|score| score * weights.document);
+            chunk
+        }));
 
-        results.sort_by(|a, b| {
-            let a_score = a.rerank_score.unwrap_or(a.similarity);
-            let b_score = b.rerank_score.unwrap_or(b.similarity);
-            b_score
-                .partial_cmp(&a_score)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+        results.sort_by(|a, b| rank_order(a.ranking_key(), b.ranking_key()));
 
-        results.truncate(limit as usize);
+        let next_cursor = take_page(&mut results, &page, limit as usize);
 
         // After finalizing hybrid results (deduplication and truncation), collect
         // memory IDs from returned results and update last_accessed in batch.
@@ -1049,7 +2970,7 @@ impl SearchService {
         let mut seen_ids: HashSet<&str> = HashSet::new();
         memory_ids.retain(|id| seen_ids.insert(*id));
 
-        if !memory_ids.is_empty() {
+        if !memory_ids.is_empty() && !req.skip_access_tracking {
             match self
                 .write_db
                 .update_memory_last_accessed_batch(&memory_ids)
@@ -1079,6 +3000,11 @@ impl SearchService {
             total,
             timing,
             rewritten_query,
+            degraded: !skipped_stages.is_empty(),
+            skipped_stages,
+            relaxed_threshold: None,
+            next_cursor,
+            weights,
         })
     }
 }
@@ -1115,6 +3041,7 @@ mod tests {
                 metadata: HashMap::new(),
                 created_at: chrono::Utc::now(),
                 updated_at: chrono::Utc::now(),
+                raw_score: None,
             },
             DocumentSearchResult {
                 document_id: "doc2".to_string(),
@@ -1128,6 +3055,7 @@ mod tests {
                 metadata: HashMap::new(),
                 created_at: chrono::Utc::now(),
                 updated_at: chrono::Utc::now(),
+                raw_score: None,
             },
             DocumentSearchResult {
                 document_id: "doc3".to_string(),
@@ -1141,6 +3069,7 @@ mod tests {
                 metadata: HashMap::new(),
                 created_at: chrono::Utc::now(),
                 updated_at: chrono::Utc::now(),
+                raw_score: None,
             },
         ];
 
@@ -1157,6 +3086,213 @@ mod tests {
         assert_eq!(results[2].document_id, "doc1");
     }
 
+    fn condition(key: &str, operator: FilterOperator, value: serde_json::Value) -> FilterCondition {
+        FilterCondition {
+            key: key.to_string(),
+            value,
+            negate: None,
+            filter_type: None,
+            operator: Some(operator),
+        }
+    }
+
+    #[test]
+    fn test_metadata_filters_compare_numbers_and_booleans() {
+        let metadata: Metadata = HashMap::from([
+            ("year".to_string(), json!(2021)),
+            ("price".to_string(), json!(99.5)),
+            ("published".to_string(), json!(true)),
+            ("title".to_string(), json!("2021 report")),
+            ("tags".to_string(), json!(["rust", "search"])),
+        ]);
+        let matches =
+            |key, operator, value| condition_matches(&condition(key, operator, value), &metadata);
+
+        assert!(matches("year", FilterOperator::Gte, json!(2020)));
+        assert!(matches("year", FilterOperator::Eq, json!(2021.0)));
+        assert!(!matches("year", FilterOperator::Lt, json!(2021)));
+        assert!(matches("price", FilterOperator::Gt, json!(99)));
+        assert!(matches("price", FilterOperator::Lte, json!(99.5)));
+        assert!(!matches("price", FilterOperator::Gt, json!(100)));
+        assert!(matches("published", FilterOperator::Eq, json!(true)));
+        assert!(matches("published", FilterOperator::Ne, json!(false)));
+        assert!(matches("title", FilterOperator::Contains, json!("report")));
+        assert!(matches("tags", FilterOperator::Contains, json!("rust")));
+        assert!(matches("tags", FilterOperator::Exists, json!(null)));
+        assert!(!matches("missing", FilterOperator::Exists, json!(null)));
+
+        // Mismatched types never match, whichever the operator.
+        assert!(!matches("title", FilterOperator::Gt, json!(100)));
+        assert!(!matches("title", FilterOperator::Ne, json!(2021)));
+        assert!(!matches("year", FilterOperator::Eq, json!("2021")));
+        assert!(!matches("published", FilterOperator::Eq, json!(1)));
+    }
+
+    #[test]
+    fn test_resolve_metadata_key_follows_dot_paths() {
+        let metadata: Metadata = HashMap::from([
+            (
+                "author".to_string(),
+                json!({"name": "Alice", "org": {"team": {"lead": "Bob"}}}),
+            ),
+            ("tags".to_string(), json!(["rust", {"kind": "search"}])),
+            ("file.ext".to_string(), json!("md")),
+        ]);
+
+        assert_eq!(
+            resolve_metadata_key(&metadata, "author.name"),
+            Some(&json!("Alice"))
+        );
+        assert_eq!(
+            resolve_metadata_key(&metadata, "author.org.team.lead"),
+            Some(&json!("Bob"))
+        );
+        assert_eq!(
+            resolve_metadata_key(&metadata, "tags.0"),
+            Some(&json!("rust"))
+        );
+        assert_eq!(
+            resolve_metadata_key(&metadata, "tags.1.kind"),
+            Some(&json!("search"))
+        );
+        assert_eq!(
+            resolve_metadata_key(&metadata, "file.ext"),
+            Some(&json!("md"))
+        );
+
+        for missing in [
+            "author.email",
+            "author.org.office.floor",
+            "author.name.first",
+            "tags.5",
+            "tags.first",
+            "editor.name",
+        ] {
+            assert_eq!(resolve_metadata_key(&metadata, missing), None, "{missing}");
+        }
+        assert!(!condition_matches(
+            &condition(
+                "author.org.office.floor",
+                FilterOperator::Exists,
+                json!(null)
+            ),
+            &metadata
+        ));
+        assert!(condition_matches(
+            &condition("author.org.team.lead", FilterOperator::Eq, json!("Bob")),
+            &metadata
+        ));
+    }
+
+    #[test]
+    fn test_apply_metadata_filters_defaults_to_equality() {
+        let result = |id: &str, year: serde_json::Value| DocumentSearchResult {
+            document_id: id.to_string(),
+            title: None,
+            doc_type: None,
+            score: 0.9,
+            rerank_score: None,
+            chunks: vec![],
+            summary: None,
+            content: None,
+            metadata: HashMap::from([("year".to_string(), year)]),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            raw_score: None,
+        };
+        let results = vec![
+            result("old", json!(2019)),
+            result("new", json!(2023)),
+            result("text", json!("2023")),
+        ];
+
+        let range = Some(SearchFilters::Flat {
+            and: Some(vec![condition("year", FilterOperator::Gt, json!(2020))]),
+            or: None,
+        });
+        let filtered = apply_metadata_filters(results.clone(), &range);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].document_id, "new");
+
+        let mut equals = condition("year", FilterOperator::Eq, json!("2023"));
+        equals.operator = None;
+        let filtered = apply_metadata_filters(
+            results,
+            &Some(SearchFilters::Flat {
+                and: None,
+                or: Some(vec![equals]),
+            }),
+        );
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].document_id, "text");
+    }
+
+    #[test]
+    fn test_nested_filter_expression_evaluates_three_levels() {
+        // (lang = rust AND (year > 2020 OR NOT draft exists)) OR NOT (team = search)
+        let filters: SearchFilters = serde_json::from_value(json!({
+            "OR": [
+                {"AND": [
+                    {"key": "lang", "value": "rust"},
+                    {"OR": [
+                        {"key": "year", "value": 2020, "operator": "gt"},
+                        {"NOT": {"key": "draft", "operator": "exists"}}
+                    ]}
+                ]},
+                {"NOT": {"key": "team", "value": "search"}}
+            ]
+        }))
+        .unwrap();
+        assert!(matches!(filters, SearchFilters::Expr(FilterExpr::Or(_))));
+        let expr = filters.to_expr();
+        let matches = |metadata: serde_json::Value| {
+            let metadata: Metadata = serde_json::from_value(metadata).unwrap();
+            expr_matches(&expr, &metadata)
+        };
+
+        assert!(matches(
+            json!({"lang": "rust", "year": 2023, "draft": true, "team": "search"})
+        ));
+        assert!(matches(
+            json!({"lang": "rust", "year": 2019, "team": "search"})
+        ));
+        assert!(!matches(
+            json!({"lang": "rust", "year": 2019, "draft": true, "team": "search"})
+        ));
+        assert!(!matches(
+            json!({"lang": "go", "year": 2023, "team": "search"})
+        ));
+        assert!(matches(json!({"lang": "go", "team": "infra"})));
+    }
+
+    #[test]
+    fn test_flat_filters_still_deserialize() {
+        let filters: SearchFilters = serde_json::from_value(json!({
+            "AND": [{"key": "lang", "value": "rust"}],
+            "OR": [{"key": "year", "value": 2023}, {"key": "year", "value": 2024}]
+        }))
+        .unwrap();
+        assert!(matches!(
+            filters,
+            SearchFilters::Flat {
+                and: Some(_),
+                or: Some(_)
+            }
+        ));
+        let expr = filters.to_expr();
+        let matches = |metadata: serde_json::Value| {
+            let metadata: Metadata = serde_json::from_value(metadata).unwrap();
+            expr_matches(&expr, &metadata)
+        };
+
+        assert!(matches(json!({"lang": "rust", "year": 2024})));
+        assert!(!matches(json!({"lang": "rust", "year": 2022})));
+        assert!(!matches(json!({"lang": "go", "year": 2023})));
+
+        let empty: SearchFilters = serde_json::from_value(json!({})).unwrap();
+        assert!(expr_matches(&empty.to_expr(), &Metadata::new()));
+    }
+
     #[test]
     fn test_rerank_level_determination() {
         assert!(determine_rerank_level("chunk", 10));
@@ -1175,6 +3311,256 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_relaxed_thresholds_step_down_to_floor() {
+        let thresholds = relaxed_thresholds(0.6, 0.3);
+        assert_eq!(thresholds.len(), MAX_RELAXATION_STEPS);
+        assert!((thresholds[0] - 0.5).abs() < 1e-6);
+        assert!((thresholds[1] - 0.4).abs() < 1e-6);
+        assert_eq!(thresholds[2], 0.3);
+        assert!(relaxed_thresholds(0.3, 0.3).is_empty());
+        assert!(relaxed_thresholds(0.2, 0.3).is_empty());
+    }
+
+    #[test]
+    fn test_calibrated_scores_min_max() {
+        let scores = calibrated_scores(&[0.82, 0.78, 0.74], ScoreCalibration::MinMax, 0.75, 15.0);
+        assert!((scores[0] - 1.0).abs() < 1e-6);
+        assert!((scores[1] - 0.5).abs() < 1e-4);
+        assert!(scores[2].abs() < 1e-6);
+
+        // A lone result has nothing to be rescaled against.
+        assert_eq!(
+            calibrated_scores(&[0.7], ScoreCalibration::MinMax, 0.75, 15.0),
+            vec![1.0]
+        );
+        assert!(calibrated_scores(&[], ScoreCalibration::MinMax, 0.75, 15.0).is_empty());
+    }
+
+    #[test]
+    fn test_calibrated_scores_sigmoid_spreads_around_midpoint() {
+        let scores = calibrated_scores(&[0.9, 0.75, 0.6], ScoreCalibration::Sigmoid, 0.75, 15.0);
+        assert!(scores[0] > 0.9);
+        assert!((scores[1] - 0.5).abs() < 1e-6);
+        assert!(scores[2] < 0.1);
+        assert!(scores.windows(2).all(|pair| pair[0] > pair[1]));
+    }
+
+    fn ranked_hit(id: &str, similarity: f32, rerank_score: Option<f32>) -> HybridSearchResult {
+        HybridSearchResult {
+            id: id.to_string(),
+            memory: Some(id.to_string()),
+            chunk: None,
+            document_id: None,
+            chunker: None,
+            highlight: None,
+            similarity,
+            raw_similarity: None,
+            rerank_score,
+            metadata: HashMap::new(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn ranked_hits() -> Vec<HybridSearchResult> {
+        let mut hits = vec![
+            ranked_hit("e", 0.6, None),
+            ranked_hit("b", 0.8, None),
+            ranked_hit("d", 0.7, None),
+            ranked_hit("a", 0.8, None),
+            ranked_hit("c", 0.2, Some(0.75)),
+        ];
+        hits.sort_by(|a, b| rank_order(a.ranking_key(), b.ranking_key()));
+        hits
+    }
+
+    fn page_ids(hits: &[HybridSearchResult]) -> Vec<&str> {
+        hits.iter().map(|hit| hit.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_cursor_pages_through_ranked_results_once() {
+        let mut pages = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let start = PageStart::from_request(cursor.as_deref(), None).unwrap();
+            let mut hits = ranked_hits();
+            cursor = take_page(&mut hits, &start, 2);
+            pages.push(page_ids(&hits).join(""));
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        // Ties are broken by id; the rerank score decides where "c" lands.
+        assert_eq!(pages, vec!["ab", "cd", "e"]);
+    }
+
+    #[test]
+    fn test_cursor_skips_past_result_that_dropped_out() {
+        let start = PageStart::from_request(None, None).unwrap();
+        let mut hits = ranked_hits();
+        let cursor = take_page(&mut hits, &start, 3).unwrap();
+
+        let start = PageStart::from_request(Some(&cursor), None).unwrap();
+        let mut hits = ranked_hits();
+        hits.retain(|hit| hit.id != "c");
+        assert!(take_page(&mut hits, &start, 3).is_none());
+        assert_eq!(page_ids(&hits), vec!["d", "e"]);
+    }
+
+    #[test]
+    fn test_offset_pages_and_rejects_bad_cursors() {
+        let start = PageStart::from_request(None, Some(3)).unwrap();
+        assert_eq!(start.depth(10), 14);
+        let mut hits = ranked_hits();
+        assert!(take_page(&mut hits, &start, 10).is_none());
+        assert_eq!(page_ids(&hits), vec!["d", "e"]);
+
+        assert_eq!(
+            PageStart::from_request(None, Some(5_000))
+                .unwrap()
+                .depth(10),
+            MAX_SEARCH_DEPTH
+        );
+        assert!(matches!(
+            PageStart::from_request(Some("not a cursor"), None),
+            Err(MomoError::Validation(_))
+        ));
+        let cursor = SearchCursor {
+            position: 2,
+            score: 0.8,
+            id: "b".to_string(),
+        }
+        .encode();
+        assert!(matches!(
+            PageStart::from_request(Some(&cursor), Some(2)),
+            Err(MomoError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_fuse_ranked_rewards_hits_found_by_both() {
+        let hits = |ids: &[&str]| -> Vec<(String, f32)> {
+            ids.iter().map(|id| (id.to_string(), 0.0)).collect()
+        };
+        let fuse = |vector, keyword| {
+            fuse_ranked(
+                vector,
+                keyword,
+                |hit: &(String, f32)| hit.0.as_str(),
+                |hit, score| hit.1 = score,
+            )
+        };
+
+        let fused = fuse(hits(&["a", "b", "c"]), hits(&["c", "d"]));
+        let ids: Vec<&str> = fused.iter().map(|hit| hit.0.as_str()).collect();
+        assert_eq!(ids, vec!["c", "a", "b", "d"]);
+        assert!(fused[0].1 < 1.0 && fused[0].1 > fused[1].1);
+
+        let fused = fuse(hits(&["a"]), hits(&["a"]));
+        assert!((fused[0].1 - 1.0).abs() < 1e-6);
+
+        // A single non-empty list keeps its own scores.
+        let vector = vec![("a".to_string(), 0.9)];
+        assert_eq!(fuse(vector.clone(), Vec::new()), vector);
+        assert_eq!(fuse(Vec::new(), vector.clone()), vector);
+    }
+
+    #[test]
+    fn test_diversity_lambda_only_applies_when_requested() {
+        assert_eq!(diversity_lambda(None, Some(0.2)).unwrap(), None);
+        assert_eq!(
+            diversity_lambda(Some(true), None).unwrap(),
+            Some(DEFAULT_DIVERSITY_LAMBDA)
+        );
+        assert_eq!(diversity_lambda(Some(true), Some(0.2)).unwrap(), Some(0.2));
+        for invalid in [-0.1, 1.5, f32::NAN] {
+            assert!(matches!(
+                diversity_lambda(Some(true), Some(invalid)),
+                Err(MomoError::Validation(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_freshness_weight_rejects_values_outside_unit_range() {
+        assert_eq!(freshness_weight(None).unwrap(), None);
+        assert_eq!(freshness_weight(Some(0.0)).unwrap(), None);
+        assert_eq!(freshness_weight(Some(0.4)).unwrap(), Some(0.4));
+        for invalid in [-0.1, 1.5, f32::NAN] {
+            assert!(matches!(
+                freshness_weight(Some(invalid)),
+                Err(MomoError::Validation(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_container_tag_weights_reject_invalid_values() {
+        assert!(check_container_tag_weights(None).is_ok());
+        let valid = HashMap::from([("a".to_string(), 0.0), ("b".to_string(), 2.5)]);
+        assert!(check_container_tag_weights(Some(&valid)).is_ok());
+
+        for invalid in [-1.0, f32::NAN, f32::INFINITY] {
+            let weights = HashMap::from([("a".to_string(), invalid)]);
+            assert!(matches!(
+                check_container_tag_weights(Some(&weights)),
+                Err(MomoError::Validation(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_requested_model_must_match_container_dimensions() {
+        let record = ContainerEmbeddingModel {
+            container_tag: "multilingual".to_string(),
+            model: "intfloat/multilingual-e5-base".to_string(),
+            dimensions: 768,
+        };
+
+        assert!(check_requested_model_dimensions(&record, "BAAI/bge-base-en-v1.5", 768).is_ok());
+        match check_requested_model_dimensions(&record, "BAAI/bge-small-en-v1.5", 384) {
+            Err(MomoError::Validation(msg)) => {
+                assert!(msg.contains("384"), "{msg}");
+                assert!(msg.contains("768"), "{msg}");
+                assert!(msg.contains("multilingual"), "{msg}");
+            }
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_hybrid_weights_default_and_reject_invalid_values() {
+        assert_eq!(
+            hybrid_weights(None, None).unwrap(),
+            HybridWeights::default()
+        );
+        let weights = hybrid_weights(Some(2.0), Some(0.0)).unwrap();
+        assert_eq!(weights.memory, 2.0);
+        assert_eq!(weights.document, 0.0);
+
+        for invalid in [-1.0, f32::NAN, f32::INFINITY] {
+            assert!(matches!(
+                hybrid_weights(Some(invalid), None),
+                Err(MomoError::Validation(_))
+            ));
+            assert!(matches!(
+                hybrid_weights(None, Some(invalid)),
+                Err(MomoError::Validation(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_min_results_target_is_capped_at_limit() {
+        assert_eq!(min_results_target(None, Some(5)), None);
+        assert_eq!(min_results_target(Some(0), Some(5)), None);
+        assert_eq!(min_results_target(Some(3), Some(5)), Some(3));
+        assert_eq!(min_results_target(Some(8), Some(5)), Some(5));
+        assert_eq!(min_results_target(Some(20), None), Some(10));
+    }
+
     #[test]
     fn test_search_memory_similarity_uses_db_score_for_fact() {
         let ranker = TemporalSearchRanker::default();
@@ -1186,7 +3572,7 @@ mod tests {
         memory.memory_type = crate::models::MemoryType::Fact;
 
         let db_similarity = 0.73;
-        let similarity = apply_memory_similarity(&ranker, &memory, db_similarity);
+        let similarity = apply_memory_similarity(&ranker, &memory, db_similarity, false);
 
         assert!((similarity - db_similarity).abs() < 0.0001);
     }
@@ -1203,7 +3589,7 @@ mod tests {
         memory.last_accessed = Some(chrono::Utc::now() - chrono::Duration::days(60));
 
         let db_similarity = 0.9;
-        let similarity = apply_memory_similarity(&ranker, &memory, db_similarity);
+        let similarity = apply_memory_similarity(&ranker, &memory, db_similarity, false);
 
         assert!(similarity < db_similarity);
         assert!(similarity > 0.0);
@@ -1220,11 +3606,153 @@ mod tests {
         memory.memory_type = crate::models::MemoryType::Preference;
 
         let db_similarity = 0.64;
-        let similarity = apply_memory_similarity(&ranker, &memory, db_similarity);
+        let similarity = apply_memory_similarity(&ranker, &memory, db_similarity, false);
 
         assert!((similarity - db_similarity).abs() < 0.0001);
     }
 
+    #[test]
+    fn test_search_memory_similarity_disable_decay_keeps_raw_ordering() {
+        let ranker = TemporalSearchRanker::default();
+        let episode = |id: &str, days_old: i64| {
+            let mut memory = Memory::new(
+                id.to_string(),
+                format!("Episode {id}"),
+                "space-id".to_string(),
+            );
+            memory.memory_type = crate::models::MemoryType::Episode;
+            memory.last_accessed = Some(chrono::Utc::now() - chrono::Duration::days(days_old));
+            memory
+        };
+        let hits = [(episode("old", 120), 0.9f32), (episode("fresh", 0), 0.8f32)];
+
+        let ranked = |disable_decay: bool| {
+            let mut scored: Vec<(&str, f32)> = hits
+                .iter()
+                .map(|(memory, db_similarity)| {
+                    (
+                        memory.id.as_str(),
+                        apply_memory_similarity(&ranker, memory, *db_similarity, disable_decay),
+                    )
+                })
+                .collect();
+            scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+            scored
+        };
+
+        let decayed = ranked(false);
+        assert_eq!(decayed[0].0, "fresh");
+        assert!(decayed[1].1 < 0.9);
+
+        let raw = ranked(true);
+        assert_eq!(raw[0], ("old", 0.9));
+        assert_eq!(raw[1], ("fresh", 0.8));
+    }
+
+    #[test]
+    fn test_memory_decay_debug_reports_raw_and_decayed_scores() {
+        let ranker = TemporalSearchRanker::default();
+        let mut memory = Memory::new(
+            "memory-id".to_string(),
+            "Episode memory".to_string(),
+            "space-id".to_string(),
+        );
+        memory.memory_type = crate::models::MemoryType::Episode;
+        memory.last_accessed = Some(chrono::Utc::now() - chrono::Duration::days(90));
+
+        let debug = memory_decay_debug(&ranker, &memory, 0.9, false);
+        assert_eq!(debug.db_similarity, 0.9);
+        assert!(debug.decay_factor < 1.0);
+        assert!(debug.decayed_similarity < debug.db_similarity);
+        assert!((debug.decayed_similarity - 0.9 * debug.decay_factor).abs() < 0.0001);
+        assert_eq!(
+            debug.decayed_similarity,
+            apply_memory_similarity(&ranker, &memory, 0.9, false)
+        );
+
+        let undecayed = memory_decay_debug(&ranker, &memory, 0.9, true);
+        assert_eq!(undecayed.decay_factor, 1.0);
+        assert_eq!(undecayed.decayed_similarity, 0.9);
+    }
+
+    #[test]
+    fn test_build_memory_context_caps_children_parents_and_related() {
+        let memory_at = |id: &str, version: i32| {
+            let mut memory = Memory::new(id.to_string(), id.to_string(), "space-id".to_string());
+            memory.version = version;
+            memory.updated_at = chrono::Utc::now() + chrono::Duration::seconds(version as i64);
+            memory
+        };
+
+        let mut hit = memory_at("hit", 5);
+        hit.root_memory_id = Some("root".to_string());
+        let mut related_map = HashMap::new();
+        for i in 0..8 {
+            let related = memory_at(&format!("rel_{i}"), i);
+            hit.memory_relations
+                .insert(related.id.clone(), MemoryRelationType::Extends);
+            related_map.insert(related.id.clone(), related);
+        }
+
+        let children: Vec<Memory> = (0..50)
+            .rev()
+            .map(|i| {
+                let mut child = memory_at(&format!("child_{i}"), i);
+                child.parent_memory_id = Some("hit".to_string());
+                child
+            })
+            .collect();
+        let parents: Vec<Memory> = (1..5)
+            .map(|i| {
+                let mut parent = memory_at(&format!("v{i}"), i);
+                parent.root_memory_id = Some("root".to_string());
+                parent
+            })
+            .collect();
+
+        let children_by_parent = group_memories_by(children, |m| m.parent_memory_id.as_ref());
+        let parents_by_root = group_memories_by(parents, |m| m.root_memory_id.as_ref());
+        assert_eq!(children_by_parent["hit"].len(), 50);
+
+        let context =
+            build_memory_context(&hit, &parents_by_root, &children_by_parent, &related_map, 3);
+
+        let ids = |infos: &[MemoryRelationInfo]| -> Vec<String> {
+            infos.iter().map(|info| info.id.clone()).collect()
+        };
+        assert_eq!(ids(&context.children), ["child_49", "child_48", "child_47"]);
+        assert_eq!(ids(&context.parents), ["v2", "v3", "v4"]);
+        assert_eq!(ids(&context.related), ["rel_7", "rel_6", "rel_5"]);
+
+        let uncapped = build_memory_context(
+            &hit,
+            &parents_by_root,
+            &children_by_parent,
+            &related_map,
+            100,
+        );
+        assert_eq!(uncapped.children.len(), 50);
+        assert_eq!(uncapped.parents.len(), 4);
+        assert_eq!(uncapped.related.len(), 8);
+    }
+
+    #[test]
+    fn test_neighbor_context_splits_around_position() {
+        let neighbors: HashMap<i32, String> = (0..6).map(|pos| (pos, format!("c{pos}"))).collect();
+
+        let (before, after) = neighbor_context(&neighbors, 3, 2);
+        assert_eq!(before, vec!["c1", "c2"]);
+        assert_eq!(after, vec!["c4", "c5"]);
+
+        let (before, after) = neighbor_context(&neighbors, 0, 2);
+        assert!(before.is_empty());
+        assert_eq!(after, vec!["c1", "c2"]);
+
+        let (before, after) = neighbor_context(&neighbors, 5, 2);
+        assert_eq!(before, vec!["c3", "c4"]);
+        assert!(after.is_empty());
+    }
+
     #[test]
     fn test_chunk_search_result_with_rerank_score() {
         let chunk = ChunkSearchResult {
@@ -1232,6 +3760,12 @@ mod tests {
             score: 0.85,
             rerank_score: Some(0.92),
             is_relevant: true,
+            position: 0,
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            chunker: None,
+            snippet: None,
+            highlight: None,
         };
 
         assert_eq!(chunk.score, 0.85);
@@ -1245,6 +3779,12 @@ mod tests {
             score: 0.75,
             rerank_score: None,
             is_relevant: true,
+            position: 0,
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            chunker: None,
+            snippet: None,
+            highlight: None,
         };
 
         assert_eq!(chunk.score, 0.75);
@@ -1265,6 +3805,8 @@ mod tests {
                 updated_at: chrono::Utc::now(),
                 context: None,
                 documents: None,
+                raw_similarity: None,
+                decay_debug: None,
             },
             MemorySearchResult {
                 id: "mem2".to_string(),
@@ -1277,6 +3819,8 @@ mod tests {
                 updated_at: chrono::Utc::now(),
                 context: None,
                 documents: None,
+                raw_similarity: None,
+                decay_debug: None,
             },
             MemorySearchResult {
                 id: "mem3".to_string(),
@@ -1289,6 +3833,8 @@ mod tests {
                 updated_at: chrono::Utc::now(),
                 context: None,
                 documents: None,
+                raw_similarity: None,
+                decay_debug: None,
             },
         ];
 
@@ -1318,6 +3864,8 @@ mod tests {
             updated_at: chrono::Utc::now(),
             context: None,
             documents: None,
+            raw_similarity: None,
+            decay_debug: None,
         };
 
         assert_eq!(result.similarity, 0.88);
@@ -1337,6 +3885,8 @@ mod tests {
             updated_at: chrono::Utc::now(),
             context: None,
             documents: None,
+            raw_similarity: None,
+            decay_debug: None,
         };
 
         assert_eq!(result.similarity, 0.72);
@@ -1354,6 +3904,7 @@ mod tests {
             url: format!("file:{}", db_path.display()),
             auth_token: None,
             local_path: None,
+            max_connections: 4,
         };
         let db = Database::new(&config).await.unwrap();
         let conn = db.connect().unwrap();
@@ -1383,6 +3934,8 @@ mod tests {
             model: "BAAI/bge-small-en-v1.5".to_string(),
             dimensions: 384,
             batch_size: 2,
+            query_cache_size: 0,
+            max_concurrent_batches: 4,
         };
 
         let provider =
@@ -1430,6 +3983,8 @@ mod tests {
                 position: index as i32,
                 token_count: None,
                 created_at: now,
+                chunker: None,
+                language: None,
             };
 
             ChunkRepository::create(conn, &chunk).await.unwrap();
@@ -1453,18 +4008,162 @@ mod tests {
             format!("Memory {memory_id}"),
             "default".to_string(),
         );
-        memory.container_tag = container_tag.map(|tag| tag.to_string());
-        memory.memory_type = MemoryType::Fact;
-        memory.updated_at = updated_at;
+        memory.container_tag = container_tag.map(|tag| tag.to_string());
+        memory.memory_type = MemoryType::Fact;
+        memory.updated_at = updated_at;
+
+        MemoryRepository::create(conn, &memory).await.unwrap();
+
+        let embedding = embeddings.embed_passage(&memory.memory).await.unwrap();
+        MemoryRepository::update_embedding(conn, &memory.id, &embedding)
+            .await
+            .unwrap();
+
+        memory
+    }
+
+    #[tokio::test]
+    async fn test_search_memories_batch_keeps_query_order() {
+        let (db, conn, _temp_dir) = setup_hybrid_db().await;
+        let (embeddings, _mock_server) = test_embeddings_provider().await;
+        let now = Utc::now();
+        insert_memory_real(&conn, "alpha", Some("a"), now, &embeddings).await;
+        insert_memory_real(&conn, "beta", Some("b"), now, &embeddings).await;
+
+        let service = SearchService::new(
+            db.clone(),
+            db,
+            embeddings,
+            None,
+            LlmProvider::unavailable("tests"),
+            &Config::from_env(),
+        );
+        let query = |container_tag: &str| SearchMemoriesRequest {
+            q: "query".to_string(),
+            container_tag: Some(container_tag.to_string()),
+            threshold: Some(0.0),
+            ..Default::default()
+        };
+
+        let responses = service
+            .search_memories_batch(vec![query("b"), query("a"), query("none")])
+            .await
+            .unwrap();
+        let ids: Vec<Vec<&str>> = responses
+            .iter()
+            .map(|response| response.results.iter().map(|r| r.id.as_str()).collect())
+            .collect();
+        assert_eq!(ids, vec![vec!["beta"], vec!["alpha"], vec![]]);
+
+        let too_many = (0..=MAX_BATCH_QUERIES).map(|_| query("a")).collect();
+        assert!(matches!(
+            service.search_memories_batch(too_many).await,
+            Err(MomoError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_stream_memories_sends_each_hit_once_best_first() {
+        let (db, conn, _temp_dir) = setup_hybrid_db().await;
+        let (embeddings, _mock_server) = test_embeddings_provider().await;
+        let now = Utc::now();
+        for i in 0..25 {
+            insert_memory_real(&conn, &format!("m{i}"), Some("s"), now, &embeddings).await;
+        }
+        let service = SearchService::new(
+            db.clone(),
+            db,
+            embeddings,
+            None,
+            LlmProvider::unavailable("tests"),
+            &Config::from_env(),
+        );
+        let req = SearchMemoriesRequest {
+            q: "Memory".to_string(),
+            container_tag: Some("s".to_string()),
+            threshold: Some(0.0),
+            limit: Some(25),
+            ..Default::default()
+        };
+
+        // Three windows (10, 20, 25) whose overlaps must not be resent.
+        let (tx, mut rx) = mpsc::channel(100);
+        let summary = service.stream_memories(req.clone(), tx).await.unwrap();
+        let mut streamed = Vec::new();
+        while let Some(result) = rx.recv().await {
+            streamed.push(result);
+        }
+        assert_eq!(summary.total, 25);
+        assert_eq!(streamed.len(), 25);
+        let ids: HashSet<&str> = streamed.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids.len(), 25);
+        assert!(streamed[..10]
+            .windows(2)
+            .all(|pair| pair[0].similarity >= pair[1].similarity));
+
+        // A closed receiver stops the search at the first send.
+        let (tx, rx) = mpsc::channel(1);
+        drop(rx);
+        let summary = service.stream_memories(req, tx).await.unwrap();
+        assert_eq!(summary.total, 0);
+    }
+
+    #[tokio::test]
+    async fn test_stream_and_batch_reject_invalid_container_tag_weights() {
+        let (db, _conn, _temp_dir) = setup_hybrid_db().await;
+        let (embeddings, _mock_server) = test_embeddings_provider().await;
+        let service = SearchService::new(
+            db.clone(),
+            db,
+            embeddings,
+            None,
+            LlmProvider::unavailable("tests"),
+            &Config::from_env(),
+        );
+        let req = SearchMemoriesRequest {
+            q: "Memory".to_string(),
+            container_tag_weights: Some(HashMap::from([("s".to_string(), f32::NAN)])),
+            ..Default::default()
+        };
+
+        let (tx, _rx) = mpsc::channel(1);
+        assert!(matches!(
+            service.stream_memories(req.clone(), tx).await,
+            Err(MomoError::Validation(_))
+        ));
+        assert!(matches!(
+            service.search_memories_batch(vec![req]).await,
+            Err(MomoError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_search_memories_caches_query_embedding() {
+        let (db, _conn, _temp_dir) = setup_hybrid_db().await;
+        let (embeddings, _mock_server) = test_embeddings_provider().await;
+        let mut config = Config::from_env();
+        config.embeddings.query_cache_size = 10;
+        let service = SearchService::new(
+            db.clone(),
+            db,
+            embeddings,
+            None,
+            LlmProvider::unavailable("tests"),
+            &config,
+        );
 
-        MemoryRepository::create(conn, &memory).await.unwrap();
-
-        let embedding = embeddings.embed_passage(&memory.memory).await.unwrap();
-        MemoryRepository::update_embedding(conn, &memory.id, &embedding)
-            .await
-            .unwrap();
+        let cache = service.query_embedding_cache.as_ref().unwrap().clone();
+        for q in ["query", "  query "] {
+            let req = SearchMemoriesRequest {
+                q: q.to_string(),
+                ..Default::default()
+            };
+            service.search_memories(req).await.unwrap();
+        }
 
-        memory
+        // Only the first search missed the cache and ran the embedding model.
+        assert_eq!(cache.misses(), 1);
+        assert!(cache.get(&service.embedding_model, "query").is_some());
     }
 
     #[tokio::test]
@@ -1522,6 +4221,22 @@ mod tests {
                 rerank: Some(false),
                 rewrite_query: Some(false),
                 search_mode: SearchMode::Hybrid,
+                dedupe_version_chains: None,
+                container_tag_weights: None,
+                language: None,
+                exclude_low_confidence: None,
+                disable_decay: None,
+                expand_synonyms: None,
+                chunker_types: None,
+                code_languages: None,
+                min_results: None,
+                score_calibration: None,
+                cursor: None,
+                offset: None,
+                document_weight: None,
+                memory_weight: None,
+                skip_access_tracking: false,
+                highlight: None,
             })
             .await
             .unwrap();
@@ -1541,6 +4256,52 @@ mod tests {
         assert!(fetched_fact.last_accessed.is_none());
     }
 
+    #[tokio::test]
+    async fn test_search_hybrid_skips_access_tracking_when_requested() {
+        let (db, conn, _temp_dir) = setup_hybrid_db().await;
+        let (embeddings, _mock_server) = test_embeddings_provider().await;
+
+        let mut episode = Memory::new(
+            "ep_mem".to_string(),
+            "Episode memory".to_string(),
+            "default".to_string(),
+        );
+        episode.memory_type = MemoryType::Episode;
+        episode.container_tag = Some("space".to_string());
+        MemoryRepository::create(&conn, &episode).await.unwrap();
+        let embedding = embeddings.embed_passage(&episode.memory).await.unwrap();
+        MemoryRepository::update_embedding(&conn, &episode.id, &embedding)
+            .await
+            .unwrap();
+
+        let service = SearchService::new(
+            db.clone(),
+            db,
+            embeddings,
+            None,
+            LlmProvider::unavailable("tests"),
+            &Config::from_env(),
+        );
+        let response = service
+            .search_hybrid(HybridSearchRequest {
+                q: "query".to_string(),
+                container_tag: Some("space".to_string()),
+                threshold: Some(0.0),
+                search_mode: SearchMode::Memories,
+                skip_access_tracking: true,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(response.results.len(), 1);
+        let memory = MemoryRepository::get_by_id(&conn, "ep_mem")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(memory.last_accessed.is_none());
+    }
+
     #[tokio::test]
     async fn test_search_hybrid_returns_both_types() {
         let (db, conn, _temp_dir) = setup_hybrid_db().await;
@@ -1570,6 +4331,22 @@ mod tests {
                 rerank: Some(false),
                 rewrite_query: Some(false),
                 search_mode: SearchMode::Hybrid,
+                dedupe_version_chains: None,
+                container_tag_weights: None,
+                language: None,
+                exclude_low_confidence: None,
+                disable_decay: None,
+                expand_synonyms: None,
+                chunker_types: None,
+                code_languages: None,
+                min_results: None,
+                score_calibration: None,
+                cursor: None,
+                offset: None,
+                document_weight: None,
+                memory_weight: None,
+                skip_access_tracking: false,
+                highlight: None,
             })
             .await
             .unwrap();
@@ -1578,6 +4355,52 @@ mod tests {
         assert!(response.results.iter().any(|r| r.chunk.is_some()));
     }
 
+    #[tokio::test]
+    async fn test_search_hybrid_dedupes_version_chains_when_requested() {
+        let (db, conn, _temp_dir) = setup_hybrid_db().await;
+        let (embeddings, _mock_server) = test_embeddings_provider().await;
+
+        insert_memory_real(&conn, "mem1", Some("space"), Utc::now(), &embeddings).await;
+        insert_memory_real(&conn, "mem2", Some("space"), Utc::now(), &embeddings).await;
+        conn.execute(
+            "UPDATE memories SET version = 2, parent_memory_id = 'mem1', \
+             root_memory_id = 'mem1' WHERE id = 'mem2'",
+            (),
+        )
+        .await
+        .unwrap();
+
+        let service = SearchService::new(
+            db.clone(),
+            db,
+            embeddings,
+            None,
+            LlmProvider::unavailable("tests"),
+            &Config::from_env(),
+        );
+        let search = |dedupe| {
+            service.search_hybrid(HybridSearchRequest {
+                q: "query".to_string(),
+                container_tag: Some("space".to_string()),
+                threshold: Some(0.0),
+                limit: Some(10),
+                rerank: Some(false),
+                rewrite_query: Some(false),
+                search_mode: SearchMode::Memories,
+                dedupe_version_chains: Some(dedupe),
+                ..Default::default()
+            })
+        };
+
+        let memory_ids = |response: HybridSearchResponse| -> Vec<String> {
+            let mut ids: Vec<String> = response.results.into_iter().map(|r| r.id).collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(memory_ids(search(false).await.unwrap()), ["mem1", "mem2"]);
+        assert_eq!(memory_ids(search(true).await.unwrap()), ["mem2"]);
+    }
+
     #[tokio::test]
     async fn test_search_documents_reads_from_read_backend() {
         let (read_db, read_conn, _read_temp_dir) = setup_hybrid_db().await;
@@ -1617,6 +4440,19 @@ mod tests {
                 rerank_level: None,
                 rerank_top_k: None,
                 rewrite_query: Some(false),
+                container_tag_weights: None,
+                context_chunks: None,
+                exclude_low_confidence: None,
+                expand_synonyms: None,
+                chunker_types: None,
+                code_languages: None,
+                min_results: None,
+                score_calibration: None,
+                cursor: None,
+                offset: None,
+                diversity_lambda: None,
+                diversify: None,
+                highlight: None,
             })
             .await
             .unwrap();
@@ -1625,6 +4461,259 @@ mod tests {
         assert_eq!(response.results[0].document_id, "doc_read_backend");
     }
 
+    #[tokio::test]
+    async fn test_search_documents_falls_back_to_write_backend_when_replica_fails() {
+        let (read_db, _read_conn, _read_temp_dir) = setup_hybrid_db().await;
+        let (write_db, write_conn, _write_temp_dir) = setup_hybrid_db().await;
+        let (embeddings, _mock_server) = test_embeddings_provider().await;
+
+        // Written to the primary but never synced to the replica.
+        insert_document_with_chunks_real(
+            &write_conn,
+            "doc_primary_only",
+            &["primary only chunk"],
+            &embeddings,
+        )
+        .await;
+
+        let replica = ReplicaHealth::new(2).with_fallback_after(2);
+        let service = SearchService::new(
+            read_db,
+            write_db,
+            embeddings,
+            None,
+            LlmProvider::unavailable("tests"),
+            &Config::from_env(),
+        )
+        .with_replica_health(replica.clone());
+
+        let request = || SearchDocumentsRequest {
+            q: "primary only chunk".to_string(),
+            container_tags: None,
+            chunk_threshold: Some(0.0),
+            document_threshold: None,
+            doc_id: None,
+            filters: None,
+            include_full_docs: Some(false),
+            include_summary: Some(false),
+            limit: Some(5),
+            only_matching_chunks: Some(false),
+            rerank: Some(false),
+            rerank_level: None,
+            rerank_top_k: None,
+            rewrite_query: Some(false),
+            container_tag_weights: None,
+            context_chunks: None,
+            exclude_low_confidence: None,
+            expand_synonyms: None,
+            chunker_types: None,
+            code_languages: None,
+            min_results: None,
+            score_calibration: None,
+            cursor: None,
+            offset: None,
+            diversity_lambda: None,
+            diversify: None,
+            highlight: None,
+        };
+        let sync_failure = || {
+            Err(crate::error::MomoError::Internal(
+                "replica down".to_string(),
+            ))
+        };
+
+        replica.record(&sync_failure());
+        let response = service.search_documents(request()).await.unwrap();
+        assert!(response.results.is_empty());
+
+        replica.record(&sync_failure());
+        let response = service.search_documents(request()).await.unwrap();
+        assert_eq!(response.results[0].document_id, "doc_primary_only");
+    }
+
+    #[tokio::test]
+    async fn test_search_documents_falls_back_to_keyword_search_when_enabled() {
+        let (db, conn, _temp_dir) = setup_hybrid_db().await;
+        let (embeddings, _mock_server) = test_embeddings_provider().await;
+
+        insert_document_with_chunks_real(
+            &conn,
+            "doc_error_codes",
+            &["The controller reported ERR-4242 after the upgrade"],
+            &embeddings,
+        )
+        .await;
+        let mut service = SearchService::new(
+            db.clone(),
+            db,
+            embeddings,
+            None,
+            LlmProvider::unavailable("tests"),
+            &Config::from_env(),
+        );
+        let request = || SearchDocumentsRequest {
+            q: "ERR-4242".to_string(),
+            container_tags: None,
+            chunk_threshold: Some(0.99),
+            document_threshold: None,
+            doc_id: None,
+            filters: None,
+            include_full_docs: Some(false),
+            include_summary: Some(false),
+            limit: Some(5),
+            only_matching_chunks: Some(true),
+            rerank: Some(false),
+            rerank_level: None,
+            rerank_top_k: None,
+            rewrite_query: Some(false),
+            container_tag_weights: None,
+            context_chunks: None,
+            exclude_low_confidence: None,
+            expand_synonyms: None,
+            chunker_types: None,
+            code_languages: None,
+            min_results: None,
+            score_calibration: None,
+            cursor: None,
+            offset: None,
+            diversity_lambda: None,
+            diversify: None,
+            highlight: None,
+        };
+
+        service.lexical_fallback = false;
+        let response = service.search_documents(request()).await.unwrap();
+        assert!(response.results.is_empty());
+        assert!(response.fallback.is_none());
+
+        service.lexical_fallback = true;
+        let response = service.search_documents(request()).await.unwrap();
+        assert_eq!(response.fallback, Some(SearchFallback::Lexical));
+        assert_eq!(response.results.len(), 1);
+        assert_eq!(response.results[0].document_id, "doc_error_codes");
+        assert_eq!(response.results[0].chunks.len(), 1);
+        assert_eq!(response.results[0].score, 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_search_hybrid_highlights_keyword_matches_with_configured_markers() {
+        let (db, conn, _temp_dir) = setup_hybrid_db().await;
+        let (embeddings, _mock_server) = test_embeddings_provider().await;
+
+        insert_document_with_chunks_real(
+            &conn,
+            "doc_error_codes",
+            &["The controller reported ERR-4242 after the upgrade"],
+            &embeddings,
+        )
+        .await;
+        let mut service = SearchService::new(
+            db.clone(),
+            db,
+            embeddings,
+            None,
+            LlmProvider::unavailable("tests"),
+            &Config::from_env(),
+        );
+        service.highlight_markers = HighlightMarkers {
+            open: "**".to_string(),
+            close: "**".to_string(),
+        };
+        let request = |highlight| HybridSearchRequest {
+            q: "upgrade".to_string(),
+            container_tag: Some("space".to_string()),
+            search_mode: SearchMode::Keyword,
+            highlight,
+            ..Default::default()
+        };
+
+        let response = service.search_hybrid(request(None)).await.unwrap();
+        assert_eq!(response.results.len(), 1);
+        assert!(response.results[0].highlight.is_none());
+
+        let response = service.search_hybrid(request(Some(true))).await.unwrap();
+        assert_eq!(
+            response.results[0].highlight.as_deref(),
+            Some("The controller reported ERR-4242 after the **upgrade**")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_vector_searches_reject_container_indexed_at_other_dimensions() {
+        let (db, conn, _temp_dir) = setup_hybrid_db().await;
+        let (embeddings, _mock_server) = test_embeddings_provider().await;
+
+        insert_document_with_chunks_real(&conn, "doc1", &["chunk one"], &embeddings).await;
+        db.set_container_embedding_model(&ContainerEmbeddingModel {
+            container_tag: "tenant_base".to_string(),
+            model: "BAAI/bge-base-en-v1.5".to_string(),
+            dimensions: 768,
+        })
+        .await
+        .unwrap();
+        let service = SearchService::new(
+            db.clone(),
+            db,
+            embeddings,
+            None,
+            LlmProvider::unavailable("tests"),
+            &Config::from_env(),
+        );
+
+        let documents = service
+            .search_documents(SearchDocumentsRequest {
+                q: "chunk one".to_string(),
+                container_tags: Some(vec!["tenant_base".to_string()]),
+                chunk_threshold: Some(0.0),
+                document_threshold: None,
+                doc_id: None,
+                filters: None,
+                include_full_docs: Some(false),
+                include_summary: Some(false),
+                limit: Some(5),
+                only_matching_chunks: Some(false),
+                rerank: Some(false),
+                rerank_level: None,
+                rerank_top_k: None,
+                rewrite_query: Some(false),
+                container_tag_weights: None,
+                context_chunks: None,
+                exclude_low_confidence: None,
+                expand_synonyms: None,
+                chunker_types: None,
+                code_languages: None,
+                min_results: None,
+                score_calibration: None,
+                cursor: None,
+                offset: None,
+                diversity_lambda: None,
+                diversify: None,
+                highlight: None,
+            })
+            .await;
+        assert!(matches!(documents, Err(MomoError::Validation(_))));
+
+        let hybrid = service
+            .search_hybrid(HybridSearchRequest {
+                q: "chunk one".to_string(),
+                container_tag: Some("tenant_base".to_string()),
+                search_mode: SearchMode::Documents,
+                ..Default::default()
+            })
+            .await;
+        assert!(matches!(hybrid, Err(MomoError::Validation(_))));
+
+        let keyword = service
+            .search_hybrid(HybridSearchRequest {
+                q: "chunk one".to_string(),
+                container_tag: Some("tenant_base".to_string()),
+                search_mode: SearchMode::Keyword,
+                ..Default::default()
+            })
+            .await;
+        assert!(keyword.is_ok());
+    }
+
     #[tokio::test]
     async fn test_search_hybrid_deduplicates_document_chunks_when_memory_sources_exist() {
         let (db, conn, _temp_dir) = setup_hybrid_db().await;
@@ -1655,6 +4744,22 @@ mod tests {
                 rerank: Some(false),
                 rewrite_query: Some(false),
                 search_mode: SearchMode::Hybrid,
+                dedupe_version_chains: None,
+                container_tag_weights: None,
+                language: None,
+                exclude_low_confidence: None,
+                disable_decay: None,
+                expand_synonyms: None,
+                chunker_types: None,
+                code_languages: None,
+                min_results: None,
+                score_calibration: None,
+                cursor: None,
+                offset: None,
+                document_weight: None,
+                memory_weight: None,
+                skip_access_tracking: false,
+                highlight: None,
             })
             .await
             .unwrap();
@@ -1691,6 +4796,22 @@ mod tests {
                 rerank: Some(false),
                 rewrite_query: Some(false),
                 search_mode: SearchMode::Hybrid,
+                dedupe_version_chains: None,
+                container_tag_weights: None,
+                language: None,
+                exclude_low_confidence: None,
+                disable_decay: None,
+                expand_synonyms: None,
+                chunker_types: None,
+                code_languages: None,
+                min_results: None,
+                score_calibration: None,
+                cursor: None,
+                offset: None,
+                document_weight: None,
+                memory_weight: None,
+                skip_access_tracking: false,
+                highlight: None,
             })
             .await
             .unwrap();
@@ -1738,6 +4859,22 @@ mod tests {
                 rerank: Some(true),
                 rewrite_query: Some(false),
                 search_mode: SearchMode::Memories,
+                dedupe_version_chains: None,
+                container_tag_weights: None,
+                language: None,
+                exclude_low_confidence: None,
+                disable_decay: None,
+                expand_synonyms: None,
+                chunker_types: None,
+                code_languages: None,
+                min_results: None,
+                score_calibration: None,
+                cursor: None,
+                offset: None,
+                document_weight: None,
+                memory_weight: None,
+                skip_access_tracking: false,
+                highlight: None,
             })
             .await
             .unwrap();
@@ -1747,6 +4884,178 @@ mod tests {
         assert_eq!(response.results[0].rerank_score, Some(0.95));
     }
 
+    fn chain_result(id: &str, version: i32, related: Vec<(&str, i32)>) -> MemorySearchResult {
+        MemorySearchResult {
+            id: id.to_string(),
+            memory: Some(format!("memory {id}")),
+            chunk: None,
+            metadata: HashMap::new(),
+            similarity: 0.9,
+            rerank_score: None,
+            version: Some(version),
+            updated_at: Utc::now(),
+            context: Some(MemoryContext {
+                parents: Vec::new(),
+                children: Vec::new(),
+                related: related
+                    .into_iter()
+                    .map(|(id, version)| MemoryRelationInfo {
+                        id: id.to_string(),
+                        relation: crate::models::MemoryRelationType::Extends,
+                        version: Some(version),
+                        memory: format!("memory {id}"),
+                        metadata: None,
+                        updated_at: Utc::now(),
+                    })
+                    .collect(),
+            }),
+            documents: None,
+            raw_similarity: None,
+            decay_debug: None,
+        }
+    }
+
+    #[test]
+    fn test_dedupe_version_chains_keeps_newest_version_across_response() {
+        // Chain A: a1 (root) -> a2 -> a3; chain B: b1 (root) -> b2; c1 stands alone.
+        let chain_roots: HashMap<String, String> = [
+            ("a1", "a1"),
+            ("a2", "a1"),
+            ("a3", "a1"),
+            ("b1", "b1"),
+            ("b2", "b1"),
+        ]
+        .into_iter()
+        .map(|(id, root)| (id.to_string(), root.to_string()))
+        .collect();
+
+        let results = vec![
+            chain_result("a2", 2, vec![("b1", 1), ("a1", 1)]),
+            chain_result("c1", 1, vec![("b2", 2), ("b1", 1)]),
+            chain_result("a3", 3, vec![("b2", 2)]),
+        ];
+
+        let deduped = dedupe_version_chains(results, &chain_roots);
+
+        let ids: Vec<&str> = deduped.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["c1", "a3"]);
+
+        let related_ids: Vec<&str> = deduped
+            .iter()
+            .flat_map(|r| r.context.as_ref().unwrap().related.iter())
+            .map(|rel| rel.id.as_str())
+            .collect();
+        assert_eq!(related_ids, vec!["b2"]);
+    }
+
+    #[test]
+    fn test_container_tag_weight_uses_highest_matching_weight() {
+        let weights = HashMap::from([("mine".to_string(), 2.0), ("shared".to_string(), 0.5)]);
+
+        assert_eq!(container_tag_weight(&weights, &["shared".to_string()]), 0.5);
+        assert_eq!(
+            container_tag_weight(&weights, &["shared".to_string(), "mine".to_string()]),
+            2.0
+        );
+        assert_eq!(container_tag_weight(&weights, &["other".to_string()]), 1.0);
+        assert_eq!(container_tag_weight(&weights, &[]), 1.0);
+    }
+
+    #[test]
+    fn test_search_deadline_without_budget_never_expires() {
+        let deadline = SearchDeadline::new(Instant::now(), None);
+        assert!(!deadline.is_exceeded());
+        assert_eq!(deadline.cap(Duration::from_secs(2)), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_search_deadline_caps_stage_timeout_to_remaining_budget() {
+        let deadline = SearchDeadline::new(Instant::now(), Some(Duration::from_millis(200)));
+        assert!(!deadline.is_exceeded());
+        assert!(deadline.cap(Duration::from_secs(2)) <= Duration::from_millis(200));
+
+        let expired = SearchDeadline::new(
+            Instant::now() - Duration::from_millis(50),
+            Some(Duration::from_millis(10)),
+        );
+        assert!(expired.is_exceeded());
+        assert_eq!(expired.cap(Duration::from_secs(2)), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_search_deadline_run_drops_slow_stage() {
+        let deadline = SearchDeadline::new(Instant::now(), Some(Duration::from_millis(10)));
+        let result = deadline
+            .run(tokio::time::sleep(Duration::from_millis(200)))
+            .await;
+        assert!(result.is_none());
+
+        let unlimited = SearchDeadline::new(Instant::now(), None);
+        assert_eq!(unlimited.run(async { 7 }).await, Some(7));
+    }
+
+    #[tokio::test]
+    async fn test_search_hybrid_skips_rerank_when_budget_exhausted() {
+        let (db, conn, _temp_dir) = setup_hybrid_db().await;
+        let (embeddings, _mock_server) = test_embeddings_provider().await;
+
+        insert_memory_real(&conn, "mem1", Some("space"), Utc::now(), &embeddings).await;
+        insert_memory_real(&conn, "mem2", Some("space"), Utc::now(), &embeddings).await;
+
+        let reranker = RerankerProvider::new_mock(vec![RerankResult {
+            document: "Memory mem2".to_string(),
+            score: 0.95,
+            index: 1,
+        }]);
+
+        let mut service = SearchService::new(
+            db.clone(),
+            db,
+            embeddings,
+            Some(reranker),
+            LlmProvider::unavailable("tests"),
+            &Config::from_env(),
+        );
+        // A zero budget is spent before the first optional stage, however fast
+        // the mandatory stages run.
+        service.max_response_time = Some(Duration::ZERO);
+        let response = service
+            .search_hybrid(HybridSearchRequest {
+                q: "query".to_string(),
+                container_tag: Some("space".to_string()),
+                threshold: Some(0.0),
+                filters: None,
+                include: None,
+                limit: Some(10),
+                rerank: Some(true),
+                rewrite_query: Some(false),
+                search_mode: SearchMode::Memories,
+                dedupe_version_chains: None,
+                container_tag_weights: None,
+                language: None,
+                exclude_low_confidence: None,
+                disable_decay: None,
+                expand_synonyms: None,
+                chunker_types: None,
+                code_languages: None,
+                min_results: None,
+                score_calibration: None,
+                cursor: None,
+                offset: None,
+                document_weight: None,
+                memory_weight: None,
+                skip_access_tracking: false,
+                highlight: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(response.results.len(), 2);
+        assert!(response.degraded);
+        assert_eq!(response.skipped_stages, vec![SearchStage::Rerank]);
+        assert!(response.results.iter().all(|r| r.rerank_score.is_none()));
+    }
+
     #[tokio::test]
     async fn test_search_hybrid_partial_failure_returns_other_domain() {
         let (db, conn, _temp_dir) = setup_hybrid_db().await;
@@ -1778,6 +5087,22 @@ mod tests {
                 rerank: Some(false),
                 rewrite_query: Some(false),
                 search_mode: SearchMode::Hybrid,
+                dedupe_version_chains: None,
+                container_tag_weights: None,
+                language: None,
+                exclude_low_confidence: None,
+                disable_decay: None,
+                expand_synonyms: None,
+                chunker_types: None,
+                code_languages: None,
+                min_results: None,
+                score_calibration: None,
+                cursor: None,
+                offset: None,
+                document_weight: None,
+                memory_weight: None,
+                skip_access_tracking: false,
+                highlight: None,
             })
             .await
             .unwrap();
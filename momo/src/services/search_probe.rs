@@ -0,0 +1,242 @@
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::config::SearchProbeConfig;
+use crate::error::{MomoError, Result};
+use crate::models::{HybridSearchRequest, HybridSearchResult, SearchMode};
+use crate::services::SearchService;
+
+/// A canary query with the ids that a healthy index should return for it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CanaryQuery {
+    pub query: String,
+    /// Memory, chunk or document ids; any one of them appearing counts as a hit.
+    pub expected_ids: Vec<String>,
+    #[serde(default)]
+    pub container_tag: Option<String>,
+    #[serde(default)]
+    pub scope: SearchMode,
+}
+
+/// Aggregate retrieval quality over one run of the canary set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeReport {
+    pub queries: usize,
+    /// Canaries that could not be executed (search errors).
+    pub failed: usize,
+    /// Fraction of executed canaries with an expected id in the top K.
+    pub hit_rate: f64,
+    /// Mean reciprocal rank of the first expected id (0 for misses).
+    pub mrr: f64,
+}
+
+impl ProbeReport {
+    /// Build a report from the 1-based rank of the first expected id per executed
+    /// canary (`None` for a miss).
+    fn from_ranks(ranks: &[Option<usize>], failed: usize) -> Self {
+        let executed = ranks.len();
+        let (hit_rate, mrr) = if executed == 0 {
+            (0.0, 0.0)
+        } else {
+            let hits = ranks.iter().filter(|rank| rank.is_some()).count();
+            let reciprocal_sum: f64 = ranks.iter().flatten().map(|rank| 1.0 / *rank as f64).sum();
+            (
+                hits as f64 / executed as f64,
+                reciprocal_sum / executed as f64,
+            )
+        };
+
+        Self {
+            queries: executed + failed,
+            failed,
+            hit_rate,
+            mrr,
+        }
+    }
+}
+
+/// Load and validate the canary set. Returns an empty list when no file is set.
+pub fn load_canary_queries(config: &SearchProbeConfig) -> Result<Vec<CanaryQuery>> {
+    let Some(path) = config.canaries_path.as_deref() else {
+        return Ok(Vec::new());
+    };
+
+    let raw = std::fs::read_to_string(path).map_err(|e| {
+        MomoError::Validation(format!(
+            "Failed to read search probe canaries '{path}': {e}"
+        ))
+    })?;
+    let canaries: Vec<CanaryQuery> = serde_json::from_str(&raw).map_err(|e| {
+        MomoError::Validation(format!("Invalid search probe canaries '{path}': {e}"))
+    })?;
+
+    for (index, canary) in canaries.iter().enumerate() {
+        let position = index + 1;
+        if canary.query.trim().is_empty() {
+            return Err(MomoError::Validation(format!(
+                "Search probe canary {position} has an empty query"
+            )));
+        }
+        if canary.expected_ids.is_empty() {
+            return Err(MomoError::Validation(format!(
+                "Search probe canary {position} has no expected_ids"
+            )));
+        }
+    }
+
+    Ok(canaries)
+}
+
+/// Background probe that periodically runs the configured canary queries and
+/// logs hit rate and MRR, warning when either drops below its threshold.
+#[derive(Clone)]
+pub struct SearchQualityProbe {
+    search: SearchService,
+    config: SearchProbeConfig,
+}
+
+impl SearchQualityProbe {
+    pub fn new(search: SearchService, config: SearchProbeConfig) -> Self {
+        Self { search, config }
+    }
+
+    pub fn interval_secs(&self) -> u64 {
+        self.config.interval_secs
+    }
+
+    /// Run every canary once and report the aggregate quality.
+    pub async fn run_once(&self) -> Result<ProbeReport> {
+        let mut ranks = Vec::with_capacity(self.config.canaries.len());
+        let mut failed = 0;
+
+        for canary in &self.config.canaries {
+            let request = HybridSearchRequest {
+                q: canary.query.clone(),
+                container_tag: canary.container_tag.clone(),
+                limit: Some(self.config.top_k),
+                search_mode: canary.scope,
+                skip_access_tracking: true,
+                ..Default::default()
+            };
+
+            match self.search.search_hybrid(request).await {
+                Ok(response) => {
+                    let rank = first_expected_rank(&response.results, &canary.expected_ids);
+                    if rank.is_none() {
+                        debug!(query = %canary.query, "Search probe canary missed");
+                    }
+                    ranks.push(rank);
+                }
+                Err(e) => {
+                    failed += 1;
+                    warn!(query = %canary.query, error = %e, "Search probe canary failed");
+                }
+            }
+        }
+
+        let report = ProbeReport::from_ranks(&ranks, failed);
+        info!(
+            queries = report.queries,
+            failed = report.failed,
+            top_k = self.config.top_k,
+            hit_rate = report.hit_rate,
+            mrr = report.mrr,
+            "Search quality probe completed"
+        );
+
+        if report.hit_rate < self.config.min_hit_rate || report.mrr < self.config.min_mrr {
+            warn!(
+                hit_rate = report.hit_rate,
+                mrr = report.mrr,
+                min_hit_rate = self.config.min_hit_rate,
+                min_mrr = self.config.min_mrr,
+                "Search quality below threshold"
+            );
+        }
+
+        Ok(report)
+    }
+}
+
+/// 1-based rank of the first result matching one of `expected_ids`, by result id
+/// or parent document id.
+fn first_expected_rank(results: &[HybridSearchResult], expected_ids: &[String]) -> Option<usize> {
+    results
+        .iter()
+        .position(|result| {
+            expected_ids.iter().any(|expected| {
+                result.id == *expected || result.document_id.as_deref() == Some(expected.as_str())
+            })
+        })
+        .map(|index| index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(id: &str, document_id: Option<&str>) -> HybridSearchResult {
+        HybridSearchResult {
+            id: id.to_string(),
+            memory: None,
+            chunk: Some("chunk".to_string()),
+            document_id: document_id.map(str::to_string),
+            similarity: 0.9,
+            rerank_score: None,
+            metadata: Default::default(),
+            updated_at: chrono::Utc::now(),
+            chunker: None,
+            highlight: None,
+            raw_similarity: None,
+        }
+    }
+
+    #[test]
+    fn test_first_expected_rank_matches_result_or_document_id() {
+        let results = vec![
+            result("mem_1", None),
+            result("chunk_1", Some("doc_a")),
+            result("chunk_2", Some("doc_b")),
+        ];
+
+        assert_eq!(
+            first_expected_rank(&results, &["doc_a".to_string()]),
+            Some(2)
+        );
+        assert_eq!(
+            first_expected_rank(&results, &["mem_1".to_string(), "doc_b".to_string()]),
+            Some(1)
+        );
+        assert_eq!(first_expected_rank(&results, &["mem_9".to_string()]), None);
+    }
+
+    #[test]
+    fn test_report_computes_hit_rate_and_mrr() {
+        let report = ProbeReport::from_ranks(&[Some(1), Some(2), None, Some(4)], 1);
+
+        assert_eq!(report.queries, 5);
+        assert_eq!(report.failed, 1);
+        assert!((report.hit_rate - 0.75).abs() < 1e-9);
+        assert!((report.mrr - (1.0 + 0.5 + 0.25) / 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_report_with_no_executed_queries_is_zero() {
+        let report = ProbeReport::from_ranks(&[], 2);
+
+        assert_eq!(report.queries, 2);
+        assert_eq!(report.hit_rate, 0.0);
+        assert_eq!(report.mrr, 0.0);
+    }
+
+    #[test]
+    fn test_canaries_parse_with_defaults() {
+        let canaries: Vec<CanaryQuery> = serde_json::from_str(
+            r#"[{"query": "where does the user live", "expected_ids": ["mem_1"]}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(canaries[0].scope, SearchMode::Hybrid);
+        assert!(canaries[0].container_tag.is_none());
+    }
+}
@@ -0,0 +1,195 @@
+//! Webhook delivery for document processing results.
+//!
+//! When a document reaches `done` or `failed`, every webhook registered for
+//! one of its container tags receives a JSON [`DocumentProcessedEvent`].
+//! Each request carries an `X-Momo-Signature: sha256=<hex>` header, the
+//! HMAC-SHA256 of the body under the webhook's secret.
+//!
+//! Delivery is best-effort: it runs on spawned tasks so a slow endpoint never
+//! holds up the pipeline, retries a couple of times, and only logs failures.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+
+use crate::db::DatabaseBackend;
+use crate::models::{Document, ProcessingStatus, Webhook};
+
+const SIGNATURE_HEADER: &str = "X-Momo-Signature";
+const EVENT_HEADER: &str = "X-Momo-Event";
+const EVENT_DOCUMENT_PROCESSED: &str = "document.processed";
+
+/// Attempts per webhook, including the first.
+const DELIVERY_ATTEMPTS: u32 = 3;
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+/// Delay before the first retry; doubled for each further retry.
+const RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Payload sent when a document finishes processing.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentProcessedEvent {
+    pub event: &'static str,
+    pub document_id: String,
+    /// Final status: `done` or `failed`.
+    pub status: ProcessingStatus,
+    pub chunk_count: i32,
+    pub error_message: Option<String>,
+    pub container_tags: Vec<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl DocumentProcessedEvent {
+    /// Event for `doc`, or `None` while it has not reached a final status.
+    pub fn for_document(doc: &Document) -> Option<Self> {
+        if !matches!(
+            doc.status,
+            ProcessingStatus::Done | ProcessingStatus::Failed | ProcessingStatus::DeadLetter
+        ) {
+            return None;
+        }
+        Some(Self {
+            event: EVENT_DOCUMENT_PROCESSED,
+            document_id: doc.id.clone(),
+            status: doc.status.clone(),
+            chunk_count: doc.chunk_count,
+            error_message: doc.error_message.clone(),
+            container_tags: doc.container_tags.clone(),
+            timestamp: Utc::now(),
+        })
+    }
+}
+
+/// Hex-encoded HMAC-SHA256 of `body` under `secret`.
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    format!("{:x}", mac.finalize().into_bytes())
+}
+
+#[derive(Clone)]
+pub struct WebhookNotifier {
+    db: Arc<dyn DatabaseBackend>,
+    client: reqwest::Client,
+}
+
+impl WebhookNotifier {
+    pub fn new(db: Arc<dyn DatabaseBackend>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(DELIVERY_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self { db, client }
+    }
+
+    /// Notify the webhooks of `doc_id`'s containers if the document has
+    /// reached a final status. Returns once deliveries are spawned.
+    pub async fn document_processed(&self, doc_id: &str) {
+        let doc = match self.db.get_document_by_id(doc_id).await {
+            Ok(Some(doc)) => doc,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::warn!(doc_id, error = %e, "Failed to load document for webhooks");
+                return;
+            }
+        };
+        let Some(event) = DocumentProcessedEvent::for_document(&doc) else {
+            return;
+        };
+
+        let webhooks = match self.db.list_webhooks(Some(&doc.container_tags)).await {
+            Ok(webhooks) => webhooks,
+            Err(e) => {
+                tracing::warn!(doc_id, error = %e, "Failed to load webhooks");
+                return;
+            }
+        };
+        if webhooks.is_empty() {
+            return;
+        }
+
+        let body = match serde_json::to_vec(&event) {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::warn!(doc_id, error = %e, "Failed to serialize webhook payload");
+                return;
+            }
+        };
+        for webhook in webhooks {
+            tokio::spawn(deliver(self.client.clone(), webhook, body.clone()));
+        }
+    }
+}
+
+async fn deliver(client: reqwest::Client, webhook: Webhook, body: Vec<u8>) {
+    let signature = format!("sha256={}", sign(&webhook.secret, &body));
+    let mut backoff = RETRY_BACKOFF;
+
+    for attempt in 1..=DELIVERY_ATTEMPTS {
+        let result = client
+            .post(&webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, EVENT_DOCUMENT_PROCESSED)
+            .header(SIGNATURE_HEADER, &signature)
+            .body(body.clone())
+            .send()
+            .await;
+
+        let error = match result {
+            Ok(response) if response.status().is_success() => {
+                tracing::debug!(webhook_id = %webhook.id, attempt, "Webhook delivered");
+                return;
+            }
+            Ok(response) => format!("HTTP {}", response.status()),
+            Err(e) => e.to_string(),
+        };
+        tracing::warn!(
+            webhook_id = %webhook.id,
+            url = %webhook.url,
+            attempt,
+            error = %error,
+            "Webhook delivery failed"
+        );
+        if attempt < DELIVERY_ATTEMPTS {
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_matches_rfc4231_vector() {
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn event_only_for_final_statuses() {
+        let mut doc = Document::new("doc1".to_string());
+        doc.container_tags = vec!["work".to_string()];
+        doc.status = ProcessingStatus::Embedding;
+        assert!(DocumentProcessedEvent::for_document(&doc).is_none());
+
+        doc.status = ProcessingStatus::Failed;
+        doc.error_message = Some("OCR failed".to_string());
+        let event = DocumentProcessedEvent::for_document(&doc).expect("final status");
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["event"], "document.processed");
+        assert_eq!(json["documentId"], "doc1");
+        assert_eq!(json["status"], "failed");
+        assert_eq!(json["chunkCount"], 0);
+        assert_eq!(json["errorMessage"], "OCR failed");
+        assert_eq!(json["containerTags"][0], "work");
+    }
+}
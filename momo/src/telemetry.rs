@@ -0,0 +1,122 @@
+//! Optional OpenTelemetry export of `tracing` spans.
+//!
+//! Setting `OTEL_EXPORTER_OTLP_ENDPOINT` (e.g. `http://localhost:4318`) sends
+//! every span to an OTLP/HTTP collector alongside the usual log output. The
+//! exporter also honours the other standard `OTEL_*` variables, such as
+//! `OTEL_EXPORTER_OTLP_HEADERS`. Without an endpoint no OpenTelemetry layer is
+//! installed and logging is unchanged.
+
+use axum::http::HeaderMap;
+use opentelemetry::propagation::Extractor;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::Resource;
+use tracing::Subscriber;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+const ENDPOINT_ENV: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
+const SERVICE_NAME_ENV: &str = "OTEL_SERVICE_NAME";
+const DEFAULT_SERVICE_NAME: &str = "momo";
+
+/// Installed OTLP exporter. Pending spans are flushed when it is dropped.
+pub struct Telemetry {
+    provider: SdkTracerProvider,
+}
+
+impl Telemetry {
+    /// Exporter for the configured endpoint, or `None` when
+    /// `OTEL_EXPORTER_OTLP_ENDPOINT` is unset.
+    pub fn from_env() -> anyhow::Result<Option<Self>> {
+        if !std::env::var(ENDPOINT_ENV).is_ok_and(|endpoint| !endpoint.trim().is_empty()) {
+            return Ok(None);
+        }
+
+        // The exporter reads the endpoint (and headers, timeout, ...) itself.
+        let exporter = opentelemetry_otlp::SpanExporter::builder()
+            .with_http()
+            .build()?;
+        let service_name =
+            std::env::var(SERVICE_NAME_ENV).unwrap_or_else(|_| DEFAULT_SERVICE_NAME.to_string());
+        let provider = SdkTracerProvider::builder()
+            .with_resource(Resource::builder().with_service_name(service_name).build())
+            .with_batch_exporter(exporter)
+            .build();
+
+        opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+        Ok(Some(Self { provider }))
+    }
+
+    /// `tracing` layer that turns spans into OpenTelemetry spans.
+    pub fn layer<S>(&self) -> impl Layer<S>
+    where
+        S: Subscriber + for<'span> LookupSpan<'span>,
+    {
+        tracing_opentelemetry::layer().with_tracer(self.provider.tracer(DEFAULT_SERVICE_NAME))
+    }
+}
+
+impl Drop for Telemetry {
+    fn drop(&mut self) {
+        if let Err(e) = self.provider.shutdown() {
+            eprintln!("Failed to flush OpenTelemetry spans: {e}");
+        }
+    }
+}
+
+/// Span for an incoming HTTP request, continuing the caller's trace when the
+/// request carries a W3C `traceparent` header.
+pub fn http_request_span<B>(request: &axum::http::Request<B>) -> tracing::Span {
+    let span = tracing::info_span!(
+        "request",
+        method = %request.method(),
+        uri = %request.uri(),
+        version = ?request.version(),
+    );
+    let parent = opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.extract(&HeaderExtractor(request.headers()))
+    });
+    // Fails only when no OpenTelemetry layer is installed.
+    let _ = span.set_parent(parent);
+    span
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::propagation::TextMapPropagator;
+    use opentelemetry::trace::TraceContextExt;
+
+    #[test]
+    fn extracts_w3c_trace_context_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+                .parse()
+                .unwrap(),
+        );
+
+        let cx = TraceContextPropagator::new().extract(&HeaderExtractor(&headers));
+        let span_context = cx.span().span_context().clone();
+        assert!(span_context.is_remote());
+        assert_eq!(
+            span_context.trace_id().to_string(),
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+    }
+}
@@ -7,8 +7,12 @@ use tracing::{debug, warn};
 use crate::{
     config::TranscriptionConfig,
     error::{MomoError, Result},
+    models::ExtractionConfidence,
 };
 
+use super::provider::{TranscriptLanguage, TranscriptSegment, TranscriptionOutput};
+use super::whisper::language_code;
+
 const OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,18 +20,50 @@ struct TranscriptionResponse {
     text: String,
     #[serde(default)]
     segments: Vec<TranscriptionSegment>,
+    /// Language the service detected, reported with `verbose_json`, by name
+    /// (`"german"`) for OpenAI.
+    #[serde(default)]
+    language: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct TranscriptionSegment {
-    #[serde(default)]
-    id: u32,
     #[serde(default)]
     start: f64,
     #[serde(default)]
     end: f64,
     #[serde(default)]
     text: String,
+    /// Average token log-probability, reported with `verbose_json`.
+    #[serde(default)]
+    avg_logprob: Option<f64>,
+    /// Speaker label, reported with `diarized_json`.
+    #[serde(default)]
+    speaker: Option<String>,
+}
+
+impl TranscriptionResponse {
+    /// Confidence from segment log-probabilities, when the API returned them.
+    fn confidence(&self) -> Option<ExtractionConfidence> {
+        ExtractionConfidence::from_scores(
+            self.segments
+                .iter()
+                .filter_map(|segment| segment.avg_logprob)
+                .map(|logprob| logprob.exp() as f32),
+        )
+    }
+
+    fn transcript_segments(&self) -> Vec<TranscriptSegment> {
+        self.segments
+            .iter()
+            .map(|segment| TranscriptSegment {
+                start: segment.start,
+                end: segment.end,
+                text: segment.text.trim().to_string(),
+                speaker: segment.speaker.clone(),
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -61,7 +97,7 @@ impl TranscriptionApiClient {
         &self,
         audio_bytes: &[u8],
         file_extension: Option<&str>,
-    ) -> Result<String> {
+    ) -> Result<TranscriptionOutput> {
         let mut last_error: Option<MomoError> = None;
         let max_retries = 3; // Default max retries
 
@@ -74,7 +110,7 @@ impl TranscriptionApiClient {
             }
 
             match self.transcribe_internal(audio_bytes, file_extension).await {
-                Ok(text) => return Ok(text),
+                Ok(output) => return Ok(output),
                 Err(e) => {
                     // Check if error is retryable
                     let retryable = matches!(
@@ -111,7 +147,7 @@ impl TranscriptionApiClient {
         &self,
         audio_bytes: &[u8],
         file_extension: Option<&str>,
-    ) -> Result<String> {
+    ) -> Result<TranscriptionOutput> {
         // Build multipart form
         let file_name = format!("audio.{}", file_extension.unwrap_or("mp3"));
         let mime_type = self.infer_mime_type(file_extension);
@@ -124,7 +160,17 @@ impl TranscriptionApiClient {
         let form = multipart::Form::new()
             .part("file", file_part)
             .text("model", self.config.model.clone())
-            .text("response_format", "json");
+            .text("response_format", self.response_format());
+        let form = match self.fixed_language() {
+            Some(language) => form.text("language", language.to_string()),
+            None => form,
+        };
+        // Diarizing models reject requests without a chunking strategy.
+        let form = if self.response_format() == "diarized_json" {
+            form.text("chunking_strategy", "auto")
+        } else {
+            form
+        };
 
         // Build request
         let base_url = self.config.base_url.as_deref().unwrap_or(OPENAI_BASE_URL);
@@ -178,7 +224,49 @@ impl TranscriptionApiClient {
             ));
         }
 
-        Ok(transcription_response.text)
+        // The service detects the language unless one is fixed; it doesn't
+        // report how sure it is, so there's nothing to fall back on.
+        let language = match self.fixed_language() {
+            Some(_) => None,
+            None => transcription_response
+                .language
+                .as_deref()
+                .map(|language| TranscriptLanguage {
+                    code: language_code(language),
+                    confidence: None,
+                    fallback: false,
+                }),
+        };
+
+        Ok(TranscriptionOutput {
+            confidence: transcription_response.confidence(),
+            segments: transcription_response.transcript_segments(),
+            language,
+            text: transcription_response.text,
+        })
+    }
+
+    /// Whisper models support `verbose_json`, whose segments carry timing
+    /// and the log-probabilities used for confidence. Diarizing models return
+    /// timed segments with speaker labels as `diarized_json`; other models
+    /// only accept `json`.
+    fn response_format(&self) -> &'static str {
+        let model = self.config.model.to_lowercase();
+        if model.contains("diarize") {
+            "diarized_json"
+        } else if model.contains("whisper") {
+            "verbose_json"
+        } else {
+            "json"
+        }
+    }
+
+    /// The configured language, unless it's left to the service to detect.
+    fn fixed_language(&self) -> Option<&str> {
+        self.config
+            .language
+            .as_deref()
+            .filter(|language| !language.eq_ignore_ascii_case("auto"))
     }
 
     fn infer_mime_type(&self, file_extension: Option<&str>) -> String {
@@ -216,7 +304,7 @@ impl TranscriptionApiClient {
 mod tests {
     use super::*;
     use wiremock::{
-        matchers::{header, method, path},
+        matchers::{body_string_contains, header, method, path},
         Mock, MockServer, ResponseTemplate,
     };
 
@@ -229,6 +317,13 @@ mod tests {
             timeout_secs: 10,
             max_file_size: 25 * 1024 * 1024,
             max_duration_secs: 600,
+            max_concurrent_jobs: 1,
+            upload_dir: None,
+            include_segments: false,
+            chunk_by_segment: false,
+            language: None,
+            default_language: "en".to_string(),
+            language_min_confidence: 0.5,
         }
     }
 
@@ -271,7 +366,7 @@ mod tests {
 
         let result = client.transcribe(audio_bytes, Some("mp3")).await;
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "Test transcription");
+        assert_eq!(result.unwrap().text, "Test transcription");
     }
 
     #[tokio::test]
@@ -328,7 +423,145 @@ mod tests {
         let client = TranscriptionApiClient::new(&config).unwrap();
         let result = client.transcribe(b"audio", None).await;
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "Parsed response text");
+        assert_eq!(result.unwrap().text, "Parsed response text");
+    }
+
+    #[tokio::test]
+    async fn test_api_response_reports_segment_confidence() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/audio/transcriptions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "text": "Confident mumble",
+                "segments": [
+                    {"id": 0, "start": 0.0, "end": 1.0, "text": "Confident", "avg_logprob": 0.0},
+                    {"id": 1, "start": 1.0, "end": 2.0, "text": "mumble", "avg_logprob": -std::f64::consts::LN_2}
+                ]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let mut config = test_config();
+        config.base_url = Some(mock_server.uri());
+
+        let client = TranscriptionApiClient::new(&config).unwrap();
+        let output = client.transcribe(b"audio", None).await.unwrap();
+        let confidence = output.confidence.expect("segments carry log-probabilities");
+        assert!((confidence.average - 0.75).abs() < 1e-6);
+        assert!((confidence.minimum - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_response_format_requests_verbose_json_for_whisper() {
+        let mut config = test_config();
+        config.model = "openai/whisper-1".to_string();
+        let client = TranscriptionApiClient::new(&config).unwrap();
+        assert_eq!(client.response_format(), "verbose_json");
+
+        config.model = "openai/gpt-4o-transcribe".to_string();
+        let client = TranscriptionApiClient::new(&config).unwrap();
+        assert_eq!(client.response_format(), "json");
+
+        config.model = "openai/gpt-4o-transcribe-diarize".to_string();
+        let client = TranscriptionApiClient::new(&config).unwrap();
+        assert_eq!(client.response_format(), "diarized_json");
+    }
+
+    #[tokio::test]
+    async fn test_api_response_reports_detected_language() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/audio/transcriptions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "text": "Guten Morgen",
+                "language": "german",
+                "segments": []
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let mut config = test_config();
+        config.language = Some("auto".to_string());
+        config.base_url = Some(mock_server.uri());
+
+        let client = TranscriptionApiClient::new(&config).unwrap();
+        let output = client.transcribe(b"audio", None).await.unwrap();
+        assert_eq!(
+            output.language,
+            Some(TranscriptLanguage {
+                code: "de".to_string(),
+                confidence: None,
+                fallback: false,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_api_sends_fixed_language() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/audio/transcriptions"))
+            .and(body_string_contains("name=\"language\"\r\n\r\nnl"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "text": "Goedemorgen",
+                "language": "dutch"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut config = test_config();
+        config.language = Some("nl".to_string());
+        config.base_url = Some(mock_server.uri());
+
+        let client = TranscriptionApiClient::new(&config).unwrap();
+        let output = client.transcribe(b"audio", None).await.unwrap();
+        assert!(output.language.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_api_response_reports_diarized_segments() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/audio/transcriptions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "text": "Shall we start? Yes, go ahead.",
+                "segments": [
+                    {"type": "transcript.text.segment", "id": "seg_0", "start": 0.0, "end": 1.4, "text": "Shall we start?", "speaker": "A"},
+                    {"type": "transcript.text.segment", "id": "seg_1", "start": 1.6, "end": 2.9, "text": " Yes, go ahead.", "speaker": "B"}
+                ]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let mut config = test_config();
+        config.model = "gpt-4o-transcribe-diarize".to_string();
+        config.base_url = Some(mock_server.uri());
+
+        let client = TranscriptionApiClient::new(&config).unwrap();
+        let output = client.transcribe(b"audio", None).await.unwrap();
+        assert_eq!(
+            output.segments,
+            vec![
+                TranscriptSegment {
+                    start: 0.0,
+                    end: 1.4,
+                    text: "Shall we start?".to_string(),
+                    speaker: Some("A".to_string()),
+                },
+                TranscriptSegment {
+                    start: 1.6,
+                    end: 2.9,
+                    text: "Yes, go ahead.".to_string(),
+                    speaker: Some("B".to_string()),
+                },
+            ]
+        );
+        assert!(output.confidence.is_none());
     }
 
     #[tokio::test]
@@ -4,4 +4,4 @@ mod provider;
 mod whisper;
 
 pub use preprocessing::AudioPreprocessor;
-pub use provider::TranscriptionProvider;
+pub use provider::{TranscriptLanguage, TranscriptSegment, TranscriptionProvider};
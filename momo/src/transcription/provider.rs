@@ -1,9 +1,11 @@
 use std::time::Duration;
 
+use serde::Serialize;
 use tracing::{info, warn};
 
 use crate::config::{parse_provider_model, TranscriptionConfig};
 use crate::error::{MomoError, Result};
+use crate::models::ExtractionConfidence;
 
 use super::api::TranscriptionApiClient;
 
@@ -19,6 +21,46 @@ enum TranscriptionBackend {
     },
 }
 
+/// Transcribed text, with the backend's confidence when it reports one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptionOutput {
+    pub text: String,
+    /// Aggregated per-segment confidence; `None` when the backend does not
+    /// report it.
+    pub confidence: Option<ExtractionConfidence>,
+    /// Timed segments the text was assembled from; empty when the backend
+    /// doesn't report timing.
+    pub segments: Vec<TranscriptSegment>,
+    /// Language the backend detected; `None` when the language was fixed by
+    /// configuration or not reported.
+    pub language: Option<TranscriptLanguage>,
+}
+
+/// Spoken language detected in a recording.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TranscriptLanguage {
+    /// ISO 639-1 code, e.g. `"de"`.
+    pub code: String,
+    /// Detection probability in `[0, 1]`; `None` when not reported.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+    /// Set when detection was too unsure and the configured default language
+    /// was used instead.
+    pub fallback: bool,
+}
+
+/// A stretch of speech with its offsets into the recording, in seconds.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TranscriptSegment {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+    /// Who spoke, for backends that diarize; labels are only consistent
+    /// within one recording.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+}
+
 pub struct TranscriptionProvider {
     backend: TranscriptionBackend,
     config: TranscriptionConfig,
@@ -76,7 +118,7 @@ impl TranscriptionProvider {
         !matches!(self.backend, TranscriptionBackend::Unavailable { .. })
     }
 
-    pub async fn transcribe(&self, audio_bytes: &[u8]) -> Result<String> {
+    pub async fn transcribe(&self, audio_bytes: &[u8]) -> Result<TranscriptionOutput> {
         let timeout_duration = Duration::from_secs(self.config.timeout_secs);
 
         let result =
@@ -91,7 +133,7 @@ impl TranscriptionProvider {
         }
     }
 
-    async fn transcribe_internal(&self, audio_bytes: &[u8]) -> Result<String> {
+    async fn transcribe_internal(&self, audio_bytes: &[u8]) -> Result<TranscriptionOutput> {
         match &self.backend {
             TranscriptionBackend::Local { whisper } => {
                 use super::preprocessing::AudioPreprocessor;
@@ -3,10 +3,14 @@ use tokio::sync::Mutex;
 use tracing::{debug, info};
 use whisper_rs::{
     FullParams, SamplingStrategy, WhisperContext as WhisperRsContext, WhisperContextParameters,
+    WhisperState,
 };
 
 use crate::config::TranscriptionConfig;
 use crate::error::{MomoError, Result};
+use crate::models::ExtractionConfidence;
+
+use super::provider::{TranscriptLanguage, TranscriptSegment, TranscriptionOutput};
 
 /// Wrapper around whisper-rs WhisperContext with thread-safe access
 pub struct WhisperContext {
@@ -52,15 +56,17 @@ impl WhisperContext {
     /// * `audio_samples` - PCM audio samples (f32, 16kHz mono) normalized to [-1.0, 1.0]
     ///
     /// # Returns
-    /// * `Ok(String)` - Transcribed text
+    /// * `Ok(TranscriptionOutput)` - Transcribed text and the mean token
+    ///   probability of each segment, aggregated as confidence
     /// * `Err(MomoError::Transcription)` - Transcription failed
     ///
     /// # Notes
     /// This method uses spawn_blocking because Whisper is CPU-intensive.
     /// The audio_samples are expected to be 16kHz mono PCM in f32 format.
-    pub async fn transcribe(&self, audio_samples: &[f32]) -> Result<String> {
+    pub async fn transcribe(&self, audio_samples: &[f32]) -> Result<TranscriptionOutput> {
         let samples = audio_samples.to_vec();
         let context = Arc::clone(&self.context);
+        let config = self.config.clone();
 
         debug!(
             sample_count = samples.len(),
@@ -79,6 +85,18 @@ impl WhisperContext {
                 MomoError::Transcription(format!("Failed to create Whisper state: {e}"))
             })?;
 
+            let detected = match config.language.as_deref() {
+                Some(language) if language.eq_ignore_ascii_case("auto") => {
+                    Some(detect_language(&mut state, &samples, &config)?)
+                }
+                _ => None,
+            };
+            match (&detected, config.language.as_deref()) {
+                (Some(detected), _) => params.set_language(Some(&detected.code)),
+                (None, Some(language)) => params.set_language(Some(language)),
+                (None, None) => {}
+            }
+
             state
                 .full(params, &samples)
                 .map_err(|e| MomoError::Transcription(format!("Transcription failed: {e}")))?;
@@ -90,15 +108,35 @@ impl WhisperContext {
                 ));
             }
 
+            let first_special_token = ctx.token_eot();
             let mut transcript = String::new();
+            let mut segment_scores = Vec::new();
+            let mut segments = Vec::new();
             for i in 0..num_segments {
                 if let Some(segment) = state.get_segment(i) {
+                    let probabilities: Vec<f32> = (0..segment.n_tokens())
+                        .filter_map(|t| segment.get_token(t))
+                        .filter(|token| token.token_id() < first_special_token)
+                        .map(|token| token.token_probability())
+                        .collect();
+                    if !probabilities.is_empty() {
+                        segment_scores
+                            .push(probabilities.iter().sum::<f32>() / probabilities.len() as f32);
+                    }
+
                     match segment.to_str() {
                         Ok(segment_text) => {
                             if !transcript.is_empty() && !transcript.ends_with(' ') {
                                 transcript.push(' ');
                             }
                             transcript.push_str(segment_text);
+                            // Whisper timestamps are in centiseconds.
+                            segments.push(TranscriptSegment {
+                                start: segment.start_timestamp() as f64 / 100.0,
+                                end: segment.end_timestamp() as f64 / 100.0,
+                                text: segment_text.trim().to_string(),
+                                speaker: None,
+                            });
                         }
                         Err(e) => {
                             return Err(MomoError::Transcription(format!(
@@ -113,14 +151,19 @@ impl WhisperContext {
                 }
             }
 
-            Ok::<String, MomoError>(transcript.trim().to_string())
+            Ok::<TranscriptionOutput, MomoError>(TranscriptionOutput {
+                text: transcript.trim().to_string(),
+                confidence: ExtractionConfidence::from_scores(segment_scores),
+                segments,
+                language: detected,
+            })
         })
         .await
         .map_err(|e| MomoError::Transcription(format!("Transcription task panicked: {e}")))??;
 
         info!(
-            text_length = result.len(),
-            segment_count = result.split_whitespace().count(),
+            text_length = result.text.len(),
+            segment_count = result.text.split_whitespace().count(),
             "Transcription completed"
         );
 
@@ -134,6 +177,68 @@ impl WhisperContext {
     }
 }
 
+/// Detect the spoken language from the first 30 seconds of `samples`.
+fn detect_language(
+    state: &mut WhisperState,
+    samples: &[f32],
+    config: &TranscriptionConfig,
+) -> Result<TranscriptLanguage> {
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get().min(4));
+    state
+        .pcm_to_mel(samples, threads)
+        .map_err(|e| MomoError::Transcription(format!("Failed to compute mel spectrogram: {e}")))?;
+    let (language_id, probabilities) = state
+        .lang_detect(0, threads)
+        .map_err(|e| MomoError::Transcription(format!("Language detection failed: {e}")))?;
+
+    let language = choose_language(
+        whisper_rs::get_lang_str(language_id),
+        usize::try_from(language_id)
+            .ok()
+            .and_then(|id| probabilities.get(id).copied()),
+        config,
+    );
+    debug!(
+        language = %language.code,
+        confidence = ?language.confidence,
+        fallback = language.fallback,
+        "Detected spoken language"
+    );
+    Ok(language)
+}
+
+/// The detected language, or the configured default when detection is
+/// missing or less confident than `language_min_confidence`.
+fn choose_language(
+    detected: Option<&str>,
+    confidence: Option<f32>,
+    config: &TranscriptionConfig,
+) -> TranscriptLanguage {
+    match (detected, confidence) {
+        (Some(code), Some(confidence)) if confidence >= config.language_min_confidence => {
+            TranscriptLanguage {
+                code: code.to_string(),
+                confidence: Some(confidence),
+                fallback: false,
+            }
+        }
+        _ => TranscriptLanguage {
+            code: config.default_language.clone(),
+            confidence,
+            fallback: true,
+        },
+    }
+}
+
+/// ISO 639-1 code for a language reported by name (`"german"`) or code.
+pub(super) fn language_code(language: &str) -> String {
+    let language = language.trim().to_lowercase();
+    whisper_rs::get_lang_id(&language)
+        .and_then(whisper_rs::get_lang_str)
+        .map(str::to_string)
+        .unwrap_or(language)
+}
+
 impl Clone for WhisperContext {
     fn clone(&self) -> Self {
         Self {
@@ -156,6 +261,13 @@ mod tests {
             timeout_secs: 300,
             max_file_size: 104857600,
             max_duration_secs: 7200,
+            max_concurrent_jobs: 1,
+            upload_dir: None,
+            include_segments: false,
+            chunk_by_segment: false,
+            language: None,
+            default_language: "en".to_string(),
+            language_min_confidence: 0.5,
         }
     }
 
@@ -204,6 +316,39 @@ mod tests {
         assert_eq!(mock_config.model, "local/whisper-small");
     }
 
+    #[test]
+    fn test_choose_language_falls_back_when_detection_is_unsure() {
+        let config = TranscriptionConfig {
+            default_language: "fr".to_string(),
+            language_min_confidence: 0.6,
+            ..create_test_config(None)
+        };
+
+        let detected = choose_language(Some("de"), Some(0.9), &config);
+        assert_eq!(
+            detected,
+            TranscriptLanguage {
+                code: "de".to_string(),
+                confidence: Some(0.9),
+                fallback: false,
+            }
+        );
+
+        let unsure = choose_language(Some("de"), Some(0.3), &config);
+        assert_eq!(unsure.code, "fr");
+        assert_eq!(unsure.confidence, Some(0.3));
+        assert!(unsure.fallback);
+
+        assert!(choose_language(None, None, &config).fallback);
+    }
+
+    #[test]
+    fn test_language_code_normalizes_names() {
+        assert_eq!(language_code("German"), "de");
+        assert_eq!(language_code("en"), "en");
+        assert_eq!(language_code("klingon"), "klingon");
+    }
+
     #[tokio::test]
     #[ignore]
     async fn test_local_whisper_e2e() {
@@ -234,7 +379,7 @@ mod tests {
         let result = context.transcribe(&samples).await;
         assert!(result.is_ok());
 
-        let text = result.unwrap();
+        let text = result.unwrap().text;
         println!("Transcribed text: {text}");
     }
 }
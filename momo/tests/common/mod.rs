@@ -47,6 +47,88 @@ pub fn ensure_fixtures() {
     if !pptx_path.exists() {
         generate_pptx_fixture(&pptx_path);
     }
+
+    // Generate EPUB fixture
+    let epub_path = fixtures_dir.join("sample.epub");
+    if !epub_path.exists() {
+        generate_epub_fixture(&epub_path);
+    }
+
+    // Generate PDF fixture
+    let pdf_path = fixtures_dir.join("sample.pdf");
+    if !pdf_path.exists() {
+        fs::write(&pdf_path, build_pdf(Some("Field Guide"), PDF_PAGES))
+            .expect("Failed to write PDF fixture");
+    }
+}
+
+const PDF_PAGES: &[&str] = &[
+    "Herons wade in shallow water.",
+    "Kingfishers dive from overhanging branches.",
+];
+
+/// Build a PDF with one line of Helvetica text per page and an optional
+/// Info dictionary title.
+#[allow(dead_code)]
+pub fn build_pdf(title: Option<&str>, pages: &[&str]) -> Vec<u8> {
+    use lopdf::content::{Content, Operation};
+    use lopdf::{dictionary, Document, Object, Stream};
+
+    let mut doc = Document::with_version("1.5");
+    let pages_id = doc.new_object_id();
+    let font_id = doc.add_object(dictionary! {
+        "Type" => "Font",
+        "Subtype" => "Type1",
+        "BaseFont" => "Helvetica",
+    });
+    let resources_id = doc.add_object(dictionary! {
+        "Font" => dictionary! { "F1" => font_id },
+    });
+
+    let mut kids = Vec::new();
+    for text in pages {
+        let content = Content {
+            operations: vec![
+                Operation::new("BT", vec![]),
+                Operation::new("Tf", vec!["F1".into(), 12.into()]),
+                Operation::new("Td", vec![72.into(), 720.into()]),
+                Operation::new("Tj", vec![Object::string_literal(*text)]),
+                Operation::new("ET", vec![]),
+            ],
+        };
+        let content_id = doc.add_object(Stream::new(dictionary! {}, content.encode().unwrap()));
+        let page_id = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "Contents" => content_id,
+        });
+        kids.push(page_id.into());
+    }
+
+    let count = kids.len() as i64;
+    doc.objects.insert(
+        pages_id,
+        Object::Dictionary(dictionary! {
+            "Type" => "Pages",
+            "Kids" => kids,
+            "Count" => count,
+            "Resources" => resources_id,
+            "MediaBox" => vec![0.into(), 0.into(), 612.into(), 792.into()],
+        }),
+    );
+    let catalog_id = doc.add_object(dictionary! {
+        "Type" => "Catalog",
+        "Pages" => pages_id,
+    });
+    doc.trailer.set("Root", catalog_id);
+    if let Some(title) = title {
+        let info_id = doc.add_object(dictionary! { "Title" => Object::string_literal(title) });
+        doc.trailer.set("Info", info_id);
+    }
+
+    let mut buffer = Vec::new();
+    doc.save_to(&mut buffer).expect("Failed to write PDF");
+    buffer
 }
 
 fn generate_csv_fixture(path: &Path) {
@@ -272,3 +354,89 @@ const SLIDE1_PPTX: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="ye
         </p:spTree>
     </p:cSld>
 </p:sld>"#;
+
+fn generate_epub_fixture(path: &Path) {
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+    use zip::CompressionMethod;
+
+    let mut buffer = Cursor::new(Vec::new());
+    {
+        let mut zip = zip::ZipWriter::new(&mut buffer);
+        let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .unix_permissions(0o644);
+
+        // The mimetype entry must come first and be uncompressed.
+        zip.start_file("mimetype", stored).unwrap();
+        zip.write_all(b"application/epub+zip").unwrap();
+
+        zip.add_directory("META-INF", options).unwrap();
+        zip.start_file("META-INF/container.xml", options).unwrap();
+        zip.write_all(CONTAINER_EPUB.as_bytes()).unwrap();
+
+        zip.add_directory("OEBPS", options).unwrap();
+        zip.start_file("OEBPS/content.opf", options).unwrap();
+        zip.write_all(CONTENT_OPF.as_bytes()).unwrap();
+
+        zip.add_directory("OEBPS/text", options).unwrap();
+        zip.start_file("OEBPS/text/chapter1.xhtml", options)
+            .unwrap();
+        zip.write_all(CHAPTER1_XHTML.as_bytes()).unwrap();
+
+        zip.start_file("OEBPS/text/chapter2.xhtml", options)
+            .unwrap();
+        zip.write_all(CHAPTER2_XHTML.as_bytes()).unwrap();
+
+        zip.finish().unwrap();
+    }
+
+    fs::write(path, buffer.into_inner()).expect("Failed to write EPUB fixture");
+}
+
+const CONTAINER_EPUB: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+    <rootfiles>
+        <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+    </rootfiles>
+</container>"#;
+
+// Manifest lists chapter 2 first; the spine decides reading order.
+const CONTENT_OPF: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="uid">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+        <dc:identifier id="uid">urn:uuid:momo-sample</dc:identifier>
+        <dc:title>Notes &amp; Queries</dc:title>
+        <dc:creator>Ada Lovelace</dc:creator>
+        <dc:language>en</dc:language>
+    </metadata>
+    <manifest>
+        <item id="ch2" href="text/chapter2.xhtml" media-type="application/xhtml+xml"/>
+        <item id="ch1" href="text/chapter1.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine>
+        <itemref idref="ch1"/>
+        <itemref idref="ch2"/>
+    </spine>
+</package>"#;
+
+const CHAPTER1_XHTML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<html xmlns="http://www.w3.org/1999/xhtml">
+<head><title>Chapter One</title></head>
+<body>
+    <h1>Chapter One</h1>
+    <p>The analytical engine weaves algebraic patterns.</p>
+    <h2>A Section</h2>
+    <p>Just as the Jacquard loom weaves flowers and leaves.</p>
+</body>
+</html>"#;
+
+const CHAPTER2_XHTML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<html xmlns="http://www.w3.org/1999/xhtml">
+<head><title>Chapter Two</title></head>
+<body>
+    <p>This chapter has no heading of its own.</p>
+    <ul><li>First note</li><li>Second note</li></ul>
+</body>
+</html>"#;
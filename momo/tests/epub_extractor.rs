@@ -0,0 +1,120 @@
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
+
+mod common;
+use common::ensure_fixtures;
+
+use momo::models::DocumentType;
+use momo::processing::extractors::epub::EpubExtractor;
+use momo::processing::ContentExtractor;
+
+fn create_test_epub(files: &[(&str, &str)]) -> Vec<u8> {
+    let mut buffer = Cursor::new(Vec::new());
+    {
+        let mut zip = zip::ZipWriter::new(&mut buffer);
+        let options = SimpleFileOptions::default();
+
+        zip.start_file("mimetype", options).unwrap();
+        zip.write_all(b"application/epub+zip").unwrap();
+
+        for (name, content) in files {
+            zip.start_file(*name, options).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+
+        zip.finish().unwrap();
+    }
+    buffer.into_inner()
+}
+
+#[test]
+fn test_epub_with_fixture() {
+    ensure_fixtures();
+    let bytes = common::load_fixture("sample.epub");
+
+    let result = EpubExtractor::extract(&bytes);
+    assert!(result.is_ok(), "Should extract from fixture EPUB");
+
+    let extracted = result.unwrap();
+    assert_eq!(extracted.doc_type, DocumentType::Epub);
+    assert_eq!(extracted.title.as_deref(), Some("Notes & Queries"));
+    assert_eq!(extracted.author.as_deref(), Some("Ada Lovelace"));
+    assert!(extracted.word_count > 0, "Should have word count");
+}
+
+#[test]
+fn test_epub_chapters_follow_spine_order() {
+    ensure_fixtures();
+    let bytes = common::load_fixture("sample.epub");
+    let text = EpubExtractor::extract(&bytes).unwrap().text;
+
+    let chapter_one = text.find("# Chapter One").expect("chapter one heading");
+    let chapter_two = text.find("# Chapter Two").expect("chapter two heading");
+    assert!(
+        chapter_one < chapter_two,
+        "Chapters should follow the spine, not the manifest"
+    );
+}
+
+#[test]
+fn test_epub_headings_and_lists_become_markdown() {
+    ensure_fixtures();
+    let bytes = common::load_fixture("sample.epub");
+    let text = EpubExtractor::extract(&bytes).unwrap().text;
+
+    assert!(text.contains("## A Section"), "H2 should become ## Heading");
+    assert!(
+        text.contains("The analytical engine weaves algebraic patterns."),
+        "Paragraph text should be kept"
+    );
+    assert!(
+        text.contains("# Chapter Two\n\nThis chapter has no heading of its own."),
+        "Chapters without a heading should use their <title>"
+    );
+    assert!(
+        text.contains("- First note"),
+        "List items should start with -"
+    );
+    assert!(!text.contains("<p>"), "Markup should be stripped");
+}
+
+#[test]
+fn test_epub_without_container_falls_back_to_opf() {
+    let bytes = create_test_epub(&[
+        (
+            "book.opf",
+            r#"<package xmlns:dc="http://purl.org/dc/elements/1.1/"><metadata><dc:title>Loose</dc:title></metadata><manifest><item id="a" href="a.xhtml"/></manifest><spine><itemref idref="a"/></spine></package>"#,
+        ),
+        (
+            "a.xhtml",
+            "<html><body><h1>Only</h1><p>Body text.</p></body></html>",
+        ),
+    ]);
+
+    let extracted = EpubExtractor::extract(&bytes).unwrap();
+    assert_eq!(extracted.title.as_deref(), Some("Loose"));
+    assert!(extracted.author.is_none());
+    assert_eq!(extracted.text, "# Only\n\nBody text.");
+}
+
+#[test]
+fn test_epub_detected_from_bytes() {
+    ensure_fixtures();
+    let bytes = common::load_fixture("sample.epub");
+
+    assert_eq!(
+        ContentExtractor::detect_type_from_bytes(&bytes),
+        DocumentType::Epub
+    );
+    assert_eq!(
+        ContentExtractor::detect_type_from_upload(&[0x00, 0x01], Some("book.epub"), None),
+        DocumentType::Epub
+    );
+}
+
+#[test]
+fn test_epub_corrupt() {
+    let result = EpubExtractor::extract(&[0x00, 0x01, 0x02, 0x03]);
+    assert!(result.is_err(), "Should fail on corrupt EPUB data");
+    assert!(result.unwrap_err().to_string().contains("EPUB"));
+}
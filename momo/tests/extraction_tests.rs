@@ -8,6 +8,7 @@ fn test_extracted_memory_creation() {
         confidence: 0.85,
         context: Some("UI settings discussion".to_string()),
         potential_contradiction: false,
+        contradicted_memory_id: None,
     };
 
     assert_eq!(memory.content, "User prefers dark mode");
@@ -24,6 +25,7 @@ fn test_extracted_memory_without_context() {
         confidence: 0.95,
         context: None,
         potential_contradiction: false,
+        contradicted_memory_id: None,
     };
 
     assert_eq!(memory.memory_type, "Fact");
@@ -50,6 +52,7 @@ fn test_extraction_result_with_memories() {
             confidence: 0.9,
             context: None,
             potential_contradiction: false,
+            contradicted_memory_id: None,
         },
         ExtractedMemory {
             content: "Preference 1".to_string(),
@@ -57,6 +60,7 @@ fn test_extraction_result_with_memories() {
             confidence: 0.8,
             context: Some("Context".to_string()),
             potential_contradiction: false,
+            contradicted_memory_id: None,
         },
     ];
 
@@ -79,6 +83,7 @@ fn test_memory_type_values() {
         confidence: 1.0,
         context: None,
         potential_contradiction: false,
+        contradicted_memory_id: None,
     };
     let preference = ExtractedMemory {
         content: "test".to_string(),
@@ -86,6 +91,7 @@ fn test_memory_type_values() {
         confidence: 1.0,
         context: None,
         potential_contradiction: false,
+        contradicted_memory_id: None,
     };
     let episode = ExtractedMemory {
         content: "test".to_string(),
@@ -93,6 +99,7 @@ fn test_memory_type_values() {
         confidence: 1.0,
         context: None,
         potential_contradiction: false,
+        contradicted_memory_id: None,
     };
 
     assert_eq!(fact.memory_type, "Fact");
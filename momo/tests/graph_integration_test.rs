@@ -1,5 +1,5 @@
 use momo::api::create_router;
-use momo::config::{Config, DatabaseConfig, EmbeddingsConfig, LlmConfig};
+use momo::config::{ApiKey, Config, DatabaseConfig, EmbeddingsConfig, LlmConfig};
 use momo::db::repository::{DocumentRepository, MemoryRepository, MemorySourcesRepository};
 use momo::db::{Database, DatabaseBackend, LibSqlBackend};
 use momo::embeddings::EmbeddingProvider;
@@ -50,14 +50,17 @@ async fn setup_test_app() -> (SocketAddr, TempDir, MockServer, Database) {
         url: db_url.clone(),
         auth_token: None,
         local_path: None,
+        max_connections: 4,
     };
 
-    config.server.api_keys = vec!["test-key".to_string()];
+    config.server.api_keys = vec![ApiKey::unrestricted("test-key")];
 
     config.embeddings = EmbeddingsConfig {
         model: "BAAI/bge-small-en-v1.5".to_string(),
         dimensions: 384,
         batch_size: 8,
+        query_cache_size: 0,
+        max_concurrent_batches: 4,
     };
 
     config.llm = Some(LlmConfig {
@@ -72,6 +75,8 @@ async fn setup_test_app() -> (SocketAddr, TempDir, MockServer, Database) {
         enable_auto_relations: false,
         enable_contradiction_detection: false,
         filter_prompt: None,
+        max_tokens_per_run: None,
+        max_calls_per_run: None,
     });
 
     let db = Database::new(&config.database)
@@ -1,5 +1,5 @@
 use momo::api::create_router;
-use momo::config::{Config, DatabaseConfig, EmbeddingsConfig, LlmConfig};
+use momo::config::{ApiKey, Config, DatabaseConfig, EmbeddingsConfig, LlmConfig};
 use momo::db::{Database, LibSqlBackend};
 use momo::embeddings::EmbeddingProvider;
 use momo::llm::LlmProvider;
@@ -54,12 +54,15 @@ async fn setup_test_app() -> (SocketAddr, TempDir, MockServer) {
         url: db_url,
         auth_token: None,
         local_path: None,
+        max_connections: 4,
     };
 
     config.embeddings = EmbeddingsConfig {
         model: "BAAI/bge-small-en-v1.5".to_string(),
         dimensions: 384,
         batch_size: 8,
+        query_cache_size: 0,
+        max_concurrent_batches: 4,
     };
 
     config.llm = Some(LlmConfig {
@@ -74,9 +77,11 @@ async fn setup_test_app() -> (SocketAddr, TempDir, MockServer) {
         enable_auto_relations: false,
         enable_contradiction_detection: false,
         filter_prompt: None,
+        max_calls_per_run: None,
+        max_tokens_per_run: None,
     });
 
-    config.server.api_keys = vec!["test-key".to_string()];
+    config.server.api_keys = vec![ApiKey::unrestricted("test-key")];
 
     let db = Database::new(&config.database)
         .await
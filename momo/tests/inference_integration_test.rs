@@ -58,6 +58,7 @@ async fn test_database() -> (Database, TempDir) {
         url: format!("file:{}", db_path.display()),
         auth_token: None,
         local_path: None,
+        max_connections: 4,
     };
 
     let db = Database::new(&config)
@@ -72,6 +73,8 @@ async fn test_embeddings_provider() -> EmbeddingProvider {
         model: "BAAI/bge-small-en-v1.5".to_string(),
         dimensions: 384,
         batch_size: 8,
+        query_cache_size: 0,
+        max_concurrent_batches: 4,
     };
 
     EmbeddingProvider::new(&config).expect("failed to create test embeddings provider")
@@ -90,6 +93,8 @@ fn test_llm_provider(base_url: String) -> LlmProvider {
         enable_auto_relations: false,
         enable_contradiction_detection: false,
         filter_prompt: None,
+        max_calls_per_run: None,
+        max_tokens_per_run: None,
     };
 
     LlmProvider::new(Some(&config))
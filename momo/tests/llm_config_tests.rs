@@ -88,6 +88,8 @@ fn test_llm_config_some_when_model_env_set() {
     assert!(llm.base_url.is_none());
     assert_eq!(llm.timeout_secs, 30);
     assert_eq!(llm.max_retries, 3);
+    assert_eq!(llm.max_calls_per_run, None);
+    assert_eq!(llm.max_tokens_per_run, None);
 
     env::remove_var("LLM_MODEL");
 }
@@ -100,6 +102,8 @@ fn test_llm_config_with_all_env_vars() {
     env::set_var("LLM_BASE_URL", "https://api.custom.com/v1");
     env::set_var("LLM_TIMEOUT", "60");
     env::set_var("LLM_MAX_RETRIES", "5");
+    env::set_var("LLM_MAX_CALLS_PER_RUN", "100");
+    env::set_var("LLM_MAX_TOKENS_PER_RUN", "50000");
 
     let config = Config::default();
 
@@ -109,12 +113,16 @@ fn test_llm_config_with_all_env_vars() {
     assert_eq!(llm.base_url, Some("https://api.custom.com/v1".to_string()));
     assert_eq!(llm.timeout_secs, 60);
     assert_eq!(llm.max_retries, 5);
+    assert_eq!(llm.max_calls_per_run, Some(100));
+    assert_eq!(llm.max_tokens_per_run, Some(50_000));
 
     env::remove_var("LLM_MODEL");
     env::remove_var("LLM_API_KEY");
     env::remove_var("LLM_BASE_URL");
     env::remove_var("LLM_TIMEOUT");
     env::remove_var("LLM_MAX_RETRIES");
+    env::remove_var("LLM_MAX_CALLS_PER_RUN");
+    env::remove_var("LLM_MAX_TOKENS_PER_RUN");
 }
 
 #[test]
@@ -161,6 +169,8 @@ fn test_llm_config_clone() {
         enable_auto_relations: true,
         enable_contradiction_detection: false,
         filter_prompt: None,
+        max_calls_per_run: None,
+        max_tokens_per_run: None,
     };
 
     let cloned = config.clone();
@@ -3,7 +3,7 @@ use momo::llm::prompts::{memory_extraction_prompt, query_rewrite_prompt, summari
 #[test]
 fn test_memory_extraction_prompt_substitution() {
     let content = "User prefers dark mode and uses vim for coding";
-    let prompt = memory_extraction_prompt(content);
+    let prompt = memory_extraction_prompt(content, &[]);
 
     assert!(prompt.contains(content));
     assert!(prompt.contains("Extract"));
@@ -13,7 +13,7 @@ fn test_memory_extraction_prompt_substitution() {
 
 #[test]
 fn test_memory_extraction_prompt_has_example_format() {
-    let prompt = memory_extraction_prompt("test");
+    let prompt = memory_extraction_prompt("test", &[]);
 
     assert!(prompt.contains("Example format"));
     assert!(prompt.contains(r#""content""#));
@@ -57,7 +57,7 @@ fn test_summarize_prompt_no_preamble() {
 
 #[test]
 fn test_all_prompts_are_non_empty() {
-    assert!(!memory_extraction_prompt("test").is_empty());
+    assert!(!memory_extraction_prompt("test", &[]).is_empty());
     assert!(!query_rewrite_prompt("test").is_empty());
     assert!(!summarize_prompt("test", 50).is_empty());
 }
@@ -65,7 +65,7 @@ fn test_all_prompts_are_non_empty() {
 #[test]
 fn test_prompts_handle_special_characters() {
     let content_with_quotes = r#"User said "hello world""#;
-    let prompt = memory_extraction_prompt(content_with_quotes);
+    let prompt = memory_extraction_prompt(content_with_quotes, &[]);
     assert!(prompt.contains(content_with_quotes));
 
     let query_with_symbols = "rust: async/await & futures?";
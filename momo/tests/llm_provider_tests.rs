@@ -24,6 +24,8 @@ fn llm_config(model: &str) -> LlmConfig {
         enable_auto_relations: true,
         enable_contradiction_detection: false,
         filter_prompt: None,
+        max_tokens_per_run: None,
+        max_calls_per_run: None,
     }
 }
 
@@ -40,6 +42,8 @@ fn llm_config_with_base_url(model: &str, base_url: String, max_retries: u32) ->
         enable_auto_relations: true,
         enable_contradiction_detection: false,
         filter_prompt: None,
+        max_tokens_per_run: None,
+        max_calls_per_run: None,
     }
 }
 
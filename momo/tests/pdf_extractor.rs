@@ -0,0 +1,67 @@
+mod common;
+use common::{build_pdf, ensure_fixtures};
+
+use momo::models::DocumentType;
+use momo::processing::extractors::pdf::PdfExtractor;
+use momo::processing::ContentExtractor;
+
+#[test]
+fn test_pdf_with_fixture() {
+    ensure_fixtures();
+    let bytes = common::load_fixture("sample.pdf");
+
+    let result = PdfExtractor::extract(&bytes);
+    assert!(result.is_ok(), "Should extract from fixture PDF");
+
+    let extracted = result.unwrap();
+    assert_eq!(extracted.doc_type, DocumentType::Pdf);
+    assert_eq!(extracted.title.as_deref(), Some("Field Guide"));
+    assert_eq!(extracted.word_count, 10);
+}
+
+#[test]
+fn test_pdf_keeps_page_boundaries() {
+    ensure_fixtures();
+    let bytes = common::load_fixture("sample.pdf");
+    let text = PdfExtractor::extract(&bytes).unwrap().text;
+
+    let page_one = text.find("## Page 1").expect("page one heading");
+    let herons = text.find("Herons wade").expect("page one text");
+    let page_two = text.find("## Page 2").expect("page two heading");
+    let kingfishers = text.find("Kingfishers dive").expect("page two text");
+    assert!(page_one < herons && herons < page_two && page_two < kingfishers);
+}
+
+#[test]
+fn test_pdf_without_info_title_uses_first_line() {
+    let bytes = build_pdf(None, &["Annual Report", "Revenue grew this year."]);
+
+    let extracted = PdfExtractor::extract(&bytes).unwrap();
+    assert_eq!(extracted.title.as_deref(), Some("Annual Report"));
+}
+
+#[test]
+fn test_pdf_detected_from_bytes() {
+    ensure_fixtures();
+    let bytes = common::load_fixture("sample.pdf");
+
+    assert_eq!(
+        ContentExtractor::detect_type_from_bytes(&bytes),
+        DocumentType::Pdf
+    );
+    assert_eq!(
+        ContentExtractor::detect_type_from_upload(&[0x00, 0x01], Some("scan.pdf"), None),
+        DocumentType::Pdf
+    );
+    assert_eq!(
+        ContentExtractor::detect_type_from_upload(&[0x00, 0x01], None, Some("application/pdf")),
+        DocumentType::Pdf
+    );
+}
+
+#[test]
+fn test_pdf_corrupt() {
+    let result = PdfExtractor::extract(&[0x00, 0x01, 0x02, 0x03]);
+    assert!(result.is_err(), "Should fail on corrupt PDF data");
+    assert!(result.unwrap_err().to_string().contains("PDF"));
+}
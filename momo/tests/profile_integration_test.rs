@@ -82,6 +82,7 @@ async fn test_database() -> (Database, TempDir) {
         url: format!("file:{}", db_path.display()),
         auth_token: None,
         local_path: None,
+        max_connections: 4,
     };
 
     let db = Database::new(&config)
@@ -104,6 +105,8 @@ fn test_llm_provider(base_url: String) -> LlmProvider {
         enable_auto_relations: false,
         enable_contradiction_detection: false,
         filter_prompt: None,
+        max_calls_per_run: None,
+        max_tokens_per_run: None,
     };
 
     LlmProvider::new(Some(&config))
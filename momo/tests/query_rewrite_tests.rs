@@ -1,5 +1,5 @@
 use momo::api::{create_router, AppState};
-use momo::config::{Config, LlmConfig};
+use momo::config::{ApiKey, Config, LlmConfig};
 use momo::db::{Database, DatabaseBackend, LibSqlBackend};
 use momo::embeddings::EmbeddingProvider;
 use momo::llm::LlmProvider;
@@ -19,7 +19,7 @@ async fn setup_test_app(llm_enabled: bool) -> (SocketAddr, TempDir) {
     config.database.url = db_url;
     config.embeddings.model = "local/BAAI/bge-small-en-v1.5".to_string();
     config.embeddings.dimensions = 384;
-    config.server.api_keys = vec!["test-key".to_string()];
+    config.server.api_keys = vec![ApiKey::unrestricted("test-key")];
 
     // Configure LLM for query rewriting
     if llm_enabled {
@@ -35,6 +35,8 @@ async fn setup_test_app(llm_enabled: bool) -> (SocketAddr, TempDir) {
             enable_auto_relations: false,
             enable_contradiction_detection: false,
             filter_prompt: None,
+            max_calls_per_run: None,
+            max_tokens_per_run: None,
         });
     } else {
         config.llm = None;
@@ -1,5 +1,5 @@
 use momo::api::{create_router, AppState};
-use momo::config::{Config, RerankerConfig};
+use momo::config::{ApiKey, Config, RerankerConfig};
 use momo::db::{Database, DatabaseBackend, LibSqlBackend};
 use momo::embeddings::{EmbeddingProvider, RerankResult, RerankerProvider};
 use momo::llm::LlmProvider;
@@ -19,7 +19,7 @@ async fn setup_test_app(reranker_override: Option<RerankerProvider>) -> (SocketA
     config.database.url = db_url;
     config.embeddings.model = "local/BAAI/bge-small-en-v1.5".to_string();
     config.embeddings.dimensions = 384;
-    config.server.api_keys = vec!["test-key".to_string()];
+    config.server.api_keys = vec![ApiKey::unrestricted("test-key")];
 
     config.reranker = Some(RerankerConfig {
         enabled: reranker_override.is_some(),
@@ -0,0 +1,139 @@
+use momo::api::create_router;
+use momo::config::{ApiKey, Config, DatabaseConfig, EmbeddingsConfig};
+use momo::db::repository::MemoryRepository;
+use momo::db::{Database, DatabaseBackend, LibSqlBackend};
+use momo::embeddings::EmbeddingProvider;
+use momo::llm::LlmProvider;
+use momo::models::Memory;
+use momo::ocr::OcrProvider;
+use momo::transcription::TranscriptionProvider;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+async fn setup_test_app() -> (SocketAddr, TempDir, Database) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("momo_similar_test.db");
+
+    let mut config = Config::default();
+    config.database = DatabaseConfig {
+        url: format!("file:{}", db_path.to_str().unwrap()),
+        auth_token: None,
+        local_path: None,
+        max_connections: 4,
+    };
+    config.server.api_keys = vec![ApiKey::unrestricted("test-key")];
+    config.embeddings = EmbeddingsConfig {
+        model: "BAAI/bge-small-en-v1.5".to_string(),
+        dimensions: 384,
+        batch_size: 8,
+        query_cache_size: 0,
+        max_concurrent_batches: 4,
+    };
+
+    let db = Database::new(&config.database)
+        .await
+        .expect("Failed to create database");
+    let db_backend: Arc<dyn DatabaseBackend> = Arc::new(LibSqlBackend::new(db.clone()));
+    let embeddings =
+        EmbeddingProvider::new(&config.embeddings).expect("Failed to create embeddings");
+    let ocr = OcrProvider::new(&config.ocr).expect("Failed to create OCR");
+    let transcription =
+        TranscriptionProvider::new(&config.transcription).expect("Failed to create transcription");
+    let llm = LlmProvider::new(None);
+
+    let state = momo::api::AppState::new(
+        config,
+        db_backend.clone(),
+        db_backend,
+        embeddings,
+        None,
+        ocr,
+        transcription,
+        llm,
+    );
+    let app = create_router(state);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("Failed to bind");
+    let addr = listener.local_addr().expect("Failed to get address");
+
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.expect("Server failed");
+    });
+
+    (addr, temp_dir, db)
+}
+
+/// Unit vector along `axis`, tilted towards the next axis by `tilt`.
+fn embedding(axis: usize, tilt: f32) -> Vec<f32> {
+    let mut embedding = vec![0.0f32; 384];
+    embedding[axis] = 1.0;
+    embedding[axis + 1] = tilt;
+    embedding
+}
+
+#[tokio::test]
+async fn test_similar_memories_uses_stored_embedding() {
+    let (addr, _tmp, db) = setup_test_app().await;
+    let client = reqwest::Client::new();
+    let base_url = format!("http://{addr}");
+    let conn = db.connect().expect("connect");
+
+    let vectors = [
+        ("source", embedding(0, 0.0)),
+        ("close", embedding(0, 0.2)),
+        ("far", embedding(10, 0.0)),
+    ];
+    for (id, vector) in &vectors {
+        let mut memory = Memory::new(id.to_string(), format!("Memory {id}"), "space1".to_string());
+        memory.container_tag = Some("similar_test".to_string());
+        MemoryRepository::create(&conn, &memory)
+            .await
+            .expect("create memory");
+        MemoryRepository::update_embedding(&conn, id, vector)
+            .await
+            .expect("store embedding");
+    }
+    let pending = Memory::new(
+        "pending".to_string(),
+        "Not embedded yet".to_string(),
+        "space1".to_string(),
+    );
+    MemoryRepository::create(&conn, &pending)
+        .await
+        .expect("create memory");
+
+    let res = client
+        .get(format!(
+            "{base_url}/api/v1/search/similar/source?threshold=0.5&containerTag=similar_test"
+        ))
+        .header("Authorization", "Bearer test-key")
+        .send()
+        .await
+        .expect("request");
+    assert_eq!(res.status(), 200);
+    let body: serde_json::Value = res.json().await.expect("json");
+    let results = body["data"]["results"].as_array().expect("results");
+    assert_eq!(results.len(), 1, "{body}");
+    assert_eq!(results[0]["memoryId"], "close");
+
+    let res = client
+        .get(format!("{base_url}/api/v1/search/similar/pending"))
+        .header("Authorization", "Bearer test-key")
+        .send()
+        .await
+        .expect("request");
+    assert_eq!(res.status(), 409);
+    let body: serde_json::Value = res.json().await.expect("json");
+    assert_eq!(body["error"]["code"], "embedding_pending");
+
+    let res = client
+        .get(format!("{base_url}/api/v1/search/similar/missing"))
+        .header("Authorization", "Bearer test-key")
+        .send()
+        .await
+        .expect("request");
+    assert_eq!(res.status(), 404);
+}